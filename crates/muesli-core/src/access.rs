@@ -0,0 +1,64 @@
+// ABOUTME: Access levels a transcript can carry in its frontmatter (private, team, public)
+// ABOUTME: And the policy deciding which levels AI assistants may read
+
+use serde::{Deserialize, Serialize};
+
+/// Who a meeting may be shared with, least restricted first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    Public,
+    Team,
+    Private,
+}
+
+/// Which documents the MCP server exposes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessPolicy {
+    /// Most restricted level clients may see
+    pub max_access: Access,
+    /// Level of documents without an `access` tag
+    pub default_access: Access,
+}
+
+impl Default for AccessPolicy {
+    fn default() -> Self {
+        Self {
+            max_access: Access::Team,
+            default_access: Access::Team,
+        }
+    }
+}
+
+impl AccessPolicy {
+    /// Whether a document tagged `access` (None for untagged) may be shown
+    pub fn allows(&self, access: Option<Access>) -> bool {
+        access.unwrap_or(self.default_access) <= self.max_access
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_hides_private() {
+        let policy = AccessPolicy::default();
+        assert!(policy.allows(None));
+        assert!(policy.allows(Some(Access::Public)));
+        assert!(policy.allows(Some(Access::Team)));
+        assert!(!policy.allows(Some(Access::Private)));
+    }
+
+    #[test]
+    fn test_policy_levels() {
+        let policy = AccessPolicy {
+            max_access: Access::Public,
+            default_access: Access::Private,
+        };
+        assert!(!policy.allows(None));
+        assert!(!policy.allows(Some(Access::Team)));
+        assert!(policy.allows(Some(Access::Public)));
+    }
+}
@@ -0,0 +1,262 @@
+// ABOUTME: Customer/account view aggregating every meeting with one organization
+// ABOUTME: Collects meetings, last contact and open action items from saved summaries
+
+use crate::storage::scan_transcripts;
+use crate::util::organizations_from_participants;
+use crate::{Frontmatter, Paths, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Prompt for summarizing an account across its saved meeting summaries
+pub const ACCOUNT_SUMMARY_PROMPT: &str = r#"You are preparing a briefing on a customer or partner account.
+
+Below are summaries of every recorded meeting with this account, oldest first.
+Produce:
+
+1. Relationship Overview (3–5 bullets)
+2. How Things Have Evolved (chronological, key turning points)
+3. Open Action Items and Commitments (owner, task, source meeting)
+4. Risks and Open Questions
+5. Suggested Talking Points for the Next Meeting
+
+Only use information from the summaries; label inferences as "(inferred)"."#;
+
+/// One meeting that involved the account
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountMeeting {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub path: PathBuf,
+    /// Saved summary for this meeting, if `summarize --save` was run
+    pub summary_path: Option<PathBuf>,
+}
+
+/// Everything muesli knows about an account
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountView {
+    pub name: String,
+    /// Organization domains that matched the name
+    pub organizations: Vec<String>,
+    /// Meetings in chronological order
+    pub meetings: Vec<AccountMeeting>,
+    pub last_contact: Option<DateTime<Utc>>,
+    /// Unchecked action items from saved summaries, oldest meeting first
+    pub action_items: Vec<String>,
+}
+
+/// Whether an organization domain belongs to the named account
+///
+/// A name with a dot must match the domain exactly; otherwise it matches the
+/// domain's first label ("acme" matches "acme.com" and "acme.co.uk").
+pub fn matches_account(domain: &str, name: &str) -> bool {
+    let domain = domain.to_lowercase();
+    let name = name.trim().to_lowercase();
+
+    if name.contains('.') {
+        domain == name
+    } else {
+        domain.split('.').next() == Some(name.as_str())
+    }
+}
+
+fn organizations_of(fm: &Frontmatter) -> Vec<String> {
+    if fm.organizations.is_empty() {
+        organizations_from_participants(&fm.participants)
+    } else {
+        fm.organizations.clone()
+    }
+}
+
+/// Extracts unchecked list items under an "Action Items" heading of a summary
+pub fn extract_action_items(summary: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut in_section = false;
+
+    for line in summary.lines() {
+        let trimmed = line.trim();
+        let lower = trimmed.to_lowercase();
+
+        // Headings may be markdown (#) or a bold line (**Action Items**)
+        let is_heading = trimmed.starts_with('#')
+            || (trimmed.len() > 4 && trimmed.starts_with("**") && trimmed.ends_with("**"));
+        if is_heading {
+            in_section = lower.contains("action items");
+            continue;
+        }
+
+        if !in_section {
+            continue;
+        }
+
+        let numbered = trimmed
+            .split_once(". ")
+            .filter(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .map(|(_, rest)| rest);
+        let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or(numbered)
+        else {
+            continue;
+        };
+
+        if item.starts_with("[x]") || item.starts_with("[X]") {
+            continue;
+        }
+        let item = item.strip_prefix("[ ]").unwrap_or(item).trim();
+        if !item.is_empty() && !item.eq_ignore_ascii_case("none") {
+            items.push(item.to_string());
+        }
+    }
+
+    items
+}
+
+/// Saved summary path for a transcript (same convention as `summarize --save`)
+fn summary_path_for(paths: &Paths, transcript: &Path) -> Option<PathBuf> {
+    let stem = transcript.file_stem()?.to_string_lossy();
+    let path = paths.summaries_dir.join(format!("{}_summary.md", stem));
+    path.exists().then_some(path)
+}
+
+/// Builds the account view from local transcripts and saved summaries
+pub fn build_account(paths: &Paths, name: &str) -> Result<AccountView> {
+    let mut organizations = Vec::new();
+    let mut meetings = Vec::new();
+
+    for (path, fm) in scan_transcripts(&paths.transcripts_dir)? {
+        let matched: Vec<String> = organizations_of(&fm)
+            .into_iter()
+            .filter(|org| matches_account(org, name))
+            .collect();
+        if matched.is_empty() {
+            continue;
+        }
+        organizations.extend(matched);
+
+        meetings.push(AccountMeeting {
+            doc_id: fm.doc_id,
+            title: fm.title,
+            created_at: fm.created_at,
+            summary_path: summary_path_for(paths, &path),
+            path,
+        });
+    }
+
+    organizations.sort();
+    organizations.dedup();
+    meetings.sort_by_key(|m| m.created_at);
+
+    let mut action_items = Vec::new();
+    for meeting in &meetings {
+        if let Some(summary_path) = &meeting.summary_path {
+            let summary = std::fs::read_to_string(summary_path)?;
+            action_items.extend(extract_action_items(&summary));
+        }
+    }
+
+    Ok(AccountView {
+        name: name.to_string(),
+        organizations,
+        last_contact: meetings.last().map(|m| m.created_at),
+        meetings,
+        action_items,
+    })
+}
+
+/// Concatenates saved summaries into one document for an account-level summary
+pub fn combined_summaries(view: &AccountView) -> Result<String> {
+    let mut combined = String::new();
+
+    for meeting in &view.meetings {
+        if let Some(summary_path) = &meeting.summary_path {
+            combined.push_str(&format!(
+                "## {} ({})\n\n{}\n\n",
+                meeting.title.as_deref().unwrap_or("Untitled"),
+                meeting.created_at.format("%Y-%m-%d"),
+                std::fs::read_to_string(summary_path)?
+            ));
+        }
+    }
+
+    Ok(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_matches_account() {
+        assert!(matches_account("acme.com", "Acme"));
+        assert!(matches_account("acme.co.uk", "acme"));
+        assert!(matches_account("acme.com", "ACME.com"));
+        assert!(!matches_account("acme.io", "acme.com"));
+        assert!(!matches_account("notacme.com", "acme"));
+    }
+
+    #[test]
+    fn test_extract_action_items() {
+        let summary = "\
+## Executive Summary
+- Pricing agreed
+
+## Action Items
+- [ ] Alice: send contract
+- [x] Bob: book room
+* Carol: follow up on SSO
+1. Dan: share roadmap
+
+## Risks
+- Budget slip";
+
+        assert_eq!(
+            extract_action_items(summary),
+            vec![
+                "Alice: send contract",
+                "Carol: follow up on SSO",
+                "Dan: share roadmap"
+            ]
+        );
+        assert!(extract_action_items("## Action Items\n- None").is_empty());
+    }
+
+    #[test]
+    fn test_build_account() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let write_md = |name: &str, doc_id: &str, date: &str, participants: &str| {
+            let content = format!(
+                "---\ndoc_id: {}\nsource: granola\ncreated_at: {}T10:00:00Z\ntitle: {}\nparticipants: [{}]\ngenerator: muesli 1.0\n---\n\nbody\n",
+                doc_id, date, name, participants
+            );
+            std::fs::write(paths.transcripts_dir.join(format!("{}.md", name)), content).unwrap();
+        };
+        write_md("later", "d2", "2025-03-01", "\"ann@acme.com\"");
+        write_md("earlier", "d1", "2025-01-01", "\"bob@acme.com\"");
+        write_md("other", "d3", "2025-02-01", "\"cy@vendor.io\"");
+        std::fs::write(
+            paths.summaries_dir.join("earlier_summary.md"),
+            "## Action Items\n- Send proposal\n",
+        )
+        .unwrap();
+
+        let view = build_account(&paths, "acme").unwrap();
+        assert_eq!(view.organizations, vec!["acme.com"]);
+        let ids: Vec<&str> = view.meetings.iter().map(|m| m.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["d1", "d2"]);
+        assert_eq!(
+            view.last_contact.unwrap().to_rfc3339(),
+            "2025-03-01T10:00:00+00:00"
+        );
+        assert_eq!(view.action_items, vec!["Send proposal"]);
+        assert!(combined_summaries(&view)
+            .unwrap()
+            .contains("## earlier (2025-01-01)"));
+    }
+}
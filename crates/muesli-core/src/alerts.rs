@@ -0,0 +1,386 @@
+// ABOUTME: Alert rules (alerts.toml) checked against meetings newly synced by watch mode
+// ABOUTME: Matches keywords or semantic queries and fires desktop notifications and webhooks
+
+use crate::find::{parse_entry, Entry};
+use crate::storage::{strip_frontmatter, CatalogEntry};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Example shown in the README and used by tests
+pub const EXAMPLE_ALERTS: &str = r#"[[rule]]
+name = "Layoffs"
+keywords = ["layoffs", "reduction in force"]
+notify = true
+
+[[rule]]
+name = "Security incident"
+semantic = "security incident or data breach"
+threshold = 0.82
+webhook = "https://hooks.example.com/muesli"
+"#;
+
+/// Minimum cosine similarity for a semantic rule when none is given
+const DEFAULT_THRESHOLD: f32 = 0.8;
+
+/// Longest snippet included in an alert
+const SNIPPET_CHARS: usize = 300;
+
+fn default_threshold() -> f32 {
+    DEFAULT_THRESHOLD
+}
+
+/// One `[[rule]]` table: what to look for and where to send matches
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    pub name: String,
+    /// Case-insensitive phrases; any one of them triggers the rule
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    /// Query a meeting must be semantically close to (requires 'embeddings')
+    #[serde(default)]
+    pub semantic: Option<String>,
+    /// Minimum cosine similarity for a semantic match
+    #[serde(default = "default_threshold")]
+    pub threshold: f32,
+    /// Show a desktop notification
+    #[serde(default)]
+    pub notify: bool,
+    /// URL that receives each alert as a JSON POST
+    #[serde(default)]
+    pub webhook: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AlertsFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<AlertRule>,
+}
+
+/// A rule that matched a meeting
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub rule: String,
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub date: String,
+    pub path: PathBuf,
+    /// Matching line for keyword rules, opening line for semantic ones
+    pub snippet: String,
+    /// Cosine similarity for semantic matches
+    pub score: Option<f32>,
+}
+
+/// Loads rules from a TOML file with `[[rule]]` tables; a missing file means no rules
+pub fn load_rules(path: &Path) -> Result<Vec<AlertRule>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let file: AlertsFile = toml::from_str(&content)
+        .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))?;
+
+    for rule in &file.rules {
+        if rule.keywords.iter().all(|k| k.trim().is_empty()) && rule.semantic.is_none() {
+            return Err(Error::InvalidInput(format!(
+                "Invalid {}: rule '{}' needs keywords or a semantic query",
+                path.display(),
+                rule.name
+            )));
+        }
+    }
+
+    Ok(file.rules)
+}
+
+/// "Speaker (ts): text", cut to the snippet length
+fn entry_snippet(entry: &Entry) -> String {
+    let text = match &entry.timestamp {
+        Some(ts) => format!("{} ({}): {}", entry.speaker, ts, entry.text),
+        None => format!("{}: {}", entry.speaker, entry.text),
+    };
+    text.chars().take(SNIPPET_CHARS).collect()
+}
+
+/// Readable form of a transcript line: "Speaker (ts): text" for entries
+fn snippet(line: &str) -> String {
+    match parse_entry(line) {
+        Some(entry) => entry_snippet(&entry),
+        None => line.trim().chars().take(SNIPPET_CHARS).collect(),
+    }
+}
+
+/// First line of a markdown body containing one of the rule's keywords
+pub fn keyword_snippet(rule: &AlertRule, body: &str) -> Option<String> {
+    let keywords: Vec<String> = rule
+        .keywords
+        .iter()
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect();
+    if keywords.is_empty() {
+        return None;
+    }
+
+    body.lines()
+        .find(|line| {
+            let lower = line.to_lowercase();
+            keywords.iter().any(|k| lower.contains(k.as_str()))
+        })
+        .map(snippet)
+}
+
+/// First spoken line of a transcript, used to give semantic alerts some context
+#[cfg(feature = "semantic")]
+fn opening_snippet(body: &str) -> String {
+    crate::find::parse_entries(body)
+        .first()
+        .map(entry_snippet)
+        .unwrap_or_default()
+}
+
+fn alert(rule: &AlertRule, entry: &CatalogEntry, snippet: String, score: Option<f32>) -> Alert {
+    Alert {
+        rule: rule.name.clone(),
+        doc_id: entry.doc_id.clone(),
+        title: entry.title.clone(),
+        date: entry.created_at.format("%Y-%m-%d").to_string(),
+        path: entry.path.clone(),
+        snippet,
+        score,
+    }
+}
+
+/// Checks every rule against the given meetings; each rule fires at most once per meeting
+///
+/// Returns the index of the matching rule alongside each alert.
+pub fn evaluate(
+    paths: &crate::Paths,
+    rules: &[AlertRule],
+    entries: &[CatalogEntry],
+) -> Result<Vec<(usize, Alert)>> {
+    let mut alerts = Vec::new();
+    let mut bodies = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let content = std::fs::read_to_string(&entry.path)?;
+        let body = strip_frontmatter(&content).to_string();
+
+        for (i, rule) in rules.iter().enumerate() {
+            if let Some(snippet) = keyword_snippet(rule, &body) {
+                alerts.push((i, alert(rule, entry, snippet, None)));
+            }
+        }
+        bodies.push(body);
+    }
+
+    let semantic: Vec<(usize, &AlertRule)> = rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| rule.semantic.is_some())
+        .collect();
+    if semantic.is_empty() {
+        return Ok(alerts);
+    }
+
+    #[cfg(feature = "semantic")]
+    {
+        use crate::embeddings::{downloader, engine::EmbeddingEngine, models, VectorStore};
+
+        let vector_path = paths.index_dir.join("vectors");
+        if !vector_path.with_extension("meta.json").exists() {
+            return Ok(alerts);
+        }
+
+        let model_paths = downloader::ensure_model(&paths.models_dir, models::configured(paths)?)?;
+        let mut engine = EmbeddingEngine::new(&model_paths)?;
+        let mut store = VectorStore::load(&vector_path)?;
+        store.pin_model(&model_paths.id)?;
+
+        for (i, rule) in semantic {
+            let query = engine.embed_query(rule.semantic.as_deref().unwrap_or_default())?;
+            for (entry, body) in entries.iter().zip(&bodies) {
+                let already = alerts
+                    .iter()
+                    .any(|(j, a)| *j == i && a.doc_id == entry.doc_id);
+                match store.similarity(&entry.doc_id, &query) {
+                    Some(score) if score >= rule.threshold && !already => {
+                        alerts.push((i, alert(rule, entry, opening_snippet(body), Some(score))));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "semantic"))]
+    {
+        let _ = (paths, &bodies);
+        for (_, rule) in semantic {
+            eprintln!(
+                "Warning: alert rule '{}' uses a semantic query, which needs the 'embeddings' feature",
+                rule.name
+            );
+        }
+    }
+
+    Ok(alerts)
+}
+
+/// One-line description of an alert for logs and notifications
+pub fn describe(alert: &Alert) -> String {
+    format!(
+        "[{}] {} ({})",
+        alert.rule,
+        alert.title.as_deref().unwrap_or("Untitled"),
+        alert.date
+    )
+}
+
+/// Shows a desktop notification via osascript (macOS) or notify-send (elsewhere)
+fn desktop_notification(title: &str, body: &str) -> Result<()> {
+    let status = if cfg!(target_os = "macos") {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        std::process::Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "display notification {} with title {}",
+                quote(body),
+                quote(title)
+            ))
+            .status()?
+    } else {
+        std::process::Command::new("notify-send")
+            .arg(title)
+            .arg(body)
+            .status()?
+    };
+
+    if !status.success() {
+        return Err(Error::InvalidInput(format!(
+            "Notification command exited with {}",
+            status
+        )));
+    }
+    Ok(())
+}
+
+/// Sends an alert to the rule's channels, returning a message for each failure
+pub async fn dispatch(rule: &AlertRule, alert: &Alert) -> Vec<String> {
+    let mut failures = Vec::new();
+
+    if rule.notify {
+        if let Err(e) = desktop_notification(&format!("muesli: {}", alert.rule), &describe(alert)) {
+            failures.push(format!("notification failed: {}", e));
+        }
+    }
+
+    if let Some(url) = &rule.webhook {
+        if let Err(e) = crate::offline::ensure_online("the webhook") {
+            failures.push(format!("webhook {} skipped: {}", url, e));
+            return failures;
+        }
+        let sent = async {
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()?
+                .post(url)
+                .json(alert)
+                .send()
+                .await?
+                .error_for_status()
+        };
+        if let Err(e) = sent.await {
+            failures.push(format!("webhook {} failed: {}", url, e));
+        }
+    }
+
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn rule(keywords: &[&str]) -> AlertRule {
+        AlertRule {
+            name: "Layoffs".to_string(),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            semantic: None,
+            threshold: DEFAULT_THRESHOLD,
+            notify: false,
+            webhook: None,
+        }
+    }
+
+    #[test]
+    fn test_load_rules() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("alerts.toml");
+        assert!(load_rules(&path).unwrap().is_empty());
+
+        std::fs::write(&path, EXAMPLE_ALERTS).unwrap();
+        let rules = load_rules(&path).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert!(rules[0].notify);
+        assert_eq!(rules[0].threshold, DEFAULT_THRESHOLD);
+        assert_eq!(rules[1].threshold, 0.82);
+        assert!(rules[1].webhook.is_some());
+
+        std::fs::write(&path, "[[rule]]\nname = \"Empty\"\n").unwrap();
+        assert!(load_rules(&path).is_err());
+    }
+
+    #[test]
+    fn test_keyword_snippet() {
+        let body = "# Q3 Planning\n\n\
+**Alice (00:01:00):** Budget is tight\n\
+**Bob (00:02:30):** There may be LAYOFFS next quarter\n";
+
+        assert_eq!(
+            keyword_snippet(&rule(&["layoffs"]), body).as_deref(),
+            Some("Bob (00:02:30): There may be LAYOFFS next quarter")
+        );
+        assert!(keyword_snippet(&rule(&["reorg"]), body).is_none());
+        assert!(keyword_snippet(&rule(&[" "]), body).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_keyword_rules() {
+        let temp = TempDir::new().unwrap();
+        let paths = crate::Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        let path = temp.path().join("meeting.md");
+        std::fs::write(
+            &path,
+            "---\ndoc_id: d1\n---\n\n# All Hands\n\n**Alice:** No layoffs planned\n",
+        )
+        .unwrap();
+
+        let entry = CatalogEntry {
+            doc_id: "d1".to_string(),
+            path,
+            title: Some("All Hands".to_string()),
+            created_at: Utc::now(),
+            participants: Vec::new(),
+            labels: Vec::new(),
+            organizations: Vec::new(),
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: Vec::new(),
+            access: None,
+            modified: None,
+        };
+
+        let rules = vec![rule(&["hiring"]), rule(&["layoffs", "planned"])];
+        let alerts = evaluate(&paths, &rules, &[entry]).unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].0, 1);
+        assert_eq!(alerts[0].1.snippet, "Alice: No layoffs planned");
+        assert!(describe(&alerts[0].1).contains("All Hands"));
+    }
+}
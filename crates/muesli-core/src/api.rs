@@ -0,0 +1,896 @@
+// ABOUTME: Blocking and async HTTP clients for Granola API
+// ABOUTME: Handles throttling, auth headers, fail-fast errors, conditional requests and record/replay fixtures
+
+mod http_cache;
+
+use crate::config;
+use crate::{
+    DocumentMetadata, DocumentPanel, DocumentSummary, DocumentUpdates, Error, Paths, RawTranscript,
+    Result,
+};
+use async_trait::async_trait;
+use rand::Rng;
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use http_cache::{CachedResponse, HttpCache};
+
+fn truncate_str(s: &str, max_chars: usize) -> String {
+    if s.len() <= max_chars {
+        return s.to_string();
+    }
+
+    // Find a valid UTF-8 boundary at or before max_chars
+    let mut boundary = max_chars;
+    while boundary > 0 && !s.is_char_boundary(boundary) {
+        boundary -= 1;
+    }
+
+    if boundary == 0 {
+        return String::new();
+    }
+
+    format!("{}...", &s[..boundary])
+}
+
+/// Record/replay mode for API responses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Perform real requests and capture each response to the directory
+    Record(PathBuf),
+    /// Serve responses from the directory without touching the network
+    Replay(PathBuf),
+}
+
+/// A captured API response as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct Fixture {
+    endpoint: String,
+    request: serde_json::Value,
+    status: u16,
+    body: serde_json::Value,
+}
+
+/// FNV-1a, which keeps fixture names deterministic across Rust versions
+fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Derives a stable fixture filename from the endpoint and request body
+///
+/// A document's slug stays readable; the hash of its raw ID keeps IDs that
+/// slugify alike (`Doc_1`, `doc-1`) from sharing a file.
+pub(crate) fn fixture_name(endpoint: &str, body: &serde_json::Value) -> String {
+    let mut name = endpoint.trim_matches('/').replace('/', "_");
+
+    if let Some(doc_id) = body.get("document_id").and_then(|v| v.as_str()) {
+        name.push_str(&format!(
+            "__{}-{:08x}",
+            crate::util::slugify(doc_id),
+            fnv1a(doc_id) as u32
+        ));
+    } else if body.as_object().map(|o| !o.is_empty()).unwrap_or(true) {
+        name.push_str(&format!("__{:016x}", fnv1a(&body.to_string())));
+    }
+
+    format!("{}.json", name)
+}
+
+/// Deletes the responses `http_cache/` holds for a document, so its body leaves the disk with it
+pub fn forget_cached(paths: &Paths, doc_id: &str) -> Result<()> {
+    HttpCache::new(paths).forget(doc_id)
+}
+
+/// Detects login pages served in place of JSON (e.g. after token expiry)
+fn is_html_response(content_type: Option<&str>, body: &str) -> bool {
+    if let Some(ct) = content_type {
+        let ct = ct.to_ascii_lowercase();
+        if ct.starts_with("text/html") || ct.starts_with("application/xhtml") {
+            return true;
+        }
+        if ct.contains("json") {
+            return false;
+        }
+    }
+
+    let head = body.trim_start();
+    head.starts_with('<')
+        && head
+            .get(..15)
+            .map(|s| s.to_ascii_lowercase())
+            .map(|s| s.starts_with("<!doctype html") || s.starts_with("<html"))
+            .unwrap_or(false)
+}
+
+/// Replaces every occurrence of the bearer token so fixtures are safe to share
+fn scrub(value: serde_json::Value, token: &str) -> serde_json::Value {
+    if token.is_empty() {
+        return value;
+    }
+
+    match value {
+        serde_json::Value::String(s) => serde_json::Value::String(s.replace(token, "[REDACTED]")),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(|v| scrub(v, token)).collect())
+        }
+        serde_json::Value::Object(map) => {
+            serde_json::Value::Object(map.into_iter().map(|(k, v)| (k, scrub(v, token))).collect())
+        }
+        other => other,
+    }
+}
+
+/// Client identification from `[api]` in config.toml
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// Appended to the User-Agent, e.g. for a corporate proxy's allow-list
+    pub user_agent_suffix: Option<String>,
+}
+
+impl ApiConfig {
+    /// A config file's `[api]` table; the suffix must be valid in a header
+    pub fn load(path: &Path) -> Result<Self> {
+        let api: Self = config::load_section(path, "api")?;
+        if let Some(suffix) = &api.user_agent_suffix {
+            if HeaderValue::from_str(suffix).is_err() {
+                return Err(config::invalid(
+                    path,
+                    "api",
+                    "user_agent_suffix must be printable ASCII",
+                ));
+            }
+        }
+        Ok(api)
+    }
+
+    /// How this data directory's requests identify themselves
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    /// `muesli/<version> (Rust)`, then the configured suffix
+    pub fn user_agent(&self) -> String {
+        let base = format!("muesli/{} (Rust)", env!("CARGO_PKG_VERSION"));
+        match self.user_agent_suffix.as_deref().map(str::trim) {
+            Some(suffix) if !suffix.is_empty() => format!("{} {}", base, suffix),
+            _ => base,
+        }
+    }
+}
+
+/// Connection settings shared by the blocking and async clients
+#[derive(Debug, Clone)]
+struct ClientSettings {
+    base_url: String,
+    token: String,
+    throttle_min: u64,
+    throttle_max: u64,
+    fixtures: Option<FixtureMode>,
+    config: ApiConfig,
+    http_cache: Option<HttpCache>,
+}
+
+impl ClientSettings {
+    fn new(token: String, base_url: Option<String>) -> Self {
+        ClientSettings {
+            base_url: base_url.unwrap_or_else(|| "https://api.granola.ai".into()),
+            token,
+            throttle_min: 100,
+            throttle_max: 300,
+            fixtures: None,
+            config: ApiConfig::default(),
+            http_cache: None,
+        }
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        format!("{}{}", self.base_url, endpoint)
+    }
+
+    /// Headers sent with every request; the one place to add a header both clients need
+    fn headers(&self) -> Result<HeaderMap> {
+        let value = |text: String, what: &str| {
+            HeaderValue::from_str(&text).map_err(|_| {
+                Error::InvalidInput(format!(
+                    "{} contains characters not allowed in HTTP headers",
+                    what
+                ))
+            })
+        };
+
+        let mut headers = HeaderMap::new();
+        let mut auth = value(format!("Bearer {}", self.token), "The API token")?;
+        auth.set_sensitive(true);
+        headers.insert(AUTHORIZATION, auth);
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert(
+            USER_AGENT,
+            value(self.config.user_agent(), "The User-Agent")?,
+        );
+        Ok(headers)
+    }
+
+    fn throttle_delay(&self) -> Option<Duration> {
+        if self.throttle_max > 0 {
+            let sleep_ms = rand::thread_rng().gen_range(self.throttle_min..=self.throttle_max);
+            Some(Duration::from_millis(sleep_ms))
+        } else {
+            None
+        }
+    }
+
+    /// The stored response for a request, and the headers asking the server if it changed
+    fn conditions(
+        &self,
+        endpoint: &str,
+        request: &serde_json::Value,
+    ) -> (Option<CachedResponse>, HeaderMap) {
+        let cached = self
+            .http_cache
+            .as_ref()
+            .and_then(|cache| cache.lookup(endpoint, request));
+        let headers = cached
+            .as_ref()
+            .map(CachedResponse::conditions)
+            .unwrap_or_default();
+        (cached, headers)
+    }
+
+    /// Like `finish`, answering "unchanged" from the cache and caching what came back
+    ///
+    /// Every Granola endpoint is a POST, and for anything but GET and HEAD a
+    /// matching `If-None-Match` fails with 412 rather than 304. Both mean the
+    /// cached body is current.
+    fn finish_conditional<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        request: &serde_json::Value,
+        cached: Option<CachedResponse>,
+        status: u16,
+        headers: &HeaderMap,
+        text: &str,
+    ) -> Result<T> {
+        if let (304 | 412, Some(cached)) = (status, &cached) {
+            return self.finish(endpoint, request, 200, None, &cached.body);
+        }
+
+        let parsed = self.finish(
+            endpoint,
+            request,
+            status,
+            content_type(headers).as_deref(),
+            text,
+        )?;
+        if let Some(cache) = &self.http_cache {
+            // A cache that can't be written only costs bandwidth
+            if let Err(e) = cache.store(endpoint, request, headers, text) {
+                eprintln!("Warning: Failed to cache response from {}: {}", endpoint, e);
+            }
+        }
+        Ok(parsed)
+    }
+
+    fn replay_dir(&self) -> Option<&Path> {
+        match &self.fixtures {
+            Some(FixtureMode::Replay(dir)) => Some(dir),
+            _ => None,
+        }
+    }
+
+    /// Validates a completed HTTP exchange, records it if requested, and parses the body
+    fn finish<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        request: &serde_json::Value,
+        status: u16,
+        content_type: Option<&str>,
+        text: &str,
+    ) -> Result<T> {
+        if !(200..300).contains(&status) {
+            // Failures are recorded too, so a replay fails the same way
+            if let Some(FixtureMode::Record(dir)) = &self.fixtures {
+                self.record(dir, endpoint, request, status, text)?;
+            }
+            return Err(Error::Api {
+                endpoint: endpoint.into(),
+                status,
+                message: truncate_str(text, 100),
+            });
+        }
+
+        if is_html_response(content_type, text) {
+            return Err(Error::Auth(format!(
+                "{} returned an HTML page instead of JSON; your token has likely expired. \
+                 Re-login to Granola (or pass a fresh --token) and try again",
+                endpoint
+            )));
+        }
+
+        if let Some(FixtureMode::Record(dir)) = &self.fixtures {
+            self.record(dir, endpoint, request, status, text)?;
+        }
+
+        serde_json::from_str(text).map_err(|e| {
+            eprintln!("Failed to parse response from {}: {}", endpoint, e);
+            eprintln!(
+                "Response body (first 500 chars): {}",
+                truncate_str(text, 500)
+            );
+            Error::Parse(e)
+        })
+    }
+
+    fn record(
+        &self,
+        dir: &Path,
+        endpoint: &str,
+        request: &serde_json::Value,
+        status: u16,
+        text: &str,
+    ) -> Result<()> {
+        fs::create_dir_all(dir)?;
+
+        let fixture = Fixture {
+            endpoint: endpoint.into(),
+            request: scrub(request.clone(), &self.token),
+            status,
+            // Error pages aren't always JSON; keep those as a string
+            body: scrub(
+                serde_json::from_str(text)
+                    .unwrap_or_else(|_| serde_json::Value::String(text.into())),
+                &self.token,
+            ),
+        };
+
+        let json = serde_json::to_string_pretty(&fixture)?;
+        fs::write(dir.join(fixture_name(endpoint, request)), json)?;
+        Ok(())
+    }
+}
+
+fn replay<T: serde::de::DeserializeOwned>(
+    dir: &Path,
+    endpoint: &str,
+    request: &serde_json::Value,
+) -> Result<T> {
+    let path = dir.join(fixture_name(endpoint, request));
+    let content = fs::read_to_string(&path).map_err(|e| {
+        Error::Filesystem(std::io::Error::new(
+            e.kind(),
+            format!("No replay fixture at {}: {}", path.display(), e),
+        ))
+    })?;
+
+    let fixture: Fixture = serde_json::from_str(&content)?;
+    if !(200..300).contains(&fixture.status) {
+        let text = match fixture.body {
+            serde_json::Value::String(text) => text,
+            other => other.to_string(),
+        };
+        return Err(Error::Api {
+            endpoint: endpoint.into(),
+            status: fixture.status,
+            message: truncate_str(&text, 100),
+        });
+    }
+    Ok(serde_json::from_value(fixture.body)?)
+}
+
+fn content_type(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    headers
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+}
+
+#[derive(Deserialize)]
+struct DocumentsResponse {
+    docs: Vec<DocumentSummary>,
+}
+
+/// Blocking client, convenient for scripts and simple library consumers
+pub struct ApiClient {
+    client: Client,
+    settings: ClientSettings,
+}
+
+impl ApiClient {
+    pub fn new(token: String, base_url: Option<String>) -> Result<Self> {
+        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
+
+        Ok(ApiClient {
+            client,
+            settings: ClientSettings::new(token, base_url),
+        })
+    }
+
+    /// Capture every successful response as a JSON fixture in `dir`
+    pub fn with_record(mut self, dir: PathBuf) -> Self {
+        self.settings.fixtures = Some(FixtureMode::Record(dir));
+        self
+    }
+
+    /// Serve responses from fixtures in `dir` instead of the network
+    pub fn with_replay(mut self, dir: PathBuf) -> Self {
+        self.settings.fixtures = Some(FixtureMode::Replay(dir));
+        self
+    }
+
+    pub fn with_throttle(mut self, min_ms: u64, max_ms: u64) -> Self {
+        self.settings.throttle_min = min_ms;
+        self.settings.throttle_max = max_ms;
+        self
+    }
+
+    pub fn disable_throttle(mut self) -> Self {
+        self.settings.throttle_min = 0;
+        self.settings.throttle_max = 0;
+        self
+    }
+
+    /// Identify the client as `[api]` asks
+    pub fn with_config(mut self, config: ApiConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    /// Send conditional requests, answering unchanged (304) responses from `http_cache/`
+    pub fn with_http_cache(mut self, paths: &Paths) -> Self {
+        self.settings.http_cache = Some(HttpCache::new(paths));
+        self
+    }
+
+    fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<T> {
+        if let Some(dir) = self.settings.replay_dir() {
+            return replay(dir, endpoint, &body);
+        }
+        crate::offline::ensure_online("the Granola API")?;
+
+        let (cached, conditions) = self.settings.conditions(endpoint, &body);
+        let response = self
+            .client
+            .post(self.settings.url(endpoint))
+            .headers(self.settings.headers()?)
+            .headers(conditions)
+            .json(&body)
+            .send()?;
+
+        if let Some(delay) = self.settings.throttle_delay() {
+            std::thread::sleep(delay);
+        }
+
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let text = response.text()?;
+
+        self.settings
+            .finish_conditional(endpoint, &body, cached, status, &headers, &text)
+    }
+
+    pub fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        let resp: DocumentsResponse = self.post("/v2/get-documents", json!({}))?;
+        Ok(resp.docs)
+    }
+
+    /// Makes the cheapest authenticated call available to confirm the token works
+    pub fn check_token(&self) -> Result<()> {
+        let _: serde_json::Value = self.post("/v2/get-documents", json!({ "limit": 1 }))?;
+        Ok(())
+    }
+
+    pub fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        self.post(
+            "/v1/get-document-metadata",
+            json!({ "document_id": doc_id }),
+        )
+    }
+
+    pub fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        self.post(
+            "/v1/get-document-transcript",
+            json!({ "document_id": doc_id }),
+        )
+    }
+
+    /// Granola's own AI notes for the meeting
+    pub fn get_panels(&self, doc_id: &str) -> Result<Vec<DocumentPanel>> {
+        self.post("/v1/get-document-panels", json!({ "document_id": doc_id }))
+    }
+
+    /// Documents changed after `since_cursor`, or every document without one
+    pub fn get_updates(&self, since_cursor: Option<&str>) -> Result<DocumentUpdates> {
+        self.post(UPDATES_ENDPOINT, json!({ "cursor": since_cursor }))
+    }
+}
+
+const UPDATES_ENDPOINT: &str = "/v1/get-document-updates";
+
+/// `source` recorded for documents synced from Granola
+pub const GRANOLA_SOURCE: &str = "granola";
+
+/// Where sync gets documents from; the Granola API in production
+///
+/// Sync and watch only see this trait, so tests (and other tools built on the
+/// library) can sync from memory with [`crate::testing::FakeSource`].
+#[async_trait]
+pub trait DocumentSource: Send + Sync {
+    async fn list_documents(&self) -> Result<Vec<DocumentSummary>>;
+
+    async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata>;
+
+    async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript>;
+
+    /// Notes written by the source itself; none unless it has them
+    async fn get_panels(&self, _doc_id: &str) -> Result<Vec<DocumentPanel>> {
+        Ok(Vec::new())
+    }
+
+    /// A page of the changes feed after `since_cursor`, or `None` without a feed
+    ///
+    /// Sync then lists every document instead.
+    async fn get_updates(&self, _since_cursor: Option<&str>) -> Result<Option<DocumentUpdates>> {
+        Ok(None)
+    }
+
+    /// `source` recorded in the frontmatter and sync cache of what this lists
+    ///
+    /// `--prune` only removes documents recorded under this name, so meetings
+    /// imported from elsewhere survive a Granola sync.
+    fn name(&self) -> &str {
+        GRANOLA_SOURCE
+    }
+
+    /// The `source` of one document, for sources that serve several kinds
+    fn source_of(&self, _doc_id: &str) -> &str {
+        self.name()
+    }
+}
+
+/// Async client built on tokio; used by sync and the MCP server so the
+/// runtime is never blocked on network I/O
+#[derive(Clone)]
+pub struct AsyncApiClient {
+    client: reqwest::Client,
+    settings: ClientSettings,
+}
+
+impl AsyncApiClient {
+    pub fn new(token: String, base_url: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()?;
+
+        Ok(AsyncApiClient {
+            client,
+            settings: ClientSettings::new(token, base_url),
+        })
+    }
+
+    /// Capture every successful response as a JSON fixture in `dir`
+    pub fn with_record(mut self, dir: PathBuf) -> Self {
+        self.settings.fixtures = Some(FixtureMode::Record(dir));
+        self
+    }
+
+    /// Serve responses from fixtures in `dir` instead of the network
+    pub fn with_replay(mut self, dir: PathBuf) -> Self {
+        self.settings.fixtures = Some(FixtureMode::Replay(dir));
+        self
+    }
+
+    pub fn with_throttle(mut self, min_ms: u64, max_ms: u64) -> Self {
+        self.settings.throttle_min = min_ms;
+        self.settings.throttle_max = max_ms;
+        self
+    }
+
+    pub fn disable_throttle(mut self) -> Self {
+        self.settings.throttle_min = 0;
+        self.settings.throttle_max = 0;
+        self
+    }
+
+    /// Identify the client as `[api]` asks
+    pub fn with_config(mut self, config: ApiConfig) -> Self {
+        self.settings.config = config;
+        self
+    }
+
+    /// Send conditional requests, answering unchanged (304) responses from `http_cache/`
+    pub fn with_http_cache(mut self, paths: &Paths) -> Self {
+        self.settings.http_cache = Some(HttpCache::new(paths));
+        self
+    }
+
+    async fn post<T: serde::de::DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: serde_json::Value,
+    ) -> Result<T> {
+        if let Some(dir) = self.settings.replay_dir() {
+            return replay(dir, endpoint, &body);
+        }
+        crate::offline::ensure_online("the Granola API")?;
+
+        let (cached, conditions) = self.settings.conditions(endpoint, &body);
+        let response = self
+            .client
+            .post(self.settings.url(endpoint))
+            .headers(self.settings.headers()?)
+            .headers(conditions)
+            .json(&body)
+            .send()
+            .await?;
+
+        if let Some(delay) = self.settings.throttle_delay() {
+            tokio::time::sleep(delay).await;
+        }
+
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let text = response.text().await?;
+
+        self.settings
+            .finish_conditional(endpoint, &body, cached, status, &headers, &text)
+    }
+
+    pub async fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        let resp: DocumentsResponse = self.post("/v2/get-documents", json!({})).await?;
+        Ok(resp.docs)
+    }
+
+    /// Makes the cheapest authenticated call available to confirm the token works
+    pub async fn check_token(&self) -> Result<()> {
+        let _: serde_json::Value = self
+            .post("/v2/get-documents", json!({ "limit": 1 }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        self.post(
+            "/v1/get-document-metadata",
+            json!({ "document_id": doc_id }),
+        )
+        .await
+    }
+
+    pub async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        self.post(
+            "/v1/get-document-transcript",
+            json!({ "document_id": doc_id }),
+        )
+        .await
+    }
+
+    /// Granola's own AI notes for the meeting
+    pub async fn get_panels(&self, doc_id: &str) -> Result<Vec<DocumentPanel>> {
+        self.post("/v1/get-document-panels", json!({ "document_id": doc_id }))
+            .await
+    }
+
+    /// Documents changed after `since_cursor`, or every document without one
+    pub async fn get_updates(&self, since_cursor: Option<&str>) -> Result<DocumentUpdates> {
+        self.post(UPDATES_ENDPOINT, json!({ "cursor": since_cursor }))
+            .await
+    }
+}
+
+#[async_trait]
+impl DocumentSource for AsyncApiClient {
+    async fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        AsyncApiClient::list_documents(self).await
+    }
+
+    async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        AsyncApiClient::get_metadata(self, doc_id).await
+    }
+
+    async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        AsyncApiClient::get_transcript(self, doc_id).await
+    }
+
+    async fn get_panels(&self, doc_id: &str) -> Result<Vec<DocumentPanel>> {
+        AsyncApiClient::get_panels(self, doc_id).await
+    }
+
+    async fn get_updates(&self, since_cursor: Option<&str>) -> Result<Option<DocumentUpdates>> {
+        match AsyncApiClient::get_updates(self, since_cursor).await {
+            Ok(updates) => Ok(Some(updates)),
+            // Accounts or API versions without the feed
+            Err(Error::Api { status: 404, .. }) => Ok(None),
+            // Fixtures recorded before the feed existed
+            Err(Error::Filesystem(e))
+                if e.kind() == std::io::ErrorKind::NotFound
+                    && self.settings.replay_dir().is_some() =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_str_short() {
+        assert_eq!(truncate_str("hello", 100), "hello");
+    }
+
+    #[test]
+    fn test_truncate_str_exact() {
+        assert_eq!(truncate_str("hello", 5), "hello");
+    }
+
+    #[test]
+    fn test_truncate_str_long() {
+        let result = truncate_str("hello world", 7);
+        assert!(result.starts_with("hello"));
+        assert!(result.ends_with("..."));
+    }
+
+    #[test]
+    fn test_truncate_str_utf8() {
+        // Test with multi-byte UTF-8 characters - should not panic
+        let text = "Hello 世界 World";
+        let result = truncate_str(text, 10);
+        // Should not panic and should be valid UTF-8
+        assert!(!result.is_empty());
+        assert!(result.len() <= 13); // 10 chars + "..."
+    }
+
+    #[test]
+    fn test_truncate_str_emoji() {
+        // Test with emoji (4-byte UTF-8)
+        let text = "Hello 🎉🎉🎉 World";
+        let result = truncate_str(text, 10);
+        // Should not panic
+        assert!(!result.is_empty());
+    }
+
+    #[test]
+    fn test_api_client_new() {
+        let client = ApiClient::new("test_token".into(), None).unwrap();
+        assert_eq!(client.settings.base_url, "https://api.granola.ai");
+        assert_eq!(client.settings.token, "test_token");
+    }
+
+    #[test]
+    fn test_api_client_custom_base() {
+        let client = ApiClient::new("token".into(), Some("https://custom.api".into())).unwrap();
+        assert_eq!(client.settings.base_url, "https://custom.api");
+    }
+
+    #[test]
+    fn test_api_client_throttle_config() {
+        let client = ApiClient::new("token".into(), None)
+            .unwrap()
+            .with_throttle(50, 150);
+        assert_eq!(client.settings.throttle_min, 50);
+        assert_eq!(client.settings.throttle_max, 150);
+    }
+
+    #[test]
+    fn test_fixture_name_stable() {
+        assert_eq!(
+            fixture_name("/v2/get-documents", &json!({})),
+            "v2_get-documents.json"
+        );
+        assert_eq!(
+            fixture_name(
+                "/v1/get-document-metadata",
+                &json!({ "document_id": "Doc_1" })
+            ),
+            "v1_get-document-metadata__doc-1-fd461afd.json"
+        );
+        assert_ne!(
+            fixture_name("/v1/get-transcript", &json!({ "document_id": "Doc_1" })),
+            fixture_name("/v1/get-transcript", &json!({ "document_id": "doc-1" }))
+        );
+        assert_eq!(
+            fixture_name("/v1/other", &json!({ "a": 1 })),
+            fixture_name("/v1/other", &json!({ "a": 1 }))
+        );
+    }
+
+    #[test]
+    fn test_is_html_response() {
+        assert!(is_html_response(Some("text/html; charset=utf-8"), "{}"));
+        assert!(is_html_response(None, "  <!DOCTYPE html><html></html>"));
+        assert!(is_html_response(
+            Some("text/plain"),
+            "<html><body>Login</body></html>"
+        ));
+        assert!(!is_html_response(Some("application/json"), "<html>"));
+        assert!(!is_html_response(None, r#"{"docs": []}"#));
+    }
+
+    #[test]
+    fn test_scrub_removes_token() {
+        let value = json!({ "nested": ["Bearer secret123", 5], "k": "secret123" });
+        let scrubbed = scrub(value, "secret123");
+        assert!(!scrubbed.to_string().contains("secret123"));
+        assert_eq!(scrubbed["nested"][1], 5);
+    }
+
+    #[test]
+    fn test_api_client_disable_throttle() {
+        let client = ApiClient::new("token".into(), None)
+            .unwrap()
+            .disable_throttle();
+        assert_eq!(client.settings.throttle_min, 0);
+        assert_eq!(client.settings.throttle_max, 0);
+    }
+
+    #[test]
+    fn test_unchanged_answers_come_from_cache() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let mut settings = ClientSettings::new("token".into(), None);
+        settings.http_cache = Some(HttpCache::new(&paths));
+        let endpoint = "/v1/get-document-transcript";
+        let request = json!({ "document_id": "doc1" });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(reqwest::header::ETAG, HeaderValue::from_static("\"v1\""));
+        let body: Vec<u32> = settings
+            .finish_conditional(endpoint, &request, None, 200, &headers, "[1]")
+            .unwrap();
+        assert_eq!(body, vec![1]);
+
+        // A 304, or the 412 a POST gets for a matching If-None-Match
+        for status in [304, 412] {
+            let (cached, conditions) = settings.conditions(endpoint, &request);
+            assert!(conditions.contains_key(reqwest::header::IF_NONE_MATCH));
+            let body: Vec<u32> = settings
+                .finish_conditional(endpoint, &request, cached, status, &HeaderMap::new(), "")
+                .unwrap();
+            assert_eq!(body, vec![1]);
+        }
+
+        // Without a cached copy a 412 is an error like any other
+        let err = settings
+            .finish_conditional::<Vec<u32>>(endpoint, &request, None, 412, &HeaderMap::new(), "")
+            .unwrap_err();
+        assert!(matches!(err, Error::Api { status: 412, .. }));
+    }
+
+    #[test]
+    fn test_api_config_user_agent() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            ApiConfig::load(&path).unwrap().user_agent(),
+            format!("muesli/{} (Rust)", env!("CARGO_PKG_VERSION"))
+        );
+
+        std::fs::write(&path, "[api]\nuser_agent_suffix = \"acme-proxy/42\"\n").unwrap();
+        assert!(ApiConfig::load(&path)
+            .unwrap()
+            .user_agent()
+            .ends_with(" (Rust) acme-proxy/42"));
+
+        std::fs::write(&path, "[api]\nuser_agent_suffix = \"line\\nbreak\"\n").unwrap();
+        assert!(ApiConfig::load(&path).is_err());
+    }
+}
@@ -0,0 +1,200 @@
+// ABOUTME: Remembers ETag/Last-Modified and the body of each API response, per endpoint and document
+// ABOUTME: So repeat fetches send conditional headers and a 304 is answered from disk
+
+use super::fixture_name;
+use crate::storage::write_atomic;
+use crate::{Paths, Result};
+use reqwest::header::{
+    HeaderMap, HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// `http_cache/` in the data directory
+#[derive(Debug, Clone)]
+pub(super) struct HttpCache {
+    dir: PathBuf,
+    tmp_dir: PathBuf,
+}
+
+/// A response the server can confirm is unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CachedResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+    pub body: String,
+}
+
+impl CachedResponse {
+    /// `If-None-Match` / `If-Modified-Since` for the stored validators
+    pub fn conditions(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        let value = |v: &Option<String>| v.as_deref().and_then(|v| HeaderValue::from_str(v).ok());
+        if let Some(etag) = value(&self.etag) {
+            headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(date) = value(&self.last_modified) {
+            headers.insert(IF_MODIFIED_SINCE, date);
+        }
+        headers
+    }
+}
+
+impl HttpCache {
+    pub fn new(paths: &Paths) -> Self {
+        HttpCache {
+            dir: paths.data_dir.join("http_cache"),
+            tmp_dir: paths.tmp_dir.clone(),
+        }
+    }
+
+    fn path(&self, endpoint: &str, request: &serde_json::Value) -> PathBuf {
+        self.dir.join(fixture_name(endpoint, request))
+    }
+
+    /// The stored response for this request; a damaged entry is treated as missing
+    pub fn lookup(&self, endpoint: &str, request: &serde_json::Value) -> Option<CachedResponse> {
+        let content = fs::read(self.path(endpoint, request)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Keeps a successful response if the server sent validators for it
+    pub fn store(
+        &self,
+        endpoint: &str,
+        request: &serde_json::Value,
+        headers: &HeaderMap,
+        body: &str,
+    ) -> Result<()> {
+        let header = |name| {
+            headers
+                .get(name)
+                .and_then(|v: &HeaderValue| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let path = self.path(endpoint, request);
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        if etag.is_none() && last_modified.is_none() {
+            // An older entry would otherwise be revalidated against stale validators
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let cached = CachedResponse {
+            etag,
+            last_modified,
+            body: body.to_string(),
+        };
+        write_atomic(&path, &serde_json::to_vec(&cached)?, &self.tmp_dir)
+    }
+
+    /// Deletes every response stored for a document, whatever the endpoint
+    pub fn forget(&self, doc_id: &str) -> Result<()> {
+        // Per-document entries are named `<endpoint>__<slug>-<hash>.json`; older
+        // ones lack the hash, and may go with another ID's that slugifies alike
+        let suffix = fixture_name("", &serde_json::json!({ "document_id": doc_id }));
+        let legacy = format!("__{}.json", crate::util::slugify(doc_id));
+        let entries = match fs::read_dir(&self.dir) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            entries => entries?,
+        };
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .is_some_and(|name| name.ends_with(&suffix) || name.ends_with(&legacy))
+            {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_keeps_responses_with_validators() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let cache = HttpCache::new(&paths);
+        let request = json!({ "document_id": "doc1" });
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        cache
+            .store("/v1/get-document-transcript", &request, &headers, "[]")
+            .unwrap();
+
+        let cached = cache
+            .lookup("/v1/get-document-transcript", &request)
+            .unwrap();
+        assert_eq!(cached.body, "[]");
+        assert_eq!(cached.conditions().get(IF_NONE_MATCH).unwrap(), "\"v1\"");
+        assert!(cached.conditions().get(IF_MODIFIED_SINCE).is_none());
+        assert!(cache
+            .lookup(
+                "/v1/get-document-transcript",
+                &json!({ "document_id": "doc2" })
+            )
+            .is_none());
+
+        // A response without validators drops the stale entry
+        cache
+            .store(
+                "/v1/get-document-transcript",
+                &request,
+                &HeaderMap::new(),
+                "[1]",
+            )
+            .unwrap();
+        assert!(cache
+            .lookup("/v1/get-document-transcript", &request)
+            .is_none());
+    }
+
+    #[test]
+    fn test_forget_drops_only_that_document() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let cache = HttpCache::new(&paths);
+        cache.forget("doc1").unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        for doc_id in ["doc1", "doc2"] {
+            for endpoint in ["/v1/get-document-transcript", "/v1/get-document-panels"] {
+                cache
+                    .store(endpoint, &json!({ "document_id": doc_id }), &headers, "[]")
+                    .unwrap();
+            }
+        }
+        cache
+            .store("/v2/get-documents", &json!({}), &headers, "{}")
+            .unwrap();
+
+        cache.forget("doc1").unwrap();
+        for endpoint in ["/v1/get-document-transcript", "/v1/get-document-panels"] {
+            assert!(cache
+                .lookup(endpoint, &json!({ "document_id": "doc1" }))
+                .is_none());
+            assert!(cache
+                .lookup(endpoint, &json!({ "document_id": "doc2" }))
+                .is_some());
+        }
+        assert!(cache.lookup("/v2/get-documents", &json!({})).is_some());
+    }
+}
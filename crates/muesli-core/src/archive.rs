@@ -0,0 +1,137 @@
+// ABOUTME: A quick inventory of the data directory: files, index size, vectors and last sync
+// ABOUTME: Answers "what's in this archive" for the MCP get_stats tool without opening every file
+
+use crate::sync::last_sync;
+use crate::{Paths, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What the data directory holds
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveStats {
+    pub data_dir: PathBuf,
+    pub transcripts: usize,
+    pub raw_transcripts: usize,
+    /// Markdown summaries saved by `summarize --save`
+    pub summaries: usize,
+    /// Documents in the text index; None without an index (or the index feature)
+    pub indexed_documents: Option<usize>,
+    /// Bytes used by the search index directory, vectors included
+    pub index_bytes: u64,
+    /// None without a vector store
+    pub vectors: Option<usize>,
+    pub last_sync: Option<DateTime<Utc>>,
+}
+
+/// Files in `dir` whose names end with `suffix`; 0 if the directory is missing
+fn count_files(dir: &Path, suffix: &str) -> Result<usize> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut count = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() && entry.file_name().to_string_lossy().ends_with(suffix) {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Total size of the files under `dir`
+fn dir_bytes(dir: &Path) -> Result<u64> {
+    if !dir.exists() {
+        return Ok(0);
+    }
+    let mut total = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            total += dir_bytes(&entry.path())?;
+        } else if file_type.is_file() {
+            total += entry.metadata()?.len();
+        }
+    }
+    Ok(total)
+}
+
+/// Vectors in the store, read from its mapping so the vectors themselves stay on disk
+fn vector_count(paths: &Paths) -> Option<usize> {
+    #[derive(Deserialize)]
+    struct Metadata {
+        mapping: Vec<serde::de::IgnoredAny>,
+    }
+
+    let json = std::fs::read_to_string(paths.index_dir.join("vectors.meta.json")).ok()?;
+    serde_json::from_str::<Metadata>(&json)
+        .ok()
+        .map(|m| m.mapping.len())
+}
+
+#[cfg(feature = "index")]
+fn indexed_documents(paths: &Paths) -> Option<usize> {
+    // Opening a missing index would create an empty one
+    if !paths.index_dir.exists() {
+        return None;
+    }
+    let index = crate::index::text::open_for_search(&paths.index_dir).ok()?;
+    crate::index::text::indexed_doc_ids(&index)
+        .ok()
+        .map(|ids| ids.len())
+}
+
+#[cfg(not(feature = "index"))]
+fn indexed_documents(_paths: &Paths) -> Option<usize> {
+    None
+}
+
+/// Takes stock of the data directory
+pub fn collect(paths: &Paths) -> Result<ArchiveStats> {
+    Ok(ArchiveStats {
+        data_dir: paths.data_dir.clone(),
+        transcripts: count_files(&paths.transcripts_dir, ".md")?,
+        raw_transcripts: count_files(&paths.raw_dir, ".json")?,
+        summaries: count_files(&paths.summaries_dir, "_summary.md")?,
+        indexed_documents: indexed_documents(paths),
+        index_bytes: dir_bytes(&paths.index_dir)?,
+        vectors: vector_count(paths),
+        last_sync: last_sync(paths),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        let empty = collect(&paths).unwrap();
+        assert_eq!(empty.transcripts, 0);
+        assert_eq!(empty.index_bytes, 0);
+        assert!(empty.vectors.is_none());
+        assert!(empty.last_sync.is_none());
+
+        paths.ensure_dirs().unwrap();
+        for name in ["2025-01-01_a.md", "2025-01-02_b.md", "notes.txt"] {
+            std::fs::write(paths.transcripts_dir.join(name), "x").unwrap();
+        }
+        std::fs::write(paths.summaries_dir.join("2025-01-01_a_summary.md"), "s").unwrap();
+        std::fs::write(paths.summaries_dir.join("2025-01-01_a_summary.json"), "{}").unwrap();
+        std::fs::write(
+            paths.index_dir.join("vectors.meta.json"),
+            r#"{"dim": 2, "mapping": [{"doc_id": "a"}, {"doc_id": "b"}]}"#,
+        )
+        .unwrap();
+
+        let stats = collect(&paths).unwrap();
+        assert_eq!(stats.transcripts, 2);
+        assert_eq!(stats.summaries, 1);
+        assert_eq!(stats.vectors, Some(2));
+        assert!(stats.index_bytes > 0);
+    }
+}
@@ -0,0 +1,318 @@
+// ABOUTME: Token discovery with precedence chain
+// ABOUTME: CLI flag → env var → stored token → Granola session file (default), plus JWT inspection
+
+use crate::profile::Profile;
+use crate::{Error, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where a resolved bearer token came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Flag,
+    /// The named environment variable
+    Env(String),
+    /// Stored with `muesli auth login` in the OS credential store
+    Keychain,
+    SessionFile(PathBuf),
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenSource::Flag => write!(f, "--token flag"),
+            TokenSource::Env(var) => write!(f, "{} environment variable", var),
+            TokenSource::Keychain => write!(f, "token stored in the system keychain"),
+            TokenSource::SessionFile(path) => write!(f, "session file {}", path.display()),
+        }
+    }
+}
+
+pub fn resolve_token(cli_token: Option<String>, profile: Option<&Profile>) -> Result<String> {
+    resolve_token_with_source(cli_token, profile).map(|(token, _)| token)
+}
+
+/// Resolves the bearer token and reports which source supplied it
+///
+/// A profile's `token_env`, stored token and `session_file` replace BEARER_TOKEN,
+/// the default stored token and the usual session file locations, so one
+/// account's token never leaks into another.
+pub fn resolve_token_with_source(
+    cli_token: Option<String>,
+    profile: Option<&Profile>,
+) -> Result<(String, TokenSource)> {
+    // 1. CLI flag (explicit override)
+    if let Some(token) = cli_token {
+        return Ok((token, TokenSource::Flag));
+    }
+
+    // 2. Environment variable (explicit override)
+    let var = profile
+        .and_then(|p| p.token_env.as_deref())
+        .unwrap_or("BEARER_TOKEN");
+    if let Ok(token) = env::var(var) {
+        return Ok((token, TokenSource::Env(var.to_string())));
+    }
+
+    // 3. Token stored with `muesli auth login`. A credential store that can't be
+    // reached (no Secret Service on a headless box) just means there isn't one.
+    #[cfg(feature = "keychain")]
+    if let Ok(Some(token)) = crate::keychain::get(&crate::keychain::granola_token_account(profile))
+    {
+        return Ok((token, TokenSource::Keychain));
+    }
+
+    // 4. Granola session file (default)
+    let session_file = profile.and_then(|p| p.session_file.clone());
+    if let Some((token, path)) = try_session_file(session_file)? {
+        return Ok((token, TokenSource::SessionFile(path)));
+    }
+
+    Err(Error::Auth(match profile {
+        Some(profile) => format!(
+            "No bearer token found for profile '{}'. Provide via --token or {}, or set \
+             session_file in its profiles.toml section",
+            profile.name, var
+        ),
+        None => "No bearer token found. Provide via --token or BEARER_TOKEN env var, or log in to Granola"
+            .into(),
+    }))
+}
+
+fn try_session_file(configured: Option<PathBuf>) -> Result<Option<(String, PathBuf)>> {
+    let candidates = match configured {
+        Some(path) => vec![path],
+        None => session_file_candidates(env::consts::OS, |key| env::var(key).ok()),
+    };
+
+    for path in candidates {
+        if let Some(token) = parse_session_file(&path)? {
+            return Ok(Some((token, path)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads the `exp` claim from a JWT without verifying its signature.
+/// Returns None for opaque (non-JWT) tokens.
+pub fn token_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+    let exp = claims.get("exp")?.as_i64()?;
+    DateTime::from_timestamp(exp, 0)
+}
+
+/// Returns the Granola session file locations to probe for the given OS, in priority order.
+/// Environment lookups are injected so every platform can be tested on any host.
+pub fn session_file_candidates<F>(os: &str, get_env: F) -> Vec<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut candidates = Vec::new();
+
+    match os {
+        "macos" => {
+            if let Some(home) = get_env("HOME") {
+                candidates.push(
+                    PathBuf::from(home)
+                        .join("Library")
+                        .join("Application Support")
+                        .join("Granola")
+                        .join("supabase.json"),
+                );
+            }
+        }
+        "windows" => {
+            for key in ["APPDATA", "LOCALAPPDATA"] {
+                if let Some(dir) = get_env(key) {
+                    candidates.push(PathBuf::from(dir).join("Granola").join("supabase.json"));
+                }
+            }
+        }
+        _ => {
+            if let Some(config) = get_env("XDG_CONFIG_HOME") {
+                candidates.push(PathBuf::from(config).join("Granola").join("supabase.json"));
+            }
+            if let Some(home) = get_env("HOME") {
+                candidates.push(
+                    PathBuf::from(home)
+                        .join(".config")
+                        .join("Granola")
+                        .join("supabase.json"),
+                );
+            }
+        }
+    }
+
+    candidates
+}
+
+fn parse_session_file(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    let json: serde_json::Value = serde_json::from_str(&content)?;
+
+    // Parse workos_tokens (which is a stringified JSON)
+    if let Some(workos_str) = json.get("workos_tokens").and_then(|v| v.as_str()) {
+        let workos: serde_json::Value = serde_json::from_str(workos_str)?;
+        if let Some(access_token) = workos.get("access_token").and_then(|v| v.as_str()) {
+            return Ok(Some(access_token.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_token_cli_precedence() {
+        let token = resolve_token(Some("cli_token".into()), None).unwrap();
+        assert_eq!(token, "cli_token");
+    }
+
+    #[test]
+    fn test_resolve_token_env() {
+        env::set_var("BEARER_TOKEN", "env_token");
+        let token = resolve_token(None, None).unwrap();
+        assert_eq!(token, "env_token");
+        env::remove_var("BEARER_TOKEN");
+    }
+
+    #[test]
+    fn test_resolve_token_from_profile() {
+        let temp = TempDir::new().unwrap();
+        let session_path = temp.path().join("work.json");
+        fs::write(
+            &session_path,
+            r#"{"workos_tokens": "{\"access_token\": \"work_token\"}"}"#,
+        )
+        .unwrap();
+        let mut profile = Profile {
+            name: "work".into(),
+            token_env: Some("MUESLI_TEST_WORK_TOKEN".into()),
+            session_file: Some(session_path.clone()),
+            ..Default::default()
+        };
+
+        let (token, source) = resolve_token_with_source(None, Some(&profile)).unwrap();
+        assert_eq!(token, "work_token");
+        assert_eq!(source, TokenSource::SessionFile(session_path));
+
+        env::set_var("MUESLI_TEST_WORK_TOKEN", "env_work");
+        let (token, source) = resolve_token_with_source(None, Some(&profile)).unwrap();
+        env::remove_var("MUESLI_TEST_WORK_TOKEN");
+        assert_eq!(token, "env_work");
+        assert_eq!(source, TokenSource::Env("MUESLI_TEST_WORK_TOKEN".into()));
+
+        profile.session_file = Some(temp.path().join("missing.json"));
+        let err = resolve_token(None, Some(&profile)).unwrap_err().to_string();
+        assert!(err.contains("profile 'work'"), "{}", err);
+    }
+
+    #[test]
+    fn test_resolve_token_with_source_flag() {
+        let (token, source) = resolve_token_with_source(Some("cli".into()), None).unwrap();
+        assert_eq!(token, "cli");
+        assert_eq!(source, TokenSource::Flag);
+    }
+
+    #[test]
+    fn test_token_expiry_jwt() {
+        let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(r#"{"sub":"user","exp":1767225600}"#);
+        let token = format!("eyJhbGciOiJIUzI1NiJ9.{}.signature", payload);
+        let exp = token_expiry(&token).unwrap();
+        assert_eq!(exp.to_rfc3339(), "2026-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_token_expiry_opaque() {
+        assert!(token_expiry("not-a-jwt").is_none());
+        assert!(token_expiry("a.!!!.c").is_none());
+    }
+
+    #[test]
+    fn test_parse_session_file_valid() {
+        let temp = TempDir::new().unwrap();
+        let session_path = temp.path().join("supabase.json");
+
+        let content = r#"{
+            "workos_tokens": "{\"access_token\": \"test_token_123\"}"
+        }"#;
+        fs::write(&session_path, content).unwrap();
+
+        let token = parse_session_file(&session_path).unwrap();
+        assert_eq!(token, Some("test_token_123".into()));
+    }
+
+    #[test]
+    fn test_session_candidates_macos() {
+        let candidates = session_file_candidates("macos", |k| {
+            (k == "HOME").then(|| "/Users/alice".to_string())
+        });
+        assert_eq!(
+            candidates,
+            vec![PathBuf::from(
+                "/Users/alice/Library/Application Support/Granola/supabase.json"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_session_candidates_windows() {
+        let candidates = session_file_candidates("windows", |k| match k {
+            "APPDATA" => Some("C:\\Users\\alice\\AppData\\Roaming".to_string()),
+            "HOME" => Some("/ignored".to_string()),
+            _ => None,
+        });
+        assert_eq!(candidates.len(), 1);
+        assert!(candidates[0].starts_with("C:\\Users\\alice\\AppData\\Roaming"));
+        assert!(candidates[0].ends_with("supabase.json"));
+    }
+
+    #[test]
+    fn test_session_candidates_linux() {
+        let candidates = session_file_candidates("linux", |k| match k {
+            "XDG_CONFIG_HOME" => Some("/xdg".to_string()),
+            "HOME" => Some("/home/alice".to_string()),
+            _ => None,
+        });
+        assert_eq!(
+            candidates,
+            vec![
+                PathBuf::from("/xdg/Granola/supabase.json"),
+                PathBuf::from("/home/alice/.config/Granola/supabase.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_session_candidates_no_env() {
+        assert!(session_file_candidates("linux", |_| None).is_empty());
+        assert!(session_file_candidates("windows", |_| None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_session_file_missing() {
+        let temp = TempDir::new().unwrap();
+        let session_path = temp.path().join("missing.json");
+
+        let token = parse_session_file(&session_path).unwrap();
+        assert!(token.is_none());
+    }
+}
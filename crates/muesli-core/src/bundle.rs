@@ -0,0 +1,301 @@
+// ABOUTME: Packs everything muesli holds about one meeting into a single zip with a manifest
+// ABOUTME: For attaching the complete record of a meeting to a ticket or a legal request
+
+use crate::account::extract_action_items;
+use crate::storage::{read_frontmatter, Catalog};
+use crate::{Error, Paths, Result};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// `manifest.json` at the top of a bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleManifest {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub participants: Vec<String>,
+    /// muesli version that wrote the bundle
+    pub generator: String,
+    pub bundled_at: DateTime<Utc>,
+    pub files: Vec<BundledFile>,
+    /// Parts of the record that are not in the bundle, and why
+    pub missing: Vec<MissingPart>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BundledFile {
+    /// Path inside the zip
+    pub name: String,
+    pub bytes: u64,
+    /// Lets a recipient check the file was not changed after bundling
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingPart {
+    pub name: String,
+    pub reason: String,
+}
+
+/// `exports/<transcript name>.zip` in the data directory
+pub fn default_path(paths: &Paths, doc_id: &str) -> Result<PathBuf> {
+    let entry = Catalog::open(paths)?.entry_for(doc_id)?;
+    let stem = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+    Ok(paths.data_dir.join("exports").join(format!("{}.zip", stem)))
+}
+
+/// Writes the bundle for `doc_id` to `out`, returning its manifest
+///
+/// The zip holds the markdown transcript, raw JSON, timings, saved summaries
+/// and the action items found in them. Audio is listed as missing: Granola's
+/// API does not hand out recordings.
+pub fn write_bundle(paths: &Paths, doc_id: &str, out: &Path) -> Result<BundleManifest> {
+    let entry = Catalog::open(paths)?.entry_for(doc_id)?;
+    let fm = read_frontmatter(&entry.path)?.ok_or_else(|| {
+        Error::InvalidInput(format!("{} has no frontmatter", entry.path.display()))
+    })?;
+    let stem = entry
+        .path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let summary_md = crate::sync::summary_path(paths, &entry.path);
+    let summary_json = summary_md.with_extension("json");
+    let candidates = [
+        ("transcript.md", entry.path.clone()),
+        (
+            "transcript.json",
+            paths.raw_dir.join(format!("{}.json", stem)),
+        ),
+        ("timings.json", crate::timings::path_for(paths, &stem)),
+        ("summary.md", summary_md),
+        ("summary.json", summary_json),
+    ];
+
+    let mut parts: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut missing = Vec::new();
+    for (name, path) in candidates {
+        match std::fs::read(&path) {
+            Ok(content) => parts.push((name.to_string(), content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => missing.push(MissingPart {
+                name: name.to_string(),
+                reason: missing_reason(name).to_string(),
+            }),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let items = action_items(&parts);
+    if items.is_empty() {
+        missing.push(MissingPart {
+            name: "action_items.md".to_string(),
+            reason: "no saved summary lists action items".to_string(),
+        });
+    } else {
+        let list: String = items
+            .iter()
+            .map(|item| format!("- [ ] {}\n", item))
+            .collect();
+        parts.push(("action_items.md".to_string(), list.into_bytes()));
+    }
+    missing.push(MissingPart {
+        name: "audio".to_string(),
+        reason: "Granola's API does not provide meeting recordings".to_string(),
+    });
+
+    let files = parts
+        .iter()
+        .map(|(name, content)| BundledFile {
+            name: format!("{}/{}", stem, name),
+            bytes: content.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(content)),
+        })
+        .collect();
+    let manifest = BundleManifest {
+        doc_id: fm.doc_id.clone(),
+        title: fm.title.clone(),
+        created_at: fm.created_at,
+        participants: fm.participants.clone(),
+        generator: format!("muesli {}", env!("CARGO_PKG_VERSION")),
+        bundled_at: Utc::now(),
+        files,
+        missing,
+    };
+
+    // Entries carry the meeting time, like the files they were read from
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(zip_time(&fm.created_at));
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+    for (name, content) in
+        std::iter::once(("manifest.json".to_string(), manifest_json)).chain(parts)
+    {
+        zip.start_file(format!("{}/{}", stem, name), options)
+            .map_err(zip_err)?;
+        zip.write_all(&content)?;
+    }
+    let bytes = zip.finish().map_err(zip_err)?.into_inner();
+
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(out, bytes)?;
+    Ok(manifest)
+}
+
+fn missing_reason(name: &str) -> &'static str {
+    match name {
+        "transcript.json" => "raw JSON not on disk; run 'muesli fetch' for this document",
+        "timings.json" => "no timestamps in the transcript",
+        "summary.md" => "no saved summary; run 'muesli summarize --save'",
+        "summary.json" => "no saved structured summary",
+        _ => "not on disk",
+    }
+}
+
+/// Action items from the structured summary, else from the markdown one
+fn action_items(parts: &[(String, Vec<u8>)]) -> Vec<String> {
+    let part = |name: &str| {
+        parts
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, content)| String::from_utf8_lossy(content).to_string())
+    };
+
+    let structured = part("summary.json")
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|value| {
+            value
+                .get("action_items")
+                .and_then(|v| v.as_array())
+                .cloned()
+        });
+    if let Some(items) = structured {
+        return items
+            .iter()
+            .filter_map(|item| {
+                let task = item.get("task")?.as_str()?;
+                let details: Vec<&str> = ["owner", "due"]
+                    .iter()
+                    .filter_map(|key| item.get(*key).and_then(|v| v.as_str()))
+                    .collect();
+                Some(if details.is_empty() {
+                    task.to_string()
+                } else {
+                    format!("{} ({})", task, details.join(", "))
+                })
+            })
+            .collect();
+    }
+
+    part("summary.md")
+        .map(|summary| extract_action_items(&summary))
+        .unwrap_or_default()
+}
+
+fn zip_time(at: &DateTime<Utc>) -> zip::DateTime {
+    zip::DateTime::from_date_and_time(
+        at.year().clamp(1980, 2107) as u16,
+        at.month() as u8,
+        at.day() as u8,
+        at.hour() as u8,
+        at.minute() as u8,
+        at.second() as u8,
+    )
+    .unwrap_or_default()
+}
+
+fn zip_err(e: zip::result::ZipError) -> Error {
+    Error::Filesystem(e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_holds_the_record_and_manifest() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let md = paths.transcripts_dir.join("2025-03-01_standup.md");
+        std::fs::write(
+            &md,
+            "---\ndoc_id: d1\nsource: granola\ncreated_at: 2025-03-01T10:00:00Z\ntitle: Standup\ngenerator: muesli 1.0\n---\n\n# Standup\n",
+        )
+        .unwrap();
+        std::fs::write(
+            paths.raw_dir.join("2025-03-01_standup.json"),
+            r#"{"entries": []}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            paths.summaries_dir.join("2025-03-01_standup_summary.md"),
+            "## Action Items\n- Ship the release\n",
+        )
+        .unwrap();
+
+        let out = temp.path().join("out/standup.zip");
+        let manifest = write_bundle(&paths, "d1", &out).unwrap();
+
+        let names: Vec<&str> = manifest.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "2025-03-01_standup/transcript.md",
+                "2025-03-01_standup/transcript.json",
+                "2025-03-01_standup/summary.md",
+                "2025-03-01_standup/action_items.md",
+            ]
+        );
+        let missing: Vec<&str> = manifest.missing.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(missing, vec!["timings.json", "summary.json", "audio"]);
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&out).unwrap()).unwrap();
+        assert_eq!(archive.len(), 5);
+        let mut items = String::new();
+        archive
+            .by_name("2025-03-01_standup/action_items.md")
+            .unwrap()
+            .read_to_string(&mut items)
+            .unwrap();
+        assert_eq!(items, "- [ ] Ship the release\n");
+
+        let mut json = String::new();
+        archive
+            .by_name("2025-03-01_standup/manifest.json")
+            .unwrap()
+            .read_to_string(&mut json)
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["doc_id"], "d1");
+        assert_eq!(value["files"][0]["sha256"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn test_structured_action_items_win() {
+        let parts = vec![
+            (
+                "summary.md".to_string(),
+                b"## Action Items\n- From markdown\n".to_vec(),
+            ),
+            (
+                "summary.json".to_string(),
+                br#"{"action_items": [{"task": "Send notes", "owner": "Alice", "due": null}]}"#
+                    .to_vec(),
+            ),
+        ];
+        assert_eq!(action_items(&parts), vec!["Send notes (Alice)"]);
+    }
+}
@@ -0,0 +1,16 @@
+// ABOUTME: System clipboard access for the --copy flags
+// ABOUTME: Puts summaries and transcript excerpts on the clipboard via arboard
+
+use crate::{Error, Result};
+
+/// Replaces the clipboard contents with `text`
+///
+/// On Linux the text stays available after muesli exits only if a clipboard
+/// manager is running.
+pub fn copy(text: &str) -> Result<()> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| Error::Clipboard(format!("Clipboard unavailable: {}", e)))?;
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| Error::Clipboard(format!("Failed to copy to clipboard: {}", e)))
+}
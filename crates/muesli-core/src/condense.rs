@@ -0,0 +1,383 @@
+// ABOUTME: Condensed transcripts for sharing: drops greetings, small talk and filler words
+// ABOUTME: Heuristic phrase lists, masked profanity, and optional per-paragraph LLM classification
+
+use crate::convert::{to_markdown_with_local, LocalFields, MarkdownOptions, MarkdownOutput};
+use crate::storage::{read_frontmatter, write_atomic, CatalogEntry};
+use crate::{DocumentMetadata, Error, Paths, RawTranscript, Result};
+use std::path::PathBuf;
+
+/// Words that carry nothing on their own and are removed wherever they appear
+const FILLER_WORDS: &[&str] = &[
+    "um", "umm", "uh", "uhh", "uhm", "er", "erm", "ah", "hmm", "mm", "mhm", "mm-hmm", "uh-huh",
+];
+
+/// Phrases that make up small talk when an entry says nothing else
+const SMALLTALK: &[&str] = &[
+    "hi",
+    "hello",
+    "hey",
+    "hey there",
+    "hi all",
+    "hi everyone",
+    "hey everyone",
+    "hello everyone",
+    "morning",
+    "good morning",
+    "good afternoon",
+    "good evening",
+    "how are you",
+    "how are you doing",
+    "how's it going",
+    "how is everyone",
+    "how was your weekend",
+    "i'm good",
+    "i'm well",
+    "good",
+    "good thanks",
+    "doing well",
+    "not bad",
+    "all good",
+    "can you hear me",
+    "can everyone hear me",
+    "can you see my screen",
+    "you're on mute",
+    "you're muted",
+    "sorry i'm late",
+    "give me a second",
+    "one sec",
+    "one second",
+    "thanks",
+    "thank you",
+    "thanks everyone",
+    "thanks all",
+    "thank you all",
+    "thanks so much",
+    "bye",
+    "bye bye",
+    "bye everyone",
+    "see you",
+    "see ya",
+    "talk soon",
+    "take care",
+    "have a good one",
+    "have a good day",
+    "have a good weekend",
+    "cheers",
+    "ok",
+    "okay",
+    "yeah",
+    "yep",
+    "yes",
+    "sure",
+    "right",
+    "cool",
+    "great",
+    "nice",
+    "awesome",
+    "sounds good",
+    "perfect",
+    "got it",
+];
+
+/// A greeting followed by one more word, usually a name: "hi Alice", "thanks Bob"
+const GREETINGS: &[&str] = &["hi", "hey", "hello", "morning", "thanks", "bye"];
+
+/// Masked as their first letter followed by asterisks
+const PROFANITY: &[&str] = &[
+    "shit", "shitty", "fuck", "fucking", "fucked", "bullshit", "damn", "crap", "asshole",
+    "bastard", "bitch",
+];
+
+/// A transcript with small talk removed
+#[derive(Debug, Clone)]
+pub struct Condensed {
+    pub raw: RawTranscript,
+    /// Entries in the full transcript
+    pub original: usize,
+}
+
+impl Condensed {
+    pub fn kept(&self) -> usize {
+        self.raw.entries.len()
+    }
+
+    /// Removes the entries flagged as small talk (one flag per remaining entry)
+    pub fn drop_flagged(&mut self, flags: &[bool]) {
+        let mut flags = flags.iter();
+        self.raw
+            .entries
+            .retain(|_| !flags.next().copied().unwrap_or(false));
+    }
+}
+
+/// The transcript without small-talk entries, with filler words removed
+/// and profanity masked in what remains
+pub fn condense(raw: &RawTranscript) -> Condensed {
+    let entries = raw
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let text = clean_text(&entry.text)?;
+            Some(crate::model::TranscriptEntry {
+                text,
+                words: Vec::new(),
+                ..entry.clone()
+            })
+        })
+        .collect();
+
+    Condensed {
+        raw: RawTranscript { entries },
+        original: raw.entries.len(),
+    }
+}
+
+/// Letters, digits and in-word punctuation of a token, lowercased
+fn core(token: &str) -> String {
+    token
+        .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-')
+        .to_lowercase()
+}
+
+/// An entry's text without filler and with profanity masked, or None if
+/// nothing but small talk is left
+pub fn clean_text(text: &str) -> Option<String> {
+    let mut words = Vec::new();
+    for token in text.split_whitespace() {
+        let word = core(token);
+        if FILLER_WORDS.contains(&word.as_str()) {
+            continue;
+        }
+        if PROFANITY.contains(&word.as_str()) {
+            let mut masked = String::new();
+            let mut letters = 0;
+            for c in token.chars() {
+                if c.is_alphanumeric() {
+                    // Keep the first letter as written
+                    masked.push(if letters == 0 { c } else { '*' });
+                    letters += 1;
+                } else {
+                    masked.push(c);
+                }
+            }
+            words.push(masked);
+            continue;
+        }
+        words.push(token.to_string());
+    }
+
+    let mut cleaned = words.join(" ");
+    cleaned = cleaned
+        .trim_start_matches(|c: char| c == ',' || c == '.' || c.is_whitespace())
+        .to_string();
+    if text.starts_with(|c: char| c.is_uppercase()) {
+        if let Some(first) = cleaned.chars().next() {
+            cleaned.replace_range(..first.len_utf8(), &first.to_uppercase().to_string());
+        }
+    }
+
+    if cleaned.is_empty() || is_smalltalk(&cleaned) {
+        None
+    } else {
+        Some(cleaned)
+    }
+}
+
+/// True when every sentence of the text is a known small-talk phrase
+fn is_smalltalk(text: &str) -> bool {
+    text.split(['.', ',', '!', '?', ';'])
+        .map(|phrase| {
+            phrase
+                .split_whitespace()
+                .map(core)
+                .filter(|w| !w.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .filter(|words| !words.is_empty())
+        .all(|words| {
+            let phrase = words.join(" ");
+            SMALLTALK.contains(&phrase.as_str())
+                || (words.len() == 2 && GREETINGS.contains(&words[0].as_str()))
+        })
+}
+
+/// Where the condensed variant of a transcript is written: condensed/<name>.md
+pub fn path_for(paths: &Paths, entry: &CatalogEntry) -> PathBuf {
+    let stem = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+    paths
+        .data_dir
+        .join("condensed")
+        .join(format!("{}.md", stem))
+}
+
+/// Writes the condensed transcript beside the full one, returning its path
+///
+/// It keeps the full transcript's frontmatter, so speaker names and the
+/// access tag carry over.
+pub fn write(paths: &Paths, entry: &CatalogEntry, condensed: &Condensed) -> Result<PathBuf> {
+    let md = to_markdown(paths, entry, condensed)?;
+
+    let path = path_for(paths, entry);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let content = format!("---\n{}---\n\n{}", md.frontmatter_yaml, md.body);
+    write_atomic(&path, content.as_bytes(), &paths.tmp_dir)?;
+    Ok(path)
+}
+
+/// The condensed transcript as markdown, with the full transcript's frontmatter
+pub fn to_markdown(
+    paths: &Paths,
+    entry: &CatalogEntry,
+    condensed: &Condensed,
+) -> Result<MarkdownOutput> {
+    let fm = read_frontmatter(&entry.path)?.ok_or_else(|| {
+        Error::InvalidInput(format!("{} has no frontmatter", entry.path.display()))
+    })?;
+    let meta = DocumentMetadata {
+        id: Some(fm.doc_id.clone()),
+        title: fm.title.clone(),
+        created_at: fm.created_at,
+        updated_at: fm.remote_updated_at,
+        participants: fm.participants.clone(),
+        duration_seconds: fm.duration_seconds,
+        labels: fm.labels.clone(),
+        calendar_event: fm.calendar_event.clone(),
+    };
+    to_markdown_with_local(
+        &condensed.raw,
+        &meta,
+        &fm.doc_id,
+        &MarkdownOptions::configured(paths)?,
+        &LocalFields::from_frontmatter(&fm),
+    )
+}
+
+#[cfg(feature = "summaries")]
+const CLASSIFY_PROMPT: &str = r#"You are cleaning up a meeting transcript before it is shared outside the team.
+
+Below are numbered transcript paragraphs. Find the paragraphs that are only greetings, small talk, jokes, or filler and carry nothing about the meeting's subject.
+
+Respond with only a JSON object listing their numbers, like {"smalltalk": [2, 7]}, or {"smalltalk": []} if there are none."#;
+
+/// Asks the summary model which entries are small talk, one flag per entry
+///
+/// Entries are sent in batches that fit the configured context window. A
+/// batch whose reply can't be read keeps all of its entries.
+#[cfg(feature = "summaries")]
+pub async fn classify(
+    raw: &RawTranscript,
+    api_key: Option<&str>,
+    config: &crate::summary::SummaryConfig,
+) -> Result<Vec<bool>> {
+    #[derive(serde::Deserialize)]
+    struct Reply {
+        smalltalk: Vec<usize>,
+    }
+
+    use crate::summary::Summarizer;
+
+    let mut backend = crate::summary::Backend::new(api_key, config)?;
+    let mut flags = vec![false; raw.entries.len()];
+    let mut start = 0;
+    while start < raw.entries.len() {
+        let mut batch = String::new();
+        let mut end = start;
+        while end < raw.entries.len() {
+            let entry = &raw.entries[end];
+            let line = format!(
+                "[{}] {}: {}\n",
+                end,
+                entry.speaker.as_deref().unwrap_or("Speaker"),
+                entry.text
+            );
+            if end > start && batch.len() + line.len() > config.context_window_chars {
+                break;
+            }
+            batch.push_str(&line);
+            end += 1;
+        }
+
+        let prompt = format!("{}\n\n{}", CLASSIFY_PROMPT, batch);
+        let reply = backend.complete_json(prompt).await?;
+        let parsed = match (reply.find('{'), reply.rfind('}')) {
+            (Some(a), Some(b)) if a < b => serde_json::from_str::<Reply>(&reply[a..=b]).ok(),
+            _ => None,
+        };
+        match parsed {
+            Some(reply) => {
+                for i in reply.smalltalk {
+                    if (start..end).contains(&i) {
+                        flags[i] = true;
+                    }
+                }
+            }
+            None => eprintln!(
+                "Warning: Couldn't read the classification of paragraphs {}-{}; keeping them",
+                start,
+                end - 1
+            ),
+        }
+        start = end;
+    }
+    Ok(flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_text() {
+        assert_eq!(clean_text("Hi everyone!"), None);
+        assert_eq!(clean_text("Um, hey Alice. How are you?"), None);
+        assert_eq!(clean_text("Thanks, bye!"), None);
+        assert_eq!(clean_text("uh, um..."), None);
+        assert_eq!(
+            clean_text("Um, so we shipped the, uh, billing fix").as_deref(),
+            Some("So we shipped the, billing fix")
+        );
+        assert_eq!(
+            clean_text("Okay. The migration is blocked").as_deref(),
+            Some("Okay. The migration is blocked")
+        );
+        assert_eq!(
+            clean_text("That deploy was a Fucking mess, damn.").as_deref(),
+            Some("That deploy was a F****** mess, d***.")
+        );
+    }
+
+    #[test]
+    fn test_condense_and_drop_flagged() {
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[
+                {"speaker": "Alice", "text": "Good morning!"},
+                {"speaker": "Bob", "text": "Morning. Can you hear me?"},
+                {"speaker": "Alice", "text": "Let's review the Q3 roadmap"},
+                {"speaker": "Bob", "text": "Did you see the game last night"},
+                {"speaker": "Alice", "text": "Thanks all, bye"}
+            ]"#,
+        )
+        .unwrap();
+
+        let mut condensed = condense(&raw);
+        assert_eq!(condensed.original, 5);
+        let texts: Vec<&str> = condensed
+            .raw
+            .entries
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Let's review the Q3 roadmap",
+                "Did you see the game last night"
+            ]
+        );
+
+        condensed.drop_flagged(&[false, true]);
+        assert_eq!(condensed.kept(), 1);
+    }
+}
@@ -0,0 +1,88 @@
+// ABOUTME: The data directory's config.toml, which holds every subsystem's settings
+// ABOUTME: Each subsystem reads its own table, e.g. [markdown] or [mcp]; missing tables mean defaults
+
+use crate::{Error, Paths, Result};
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+/// Name of the settings file in the data directory
+pub const FILE_NAME: &str = "config.toml";
+
+/// The `[name]` table of a config file as `T`
+///
+/// A missing file or table gives `T::default()`, so every table is optional.
+pub fn load_section<T: DeserializeOwned + Default>(path: &Path, name: &str) -> Result<T> {
+    match raw_section(path, name)? {
+        Some(value) => value.try_into().map_err(|e| invalid(path, name, e)),
+        None => Ok(T::default()),
+    }
+}
+
+/// The `[name]` table of the data directory's config.toml
+pub fn section<T: DeserializeOwned + Default>(paths: &Paths, name: &str) -> Result<T> {
+    load_section(&paths.config_file, name)
+}
+
+/// The `[name]` table as written, for copying settings elsewhere unchanged
+pub fn raw_section(path: &Path, name: &str) -> Result<Option<toml::Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let mut tables: toml::Table = toml::from_str(&content)
+        .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))?;
+    Ok(tables.remove(name))
+}
+
+/// An error naming the table and file a bad setting came from
+pub fn invalid(path: &Path, name: &str, reason: impl std::fmt::Display) -> Error {
+    Error::InvalidInput(format!(
+        "Invalid [{}] in {}: {}",
+        name,
+        path.display(),
+        reason
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tempfile::TempDir;
+
+    #[derive(Debug, Default, PartialEq, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Example {
+        limit: u32,
+    }
+
+    #[test]
+    fn test_load_section() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(FILE_NAME);
+        assert_eq!(
+            load_section::<Example>(&path, "example").unwrap(),
+            Example::default()
+        );
+
+        std::fs::write(&path, "[other]\nkey = 1\n").unwrap();
+        assert_eq!(
+            load_section::<Example>(&path, "example").unwrap(),
+            Example::default()
+        );
+
+        std::fs::write(&path, "[other]\nkey = 1\n\n[example]\nlimit = 3\n").unwrap();
+        assert_eq!(
+            load_section::<Example>(&path, "example").unwrap(),
+            Example { limit: 3 }
+        );
+
+        std::fs::write(&path, "[example]\nlimt = 3\n").unwrap();
+        let err = load_section::<Example>(&path, "example").unwrap_err();
+        assert!(err.to_string().contains("[example]"), "{}", err);
+
+        std::fs::write(&path, "[example\n").unwrap();
+        assert!(load_section::<Example>(&path, "other").is_err());
+    }
+}
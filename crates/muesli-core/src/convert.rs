@@ -0,0 +1,1030 @@
+// ABOUTME: Converts raw transcript JSON to structured Markdown
+// ABOUTME: Supports both segment and monologue formats with frontmatter
+
+use crate::access::Access;
+use crate::api::GRANOLA_SOURCE;
+use crate::dedupe::content_hash;
+use crate::gaps::{find_gaps, gap_marker, GAP_THRESHOLD_SECS};
+use crate::pipeline::{Pipeline, ProcessorConfig};
+use crate::speakers::{rename, InferredSpeaker, SpeakerAliases};
+use crate::template::{EntryView, TemplateContext, TemplateMetadata, TranscriptTemplate};
+use crate::util::{normalize_timestamp, organizations_from_participants};
+use crate::{
+    config, CalendarEvent, CalendarPerson, DocumentMetadata, Frontmatter, Paths, RawTranscript,
+    Result,
+};
+use serde::Deserialize;
+use std::path::Path;
+
+pub struct MarkdownOutput {
+    pub frontmatter_yaml: String,
+    pub body: String,
+}
+
+/// How each speaker entry is introduced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeakerStyle {
+    /// `**Alice (00:01:05):** text` on every entry
+    #[default]
+    Bold,
+    /// A `### Alice (00:01:05)` heading per speaker turn, entries below it
+    Heading,
+}
+
+/// Whether timestamped entries get an id to link to, and in which syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnchorStyle {
+    #[default]
+    None,
+    /// `<a id="t-00-05-10"></a>` in front of the entry, or after a speaker heading
+    Html,
+    /// `### Alice (00:05:10) {#t-00-05-10}` on speaker headings, for Pandoc and
+    /// similar renderers; bold entries have no heading and get the HTML anchor
+    HeadingId,
+}
+
+/// The anchor id for an entry that started at `HH:MM:SS`, e.g. `t-00-05-10`
+pub fn anchor_id(timestamp: &str) -> String {
+    format!("t-{}", timestamp.replace(':', "-"))
+}
+
+/// Splits an anchor muesli wrote off a transcript line, returning the line
+/// without it and the anchor id
+pub fn strip_anchor(line: &str) -> (&str, Option<&str>) {
+    if let Some(rest) = line.strip_prefix("<a id=\"") {
+        if let Some((id, rest)) = rest.split_once("\"></a>") {
+            return (rest, Some(id));
+        }
+    }
+    let trimmed = line.trim_end();
+    if let Some((rest, id)) = trimmed
+        .strip_suffix("\"></a>")
+        .and_then(|l| l.rsplit_once(" <a id=\""))
+    {
+        return (rest, Some(id));
+    }
+    if let Some((rest, id)) = trimmed.strip_suffix('}').and_then(|l| l.rsplit_once(" {#")) {
+        return (rest, Some(id));
+    }
+    (line, None)
+}
+
+/// How much of each entry's timestamp is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampPrecision {
+    /// HH:MM:SS
+    #[default]
+    Seconds,
+    /// HH:MM
+    Minutes,
+}
+
+/// Layout settings from `[markdown]` in config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MarkdownOptions {
+    pub speaker_style: SpeakerStyle,
+    /// Show when each entry (or turn) started
+    pub timestamps: bool,
+    pub timestamp_precision: TimestampPrecision,
+    /// Separate entries with blank lines so each renders as its own paragraph
+    pub paragraphs: bool,
+    /// Join consecutive entries by the same speaker into one, under the first timestamp
+    pub merge_turns: bool,
+    /// Mark speakers attributed with less confidence than this as `Alice(?)`
+    pub min_confidence: Option<f64>,
+    /// Ids on timestamped entries, so links can point at a moment in the meeting
+    pub anchors: AnchorStyle,
+    /// `[[markdown.processors]]` run in order over the finished markdown, e.g. dedupe-turns or redact
+    pub processors: Vec<ProcessorConfig>,
+    /// `templates/transcript.md.hbs`, which replaces the body layout when present
+    #[serde(skip)]
+    pub template: Option<TranscriptTemplate>,
+    /// Canonical names from `[speakers.aliases]`, for speakers and the participants line
+    #[serde(skip)]
+    pub aliases: SpeakerAliases,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            speaker_style: SpeakerStyle::Bold,
+            timestamps: true,
+            timestamp_precision: TimestampPrecision::Seconds,
+            paragraphs: false,
+            merge_turns: false,
+            min_confidence: None,
+            anchors: AnchorStyle::None,
+            processors: Vec::new(),
+            template: None,
+            aliases: SpeakerAliases::default(),
+        }
+    }
+}
+
+impl MarkdownOptions {
+    /// The layout keys and processors in a config file's `[markdown]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "markdown")
+    }
+
+    /// The data directory's `[markdown]` layout, with its transcript template and speaker aliases
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Ok(Self {
+            template: TranscriptTemplate::configured(paths)?,
+            aliases: SpeakerAliases::configured(paths)?,
+            ..Self::load(&paths.config_file)?
+        })
+    }
+
+    /// HH:MM:SS (or HH:MM) for an entry start, or None when timestamps are off or unknown
+    fn timestamp(&self, start: Option<&str>) -> Option<String> {
+        if !self.timestamps {
+            return None;
+        }
+        start
+            .and_then(normalize_timestamp)
+            .map(|ts| match self.timestamp_precision {
+                TimestampPrecision::Seconds => ts,
+                TimestampPrecision::Minutes => match ts.rsplit_once(':') {
+                    Some((hm, _)) if hm.contains(':') => hm.to_string(),
+                    _ => ts,
+                },
+            })
+    }
+}
+
+/// Ends the body with a blank line, so the next block starts a new paragraph
+fn blank_line(body: &mut String) {
+    while !body.is_empty() && !body.ends_with("\n\n") {
+        body.push('\n');
+    }
+}
+
+pub fn to_markdown(
+    raw: &RawTranscript,
+    meta: &DocumentMetadata,
+    doc_id: &str,
+    options: &MarkdownOptions,
+) -> Result<MarkdownOutput> {
+    to_markdown_with_local(raw, meta, doc_id, options, &LocalFields::default())
+}
+
+/// Frontmatter fields decided on this machine rather than fetched, kept when a transcript is rewritten
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalFields {
+    /// Attendee names for generic speaker labels
+    pub inferred_speakers: Vec<InferredSpeaker>,
+    pub access: Option<Access>,
+    pub duplicate_of: Option<String>,
+    pub merged_duplicates: Vec<String>,
+    /// Link to the companion notes file, which sync writes next to the transcript
+    pub notes: Option<String>,
+    /// Where the meeting came from: "granola", or an import such as "vtt" or "email"
+    pub source: String,
+}
+
+impl Default for LocalFields {
+    fn default() -> Self {
+        Self {
+            inferred_speakers: Vec::new(),
+            access: None,
+            duplicate_of: None,
+            merged_duplicates: Vec::new(),
+            notes: None,
+            source: GRANOLA_SOURCE.to_string(),
+        }
+    }
+}
+
+impl LocalFields {
+    /// The fields recorded in an earlier version of the transcript
+    pub fn from_frontmatter(fm: &Frontmatter) -> Self {
+        Self {
+            inferred_speakers: fm.inferred_speakers.clone(),
+            access: fm.access,
+            duplicate_of: fm.duplicate_of.clone(),
+            merged_duplicates: fm.merged_duplicates.clone(),
+            notes: fm.notes.clone(),
+            source: fm.source.clone(),
+        }
+    }
+}
+
+/// Like `to_markdown`, with local fields recorded in the frontmatter
+///
+/// Generic speaker labels named in `inferred_speakers` are shown as the attendee's name.
+pub fn to_markdown_with_local(
+    raw: &RawTranscript,
+    meta: &DocumentMetadata,
+    doc_id: &str,
+    options: &MarkdownOptions,
+    local: &LocalFields,
+) -> Result<MarkdownOutput> {
+    let speakers = &local.inferred_speakers;
+    // Build frontmatter
+    let frontmatter = Frontmatter {
+        doc_id: doc_id.to_string(),
+        source: local.source.clone(),
+        created_at: meta.created_at,
+        remote_updated_at: meta.updated_at,
+        title: meta.title.clone(),
+        participants: meta.participants.clone(),
+        duration_seconds: meta.duration_seconds,
+        labels: meta.labels.clone(),
+        organizations: organizations_from_participants(&meta.participants),
+        inferred_speakers: speakers.to_vec(),
+        access: local.access,
+        content_hash: content_hash(raw),
+        duplicate_of: local.duplicate_of.clone(),
+        merged_duplicates: local.merged_duplicates.clone(),
+        notes: local.notes.clone(),
+        calendar_event: meta.calendar_event.clone(),
+        generator: "muesli 1.0".into(),
+    };
+
+    let frontmatter_yaml = serde_yaml::to_string(&frontmatter).map_err(|e| {
+        crate::Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to serialize frontmatter: {}", e),
+        ))
+    })?;
+
+    let title = meta.title.as_deref().unwrap_or("Untitled Meeting");
+
+    // Metadata line
+    let date = meta.created_at.format("%Y-%m-%d");
+    let mut meta_parts = vec![format!("Date: {}", date)];
+
+    if let Some(duration) = meta.duration_seconds {
+        let minutes = duration / 60;
+        meta_parts.push(format!("Duration: {}m", minutes));
+    }
+
+    // The frontmatter keeps the addresses; the body shows one name per person
+    let mut participants: Vec<String> = Vec::new();
+    for participant in &meta.participants {
+        let name = options.aliases.apply(participant);
+        if !participants.iter().any(|p| p == name) {
+            participants.push(name.to_string());
+        }
+    }
+    if !participants.is_empty() {
+        meta_parts.push(format!("Participants: {}", participants.join(", ")));
+    }
+
+    // Aliases, merged turns, anchors and the [[processors]] all run in the pipeline
+    let pipeline = Pipeline::configured(options)?;
+    let entries = pipeline.run_entries(entry_views(raw, options, speakers));
+    let transcript = layout_entries(&entries, options);
+    let attendees = meta.calendar_event.as_ref().and_then(attendees_section);
+
+    let body = match &options.template {
+        Some(template) => template.render(&TemplateContext {
+            frontmatter: &frontmatter,
+            metadata: TemplateMetadata {
+                title,
+                date: date.to_string(),
+                time: meta.created_at.format("%H:%M").to_string(),
+                duration_minutes: meta.duration_seconds.map(|d| d / 60),
+                participants: &participants,
+                labels: &meta.labels,
+                line: meta_parts.join(" · "),
+                attendees: attendees.clone().unwrap_or_default(),
+            },
+            entries,
+            transcript,
+        })?,
+        None => match attendees {
+            Some(attendees) => format!(
+                "# {}\n\n_{}_\n\n{}\n## Transcript\n\n{}",
+                title,
+                meta_parts.join(" · "),
+                attendees,
+                transcript
+            ),
+            None => format!(
+                "# {}\n\n_{}_\n\n{}",
+                title,
+                meta_parts.join(" · "),
+                transcript
+            ),
+        },
+    };
+
+    pipeline.run(MarkdownOutput {
+        frontmatter_yaml,
+        body,
+    })
+}
+
+/// "## Attendees" with everyone invited, the organizer first; `None` without attendees
+fn attendees_section(event: &CalendarEvent) -> Option<String> {
+    if event.attendees.is_empty() {
+        return None;
+    }
+    let mut attendees: Vec<&CalendarPerson> = event.attendees.iter().collect();
+    attendees.sort_by_key(|person| !event.is_organizer(person));
+
+    let mut out = String::from("## Attendees\n\n");
+    for person in attendees {
+        let mut line = match (&person.name, &person.email) {
+            (Some(name), Some(email)) => format!("{} <{}>", name, email),
+            (Some(name), None) => name.clone(),
+            (None, Some(email)) => email.clone(),
+            (None, None) => continue,
+        };
+        if event.is_organizer(person) {
+            line.push_str(" (organizer)");
+        }
+        match person.response.as_deref() {
+            Some("declined") => line.push_str(" — declined"),
+            Some("tentative") => line.push_str(" — maybe"),
+            Some("needsAction") => line.push_str(" — no response"),
+            _ => {}
+        }
+        out.push_str(&format!("- {}\n", line));
+    }
+    Some(out)
+}
+
+/// Each entry with its speaker (inferred names applied) and timestamp as shown,
+/// and the gap before it
+fn entry_views(
+    raw: &RawTranscript,
+    options: &MarkdownOptions,
+    speakers: &[InferredSpeaker],
+) -> Vec<EntryView> {
+    let gaps = find_gaps(&raw.entries, GAP_THRESHOLD_SECS);
+    let mut gaps = gaps.iter().peekable();
+
+    raw.entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| EntryView {
+            speaker: rename(entry.speaker.as_deref().unwrap_or("Speaker"), speakers).to_string(),
+            timestamp: options.timestamp(entry.start.as_deref()),
+            anchor: None,
+            text: entry.text.clone(),
+            start: entry.start.clone(),
+            end: entry.end.clone(),
+            confidence: entry.confidence,
+            gap_before: gaps
+                .next_if(|gap| gap.before == i)
+                .map(|gap| gap_marker(gap.seconds)),
+        })
+        .collect()
+}
+
+/// The entries in the layout `[markdown]` asks for
+fn layout_entries(entries: &[EntryView], options: &MarkdownOptions) -> String {
+    if entries.is_empty() {
+        return "_No transcript content available._\n".to_string();
+    }
+
+    let mut body = String::new();
+    let headings = options.speaker_style == SpeakerStyle::Heading;
+    let separator = if options.paragraphs { "\n\n" } else { "\n" };
+    let mut turn: Option<&str> = None;
+
+    for entry in entries {
+        if let Some(gap) = &entry.gap_before {
+            if headings {
+                blank_line(&mut body);
+            }
+            body.push_str(gap);
+            body.push_str(if headings { "\n\n" } else { separator });
+        }
+
+        let timestamp = entry
+            .timestamp
+            .as_deref()
+            .map(|ts| format!(" ({})", ts))
+            .unwrap_or_default();
+        let anchor = entry
+            .anchor
+            .as_deref()
+            .filter(|_| options.anchors != AnchorStyle::None);
+
+        if headings {
+            if turn != Some(entry.speaker.as_str()) {
+                blank_line(&mut body);
+                let anchor = match (anchor, options.anchors) {
+                    (Some(id), AnchorStyle::HeadingId) => format!(" {{#{}}}", id),
+                    (Some(id), _) => format!(" <a id=\"{}\"></a>", id),
+                    (None, _) => String::new(),
+                };
+                body.push_str(&format!("### {}{}{}\n\n", entry.speaker, timestamp, anchor));
+                turn = Some(&entry.speaker);
+            }
+            body.push_str(&entry.text);
+        } else {
+            if let Some(id) = anchor {
+                body.push_str(&format!("<a id=\"{}\"></a>", id));
+            }
+            body.push_str(&format!(
+                "**{}{}:** {}",
+                entry.speaker, timestamp, entry.text
+            ));
+        }
+        body.push_str(separator);
+    }
+
+    // Paragraph separators leave one blank line too many at the end
+    if options.paragraphs {
+        body.pop();
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TranscriptEntry;
+
+    #[test]
+    fn test_to_markdown_entries() {
+        let raw = RawTranscript {
+            entries: vec![
+                TranscriptEntry {
+                    document_id: Some("doc123".into()),
+                    speaker: Some("Alice".into()),
+                    start: Some("2025-10-01T21:35:12.500Z".into()),
+                    end: Some("2025-10-01T21:35:18.000Z".into()),
+                    text: "Hello everyone".into(),
+                    source: Some("microphone".into()),
+                    id: Some("entry1".into()),
+                    is_final: Some(true),
+                    confidence: None,
+                    words: vec![],
+                },
+                TranscriptEntry {
+                    document_id: Some("doc123".into()),
+                    speaker: Some("Bob".into()),
+                    start: Some("2025-10-01T21:35:20.000Z".into()),
+                    end: Some("2025-10-01T21:35:22.000Z".into()),
+                    text: "Hi there".into(),
+                    source: Some("microphone".into()),
+                    id: Some("entry2".into()),
+                    is_final: Some(true),
+                    confidence: None,
+                    words: vec![],
+                },
+            ],
+        };
+
+        let meta = DocumentMetadata {
+            id: Some("doc123".into()),
+            title: Some("Test Meeting".into()),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec!["Alice".into(), "Bob".into()],
+            duration_seconds: Some(3600),
+            labels: vec![],
+            calendar_event: None,
+        };
+
+        let output = to_markdown(&raw, &meta, "doc123", &MarkdownOptions::default()).unwrap();
+
+        assert!(output.body.contains("# Test Meeting"));
+        assert!(output.body.contains("**Alice"));
+        assert!(output.body.contains("Hello everyone"));
+        assert!(output.body.contains("**Bob"));
+        assert!(output.body.contains("Hi there"));
+        assert!(output.body.contains("Duration: 60m"));
+        assert!(output.frontmatter_yaml.contains("doc123"));
+    }
+
+    #[test]
+    fn test_calendar_attendees_section() {
+        let raw = RawTranscript {
+            entries: vec![TranscriptEntry {
+                document_id: None,
+                speaker: Some("Alice".into()),
+                start: None,
+                end: None,
+                text: "Let's start".into(),
+                source: None,
+                id: None,
+                is_final: None,
+                confidence: None,
+                words: vec![],
+            }],
+        };
+        let person = |name: Option<&str>, email: &str, response: &str| CalendarPerson {
+            email: Some(email.into()),
+            name: name.map(str::to_string),
+            response: Some(response.into()),
+        };
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Planning".into()),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: Some(CalendarEvent {
+                organizer: Some(person(None, "alice@acme.com", "accepted")),
+                attendees: vec![
+                    person(None, "bob@acme.com", "declined"),
+                    person(Some("Alice"), "alice@acme.com", "accepted"),
+                    person(None, "carol@acme.com", "needsAction"),
+                ],
+                meeting_url: Some("https://meet.google.com/abc".into()),
+                recurrence_id: Some("weekly123".into()),
+            }),
+        };
+
+        let output = to_markdown(&raw, &meta, "doc", &MarkdownOptions::default()).unwrap();
+        assert!(output.body.contains(
+            "## Attendees\n\n- Alice <alice@acme.com> (organizer)\n- bob@acme.com — declined\n\
+             - carol@acme.com — no response\n\n## Transcript\n\n**Alice:** Let's start"
+        ));
+        let fm: Frontmatter = serde_yaml::from_str(&output.frontmatter_yaml).unwrap();
+        assert_eq!(fm.calendar_event, meta.calendar_event);
+        assert!(output.frontmatter_yaml.contains("recurrence_id: weekly123"));
+
+        // Without attendees the body is unchanged
+        let meta = DocumentMetadata {
+            calendar_event: Some(CalendarEvent::default()),
+            ..meta
+        };
+        let output = to_markdown(&raw, &meta, "doc", &MarkdownOptions::default()).unwrap();
+        assert!(!output.body.contains("## "));
+    }
+
+    #[test]
+    fn test_to_markdown_marks_long_gaps() {
+        let entry = |speaker: &str, start: &str, end: &str, text: &str| TranscriptEntry {
+            document_id: None,
+            speaker: Some(speaker.into()),
+            start: Some(start.into()),
+            end: Some(end.into()),
+            text: text.into(),
+            source: None,
+            id: None,
+            is_final: None,
+            confidence: None,
+            words: vec![],
+        };
+        let raw = RawTranscript {
+            entries: vec![
+                entry(
+                    "Alice",
+                    "2025-10-01T10:00:00Z",
+                    "2025-10-01T10:00:05Z",
+                    "Sharing my screen",
+                ),
+                entry(
+                    "Bob",
+                    "2025-10-01T10:12:05Z",
+                    "2025-10-01T10:12:08Z",
+                    "Looks good",
+                ),
+            ],
+        };
+        let meta = DocumentMetadata {
+            id: None,
+            title: None,
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+
+        let body = to_markdown(&raw, &meta, "doc", &MarkdownOptions::default())
+            .unwrap()
+            .body;
+        assert!(body.contains(
+            "**Alice (10:00:00):** Sharing my screen\n[12m gap]\n**Bob (10:12:05):** Looks good\n"
+        ));
+    }
+
+    #[test]
+    fn test_to_markdown_layout_options() {
+        let entry = |speaker: &str, start: &str, text: &str| TranscriptEntry {
+            document_id: None,
+            speaker: Some(speaker.into()),
+            start: Some(start.into()),
+            end: None,
+            text: text.into(),
+            source: None,
+            id: None,
+            is_final: None,
+            confidence: None,
+            words: vec![],
+        };
+        let raw = RawTranscript {
+            entries: vec![
+                entry("Alice", "2025-10-01T10:00:05Z", "Hi all"),
+                entry("Alice", "2025-10-01T10:00:09Z", "Quick agenda"),
+                entry("Bob", "2025-10-01T10:01:30Z", "Sounds good"),
+            ],
+        };
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Sync".into()),
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let body =
+            |options: MarkdownOptions| to_markdown(&raw, &meta, "doc", &options).unwrap().body;
+
+        let headings = body(MarkdownOptions {
+            speaker_style: SpeakerStyle::Heading,
+            timestamp_precision: TimestampPrecision::Minutes,
+            ..Default::default()
+        });
+        assert!(headings.ends_with(
+            "_Date: 2025-10-01_\n\n### Alice (10:00)\n\nHi all\nQuick agenda\n\n### Bob (10:01)\n\nSounds good\n"
+        ));
+
+        let paragraphs = body(MarkdownOptions {
+            timestamps: false,
+            paragraphs: true,
+            ..Default::default()
+        });
+        assert!(paragraphs
+            .ends_with("**Alice:** Hi all\n\n**Alice:** Quick agenda\n\n**Bob:** Sounds good\n"));
+    }
+
+    #[test]
+    fn test_low_confidence_speakers_are_marked() {
+        let entry = |speaker: &str, confidence: Option<f64>, text: &str| TranscriptEntry {
+            document_id: None,
+            speaker: Some(speaker.into()),
+            start: None,
+            end: None,
+            text: text.into(),
+            source: None,
+            id: None,
+            is_final: None,
+            confidence,
+            words: vec![],
+        };
+        let raw = RawTranscript {
+            entries: vec![
+                entry("Alice", Some(0.9), "Sure"),
+                entry("Alice", Some(0.4), "Maybe"),
+                entry("Bob", None, "Ok"),
+            ],
+        };
+        let meta = DocumentMetadata {
+            id: None,
+            title: None,
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let body =
+            |options: MarkdownOptions| to_markdown(&raw, &meta, "doc", &options).unwrap().body;
+
+        assert!(body(MarkdownOptions::default())
+            .ends_with("**Alice:** Sure\n**Alice:** Maybe\n**Bob:** Ok\n"));
+        assert!(body(MarkdownOptions {
+            min_confidence: Some(0.6),
+            ..Default::default()
+        })
+        .ends_with("**Alice:** Sure\n**Alice(?):** Maybe\n**Bob:** Ok\n"));
+        assert!(body(MarkdownOptions {
+            speaker_style: SpeakerStyle::Heading,
+            min_confidence: Some(0.6),
+            ..Default::default()
+        })
+        .ends_with("### Alice\n\nSure\n\n### Alice(?)\n\nMaybe\n\n### Bob\n\nOk\n"));
+    }
+
+    #[test]
+    fn test_markdown_options_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            MarkdownOptions::load(&path).unwrap(),
+            MarkdownOptions::default()
+        );
+
+        std::fs::write(
+            &path,
+            "[markdown]\nspeaker_style = \"heading\"\nparagraphs = true\n",
+        )
+        .unwrap();
+        let options = MarkdownOptions::load(&path).unwrap();
+        assert_eq!(options.speaker_style, SpeakerStyle::Heading);
+        assert!(options.paragraphs);
+        assert!(options.timestamps);
+
+        std::fs::write(&path, "[markdown]\nspeaker_style = \"italic\"\n").unwrap();
+        assert!(MarkdownOptions::load(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "[[markdown.processors]]\nname = \"redact\"\nterms = [\"Alice\"]\n",
+        )
+        .unwrap();
+        let options = MarkdownOptions::load(&path).unwrap();
+        assert_eq!(
+            options.processors,
+            vec![ProcessorConfig::Redact {
+                terms: vec!["Alice".to_string()],
+                emails: false
+            }]
+        );
+    }
+
+    #[test]
+    fn test_merge_turns() {
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[{"speaker": "Alice", "text": "First point.", "start_timestamp": "2025-10-01T10:00:00Z"},
+                {"speaker": "Alice", "text": "Second point.", "start_timestamp": "2025-10-01T10:00:04Z"},
+                {"speaker": "Bob", "text": "Agreed.", "start_timestamp": "2025-10-01T10:00:09Z"},
+                {"speaker": "Alice", "text": "After the break.", "start_timestamp": "2025-10-01T10:00:12Z"},
+                {"speaker": "Alice", "text": "Back again.", "start_timestamp": "2025-10-01T10:09:00Z"}]"#,
+        )
+        .unwrap();
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Sync".into()),
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let body =
+            |options: MarkdownOptions| to_markdown(&raw, &meta, "doc", &options).unwrap().body;
+
+        let merged = body(MarkdownOptions {
+            merge_turns: true,
+            ..Default::default()
+        });
+        assert!(merged.ends_with(
+            "**Alice (10:00:00):** First point. Second point.\n\
+             **Bob (10:00:09):** Agreed.\n\
+             **Alice (10:00:12):** After the break.\n\
+             [8m gap]\n\
+             **Alice (10:09:00):** Back again.\n"
+        ));
+
+        let headings = body(MarkdownOptions {
+            merge_turns: true,
+            speaker_style: SpeakerStyle::Heading,
+            ..Default::default()
+        });
+        assert!(headings.contains("### Alice (10:00:00)\n\nFirst point. Second point.\n\n### Bob"));
+    }
+
+    #[test]
+    fn test_anchors() {
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[{"speaker": "Alice", "text": "Hi.", "start_timestamp": "2025-10-01T10:00:00.200Z"},
+                {"speaker": "Alice", "text": "Same second.", "start_timestamp": "2025-10-01T10:00:00.900Z"},
+                {"speaker": "Bob", "text": "Hello.", "start_timestamp": "2025-10-01T10:01:10Z"},
+                {"speaker": "Carol", "text": "No time."}]"#,
+        )
+        .unwrap();
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Sync".into()),
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let body =
+            |options: MarkdownOptions| to_markdown(&raw, &meta, "doc", &options).unwrap().body;
+
+        // Off by default
+        assert!(!body(MarkdownOptions::default()).contains("t-10-00-00"));
+
+        let html = body(MarkdownOptions {
+            anchors: AnchorStyle::Html,
+            ..Default::default()
+        });
+        assert!(html.ends_with(
+            "<a id=\"t-10-00-00\"></a>**Alice (10:00:00):** Hi.\n\
+             **Alice (10:00:00):** Same second.\n\
+             <a id=\"t-10-01-10\"></a>**Bob (10:01:10):** Hello.\n\
+             **Carol:** No time.\n"
+        ));
+
+        let headings = body(MarkdownOptions {
+            anchors: AnchorStyle::HeadingId,
+            speaker_style: SpeakerStyle::Heading,
+            ..Default::default()
+        });
+        assert!(headings.contains("### Bob (10:01:10) {#t-10-01-10}\n\nHello.\n"));
+
+        // find-in reads the anchors back
+        let entries = crate::find::parse_entries(&html);
+        assert_eq!(entries[2].anchor.as_deref(), Some("t-10-01-10"));
+        assert_eq!(entries[2].speaker, "Bob");
+        let entries = crate::find::parse_entries(&headings);
+        assert_eq!(entries[0].anchor.as_deref(), Some("t-10-00-00"));
+    }
+
+    #[test]
+    fn test_aliases_name_speakers_and_participants() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        std::fs::write(
+            &paths.config_file,
+            "[speakers.aliases]\n\"John Doe\" = [\"jdoe@corp.com\", \"John D\"]\n",
+        )
+        .unwrap();
+        let options = MarkdownOptions::configured(&paths).unwrap();
+
+        let raw: RawTranscript =
+            serde_json::from_str(r#"[{"speaker": "john d", "text": "Morning"}]"#).unwrap();
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Sync".into()),
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec!["jdoe@corp.com".into(), "John D".into(), "Alice".into()],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let output = to_markdown(&raw, &meta, "doc1", &options).unwrap();
+        assert!(output.body.ends_with(
+            "_Date: 2025-10-01 · Participants: John Doe, Alice_\n\n**John Doe:** Morning\n"
+        ));
+        // Addresses stay in the frontmatter, where organizations come from
+        assert!(output.frontmatter_yaml.contains("- jdoe@corp.com\n"));
+        assert!(output.frontmatter_yaml.contains("- corp.com\n"));
+    }
+
+    #[test]
+    fn test_template_replaces_body_layout() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        assert_eq!(MarkdownOptions::configured(&paths).unwrap().template, None);
+
+        let path = paths.data_dir.join(crate::template::TEMPLATE_FILE);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(
+            &path,
+            "## {{metadata.title}} <{{frontmatter.doc_id}}>\n{{metadata.line}}\n\n\
+             {{#each entries}}- {{speaker}}{{#if timestamp}} @ {{timestamp}}{{/if}}: {{text}}\n{{/each}}",
+        )
+        .unwrap();
+        let options = MarkdownOptions::configured(&paths).unwrap();
+
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[{"speaker": "Alice", "text": "A & B", "start_timestamp": "2025-10-01T10:00:05Z"},
+                {"speaker": "Bob", "text": "Ok"}]"#,
+        )
+        .unwrap();
+        let meta = DocumentMetadata {
+            id: None,
+            title: Some("Sync".into()),
+            created_at: "2025-10-01T10:00:00Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec!["Alice".into()],
+            duration_seconds: Some(600),
+            labels: vec![],
+            calendar_event: None,
+        };
+        let output = to_markdown(&raw, &meta, "doc1", &options).unwrap();
+        assert_eq!(
+            output.body,
+            "## Sync <doc1>\nDate: 2025-10-01 · Duration: 10m · Participants: Alice\n\n\
+             - Alice @ 10:00:05: A & B\n- Bob: Ok\n"
+        );
+        assert!(output.frontmatter_yaml.contains("doc_id: doc1"));
+
+        std::fs::write(&path, "{{#each entries}}").unwrap();
+        let err = MarkdownOptions::configured(&paths).unwrap_err();
+        assert!(err.to_string().contains("transcript.md.hbs"));
+    }
+
+    #[test]
+    fn test_to_markdown_with_local() {
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[{"speaker": "Speaker 1", "text": "Hi Bob"}, {"speaker": "Speaker 2", "text": "Hi"}]"#,
+        )
+        .unwrap();
+        let meta = DocumentMetadata {
+            id: None,
+            title: None,
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec!["Alice".into(), "Bob".into()],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+        let local = LocalFields {
+            inferred_speakers: vec![InferredSpeaker {
+                label: "Speaker 1".into(),
+                name: "Alice".into(),
+                confidence: 0.8,
+                confirmed: false,
+            }],
+            access: Some(Access::Private),
+            ..LocalFields::default()
+        };
+
+        let output =
+            to_markdown_with_local(&raw, &meta, "doc", &MarkdownOptions::default(), &local)
+                .unwrap();
+        assert!(output
+            .body
+            .contains("**Alice:** Hi Bob\n**Speaker 2:** Hi\n"));
+        assert!(output
+            .frontmatter_yaml
+            .contains("inferred_speakers:\n- label: Speaker 1\n  name: Alice\n  confidence: 0.8"));
+        assert!(output.frontmatter_yaml.contains("\naccess: private\n"));
+    }
+
+    #[test]
+    fn test_to_markdown_empty_transcript() {
+        let raw = RawTranscript { entries: vec![] };
+
+        let meta = DocumentMetadata {
+            id: Some("doc123".into()),
+            title: None,
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            updated_at: None,
+            participants: vec![],
+            duration_seconds: None,
+            labels: vec![],
+            calendar_event: None,
+        };
+
+        let output = to_markdown(&raw, &meta, "doc123", &MarkdownOptions::default()).unwrap();
+
+        assert!(output.body.contains("# Untitled Meeting"));
+        assert!(output.body.contains("_No transcript content available._"));
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::*;
+    use crate::model::TranscriptEntry;
+
+    #[test]
+    fn test_markdown_output_snapshot() {
+        let raw = RawTranscript {
+            entries: vec![
+                TranscriptEntry {
+                    document_id: Some("doc456".into()),
+                    speaker: Some("Alice".into()),
+                    start: Some("2025-10-28T15:05:10.000Z".into()),
+                    end: Some("2025-10-28T15:05:15.000Z".into()),
+                    text: "First thought.".into(),
+                    source: Some("microphone".into()),
+                    id: Some("entry1".into()),
+                    is_final: Some(true),
+                    confidence: None,
+                    words: vec![],
+                },
+                TranscriptEntry {
+                    document_id: Some("doc456".into()),
+                    speaker: Some("Alice".into()),
+                    start: Some("2025-10-28T15:05:16.000Z".into()),
+                    end: Some("2025-10-28T15:05:20.000Z".into()),
+                    text: "Second thought.".into(),
+                    source: Some("microphone".into()),
+                    id: Some("entry2".into()),
+                    is_final: Some(true),
+                    confidence: None,
+                    words: vec![],
+                },
+            ],
+        };
+
+        let meta = DocumentMetadata {
+            id: Some("doc456".into()),
+            title: Some("Planning Session".into()),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            updated_at: Some("2025-10-29T01:23:45Z".parse().unwrap()),
+            participants: vec!["Alice".into(), "Bob".into()],
+            duration_seconds: Some(3170),
+            labels: vec!["Planning".into()],
+            calendar_event: None,
+        };
+
+        let output = to_markdown(&raw, &meta, "doc456", &MarkdownOptions::default()).unwrap();
+        let full = format!("---\n{}---\n\n{}", output.frontmatter_yaml, output.body);
+
+        insta::assert_snapshot!(full);
+    }
+}
@@ -0,0 +1,380 @@
+// ABOUTME: Finds transcripts with identical content, which Granola creates when a meeting reconnects
+// ABOUTME: Duplicates are hidden from search or merged into the earliest copy of the meeting
+
+use crate::output::Row;
+use crate::storage::{read_frontmatter, write_frontmatter, Catalog, CatalogEntry};
+use crate::{Error, Paths, RawTranscript, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Hash of a transcript's words, or `None` when it has none
+///
+/// Speakers, timestamps, case and punctuation are left out, so two recordings
+/// of one meeting that differ only in labels or timing hash the same.
+pub fn content_hash(raw: &RawTranscript) -> Option<String> {
+    // 128-bit FNV-1a keeps hashes stable across Rust versions and platforms
+    let mut hash: u128 = 0x6c62272e07bb014262b821756295c58d;
+    let mut feed = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= byte as u128;
+            hash = hash.wrapping_mul(0x0000000001000000000000000000013b);
+        }
+    };
+
+    let mut empty = true;
+    let words = raw
+        .entries
+        .iter()
+        .filter(|entry| entry.is_final != Some(false))
+        .flat_map(|entry| entry.text.split(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty());
+    for word in words {
+        if !empty {
+            feed(b" ");
+        }
+        feed(word.to_lowercase().as_bytes());
+        empty = false;
+    }
+
+    (!empty).then(|| format!("{:032x}", hash))
+}
+
+/// Transcripts sharing one content hash
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    pub content_hash: String,
+    /// The copy that is kept: the earliest one not already marked as a duplicate
+    pub canonical: CatalogEntry,
+    /// The other copies, oldest first
+    pub duplicates: Vec<CatalogEntry>,
+}
+
+/// One copy of a duplicated meeting, as listed by `muesli dedupe`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateRow {
+    pub doc_id: String,
+    pub date: String,
+    pub title: Option<String>,
+    /// "keep" for the canonical copy, "duplicate" or "hidden" for the others
+    pub status: &'static str,
+    pub duplicate_of: Option<String>,
+}
+
+impl Row for DuplicateRow {
+    fn columns() -> &'static [&'static str] {
+        &["doc_id", "date", "title", "status", "duplicate_of"]
+    }
+
+    fn value(&self, column: &str) -> Value {
+        match column {
+            "doc_id" => Value::from(self.doc_id.clone()),
+            "date" => Value::from(self.date.clone()),
+            "title" => Value::from(self.title.clone()),
+            "status" => Value::from(self.status),
+            "duplicate_of" => Value::from(self.duplicate_of.clone()),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Every copy in each group, canonical copy first
+pub fn rows(groups: &[DuplicateGroup]) -> Vec<DuplicateRow> {
+    let row = |entry: &CatalogEntry, status, duplicate_of: Option<&String>| DuplicateRow {
+        doc_id: entry.doc_id.clone(),
+        date: entry.created_at.format("%Y-%m-%d %H:%M").to_string(),
+        title: entry.title.clone(),
+        status,
+        duplicate_of: duplicate_of.cloned(),
+    };
+
+    let mut rows = Vec::new();
+    for group in groups {
+        rows.push(row(&group.canonical, "keep", None));
+        for duplicate in &group.duplicates {
+            let status = if duplicate.duplicate_of.is_some() {
+                "hidden"
+            } else {
+                "duplicate"
+            };
+            rows.push(row(duplicate, status, Some(&group.canonical.doc_id)));
+        }
+    }
+    rows
+}
+
+/// What `muesli dedupe` does with the duplicates it finds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// Keep the files but leave them out of search and embeddings
+    Hide,
+    /// Delete the files and never download the document again
+    Merge,
+}
+
+/// Duplicates handled by [`resolve`]
+#[derive(Debug, Default)]
+pub struct DedupeReport {
+    pub hidden: usize,
+    pub merged: usize,
+}
+
+/// Every group of two or more transcripts with the same content
+///
+/// Transcripts synced before hashes were recorded are hashed from their raw
+/// JSON; ones without raw JSON are left out.
+pub fn find_duplicates(paths: &Paths) -> Result<Vec<DuplicateGroup>> {
+    let mut groups: Vec<(String, Vec<CatalogEntry>)> = Vec::new();
+    let mut by_hash: HashMap<String, usize> = HashMap::new();
+
+    // Oldest first, so each group lists the earliest copy first
+    for entry in Catalog::open(paths)?.entries()? {
+        let hash = match &entry.content_hash {
+            Some(hash) => hash.clone(),
+            None => match hash_raw_file(paths, &entry.path) {
+                Some(hash) => hash,
+                None => continue,
+            },
+        };
+        match by_hash.get(&hash) {
+            Some(&i) => groups[i].1.push(entry),
+            None => {
+                by_hash.insert(hash.clone(), groups.len());
+                groups.push((hash, vec![entry]));
+            }
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, entries)| entries.len() > 1)
+        .map(|(content_hash, mut entries)| {
+            let keep = entries
+                .iter()
+                .position(|e| e.duplicate_of.is_none())
+                .unwrap_or(0);
+            let canonical = entries.remove(keep);
+            DuplicateGroup {
+                content_hash,
+                canonical,
+                duplicates: entries,
+            }
+        })
+        .collect())
+}
+
+fn hash_raw_file(paths: &Paths, md_path: &Path) -> Option<String> {
+    let stem = md_path.file_stem()?.to_string_lossy();
+    let json = std::fs::read_to_string(paths.raw_dir.join(format!("{}.json", stem))).ok()?;
+    content_hash(&serde_json::from_str(&json).ok()?)
+}
+
+/// Hides or merges the duplicates in each group, keeping the canonical copy
+///
+/// Either way the duplicates leave the search index and vector store. Merging
+/// also deletes their files and records them in the canonical transcript's
+/// frontmatter, so sync does not download them again.
+pub fn resolve(
+    paths: &Paths,
+    groups: &[DuplicateGroup],
+    resolution: Resolution,
+) -> Result<DedupeReport> {
+    let catalog = Catalog::open(paths)?;
+    let mut report = DedupeReport::default();
+    let mut removed = Vec::new();
+
+    for group in groups {
+        let canonical_id = &group.canonical.doc_id;
+        match resolution {
+            Resolution::Hide => {
+                for duplicate in &group.duplicates {
+                    if duplicate.duplicate_of.as_ref() == Some(canonical_id) {
+                        continue;
+                    }
+                    let mut fm = frontmatter(&duplicate.path)?;
+                    fm.duplicate_of = Some(canonical_id.clone());
+                    write_frontmatter(&duplicate.path, &fm, &paths.tmp_dir)?;
+                    catalog.record_file(&duplicate.path)?;
+                    report.hidden += 1;
+                }
+            }
+            Resolution::Merge => {
+                let mut fm = frontmatter(&group.canonical.path)?;
+                for duplicate in &group.duplicates {
+                    if !fm.merged_duplicates.contains(&duplicate.doc_id) {
+                        fm.merged_duplicates.push(duplicate.doc_id.clone());
+                    }
+                }
+                write_frontmatter(&group.canonical.path, &fm, &paths.tmp_dir)?;
+                catalog.record_file(&group.canonical.path)?;
+
+                for duplicate in &group.duplicates {
+                    let filename = duplicate.path.file_stem().unwrap_or_default();
+                    crate::sync::remove_document(
+                        paths,
+                        &duplicate.doc_id,
+                        &filename.to_string_lossy(),
+                    )?;
+                    report.merged += 1;
+                }
+            }
+        }
+        removed.extend(group.duplicates.iter().map(|d| d.doc_id.as_str()));
+    }
+
+    unindex(paths, &removed)?;
+    Ok(report)
+}
+
+fn frontmatter(path: &Path) -> Result<crate::Frontmatter> {
+    read_frontmatter(path)?
+        .ok_or_else(|| Error::InvalidInput(format!("{} has no frontmatter", path.display())))
+}
+
+/// Drops documents from the search index and vector store
+#[cfg_attr(not(feature = "index"), allow(unused_variables))]
+fn unindex(paths: &Paths, doc_ids: &[&str]) -> Result<()> {
+    if doc_ids.is_empty() {
+        return Ok(());
+    }
+
+    #[cfg(feature = "index")]
+    {
+        use crate::index::{text, writer::WriterConfig};
+
+        let index = text::create_or_open_index(&paths.index_dir)?;
+        let mut writer = WriterConfig::configured(paths)?.writer(&index)?;
+        for doc_id in doc_ids {
+            text::delete_document(&mut writer, &index, doc_id)?;
+        }
+        writer
+            .commit()
+            .map_err(|e| Error::Indexing(format!("Failed to commit index changes: {}", e)))?;
+    }
+
+    #[cfg(feature = "semantic")]
+    if paths.index_dir.join("vectors.meta.json").exists() {
+        let vector_path = paths.index_dir.join("vectors");
+        let mut store = crate::embeddings::VectorStore::load(&vector_path)?;
+        let mut changed = false;
+        for doc_id in doc_ids {
+            changed |= store.remove_document(doc_id);
+        }
+        if changed {
+            store.save(&vector_path, &paths.tmp_dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TranscriptEntry;
+    use tempfile::TempDir;
+
+    fn transcript(lines: &[(&str, &str)]) -> RawTranscript {
+        RawTranscript {
+            entries: lines
+                .iter()
+                .map(|(speaker, text)| TranscriptEntry {
+                    document_id: None,
+                    start: None,
+                    end: None,
+                    text: text.to_string(),
+                    source: None,
+                    id: None,
+                    is_final: Some(true),
+                    speaker: Some(speaker.to_string()),
+                    confidence: None,
+                    words: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_content_hash_ignores_speakers_case_and_punctuation() {
+        let a = transcript(&[("Alice", "Hello, everyone."), ("Bob", "Let's start!")]);
+        let b = transcript(&[
+            ("Speaker 1", "hello everyone"),
+            ("Speaker 2", "let s  start"),
+        ]);
+        let c = transcript(&[("Alice", "Hello everyone"), ("Bob", "Let's stop")]);
+
+        assert_eq!(content_hash(&a), content_hash(&b));
+        assert_ne!(content_hash(&a), content_hash(&c));
+        assert_eq!(content_hash(&a).unwrap().len(), 32);
+        assert_eq!(content_hash(&transcript(&[("Alice", "...")])), None);
+    }
+
+    fn write_doc(paths: &Paths, doc_id: &str, created_at: &str, text: &str) {
+        let md = paths.transcripts_dir.join(format!("{}.md", doc_id));
+        std::fs::write(
+            &md,
+            format!(
+                "---\ndoc_id: {}\nsource: granola\ncreated_at: {}\ngenerator: muesli 1.0\n---\n\n# Standup\n",
+                doc_id, created_at
+            ),
+        )
+        .unwrap();
+        let raw = transcript(&[("Alice", text)]);
+        std::fs::write(
+            paths.raw_dir.join(format!("{}.json", doc_id)),
+            serde_json::to_string(&raw).unwrap(),
+        )
+        .unwrap();
+    }
+
+    fn setup() -> (TempDir, Paths) {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        write_doc(&paths, "late", "2025-03-01T10:05:00Z", "Same meeting");
+        write_doc(&paths, "early", "2025-03-01T10:00:00Z", "Same meeting!");
+        write_doc(&paths, "other", "2025-03-02T10:00:00Z", "Another meeting");
+        (temp, paths)
+    }
+
+    #[test]
+    fn test_hide_keeps_earliest_copy() {
+        let (_temp, paths) = setup();
+
+        let groups = find_duplicates(&paths).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].canonical.doc_id, "early");
+        assert_eq!(groups[0].duplicates[0].doc_id, "late");
+
+        let report = resolve(&paths, &groups, Resolution::Hide).unwrap();
+        assert_eq!(report.hidden, 1);
+        let fm = read_frontmatter(&paths.transcripts_dir.join("late.md"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(fm.duplicate_of.as_deref(), Some("early"));
+
+        // Hidden duplicates are still found, and hiding again changes nothing
+        let groups = find_duplicates(&paths).unwrap();
+        let statuses: Vec<&str> = rows(&groups).iter().map(|r| r.status).collect();
+        assert_eq!(statuses, vec!["keep", "hidden"]);
+        let report = resolve(&paths, &groups, Resolution::Hide).unwrap();
+        assert_eq!(report.hidden, 0);
+    }
+
+    #[test]
+    fn test_merge_deletes_duplicate_and_records_it() {
+        let (_temp, paths) = setup();
+
+        let groups = find_duplicates(&paths).unwrap();
+        let report = resolve(&paths, &groups, Resolution::Merge).unwrap();
+        assert_eq!(report.merged, 1);
+
+        assert!(!paths.transcripts_dir.join("late.md").exists());
+        assert!(!paths.raw_dir.join("late.json").exists());
+        let fm = read_frontmatter(&paths.transcripts_dir.join("early.md"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(fm.merged_duplicates, vec!["late"]);
+        assert!(find_duplicates(&paths).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+// ABOUTME: One synced meeting read from disk: frontmatter, body, entries, chapters and summary
+// ABOUTME: The single place that splits a transcript file, so commands stop re-parsing it by hand
+
+use crate::convert::anchor_id;
+use crate::find::{parse_entries, Entry};
+use crate::gaps::{find_gaps, GAP_THRESHOLD_SECS};
+use crate::storage::strip_frontmatter;
+use crate::sync::summary_path;
+use crate::util::normalize_timestamp;
+use crate::{Error, Frontmatter, Paths, RawTranscript, Result};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A transcript file with typed access to its parts
+///
+/// The raw JSON and saved summary are found beside the transcript when it lives
+/// in a data directory's `transcripts/`, and are read only when asked for.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub path: PathBuf,
+    /// None for markdown without muesli's frontmatter
+    pub frontmatter: Option<Frontmatter>,
+    /// Everything after the frontmatter
+    pub body: String,
+    /// The data directory holding `transcripts/`, if the file is in one
+    data_dir: Option<PathBuf>,
+}
+
+/// A stretch of conversation between two long silences
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Chapter {
+    /// Length of the silence before it; None for the first chapter
+    pub gap_before_secs: Option<u64>,
+    pub entries: Vec<Entry>,
+}
+
+impl Document {
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(path, &fs::read_to_string(path)?)
+    }
+
+    /// A document from the content of the file at `path`, already read
+    pub fn parse(path: &Path, content: &str) -> Result<Self> {
+        // Files edited on Windows may carry CRLF line endings
+        let content = content.replace("\r\n", "\n");
+        let body = strip_frontmatter(&content);
+        let frontmatter = match content
+            .strip_prefix("---\n")
+            .and_then(|rest| rest.find("\n---\n").map(|end| &rest[..end]))
+        {
+            Some(yaml) => Some(serde_yaml::from_str(yaml).map_err(|e| {
+                Error::InvalidInput(format!(
+                    "Failed to parse frontmatter of {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?),
+            None => None,
+        };
+
+        // <data-dir>/transcripts/<name>.md
+        let data_dir = path
+            .parent()
+            .filter(|dir| dir.file_name().is_some_and(|name| name == "transcripts"))
+            .and_then(Path::parent)
+            .map(Path::to_path_buf);
+
+        Ok(Document {
+            path: path.to_path_buf(),
+            frontmatter,
+            body: body.to_string(),
+            data_dir,
+        })
+    }
+
+    fn paths(&self) -> Option<Paths> {
+        Paths::new(Some(self.data_dir.clone()?), None).ok()
+    }
+
+    /// `raw/<name>.json`, when the transcript is in a data directory
+    pub fn raw_path(&self) -> Option<PathBuf> {
+        let stem = self.path.file_stem()?.to_string_lossy();
+        Some(self.paths()?.raw_dir.join(format!("{}.json", stem)))
+    }
+
+    /// `summaries/<name>_summary.md`, when the transcript is in a data directory
+    pub fn summary_path(&self) -> Option<PathBuf> {
+        Some(summary_path(&self.paths()?, &self.path))
+    }
+
+    /// The transcript as downloaded, if its raw JSON was kept
+    pub fn raw(&self) -> Result<Option<RawTranscript>> {
+        match self.raw_path().filter(|path| path.exists()) {
+            Some(path) => Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The saved summary, if there is one
+    pub fn summary(&self) -> Result<Option<String>> {
+        match self.summary_path().filter(|path| path.exists()) {
+            Some(path) => Ok(Some(fs::read_to_string(path)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Every spoken entry, in order
+    ///
+    /// From the raw JSON when present, so merged turns and templates don't hide
+    /// entries; speakers are then as the transcriber named them, before aliases.
+    /// Without it the body is parsed like `find-in` does.
+    pub fn entries(&self) -> Result<Vec<Entry>> {
+        Ok(match self.raw()? {
+            Some(raw) => self.raw_entries(&raw),
+            None => parse_entries(&self.body),
+        })
+    }
+
+    /// The entries split at silences of two minutes or more
+    ///
+    /// Needs the raw JSON for timings; without it the whole meeting is one chapter.
+    pub fn chapters(&self) -> Result<Vec<Chapter>> {
+        let Some(raw) = self.raw()? else {
+            let entries = parse_entries(&self.body);
+            return Ok(if entries.is_empty() {
+                Vec::new()
+            } else {
+                vec![Chapter {
+                    gap_before_secs: None,
+                    entries,
+                }]
+            });
+        };
+
+        let mut gaps = find_gaps(&raw.entries, GAP_THRESHOLD_SECS)
+            .into_iter()
+            .peekable();
+        let mut chapters: Vec<Chapter> = Vec::new();
+        for (i, entry) in self.raw_entries(&raw).into_iter().enumerate() {
+            let gap = gaps.next_if(|gap| gap.before == i);
+            match chapters.last_mut() {
+                Some(chapter) if gap.is_none() => chapter.entries.push(entry),
+                _ => chapters.push(Chapter {
+                    gap_before_secs: gap.map(|gap| gap.seconds),
+                    entries: vec![entry],
+                }),
+            }
+        }
+        Ok(chapters)
+    }
+
+    /// One entry per raw entry, with the anchors the body was written with
+    fn raw_entries(&self, raw: &RawTranscript) -> Vec<Entry> {
+        let anchored = self.body.contains("<a id=\"t-") || self.body.contains("{#t-");
+        let mut anchors = HashSet::new();
+        raw.entries
+            .iter()
+            .map(|entry| {
+                let timestamp = entry.start.as_deref().and_then(normalize_timestamp);
+                Entry {
+                    speaker: entry
+                        .speaker
+                        .clone()
+                        .unwrap_or_else(|| "Speaker".to_string()),
+                    anchor: timestamp
+                        .as_deref()
+                        .filter(|_| anchored)
+                        .map(anchor_id)
+                        .filter(|id| anchors.insert(id.clone())),
+                    timestamp,
+                    text: entry.text.trim().to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TRANSCRIPT: &str = "---\ndoc_id: doc1\nsource: granola\ncreated_at: 2025-01-01T10:00:00Z\ntitle: Standup\ngenerator: muesli 1.0\n---\n\n# Standup\n\n---\n\n**Alice (10:00:00):** Morning\n";
+
+    #[test]
+    fn test_load_without_data_dir() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("standup.md");
+        fs::write(&path, TRANSCRIPT.replace('\n', "\r\n")).unwrap();
+
+        let document = Document::load(&path).unwrap();
+        assert_eq!(document.frontmatter.as_ref().unwrap().doc_id, "doc1");
+        // A horizontal rule in the body is not mistaken for the frontmatter's end
+        assert_eq!(
+            document.body,
+            "# Standup\n\n---\n\n**Alice (10:00:00):** Morning\n"
+        );
+        assert!(document.raw_path().is_none());
+        assert_eq!(document.entries().unwrap()[0].text, "Morning");
+        assert_eq!(document.chapters().unwrap().len(), 1);
+        assert_eq!(document.summary().unwrap(), None);
+
+        let plain = Document::parse(&path, "# Notes\n").unwrap();
+        assert!(plain.frontmatter.is_none());
+        assert_eq!(plain.body, "# Notes\n");
+        assert!(Document::parse(&path, "---\ndoc_id: [\n---\n").is_err());
+    }
+
+    #[test]
+    fn test_entries_and_chapters_from_raw() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let path = paths.transcripts_dir.join("2025-01-01_standup.md");
+        fs::write(&path, TRANSCRIPT).unwrap();
+        fs::write(
+            paths.raw_dir.join("2025-01-01_standup.json"),
+            r#"[
+                {"speaker": "Alice", "text": "Morning", "start_timestamp": "2025-01-01T10:00:00Z"},
+                {"speaker": "Bob", "text": "Hi", "start_timestamp": "2025-01-01T10:00:05Z"},
+                {"speaker": "Alice", "text": "Back again", "start_timestamp": "2025-01-01T10:10:00Z"}
+            ]"#,
+        )
+        .unwrap();
+        fs::write(
+            paths.summaries_dir.join("2025-01-01_standup_summary.md"),
+            "# Summary\n",
+        )
+        .unwrap();
+
+        let document = Document::load(&path).unwrap();
+        let entries = document.entries().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].speaker, "Bob");
+        assert_eq!(entries[2].timestamp.as_deref(), Some("10:10:00"));
+        assert_eq!(entries[0].anchor, None);
+
+        let chapters = document.chapters().unwrap();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].entries.len(), 2);
+        assert_eq!(chapters[1].gap_before_secs, Some(595));
+        assert_eq!(document.summary().unwrap().as_deref(), Some("# Summary\n"));
+    }
+}
@@ -0,0 +1,458 @@
+// ABOUTME: Meeting notes that arrive by email, read from an IMAP folder or .eml files
+// ABOUTME: Turns forwarded Otter/Zoom summary emails into meetings for the import pipeline
+
+use crate::config;
+use crate::imap;
+use crate::ingest::{sync_imported, ImportOptions, ImportSource, ImportedMeeting};
+use crate::model::TranscriptEntry;
+use crate::warnings::Warnings;
+use crate::{DocumentMetadata, DocumentSummary, Error, Paths, RawTranscript, Result};
+use chrono::{TimeZone, Utc};
+use mail_parser::MessageParser;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Mailbox settings, from `[email]` in config.toml
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmailConfig {
+    /// IMAP server, e.g. imap.fastmail.com; reached over TLS
+    pub host: Option<String>,
+    pub port: u16,
+    pub username: Option<String>,
+    /// The folder notes are filed into, by a mail rule or by hand
+    pub folder: String,
+    /// Environment variable holding the password, checked before the keychain
+    pub password_env: String,
+    /// Flag imported messages as read, so the next run skips them
+    pub mark_seen: bool,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        EmailConfig {
+            host: None,
+            port: 993,
+            username: None,
+            folder: "Muesli".to_string(),
+            password_env: "MUESLI_IMAP_PASSWORD".to_string(),
+            mark_seen: true,
+        }
+    }
+}
+
+impl EmailConfig {
+    /// The mailbox described by a config file's `[email]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "email")
+    }
+
+    /// The mailbox `muesli import-email` reads for this data directory
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    /// The server and user, which have no defaults
+    pub fn account(&self) -> Result<(&str, &str)> {
+        match (self.host.as_deref(), self.username.as_deref()) {
+            (Some(host), Some(username)) => Ok((host, username)),
+            _ => Err(Error::InvalidInput(
+                "Set host and username under [email] in config.toml to read notes from a mailbox"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Keychain account for the password, e.g. `imap_password:me@example.com@imap.example.com`
+    pub fn keychain_account(&self) -> Result<String> {
+        let (host, username) = self.account()?;
+        Ok(format!("imap_password:{}@{}", username, host))
+    }
+
+    /// The password from `password_env`, else the one stored with `--save-password`
+    pub fn password(&self) -> Result<String> {
+        if let Ok(password) = std::env::var(&self.password_env) {
+            return Ok(password);
+        }
+        #[cfg(feature = "keychain")]
+        if let Some(password) = crate::keychain::get(&self.keychain_account()?)? {
+            return Ok(password);
+        }
+        Err(Error::Auth(format!(
+            "No IMAP password. Set {} or store one with: muesli import-email --save-password",
+            self.password_env
+        )))
+    }
+}
+
+/// Header lines mail clients put above a forwarded message's body
+const FORWARD_MARKERS: &[&str] = &["forwarded message", "original message"];
+
+/// The meeting a notes email describes
+///
+/// The document ID comes from the Message-ID, so the same email imported twice
+/// updates one meeting. For a forwarded email, the original sender and date
+/// are used, read from the header block most mail clients quote. Each paragraph
+/// of the text body becomes an untimed entry by its sender.
+pub fn parse_email(raw: &[u8], options: &ImportOptions) -> Result<ImportedMeeting> {
+    let message = MessageParser::default()
+        .parse(raw)
+        .filter(|m| m.subject().is_some() || m.from().is_some())
+        .ok_or_else(|| Error::InvalidInput("not an email message".to_string()))?;
+    let body = message
+        .body_text(0)
+        .map(|b| b.replace("\r\n", "\n"))
+        .unwrap_or_default();
+    let (forwarded, note, original) = split_forwarded(&body);
+
+    let sender = message
+        .from()
+        .and_then(|f| f.first())
+        .and_then(|a| a.name().or(a.address()))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let author = forwarded.from.clone().or_else(|| sender.clone());
+    let sent = forwarded
+        .date
+        .or_else(|| message.date().map(|d| d.to_timestamp()))
+        .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single());
+    let created_at = match options.date.and_then(|d| d.and_hms_opt(0, 0, 0)) {
+        Some(midnight) => Utc.from_utc_datetime(&midnight),
+        None => sent.ok_or_else(|| Error::InvalidInput("email has no date".to_string()))?,
+    };
+
+    let key = match message.message_id() {
+        Some(id) => id.as_bytes().to_vec(),
+        None => raw.to_vec(),
+    };
+    let digest = format!("{:x}", Sha256::digest(key));
+    let doc_id = format!("email-{}", &digest[..16]);
+    let title = options
+        .title
+        .clone()
+        .or_else(|| forwarded.subject.or(message.subject().map(String::from)))
+        .map(|s| strip_reply_prefixes(&s).to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "Meeting notes".to_string());
+
+    // What the forwarder wrote above the original is theirs
+    let entries = paragraphs(&note)
+        .into_iter()
+        .map(|text| (sender.clone(), text))
+        .chain(
+            paragraphs(&original)
+                .into_iter()
+                .map(|text| (author.clone(), text)),
+        )
+        .map(|(speaker, text)| TranscriptEntry {
+            document_id: Some(doc_id.clone()),
+            start: None,
+            end: None,
+            text,
+            source: Some("email".to_string()),
+            id: None,
+            is_final: Some(true),
+            speaker,
+            confidence: None,
+            words: Vec::new(),
+        })
+        .collect();
+    let now = Utc::now();
+    Ok(ImportedMeeting {
+        source: "email".to_string(),
+        summary: DocumentSummary {
+            id: doc_id.clone(),
+            title: Some(title.clone()),
+            created_at,
+            updated_at: Some(now),
+        },
+        metadata: DocumentMetadata {
+            id: Some(doc_id),
+            title: Some(title),
+            created_at,
+            updated_at: Some(now),
+            participants: Vec::new(),
+            duration_seconds: None,
+            labels: Vec::new(),
+            calendar_event: None,
+        },
+        transcript: RawTranscript { entries },
+    })
+}
+
+/// The meeting in an .eml file
+pub fn read_eml(path: &Path, options: &ImportOptions) -> Result<ImportedMeeting> {
+    parse_email(&std::fs::read(path)?, options)
+        .map_err(|e| Error::InvalidInput(format!("{}: {}", path.display(), e)))
+}
+
+/// What a forwarded message's quoted header block says about the original
+#[derive(Debug, Default, PartialEq)]
+struct Forwarded {
+    from: Option<String>,
+    /// Seconds since the epoch
+    date: Option<i64>,
+    subject: Option<String>,
+}
+
+/// The quoted headers of a forwarded message, the text above them and the original below
+fn split_forwarded(body: &str) -> (Forwarded, String, String) {
+    let lines: Vec<&str> = body.lines().collect();
+    let Some(marker) = lines.iter().position(|line| {
+        let line = line.to_lowercase();
+        FORWARD_MARKERS.iter().any(|m| line.contains(m))
+    }) else {
+        return (Forwarded::default(), body.to_string(), String::new());
+    };
+
+    let mut forwarded = Forwarded::default();
+    let mut end = marker + 1;
+    // Headers run to the next blank line; Apple Mail leaves one right after the marker
+    if lines.get(end).is_some_and(|line| line.trim().is_empty()) {
+        end += 1;
+    }
+    while end < lines.len() && !lines[end].trim().is_empty() {
+        if let Some((name, value)) = lines[end].split_once(':') {
+            let value = value.trim();
+            match name.trim().to_lowercase().as_str() {
+                "from" => forwarded.from = Some(display_name(value)),
+                "date" | "sent" => forwarded.date = parse_date(value),
+                "subject" => forwarded.subject = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        end += 1;
+    }
+    (
+        forwarded,
+        lines[..marker].join("\n"),
+        lines[end..].join("\n"),
+    )
+}
+
+/// `Otter.ai <no-reply@otter.ai>` as `Otter.ai`; a bare address as itself
+fn display_name(from: &str) -> String {
+    match from.split_once('<') {
+        Some((name, _)) if !name.trim().is_empty() => name.trim().trim_matches('"').to_string(),
+        Some((_, address)) => address.trim_end_matches('>').trim().to_string(),
+        None => from.to_string(),
+    }
+}
+
+/// An RFC 2822 date, or Gmail's `Tue, Mar 4, 2025 at 10:02 AM` (taken as UTC)
+fn parse_date(value: &str) -> Option<i64> {
+    if let Some(date) = mail_parser::DateTime::parse_rfc822(value) {
+        return Some(date.to_timestamp());
+    }
+    let value = value.replace(" at ", " ").replace('\u{202f}', " ");
+    [
+        "%a, %b %d, %Y %I:%M %p",
+        "%b %d, %Y %I:%M %p",
+        "%a, %d %b %Y %H:%M",
+    ]
+    .iter()
+    .find_map(|format| chrono::NaiveDateTime::parse_from_str(value.trim(), format).ok())
+    .map(|date| date.and_utc().timestamp())
+}
+
+/// The subject without any `Re:`/`Fwd:`/`Fw:` in front
+fn strip_reply_prefixes(subject: &str) -> &str {
+    let mut subject = subject.trim();
+    loop {
+        let lower = subject.to_lowercase();
+        let Some(prefix) = ["fwd:", "fw:", "re:"]
+            .iter()
+            .find(|p| lower.starts_with(**p))
+        else {
+            return subject;
+        };
+        subject = subject[prefix.len()..].trim_start();
+    }
+}
+
+/// Blank-line separated paragraphs, skipping quoted replies
+fn paragraphs(body: &str) -> Vec<String> {
+    body.split("\n\n")
+        .map(|paragraph| {
+            paragraph
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.trim_start().starts_with('>'))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string()
+        })
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect()
+}
+
+/// Imports the notes emails in the configured folder and files them like synced meetings
+///
+/// Reads unread messages, or every message with `all`. Messages are flagged as
+/// read only after they are written and indexed, so a failed run is retried in
+/// full. Messages that aren't readable notes are skipped with a warning.
+pub async fn import_folder(
+    paths: &Paths,
+    config: &EmailConfig,
+    all: bool,
+) -> Result<Vec<DocumentSummary>> {
+    let (host, username) = config.account()?;
+    let password = config.password()?;
+    let mut session = imap::connect(host, config.port)?;
+    session.login(username, &password)?;
+    session.select(&config.folder)?;
+
+    let mut warnings = Warnings::new(paths, "import-email");
+    let mut meetings: Vec<ImportedMeeting> = Vec::new();
+    let mut read = Vec::new();
+    for uid in session.search(if all { "ALL" } else { "UNSEEN" })? {
+        let Some(raw) = session.fetch(uid)? else {
+            continue;
+        };
+        match parse_email(&raw, &ImportOptions::default()) {
+            Ok(meeting) => {
+                // The same notes filed twice: the later copy wins
+                meetings.retain(|m| m.summary.id != meeting.summary.id);
+                meetings.push(meeting);
+                read.push(uid);
+            }
+            Err(e) => warnings.warn("Skipped email", format!("UID {}: {}", uid, e)),
+        }
+    }
+    warnings.finish();
+
+    let imported = if meetings.is_empty() {
+        Vec::new()
+    } else {
+        sync_imported(paths, ImportSource::from_meetings(meetings)?).await?
+    };
+    if config.mark_seen {
+        session.mark_seen(&read)?;
+    }
+    session.logout()?;
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FORWARDED: &str = "Message-ID: <fwd-1@mail.example.com>\r\n\
+        From: Me <me@example.com>\r\n\
+        To: notes@example.com\r\n\
+        Subject: Fwd: Otter.ai notes: Weekly pricing sync\r\n\
+        Date: Wed, 5 Mar 2025 09:00:00 +0000\r\n\
+        Content-Type: text/plain; charset=utf-8\r\n\
+        \r\n\
+        FYI\r\n\
+        \r\n\
+        ---------- Forwarded message ---------\r\n\
+        From: Otter.ai <no-reply@otter.ai>\r\n\
+        Date: Tue, Mar 4, 2025 at 4:30 PM\r\n\
+        Subject: Otter.ai notes: Weekly pricing sync\r\n\
+        To: <me@example.com>\r\n\
+        \r\n\
+        Summary\r\n\
+        The team agreed to raise the Pro price in April.\r\n\
+        \r\n\
+        Action items\r\n\
+        - Dana: update the pricing page\r\n\
+        \r\n\
+        > quoted reply\r\n";
+
+    #[test]
+    fn test_parse_forwarded_email() {
+        let meeting = parse_email(FORWARDED.as_bytes(), &ImportOptions::default()).unwrap();
+        assert!(meeting.summary.id.starts_with("email-"));
+        assert_eq!(meeting.summary.id.len(), "email-".len() + 16);
+        assert_eq!(
+            meeting.summary.title.as_deref(),
+            Some("Otter.ai notes: Weekly pricing sync")
+        );
+        assert_eq!(
+            meeting.metadata.created_at.to_rfc3339(),
+            "2025-03-04T16:30:00+00:00"
+        );
+
+        let entries = &meeting.transcript.entries;
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "FYI",
+                "Summary\nThe team agreed to raise the Pro price in April.",
+                "Action items\n- Dana: update the pricing page",
+            ]
+        );
+        assert_eq!(entries[0].speaker.as_deref(), Some("Me"));
+        assert!(entries[1..]
+            .iter()
+            .all(|e| e.speaker.as_deref() == Some("Otter.ai") && e.start.is_none()));
+
+        // Same Message-ID, same meeting
+        let again = parse_email(FORWARDED.as_bytes(), &ImportOptions::default()).unwrap();
+        assert_eq!(again.summary.id, meeting.summary.id);
+    }
+
+    #[test]
+    fn test_parse_direct_email() {
+        let raw = "From: \"Zoom\" <no-reply@zoom.us>\r\n\
+            Subject: Meeting summary for Design review\r\n\
+            Date: Thu, 6 Mar 2025 18:15:00 +0100\r\n\
+            \r\n\
+            Quick recap\r\n";
+        let options = ImportOptions {
+            title: None,
+            date: "2025-03-07".parse().ok(),
+        };
+        let meeting = parse_email(raw.as_bytes(), &options).unwrap();
+        assert_eq!(
+            meeting.summary.title.as_deref(),
+            Some("Meeting summary for Design review")
+        );
+        assert_eq!(
+            meeting.metadata.created_at.to_rfc3339(),
+            "2025-03-07T00:00:00+00:00"
+        );
+        assert_eq!(
+            meeting.transcript.entries[0].speaker.as_deref(),
+            Some("Zoom")
+        );
+
+        assert!(parse_email(b"just some text", &ImportOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_strip_reply_prefixes() {
+        assert_eq!(strip_reply_prefixes("Fwd: RE: fw: Notes"), "Notes");
+        assert_eq!(strip_reply_prefixes("Refund policy"), "Refund policy");
+    }
+
+    #[test]
+    fn test_config() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        let config = EmailConfig::load(&path).unwrap();
+        assert_eq!(config, EmailConfig::default());
+        assert_eq!(config.port, 993);
+        assert!(config.account().is_err());
+
+        std::fs::write(
+            &path,
+            "[email]\nhost = \"imap.example.com\"\nusername = \"me@example.com\"\nfolder = \"Notes\"\n",
+        )
+        .unwrap();
+        let config = EmailConfig::load(&path).unwrap();
+        assert_eq!(config.folder, "Notes");
+        assert_eq!(
+            config.keychain_account().unwrap(),
+            "imap_password:me@example.com@imap.example.com"
+        );
+
+        std::fs::write(&path, "[email]\nhots = \"typo\"\n").unwrap();
+        assert!(EmailConfig::load(&path).is_err());
+    }
+}
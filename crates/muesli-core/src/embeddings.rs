@@ -0,0 +1,189 @@
+// ABOUTME: Local embedding engine using ONNX Runtime or tract
+// ABOUTME: Runs the configured embedding model (e5-small-v2 by default)
+
+#[cfg(all(
+    feature = "semantic",
+    not(any(feature = "embeddings", feature = "embeddings-tract"))
+))]
+compile_error!("the 'semantic' feature needs a backend: enable 'embeddings' or 'embeddings-tract'");
+
+#[cfg(feature = "semantic")]
+pub mod engine;
+
+#[cfg(feature = "embeddings")]
+mod ort_backend;
+
+#[cfg(all(feature = "embeddings-tract", not(feature = "embeddings")))]
+mod tract_backend;
+
+#[cfg(feature = "semantic")]
+pub mod hnsw;
+
+#[cfg(feature = "semantic")]
+pub mod vector;
+
+#[cfg(feature = "semantic")]
+pub mod downloader;
+
+#[cfg(feature = "semantic")]
+pub mod models;
+
+#[cfg(feature = "semantic")]
+pub use downloader::{ensure_model, ModelPaths};
+
+#[cfg(feature = "semantic")]
+pub use engine::{Embedder, EmbeddingEngine};
+
+#[cfg(feature = "semantic")]
+pub use vector::VectorStore;
+
+#[cfg(feature = "semantic")]
+use crate::{
+    index::text::SearchFilters,
+    search::{DocKind, SearchHit},
+    storage::Paths,
+    Result,
+};
+
+/// Candidates fetched per requested hit when results are post-filtered
+#[cfg(feature = "semantic")]
+const FILTER_OVERFETCH: usize = 10;
+
+/// Perform semantic search using embeddings
+#[cfg(feature = "semantic")]
+pub fn semantic_search(paths: &Paths, query: &str, top_k: usize) -> Result<Vec<SearchHit>> {
+    semantic_search_filtered(paths, query, top_k, &SearchFilters::default())
+}
+
+/// Semantic search restricted by filters, checked against catalog metadata
+///
+/// Vectors carry no metadata, so extra candidates are fetched and filtered
+/// afterwards; very selective filters may return fewer than `top_k` hits.
+#[cfg(feature = "semantic")]
+pub fn semantic_search_filtered(
+    paths: &Paths,
+    query: &str,
+    top_k: usize,
+    filters: &SearchFilters,
+) -> Result<Vec<SearchHit>> {
+    use crate::storage::Catalog;
+
+    // Load the embedding engine
+    let load_span = crate::perf::span("search.load_model");
+    let model_paths = downloader::ensure_model(&paths.models_dir, models::configured(paths)?)?;
+    let mut engine = engine::EmbeddingEngine::new(&model_paths)?;
+    drop(load_span);
+
+    // Generate query embedding
+    let query_vec = {
+        let _span = crate::perf::span("search.parse");
+        engine.embed_query(query)?
+    };
+
+    let retrieve_span = crate::perf::span("search.retrieve");
+    // Load vector store
+    let vector_path = paths.index_dir.join("vectors");
+    let mut vector_store = vector::VectorStore::load(&vector_path)?;
+    // Query vectors from another model would score against the store meaninglessly
+    vector_store.pin_model(&model_paths.id)?;
+
+    // Perform search
+    let candidates = if filters.is_empty() {
+        top_k
+    } else {
+        top_k.saturating_mul(FILTER_OVERFETCH)
+    };
+    let raw_results = vector_store.search(&query_vec, candidates)?;
+    drop(retrieve_span);
+    let _span = crate::perf::span("search.assemble");
+
+    let catalog = Catalog::open(paths)?;
+    let mut results = Vec::new();
+
+    for (doc_id, score) in raw_results {
+        // Vectors can outlive their transcript; keep the hit with minimal info
+        let entry = catalog.lookup(&doc_id)?;
+        if !filters.is_empty() && !entry.as_ref().is_some_and(|e| filters.matches(e)) {
+            continue;
+        }
+
+        let result = match entry {
+            Some(entry) => SearchHit {
+                doc_id,
+                title: entry.title,
+                date: entry.created_at.format("%Y-%m-%d").to_string(),
+                path: entry.path.display().to_string(),
+                score,
+                kind: DocKind::Transcript,
+                anchor: None,
+                snippet: None,
+            },
+            None => SearchHit {
+                doc_id,
+                title: None,
+                date: "unknown".to_string(),
+                path: "unknown".to_string(),
+                score,
+                kind: DocKind::Transcript,
+                anchor: None,
+                snippet: None,
+            },
+        };
+        results.push(result);
+        if results.len() == top_k {
+            break;
+        }
+    }
+
+    Ok(results)
+}
+
+/// Hybrid search: filtered BM25 and semantic hits fused by `weights.semantic_weight`
+#[cfg(feature = "semantic")]
+pub fn hybrid_search(
+    paths: &Paths,
+    query: &str,
+    top_k: usize,
+    filters: &SearchFilters,
+    weights: &crate::index::weights::SearchWeights,
+) -> Result<Vec<SearchHit>> {
+    use crate::index::{text, weights::fuse};
+    use std::collections::HashMap;
+
+    // Extra candidates from each side give the two rankings a chance to overlap
+    let candidates = top_k.saturating_mul(3);
+
+    let index = text::open_for_search(&paths.index_dir)?;
+    let lexical = text::search_weighted(&index, query, candidates, filters, weights)?;
+    let semantic = semantic_search_filtered(paths, query, candidates, filters)?;
+
+    let lexical_scores: Vec<(String, f32)> = lexical
+        .iter()
+        .map(|r| (r.doc_id.clone(), r.score))
+        .collect();
+    let semantic_scores: Vec<(String, f32)> = semantic
+        .iter()
+        .map(|r| (r.doc_id.clone(), r.score))
+        .collect();
+
+    // Text hits always have real metadata, so they win over semantic placeholders
+    let mut details: HashMap<String, SearchHit> = semantic
+        .into_iter()
+        .map(|r| (r.doc_id.clone(), r))
+        .collect();
+    for r in lexical {
+        details.insert(r.doc_id.clone(), r);
+    }
+
+    Ok(
+        fuse(&lexical_scores, &semantic_scores, weights.semantic_weight)
+            .into_iter()
+            .take(top_k)
+            .filter_map(|(doc_id, score)| {
+                details
+                    .remove(&doc_id)
+                    .map(|hit| SearchHit { score, ..hit })
+            })
+            .collect(),
+    )
+}
@@ -0,0 +1,210 @@
+// ABOUTME: Automatic downloader for the configured ONNX embedding model
+// ABOUTME: Downloads from HuggingFace and caches in XDG data directory
+
+use super::models::EmbeddingModel;
+use crate::{term, Error, Result};
+use std::fs;
+use std::io::{BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Identifies the exact model that produced a set of vectors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelId {
+    pub name: String,
+    /// FNV-1a hash of the ONNX file, so a re-published model under the same name is caught
+    pub hash: String,
+}
+
+impl std::fmt::Display for ModelId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.hash)
+    }
+}
+
+pub struct ModelPaths {
+    pub model_path: PathBuf,
+    pub tokenizer_path: PathBuf,
+    pub id: ModelId,
+    pub model: &'static EmbeddingModel,
+}
+
+/// Downloads the model and tokenizer unless they are already cached
+pub fn ensure_model(models_dir: &Path, model: &'static EmbeddingModel) -> Result<ModelPaths> {
+    let model_path = models_dir.join(format!("{}.onnx", model.name));
+    let tokenizer_path = models_dir.join(format!("{}-tokenizer.json", model.name));
+
+    if model_path.exists() && tokenizer_path.exists() {
+        let id = model_id(&model_path, model.name)?;
+        return Ok(ModelPaths {
+            model_path,
+            tokenizer_path,
+            id,
+            model,
+        });
+    }
+
+    println!(
+        "{}Downloading {} embedding model (first time only)...",
+        term::emoji("🔽 "),
+        model.name
+    );
+
+    if !model_path.exists() {
+        // A hash cached for a previous download no longer applies
+        let _ = fs::remove_file(hash_path(&model_path));
+        download_file(model.model_url, &model_path, "model.onnx")?;
+    }
+
+    if !tokenizer_path.exists() {
+        download_file(model.tokenizer_url, &tokenizer_path, "tokenizer.json")?;
+    }
+
+    println!("{}Model downloaded successfully", term::emoji("✅ "));
+
+    let id = model_id(&model_path, model.name)?;
+    Ok(ModelPaths {
+        model_path,
+        tokenizer_path,
+        id,
+        model,
+    })
+}
+
+fn hash_path(model_path: &Path) -> PathBuf {
+    model_path.with_extension("onnx.hash")
+}
+
+/// Name and content hash of the model, hashing the file once and caching the result
+fn model_id(model_path: &Path, name: &str) -> Result<ModelId> {
+    let cache = hash_path(model_path);
+    let hash = match fs::read_to_string(&cache) {
+        Ok(hash) if !hash.trim().is_empty() => hash.trim().to_string(),
+        _ => {
+            let hash = hash_file(model_path)?;
+            // Best effort: without the cache the file is simply hashed again next time
+            let _ = fs::write(&cache, &hash);
+            hash
+        }
+    };
+
+    Ok(ModelId {
+        name: name.to_string(),
+        hash,
+    })
+}
+
+/// 64-bit FNV-1a of a file's contents, as 16 hex digits
+fn hash_file(path: &Path) -> Result<String> {
+    let mut reader = BufReader::new(fs::File::open(path)?);
+    let mut buf = [0u8; 64 * 1024];
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &buf[..read] {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    Ok(format!("{:016x}", hash))
+}
+
+fn download_file(url: &str, dest: &Path, display_name: &str) -> Result<()> {
+    crate::offline::ensure_online(&format!("downloading {}", display_name))?;
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()?;
+
+    let response = client.get(url).send()?;
+
+    if !response.status().is_success() {
+        return Err(Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "Failed to download {}: HTTP {}",
+                display_name,
+                response.status()
+            ),
+        )));
+    }
+
+    let total_size = response.content_length().unwrap_or(0);
+
+    let pb = if total_size > 0 {
+        let pb = term::progress_bar(
+            total_size,
+            "{msg} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})",
+            "#>-",
+        );
+        pb.set_message(format!("Downloading {}", display_name));
+        Some(pb)
+    } else {
+        println!("Downloading {} (size unknown)...", display_name);
+        None
+    };
+
+    let mut file = fs::File::create(dest)?;
+    let mut downloaded: u64 = 0;
+
+    let bytes = response.bytes()?;
+
+    file.write_all(&bytes)?;
+    downloaded += bytes.len() as u64;
+
+    if let Some(pb) = pb {
+        pb.set_position(downloaded);
+        pb.finish_with_message(format!("Downloaded {}", display_name));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings::models::{self, DEFAULT_MODEL};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ensure_model_creates_paths() {
+        let temp = TempDir::new().unwrap();
+        let models_dir = temp.path().join("models");
+        fs::create_dir_all(&models_dir).unwrap();
+
+        let paths = ModelPaths {
+            model_path: models_dir.join("e5-small-v2.onnx"),
+            tokenizer_path: models_dir.join("e5-small-v2-tokenizer.json"),
+            id: ModelId {
+                name: DEFAULT_MODEL.to_string(),
+                hash: "0".repeat(16),
+            },
+            model: models::find(DEFAULT_MODEL).unwrap(),
+        };
+
+        assert!(paths.model_path.to_string_lossy().ends_with(".onnx"));
+        assert!(paths
+            .tokenizer_path
+            .to_string_lossy()
+            .ends_with("tokenizer.json"));
+    }
+
+    #[test]
+    fn test_model_id_hashes_once_and_caches() {
+        let temp = TempDir::new().unwrap();
+        let model_path = temp.path().join("e5-small-v2.onnx");
+        fs::write(&model_path, b"weights").unwrap();
+
+        let id = model_id(&model_path, DEFAULT_MODEL).unwrap();
+        assert_eq!(id.name, DEFAULT_MODEL);
+        assert_eq!(id.hash, hash_file(&model_path).unwrap());
+        assert_eq!(fs::read_to_string(hash_path(&model_path)).unwrap(), id.hash);
+
+        // Different contents hash differently
+        fs::write(&model_path, b"other weights").unwrap();
+        assert_ne!(hash_file(&model_path).unwrap(), id.hash);
+    }
+}
@@ -0,0 +1,244 @@
+// ABOUTME: Embedding engine for the models in the embedding registry
+// ABOUTME: Handles tokenization and mean pooling around an ONNX inference backend
+
+use super::downloader::{ModelId, ModelPaths};
+use super::models::{EmbeddingModel, Pooling};
+#[cfg(feature = "embeddings")]
+use super::ort_backend::Backend;
+#[cfg(all(feature = "embeddings-tract", not(feature = "embeddings")))]
+use super::tract_backend::Backend;
+use crate::{Error, Result};
+use std::sync::Arc;
+use tokenizers::Tokenizer;
+
+const MAX_LENGTH: usize = 512;
+
+/// A passage tokenized and truncated to the model's input length
+#[derive(Debug, Clone)]
+pub struct Tokens {
+    ids: Vec<u32>,
+    attention_mask: Vec<u32>,
+}
+
+/// Tokenizes passages apart from the engine, so another thread can prepare
+/// the next documents while the model runs
+#[derive(Clone)]
+pub struct PassageTokenizer {
+    tokenizer: Arc<Tokenizer>,
+    prefix: &'static str,
+}
+
+impl PassageTokenizer {
+    pub fn tokenize(&self, text: &str) -> Result<Tokens> {
+        tokenize(&self.tokenizer, &format!("{}{}", self.prefix, text))
+    }
+}
+
+fn tokenize(tokenizer: &Tokenizer, text: &str) -> Result<Tokens> {
+    let encoding = tokenizer
+        .encode(text, true)
+        .map_err(|e| Error::Embedding(format!("Tokenization failed: {}", e)))?;
+
+    // Truncate to max length
+    let len = encoding.get_ids().len().min(MAX_LENGTH);
+    Ok(Tokens {
+        ids: encoding.get_ids()[..len].to_vec(),
+        attention_mask: encoding.get_attention_mask()[..len].to_vec(),
+    })
+}
+
+/// Turns text into vectors for the vector store
+///
+/// Sync embeds through this trait, so tests can swap the ONNX model for
+/// [`crate::testing::FakeEmbedder`].
+pub trait Embedder: Send {
+    /// The model behind the vectors; a store only holds vectors from one model
+    fn model(&self) -> &ModelId;
+
+    fn dim(&self) -> usize;
+
+    fn embed_query(&mut self, text: &str) -> Result<Vec<f32>>;
+
+    fn embed_passage(&mut self, text: &str) -> Result<Vec<f32>>;
+}
+
+pub struct EmbeddingEngine {
+    backend: Backend,
+    tokenizer: Arc<Tokenizer>,
+    model: &'static EmbeddingModel,
+    id: ModelId,
+}
+
+impl EmbeddingEngine {
+    pub fn new(paths: &ModelPaths) -> Result<Self> {
+        // Load tokenizer
+        let tokenizer = Arc::new(
+            Tokenizer::from_file(&paths.tokenizer_path)
+                .map_err(|e| Error::Embedding(format!("Failed to load tokenizer: {}", e)))?,
+        );
+
+        let backend = Backend::load(&paths.model_path)?;
+
+        Ok(EmbeddingEngine {
+            backend,
+            tokenizer,
+            model: paths.model,
+            id: paths.id.clone(),
+        })
+    }
+
+    pub fn dim(&self) -> usize {
+        self.model.dim
+    }
+
+    pub fn embed_query(&mut self, text: &str) -> Result<Vec<f32>> {
+        // Asymmetric models (e5, bge) expect queries and passages to be marked
+        let prefixed = format!("{}{}", self.model.query_prefix, text);
+        self.embed_text(&prefixed)
+    }
+
+    pub fn embed_passage(&mut self, text: &str) -> Result<Vec<f32>> {
+        let prefixed = format!("{}{}", self.model.passage_prefix, text);
+        self.embed_text(&prefixed)
+    }
+
+    /// A tokenizer that adds the passage prefix, for use off this thread
+    pub fn passage_tokenizer(&self) -> PassageTokenizer {
+        PassageTokenizer {
+            tokenizer: Arc::clone(&self.tokenizer),
+            prefix: self.model.passage_prefix,
+        }
+    }
+
+    fn embed_text(&mut self, text: &str) -> Result<Vec<f32>> {
+        let tokens = tokenize(&self.tokenizer, text)?;
+        self.embed_tokens(&tokens)
+    }
+
+    /// Runs the model on already tokenized text
+    pub fn embed_tokens(&mut self, tokens: &Tokens) -> Result<Vec<f32>> {
+        let input_ids = &tokens.ids;
+        let attention_mask = &tokens.attention_mask;
+        let len = input_ids.len();
+
+        // Convert to i64 arrays (ONNX expects i64)
+        let input_ids_i64: Vec<i64> = input_ids.iter().map(|&id| id as i64).collect();
+        let attention_mask_i64: Vec<i64> = attention_mask.iter().map(|&mask| mask as i64).collect();
+
+        // Create token_type_ids (all zeros for single sequence)
+        let token_type_ids: Vec<i64> = vec![0; len];
+
+        let (shape, data) =
+            self.backend
+                .last_hidden_state(input_ids_i64, attention_mask_i64, token_type_ids)?;
+
+        // shape should be [1, seq_len, dim]
+        if shape.len() != 3 {
+            return Err(Error::Embedding(format!(
+                "Unexpected output shape: expected 3 dimensions, got {}",
+                shape.len()
+            )));
+        }
+
+        let batch_size = shape[0];
+        let seq_len = shape[1];
+        let hidden_dim = shape[2];
+
+        if batch_size != 1 || hidden_dim != self.model.dim {
+            return Err(Error::Embedding(format!(
+                "Unexpected output shape: got [{}, {}, {}], expected [1, {}, {}]",
+                batch_size, seq_len, hidden_dim, seq_len, self.model.dim
+            )));
+        }
+
+        let embedding = match self.model.pooling {
+            Pooling::Mean => mean_pool(&data, seq_len, hidden_dim, attention_mask)?,
+            Pooling::Cls => data[..hidden_dim].to_vec(),
+        };
+
+        // Normalize
+        Ok(normalize_vector(embedding))
+    }
+}
+
+impl Embedder for EmbeddingEngine {
+    fn model(&self) -> &ModelId {
+        &self.id
+    }
+
+    fn dim(&self) -> usize {
+        EmbeddingEngine::dim(self)
+    }
+
+    fn embed_query(&mut self, text: &str) -> Result<Vec<f32>> {
+        EmbeddingEngine::embed_query(self, text)
+    }
+
+    fn embed_passage(&mut self, text: &str) -> Result<Vec<f32>> {
+        EmbeddingEngine::embed_passage(self, text)
+    }
+}
+
+fn mean_pool(
+    data: &[f32],
+    seq_len: usize,
+    hidden_dim: usize,
+    attention_mask: &[u32],
+) -> Result<Vec<f32>> {
+    // data is flattened [1, seq_len, hidden_dim] tensor
+    // Skip first batch dimension (always 1), start at seq_len * hidden_dim
+    let mut pooled = vec![0.0f32; hidden_dim];
+    let mut mask_sum = 0.0f32;
+
+    for (i, &mask) in attention_mask.iter().enumerate().take(seq_len) {
+        if mask > 0 {
+            let offset = i * hidden_dim;
+            for j in 0..hidden_dim {
+                pooled[j] += data[offset + j];
+            }
+            mask_sum += 1.0;
+        }
+    }
+
+    if mask_sum > 0.0 {
+        for val in pooled.iter_mut() {
+            *val /= mask_sum;
+        }
+    }
+
+    Ok(pooled)
+}
+
+pub(crate) fn normalize_vector(mut vec: Vec<f32>) -> Vec<f32> {
+    let norm: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for val in vec.iter_mut() {
+            *val /= norm;
+        }
+    }
+    vec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_vector() {
+        let vec = vec![3.0, 4.0];
+        let normalized = normalize_vector(vec);
+        assert!((normalized[0] - 0.6).abs() < 0.001);
+        assert!((normalized[1] - 0.8).abs() < 0.001);
+
+        // Check unit length
+        let length: f32 = normalized.iter().map(|x| x * x).sum::<f32>().sqrt();
+        assert!((length - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_engine_dimension() {
+        // e5-small-v2 dimension
+        let model = crate::embeddings::models::find("e5-small-v2").unwrap();
+        assert_eq!(model.dim, 384);
+    }
+}
@@ -0,0 +1,321 @@
+// ABOUTME: Hierarchical Navigable Small World graph for approximate nearest neighbors
+// ABOUTME: Indexes VectorStore rows by position and is persisted next to vectors.bin
+
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+/// Links per node on upper layers (layer 0 keeps twice as many)
+const M: usize = 16;
+
+/// Candidate list size while inserting; higher builds a better graph, slower
+const EF_CONSTRUCTION: usize = 100;
+
+/// Minimum candidate list size while searching
+pub const EF_SEARCH: usize = 64;
+
+/// Cosine distance paired with a node id, ordered by distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    node: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+/// HNSW graph over fixed-size vectors stored contiguously elsewhere
+///
+/// Node `i` is the vector at `data[i * dim..(i + 1) * dim]`. The graph holds no
+/// vector data itself, so callers pass the backing slice to every operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswIndex {
+    dim: usize,
+    entry_point: Option<u32>,
+    max_level: usize,
+    /// `links[node][layer]` lists the node's neighbors on that layer
+    links: Vec<Vec<Vec<u32>>>,
+}
+
+impl HnswIndex {
+    pub fn new(dim: usize) -> Self {
+        HnswIndex {
+            dim,
+            entry_point: None,
+            max_level: 0,
+            links: Vec::new(),
+        }
+    }
+
+    /// Builds a graph over every vector in `data`
+    pub fn build(data: &[f32], dim: usize) -> Self {
+        let mut index = Self::new(dim);
+        for _ in 0..data.len() / dim {
+            index.insert(data);
+        }
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.links.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.links.is_empty()
+    }
+
+    fn vector<'a>(&self, data: &'a [f32], node: u32) -> &'a [f32] {
+        let start = node as usize * self.dim;
+        &data[start..start + self.dim]
+    }
+
+    fn distance(&self, data: &[f32], query: &[f32], node: u32) -> f32 {
+        1.0 - super::vector::cosine_similarity(query, self.vector(data, node))
+    }
+
+    /// Deterministic level for a node so rebuilds produce the same graph
+    fn level_for(node: u32) -> usize {
+        // splitmix64
+        let mut z = (node as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        let uniform = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let level_mult = 1.0 / (M as f64).ln();
+        (-uniform.ln() * level_mult).floor() as usize
+    }
+
+    fn max_links(layer: usize) -> usize {
+        if layer == 0 {
+            2 * M
+        } else {
+            M
+        }
+    }
+
+    /// Best-first search on one layer, returning up to `ef` nodes nearest first
+    fn search_layer(
+        &self,
+        data: &[f32],
+        query: &[f32],
+        entry_points: &[Candidate],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited: HashSet<u32> = entry_points.iter().map(|c| c.node).collect();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> =
+            entry_points.iter().copied().map(Reverse).collect();
+        let mut nearest: BinaryHeap<Candidate> = entry_points.iter().copied().collect();
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            let furthest = nearest.peek().map_or(f32::INFINITY, |c| c.distance);
+            if current.distance > furthest && nearest.len() >= ef {
+                break;
+            }
+
+            for &neighbor in &self.links[current.node as usize][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+
+                let candidate = Candidate {
+                    distance: self.distance(data, query, neighbor),
+                    node: neighbor,
+                };
+                let furthest = nearest.peek().map_or(f32::INFINITY, |c| c.distance);
+                if nearest.len() < ef || candidate.distance < furthest {
+                    candidates.push(Reverse(candidate));
+                    nearest.push(candidate);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+
+    /// Greedily descends from the entry point to `target_layer`
+    fn descend(&self, data: &[f32], query: &[f32], target_layer: usize) -> Option<Candidate> {
+        let entry = self.entry_point?;
+        let mut best = Candidate {
+            distance: self.distance(data, query, entry),
+            node: entry,
+        };
+
+        for layer in (target_layer + 1..=self.max_level).rev() {
+            best = self.search_layer(data, query, &[best], 1, layer)[0];
+        }
+
+        Some(best)
+    }
+
+    /// Adds the next row of `data` (node `self.len()`) to the graph
+    pub fn insert(&mut self, data: &[f32]) {
+        let node = self.links.len() as u32;
+        let level = Self::level_for(node);
+        self.links.push(vec![Vec::new(); level + 1]);
+
+        let query = self.vector(data, node);
+        let Some(entry) = self.descend(data, query, level) else {
+            self.entry_point = Some(node);
+            self.max_level = level;
+            return;
+        };
+
+        let mut entry_points = vec![entry];
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let found = self.search_layer(data, query, &entry_points, EF_CONSTRUCTION, layer);
+            let neighbors: Vec<u32> = found.iter().take(M).map(|c| c.node).collect();
+
+            for &neighbor in &neighbors {
+                self.links[neighbor as usize][layer].push(node);
+                self.shrink_links(data, neighbor, layer);
+            }
+            self.links[node as usize][layer] = neighbors;
+            entry_points = found;
+        }
+
+        if level > self.max_level {
+            self.entry_point = Some(node);
+            self.max_level = level;
+        }
+    }
+
+    /// Keeps only a node's closest links once it exceeds the per-layer limit
+    fn shrink_links(&mut self, data: &[f32], node: u32, layer: usize) {
+        let limit = Self::max_links(layer);
+        if self.links[node as usize][layer].len() <= limit {
+            return;
+        }
+
+        let base = self.vector(data, node);
+        let mut ranked: Vec<Candidate> = self.links[node as usize][layer]
+            .iter()
+            .map(|&n| Candidate {
+                distance: self.distance(data, base, n),
+                node: n,
+            })
+            .collect();
+        ranked.sort();
+        self.links[node as usize][layer] = ranked.into_iter().take(limit).map(|c| c.node).collect();
+    }
+
+    /// Approximate `top_k` nearest nodes to `query`, as (node, distance) nearest first
+    pub fn search(&self, data: &[f32], query: &[f32], top_k: usize) -> Vec<(usize, f32)> {
+        let Some(entry) = self.descend(data, query, 0) else {
+            return Vec::new();
+        };
+
+        self.search_layer(data, query, &[entry], top_k.max(EF_SEARCH), 0)
+            .into_iter()
+            .take(top_k)
+            .map(|c| (c.node as usize, c.distance))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random unit vectors
+    fn random_vectors(count: usize, dim: usize) -> Vec<f32> {
+        let mut state = 42u64;
+        let mut data = Vec::with_capacity(count * dim);
+        for _ in 0..count {
+            let row: Vec<f32> = (0..dim)
+                .map(|_| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    ((state >> 33) as f32 / (1u64 << 31) as f32) - 0.5
+                })
+                .collect();
+            let norm = row.iter().map(|x| x * x).sum::<f32>().sqrt();
+            data.extend(row.iter().map(|x| x / norm));
+        }
+        data
+    }
+
+    fn exact_top_k(data: &[f32], dim: usize, query: &[f32], k: usize) -> Vec<usize> {
+        let mut scored: Vec<(usize, f32)> = (0..data.len() / dim)
+            .map(|i| {
+                let sim =
+                    super::super::vector::cosine_similarity(query, &data[i * dim..(i + 1) * dim]);
+                (i, sim)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(i, _)| i).collect()
+    }
+
+    #[test]
+    fn test_empty_index() {
+        let index = HnswIndex::new(3);
+        assert!(index.is_empty());
+        assert!(index.search(&[], &[1.0, 0.0, 0.0], 5).is_empty());
+    }
+
+    #[test]
+    fn test_finds_exact_match() {
+        let dim = 16;
+        let data = random_vectors(500, dim);
+        let index = HnswIndex::build(&data, dim);
+        assert_eq!(index.len(), 500);
+
+        let query = &data[123 * dim..124 * dim];
+        let results = index.search(&data, query, 1);
+        assert_eq!(results[0].0, 123);
+        assert!(results[0].1.abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_recall_against_linear_scan() {
+        let dim = 32;
+        let data = random_vectors(2000, dim);
+        let queries = random_vectors(2020, dim);
+        let index = HnswIndex::build(&data, dim);
+
+        let mut hits = 0;
+        let mut total = 0;
+        for q in 2000..2020 {
+            let query = &queries[q * dim..(q + 1) * dim];
+            let exact = exact_top_k(&data, dim, query, 10);
+            let approx: Vec<usize> = index
+                .search(&data, query, 10)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect();
+            hits += exact.iter().filter(|i| approx.contains(i)).count();
+            total += exact.len();
+        }
+
+        let recall = hits as f32 / total as f32;
+        assert!(recall > 0.9, "recall too low: {}", recall);
+    }
+
+    #[test]
+    fn test_build_is_deterministic() {
+        let dim = 8;
+        let data = random_vectors(200, dim);
+        let a = serde_json::to_string(&HnswIndex::build(&data, dim)).unwrap();
+        let b = serde_json::to_string(&HnswIndex::build(&data, dim)).unwrap();
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,163 @@
+// ABOUTME: Registry of supported embedding models (dims, prefixes, pooling, URLs)
+// ABOUTME: Selects the model from [embeddings] in config.toml, defaulting to e5-small-v2
+
+use crate::{config, Error, Paths, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// How token embeddings are reduced to one sentence vector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pooling {
+    /// Average over all attended tokens
+    Mean,
+    /// The first ([CLS]) token
+    Cls,
+}
+
+/// Everything needed to download and run one embedding model
+#[derive(Debug, PartialEq, Eq)]
+pub struct EmbeddingModel {
+    /// Name used in config.toml, file names and vectors.meta.json
+    pub name: &'static str,
+    pub dim: usize,
+    pub query_prefix: &'static str,
+    pub passage_prefix: &'static str,
+    pub pooling: Pooling,
+    pub model_url: &'static str,
+    pub tokenizer_url: &'static str,
+}
+
+/// The model used when none is configured, and the one older stores were built with
+pub const DEFAULT_MODEL: &str = "e5-small-v2";
+
+/// Models muesli knows how to download and run
+pub const MODELS: &[EmbeddingModel] = &[
+    EmbeddingModel {
+        name: "e5-small-v2",
+        dim: 384,
+        query_prefix: "query: ",
+        passage_prefix: "passage: ",
+        pooling: Pooling::Mean,
+        model_url: "https://huggingface.co/intfloat/e5-small-v2/resolve/main/model.onnx",
+        tokenizer_url: "https://huggingface.co/intfloat/e5-small-v2/resolve/main/tokenizer.json",
+    },
+    EmbeddingModel {
+        name: "bge-small-en-v1.5",
+        dim: 384,
+        query_prefix: "Represent this sentence for searching relevant passages: ",
+        passage_prefix: "",
+        pooling: Pooling::Cls,
+        model_url: "https://huggingface.co/BAAI/bge-small-en-v1.5/resolve/main/onnx/model.onnx",
+        tokenizer_url: "https://huggingface.co/BAAI/bge-small-en-v1.5/resolve/main/tokenizer.json",
+    },
+    EmbeddingModel {
+        name: "bge-base-en-v1.5",
+        dim: 768,
+        query_prefix: "Represent this sentence for searching relevant passages: ",
+        passage_prefix: "",
+        pooling: Pooling::Cls,
+        model_url: "https://huggingface.co/BAAI/bge-base-en-v1.5/resolve/main/onnx/model.onnx",
+        tokenizer_url: "https://huggingface.co/BAAI/bge-base-en-v1.5/resolve/main/tokenizer.json",
+    },
+    EmbeddingModel {
+        name: "all-minilm-l6-v2",
+        dim: 384,
+        query_prefix: "",
+        passage_prefix: "",
+        pooling: Pooling::Mean,
+        model_url:
+            "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/onnx/model.onnx",
+        tokenizer_url:
+            "https://huggingface.co/sentence-transformers/all-MiniLM-L6-v2/resolve/main/tokenizer.json",
+    },
+];
+
+/// Looks up a model by name (case-insensitive)
+pub fn find(name: &str) -> Option<&'static EmbeddingModel> {
+    MODELS
+        .iter()
+        .find(|m| m.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Settings from `[embeddings]` in config.toml
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbeddingConfig {
+    pub embedding_model: String,
+}
+
+impl Default for EmbeddingConfig {
+    fn default() -> Self {
+        Self {
+            embedding_model: DEFAULT_MODEL.to_string(),
+        }
+    }
+}
+
+impl EmbeddingConfig {
+    /// The model choice in a config file's `[embeddings]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "embeddings")
+    }
+
+    /// The registry entry for the configured model
+    pub fn model(&self) -> Result<&'static EmbeddingModel> {
+        find(&self.embedding_model).ok_or_else(|| {
+            let known: Vec<&str> = MODELS.iter().map(|m| m.name).collect();
+            Error::InvalidInput(format!(
+                "Unknown embedding_model '{}' (supported: {})",
+                self.embedding_model,
+                known.join(", ")
+            ))
+        })
+    }
+}
+
+/// The model this data directory's vectors are made with
+pub fn configured(paths: &Paths) -> Result<&'static EmbeddingModel> {
+    EmbeddingConfig::load(&paths.config_file)?.model()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_registry_entries_are_usable() {
+        assert!(find(DEFAULT_MODEL).is_some());
+        for model in MODELS {
+            assert!(model.dim > 0, "{}", model.name);
+            assert!(model.model_url.starts_with("https://huggingface.co/"));
+            assert!(model.model_url.ends_with(".onnx"));
+            assert!(model.tokenizer_url.ends_with("tokenizer.json"));
+            assert_eq!(find(model.name), Some(model));
+        }
+    }
+
+    #[test]
+    fn test_config_selects_model() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            EmbeddingConfig::load(&path).unwrap().model().unwrap().name,
+            DEFAULT_MODEL
+        );
+
+        std::fs::write(
+            &path,
+            "[embeddings]\nembedding_model = \"BGE-small-en-v1.5\"\n",
+        )
+        .unwrap();
+        let model = EmbeddingConfig::load(&path).unwrap().model().unwrap();
+        assert_eq!(model.name, "bge-small-en-v1.5");
+        assert_eq!(model.pooling, Pooling::Cls);
+
+        std::fs::write(&path, "[embeddings]\nembedding_model = \"word2vec\"\n").unwrap();
+        let err = EmbeddingConfig::load(&path).unwrap().model().unwrap_err();
+        assert!(err.to_string().contains("e5-small-v2"));
+
+        std::fs::write(&path, "[embeddings]\nmodel = \"e5-small-v2\"\n").unwrap();
+        assert!(EmbeddingConfig::load(&path).is_err());
+    }
+}
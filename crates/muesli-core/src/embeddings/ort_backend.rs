@@ -0,0 +1,71 @@
+// ABOUTME: ONNX Runtime inference backend for the embedding engine
+// ABOUTME: Default backend; links libonnxruntime (downloaded at build time)
+
+use crate::{Error, Result};
+use ort::{inputs, session::Session, value::Value};
+use std::path::Path;
+
+pub struct Backend {
+    session: Session,
+}
+
+impl Backend {
+    pub fn load(model_path: &Path) -> Result<Self> {
+        // Initialize ort globally (idempotent)
+        ort::init()
+            .commit()
+            .map_err(|e| Error::Embedding(format!("Failed to initialize ort: {}", e)))?;
+
+        // Create session - read model into memory first
+        let model_bytes = std::fs::read(model_path)
+            .map_err(|e| Error::Embedding(format!("Failed to read model file: {}", e)))?;
+
+        let session = Session::builder()
+            .map_err(|e| Error::Embedding(format!("Failed to create session builder: {}", e)))?
+            .commit_from_memory(&model_bytes)
+            .map_err(|e| Error::Embedding(format!("Failed to load ONNX model: {}", e)))?;
+
+        Ok(Backend { session })
+    }
+
+    /// Runs one sequence through the model, returning `last_hidden_state` as (shape, data)
+    pub fn last_hidden_state(
+        &mut self,
+        input_ids: Vec<i64>,
+        attention_mask: Vec<i64>,
+        token_type_ids: Vec<i64>,
+    ) -> Result<(Vec<usize>, Vec<f32>)> {
+        let len = input_ids.len();
+
+        // Create Value tensors - ort 2.0 expects (shape, data) tuple
+        let input_ids_value = Value::from_array((vec![1, len], input_ids))
+            .map_err(|e| Error::Embedding(format!("Failed to create input_ids tensor: {}", e)))?;
+
+        let attention_mask_value =
+            Value::from_array((vec![1, len], attention_mask)).map_err(|e| {
+                Error::Embedding(format!("Failed to create attention_mask tensor: {}", e))
+            })?;
+
+        let token_type_ids_value =
+            Value::from_array((vec![1, len], token_type_ids)).map_err(|e| {
+                Error::Embedding(format!("Failed to create token_type_ids tensor: {}", e))
+            })?;
+
+        // Run inference using ort 2.0 API
+        let outputs = self
+            .session
+            .run(inputs![
+                "input_ids" => input_ids_value,
+                "attention_mask" => attention_mask_value,
+                "token_type_ids" => token_type_ids_value
+            ])
+            .map_err(|e| Error::Embedding(format!("ONNX inference failed: {}", e)))?;
+
+        // Extract embeddings - ort 2.0 returns (shape, data) tuple
+        let (shape, data) = outputs["last_hidden_state"]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| Error::Embedding(format!("Failed to extract output tensor: {}", e)))?;
+
+        Ok((shape.iter().map(|&d| d as usize).collect(), data.to_vec()))
+    }
+}
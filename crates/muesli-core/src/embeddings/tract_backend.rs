@@ -0,0 +1,79 @@
+// ABOUTME: Pure-Rust tract inference backend for the embedding engine
+// ABOUTME: Used by the embeddings-tract feature so binaries need no libonnxruntime
+
+use crate::{Error, Result};
+use std::path::Path;
+use tract_onnx::prelude::*;
+
+type Plan = SimplePlan<TypedFact, Box<dyn TypedOp>, TypedModel>;
+
+const INPUTS: [&str; 3] = ["input_ids", "attention_mask", "token_type_ids"];
+
+fn tract_err(context: &str, e: TractError) -> Error {
+    Error::Embedding(format!("{}: {}", context, e))
+}
+
+pub struct Backend {
+    plan: Plan,
+}
+
+impl Backend {
+    pub fn load(model_path: &Path) -> Result<Self> {
+        let mut model = tract_onnx::onnx()
+            .model_for_path(model_path)
+            .map_err(|e| tract_err("Failed to load ONNX model", e))?;
+
+        model
+            .set_input_names(INPUTS)
+            .and_then(|_| model.set_output_names(["last_hidden_state"]))
+            .map_err(|e| tract_err("Unexpected model inputs or outputs", e))?;
+
+        // One sequence at a time, of any length
+        let seq_len = model.symbol_table.sym("S");
+        for input in 0..INPUTS.len() {
+            model
+                .set_input_fact(
+                    input,
+                    InferenceFact::dt_shape(i64::datum_type(), tvec!(1.to_dim(), seq_len.to_dim())),
+                )
+                .map_err(|e| tract_err("Failed to set model input shape", e))?;
+        }
+
+        let plan = model
+            .into_optimized()
+            .and_then(|model| model.into_runnable())
+            .map_err(|e| tract_err("Failed to prepare ONNX model", e))?;
+
+        Ok(Backend { plan })
+    }
+
+    /// Runs one sequence through the model, returning `last_hidden_state` as (shape, data)
+    pub fn last_hidden_state(
+        &mut self,
+        input_ids: Vec<i64>,
+        attention_mask: Vec<i64>,
+        token_type_ids: Vec<i64>,
+    ) -> Result<(Vec<usize>, Vec<f32>)> {
+        let len = input_ids.len();
+        let tensor = |values: Vec<i64>| -> Result<TValue> {
+            let array = tract_ndarray::Array2::from_shape_vec((1, len), values)
+                .map_err(|e| Error::Embedding(format!("Failed to create input tensor: {}", e)))?;
+            Ok(array.into_tensor().into())
+        };
+
+        let outputs = self
+            .plan
+            .run(tvec!(
+                tensor(input_ids)?,
+                tensor(attention_mask)?,
+                tensor(token_type_ids)?
+            ))
+            .map_err(|e| tract_err("ONNX inference failed", e))?;
+
+        let hidden = outputs[0]
+            .to_array_view::<f32>()
+            .map_err(|e| tract_err("Failed to extract output tensor", e))?;
+
+        Ok((hidden.shape().to_vec(), hidden.iter().copied().collect()))
+    }
+}
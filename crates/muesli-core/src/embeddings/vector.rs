@@ -0,0 +1,687 @@
+// ABOUTME: Vector storage with cosine similarity search
+// ABOUTME: Searches through an HNSW graph persisted alongside vectors.bin
+
+use super::downloader::ModelId;
+use super::hnsw::HnswIndex;
+use super::models::DEFAULT_MODEL;
+use crate::storage::write_atomic;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VectorMapping {
+    pub doc_id: String,
+    pub offset: usize,
+}
+
+/// Vectors stored row-major in insertion order; row `i` belongs to `mapping[i]`
+pub struct VectorStore {
+    vectors: Vec<f32>,
+    mapping: Vec<VectorMapping>,
+    dim: usize,
+    index: HnswIndex,
+    /// Set when vectors changed under the graph; `save` rebuilds it once
+    dirty: bool,
+    /// Embedding model that produced the vectors (None until pinned, or for older stores)
+    model: Option<ModelId>,
+}
+
+impl VectorStore {
+    pub fn new(dim: usize) -> Self {
+        VectorStore {
+            vectors: Vec::new(),
+            mapping: Vec::new(),
+            dim,
+            index: HnswIndex::new(dim),
+            dirty: false,
+            model: None,
+        }
+    }
+
+    pub fn has_document(&self, doc_id: &str) -> bool {
+        self.mapping.iter().any(|m| m.doc_id == doc_id)
+    }
+
+    pub fn add_document(&mut self, doc_id: String, vector: Vec<f32>) -> Result<()> {
+        if vector.len() != self.dim {
+            return Err(Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Vector dimension mismatch: expected {}, got {}",
+                    self.dim,
+                    vector.len()
+                ),
+            )));
+        }
+
+        let offset = self.vectors.len();
+
+        self.mapping.push(VectorMapping { doc_id, offset });
+        self.vectors.extend_from_slice(&vector);
+        if !self.dirty {
+            self.index.insert(&self.vectors);
+        }
+
+        Ok(())
+    }
+
+    /// Stores a document's vector, replacing any previous one; returns true if it replaced
+    ///
+    /// A replaced vector keeps its row, so other documents' offsets are unchanged.
+    pub fn upsert_document(&mut self, doc_id: String, vector: Vec<f32>) -> Result<bool> {
+        let Some(offset) = self
+            .mapping
+            .iter()
+            .find(|m| m.doc_id == doc_id)
+            .map(|m| m.offset)
+        else {
+            self.add_document(doc_id, vector)?;
+            return Ok(false);
+        };
+
+        if vector.len() != self.dim {
+            return Err(Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Vector dimension mismatch: expected {}, got {}",
+                    self.dim,
+                    vector.len()
+                ),
+            )));
+        }
+
+        self.vectors[offset..offset + self.dim].copy_from_slice(&vector);
+        // The node's neighbors were chosen for the old vector
+        self.dirty = true;
+
+        Ok(true)
+    }
+
+    /// Removes a document's vector, compacting storage; returns false if it was absent
+    pub fn remove_document(&mut self, doc_id: &str) -> bool {
+        let Some(pos) = self.mapping.iter().position(|m| m.doc_id == doc_id) else {
+            return false;
+        };
+
+        let removed = self.mapping.remove(pos);
+        self.vectors
+            .drain(removed.offset..removed.offset + self.dim);
+
+        for mapping in &mut self.mapping {
+            if mapping.offset > removed.offset {
+                mapping.offset -= self.dim;
+            }
+        }
+
+        // Rows shifted, so graph node ids no longer line up
+        self.dirty = true;
+
+        true
+    }
+
+    /// The stored vector of a document
+    pub fn vector(&self, doc_id: &str) -> Option<&[f32]> {
+        let mapping = self.mapping.iter().find(|m| m.doc_id == doc_id)?;
+        self.vectors.get(mapping.offset..mapping.offset + self.dim)
+    }
+
+    /// Cosine similarity between a stored document and a query vector
+    pub fn similarity(&self, doc_id: &str, query_vec: &[f32]) -> Option<f32> {
+        let vector = self.vector(doc_id)?;
+        (query_vec.len() == self.dim).then(|| cosine_similarity(vector, query_vec))
+    }
+
+    pub fn search(&self, query_vec: &[f32], top_k: usize) -> Result<Vec<(String, f32)>> {
+        if query_vec.len() != self.dim {
+            return Err(Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Query vector dimension mismatch: expected {}, got {}",
+                    self.dim,
+                    query_vec.len()
+                ),
+            )));
+        }
+
+        if self.dirty {
+            // The graph is stale until the next save, so compare against every vector
+            let mut scores: Vec<(String, f32)> = self
+                .iter()
+                .map(|(doc_id, vector)| (doc_id.to_string(), cosine_similarity(vector, query_vec)))
+                .collect();
+            scores.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scores.truncate(top_k);
+            return Ok(scores);
+        }
+
+        let scores = self
+            .index
+            .search(&self.vectors, query_vec, top_k)
+            .into_iter()
+            .map(|(row, distance)| (self.mapping[row].doc_id.clone(), 1.0 - distance))
+            .collect();
+
+        Ok(scores)
+    }
+
+    /// Writes the store next to `path`, rebuilding the graph first if vectors changed
+    pub fn save(&mut self, path: &Path, tmp_dir: &Path) -> Result<()> {
+        if self.dirty {
+            self.rebuild_index();
+        }
+
+        #[derive(Serialize)]
+        struct Metadata<'a> {
+            dim: usize,
+            model: Option<&'a str>,
+            model_hash: Option<&'a str>,
+            mapping: Vec<VectorMapping>,
+        }
+
+        let metadata = Metadata {
+            dim: self.dim,
+            model: self.model.as_ref().map(|m| m.name.as_str()),
+            model_hash: self.model.as_ref().map(|m| m.hash.as_str()),
+            mapping: self.mapping.clone(),
+        };
+
+        // Metadata goes last: if a crash leaves the old mapping next to new
+        // vectors, the length check in `load` catches it
+        let vectors_bytes: Vec<u8> = self.vectors.iter().flat_map(|f| f.to_le_bytes()).collect();
+        write_atomic(&path.with_extension("vectors.bin"), &vectors_bytes, tmp_dir)?;
+        self.save_index(path, tmp_dir)?;
+
+        let metadata_json = serde_json::to_string(&metadata)?;
+        write_atomic(
+            &path.with_extension("meta.json"),
+            metadata_json.as_bytes(),
+            tmp_dir,
+        )
+    }
+
+    /// Deletes the files `save` wrote for `path`, if any
+    pub fn delete(path: &Path) -> Result<()> {
+        for extension in ["meta.json", "vectors.bin", "hnsw.json"] {
+            match fs::remove_file(path.with_extension(extension)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn save_index(&self, path: &Path, tmp_dir: &Path) -> Result<()> {
+        let index_json = serde_json::to_string(&self.index)?;
+        write_atomic(
+            &path.with_extension("hnsw.json"),
+            index_json.as_bytes(),
+            tmp_dir,
+        )
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Metadata {
+            dim: usize,
+            #[serde(default)]
+            model: Option<String>,
+            #[serde(default)]
+            model_hash: Option<String>,
+            mapping: Vec<VectorMapping>,
+        }
+
+        let metadata_path = path.with_extension("meta.json");
+        let vectors_path = path.with_extension("vectors.bin");
+
+        // Load metadata
+        let metadata_json = fs::read_to_string(&metadata_path)?;
+        let metadata: Metadata = serde_json::from_str(&metadata_json)?;
+
+        // Load vectors
+        let vectors_bytes = fs::read(&vectors_path)?;
+        let mut vectors = Vec::with_capacity(vectors_bytes.len() / 4);
+        for chunk in vectors_bytes.chunks_exact(4) {
+            let bytes: [u8; 4] = chunk.try_into().map_err(|_| {
+                Error::Filesystem(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Invalid vector data",
+                ))
+            })?;
+            vectors.push(f32::from_le_bytes(bytes));
+        }
+
+        if vectors.len() != metadata.mapping.len() * metadata.dim {
+            return Err(Error::Embedding(format!(
+                "Vector store is inconsistent ({} values for {} documents of dimension {}); \
+                 run 'muesli reembed --force' to rebuild it",
+                vectors.len(),
+                metadata.mapping.len(),
+                metadata.dim
+            )));
+        }
+
+        let mut store = VectorStore {
+            vectors,
+            mapping: metadata.mapping,
+            dim: metadata.dim,
+            index: HnswIndex::new(metadata.dim),
+            dirty: false,
+            model: metadata
+                .model
+                .zip(metadata.model_hash)
+                .map(|(name, hash)| ModelId { name, hash }),
+        };
+
+        // Stores saved before the graph existed (or with a stale one) get it rebuilt
+        let index = fs::read_to_string(path.with_extension("hnsw.json"))
+            .ok()
+            .and_then(|json| serde_json::from_str::<HnswIndex>(&json).ok())
+            .filter(|index| index.len() == store.mapping.len());
+        match index {
+            Some(index) => store.index = index,
+            None => {
+                store.rebuild_index();
+                // Best effort: a read-only data dir still searches, just rebuilds each load.
+                // The store's own directory stands in for the tmp dir it doesn't know.
+                if let Some(dir) = path.parent() {
+                    let _ = store.save_index(path, dir);
+                }
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// Rebuilds the nearest-neighbor graph from the stored vectors
+    pub fn rebuild_index(&mut self) {
+        self.index = HnswIndex::build(&self.vectors, self.dim);
+        self.dirty = false;
+    }
+
+    pub fn len(&self) -> usize {
+        self.mapping.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn model(&self) -> Option<&ModelId> {
+        self.model.as_ref()
+    }
+
+    /// Ties the store to the model about to read or write it
+    ///
+    /// Vectors from different models are not comparable, so a store built with
+    /// another model is refused. Empty stores adopt the given model, as do stores
+    /// from before models were recorded when it is the default model they were
+    /// built with.
+    pub fn pin_model(&mut self, model: &ModelId) -> Result<()> {
+        let current = match &self.model {
+            Some(current) if current != model => Some(current.to_string()),
+            None if model.name != DEFAULT_MODEL => Some(DEFAULT_MODEL.to_string()),
+            _ => None,
+        };
+
+        match current {
+            Some(current) if !self.is_empty() => Err(Error::Embedding(format!(
+                "Vectors were built with {} but the configured model is {}; \
+                 run 'muesli sync --reindex --embeddings --migrate-model' to rebuild them",
+                current, model
+            ))),
+            _ => {
+                self.model = Some(model.clone());
+                Ok(())
+            }
+        }
+    }
+
+    /// Document IDs in storage order
+    pub fn doc_ids(&self) -> impl Iterator<Item = &str> {
+        self.mapping.iter().map(|m| m.doc_id.as_str())
+    }
+
+    /// Each document ID with its vector, in storage order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[f32])> {
+        self.mapping.iter().map(|m| {
+            (
+                m.doc_id.as_str(),
+                &self.vectors[m.offset..m.offset + self.dim],
+            )
+        })
+    }
+
+    /// Mean L2 norm of the stored vectors (about 1.0 for normalized embeddings)
+    pub fn average_norm(&self) -> f32 {
+        if self.mapping.is_empty() {
+            return 0.0;
+        }
+
+        let total: f32 = self
+            .vectors
+            .chunks_exact(self.dim)
+            .map(|v| v.iter().map(|x| x * x).sum::<f32>().sqrt())
+            .sum();
+        total / self.mapping.len() as f32
+    }
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b.iter()).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn model_id(hash: &str) -> ModelId {
+        ModelId {
+            name: "e5-small-v2".into(),
+            hash: hash.into(),
+        }
+    }
+
+    #[test]
+    fn test_pin_model_refuses_other_model() {
+        let mut store = VectorStore::new(3);
+        store.pin_model(&model_id("aaa")).unwrap();
+        // Nothing stored yet, so switching is harmless
+        store.pin_model(&model_id("bbb")).unwrap();
+
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+        store.pin_model(&model_id("bbb")).unwrap();
+        let err = store.pin_model(&model_id("ccc")).unwrap_err();
+        assert!(err.to_string().contains("--migrate-model"));
+        assert_eq!(store.model(), Some(&model_id("bbb")));
+    }
+
+    #[test]
+    fn test_pin_model_legacy_store_assumes_default_model() {
+        let mut store = VectorStore::new(3);
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+
+        let other = ModelId {
+            name: "bge-small-en-v1.5".into(),
+            hash: "aaa".into(),
+        };
+        assert!(store.pin_model(&other).is_err());
+        assert!(store.model().is_none());
+
+        store.pin_model(&model_id("aaa")).unwrap();
+        assert_eq!(store.model(), Some(&model_id("aaa")));
+    }
+
+    #[test]
+    fn test_similarity_to_stored_document() {
+        let mut store = VectorStore::new(3);
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+
+        let score = store.similarity("doc1", &[1.0, 0.0, 0.0]).unwrap();
+        assert!((score - 1.0).abs() < 0.001);
+        assert!(store.similarity("missing", &[1.0, 0.0, 0.0]).is_none());
+        assert!(store.similarity("doc1", &[1.0, 0.0]).is_none());
+    }
+
+    fn create_normalized_vector(values: &[f32]) -> Vec<f32> {
+        let norm: f32 = values.iter().map(|x| x * x).sum::<f32>().sqrt();
+        values.iter().map(|x| x / norm).collect()
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        let a = vec![1.0, 0.0, 0.0];
+        let b = vec![1.0, 0.0, 0.0];
+        assert!((cosine_similarity(&a, &b) - 1.0).abs() < 0.001);
+
+        let c = vec![0.0, 1.0, 0.0];
+        assert!((cosine_similarity(&a, &c) - 0.0).abs() < 0.001);
+
+        let d = vec![
+            std::f32::consts::FRAC_1_SQRT_2,
+            std::f32::consts::FRAC_1_SQRT_2,
+            0.0,
+        ];
+        assert!(cosine_similarity(&a, &d) > 0.7);
+    }
+
+    #[test]
+    fn test_vector_store_creation() {
+        let store = VectorStore::new(384);
+        assert_eq!(store.dim, 384);
+        assert_eq!(store.len(), 0);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_add_document() {
+        let mut store = VectorStore::new(3);
+        let vec = create_normalized_vector(&[1.0, 0.0, 0.0]);
+        store.add_document("doc1".into(), vec).unwrap();
+
+        assert_eq!(store.len(), 1);
+        assert!(!store.is_empty());
+    }
+
+    #[test]
+    fn test_remove_document() {
+        let mut store = VectorStore::new(3);
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+        store
+            .add_document("doc2".into(), vec![0.0, 1.0, 0.0])
+            .unwrap();
+        store
+            .add_document("doc3".into(), vec![0.0, 0.0, 1.0])
+            .unwrap();
+
+        assert!(store.remove_document("doc2"));
+        assert!(!store.remove_document("doc2"));
+        assert_eq!(store.len(), 2);
+        assert!(!store.has_document("doc2"));
+
+        // Remaining vectors still resolve to the right documents
+        let results = store.search(&[0.0, 0.0, 1.0], 1).unwrap();
+        assert_eq!(results[0].0, "doc3");
+        assert!((results[0].1 - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_upsert_document_replaces_in_place() {
+        let mut store = VectorStore::new(3);
+        assert!(!store
+            .upsert_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap());
+        store
+            .add_document("doc2".into(), vec![0.0, 1.0, 0.0])
+            .unwrap();
+
+        assert!(store
+            .upsert_document("doc1".into(), vec![0.0, 0.0, 1.0])
+            .unwrap());
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.doc_ids().collect::<Vec<_>>(), vec!["doc1", "doc2"]);
+        assert_eq!(store.vector("doc1"), Some(&[0.0, 0.0, 1.0][..]));
+        assert_eq!(store.vector("doc2"), Some(&[0.0, 1.0, 0.0][..]));
+
+        let results = store.search(&[0.0, 0.0, 1.0], 2).unwrap();
+        assert_eq!(results[0].0, "doc1");
+        assert!((results[0].1 - 1.0).abs() < 0.001);
+
+        assert!(store.upsert_document("doc1".into(), vec![1.0]).is_err());
+        assert_eq!(store.vector("doc1"), Some(&[0.0, 0.0, 1.0][..]));
+    }
+
+    #[test]
+    fn test_changes_rebuild_graph_once_on_save() {
+        let temp = TempDir::new().unwrap();
+        let store_path = temp.path().join("vectors");
+
+        let mut store = VectorStore::new(3);
+        for (doc_id, vector) in [
+            ("doc1", [1.0, 0.0, 0.0]),
+            ("doc2", [0.0, 1.0, 0.0]),
+            ("doc3", [0.0, 0.0, 1.0]),
+        ] {
+            store.add_document(doc_id.into(), vector.to_vec()).unwrap();
+        }
+        store.remove_document("doc1");
+        store
+            .upsert_document("doc2".into(), vec![0.0, 0.0, 1.0])
+            .unwrap();
+        store
+            .add_document("doc4".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+        assert!(store.dirty);
+
+        // Searches in between see every change
+        let results = store.search(&[1.0, 0.0, 0.0], 1).unwrap();
+        assert_eq!(results[0].0, "doc4");
+
+        store.save(&store_path, temp.path()).unwrap();
+        assert!(!store.dirty);
+        let loaded = VectorStore::load(&store_path).unwrap();
+        let results = loaded.search(&[1.0, 0.0, 0.0], 1).unwrap();
+        assert_eq!(results[0].0, "doc4");
+        assert_eq!(loaded.search(&[0.0, 0.0, 1.0], 5).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let mut store = VectorStore::new(3);
+        let vec = vec![1.0, 0.0]; // Wrong dimension
+
+        let result = store.add_document("doc1".into(), vec);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_search() {
+        let mut store = VectorStore::new(3);
+
+        // Add three normalized vectors
+        let vec1 = create_normalized_vector(&[1.0, 0.0, 0.0]);
+        let vec2 = create_normalized_vector(&[0.0, 1.0, 0.0]);
+        let vec3 = create_normalized_vector(&[0.7, 0.7, 0.0]);
+
+        store.add_document("doc1".into(), vec1.clone()).unwrap();
+        store.add_document("doc2".into(), vec2).unwrap();
+        store.add_document("doc3".into(), vec3).unwrap();
+
+        // Search with query similar to doc1
+        let query = create_normalized_vector(&[0.9, 0.1, 0.0]);
+        let results = store.search(&query, 2).unwrap();
+
+        assert_eq!(results.len(), 2);
+        // doc1 should be most similar
+        assert_eq!(results[0].0, "doc1");
+        assert!(results[0].1 > 0.9); // High similarity
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let store_path = temp.path().join("vectors");
+
+        // Create and populate store
+        let mut store = VectorStore::new(3);
+        store.pin_model(&model_id("e5")).unwrap();
+        let vec1 = create_normalized_vector(&[1.0, 0.0, 0.0]);
+        let vec2 = create_normalized_vector(&[0.0, 1.0, 0.0]);
+
+        store.add_document("doc1".into(), vec1).unwrap();
+        store.add_document("doc2".into(), vec2).unwrap();
+
+        // Save
+        store.save(&store_path, temp.path()).unwrap();
+
+        // Load
+        let loaded_store = VectorStore::load(&store_path).unwrap();
+
+        assert_eq!(loaded_store.dim, 3);
+        assert_eq!(loaded_store.len(), 2);
+        assert_eq!(loaded_store.model(), Some(&model_id("e5")));
+        assert_eq!(
+            loaded_store.doc_ids().collect::<Vec<_>>(),
+            vec!["doc1", "doc2"]
+        );
+        assert!((loaded_store.average_norm() - 1.0).abs() < 0.001);
+
+        // Verify search still works
+        let query = create_normalized_vector(&[1.0, 0.0, 0.0]);
+        let results = loaded_store.search(&query, 1).unwrap();
+        assert_eq!(results[0].0, "doc1");
+    }
+
+    #[test]
+    fn test_load_rebuilds_missing_index() {
+        let temp = TempDir::new().unwrap();
+        let store_path = temp.path().join("vectors");
+
+        let mut store = VectorStore::new(3);
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+        store
+            .add_document("doc2".into(), vec![0.0, 1.0, 0.0])
+            .unwrap();
+        store.save(&store_path, temp.path()).unwrap();
+
+        // Simulate a store written before the HNSW graph was persisted
+        let index_path = store_path.with_extension("hnsw.json");
+        fs::remove_file(&index_path).unwrap();
+
+        let loaded = VectorStore::load(&store_path).unwrap();
+        assert!(index_path.exists());
+        let results = loaded.search(&[0.0, 1.0, 0.0], 1).unwrap();
+        assert_eq!(results[0].0, "doc2");
+    }
+
+    #[test]
+    fn test_load_rejects_inconsistent_store() {
+        let temp = TempDir::new().unwrap();
+        let store_path = temp.path().join("vectors");
+
+        let mut store = VectorStore::new(3);
+        store
+            .add_document("doc1".into(), vec![1.0, 0.0, 0.0])
+            .unwrap();
+        store.save(&store_path, temp.path()).unwrap();
+
+        // Vectors from a save that never got to write its metadata
+        fs::write(store_path.with_extension("vectors.bin"), [0u8; 24]).unwrap();
+
+        let err = VectorStore::load(&store_path).err().unwrap();
+        assert!(err.to_string().contains("muesli reembed --force"));
+        assert!(!store_path.with_extension("meta.tmp").exists());
+    }
+
+    #[test]
+    fn test_empty_search() {
+        let store = VectorStore::new(3);
+        let query = create_normalized_vector(&[1.0, 0.0, 0.0]);
+        let results = store.search(&query, 10).unwrap();
+        assert_eq!(results.len(), 0);
+    }
+}
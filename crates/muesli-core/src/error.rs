@@ -32,6 +32,24 @@ pub enum Error {
 
     #[error("Embedding error: {0}")]
     Embedding(String),
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Catalog error: {0}")]
+    Catalog(String),
+
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+
+    #[error("Offline mode: {0}; run without --offline")]
+    Offline(String),
+
+    #[error("Transcription error: {0}")]
+    Transcription(String),
+
+    #[error("Mail server error: {0}")]
+    Email(String),
 }
 
 impl Error {
@@ -45,6 +63,12 @@ impl Error {
             Error::Summarization(_) => 7,
             Error::Indexing(_) => 8,
             Error::Embedding(_) => 9,
+            Error::InvalidInput(_) => 10,
+            Error::Catalog(_) => 11,
+            Error::Clipboard(_) => 12,
+            Error::Offline(_) => 13,
+            Error::Transcription(_) => 14,
+            Error::Email(_) => 15,
         }
     }
 }
@@ -68,5 +92,11 @@ mod tests {
             4
         );
         assert_eq!(Error::Summarization("test".into()).exit_code(), 7);
+        assert_eq!(Error::InvalidInput("test".into()).exit_code(), 10);
+        assert_eq!(Error::Catalog("test".into()).exit_code(), 11);
+        assert_eq!(Error::Clipboard("test".into()).exit_code(), 12);
+        assert_eq!(Error::Offline("test".into()).exit_code(), 13);
+        assert_eq!(Error::Transcription("test".into()).exit_code(), 14);
+        assert_eq!(Error::Email("test".into()).exit_code(), 15);
     }
 }
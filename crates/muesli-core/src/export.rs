@@ -0,0 +1,348 @@
+// ABOUTME: Exports transcripts as standalone HTML pages or subtitles, one document or the whole archive
+// ABOUTME: HTML has a metadata header from the frontmatter and one styled block per speaker turn
+
+use crate::convert::strip_anchor;
+use crate::related::strip_related;
+use crate::storage::{read_frontmatter, strip_frontmatter, Catalog, CatalogEntry};
+use crate::subtitles::{self, SubtitleFormat};
+use crate::{Error, Frontmatter, Paths, Result};
+use pulldown_cmark::{escape::escape_html, html, Event, Options, Parser};
+use std::path::Path;
+
+/// What `muesli export` writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// A self-contained HTML page
+    Html,
+    Subtitles(SubtitleFormat),
+}
+
+impl ExportFormat {
+    /// File extension without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Html => "html",
+            ExportFormat::Subtitles(format) => format.extension(),
+        }
+    }
+}
+
+/// Documents written by [`export_all`], and the ones that could not be exported
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    pub written: usize,
+    /// (doc_id, reason) for each document left out
+    pub skipped: Vec<(String, String)>,
+}
+
+const STYLE: &str = r#"
+body { margin: 0; background: #f6f6f4; color: #222; font: 16px/1.55 -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; }
+main { max-width: 46rem; margin: 2rem auto; padding: 2rem 2.5rem; background: #fff; border-radius: 8px; box-shadow: 0 1px 4px rgba(0, 0, 0, 0.08); }
+header { border-bottom: 1px solid #e4e4e0; margin-bottom: 1.5rem; }
+header h1 { margin: 0 0 0.75rem; font-size: 1.6rem; }
+dl.meta { display: grid; grid-template-columns: max-content 1fr; gap: 0.2rem 1rem; margin: 0 0 1.25rem; color: #555; font-size: 0.9rem; }
+dl.meta dt { font-weight: 600; }
+dl.meta dd { margin: 0; }
+.turn { margin: 0 0 0.9rem; }
+.speaker { font-weight: 600; color: #1f5fa8; }
+.time { margin-left: 0.4rem; color: #888; text-decoration: none; font-size: 0.8rem; font-variant-numeric: tabular-nums; }
+.turn p { margin: 0.15rem 0 0; }
+h3 { margin: 1.5rem 0 0.3rem; font-size: 1rem; color: #1f5fa8; }
+hr { border: 0; border-top: 1px dashed #ccc; }
+@media print { body { background: #fff; } main { box-shadow: none; margin: 0; } }
+"#;
+
+/// Renders one transcript
+///
+/// `condensed` leaves out small talk and filler, as `muesli condense` does.
+pub fn export(
+    paths: &Paths,
+    doc_id: &str,
+    format: ExportFormat,
+    condensed: bool,
+) -> Result<String> {
+    match format {
+        ExportFormat::Html => {
+            let entry = Catalog::open(paths)?.entry_for(doc_id)?;
+            html_for(paths, &entry, condensed)
+        }
+        ExportFormat::Subtitles(format) => subtitles::export(paths, doc_id, format, condensed),
+    }
+}
+
+/// Writes every transcript into `dir`, named like its markdown file
+///
+/// A document that can't be exported (subtitles need timestamps) is skipped
+/// and reported instead of stopping the export.
+pub fn export_all(
+    paths: &Paths,
+    dir: &Path,
+    format: ExportFormat,
+    condensed: bool,
+) -> Result<ExportReport> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut report = ExportReport::default();
+    for entry in Catalog::open(paths)?.entries()? {
+        let rendered = match format {
+            ExportFormat::Html => html_for(paths, &entry, condensed),
+            ExportFormat::Subtitles(format) => {
+                subtitles::export(paths, &entry.doc_id, format, condensed)
+            }
+        };
+        let content = match rendered {
+            Ok(content) => content,
+            Err(e) => {
+                report.skipped.push((entry.doc_id, e.to_string()));
+                continue;
+            }
+        };
+
+        let stem = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+        std::fs::write(
+            dir.join(format!("{}.{}", stem, format.extension())),
+            content,
+        )?;
+        report.written += 1;
+    }
+
+    Ok(report)
+}
+
+fn html_for(paths: &Paths, entry: &CatalogEntry, condensed: bool) -> Result<String> {
+    let (fm, body) = transcript_for(paths, entry, condensed)?;
+    Ok(to_html(&fm, &body))
+}
+
+/// A transcript's frontmatter and markdown body, condensed if asked
+pub(crate) fn transcript_for(
+    paths: &Paths,
+    entry: &CatalogEntry,
+    condensed: bool,
+) -> Result<(Frontmatter, String)> {
+    let fm = read_frontmatter(&entry.path)?.ok_or_else(|| {
+        Error::InvalidInput(format!("{} has no frontmatter", entry.path.display()))
+    })?;
+
+    let body = if condensed {
+        let (_, raw) = crate::storage::load_raw_transcript(paths, &entry.doc_id)?;
+        let condensed = crate::condense::condense(&raw);
+        crate::condense::to_markdown(paths, entry, &condensed)?.body
+    } else {
+        let content = std::fs::read_to_string(&entry.path)?.replace("\r\n", "\n");
+        strip_frontmatter(&content).to_string()
+    };
+
+    Ok((fm, body))
+}
+
+/// A standalone HTML page for a transcript's frontmatter and markdown body
+///
+/// The body's own title and metadata line are replaced by the header, and the
+/// related-meetings footer is dropped since its links point into the archive.
+/// HTML in the transcript is shown as text, never passed through.
+pub fn to_html(fm: &Frontmatter, body: &str) -> String {
+    let title = fm.title.as_deref().unwrap_or("Untitled Meeting");
+
+    let mut meta = vec![(
+        "Date",
+        fm.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+    )];
+    if let Some(duration) = fm.duration_seconds {
+        meta.push(("Duration", format!("{}m", duration / 60)));
+    }
+    if !fm.participants.is_empty() {
+        meta.push(("Participants", fm.participants.join(", ")));
+    }
+    if !fm.labels.is_empty() {
+        meta.push(("Labels", fm.labels.join(", ")));
+    }
+
+    let mut out =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(title)));
+    out.push_str(&format!(
+        "<style>{}</style>\n</head>\n<body>\n<main>\n",
+        STYLE
+    ));
+
+    out.push_str(&format!(
+        "<header>\n<h1>{}</h1>\n<dl class=\"meta\">\n",
+        escape(title)
+    ));
+    for (name, value) in meta {
+        out.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", name, escape(&value)));
+    }
+    out.push_str("</dl>\n</header>\n<article>\n");
+    out.push_str(&body_html(strip_header(strip_related(body))));
+    out.push_str("</article>\n</main>\n</body>\n</html>\n");
+    out
+}
+
+/// The body without the `# Title` and `_Date: ..._` lines the converter puts first
+pub(crate) fn strip_header(body: &str) -> &str {
+    let mut rest = body.trim_start();
+    if let Some(after) = rest.strip_prefix("# ") {
+        rest = after.split_once('\n').map_or("", |(_, r)| r).trim_start();
+    }
+    if rest.starts_with("_Date: ") {
+        rest = rest.split_once('\n').map_or("", |(_, r)| r).trim_start();
+    }
+    rest
+}
+
+/// `**Speaker (10:00:05):** text` lines become turn blocks; everything else is markdown
+fn body_html(body: &str) -> String {
+    let mut out = String::new();
+    let mut markdown = String::new();
+
+    for line in body.lines() {
+        let (line, anchor) = strip_anchor(line);
+        let Some((label, text)) = line
+            .strip_prefix("**")
+            .and_then(|rest| rest.split_once(":** "))
+        else {
+            match anchor {
+                // A speaker heading: keep the anchor as the heading's id
+                Some(id) if line.starts_with("### ") => {
+                    out.push_str(&render_markdown(&markdown));
+                    markdown.clear();
+                    out.push_str(&render_markdown(line).replacen(
+                        "<h3>",
+                        &format!("<h3 id=\"{}\">", escape(id)),
+                        1,
+                    ));
+                }
+                _ => {
+                    markdown.push_str(line);
+                    markdown.push('\n');
+                }
+            }
+            continue;
+        };
+
+        out.push_str(&render_markdown(&markdown));
+        markdown.clear();
+
+        let (speaker, time) = match label.strip_suffix(')').and_then(|l| l.rsplit_once(" (")) {
+            Some((speaker, time)) => (speaker, Some(time)),
+            None => (label, None),
+        };
+        match anchor {
+            Some(id) => out.push_str(&format!(
+                "<div class=\"turn\" id=\"{}\"><span class=\"speaker\">{}</span>",
+                escape(id),
+                escape(speaker)
+            )),
+            None => out.push_str(&format!(
+                "<div class=\"turn\"><span class=\"speaker\">{}</span>",
+                escape(speaker)
+            )),
+        }
+        match (time, anchor) {
+            // Linking the time lets a reader copy a link to the moment
+            (Some(time), Some(id)) => out.push_str(&format!(
+                "<a class=\"time\" href=\"#{}\">{}</a>",
+                escape(id),
+                escape(time)
+            )),
+            (Some(time), None) => {
+                out.push_str(&format!("<span class=\"time\">{}</span>", escape(time)))
+            }
+            (None, _) => {}
+        }
+        out.push_str(&render_markdown(text));
+        out.push_str("</div>\n");
+    }
+
+    out.push_str(&render_markdown(&markdown));
+    out
+}
+
+fn render_markdown(markdown: &str) -> String {
+    let parser = Parser::new_ext(markdown, Options::empty()).map(|event| match event {
+        Event::Html(raw) => Event::Text(raw),
+        event => event,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = escape_html(&mut out, text);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frontmatter() -> Frontmatter {
+        Frontmatter {
+            doc_id: "doc1".into(),
+            source: "granola".into(),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            remote_updated_at: None,
+            title: Some("Planning <Q4>".into()),
+            participants: vec!["Alice".into(), "Bob".into()],
+            duration_seconds: Some(3170),
+            labels: vec!["Planning".into()],
+            organizations: Vec::new(),
+            inferred_speakers: Vec::new(),
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        }
+    }
+
+    #[test]
+    fn test_html_has_header_and_turns() {
+        let body = "# Planning <Q4>\n\n_Date: 2025-10-28 · Duration: 52m_\n\n\
+                    **Alice (15:05:10):** First *idea*\n\
+                    **Bob:** Ship it <script>alert(1)</script>\n\
+                    <a id=\"t-15-06-00\"></a>**Carol (15:06:00):** Agreed\n";
+        let html = to_html(&frontmatter(), body);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Planning &lt;Q4&gt;</title>"));
+        assert!(html.contains("<dt>Duration</dt><dd>52m</dd>"));
+        assert!(html.contains("<dd>Alice, Bob</dd>"));
+        // The header replaces the body's own title and metadata line
+        assert_eq!(html.matches("<h1>").count(), 1);
+        assert!(!html.contains("_Date:"));
+
+        assert!(html.contains(
+            "<div class=\"turn\"><span class=\"speaker\">Alice</span><span class=\"time\">15:05:10</span><p>First <em>idea</em></p>"
+        ));
+        assert!(html.contains("<span class=\"speaker\">Bob</span>"));
+        // Anchored turns keep their id, and the time links to it
+        assert!(html.contains(
+            "<div class=\"turn\" id=\"t-15-06-00\"><span class=\"speaker\">Carol</span><a class=\"time\" href=\"#t-15-06-00\">15:06:00</a>"
+        ));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_html_keeps_heading_layout_and_drops_related_footer() {
+        let body = format!(
+            "# Planning\n\n### Alice (10:00)\n\nHi all\n\n### Bob (10:05) {{#t-10-05-00}}\n\nHello\n\n{}\n## Related meetings\n\n- [Other](other.md)\n{}\n",
+            crate::related::RELATED_START,
+            crate::related::RELATED_END
+        );
+        let html = to_html(&frontmatter(), &body);
+
+        assert!(html.contains("<h3>Alice (10:00)</h3>"));
+        assert!(html.contains("<p>Hi all</p>"));
+        assert!(html.contains("<h3 id=\"t-10-05-00\">Bob (10:05)</h3>"));
+        assert!(!html.contains("Related meetings"));
+    }
+}
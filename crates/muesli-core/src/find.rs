@@ -0,0 +1,257 @@
+// ABOUTME: Searches inside a single transcript for `muesli find-in`
+// ABOUTME: Parses speaker entries, ranks them against the query and adds context
+
+use crate::convert::strip_anchor;
+use crate::storage::strip_frontmatter;
+use crate::{DocKind, SearchHit};
+use serde::Serialize;
+
+/// One spoken entry from a transcript body (`**Speaker (HH:MM:SS):** text`,
+/// or a line under a `### Speaker (HH:MM:SS)` heading)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    pub speaker: String,
+    /// Offset into the meeting, when the transcript recorded one
+    pub timestamp: Option<String>,
+    pub text: String,
+    /// Id to link to (`file.md#t-00-05-10`), when the transcript was written with anchors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+}
+
+/// An entry matching the query, with its neighbours for context
+#[derive(Debug, Clone, Serialize)]
+pub struct FindHit {
+    /// Position of the entry in the transcript (0-based)
+    pub position: usize,
+    pub entry: Entry,
+    /// How many distinct query terms the entry contains
+    pub matched_terms: usize,
+    /// Total occurrences of all query terms
+    pub occurrences: usize,
+    pub before: Vec<Entry>,
+    pub after: Vec<Entry>,
+}
+
+/// Splits "Speaker (HH:MM:SS)" into the speaker and timestamp
+fn split_header(header: &str) -> (&str, Option<String>) {
+    match header.strip_suffix(')').and_then(|h| h.rsplit_once(" (")) {
+        Some((speaker, ts))
+            if !ts.is_empty() && ts.chars().all(|c| c.is_ascii_digit() || c == ':') =>
+        {
+            (speaker, Some(ts.to_string()))
+        }
+        _ => (header, None),
+    }
+}
+
+/// Parses one bold-style markdown line into an entry; other lines yield None
+pub fn parse_entry(line: &str) -> Option<Entry> {
+    let (line, anchor) = strip_anchor(line);
+    let rest = line.strip_prefix("**")?;
+    let (header, text) = rest.split_once(":** ")?;
+    let (speaker, timestamp) = split_header(header);
+
+    Some(Entry {
+        speaker: speaker.to_string(),
+        timestamp,
+        text: text.trim().to_string(),
+        anchor: anchor.map(str::to_string),
+    })
+}
+
+/// All speaker entries in a transcript body, in order
+///
+/// Handles both layouts `[markdown]` can produce: bold lines, and lines
+/// under `###` speaker headings (which share the heading's timestamp and anchor).
+pub fn parse_entries(body: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut turn: Option<(&str, Option<String>, Option<&str>)> = None;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if let Some(entry) = parse_entry(line) {
+            entries.push(entry);
+            turn = None;
+        } else if let Some(header) = trimmed.strip_prefix("### ") {
+            let (header, anchor) = strip_anchor(header.trim());
+            let (speaker, timestamp) = split_header(header);
+            turn = Some((speaker, timestamp, anchor));
+        } else if trimmed.starts_with('#') || trimmed.starts_with("<!--") {
+            // Other headings and generated sections end the speaker turn
+            turn = None;
+        } else if trimmed.is_empty() || (trimmed.starts_with('[') && trimmed.ends_with(" gap]")) {
+            continue;
+        } else if let Some((speaker, timestamp, anchor)) = &turn {
+            entries.push(Entry {
+                speaker: speaker.to_string(),
+                timestamp: timestamp.clone(),
+                text: trimmed.to_string(),
+                anchor: anchor.map(str::to_string),
+            });
+        }
+    }
+
+    entries
+}
+
+/// Ranks a transcript's entries against a query, best first
+///
+/// Terms match case-insensitively anywhere in an entry ("price" matches
+/// "pricing"). Entries containing the whole query as a phrase rank first, then
+/// those matching more distinct terms, then more occurrences; ties keep
+/// transcript order. Each hit carries up to `context` entries on either side.
+pub fn find_in(body: &str, query: &str, context: usize, limit: usize) -> Vec<FindHit> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let phrase = terms.join(" ");
+
+    let entries = parse_entries(body);
+    let mut scored: Vec<(bool, usize, usize, usize)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(position, entry)| {
+            let text = entry.text.to_lowercase();
+            let counts: Vec<usize> = terms
+                .iter()
+                .map(|t| text.matches(t.as_str()).count())
+                .collect();
+            let matched = counts.iter().filter(|&&c| c > 0).count();
+            if matched == 0 {
+                return None;
+            }
+            let is_phrase = terms.len() > 1 && text.contains(&phrase);
+            Some((is_phrase, matched, counts.iter().sum(), position))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| {
+        (b.0, b.1, b.2)
+            .cmp(&(a.0, a.1, a.2))
+            .then_with(|| a.3.cmp(&b.3))
+    });
+    scored.truncate(limit);
+
+    scored
+        .into_iter()
+        .map(|(_, matched_terms, occurrences, position)| FindHit {
+            position,
+            entry: entries[position].clone(),
+            matched_terms,
+            occurrences,
+            before: entries[position.saturating_sub(context)..position].to_vec(),
+            after: entries[position + 1..(position + 1 + context).min(entries.len())].to_vec(),
+        })
+        .collect()
+}
+
+/// Points each transcript hit at the entry that best matches the query
+///
+/// Only transcripts written with anchors (`anchors` under `[markdown]`) have
+/// something to point at; other hits are left as they are.
+pub fn link_hits(hits: &mut [SearchHit], query: &str) {
+    for hit in hits.iter_mut().filter(|h| h.kind == DocKind::Transcript) {
+        let Ok(content) = std::fs::read_to_string(&hit.path) else {
+            continue;
+        };
+        hit.anchor = find_in(strip_frontmatter(&content), query, 0, 1)
+            .into_iter()
+            .next()
+            .and_then(|best| best.entry.anchor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "# Pricing Sync\n\n\
+_Date: 2025-10-29 · Duration: 30m_\n\n\
+**Alice (00:00:05):** Welcome everyone\n\
+**Bob (00:01:10):** The pricing page needs work\n\
+**Alice (00:02:00):** Agreed, pricing and the new page layout\n\
+**Carol:** Let's talk about the new page after lunch\n\
+**Bob (00:03:30):** Sounds good\n";
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            parse_entry("**Alice (00:00:05):** Welcome everyone"),
+            Some(Entry {
+                speaker: "Alice".to_string(),
+                timestamp: Some("00:00:05".to_string()),
+                text: "Welcome everyone".to_string(),
+                anchor: None,
+            })
+        );
+
+        let entry = parse_entry("<a id=\"t-00-00-05\"></a>**Alice (00:00:05):** Hi").unwrap();
+        assert_eq!(entry.speaker, "Alice");
+        assert_eq!(entry.anchor.as_deref(), Some("t-00-00-05"));
+
+        let entry = parse_entry("**Dr. Who (Guest):** Hello").unwrap();
+        assert_eq!(entry.speaker, "Dr. Who (Guest)");
+        assert_eq!(entry.timestamp, None);
+
+        assert!(parse_entry("# Pricing Sync").is_none());
+        assert!(parse_entry("_Date: 2025-10-29_").is_none());
+    }
+
+    #[test]
+    fn test_parse_entries_with_speaker_headings() {
+        let body = "# Pricing Sync\n\n_Date: 2025-10-29_\n\n\
+### Alice (00:00) {#t-00-00-05}\n\nWelcome everyone\nLet's start\n\n\
+[12m gap]\n\n\
+### Bob\n\nThe pricing page needs work\n\n\
+<!-- muesli:related -->\n## Related meetings\n\n- [Kickoff](a.md)\n";
+
+        let entries = parse_entries(body);
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Welcome everyone",
+                "Let's start",
+                "The pricing page needs work"
+            ]
+        );
+        assert_eq!(entries[1].speaker, "Alice");
+        assert_eq!(entries[1].timestamp.as_deref(), Some("00:00"));
+        assert_eq!(entries[1].anchor.as_deref(), Some("t-00-00-05"));
+        assert_eq!(entries[2].timestamp, None);
+        assert_eq!(entries[2].anchor, None);
+    }
+
+    #[test]
+    fn test_find_in_ranks_phrase_and_terms() {
+        let hits = find_in(BODY, "new page", 0, 10);
+        let positions: Vec<usize> = hits.iter().map(|h| h.position).collect();
+        // Both phrase matches (transcript order), then the single-term match
+        assert_eq!(positions, vec![2, 3, 1]);
+        assert_eq!(hits[2].matched_terms, 1);
+    }
+
+    #[test]
+    fn test_find_in_context_and_limit() {
+        let hits = find_in(BODY, "PRICING", 1, 1);
+        assert_eq!(hits.len(), 1);
+
+        let hit = &hits[0];
+        assert_eq!(hit.position, 1);
+        assert_eq!(hit.entry.timestamp.as_deref(), Some("00:01:10"));
+        assert_eq!(hit.before[0].text, "Welcome everyone");
+        assert_eq!(hit.after.len(), 1);
+
+        let last = find_in(BODY, "sounds", 2, 10);
+        assert_eq!(last[0].before.len(), 2);
+        assert!(last[0].after.is_empty());
+    }
+
+    #[test]
+    fn test_find_in_no_match() {
+        assert!(find_in(BODY, "budget", 1, 10).is_empty());
+        assert!(find_in(BODY, "   ", 1, 10).is_empty());
+    }
+}
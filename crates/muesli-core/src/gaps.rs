@@ -0,0 +1,159 @@
+// ABOUTME: Detects long silences between transcript entries from their timestamps
+// ABOUTME: Used for "[12m gap]" markers in markdown and dead-time totals in stats
+
+use crate::model::TranscriptEntry;
+use chrono::{DateTime, Utc};
+
+/// Silences at least this long are marked and counted as dead time
+pub const GAP_THRESHOLD_SECS: u64 = 120;
+
+/// A silence between two entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gap {
+    /// Index of the entry the silence precedes
+    pub before: usize,
+    pub seconds: u64,
+}
+
+/// Seconds for an ISO 8601 timestamp or an "HH:MM:SS(.sss)" offset
+///
+/// Only differences between values of the same transcript are meaningful.
+pub(crate) fn seconds(ts: &str) -> Option<f64> {
+    if let Ok(dt) = ts.parse::<DateTime<Utc>>() {
+        return Some(dt.timestamp_millis() as f64 / 1000.0);
+    }
+
+    let parts: Vec<&str> = ts.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let hours: f64 = parts[0].parse().ok()?;
+    let minutes: f64 = parts[1].parse().ok()?;
+    let secs: f64 = parts[2].parse().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + secs)
+}
+
+/// Silences of at least `threshold` seconds between entries
+///
+/// A silence runs from the latest end seen so far (so overlapping speakers don't
+/// create gaps) to the next entry's start. Entries without timestamps are skipped.
+pub fn find_gaps(entries: &[TranscriptEntry], threshold: u64) -> Vec<Gap> {
+    let mut gaps = Vec::new();
+    let mut spoken_until: Option<f64> = None;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let Some(start) = entry.start.as_deref().and_then(seconds) else {
+            continue;
+        };
+        let end = entry
+            .end
+            .as_deref()
+            .and_then(seconds)
+            .unwrap_or(start)
+            .max(start);
+
+        if let Some(until) = spoken_until {
+            let silence = (start - until).max(0.0) as u64;
+            if silence >= threshold {
+                gaps.push(Gap {
+                    before: i,
+                    seconds: silence,
+                });
+            }
+        }
+        spoken_until = Some(spoken_until.map_or(end, |until| until.max(end)));
+    }
+
+    gaps
+}
+
+/// Time from the first entry's start to the last entry's end, if timestamped
+pub fn transcript_span(entries: &[TranscriptEntry]) -> Option<u64> {
+    let starts = entries
+        .iter()
+        .filter_map(|e| e.start.as_deref().and_then(seconds));
+    let ends = entries
+        .iter()
+        .filter_map(|e| e.end.as_deref().or(e.start.as_deref()).and_then(seconds));
+
+    let first = starts.fold(None, |min: Option<f64>, s| {
+        Some(min.map_or(s, |m| m.min(s)))
+    })?;
+    let last = ends.fold(None, |max: Option<f64>, e| {
+        Some(max.map_or(e, |m| m.max(e)))
+    })?;
+    Some((last - first).max(0.0) as u64)
+}
+
+/// Compact duration like "45s", "12m" or "1h 5m"
+pub fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s % 3600 < 60 => format!("{}h", s / 3600),
+        s => format!("{}h {}m", s / 3600, (s % 3600) / 60),
+    }
+}
+
+/// The markdown line written in place of a silence
+pub fn gap_marker(seconds: u64) -> String {
+    format!("[{} gap]", format_duration(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start: &str, end: &str) -> TranscriptEntry {
+        TranscriptEntry {
+            document_id: None,
+            start: (!start.is_empty()).then(|| start.to_string()),
+            end: (!end.is_empty()).then(|| end.to_string()),
+            text: "x".into(),
+            source: None,
+            id: None,
+            is_final: None,
+            confidence: None,
+            words: vec![],
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn test_find_gaps() {
+        let entries = vec![
+            entry("2025-10-01T10:00:00Z", "2025-10-01T10:00:10Z"),
+            // Starts before the previous entry ends
+            entry("2025-10-01T10:00:05Z", "2025-10-01T10:01:00Z"),
+            entry("2025-10-01T10:02:30Z", "2025-10-01T10:02:40Z"),
+            entry("", ""),
+            entry("2025-10-01T10:15:00Z", ""),
+        ];
+
+        let gaps = find_gaps(&entries, GAP_THRESHOLD_SECS);
+        assert_eq!(
+            gaps,
+            vec![Gap {
+                before: 4,
+                seconds: 740
+            }]
+        );
+        assert_eq!(find_gaps(&entries, 60).len(), 2);
+        assert_eq!(transcript_span(&entries), Some(900));
+    }
+
+    #[test]
+    fn test_offset_timestamps() {
+        let entries = vec![entry("00:00:05.500", "00:00:09"), entry("00:12:10", "")];
+        assert_eq!(find_gaps(&entries, GAP_THRESHOLD_SECS)[0].seconds, 721);
+        assert!(transcript_span(&[entry("", "")]).is_none());
+    }
+
+    #[test]
+    fn test_gap_marker() {
+        assert_eq!(gap_marker(740), "[12m gap]");
+        assert_eq!(gap_marker(3600), "[1h gap]");
+        assert_eq!(gap_marker(3900), "[1h 5m gap]");
+        assert_eq!(format_duration(45), "45s");
+    }
+}
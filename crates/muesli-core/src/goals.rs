@@ -0,0 +1,309 @@
+// ABOUTME: Goal/OKR tracking across meetings using keywords from goals.toml
+// ABOUTME: Finds mentions per goal, buckets them by month and renders a text chart
+
+use crate::storage::strip_frontmatter;
+use crate::{Error, Frontmatter, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Example shown when no goals file exists yet
+pub const EXAMPLE_GOALS: &str = r#"[[goal]]
+name = "Launch v2"
+keywords = ["v2 launch", "version 2", "launch date"]
+
+[[goal]]
+name = "Reduce churn"
+keywords = ["churn", "retention"]
+"#;
+
+/// Prompt for extracting a goal's latest status from meeting excerpts
+pub const GOAL_STATUS_PROMPT: &str = r#"You are tracking progress on a team goal across meetings.
+
+Below are excerpts from meetings that mention the goal, oldest first. Reply with:
+
+1. Current Status (one or two sentences, quoting the most recent statement)
+2. Trend (improving, stalled, at risk, or unclear) with a one-line reason
+3. Latest Commitments or Blockers (bullets, with meeting date)
+
+Only use information from the excerpts; say "unclear" rather than guessing."#;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Goal {
+    pub name: String,
+    /// Case-insensitive phrases that count as a mention
+    #[serde(default)]
+    pub keywords: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GoalsFile {
+    #[serde(default, rename = "goal")]
+    goals: Vec<Goal>,
+}
+
+/// One meeting that mentions a goal
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalMention {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub date: NaiveDate,
+    pub path: PathBuf,
+    /// Number of keyword occurrences (0 for semantic-only matches)
+    pub count: usize,
+    /// Lines containing a keyword
+    pub snippets: Vec<String>,
+}
+
+/// Loads goals from a TOML file with `[[goal]]` tables
+pub fn load_goals(path: &Path) -> Result<Vec<Goal>> {
+    if !path.exists() {
+        return Err(Error::InvalidInput(format!(
+            "No goals file at {}. Create one like:\n\n{}",
+            path.display(),
+            EXAMPLE_GOALS
+        )));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let file: GoalsFile = toml::from_str(&content)
+        .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))?;
+
+    Ok(file.goals)
+}
+
+/// Phrases to search for: the goal's keywords, or its name if none are given
+fn search_terms(goal: &Goal) -> Vec<String> {
+    let terms: Vec<String> = goal
+        .keywords
+        .iter()
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect();
+
+    if terms.is_empty() {
+        vec![goal.name.to_lowercase()]
+    } else {
+        terms
+    }
+}
+
+/// Counts keyword mentions in a markdown body, returning (count, snippets)
+pub fn scan_body(goal: &Goal, body: &str) -> (usize, Vec<String>) {
+    let terms = search_terms(goal);
+    let mut count = 0;
+    let mut snippets = Vec::new();
+
+    for line in body.lines() {
+        let lower = line.to_lowercase();
+        let hits: usize = terms
+            .iter()
+            .map(|t| lower.matches(t.as_str()).count())
+            .sum();
+        if hits > 0 {
+            count += hits;
+            let snippet: String = line.trim().chars().take(300).collect();
+            snippets.push(snippet);
+        }
+    }
+
+    (count, snippets)
+}
+
+/// Finds every transcript mentioning the goal, oldest first
+pub fn find_mentions(
+    goal: &Goal,
+    transcripts: &[(PathBuf, Frontmatter)],
+) -> Result<Vec<GoalMention>> {
+    let mut mentions = Vec::new();
+
+    for (path, fm) in transcripts {
+        let content = std::fs::read_to_string(path)?;
+        let body = crate::related::strip_related(strip_frontmatter(&content));
+        let (count, snippets) = scan_body(goal, body);
+        if count == 0 {
+            continue;
+        }
+
+        mentions.push(GoalMention {
+            doc_id: fm.doc_id.clone(),
+            title: fm.title.clone(),
+            date: fm.created_at.date_naive(),
+            path: path.clone(),
+            count,
+            snippets,
+        });
+    }
+
+    mentions.sort_by_key(|m| m.date);
+    Ok(mentions)
+}
+
+/// Number of meetings mentioning the goal per month (YYYY-MM)
+pub fn monthly_counts(mentions: &[GoalMention]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for mention in mentions {
+        *counts
+            .entry(mention.date.format("%Y-%m").to_string())
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Renders monthly counts as a horizontal bar chart scaled to `width` characters
+pub fn render_chart(counts: &BTreeMap<String, usize>, width: usize) -> String {
+    let max = counts.values().copied().max().unwrap_or(0);
+    if max == 0 {
+        return String::new();
+    }
+
+    counts
+        .iter()
+        .map(|(month, &count)| {
+            let len = (count * width + max - 1) / max;
+            format!("{}  {:<width$}  {}\n", month, "#".repeat(len), count)
+        })
+        .collect()
+}
+
+/// Everything reported for one goal
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalReport {
+    pub goal: Goal,
+    pub mentions: Vec<GoalMention>,
+    pub monthly: BTreeMap<String, usize>,
+    /// LLM-extracted latest status, when requested
+    pub status: Option<String>,
+}
+
+/// Minimum cosine similarity for a semantic-only match to count as a mention
+#[cfg(feature = "semantic")]
+const SEMANTIC_THRESHOLD: f32 = 0.8;
+
+/// Adds meetings that are semantically close to the goal but never use its keywords
+#[cfg(feature = "semantic")]
+pub fn add_semantic_mentions(
+    paths: &crate::Paths,
+    goal: &Goal,
+    mentions: &mut Vec<GoalMention>,
+) -> Result<()> {
+    let query = format!("{}: {}", goal.name, goal.keywords.join(", "));
+
+    for result in crate::embeddings::semantic_search(paths, &query, 20)? {
+        if result.score < SEMANTIC_THRESHOLD || mentions.iter().any(|m| m.doc_id == result.doc_id) {
+            continue;
+        }
+        let Ok(date) = NaiveDate::parse_from_str(&result.date, "%Y-%m-%d") else {
+            continue;
+        };
+
+        mentions.push(GoalMention {
+            doc_id: result.doc_id,
+            title: result.title,
+            date,
+            path: PathBuf::from(result.path),
+            count: 0,
+            snippets: Vec::new(),
+        });
+    }
+
+    mentions.sort_by_key(|m| m.date);
+    Ok(())
+}
+
+/// Builds the LLM input from the most recent mentions' snippets, oldest first
+pub fn status_excerpts(goal: &Goal, mentions: &[GoalMention], recent: usize) -> String {
+    let start = mentions.len().saturating_sub(recent);
+    let mut text = format!("Goal: {}\n\n", goal.name);
+
+    for mention in &mentions[start..] {
+        text.push_str(&format!(
+            "## {} ({})\n",
+            mention.title.as_deref().unwrap_or("Untitled"),
+            mention.date
+        ));
+        for snippet in &mention.snippets {
+            text.push_str(&format!("- {}\n", snippet));
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal() -> Goal {
+        Goal {
+            name: "Launch v2".into(),
+            keywords: vec!["v2 launch".into(), "Churn".into()],
+        }
+    }
+
+    fn mention(date: &str) -> GoalMention {
+        GoalMention {
+            doc_id: "d".into(),
+            title: Some("Sync".into()),
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            path: PathBuf::from("x.md"),
+            count: 1,
+            snippets: vec![format!("said on {}", date)],
+        }
+    }
+
+    #[test]
+    fn test_goals_file_parses() {
+        let file: GoalsFile = toml::from_str(EXAMPLE_GOALS).unwrap();
+        assert_eq!(file.goals.len(), 2);
+        assert_eq!(file.goals[1].keywords, vec!["churn", "retention"]);
+    }
+
+    #[test]
+    fn test_scan_body_counts_case_insensitively() {
+        let body = "**Ann:** The V2 launch slips a week.\n**Bob:** churn is flat, churn is fine.\n**Cy:** lunch?";
+        let (count, snippets) = scan_body(&goal(), body);
+        assert_eq!(count, 3);
+        assert_eq!(snippets.len(), 2);
+        assert!(snippets[0].contains("V2 launch"));
+    }
+
+    #[test]
+    fn test_scan_body_falls_back_to_name() {
+        let goal = Goal {
+            name: "Hiring".into(),
+            keywords: vec![],
+        };
+        assert_eq!(scan_body(&goal, "hiring plan").0, 1);
+    }
+
+    #[test]
+    fn test_monthly_counts_and_chart() {
+        let mentions = vec![
+            mention("2025-01-03"),
+            mention("2025-01-20"),
+            mention("2025-03-01"),
+        ];
+        let counts = monthly_counts(&mentions);
+        assert_eq!(counts["2025-01"], 2);
+        assert_eq!(counts["2025-03"], 1);
+
+        let chart = render_chart(&counts, 4);
+        assert_eq!(chart, "2025-01  ####  2\n2025-03  ##    1\n");
+    }
+
+    #[test]
+    fn test_status_excerpts_uses_recent_mentions() {
+        let mentions = vec![
+            mention("2025-01-01"),
+            mention("2025-02-01"),
+            mention("2025-03-01"),
+        ];
+        let text = status_excerpts(&goal(), &mentions, 2);
+        assert!(text.starts_with("Goal: Launch v2"));
+        assert!(!text.contains("2025-01-01"));
+        assert!(text.contains("said on 2025-03-01"));
+    }
+}
@@ -0,0 +1,268 @@
+// ABOUTME: Just enough IMAP4rev1 to read one folder: login, select, search, fetch, flag
+// ABOUTME: Runs over TLS (native-tls) or, in tests, any scripted Read + Write stream
+
+use crate::{Error, Result};
+use native_tls::TlsConnector;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// How long to wait for the server before giving up
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// One untagged response line, with the literals (`{n}` blocks) it carried
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Response {
+    /// The line without its literals; each one is left as `{n}`
+    pub line: String,
+    pub literals: Vec<Vec<u8>>,
+}
+
+/// A logged-in IMAP connection
+pub struct Session<S: Read + Write> {
+    stream: BufReader<S>,
+    tag: u32,
+}
+
+/// Connects to `host:port` over TLS and reads the greeting
+pub fn connect(host: &str, port: u16) -> Result<Session<native_tls::TlsStream<TcpStream>>> {
+    let failed = |e: &dyn std::fmt::Display| {
+        Error::Email(format!("Can't connect to {}:{}: {}", host, port, e))
+    };
+    let tcp = TcpStream::connect((host, port)).map_err(|e| failed(&e))?;
+    tcp.set_read_timeout(Some(TIMEOUT))
+        .map_err(|e| failed(&e))?;
+    tcp.set_write_timeout(Some(TIMEOUT))
+        .map_err(|e| failed(&e))?;
+    let tls = TlsConnector::new()
+        .map_err(|e| failed(&e))?
+        .connect(host, tcp)
+        .map_err(|e| failed(&e))?;
+    Session::start(tls)
+}
+
+impl<S: Read + Write> Session<S> {
+    /// A session on an open stream, once the server has said hello
+    pub fn start(stream: S) -> Result<Self> {
+        let mut session = Session {
+            stream: BufReader::new(stream),
+            tag: 0,
+        };
+        let greeting = session.read_line()?;
+        if !greeting.starts_with("* OK") && !greeting.starts_with("* PREAUTH") {
+            return Err(Error::Email(format!(
+                "Unexpected greeting: {}",
+                greeting.trim_end()
+            )));
+        }
+        Ok(session)
+    }
+
+    pub fn login(&mut self, username: &str, password: &str) -> Result<()> {
+        let command = format!("LOGIN {} {}", quote(username), quote(password));
+        self.run(&command).map(|_| ()).map_err(|e| match e {
+            Error::Email(message) => Error::Auth(format!("IMAP login failed: {}", message)),
+            other => other,
+        })
+    }
+
+    /// Opens `folder` read-write, so messages can be flagged as read
+    pub fn select(&mut self, folder: &str) -> Result<()> {
+        self.run(&format!("SELECT {}", quote(folder))).map(|_| ())
+    }
+
+    /// UIDs of the messages matching `criteria`, e.g. `UNSEEN` or `ALL`
+    pub fn search(&mut self, criteria: &str) -> Result<Vec<u32>> {
+        let mut uids = Vec::new();
+        for response in self.run(&format!("UID SEARCH {}", criteria))? {
+            if let Some(rest) = response.line.strip_prefix("* SEARCH") {
+                uids.extend(
+                    rest.split_whitespace()
+                        .filter_map(|n| n.parse::<u32>().ok()),
+                );
+            }
+        }
+        Ok(uids)
+    }
+
+    /// The full message with `uid`, without marking it read
+    pub fn fetch(&mut self, uid: u32) -> Result<Option<Vec<u8>>> {
+        let responses = self.run(&format!("UID FETCH {} BODY.PEEK[]", uid))?;
+        Ok(responses
+            .into_iter()
+            .find(|r| r.line.contains(" FETCH ") && r.line.contains("BODY[]"))
+            .and_then(|r| r.literals.into_iter().next()))
+    }
+
+    /// Flags the messages as read
+    pub fn mark_seen(&mut self, uids: &[u32]) -> Result<()> {
+        if uids.is_empty() {
+            return Ok(());
+        }
+        let set: Vec<String> = uids.iter().map(|uid| uid.to_string()).collect();
+        self.run(&format!(
+            "UID STORE {} +FLAGS.SILENT (\\Seen)",
+            set.join(",")
+        ))
+        .map(|_| ())
+    }
+
+    pub fn logout(mut self) -> Result<()> {
+        self.run("LOGOUT").map(|_| ())
+    }
+
+    /// Sends a command and collects the untagged responses up to its tagged one
+    fn run(&mut self, command: &str) -> Result<Vec<Response>> {
+        self.tag += 1;
+        let tag = format!("A{}", self.tag);
+        let stream = self.stream.get_mut();
+        write!(stream, "{} {}\r\n", tag, command)?;
+        stream.flush()?;
+
+        let mut responses = Vec::new();
+        loop {
+            let line = self.read_line()?;
+            if let Some(status) = line.strip_prefix(&format!("{} ", tag)) {
+                let status = status.trim_end();
+                if status.starts_with("OK") {
+                    return Ok(responses);
+                }
+                // Never echo the password back in an error
+                let verb = command.split_whitespace().next().unwrap_or(command);
+                return Err(Error::Email(format!("{} failed: {}", verb, status)));
+            }
+            responses.push(self.read_literals(line)?);
+        }
+    }
+
+    /// Reads the literals a response line announces with a trailing `{n}`
+    fn read_literals(&mut self, mut line: String) -> Result<Response> {
+        let mut response = Response::default();
+        while let Some(size) = literal_size(&line) {
+            let mut literal = vec![0; size];
+            self.stream.read_exact(&mut literal)?;
+            response.literals.push(literal);
+            response.line.push_str(line.trim_end());
+            line = self.read_line()?;
+        }
+        response.line.push_str(line.trim_end());
+        Ok(response)
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        let mut line = Vec::new();
+        if self.stream.read_until(b'\n', &mut line)? == 0 {
+            return Err(Error::Email("Server closed the connection".to_string()));
+        }
+        Ok(String::from_utf8_lossy(&line).into_owned())
+    }
+}
+
+/// The size `n` of a literal announced at the end of a line as `{n}`
+fn literal_size(line: &str) -> Option<usize> {
+    let line = line.trim_end();
+    let open = line.strip_suffix('}')?.rfind('{')?;
+    line[open + 1..line.len() - 1].parse().ok()
+}
+
+/// A quoted IMAP string
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Replays a server transcript and records what the client sent
+    struct Scripted {
+        server: Cursor<Vec<u8>>,
+        sent: Vec<u8>,
+    }
+
+    impl Read for Scripted {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.server.read(buf)
+        }
+    }
+
+    impl Write for Scripted {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.sent.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn scripted(server: &str) -> Session<Scripted> {
+        Session::start(Scripted {
+            server: Cursor::new(server.as_bytes().to_vec()),
+            sent: Vec::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_session() {
+        let message = "Subject: Hi\r\n\r\nBody\r\n";
+        let server = format!(
+            "* OK IMAP4rev1 ready\r\n\
+             A1 OK LOGIN completed\r\n\
+             * 3 EXISTS\r\n\
+             A2 OK [READ-WRITE] SELECT completed\r\n\
+             * SEARCH 4 7\r\n\
+             A3 OK SEARCH completed\r\n\
+             * 2 FETCH (UID 7 BODY[] {{{}}}\r\n{})\r\n\
+             A4 OK FETCH completed\r\n\
+             A5 OK STORE completed\r\n",
+            message.len(),
+            message
+        );
+        let mut session = scripted(&server);
+        session.login("me@example.com", "p\"w").unwrap();
+        session.select("Meeting notes").unwrap();
+        assert_eq!(session.search("UNSEEN").unwrap(), vec![4, 7]);
+        assert_eq!(session.fetch(7).unwrap().unwrap(), message.as_bytes());
+        session.mark_seen(&[4, 7]).unwrap();
+
+        let sent = String::from_utf8(session.stream.get_ref().sent.clone()).unwrap();
+        assert_eq!(
+            sent,
+            "A1 LOGIN \"me@example.com\" \"p\\\"w\"\r\n\
+             A2 SELECT \"Meeting notes\"\r\n\
+             A3 UID SEARCH UNSEEN\r\n\
+             A4 UID FETCH 7 BODY.PEEK[]\r\n\
+             A5 UID STORE 4,7 +FLAGS.SILENT (\\Seen)\r\n"
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session =
+            scripted("* OK ready\r\nA1 NO [AUTHENTICATIONFAILED] Invalid credentials\r\n");
+        let err = session.login("me", "secret").unwrap_err();
+        assert!(matches!(err, Error::Auth(_)));
+        assert!(!err.to_string().contains("secret"), "{}", err);
+
+        let mut session = scripted("* OK ready\r\nA1 NO Mailbox doesn't exist\r\n");
+        let err = session.select("Missing").unwrap_err().to_string();
+        assert!(
+            err.contains("SELECT failed: NO Mailbox doesn't exist"),
+            "{}",
+            err
+        );
+
+        let mut session = scripted("* OK ready\r\n");
+        assert!(session.search("ALL").is_err());
+    }
+
+    #[test]
+    fn test_literal_size() {
+        assert_eq!(literal_size("* 1 FETCH (BODY[] {42}\r\n"), Some(42));
+        assert_eq!(literal_size("* 1 FETCH (FLAGS (\\Seen))\r\n"), None);
+        assert_eq!(literal_size("* OK {not a number}\r\n"), None);
+    }
+}
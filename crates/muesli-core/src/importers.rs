@@ -0,0 +1,299 @@
+// ABOUTME: Fireflies.ai and Otter.ai export JSON read as meetings for `muesli import`
+// ABOUTME: Maps their speakers and timed segments onto the captions every import is built from
+
+use crate::ingest::{Caption, ImportOptions, ImportedMeeting, Recorded};
+use crate::{Error, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// A Fireflies.ai transcript, as its API and JSON download return it
+///
+/// Sentence times are seconds from the start; `date` is milliseconds since the epoch.
+#[derive(Debug, Deserialize)]
+struct FirefliesTranscript {
+    id: Option<String>,
+    title: Option<String>,
+    date: Option<f64>,
+    #[serde(rename = "dateString")]
+    date_string: Option<DateTime<Utc>>,
+    /// Minutes
+    duration: Option<f64>,
+    #[serde(default)]
+    participants: Vec<String>,
+    sentences: Vec<FirefliesSentence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FirefliesSentence {
+    speaker_name: Option<String>,
+    text: String,
+    start_time: f64,
+    end_time: f64,
+}
+
+/// An Otter.ai speech, as its JSON export returns it
+///
+/// Segment offsets are milliseconds from the start; `created_at` is seconds since the epoch.
+#[derive(Debug, Deserialize)]
+struct OtterSpeech {
+    #[serde(alias = "speech_id")]
+    otid: Option<String>,
+    title: Option<String>,
+    created_at: Option<i64>,
+    /// Seconds
+    duration: Option<u64>,
+    #[serde(default)]
+    speakers: Vec<OtterSpeaker>,
+    transcripts: Vec<OtterSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OtterSpeaker {
+    #[serde(alias = "speaker_id")]
+    id: Value,
+    speaker_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OtterSegment {
+    start_offset: u64,
+    end_offset: u64,
+    speaker_id: Option<Value>,
+    transcript: String,
+}
+
+/// Every meeting in a Fireflies.ai or Otter.ai export
+///
+/// The file may hold one transcript or a list of them, bare or wrapped the way
+/// the tools' APIs return them. Meetings are identified by the tool's own ID as
+/// `fireflies-<id>` or `otter-<id>`, so exporting and importing again updates them.
+pub fn read_export(path: &Path, options: &ImportOptions) -> Result<Vec<ImportedMeeting>> {
+    let invalid = |reason: String| Error::InvalidInput(format!("{}: {}", path.display(), reason));
+    let content: Value = serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| invalid(format!("not JSON ({})", e)))?;
+
+    let items = match unwrap_response(content) {
+        Value::Array(items) => items,
+        item => vec![item],
+    };
+    let mut meetings = Vec::new();
+    for item in items {
+        let (recorded, captions) = if item.get("sentences").is_some() {
+            let transcript: FirefliesTranscript = serde_json::from_value(item)
+                .map_err(|e| invalid(format!("unexpected Fireflies export ({})", e)))?;
+            from_fireflies(transcript)
+        } else if item.get("transcripts").is_some() {
+            let speech: OtterSpeech = serde_json::from_value(item)
+                .map_err(|e| invalid(format!("unexpected Otter export ({})", e)))?;
+            from_otter(speech)
+        } else {
+            return Err(invalid(
+                "not a Fireflies.ai or Otter.ai export (no sentences or transcripts)".to_string(),
+            ));
+        };
+        meetings.push(ImportedMeeting::assemble(
+            path, recorded, captions, options,
+        )?);
+    }
+    Ok(meetings)
+}
+
+/// The transcripts inside an API response, e.g. `{"data": {"transcript": ...}}` or `{"speech": ...}`
+fn unwrap_response(mut content: Value) -> Value {
+    if let Some(data) = content.get_mut("data") {
+        content = data.take();
+    }
+    for key in ["transcript", "speech", "speeches"] {
+        if let Some(inner) = content.get_mut(key) {
+            return inner.take();
+        }
+    }
+    // Fireflies lists transcripts under "transcripts", Otter's name for a speech's segments
+    let listed = content
+        .get("transcripts")
+        .and_then(|items| items.get(0))
+        .is_some_and(|first| first.get("sentences").is_some());
+    if listed {
+        return content["transcripts"].take();
+    }
+    content
+}
+
+fn from_fireflies(transcript: FirefliesTranscript) -> (Recorded, Vec<Caption>) {
+    let seconds_ms = |seconds: f64| (seconds.max(0.0) * 1000.0).round() as u64;
+    let captions = transcript
+        .sentences
+        .into_iter()
+        .filter(|s| !s.text.trim().is_empty())
+        .map(|s| Caption {
+            start_ms: seconds_ms(s.start_time),
+            end_ms: seconds_ms(s.end_time.max(s.start_time)),
+            speaker: s.speaker_name.filter(|name| !name.trim().is_empty()),
+            text: s.text.trim().to_string(),
+        })
+        .collect();
+    let created_at = transcript
+        .date_string
+        .or_else(|| Utc.timestamp_millis_opt(transcript.date? as i64).single());
+    let recorded = Recorded {
+        source: "fireflies",
+        id: transcript.id.map(|id| format!("fireflies-{}", id)),
+        title: transcript.title,
+        created_at,
+        participants: transcript.participants,
+        duration_seconds: transcript
+            .duration
+            .map(|minutes| (minutes * 60.0).round() as u64),
+    };
+    (recorded, captions)
+}
+
+fn from_otter(speech: OtterSpeech) -> (Recorded, Vec<Caption>) {
+    let names: HashMap<String, String> = speech
+        .speakers
+        .into_iter()
+        .map(|speaker| (id_string(&speaker.id), speaker.speaker_name))
+        .collect();
+    let captions = speech
+        .transcripts
+        .into_iter()
+        .filter(|s| !s.transcript.trim().is_empty())
+        .map(|s| Caption {
+            start_ms: s.start_offset,
+            end_ms: s.end_offset.max(s.start_offset),
+            speaker: s
+                .speaker_id
+                .as_ref()
+                .and_then(|id| names.get(&id_string(id)))
+                .cloned(),
+            text: s.transcript.trim().to_string(),
+        })
+        .collect();
+    let recorded = Recorded {
+        source: "otter",
+        id: speech.otid.map(|id| format!("otter-{}", id)),
+        title: speech.title,
+        created_at: speech
+            .created_at
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single()),
+        participants: Vec::new(),
+        duration_seconds: speech.duration,
+    };
+    (recorded, captions)
+}
+
+/// Otter speaker IDs are numbers in some exports and strings in others
+fn id_string(id: &Value) -> String {
+    match id {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const FIREFLIES: &str = r#"{"data": {"transcript": {
+        "id": "01HX",
+        "title": "Vendor review",
+        "date": 1740823200000,
+        "duration": 31.5,
+        "participants": ["alice@acme.com", "bob@vendor.io"],
+        "sentences": [
+            {"index": 0, "speaker_name": "Alice", "text": "Let's start.", "start_time": 1.2, "end_time": 2.5},
+            {"index": 1, "speaker_name": "Bob", "text": " Sure. ", "start_time": 3, "end_time": 4.25},
+            {"index": 2, "speaker_name": "Bob", "text": "", "start_time": 5, "end_time": 5}
+        ]
+    }}}"#;
+
+    const OTTER: &str = r#"[{
+        "otid": "abc123",
+        "title": "Design sync",
+        "created_at": 1740909600,
+        "duration": 900,
+        "speakers": [{"id": 1, "speaker_name": "Carol"}, {"id": 2, "speaker_name": "Dan"}],
+        "transcripts": [
+            {"start_offset": 0, "end_offset": 4000, "speaker_id": 1, "transcript": "Mockups are up."},
+            {"start_offset": 4500, "end_offset": 6000, "speaker_id": 2, "transcript": "Looks good."},
+            {"start_offset": 7000, "end_offset": 8000, "speaker_id": null, "transcript": "Who's that?"}
+        ]
+    }]"#;
+
+    fn read(name: &str, content: &str, options: &ImportOptions) -> Result<Vec<ImportedMeeting>> {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(name);
+        fs::write(&path, content).unwrap();
+        read_export(&path, options)
+    }
+
+    #[test]
+    fn test_fireflies_export() {
+        let meetings = read("fireflies.json", FIREFLIES, &ImportOptions::default()).unwrap();
+        assert_eq!(meetings.len(), 1);
+        let meeting = &meetings[0];
+        assert_eq!(meeting.summary.id, "fireflies-01HX");
+        assert_eq!(meeting.summary.title.as_deref(), Some("Vendor review"));
+        assert_eq!(
+            meeting.metadata.created_at.to_rfc3339(),
+            "2025-03-01T10:00:00+00:00"
+        );
+        assert_eq!(meeting.metadata.duration_seconds, Some(1890));
+        assert_eq!(
+            meeting.metadata.participants,
+            vec!["alice@acme.com", "bob@vendor.io"]
+        );
+
+        let entries = &meeting.transcript.entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].speaker.as_deref(), Some("Bob"));
+        assert_eq!(entries[1].text, "Sure.");
+        assert_eq!(
+            entries[1].start.as_deref(),
+            Some("2025-03-01T10:00:03.000Z")
+        );
+        assert_eq!(entries[1].end.as_deref(), Some("2025-03-01T10:00:04.250Z"));
+    }
+
+    #[test]
+    fn test_otter_export() {
+        let options = ImportOptions {
+            title: Some("Weekly design".to_string()),
+            date: None,
+        };
+        let meetings = read("otter.json", OTTER, &options).unwrap();
+        let meeting = &meetings[0];
+        assert_eq!(meeting.summary.id, "otter-abc123");
+        assert_eq!(meeting.summary.title.as_deref(), Some("Weekly design"));
+        assert_eq!(meeting.metadata.participants, vec!["Carol", "Dan"]);
+        assert_eq!(meeting.metadata.duration_seconds, Some(900));
+
+        let entries = &meeting.transcript.entries;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].speaker.as_deref(), Some("Carol"));
+        assert_eq!(
+            entries[1].start.as_deref(),
+            Some("2025-03-02T10:00:04.500Z")
+        );
+        assert_eq!(entries[2].speaker, None);
+
+        let wrapped = format!("{{\"speech\": {}}}", &OTTER[1..OTTER.len() - 1]);
+        assert_eq!(
+            read("otter.json", &wrapped, &ImportOptions::default()).unwrap()[0]
+                .summary
+                .id,
+            "otter-abc123"
+        );
+    }
+
+    #[test]
+    fn test_not_an_export() {
+        assert!(read("raw.json", r#"[{"text": "hi"}]"#, &ImportOptions::default()).is_err());
+        assert!(read("bad.json", "{", &ImportOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+// ABOUTME: Text index and vector store statistics for `muesli index stats`
+// ABOUTME: Reports counts, embedding details and doc_ids present in only one store
+
+use super::text;
+use crate::{Paths, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Summary of the vector store in the index directory
+#[derive(Debug, Clone, Serialize)]
+pub struct VectorStats {
+    pub count: usize,
+    pub dim: usize,
+    /// Embedding model recorded with the vectors (None for older stores)
+    pub model: Option<String>,
+    /// Mean L2 norm; far from 1.0 suggests vectors from a different pipeline
+    pub average_norm: f32,
+}
+
+/// Health of the search indexes and how far they have drifted apart
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    pub text_docs: usize,
+    /// None when there is no vector store (or embeddings are not compiled in)
+    pub vectors: Option<VectorStats>,
+    /// In the text index but without an embedding
+    pub missing_vectors: Vec<String>,
+    /// Embedded but absent from the text index
+    pub missing_text: Vec<String>,
+}
+
+impl IndexStats {
+    /// True when both stores cover the same documents
+    pub fn in_sync(&self) -> bool {
+        self.missing_vectors.is_empty() && self.missing_text.is_empty()
+    }
+}
+
+/// Splits two doc_id sets into (only in text, only in vectors), both sorted
+pub fn drift(
+    text_ids: &BTreeSet<String>,
+    vector_ids: &BTreeSet<String>,
+) -> (Vec<String>, Vec<String>) {
+    (
+        text_ids.difference(vector_ids).cloned().collect(),
+        vector_ids.difference(text_ids).cloned().collect(),
+    )
+}
+
+/// Gathers statistics for the text index and, when present, the vector store
+pub fn collect(paths: &Paths) -> Result<IndexStats> {
+    let index = text::open_for_search(&paths.index_dir)?;
+    let text_ids = text::indexed_doc_ids(&index)?;
+
+    #[cfg(feature = "semantic")]
+    {
+        let vector_path = paths.index_dir.join("vectors");
+        if vector_path.with_extension("meta.json").exists() {
+            let store = crate::embeddings::VectorStore::load(&vector_path)?;
+            let vector_ids: BTreeSet<String> = store.doc_ids().map(String::from).collect();
+            let (missing_vectors, missing_text) = drift(&text_ids, &vector_ids);
+
+            return Ok(IndexStats {
+                text_docs: text_ids.len(),
+                vectors: Some(VectorStats {
+                    count: store.len(),
+                    dim: store.dim(),
+                    model: store.model().map(|m| m.to_string()),
+                    average_norm: store.average_norm(),
+                }),
+                missing_vectors,
+                missing_text,
+            });
+        }
+    }
+
+    Ok(IndexStats {
+        text_docs: text_ids.len(),
+        vectors: None,
+        missing_vectors: Vec::new(),
+        missing_text: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(list: &[&str]) -> BTreeSet<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_drift_reports_both_directions() {
+        let (missing_vectors, missing_text) = drift(&ids(&["a", "b", "c"]), &ids(&["b", "d"]));
+        assert_eq!(missing_vectors, vec!["a", "c"]);
+        assert_eq!(missing_text, vec!["d"]);
+    }
+
+    #[test]
+    fn test_collect_without_vector_store() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let index = text::create_or_open_index(&paths.index_dir).unwrap();
+        text::index_markdown(
+            &index,
+            "d1",
+            None,
+            "2025-10-29",
+            "body",
+            std::path::Path::new("/test/d1.md"),
+        )
+        .unwrap();
+
+        let stats = collect(&paths).unwrap();
+        assert_eq!(stats.text_docs, 1);
+        assert!(stats.vectors.is_none());
+        assert!(stats.in_sync());
+    }
+}
@@ -0,0 +1,401 @@
+// ABOUTME: Top TF-IDF terms of recent meetings, read from the index's term dictionaries
+// ABOUTME: Backs `muesli terms` with a ranked list or an SVG word cloud
+
+use super::text;
+use crate::error::{Error, Result};
+use crate::search::DocKind;
+use crate::Paths;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use tantivy::schema::{IndexRecordOption, Value};
+use tantivy::{DocAddress, DocSet, Index, Postings, TantivyDocument, TERMINATED};
+
+/// Words too common in conversation to say anything about a meeting
+const STOPWORDS: &str = "\
+    about actually after again all also and any are back because been before being but \
+    can could did does doing don down even for from get getting going gonna good got had \
+    has have her here him his how into its just know let like little lot make maybe mean \
+    more much need not now okay one only other our out over really right said say see \
+    she should some something speaker sure take than that the their them then there \
+    these they thing things think this those through time too transcript two uh um want \
+    was way well were what when where which who why will with would yeah yes you your";
+
+/// Shortest term worth reporting; shorter ones are mostly fillers
+const MIN_TERM_CHARS: usize = 3;
+
+/// One term and how it ranks in the period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermScore {
+    pub term: String,
+    /// Occurrences in the period's meetings times the term's inverse document frequency
+    pub score: f64,
+    pub occurrences: u64,
+    /// Meetings in the period that use it
+    pub meetings: usize,
+}
+
+/// The top terms of the transcripts dated between `since` and `until`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermsReport {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub meetings: usize,
+    pub terms: Vec<TermScore>,
+}
+
+/// A live transcript in the index
+#[derive(Debug, Clone)]
+pub(crate) struct Meeting {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub participants: Vec<String>,
+}
+
+impl Meeting {
+    pub fn in_period(&self, since: Option<NaiveDate>, until: Option<NaiveDate>) -> bool {
+        self.date.is_some_and(|date| {
+            since.map_or(true, |s| date >= s) && until.map_or(true, |u| date <= u)
+        })
+    }
+}
+
+/// Every transcript's body terms, read from the term dictionaries
+pub(crate) struct TermScan {
+    pub meetings: Vec<Meeting>,
+    /// Per candidate term: (index into `meetings`, occurrences) for each meeting using it
+    pub terms: HashMap<String, Vec<(usize, u32)>>,
+}
+
+impl TermScan {
+    /// Lowercased words of the participant names of the meetings `included` picks
+    pub fn names(&self, included: impl Fn(&Meeting) -> bool) -> HashSet<String> {
+        self.meetings
+            .iter()
+            .filter(|m| included(m))
+            .flat_map(|m| m.participants.iter().flat_map(|p| words(p)))
+            .collect()
+    }
+}
+
+/// Reads every live transcript and, per term worth reporting, the meetings using it
+pub(crate) fn scan(index: &Index) -> Result<TermScan> {
+    let schema = index.schema();
+    let field = |name: &str| {
+        schema
+            .get_field(name)
+            .map_err(|e| Error::Indexing(format!("Missing {} field: {}", name, e)))
+    };
+    let (body_field, doc_id_field, title_field, date_field, kind_field, participants_field) = (
+        field("body")?,
+        field("doc_id")?,
+        field("title")?,
+        field("date")?,
+        field("kind")?,
+        field("participants")?,
+    );
+    let searcher = index
+        .reader()
+        .map_err(|e| Error::Indexing(format!("Failed to create reader: {}", e)))?
+        .searcher();
+
+    let mut meetings = Vec::new();
+    let mut terms: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+    for (segment_ord, segment) in searcher.segment_readers().iter().enumerate() {
+        // Per document of the segment: its place in `meetings`, if a live transcript
+        let mut positions: Vec<Option<usize>> = vec![None; segment.max_doc() as usize];
+        for doc in 0..segment.max_doc() {
+            if segment
+                .alive_bitset()
+                .is_some_and(|alive| alive.is_deleted(doc))
+            {
+                continue;
+            }
+            let document: TantivyDocument = searcher
+                .doc(DocAddress::new(segment_ord as u32, doc))
+                .map_err(|e| Error::Indexing(format!("Failed to retrieve document: {}", e)))?;
+            let text = |field| {
+                document
+                    .get_first(field)
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            };
+            if text(kind_field).as_deref() == Some(DocKind::Summary.as_str()) {
+                continue;
+            }
+            positions[doc as usize] = Some(meetings.len());
+            meetings.push(Meeting {
+                doc_id: text(doc_id_field).unwrap_or_default(),
+                title: text(title_field),
+                date: text(date_field).and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+                participants: document
+                    .get_all(participants_field)
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect(),
+            });
+        }
+
+        let inverted = segment
+            .inverted_index(body_field)
+            .map_err(|e| Error::Indexing(format!("Failed to read terms: {}", e)))?;
+        let mut stream = inverted.terms().stream()?;
+        while stream.advance() {
+            let Ok(term) = std::str::from_utf8(stream.key()) else {
+                continue;
+            };
+            if !is_candidate(term) {
+                continue;
+            }
+            let mut postings = inverted
+                .read_postings_from_terminfo(stream.value(), IndexRecordOption::WithFreqs)?;
+            let mut uses = Vec::new();
+            let mut doc = postings.doc();
+            while doc != TERMINATED {
+                if let Some(position) = positions[doc as usize] {
+                    uses.push((position, postings.term_freq()));
+                }
+                doc = postings.advance();
+            }
+            if !uses.is_empty() {
+                terms.entry(term.to_string()).or_default().extend(uses);
+            }
+        }
+    }
+    Ok(TermScan { meetings, terms })
+}
+
+/// Ranks the body terms of the transcripts between `since` and `until` (both inclusive)
+///
+/// A term's score is its occurrences in the period weighted by a smoothed inverse
+/// document frequency over every transcript, so words every meeting uses sink below
+/// the ones this period is about. Stopwords, numbers, short words and the
+/// period's participant names are left out. `limit` caps the list.
+pub fn top_terms(
+    index: &Index,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    limit: usize,
+) -> Result<TermsReport> {
+    let scan = scan(index)?;
+    let in_period: Vec<bool> = scan
+        .meetings
+        .iter()
+        .map(|m| m.in_period(since, until))
+        .collect();
+    let names = scan.names(|m| m.in_period(since, until));
+    let transcripts = scan.meetings.len();
+
+    let mut terms: Vec<TermScore> = scan
+        .terms
+        .iter()
+        .filter(|(term, _)| !names.contains(*term))
+        .filter_map(|(term, uses)| {
+            let recent: Vec<u32> = uses
+                .iter()
+                .filter(|(meeting, _)| in_period[*meeting])
+                .map(|(_, count)| *count)
+                .collect();
+            let occurrences: u64 = recent.iter().map(|&c| c as u64).sum();
+            if occurrences == 0 {
+                return None;
+            }
+            let idf = ((1 + transcripts) as f64 / (1 + uses.len()) as f64).ln() + 1.0;
+            Some(TermScore {
+                term: term.clone(),
+                score: (occurrences as f64 * idf * 100.0).round() / 100.0,
+                occurrences,
+                meetings: recent.len(),
+            })
+        })
+        .collect();
+    terms.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.term.cmp(&b.term))
+    });
+    terms.truncate(limit);
+
+    Ok(TermsReport {
+        since,
+        until,
+        meetings: in_period.iter().filter(|&&included| included).count(),
+        terms,
+    })
+}
+
+/// The top terms in the data directory's index
+pub fn collect(
+    paths: &Paths,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    limit: usize,
+) -> Result<TermsReport> {
+    let index = text::open_for_search(&paths.index_dir)?;
+    top_terms(&index, since, until, limit)
+}
+
+fn is_candidate(term: &str) -> bool {
+    term.chars().count() >= MIN_TERM_CHARS
+        && !term.chars().all(|c| c.is_ascii_digit())
+        && !STOPWORDS.split_whitespace().any(|w| w == term)
+}
+
+/// Lowercased words of a participant, as the body tokenizer would split them
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+}
+
+const CLOUD_WIDTH: f64 = 800.0;
+const CLOUD_HEIGHT: f64 = 500.0;
+const CLOUD_COLORS: &[&str] = &["#1f4e79", "#2e75b6", "#c55a11", "#548235", "#7030a0"];
+
+/// A word cloud of the terms as a standalone SVG
+///
+/// Font size grows with the square root of the score. Words are laid out on a
+/// spiral from the centre, biggest first; any that don't fit are left out.
+pub fn word_cloud_svg(terms: &[TermScore]) -> String {
+    let top = terms.iter().map(|t| t.score).fold(0.0, f64::max);
+    let mut placed: Vec<(f64, f64, f64, f64)> = Vec::new();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"Helvetica, Arial, sans-serif\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        w = CLOUD_WIDTH,
+        h = CLOUD_HEIGHT
+    );
+
+    for (i, term) in terms.iter().enumerate() {
+        let size = if top > 0.0 {
+            (14.0 + 50.0 * (term.score / top).sqrt()).round()
+        } else {
+            14.0
+        };
+        // Rough glyph metrics; close enough to keep words from overlapping
+        let width = term.term.chars().count() as f64 * size * 0.6;
+        let height = size;
+        let Some((x, y)) = find_spot(&placed, width, height) else {
+            continue;
+        };
+        placed.push((x, y, width, height));
+        // Terms are index tokens: letters and digits only, nothing to escape
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.0}\" y=\"{:.0}\" font-size=\"{}\" fill=\"{}\">{}</text>",
+            x,
+            y + height * 0.8,
+            size,
+            CLOUD_COLORS[i % CLOUD_COLORS.len()],
+            term.term
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// The first free spot for a `width` x `height` box along a spiral from the centre
+fn find_spot(placed: &[(f64, f64, f64, f64)], width: f64, height: f64) -> Option<(f64, f64)> {
+    let mut angle: f64 = 0.0;
+    while angle < 300.0 {
+        let radius = 3.0 * angle;
+        let x = CLOUD_WIDTH / 2.0 + radius * angle.cos() - width / 2.0;
+        let y = CLOUD_HEIGHT / 2.0 + radius * angle.sin() * 0.6 - height / 2.0;
+        let inside = x >= 0.0 && y >= 0.0 && x + width <= CLOUD_WIDTH && y + height <= CLOUD_HEIGHT;
+        let free = placed.iter().all(|&(px, py, pw, ph)| {
+            x + width <= px || px + pw <= x || y + height <= py || py + ph <= y
+        });
+        if inside && free {
+            return Some((x, y));
+        }
+        angle += 0.1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::text::{create_or_open_index, index_document_batch, IndexedDocument};
+    use crate::index::writer::WriterConfig;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn add(writer: &mut tantivy::IndexWriter, index: &Index, id: &str, date: &str, body: &str) {
+        let participants = vec!["Alice Smith".to_string()];
+        index_document_batch(
+            writer,
+            index,
+            &IndexedDocument {
+                doc_id: id,
+                title: None,
+                date,
+                body,
+                path: Path::new("/tmp/x.md"),
+                organizations: &[],
+                participants: &participants,
+                labels: &[],
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_top_terms() {
+        let temp = TempDir::new().unwrap();
+        let index = create_or_open_index(temp.path()).unwrap();
+        let mut writer = WriterConfig::default().writer(&index).unwrap();
+        add(
+            &mut writer,
+            &index,
+            "old",
+            "2025-01-05",
+            "**Alice:** The roadmap and the budget, yeah.",
+        );
+        add(
+            &mut writer,
+            &index,
+            "new1",
+            "2025-03-01",
+            "**Alice:** Pricing pricing pricing. The budget is fine, 2025.",
+        );
+        add(
+            &mut writer,
+            &index,
+            "new2",
+            "2025-03-08",
+            "**Alice:** Pricing for the migration.",
+        );
+        writer.commit().unwrap();
+
+        let since = NaiveDate::from_ymd_opt(2025, 3, 1);
+        let report = top_terms(&index, since, None, 10).unwrap();
+        assert_eq!(report.meetings, 2);
+        let terms: Vec<&str> = report.terms.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(terms, vec!["pricing", "fine", "migration", "budget"]);
+        assert_eq!(report.terms[0].occurrences, 4);
+        assert_eq!(report.terms[0].meetings, 2);
+        // Seen before the period too, so it ranks below an equally frequent new term
+        assert!(report.terms[3].score < report.terms[2].score);
+
+        let all = top_terms(&index, None, None, 1).unwrap();
+        assert_eq!(all.meetings, 3);
+        assert_eq!(all.terms.len(), 1);
+    }
+
+    #[test]
+    fn test_word_cloud_svg() {
+        let term = |term: &str, score: f64| TermScore {
+            term: term.to_string(),
+            score,
+            occurrences: 1,
+            meetings: 1,
+        };
+        let svg = word_cloud_svg(&[term("pricing", 10.0), term("budget", 2.5)]);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("font-size=\"64\" fill=\"#1f4e79\">pricing</text>"));
+        assert!(svg.contains("font-size=\"39\" fill=\"#2e75b6\">budget</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(word_cloud_svg(&[]).matches("<text").count(), 0);
+    }
+}
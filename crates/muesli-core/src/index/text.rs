@@ -0,0 +1,1646 @@
+// ABOUTME: Tantivy implementation for full-text search indexing
+// ABOUTME: Provides schema definition and document indexing functions
+
+use super::weights::SearchWeights;
+use super::writer::WriterConfig;
+use crate::error::{Error, Result};
+use crate::storage::CatalogEntry;
+use crate::util::organizations_from_participants;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tantivy::schema::{Schema, Value, STORED, STRING, TEXT};
+use tantivy::{doc, Index, Searcher, Term};
+
+pub use crate::search::{DocKind, SearchHit};
+
+/// The upsert key: a summary shares its meeting's doc_id but must not replace it
+fn upsert_key(kind: DocKind, doc_id: &str) -> String {
+    match kind {
+        DocKind::Transcript => doc_id.to_string(),
+        DocKind::Summary => format!("summary:{}", doc_id),
+    }
+}
+
+/// A document to add to the index, with optional structured metadata
+#[derive(Debug, Clone, Copy)]
+pub struct IndexedDocument<'a> {
+    pub doc_id: &'a str,
+    pub title: Option<&'a str>,
+    pub date: &'a str,
+    pub body: &'a str,
+    pub path: &'a Path,
+    pub organizations: &'a [String],
+    pub participants: &'a [String],
+    pub labels: &'a [String],
+}
+
+/// Optional restrictions applied on top of a text query
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilters {
+    /// Only match documents with a participant from this email domain
+    pub organization: Option<String>,
+    /// Only match documents with a participant whose name or email contains
+    /// these words in order (case-insensitive)
+    pub participant: Option<String>,
+    /// Only match documents carrying this label (case-insensitive)
+    pub label: Option<String>,
+    /// Drop documents with a participant matching any of these (matched like `participant`)
+    pub exclude_participants: Vec<String>,
+    /// Drop documents carrying any of these labels
+    pub exclude_labels: Vec<String>,
+    /// Also match saved summaries, not only transcripts
+    pub include_summaries: bool,
+}
+
+impl SearchFilters {
+    pub fn is_empty(&self) -> bool {
+        self.organization.is_none()
+            && self.participant.is_none()
+            && self.label.is_none()
+            && self.exclude_participants.is_empty()
+            && self.exclude_labels.is_empty()
+    }
+
+    /// Applies the filters to catalog metadata, mirroring what the index query does
+    ///
+    /// Used to post-filter semantic results, which have no index query to restrict.
+    pub fn matches(&self, entry: &CatalogEntry) -> bool {
+        let has_label = |label: &str| {
+            let label = label.trim().to_lowercase();
+            entry.labels.iter().any(|l| l.to_lowercase() == label)
+        };
+        let has_participant = |needle: &str| {
+            let needle = words(needle);
+            !needle.is_empty()
+                && entry
+                    .participants
+                    .iter()
+                    .any(|p| words(p).windows(needle.len()).any(|w| w == needle))
+        };
+
+        if let Some(org) = &self.organization {
+            // Older transcripts only carry participants
+            let organizations = if entry.organizations.is_empty() {
+                organizations_from_participants(&entry.participants)
+            } else {
+                entry.organizations.clone()
+            };
+            if !organizations.iter().any(|o| o.eq_ignore_ascii_case(org)) {
+                return false;
+            }
+        }
+
+        self.participant.as_deref().map_or(true, has_participant)
+            && self.label.as_deref().map_or(true, has_label)
+            && !self.exclude_participants.iter().any(|p| has_participant(p))
+            && !self.exclude_labels.iter().any(|l| has_label(l))
+    }
+}
+
+/// Lowercased alphanumeric words, approximating the index's default tokenizer
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Fields every index must have; older indexes missing any are rebuilt
+const REQUIRED_FIELDS: &[&str] = &[
+    "doc_id",
+    "title",
+    "date",
+    "body",
+    "path",
+    "organizations",
+    "participants",
+    "labels",
+    "kind",
+    "key",
+];
+
+/// Returns true if an index exists in the directory but predates the current schema
+pub fn schema_is_outdated(index_dir: &Path) -> bool {
+    match Index::open_in_dir(index_dir) {
+        Ok(index) => {
+            let schema = index.schema();
+            REQUIRED_FIELDS
+                .iter()
+                .any(|name| schema.get_field(name).is_err())
+        }
+        Err(_) => false,
+    }
+}
+
+/// Opens the index for reading, refusing to discard an outdated one
+///
+/// `create_or_open_index` recreates an outdated index empty, which only sync can
+/// repopulate; readers report the problem instead so the next sync rebuilds it.
+pub fn open_for_search(index_dir: &Path) -> Result<Index> {
+    if schema_is_outdated(index_dir) {
+        return Err(Error::Indexing(
+            "Search index format changed; run 'muesli sync' to rebuild it".to_string(),
+        ));
+    }
+    create_or_open_index(index_dir)
+}
+
+/// The vector store shares the index directory; its files are not Tantivy's
+fn is_vector_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.starts_with("vectors."))
+}
+
+/// Removes Tantivy's files from the index directory, leaving the vector store in place
+fn reset_index_dir(index_dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(index_dir)? {
+        let path = entry?.path();
+        if path.is_file() && !is_vector_file(&path) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Segment count, pending deletes and on-disk size of an index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexFootprint {
+    pub segments: usize,
+    /// Deleted or replaced documents still occupying space in segments
+    pub deleted_docs: u64,
+    /// Size of Tantivy's files (the vector store is not counted)
+    pub bytes: u64,
+}
+
+fn footprint(index: &Index, index_dir: &Path) -> Result<IndexFootprint> {
+    let metas = index
+        .searchable_segment_metas()
+        .map_err(|e| Error::Indexing(format!("Failed to read segments: {}", e)))?;
+
+    let mut bytes = 0;
+    for entry in std::fs::read_dir(index_dir)? {
+        let path = entry?.path();
+        if path.is_file() && !is_vector_file(&path) {
+            bytes += path.metadata()?.len();
+        }
+    }
+
+    Ok(IndexFootprint {
+        segments: metas.len(),
+        deleted_docs: metas.iter().map(|m| m.num_deleted_docs() as u64).sum(),
+        bytes,
+    })
+}
+
+/// Index footprint before and after `optimize`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptimizeReport {
+    pub before: IndexFootprint,
+    pub after: IndexFootprint,
+}
+
+/// Merges all segments into one, dropping deleted documents and unused files
+///
+/// Does nothing beyond measuring when the index is already a single clean segment.
+pub fn optimize(index_dir: &Path, config: &WriterConfig) -> Result<OptimizeReport> {
+    let index = open_for_search(index_dir)?;
+    let before = footprint(&index, index_dir)?;
+
+    if before.segments > 1 || before.deleted_docs > 0 {
+        let mut writer = config.writer(&index)?;
+        let segment_ids = index
+            .searchable_segment_ids()
+            .map_err(|e| Error::Indexing(format!("Failed to read segments: {}", e)))?;
+
+        writer
+            .merge(&segment_ids)
+            .wait()
+            .map_err(|e| Error::Indexing(format!("Failed to merge segments: {}", e)))?;
+        writer
+            .garbage_collect_files()
+            .wait()
+            .map_err(|e| Error::Indexing(format!("Failed to remove unused files: {}", e)))?;
+        writer
+            .wait_merging_threads()
+            .map_err(|e| Error::Indexing(format!("Failed to finish merging: {}", e)))?;
+    }
+
+    Ok(OptimizeReport {
+        before,
+        after: footprint(&index, index_dir)?,
+    })
+}
+
+/// Creates or opens a Tantivy index at the specified directory
+///
+/// An existing index with an outdated schema is discarded and recreated empty;
+/// callers should reindex from disk when `schema_is_outdated` reported true.
+pub fn create_or_open_index(index_dir: &Path) -> Result<Index> {
+    // Create directory if it doesn't exist
+    std::fs::create_dir_all(index_dir)?;
+
+    // Try to open existing index first
+    if schema_is_outdated(index_dir) {
+        reset_index_dir(index_dir)?;
+    } else if let Ok(index) = Index::open_in_dir(index_dir) {
+        return Ok(index);
+    }
+
+    // Create new index with schema
+    let mut schema_builder = Schema::builder();
+
+    // doc_id: STRING, STORED - primary key
+    schema_builder.add_text_field("doc_id", STRING | STORED);
+
+    // title: TEXT, STORED - analyzed for search and retrievable
+    schema_builder.add_text_field("title", TEXT | STORED);
+
+    // date: STRING, STORED - for sorting
+    schema_builder.add_text_field("date", STRING | STORED);
+
+    // body: TEXT - full markdown content
+    schema_builder.add_text_field("body", TEXT);
+
+    // path: STRING, STORED - absolute path to .md
+    schema_builder.add_text_field("path", STRING | STORED);
+
+    // organizations: STRING, STORED - participant email domains (multi-valued)
+    schema_builder.add_text_field("organizations", STRING | STORED);
+
+    // participants: TEXT, STORED - names and emails, tokenized so "alice" matches
+    schema_builder.add_text_field("participants", TEXT | STORED);
+
+    // labels: STRING, STORED - Granola labels, lowercased (multi-valued)
+    schema_builder.add_text_field("labels", STRING | STORED);
+
+    // kind: STRING, STORED - "transcript" or "summary"
+    schema_builder.add_text_field("kind", STRING | STORED);
+
+    // key: STRING - upsert key, unique per document and kind
+    schema_builder.add_text_field("key", STRING);
+
+    let schema = schema_builder.build();
+
+    Index::create_in_dir(index_dir, schema)
+        .map_err(|e| Error::Indexing(format!("Failed to create index: {}", e)))
+}
+
+/// Indexes a markdown document with upsert semantics (delete old + insert new)
+/// This function creates its own writer (with the default settings) and commits
+/// immediately. For batch operations, use `index_markdown_batch` instead.
+pub fn index_markdown(
+    index: &Index,
+    doc_id: &str,
+    title: Option<&str>,
+    date: &str,
+    body: &str,
+    path: &Path,
+) -> Result<()> {
+    let mut writer = WriterConfig::default().writer(index)?;
+
+    index_markdown_batch(&mut writer, index, doc_id, title, date, body, path)?;
+
+    // Commit the changes
+    writer
+        .commit()
+        .map_err(|e| Error::Indexing(format!("Failed to commit: {}", e)))?;
+
+    Ok(())
+}
+
+/// Indexes a markdown document using an existing writer (for batch operations)
+/// Does not commit - caller must call writer.commit() when ready
+pub fn index_markdown_batch(
+    writer: &mut tantivy::IndexWriter,
+    index: &Index,
+    doc_id: &str,
+    title: Option<&str>,
+    date: &str,
+    body: &str,
+    path: &Path,
+) -> Result<()> {
+    index_document_batch(
+        writer,
+        index,
+        &IndexedDocument {
+            doc_id,
+            title,
+            date,
+            body,
+            path,
+            organizations: &[],
+            participants: &[],
+            labels: &[],
+        },
+    )
+}
+
+/// Indexes a document with its metadata using an existing writer (upsert)
+/// Does not commit - caller must call writer.commit() when ready
+pub fn index_document_batch(
+    writer: &mut tantivy::IndexWriter,
+    index: &Index,
+    document: &IndexedDocument,
+) -> Result<()> {
+    add_document(writer, index, document, DocKind::Transcript)
+}
+
+/// Indexes a saved summary under its meeting's doc_id and metadata (upsert)
+///
+/// `document.body` is the summary text and `document.path` the summary file.
+/// Replacing the meeting's transcript leaves its summary in place and vice versa.
+/// Does not commit - caller must call writer.commit() when ready
+pub fn index_summary_batch(
+    writer: &mut tantivy::IndexWriter,
+    index: &Index,
+    document: &IndexedDocument,
+) -> Result<()> {
+    add_document(writer, index, document, DocKind::Summary)
+}
+
+fn add_document(
+    writer: &mut tantivy::IndexWriter,
+    index: &Index,
+    document: &IndexedDocument,
+    kind: DocKind,
+) -> Result<()> {
+    let IndexedDocument {
+        doc_id,
+        title,
+        date,
+        body,
+        path,
+        organizations,
+        participants,
+        labels,
+    } = *document;
+    let schema = index.schema();
+
+    let doc_id_field = schema
+        .get_field("doc_id")
+        .map_err(|e| Error::Indexing(format!("Missing doc_id field: {}", e)))?;
+    let title_field = schema
+        .get_field("title")
+        .map_err(|e| Error::Indexing(format!("Missing title field: {}", e)))?;
+    let date_field = schema
+        .get_field("date")
+        .map_err(|e| Error::Indexing(format!("Missing date field: {}", e)))?;
+    let body_field = schema
+        .get_field("body")
+        .map_err(|e| Error::Indexing(format!("Missing body field: {}", e)))?;
+    let path_field = schema
+        .get_field("path")
+        .map_err(|e| Error::Indexing(format!("Missing path field: {}", e)))?;
+    let organizations_field = schema
+        .get_field("organizations")
+        .map_err(|e| Error::Indexing(format!("Missing organizations field: {}", e)))?;
+    let participants_field = schema
+        .get_field("participants")
+        .map_err(|e| Error::Indexing(format!("Missing participants field: {}", e)))?;
+    let labels_field = schema
+        .get_field("labels")
+        .map_err(|e| Error::Indexing(format!("Missing labels field: {}", e)))?;
+    let kind_field = schema
+        .get_field("kind")
+        .map_err(|e| Error::Indexing(format!("Missing kind field: {}", e)))?;
+    let key_field = schema
+        .get_field("key")
+        .map_err(|e| Error::Indexing(format!("Missing key field: {}", e)))?;
+
+    // Delete any existing document of this kind for the doc_id (upsert)
+    let key = upsert_key(kind, doc_id);
+    writer.delete_term(Term::from_field_text(key_field, &key));
+
+    // Build the new document
+    let path_str = path.to_string_lossy().to_string();
+
+    let mut document = doc!(
+        doc_id_field => doc_id,
+        date_field => date,
+        body_field => body,
+        path_field => path_str,
+        kind_field => kind.as_str(),
+        key_field => key,
+    );
+
+    // Add title if present
+    if let Some(t) = title {
+        document.add_text(title_field, t);
+    }
+
+    for org in organizations {
+        document.add_text(organizations_field, org.to_lowercase());
+    }
+
+    for participant in participants {
+        document.add_text(participants_field, participant);
+    }
+
+    for label in labels {
+        document.add_text(labels_field, label.to_lowercase());
+    }
+
+    // Add the document
+    writer
+        .add_document(document)
+        .map_err(|e| Error::Indexing(format!("Failed to add document: {}", e)))?;
+
+    Ok(())
+}
+
+/// Removes a document and its summary from the index by doc_id using an existing writer
+/// Does not commit - caller must call writer.commit() when ready
+pub fn delete_document(
+    writer: &mut tantivy::IndexWriter,
+    index: &Index,
+    doc_id: &str,
+) -> Result<()> {
+    let doc_id_field = index
+        .schema()
+        .get_field("doc_id")
+        .map_err(|e| Error::Indexing(format!("Missing doc_id field: {}", e)))?;
+
+    writer.delete_term(Term::from_field_text(doc_id_field, doc_id));
+
+    Ok(())
+}
+
+/// Every transcript's document ID in the index (deleted and replaced documents excluded)
+pub fn indexed_doc_ids(index: &Index) -> Result<BTreeSet<String>> {
+    use tantivy::collector::DocSetCollector;
+    use tantivy::query::AllQuery;
+
+    let schema = index.schema();
+    let doc_id_field = schema
+        .get_field("doc_id")
+        .map_err(|e| Error::Indexing(format!("Missing doc_id field: {}", e)))?;
+    let kind_field = schema
+        .get_field("kind")
+        .map_err(|e| Error::Indexing(format!("Missing kind field: {}", e)))?;
+    let searcher = index
+        .reader()
+        .map_err(|e| Error::Indexing(format!("Failed to create reader: {}", e)))?
+        .searcher();
+
+    let addresses = searcher
+        .search(&AllQuery, &DocSetCollector)
+        .map_err(|e| Error::Indexing(format!("Failed to list documents: {}", e)))?;
+
+    let mut doc_ids = BTreeSet::new();
+    for address in addresses {
+        let document = searcher
+            .doc::<tantivy::TantivyDocument>(address)
+            .map_err(|e| Error::Indexing(format!("Failed to retrieve document: {}", e)))?;
+        let kind = document.get_first(kind_field).and_then(|v| v.as_str());
+        if kind == Some(DocKind::Summary.as_str()) {
+            continue;
+        }
+        if let Some(doc_id) = document.get_first(doc_id_field).and_then(|v| v.as_str()) {
+            doc_ids.insert(doc_id.to_string());
+        }
+    }
+
+    Ok(doc_ids)
+}
+
+/// Searches the index using BM25 ranking
+///
+/// Searches both title and body fields with the given query string.
+/// Returns top N results sorted by relevance score (highest first).
+pub fn search(index: &Index, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+    search_with_filters(index, query, limit, &SearchFilters::default())
+}
+
+/// Searches the index using BM25 ranking, restricted by the given filters
+pub fn search_with_filters(
+    index: &Index,
+    query: &str,
+    limit: usize,
+    filters: &SearchFilters,
+) -> Result<Vec<SearchHit>> {
+    search_weighted(index, query, limit, filters, &SearchWeights::default())
+}
+
+/// Matches participants containing these words in order, tokenized like the index
+/// so "Alice Smith" and "alice@acme.com" both work
+fn participant_query(index: &Index, participant: &str) -> Result<Box<dyn tantivy::query::Query>> {
+    use tantivy::query::{PhraseQuery, TermQuery};
+    use tantivy::schema::IndexRecordOption;
+
+    let participants_field = index
+        .schema()
+        .get_field("participants")
+        .map_err(|e| Error::Indexing(format!("Missing participants field: {}", e)))?;
+    let mut tokenizer = index
+        .tokenizer_for_field(participants_field)
+        .map_err(|e| Error::Indexing(format!("Missing participants tokenizer: {}", e)))?;
+
+    let mut terms = Vec::new();
+    let mut stream = tokenizer.token_stream(participant);
+    while let Some(token) = stream.next() {
+        terms.push(Term::from_field_text(participants_field, &token.text));
+    }
+
+    match terms.len() {
+        0 => Err(Error::InvalidInput(
+            "Participant filter needs at least one word".to_string(),
+        )),
+        1 => Ok(Box::new(TermQuery::new(
+            terms.remove(0),
+            IndexRecordOption::Basic,
+        ))),
+        _ => Ok(Box::new(PhraseQuery::new(terms))),
+    }
+}
+
+fn label_query(index: &Index, label: &str) -> Result<Box<dyn tantivy::query::Query>> {
+    use tantivy::query::TermQuery;
+    use tantivy::schema::IndexRecordOption;
+
+    let labels_field = index
+        .schema()
+        .get_field("labels")
+        .map_err(|e| Error::Indexing(format!("Missing labels field: {}", e)))?;
+    Ok(Box::new(TermQuery::new(
+        Term::from_field_text(labels_field, &label.trim().to_lowercase()),
+        IndexRecordOption::Basic,
+    )))
+}
+
+/// Like `search_with_filters`, with title and body matches scaled by the given boosts
+pub fn search_weighted(
+    index: &Index,
+    query: &str,
+    limit: usize,
+    filters: &SearchFilters,
+    weights: &SearchWeights,
+) -> Result<Vec<SearchHit>> {
+    let reader = index
+        .reader()
+        .map_err(|e| Error::Indexing(format!("Failed to create reader: {}", e)))?;
+    search_in(&reader.searcher(), query, limit, filters, weights)
+}
+
+/// Like `search_weighted`, on a searcher from a reader the caller keeps open
+pub fn search_in(
+    searcher: &Searcher,
+    query: &str,
+    limit: usize,
+    filters: &SearchFilters,
+    weights: &SearchWeights,
+) -> Result<Vec<SearchHit>> {
+    use tantivy::collector::TopDocs;
+    use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+    use tantivy::schema::IndexRecordOption;
+
+    let parse_span = crate::perf::span("search.parse");
+    let index = searcher.index();
+    let schema = index.schema();
+
+    // Get the fields we want to search
+    let title_field = schema
+        .get_field("title")
+        .map_err(|e| Error::Indexing(format!("Missing title field: {}", e)))?;
+    let body_field = schema
+        .get_field("body")
+        .map_err(|e| Error::Indexing(format!("Missing body field: {}", e)))?;
+
+    // Get the stored fields for results
+    let doc_id_field = schema
+        .get_field("doc_id")
+        .map_err(|e| Error::Indexing(format!("Missing doc_id field: {}", e)))?;
+    let date_field = schema
+        .get_field("date")
+        .map_err(|e| Error::Indexing(format!("Missing date field: {}", e)))?;
+    let path_field = schema
+        .get_field("path")
+        .map_err(|e| Error::Indexing(format!("Missing path field: {}", e)))?;
+    let kind_field = schema
+        .get_field("kind")
+        .map_err(|e| Error::Indexing(format!("Missing kind field: {}", e)))?;
+
+    // Parse the query - search both title and body fields
+    let mut query_parser = QueryParser::for_index(index, vec![title_field, body_field]);
+    query_parser.set_field_boost(title_field, weights.title_boost);
+    query_parser.set_field_boost(body_field, weights.body_boost);
+    let mut parsed_query: Box<dyn Query> = query_parser
+        .parse_query(query)
+        .map_err(|e| Error::Indexing(format!("Failed to parse query '{}': {}", query, e)))?;
+
+    let mut clauses: Vec<(Occur, Box<dyn Query>)> = Vec::new();
+
+    if let Some(org) = &filters.organization {
+        let organizations_field = schema
+            .get_field("organizations")
+            .map_err(|e| Error::Indexing(format!("Missing organizations field: {}", e)))?;
+        let org_query = TermQuery::new(
+            Term::from_field_text(organizations_field, &org.to_lowercase()),
+            IndexRecordOption::Basic,
+        );
+        clauses.push((Occur::Must, Box::new(org_query)));
+    }
+
+    if let Some(participant) = &filters.participant {
+        clauses.push((Occur::Must, participant_query(index, participant)?));
+    }
+
+    if let Some(label) = &filters.label {
+        clauses.push((Occur::Must, label_query(index, label)?));
+    }
+
+    for participant in &filters.exclude_participants {
+        clauses.push((Occur::MustNot, participant_query(index, participant)?));
+    }
+
+    for label in &filters.exclude_labels {
+        clauses.push((Occur::MustNot, label_query(index, label)?));
+    }
+
+    if !filters.include_summaries {
+        let summaries = TermQuery::new(
+            Term::from_field_text(kind_field, DocKind::Summary.as_str()),
+            IndexRecordOption::Basic,
+        );
+        clauses.push((Occur::MustNot, Box::new(summaries)));
+    }
+
+    if !clauses.is_empty() {
+        clauses.insert(0, (Occur::Must, parsed_query));
+        parsed_query = Box::new(BooleanQuery::new(clauses));
+    }
+
+    drop(parse_span);
+
+    // Execute the search with BM25 scoring (default in Tantivy)
+    let top_docs = {
+        let _span = crate::perf::span("search.retrieve");
+        searcher
+            .search(&parsed_query, &TopDocs::with_limit(limit))
+            .map_err(|e| Error::Indexing(format!("Search failed: {}", e)))?
+    };
+    let _span = crate::perf::span("search.assemble");
+
+    // Convert results to SearchHit structs
+    let mut results = Vec::new();
+    for (score, doc_address) in top_docs {
+        let retrieved_doc = searcher
+            .doc::<tantivy::TantivyDocument>(doc_address)
+            .map_err(|e| Error::Indexing(format!("Failed to retrieve document: {}", e)))?;
+
+        // Extract fields from the document
+        let doc_id = retrieved_doc
+            .get_first(doc_id_field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Indexing("Document missing doc_id".to_string()))?
+            .to_string();
+
+        let title = retrieved_doc
+            .get_first(title_field)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let date = retrieved_doc
+            .get_first(date_field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Indexing("Document missing date".to_string()))?
+            .to_string();
+
+        let path = retrieved_doc
+            .get_first(path_field)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::Indexing("Document missing path".to_string()))?
+            .to_string();
+
+        let kind = match retrieved_doc.get_first(kind_field).and_then(|v| v.as_str()) {
+            Some("summary") => DocKind::Summary,
+            _ => DocKind::Transcript,
+        };
+
+        results.push(SearchHit {
+            doc_id,
+            title,
+            date,
+            path,
+            score,
+            kind,
+            anchor: None,
+            snippet: None,
+        });
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // Test helper: creates a temporary directory for test indexes
+    fn test_index_dir() -> TempDir {
+        TempDir::new().expect("Failed to create temp dir")
+    }
+
+    #[test]
+    fn test_schema_creation() {
+        // Test that we can create a new index with the correct schema
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+        let schema = index.schema();
+
+        // Verify all 5 required fields exist
+        assert!(schema.get_field("doc_id").is_ok(), "doc_id field missing");
+        assert!(schema.get_field("title").is_ok(), "title field missing");
+        assert!(schema.get_field("date").is_ok(), "date field missing");
+        assert!(schema.get_field("body").is_ok(), "body field missing");
+        assert!(schema.get_field("path").is_ok(), "path field missing");
+    }
+
+    #[test]
+    fn test_schema_reopening() {
+        // Test that we can reopen an existing index
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+
+        // Create the index
+        let _index1 = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Reopen the index
+        let index2 = create_or_open_index(index_path).expect("Failed to reopen index");
+        let schema = index2.schema();
+
+        // Verify fields still exist
+        assert!(
+            schema.get_field("doc_id").is_ok(),
+            "doc_id field missing after reopen"
+        );
+    }
+
+    #[test]
+    fn test_index_document() {
+        // Test indexing a single document
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        let doc_path = Path::new("/test/documents/test.md");
+        let result = index_markdown(
+            &index,
+            "doc123",
+            Some("Test Document"),
+            "2025-10-29",
+            "This is the body of the test document.",
+            doc_path,
+        );
+
+        assert!(result.is_ok(), "Failed to index document: {:?}", result);
+    }
+
+    #[test]
+    fn test_index_document_without_title() {
+        // Test indexing a document without a title
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        let doc_path = Path::new("/test/documents/notitle.md");
+        let result = index_markdown(
+            &index,
+            "doc456",
+            None,
+            "2025-10-29",
+            "Document without a title.",
+            doc_path,
+        );
+
+        assert!(
+            result.is_ok(),
+            "Failed to index document without title: {:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_upsert_document() {
+        // Test that indexing the same doc_id twice updates (not duplicates)
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+        let doc_path = Path::new("/test/documents/update.md");
+
+        // Index first version
+        index_markdown(
+            &index,
+            "doc789",
+            Some("Original Title"),
+            "2025-10-29",
+            "Original body content.",
+            doc_path,
+        )
+        .expect("Failed to index original document");
+
+        // Update with same doc_id
+        index_markdown(
+            &index,
+            "doc789",
+            Some("Updated Title"),
+            "2025-10-30",
+            "Updated body content.",
+            doc_path,
+        )
+        .expect("Failed to update document");
+
+        // Search to verify only one document exists with doc_id="doc789"
+        use tantivy::collector::TopDocs;
+        use tantivy::query::QueryParser;
+
+        let reader = index.reader().expect("Failed to create reader");
+        let searcher = reader.searcher();
+        let schema = index.schema();
+
+        let doc_id_field = schema.get_field("doc_id").unwrap();
+        let query_parser = QueryParser::for_index(&index, vec![doc_id_field]);
+        let query = query_parser
+            .parse_query("doc789")
+            .expect("Failed to parse query");
+
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(10))
+            .expect("Search failed");
+
+        // Should only have 1 document (the updated one)
+        assert_eq!(
+            top_docs.len(),
+            1,
+            "Expected exactly 1 document after upsert, found {}",
+            top_docs.len()
+        );
+    }
+
+    #[test]
+    fn test_search_indexed_content() {
+        // Test that we can search and find indexed documents
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index multiple documents
+        index_markdown(
+            &index,
+            "doc1",
+            Some("Rust Programming"),
+            "2025-10-29",
+            "Rust is a systems programming language that runs blazingly fast.",
+            Path::new("/test/rust.md"),
+        )
+        .expect("Failed to index doc1");
+
+        index_markdown(
+            &index,
+            "doc2",
+            Some("Python Basics"),
+            "2025-10-28",
+            "Python is a high-level programming language.",
+            Path::new("/test/python.md"),
+        )
+        .expect("Failed to index doc2");
+
+        // Commit and search
+        use tantivy::collector::TopDocs;
+        use tantivy::query::QueryParser;
+
+        let reader = index.reader().expect("Failed to create reader");
+        let searcher = reader.searcher();
+        let schema = index.schema();
+
+        let body_field = schema.get_field("body").unwrap();
+        let title_field = schema.get_field("title").unwrap();
+
+        let query_parser = QueryParser::for_index(&index, vec![title_field, body_field]);
+        let query = query_parser
+            .parse_query("rust")
+            .expect("Failed to parse query");
+
+        let top_docs = searcher
+            .search(&query, &TopDocs::with_limit(10))
+            .expect("Search failed");
+
+        // Should find the Rust document
+        assert!(
+            !top_docs.is_empty(),
+            "Expected to find at least one document for 'rust'"
+        );
+        assert_eq!(
+            top_docs.len(),
+            1,
+            "Expected exactly 1 document matching 'rust'"
+        );
+    }
+
+    #[test]
+    fn test_search_single_term() {
+        // Test searching with a single term
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index test documents
+        index_markdown(
+            &index,
+            "doc1",
+            Some("OKRs for Q4"),
+            "2025-10-29",
+            "Discussing quarterly OKRs and objectives.",
+            Path::new("/test/okrs.md"),
+        )
+        .expect("Failed to index doc1");
+
+        index_markdown(
+            &index,
+            "doc2",
+            Some("Onboarding Process"),
+            "2025-10-28",
+            "Steps for employee onboarding.",
+            Path::new("/test/onboarding.md"),
+        )
+        .expect("Failed to index doc2");
+
+        index_markdown(
+            &index,
+            "doc3",
+            Some("Team Meeting"),
+            "2025-10-27",
+            "General team updates.",
+            Path::new("/test/team.md"),
+        )
+        .expect("Failed to index doc3");
+
+        // Search for "OKRs"
+        let results = super::search(&index, "OKRs", 10).expect("Search failed");
+
+        assert!(!results.is_empty(), "Expected to find results for 'OKRs'");
+        assert_eq!(results[0].doc_id, "doc1", "Expected doc1 to be top result");
+        assert_eq!(results[0].title, Some("OKRs for Q4".to_string()));
+        assert_eq!(results[0].date, "2025-10-29");
+        assert!(results[0].score > 0.0, "Expected positive score");
+    }
+
+    #[test]
+    fn test_search_multi_term() {
+        // Test searching with multiple terms
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index test documents
+        index_markdown(
+            &index,
+            "doc1",
+            Some("OKRs Discussion"),
+            "2025-10-29",
+            "Talking about onboarding and OKRs together.",
+            Path::new("/test/both.md"),
+        )
+        .expect("Failed to index doc1");
+
+        index_markdown(
+            &index,
+            "doc2",
+            Some("Onboarding Process"),
+            "2025-10-28",
+            "Steps for employee onboarding only.",
+            Path::new("/test/onboarding.md"),
+        )
+        .expect("Failed to index doc2");
+
+        index_markdown(
+            &index,
+            "doc3",
+            Some("Team Meeting"),
+            "2025-10-27",
+            "General team updates, no relevant content.",
+            Path::new("/test/team.md"),
+        )
+        .expect("Failed to index doc3");
+
+        // Search for "OKRs onboarding"
+        let results = super::search(&index, "OKRs onboarding", 10).expect("Search failed");
+
+        assert!(!results.is_empty(), "Expected to find results");
+        // doc1 should rank higher because it contains both terms
+        assert_eq!(
+            results[0].doc_id, "doc1",
+            "Expected doc1 with both terms to rank highest"
+        );
+        assert!(results.len() >= 2, "Expected at least 2 results");
+    }
+
+    #[test]
+    fn test_search_partial_match() {
+        // Test searching with tokenized matches
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index document with "programming guide"
+        index_markdown(
+            &index,
+            "doc1",
+            Some("Programming Guide"),
+            "2025-10-29",
+            "A guide to programming in various languages.",
+            Path::new("/test/programming.md"),
+        )
+        .expect("Failed to index doc1");
+
+        // Search for just "guide" (matches "Programming Guide")
+        let results = super::search(&index, "guide", 10).expect("Search failed");
+
+        assert!(
+            !results.is_empty(),
+            "Expected to find results for partial match"
+        );
+        assert_eq!(results[0].doc_id, "doc1");
+    }
+
+    #[test]
+    fn test_search_bm25_ranking() {
+        // Test that BM25 ranking prioritizes more relevant documents
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Document with "rust" in title and multiple times in body
+        index_markdown(
+            &index,
+            "doc1",
+            Some("Rust Programming Language"),
+            "2025-10-29",
+            "Rust is great. Rust is fast. Rust is safe. All about Rust programming.",
+            Path::new("/test/rust-deep.md"),
+        )
+        .expect("Failed to index doc1");
+
+        // Document with "rust" only once in body
+        index_markdown(
+            &index,
+            "doc2",
+            Some("Programming Languages Overview"),
+            "2025-10-28",
+            "Various languages including Rust are discussed here.",
+            Path::new("/test/overview.md"),
+        )
+        .expect("Failed to index doc2");
+
+        // Search for "rust"
+        let results = super::search(&index, "rust", 10).expect("Search failed");
+
+        assert!(results.len() >= 2, "Expected at least 2 results");
+        // doc1 should rank higher due to more occurrences and title match
+        assert_eq!(results[0].doc_id, "doc1", "Expected doc1 to rank higher");
+        assert!(
+            results[0].score > results[1].score,
+            "Expected doc1 to have higher score"
+        );
+    }
+
+    #[test]
+    fn test_search_limit() {
+        // Test that the limit parameter works correctly
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index 5 documents
+        for i in 1..=5 {
+            index_markdown(
+                &index,
+                &format!("doc{}", i),
+                Some(&format!("Document {}", i)),
+                "2025-10-29",
+                "This document contains the word test for searching.",
+                Path::new(&format!("/test/doc{}.md", i)),
+            )
+            .unwrap_or_else(|_| panic!("Failed to index doc{}", i));
+        }
+
+        // Search with limit 3
+        let results = super::search(&index, "test", 3).expect("Search failed");
+
+        assert_eq!(results.len(), 3, "Expected exactly 3 results with limit=3");
+    }
+
+    #[test]
+    fn test_search_no_results() {
+        // Test searching when no documents match
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Index a document
+        index_markdown(
+            &index,
+            "doc1",
+            Some("Rust Programming"),
+            "2025-10-29",
+            "All about Rust.",
+            Path::new("/test/rust.md"),
+        )
+        .expect("Failed to index doc1");
+
+        // Search for something that doesn't exist
+        let results = super::search(&index, "xyznonexistent", 10).expect("Search failed");
+
+        assert!(
+            results.is_empty(),
+            "Expected no results for non-matching query"
+        );
+    }
+
+    #[test]
+    fn test_search_empty_index() {
+        // Test searching an empty index
+        let temp_dir = test_index_dir();
+        let index_path = temp_dir.path();
+        let index = create_or_open_index(index_path).expect("Failed to create index");
+
+        // Search without indexing any documents
+        let results = super::search(&index, "anything", 10).expect("Search failed");
+
+        assert!(results.is_empty(), "Expected no results from empty index");
+    }
+
+    #[test]
+    fn test_delete_document() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+
+        index_markdown(
+            &index,
+            "keep",
+            Some("Kept Meeting"),
+            "2025-10-29",
+            "Roadmap discussion.",
+            Path::new("/test/keep.md"),
+        )
+        .expect("Failed to index keep");
+        index_markdown(
+            &index,
+            "gone",
+            Some("Deleted Meeting"),
+            "2025-10-29",
+            "Roadmap review.",
+            Path::new("/test/gone.md"),
+        )
+        .expect("Failed to index gone");
+
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+        delete_document(&mut writer, &index, "gone").expect("Failed to delete");
+        writer.commit().unwrap();
+
+        let results = super::search(&index, "roadmap", 10).expect("Search failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, "keep");
+    }
+
+    #[test]
+    fn test_indexed_doc_ids_skips_replaced_and_deleted() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let path = Path::new("/test/x.md");
+
+        for (doc_id, body) in [
+            ("a", "alpha"),
+            ("b", "beta"),
+            ("a", "alpha again"),
+            ("c", "gamma"),
+        ] {
+            index_markdown(&index, doc_id, None, "2025-10-29", body, path).unwrap();
+        }
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+        delete_document(&mut writer, &index, "c").unwrap();
+        writer.commit().unwrap();
+
+        let ids: Vec<String> = indexed_doc_ids(&index).unwrap().into_iter().collect();
+        assert_eq!(ids, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_optimize_merges_segments_and_drops_deletes() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+
+        // One segment holding both documents, then a second one replacing "a"
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+        for (doc_id, body) in [("a", "alpha"), ("b", "beta")] {
+            index_markdown_batch(
+                &mut writer,
+                &index,
+                doc_id,
+                None,
+                "2025-10-29",
+                body,
+                Path::new("/test/x.md"),
+            )
+            .expect("Failed to index");
+        }
+        writer.commit().unwrap();
+        drop(writer);
+        index_markdown(
+            &index,
+            "a",
+            None,
+            "2025-10-29",
+            "alpha again",
+            Path::new("/test/a.md"),
+        )
+        .expect("Failed to upsert");
+
+        let report =
+            optimize(temp_dir.path(), &WriterConfig::default()).expect("Failed to optimize");
+        assert_eq!(report.before.segments, 2);
+        assert_eq!(report.before.deleted_docs, 1);
+        assert_eq!(report.after.segments, 1);
+        assert_eq!(report.after.deleted_docs, 0);
+
+        let index = create_or_open_index(temp_dir.path()).unwrap();
+        let results = super::search(&index, "alpha", 10).expect("Search failed");
+        assert_eq!(results.len(), 1);
+
+        // A clean index is left alone
+        let again =
+            optimize(temp_dir.path(), &WriterConfig::default()).expect("Failed to optimize");
+        assert_eq!(again.before, again.after);
+    }
+
+    #[test]
+    fn test_search_with_organization_filter() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+
+        let acme = vec!["acme.com".to_string()];
+        let vendor = vec!["vendor.io".to_string()];
+        for (doc_id, orgs) in [("with-acme", &acme), ("with-vendor", &vendor)] {
+            index_document_batch(
+                &mut writer,
+                &index,
+                &IndexedDocument {
+                    doc_id,
+                    title: Some("Pricing review"),
+                    date: "2025-10-29",
+                    body: "We discussed pricing.",
+                    path: Path::new("/test/pricing.md"),
+                    organizations: orgs,
+                    participants: &[],
+                    labels: &[],
+                },
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let filters = SearchFilters {
+            organization: Some("ACME.com".into()),
+            ..Default::default()
+        };
+        let results = search_with_filters(&index, "pricing", 10, &filters).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, "with-acme");
+
+        assert_eq!(super::search(&index, "pricing", 10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_search_with_participant_filter() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+
+        let alice = vec![
+            "Alice Smith <alice@acme.com>".to_string(),
+            "Bob".to_string(),
+        ];
+        let carol = vec!["Carol Jones <carol@vendor.io>".to_string()];
+        for (doc_id, participants) in [("with-alice", &alice), ("with-carol", &carol)] {
+            index_document_batch(
+                &mut writer,
+                &index,
+                &IndexedDocument {
+                    doc_id,
+                    title: Some("Roadmap"),
+                    date: "2025-10-29",
+                    body: "Roadmap planning.",
+                    path: Path::new("/test/roadmap.md"),
+                    organizations: &[],
+                    participants,
+                    labels: &[],
+                },
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let by = |participant: &str| {
+            let filters = SearchFilters {
+                participant: Some(participant.into()),
+                ..Default::default()
+            };
+            search_with_filters(&index, "roadmap", 10, &filters)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.doc_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(by("alice"), vec!["with-alice"]);
+        assert_eq!(by("Alice Smith"), vec!["with-alice"]);
+        assert_eq!(by("carol@vendor.io"), vec!["with-carol"]);
+        assert!(by("smith carol").is_empty());
+        assert!(by("dave").is_empty());
+        assert!(search_with_filters(
+            &index,
+            "roadmap",
+            10,
+            &SearchFilters {
+                participant: Some("  ".into()),
+                ..Default::default()
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_search_weighted_field_boosts() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+
+        let corpus = [
+            (
+                "title-hit",
+                "Budget review",
+                "Quarterly numbers and hiring.",
+            ),
+            (
+                "body-hit",
+                "Weekly sync",
+                "The budget came up again: budget cuts, budget owners, budget timing.",
+            ),
+            ("unrelated", "Offsite", "Venue and travel."),
+        ];
+        for (doc_id, title, body) in corpus {
+            index_markdown_batch(
+                &mut writer,
+                &index,
+                doc_id,
+                Some(title),
+                "2025-10-29",
+                body,
+                Path::new("/test/x.md"),
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let top = |title_boost: f32, body_boost: f32| {
+            let weights = SearchWeights {
+                title_boost,
+                body_boost,
+                ..Default::default()
+            };
+            let results =
+                search_weighted(&index, "budget", 10, &SearchFilters::default(), &weights).unwrap();
+            assert_eq!(results.len(), 2);
+            results[0].doc_id.clone()
+        };
+
+        assert_eq!(top(10.0, 1.0), "title-hit");
+        assert_eq!(top(1.0, 10.0), "body-hit");
+    }
+
+    #[test]
+    fn test_search_with_label_filter() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+
+        let planning = vec!["Planning".to_string(), "Q4".to_string()];
+        let hiring = vec!["Hiring".to_string()];
+        for (doc_id, labels) in [("planning", &planning), ("hiring", &hiring)] {
+            index_document_batch(
+                &mut writer,
+                &index,
+                &IndexedDocument {
+                    doc_id,
+                    title: Some("Weekly sync"),
+                    date: "2025-10-29",
+                    body: "Weekly sync notes.",
+                    path: Path::new("/test/sync.md"),
+                    organizations: &[],
+                    participants: &[],
+                    labels,
+                },
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let by = |label: &str| {
+            let filters = SearchFilters {
+                label: Some(label.into()),
+                ..Default::default()
+            };
+            search_with_filters(&index, "weekly", 10, &filters)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.doc_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(by("planning"), vec!["planning"]);
+        assert_eq!(by("q4"), vec!["planning"]);
+        assert_eq!(by("HIRING"), vec!["hiring"]);
+        assert!(by("plan").is_empty());
+    }
+
+    #[test]
+    fn test_search_with_exclusions() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+
+        let personal = vec!["Personal".to_string()];
+        let alice = vec!["Alice Smith <alice@acme.com>".to_string()];
+        let corpus: [(&str, &str, &[String], &[String]); 3] = [
+            ("dentist", "Planning the dentist visit", &[], &personal),
+            ("with-alice", "Planning the launch", &alice, &[]),
+            ("budget", "Planning the budget", &[], &[]),
+        ];
+        for (doc_id, body, participants, labels) in corpus {
+            index_document_batch(
+                &mut writer,
+                &index,
+                &IndexedDocument {
+                    doc_id,
+                    title: None,
+                    date: "2025-10-29",
+                    body,
+                    path: Path::new("/test/x.md"),
+                    organizations: &[],
+                    participants,
+                    labels,
+                },
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let ids = |query: &str, filters: &SearchFilters| {
+            let mut ids: Vec<String> = search_with_filters(&index, query, 10, filters)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.doc_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        let filters = SearchFilters {
+            exclude_labels: vec!["personal".into()],
+            exclude_participants: vec!["alice".into()],
+            ..Default::default()
+        };
+        assert_eq!(ids("planning", &filters), vec!["budget"]);
+
+        // Query syntax exclusions work too
+        let none = SearchFilters::default();
+        assert_eq!(
+            ids("planning -dentist", &none),
+            vec!["budget", "with-alice"]
+        );
+    }
+
+    #[test]
+    fn test_filters_match_catalog_entries() {
+        let entry = CatalogEntry {
+            doc_id: "d1".into(),
+            path: "/test/d1.md".into(),
+            title: None,
+            created_at: chrono::Utc::now(),
+            participants: vec!["Alice Smith <alice@acme.com>".into()],
+            labels: vec!["Planning".into()],
+            organizations: vec![],
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            access: None,
+            modified: None,
+        };
+        let check = |filters: SearchFilters| filters.matches(&entry);
+
+        assert!(check(SearchFilters::default()));
+        assert!(check(SearchFilters {
+            organization: Some("ACME.com".into()),
+            participant: Some("alice smith".into()),
+            label: Some("planning".into()),
+            ..Default::default()
+        }));
+        assert!(!check(SearchFilters {
+            participant: Some("smith acme".into()),
+            ..Default::default()
+        }));
+        assert!(!check(SearchFilters {
+            exclude_labels: vec!["PLANNING".into()],
+            ..Default::default()
+        }));
+        assert!(!check(SearchFilters {
+            exclude_participants: vec!["alice@acme.com".into()],
+            ..Default::default()
+        }));
+        assert!(check(SearchFilters {
+            exclude_participants: vec!["bob".into()],
+            exclude_labels: vec!["personal".into()],
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_outdated_schema_is_rebuilt() {
+        let temp_dir = test_index_dir();
+        let dir = temp_dir.path();
+
+        // Simulate an index from before the organizations field existed
+        let mut builder = Schema::builder();
+        builder.add_text_field("doc_id", STRING | STORED);
+        builder.add_text_field("title", TEXT | STORED);
+        builder.add_text_field("date", STRING | STORED);
+        builder.add_text_field("body", TEXT);
+        builder.add_text_field("path", STRING | STORED);
+        Index::create_in_dir(dir, builder.build()).unwrap();
+        std::fs::write(dir.join("vectors.meta.json"), "{}").unwrap();
+
+        assert!(schema_is_outdated(dir));
+        let index = create_or_open_index(dir).unwrap();
+
+        assert!(index.schema().get_field("organizations").is_ok());
+        assert!(!schema_is_outdated(dir));
+        assert!(dir.join("vectors.meta.json").exists());
+    }
+
+    #[test]
+    fn test_summaries_are_indexed_beside_transcripts() {
+        let temp_dir = test_index_dir();
+        let index = create_or_open_index(temp_dir.path()).expect("Failed to create index");
+        let labels = vec!["planning".to_string()];
+        let transcript = IndexedDocument {
+            doc_id: "d1",
+            title: Some("Roadmap Sync"),
+            date: "2025-10-29",
+            body: "We went back and forth on pricing.",
+            path: Path::new("/test/d1.md"),
+            organizations: &[],
+            participants: &[],
+            labels: &labels,
+        };
+        let summary = IndexedDocument {
+            body: "Decision: ship the annual plan in March.",
+            path: Path::new("/summaries/d1_summary.md"),
+            ..transcript
+        };
+
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+        index_summary_batch(&mut writer, &index, &summary).unwrap();
+        // Re-indexing the transcript must not replace its summary
+        index_document_batch(&mut writer, &index, &transcript).unwrap();
+        index_document_batch(&mut writer, &index, &transcript).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        assert!(super::search(&index, "annual", 10).unwrap().is_empty());
+        let filters = SearchFilters {
+            label: Some("planning".into()),
+            include_summaries: true,
+            ..Default::default()
+        };
+        let results = search_with_filters(&index, "annual OR pricing", 10, &filters).unwrap();
+        let mut kinds: Vec<DocKind> = results.iter().map(|r| r.kind).collect();
+        kinds.sort_by_key(|k| k.as_str());
+        assert_eq!(kinds, vec![DocKind::Summary, DocKind::Transcript]);
+        let hit = results.iter().find(|r| r.kind == DocKind::Summary).unwrap();
+        assert_eq!(hit.doc_id, "d1");
+        assert_eq!(hit.path, "/summaries/d1_summary.md");
+
+        // Summaries don't count as documents, and go away with their meeting
+        assert_eq!(indexed_doc_ids(&index).unwrap().len(), 1);
+        let mut writer: tantivy::IndexWriter = index.writer(15_000_000).unwrap();
+        delete_document(&mut writer, &index, "d1").unwrap();
+        writer.commit().unwrap();
+        assert!(search_with_filters(&index, "annual", 10, &filters)
+            .unwrap()
+            .is_empty());
+    }
+}
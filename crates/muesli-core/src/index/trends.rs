@@ -0,0 +1,374 @@
+// ABOUTME: Terms rising or falling between two periods, for `muesli trends`
+// ABOUTME: Compares term frequencies with a log-likelihood test and names example meetings
+
+use super::terms::{scan, Meeting, TermScan};
+use super::text;
+use crate::{Paths, Result};
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Log-likelihood a change must reach to be reported (p < 0.01, one degree of freedom)
+pub const SIGNIFICANCE: f64 = 6.63;
+
+/// Fewest occurrences across both periods for a term to be compared at all
+pub const MIN_OCCURRENCES: u64 = 3;
+
+/// One side of the comparison, both dates inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Period {
+    pub since: NaiveDate,
+    pub until: NaiveDate,
+}
+
+impl Period {
+    /// The period of the same length that ends the day before this one starts
+    pub fn previous(&self) -> Period {
+        let days = (self.until - self.since).num_days().max(0) as u64 + 1;
+        let until = self.since - Days::new(1);
+        Period {
+            since: until - Days::new(days - 1),
+            until,
+        }
+    }
+
+    fn contains(&self, meeting: &Meeting) -> bool {
+        meeting.in_period(Some(self.since), Some(self.until))
+    }
+}
+
+/// A meeting that shows a trend, with how often it used the term
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Example {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub date: Option<NaiveDate>,
+    pub occurrences: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TermTrend {
+    pub term: String,
+    /// Occurrences in the baseline period
+    pub before: u64,
+    /// Occurrences in the period compared with it
+    pub after: u64,
+    pub before_per_meeting: f64,
+    pub after_per_meeting: f64,
+    /// Dunning's G²: how unlikely the change is if the term's share stayed the same
+    pub log_likelihood: f64,
+    /// Meetings using it most: from the later period for risers, the baseline for decliners
+    pub examples: Vec<Example>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PeriodSummary {
+    #[serde(flatten)]
+    pub period: Period,
+    pub meetings: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrendsReport {
+    pub baseline: PeriodSummary,
+    pub current: PeriodSummary,
+    /// Most significant first
+    pub rising: Vec<TermTrend>,
+    pub falling: Vec<TermTrend>,
+}
+
+/// Terms whose share of the conversation changed significantly from `baseline` to `current`
+///
+/// Shares are taken of all the words counted in each period, so a period with more
+/// meetings doesn't make every term look like it rose. Participant names of either
+/// period are skipped. `limit` caps each list and `examples` the meetings per term.
+pub(crate) fn compare(
+    scan: &TermScan,
+    baseline: Period,
+    current: Period,
+    limit: usize,
+    examples: usize,
+) -> TrendsReport {
+    let names = scan.names(|m| baseline.contains(m) || current.contains(m));
+    let totals = |period: Period| -> u64 {
+        scan.terms
+            .iter()
+            .filter(|(term, _)| !names.contains(*term))
+            .flat_map(|(_, uses)| uses)
+            .filter(|(meeting, _)| period.contains(&scan.meetings[*meeting]))
+            .map(|(_, count)| *count as u64)
+            .sum()
+    };
+    let (baseline_words, current_words) = (totals(baseline), totals(current));
+    let meetings = |period: Period| scan.meetings.iter().filter(|m| period.contains(m)).count();
+    let (baseline_meetings, current_meetings) = (meetings(baseline), meetings(current));
+    let per_meeting = |count: u64, meetings: usize| {
+        if meetings == 0 {
+            0.0
+        } else {
+            (count as f64 / meetings as f64 * 100.0).round() / 100.0
+        }
+    };
+
+    let mut rising = Vec::new();
+    let mut falling = Vec::new();
+    for (term, uses) in &scan.terms {
+        if names.contains(term) {
+            continue;
+        }
+        let count = |period: Period| -> u64 {
+            uses.iter()
+                .filter(|(meeting, _)| period.contains(&scan.meetings[*meeting]))
+                .map(|(_, count)| *count as u64)
+                .sum()
+        };
+        let (before, after) = (count(baseline), count(current));
+        if before + after < MIN_OCCURRENCES {
+            continue;
+        }
+        let g2 = log_likelihood(after, current_words, before, baseline_words);
+        if g2 < SIGNIFICANCE {
+            continue;
+        }
+        let rose = after as f64 * baseline_words as f64 > before as f64 * current_words as f64;
+        let shown = if rose { current } else { baseline };
+        let mut found: Vec<Example> = uses
+            .iter()
+            .filter(|(meeting, _)| shown.contains(&scan.meetings[*meeting]))
+            .map(|&(meeting, occurrences)| {
+                let meeting = &scan.meetings[meeting];
+                Example {
+                    doc_id: meeting.doc_id.clone(),
+                    title: meeting.title.clone(),
+                    date: meeting.date,
+                    occurrences,
+                }
+            })
+            .collect();
+        found.sort_by(|a, b| {
+            b.occurrences
+                .cmp(&a.occurrences)
+                .then_with(|| b.date.cmp(&a.date))
+        });
+        found.truncate(examples);
+
+        let trend = TermTrend {
+            term: term.clone(),
+            before,
+            after,
+            before_per_meeting: per_meeting(before, baseline_meetings),
+            after_per_meeting: per_meeting(after, current_meetings),
+            log_likelihood: (g2 * 100.0).round() / 100.0,
+            examples: found,
+        };
+        if rose {
+            rising.push(trend);
+        } else {
+            falling.push(trend);
+        }
+    }
+    for list in [&mut rising, &mut falling] {
+        list.sort_by(|a, b| {
+            b.log_likelihood
+                .total_cmp(&a.log_likelihood)
+                .then_with(|| a.term.cmp(&b.term))
+        });
+        list.truncate(limit);
+    }
+
+    TrendsReport {
+        baseline: PeriodSummary {
+            period: baseline,
+            meetings: baseline_meetings,
+        },
+        current: PeriodSummary {
+            period: current,
+            meetings: current_meetings,
+        },
+        rising,
+        falling,
+    }
+}
+
+/// Dunning's log-likelihood for `a` of `c` words against `b` of `d`
+fn log_likelihood(a: u64, c: u64, b: u64, d: u64) -> f64 {
+    if c == 0 || d == 0 {
+        return 0.0;
+    }
+    let (a, b, c, d) = (a as f64, b as f64, c as f64, d as f64);
+    let expected_a = c * (a + b) / (c + d);
+    let expected_b = d * (a + b) / (c + d);
+    let part = |observed: f64, expected: f64| {
+        if observed > 0.0 {
+            observed * (observed / expected).ln()
+        } else {
+            0.0
+        }
+    };
+    2.0 * (part(a, expected_a) + part(b, expected_b))
+}
+
+/// Trends in the data directory's index
+pub fn collect(
+    paths: &Paths,
+    baseline: Period,
+    current: Period,
+    limit: usize,
+    examples: usize,
+) -> Result<TrendsReport> {
+    let index = text::open_for_search(&paths.index_dir)?;
+    Ok(compare(&scan(&index)?, baseline, current, limit, examples))
+}
+
+/// The report as a markdown document
+pub fn to_markdown(report: &TrendsReport) -> String {
+    let mut out = format!(
+        "# Trends: {} to {} vs {} to {}\n\n{} meetings, against {} in the baseline.\n",
+        report.current.period.since,
+        report.current.period.until,
+        report.baseline.period.since,
+        report.baseline.period.until,
+        report.current.meetings,
+        report.baseline.meetings
+    );
+    for (heading, trends) in [("Rising", &report.rising), ("Falling", &report.falling)] {
+        let _ = write!(out, "\n## {}\n\n", heading);
+        if trends.is_empty() {
+            out.push_str("No significant changes.\n");
+            continue;
+        }
+        out.push_str("| Term | Before | After | Per meeting | Examples |\n");
+        out.push_str("|------|-------:|------:|-------------|----------|\n");
+        for trend in trends {
+            let examples: Vec<String> = trend
+                .examples
+                .iter()
+                .map(|e| {
+                    let date = e.date.map(|d| format!("{} ", d)).unwrap_or_default();
+                    let title = e.title.as_deref().unwrap_or(&e.doc_id).replace('|', "\\|");
+                    format!("{}{} ({}×)", date, title, e.occurrences)
+                })
+                .collect();
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {:.2} → {:.2} | {} |",
+                trend.term,
+                trend.before,
+                trend.after,
+                trend.before_per_meeting,
+                trend.after_per_meeting,
+                examples.join("; ")
+            );
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::text::{create_or_open_index, index_document_batch, IndexedDocument};
+    use crate::index::writer::WriterConfig;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_previous_period() {
+        let march = Period {
+            since: date("2025-03-01"),
+            until: date("2025-03-31"),
+        };
+        assert_eq!(
+            march.previous(),
+            Period {
+                since: date("2025-01-29"),
+                until: date("2025-02-28"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_log_likelihood() {
+        assert_eq!(log_likelihood(10, 1000, 10, 1000), 0.0);
+        assert!(log_likelihood(30, 1000, 5, 1000) > SIGNIFICANCE);
+        assert!(log_likelihood(6, 1000, 4, 1000) < SIGNIFICANCE);
+        assert_eq!(log_likelihood(3, 0, 0, 1000), 0.0);
+    }
+
+    #[test]
+    fn test_compare() {
+        let temp = TempDir::new().unwrap();
+        let index = create_or_open_index(temp.path()).unwrap();
+        let mut writer = WriterConfig::default().writer(&index).unwrap();
+        let filler = "roadmap review hiring plan ".repeat(10);
+        let meetings = [
+            (
+                "feb1",
+                "2025-02-10",
+                format!("{} budget budget budget budget", filler),
+            ),
+            (
+                "feb2",
+                "2025-02-20",
+                format!("{} budget budget budget", filler),
+            ),
+            (
+                "mar1",
+                "2025-03-05",
+                format!("{} pricing pricing pricing pricing", filler),
+            ),
+            (
+                "mar2",
+                "2025-03-12",
+                format!("{} pricing pricing pricing pricing pricing Dana", filler),
+            ),
+        ];
+        let participants = vec!["Dana Lee".to_string()];
+        for (id, day, body) in &meetings {
+            index_document_batch(
+                &mut writer,
+                &index,
+                &IndexedDocument {
+                    doc_id: id,
+                    title: Some(&id.to_uppercase()),
+                    date: day,
+                    body,
+                    path: Path::new("/tmp/x.md"),
+                    organizations: &[],
+                    participants: &participants,
+                    labels: &[],
+                },
+            )
+            .unwrap();
+        }
+        writer.commit().unwrap();
+
+        let current = Period {
+            since: date("2025-03-01"),
+            until: date("2025-03-31"),
+        };
+        let report = compare(&scan(&index).unwrap(), current.previous(), current, 5, 1);
+        assert_eq!(report.current.meetings, 2);
+        assert_eq!(report.baseline.meetings, 2);
+
+        let rising: Vec<&str> = report.rising.iter().map(|t| t.term.as_str()).collect();
+        assert_eq!(rising, vec!["pricing"]);
+        let pricing = &report.rising[0];
+        assert_eq!((pricing.before, pricing.after), (0, 9));
+        assert_eq!(pricing.after_per_meeting, 4.5);
+        assert_eq!(pricing.examples.len(), 1);
+        assert_eq!(pricing.examples[0].doc_id, "mar2");
+        assert_eq!(report.falling[0].term, "budget");
+        assert_eq!(report.falling[0].examples[0].doc_id, "feb1");
+
+        let markdown = to_markdown(&report);
+        assert!(markdown
+            .starts_with("# Trends: 2025-03-01 to 2025-03-31 vs 2025-01-29 to 2025-02-28\n"));
+        assert!(markdown.contains("| pricing | 0 | 9 | 0.00 → 4.50 | 2025-03-12 MAR2 (5×) |\n"));
+        assert!(!markdown.contains("dana"));
+    }
+}
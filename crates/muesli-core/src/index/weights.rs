@@ -0,0 +1,182 @@
+// ABOUTME: Search ranking weights from [search] in config.toml (field boosts, hybrid mix)
+// ABOUTME: Also fuses lexical and semantic result lists into one ranking
+
+use crate::{config, Paths, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How matches in each field and each search mode contribute to ranking
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SearchWeights {
+    /// Multiplier for BM25 scores from title matches
+    pub title_boost: f32,
+    /// Multiplier for BM25 scores from body matches
+    pub body_boost: f32,
+    /// Share of the semantic score in hybrid search (0 = lexical only, 1 = semantic only)
+    pub semantic_weight: f32,
+}
+
+impl Default for SearchWeights {
+    fn default() -> Self {
+        Self {
+            title_boost: 1.0,
+            body_boost: 1.0,
+            semantic_weight: 0.5,
+        }
+    }
+}
+
+impl SearchWeights {
+    /// A config file's `[search]` table, with boosts and the semantic share range-checked
+    pub fn load(path: &Path) -> Result<Self> {
+        let weights: Self = config::load_section(path, "search")?;
+        weights
+            .validate()
+            .map_err(|msg| config::invalid(path, "search", msg))?;
+
+        Ok(weights)
+    }
+
+    /// The ranking both `muesli search` and the MCP search tool use
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        for (name, boost) in [
+            ("title_boost", self.title_boost),
+            ("body_boost", self.body_boost),
+        ] {
+            if !(boost > 0.0 && boost.is_finite()) {
+                return Err(format!("{} must be a positive number", name));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.semantic_weight) {
+            return Err("semantic_weight must be between 0 and 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Scales scores into 0..=1 so BM25 and cosine similarity can be combined
+fn normalized(results: &[(String, f32)]) -> HashMap<&str, f32> {
+    let max = results.iter().map(|r| r.1).fold(f32::MIN, f32::max);
+    let min = results.iter().map(|r| r.1).fold(f32::MAX, f32::min);
+    let range = max - min;
+
+    results
+        .iter()
+        .map(|(doc_id, score)| {
+            let scaled = if range > 0.0 {
+                (score - min) / range
+            } else {
+                1.0
+            };
+            (doc_id.as_str(), scaled)
+        })
+        .collect()
+}
+
+/// Combines lexical and semantic hits into one ranking, best first
+///
+/// Each list is min-max normalized; a document missing from one list scores 0
+/// for that component.
+pub fn fuse(
+    lexical: &[(String, f32)],
+    semantic: &[(String, f32)],
+    semantic_weight: f32,
+) -> Vec<(String, f32)> {
+    let lexical_scores = normalized(lexical);
+    let semantic_scores = normalized(semantic);
+
+    let mut doc_ids: Vec<&str> = lexical_scores.keys().copied().collect();
+    doc_ids.extend(
+        semantic_scores
+            .keys()
+            .filter(|id| !lexical_scores.contains_key(*id)),
+    );
+
+    let mut fused: Vec<(String, f32)> = doc_ids
+        .into_iter()
+        .map(|doc_id| {
+            let lexical = lexical_scores.get(doc_id).copied().unwrap_or(0.0);
+            let semantic = semantic_scores.get(doc_id).copied().unwrap_or(0.0);
+            let score = (1.0 - semantic_weight) * lexical + semantic_weight * semantic;
+            (doc_id.to_string(), score)
+        })
+        .collect();
+
+    fused.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    fused
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hits(list: &[(&str, f32)]) -> Vec<(String, f32)> {
+        list.iter().map(|(id, s)| (id.to_string(), *s)).collect()
+    }
+
+    #[test]
+    fn test_load_defaults_and_overrides() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            SearchWeights::load(&path).unwrap(),
+            SearchWeights::default()
+        );
+
+        std::fs::write(
+            &path,
+            "[search]\ntitle_boost = 2.0\nsemantic_weight = 0.6\n",
+        )
+        .unwrap();
+        let weights = SearchWeights::load(&path).unwrap();
+        assert_eq!(weights.title_boost, 2.0);
+        assert_eq!(weights.body_boost, 1.0);
+        assert_eq!(weights.semantic_weight, 0.6);
+    }
+
+    #[test]
+    fn test_load_rejects_bad_values() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        for bad in [
+            "title_boost = 0.0",
+            "semantic_weight = 1.5",
+            "title_bost = 2.0",
+        ] {
+            std::fs::write(&path, format!("[search]\n{}", bad)).unwrap();
+            assert!(SearchWeights::load(&path).is_err(), "accepted {}", bad);
+        }
+    }
+
+    #[test]
+    fn test_fuse_mixes_by_weight() {
+        let lexical = hits(&[("keyword", 12.0), ("both", 6.0), ("weak", 0.0)]);
+        let semantic = hits(&[("meaning", 0.9), ("both", 0.8), ("far", 0.5)]);
+
+        let ids = |weight: f32| -> Vec<String> {
+            fuse(&lexical, &semantic, weight)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+
+        assert_eq!(ids(0.0)[0], "keyword");
+        assert_eq!(ids(1.0)[0], "meaning");
+        // Ranked well by both, so it wins an even mix
+        assert_eq!(ids(0.5)[0], "both");
+        assert_eq!(ids(0.5).len(), 5);
+    }
+
+    #[test]
+    fn test_fuse_single_hit_lists() {
+        let fused = fuse(&hits(&[("a", 3.0)]), &[], 0.5);
+        assert_eq!(fused, hits(&[("a", 0.5)]));
+    }
+}
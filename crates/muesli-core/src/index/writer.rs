@@ -0,0 +1,146 @@
+// ABOUTME: Memory, thread and commit settings for Tantivy index writers
+// ABOUTME: Read from [index] in config.toml and MUESLI_INDEX_* variables; every writer is opened here
+
+use crate::{config, Error, Paths, Result};
+use serde::Deserialize;
+use std::path::Path;
+use tantivy::{Index, IndexWriter};
+
+/// Tantivy refuses to give an indexing thread less memory than this
+const MIN_MEMORY_MB_PER_THREAD: usize = 15;
+
+/// `[index]` in config.toml; every key is optional
+///
+/// `MUESLI_INDEX_MEMORY_MB`, `MUESLI_INDEX_THREADS` and `MUESLI_INDEX_COMMIT_EVERY`
+/// override the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WriterConfig {
+    /// Memory shared by the indexing threads, in megabytes
+    pub memory_mb: usize,
+    /// Indexing threads; 0 lets Tantivy pick from the CPU count and memory
+    pub threads: usize,
+    /// Documents between commits during sync and reindex; 0 commits once at the end
+    pub commit_every: usize,
+}
+
+impl Default for WriterConfig {
+    fn default() -> Self {
+        Self {
+            memory_mb: 50,
+            threads: 0,
+            commit_every: 0,
+        }
+    }
+}
+
+impl WriterConfig {
+    /// The data directory's `[index]` table with the environment applied on top
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)?.with_env(|key| std::env::var(key).ok())
+    }
+
+    /// A config file's `[index]` table, checked for enough memory per thread
+    pub fn load(path: &Path) -> Result<Self> {
+        let writer: Self = config::load_section(path, "index")?;
+        writer
+            .validate()
+            .map_err(|e| config::invalid(path, "index", e))?;
+        Ok(writer)
+    }
+
+    /// Applies the `MUESLI_INDEX_*` variables `var` returns
+    pub fn with_env(mut self, var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        for (key, field) in [
+            ("MUESLI_INDEX_MEMORY_MB", &mut self.memory_mb),
+            ("MUESLI_INDEX_THREADS", &mut self.threads),
+            ("MUESLI_INDEX_COMMIT_EVERY", &mut self.commit_every),
+        ] {
+            if let Some(value) = var(key) {
+                *field = value.trim().parse().map_err(|_| {
+                    Error::InvalidInput(format!(
+                        "Invalid {}: expected a whole number, got '{}'",
+                        key, value
+                    ))
+                })?;
+            }
+        }
+        self.validate()
+            .map_err(|e| Error::InvalidInput(format!("Invalid index writer settings: {}", e)))?;
+        Ok(self)
+    }
+
+    fn validate(&self) -> std::result::Result<(), String> {
+        let needed = MIN_MEMORY_MB_PER_THREAD * self.threads.max(1);
+        if self.memory_mb < needed {
+            return Err(format!(
+                "memory_mb = {} is too little for {} indexing thread(s); use at least {}",
+                self.memory_mb,
+                self.threads.max(1),
+                needed
+            ));
+        }
+        Ok(())
+    }
+
+    /// Opens a writer on `index` with this memory budget and thread count
+    pub fn writer(&self, index: &Index) -> Result<IndexWriter> {
+        let budget = self.memory_mb.saturating_mul(1_000_000);
+        let writer = match self.threads {
+            0 => index.writer(budget),
+            threads => index.writer_with_num_threads(threads, budget),
+        };
+        writer.map_err(|e| Error::Indexing(format!("Failed to create index writer: {}", e)))
+    }
+
+    /// Whether `pending` uncommitted documents call for a commit before the end
+    pub fn commit_due(&self, pending: usize) -> bool {
+        self.commit_every > 0 && pending >= self.commit_every
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_and_env_overrides() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(WriterConfig::load(&path).unwrap(), WriterConfig::default());
+
+        std::fs::write(&path, "[index]\nmemory_mb = 200\nthreads = 4\n").unwrap();
+        let config = WriterConfig::load(&path).unwrap();
+        assert_eq!((config.memory_mb, config.threads), (200, 4));
+        assert!(!config.commit_due(1000));
+
+        let env: HashMap<&str, &str> = [("MUESLI_INDEX_COMMIT_EVERY", "100")].into();
+        let config = config
+            .with_env(|key| env.get(key).map(|v| v.to_string()))
+            .unwrap();
+        assert_eq!(config.commit_every, 100);
+        assert!(!config.commit_due(99));
+        assert!(config.commit_due(100));
+
+        let env: HashMap<&str, &str> = [("MUESLI_INDEX_THREADS", "lots")].into();
+        let err = config.with_env(|key| env.get(key).map(|v| v.to_string()));
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("MUESLI_INDEX_THREADS"));
+    }
+
+    #[test]
+    fn test_memory_must_cover_threads() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        std::fs::write(&path, "[index]\nmemory_mb = 40\nthreads = 4\n").unwrap();
+        let err = WriterConfig::load(&path).unwrap_err().to_string();
+        assert!(err.contains("use at least 60"), "{}", err);
+
+        std::fs::write(&path, "[index]\nmemory = 40\n").unwrap();
+        assert!(WriterConfig::load(&path).is_err());
+    }
+}
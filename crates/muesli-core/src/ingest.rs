@@ -0,0 +1,533 @@
+// ABOUTME: Turns caption files from other meeting tools (WebVTT, SRT) into raw transcripts
+// ABOUTME: And `muesli import`, which syncs them and tool exports like Granola meetings: markdown, index and embeddings
+
+use crate::api::DocumentSource;
+use crate::importers;
+use crate::model::TranscriptEntry;
+use crate::sync::{sync_all, SyncOptions};
+use crate::{DocumentMetadata, DocumentSummary, Error, Paths, RawTranscript, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, TimeZone, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// One caption: offsets from the start of the recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caption {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Parses a WebVTT file; NOTE, STYLE and REGION blocks are skipped
+pub fn parse_vtt(content: &str) -> Result<Vec<Caption>> {
+    let content = content.trim_start_matches('\u{feff}');
+    if !content.starts_with("WEBVTT") {
+        return Err(Error::InvalidInput(
+            "Not a WebVTT file (missing WEBVTT header)".to_string(),
+        ));
+    }
+
+    parse_cues(&content.replace("\r\n", "\n"), 1)
+}
+
+/// Parses a SubRip (.srt) file
+pub fn parse_srt(content: &str) -> Result<Vec<Caption>> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    parse_cues(&content, 0)
+}
+
+/// Captions from a `.vtt` or `.srt` file, by extension
+pub fn parse_captions(path: &Path) -> Result<Vec<Caption>> {
+    let content = fs::read_to_string(path)?;
+    let captions = match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("vtt") => parse_vtt(&content),
+        Some(ext) if ext.eq_ignore_ascii_case("srt") => parse_srt(&content),
+        _ => {
+            return Err(Error::InvalidInput(format!(
+                "{} is not a caption file; expected .vtt or .srt",
+                path.display()
+            )))
+        }
+    };
+    captions.map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))
+}
+
+/// Cues in blank-line separated blocks, after the first `skip` blocks (the WebVTT header)
+///
+/// A block's lines before its timing are a cue number or identifier; blocks
+/// without a timing are comments or styles.
+fn parse_cues(content: &str, skip: usize) -> Result<Vec<Caption>> {
+    let mut captions = Vec::new();
+    for block in content.split("\n\n").skip(skip) {
+        let mut lines = block.lines().skip_while(|l| !l.contains("-->"));
+        let Some(timing) = lines.next() else {
+            continue;
+        };
+        let (start_ms, end_ms) = parse_timing(timing)?;
+        let text = lines.collect::<Vec<_>>().join(" ");
+        let (speaker, text) = split_speaker(&text);
+        if text.is_empty() {
+            continue;
+        }
+        captions.push(Caption {
+            start_ms,
+            end_ms,
+            speaker,
+            text,
+        });
+    }
+    Ok(captions)
+}
+
+/// `00:01:02.500 --> 00:01:04.000 align:start` as milliseconds
+fn parse_timing(line: &str) -> Result<(u64, u64)> {
+    let invalid = || Error::InvalidInput(format!("Invalid cue timing: {}", line));
+    let (start, rest) = line.split_once("-->").ok_or_else(invalid)?;
+    let end = rest.split_whitespace().next().ok_or_else(invalid)?;
+    Ok((
+        parse_time(start.trim()).ok_or_else(invalid)?,
+        parse_time(end).ok_or_else(invalid)?,
+    ))
+}
+
+/// `HH:MM:SS.mmm` or `MM:SS.mmm` (a comma also separates milliseconds, as in SRT)
+fn parse_time(time: &str) -> Option<u64> {
+    let (clock, millis) = time.split_once(['.', ','])?;
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis.parse::<u64>().ok()?)
+}
+
+/// Speaker from a `<v Alice>` voice tag or a Zoom-style `Alice: ` prefix, and the
+/// text without markup
+fn split_speaker(text: &str) -> (Option<String>, String) {
+    // `<v Alice>` or `<v.loud Alice>`: the name follows the first space
+    let voice = text
+        .strip_prefix("<v")
+        .and_then(|rest| rest.split_once('>'))
+        .map(|(tag, _)| tag.split_once(' ').map_or("", |(_, name)| name).trim());
+    let plain = strip_tags(text);
+
+    if let Some(speaker) = voice.filter(|s| !s.is_empty()) {
+        return (Some(speaker.to_string()), plain);
+    }
+    match plain.split_once(": ") {
+        Some((speaker, rest))
+            if !speaker.is_empty() && speaker.len() <= 40 && !speaker.contains(['.', '?', '!']) =>
+        {
+            (Some(speaker.to_string()), rest.trim().to_string())
+        }
+        _ => (None, plain),
+    }
+}
+
+fn strip_tags(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    plain
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .trim()
+        .to_string()
+}
+
+/// `2025-03-01_weekly-sync` → "weekly sync"
+pub(crate) fn title_from_name(name: &str) -> String {
+    let rest = match date_from_name(name) {
+        Some(_) => name[10..].trim_start_matches(['_', '-', ' ']),
+        None => name,
+    };
+    let title = rest.replace(['_', '-'], " ");
+    if title.trim().is_empty() {
+        name.to_string()
+    } else {
+        title.trim().to_string()
+    }
+}
+
+/// Midnight UTC of a leading `YYYY-MM-DD`
+pub(crate) fn date_from_name(name: &str) -> Option<DateTime<Utc>> {
+    midnight(NaiveDate::parse_from_str(name.get(..10)?, "%Y-%m-%d").ok()?)
+}
+
+fn midnight(date: NaiveDate) -> Option<DateTime<Utc>> {
+    Some(Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0)?))
+}
+
+/// Captions as transcript entries for a meeting that started at `started_at`
+pub fn to_raw_transcript(
+    captions: &[Caption],
+    doc_id: &str,
+    started_at: DateTime<Utc>,
+) -> RawTranscript {
+    let at = |ms: u64| {
+        (started_at + Duration::milliseconds(ms as i64))
+            .to_rfc3339_opts(SecondsFormat::Millis, true)
+    };
+    RawTranscript {
+        entries: captions
+            .iter()
+            .map(|caption| TranscriptEntry {
+                document_id: Some(doc_id.to_string()),
+                start: Some(at(caption.start_ms)),
+                end: Some(at(caption.end_ms)),
+                text: caption.text.clone(),
+                source: None,
+                id: None,
+                is_final: Some(true),
+                speaker: caption.speaker.clone(),
+                confidence: None,
+                words: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+/// What `muesli import` sets instead of what the file name gives
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    pub title: Option<String>,
+    /// Meeting day; the recording is taken to start at midnight UTC
+    pub date: Option<NaiveDate>,
+}
+
+/// What an export says about its meeting; anything unset comes from the file
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Recorded {
+    /// The tool that made the file, recorded as the meeting's `source`
+    pub source: &'static str,
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub participants: Vec<String>,
+    pub duration_seconds: Option<u64>,
+}
+
+/// A caption file ready to sync
+#[derive(Debug, Clone)]
+pub struct ImportedMeeting {
+    /// Recorded as `source` in the frontmatter and sync cache, e.g. "vtt" or "otter"
+    pub source: String,
+    pub summary: DocumentSummary,
+    pub metadata: DocumentMetadata,
+    pub transcript: RawTranscript,
+}
+
+impl ImportedMeeting {
+    /// The meeting in `path`, with the file name (without extension) as document ID
+    ///
+    /// Without `--title`/`--date`, the title is the name with dashes and underscores
+    /// as spaces and the date is a leading `YYYY-MM-DD`, or the file's modification time.
+    pub fn from_file(path: &Path, options: &ImportOptions) -> Result<Self> {
+        let format = match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("srt") => "srt",
+            _ => "vtt",
+        };
+        Self::from_captions(path, format, parse_captions(path)?, options)
+    }
+
+    /// The meeting recorded in `path`, already turned into `captions` by `source`
+    ///
+    /// Named and dated the same way as [`ImportedMeeting::from_file`].
+    pub fn from_captions(
+        path: &Path,
+        source: &'static str,
+        captions: Vec<Caption>,
+        options: &ImportOptions,
+    ) -> Result<Self> {
+        let recorded = Recorded {
+            source,
+            ..Recorded::default()
+        };
+        Self::assemble(path, recorded, captions, options)
+    }
+
+    /// Every meeting in `path`: one for a caption file or an .eml notes email,
+    /// one or more for a Fireflies.ai or Otter.ai JSON export
+    pub fn read(path: &Path, options: &ImportOptions) -> Result<Vec<Self>> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => importers::read_export(path, options),
+            #[cfg(feature = "email")]
+            Some("eml") => Ok(vec![crate::email::read_eml(path, options)?]),
+            _ => Ok(vec![Self::from_file(path, options)?]),
+        }
+    }
+
+    /// The meeting from what its file `recorded`, falling back to the file's name
+    /// and modification time; `options` override both
+    pub(crate) fn assemble(
+        path: &Path,
+        recorded: Recorded,
+        captions: Vec<Caption>,
+        options: &ImportOptions,
+    ) -> Result<Self> {
+        let doc_id = match recorded.id {
+            Some(id) => id,
+            None => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .filter(|s| !s.is_empty())
+                .ok_or_else(|| {
+                    Error::InvalidInput(format!("No file name in {}", path.display()))
+                })?,
+        };
+        let created_at = match options
+            .date
+            .and_then(midnight)
+            .or(recorded.created_at)
+            .or_else(|| date_from_name(&doc_id))
+        {
+            Some(created_at) => created_at,
+            None => fs::metadata(path)?.modified()?.into(),
+        };
+        let title = options
+            .title
+            .clone()
+            .or(recorded.title)
+            .unwrap_or_else(|| title_from_name(&doc_id));
+
+        let mut participants = recorded.participants;
+        if participants.is_empty() {
+            for speaker in captions.iter().filter_map(|c| c.speaker.as_ref()) {
+                if !participants.contains(speaker) {
+                    participants.push(speaker.clone());
+                }
+            }
+        }
+        let now = Utc::now();
+        Ok(ImportedMeeting {
+            source: recorded.source.to_string(),
+            summary: DocumentSummary {
+                id: doc_id.clone(),
+                title: Some(title.clone()),
+                created_at,
+                updated_at: Some(now),
+            },
+            metadata: DocumentMetadata {
+                id: Some(doc_id.clone()),
+                title: Some(title),
+                created_at,
+                updated_at: Some(now),
+                participants,
+                duration_seconds: recorded
+                    .duration_seconds
+                    .or_else(|| captions.iter().map(|c| c.end_ms / 1000).max()),
+                labels: Vec::new(),
+                calendar_event: None,
+            },
+            transcript: to_raw_transcript(&captions, &doc_id, created_at),
+        })
+    }
+}
+
+/// Caption files served to sync as if they came from Granola
+pub struct ImportSource {
+    meetings: Vec<ImportedMeeting>,
+}
+
+impl ImportSource {
+    pub fn new(files: &[PathBuf], options: &ImportOptions) -> Result<Self> {
+        let mut meetings = Vec::new();
+        for path in files {
+            meetings.extend(ImportedMeeting::read(path, options)?);
+        }
+        Self::from_meetings(meetings)
+    }
+
+    /// Meetings read some other way, e.g. transcribed from audio
+    pub fn from_meetings(imported: Vec<ImportedMeeting>) -> Result<Self> {
+        let mut meetings: Vec<ImportedMeeting> = Vec::new();
+        for meeting in imported {
+            if meetings.iter().any(|m| m.summary.id == meeting.summary.id) {
+                return Err(Error::InvalidInput(format!(
+                    "Two meetings would have the ID {}; rename one of the files so each has its own",
+                    meeting.summary.id
+                )));
+            }
+            meetings.push(meeting);
+        }
+        Ok(ImportSource { meetings })
+    }
+
+    pub fn meetings(&self) -> &[ImportedMeeting] {
+        &self.meetings
+    }
+
+    /// Sync settings that rewrite every imported meeting, even one imported before
+    pub fn sync_options(&self) -> SyncOptions {
+        SyncOptions {
+            force: self.meetings.iter().map(|m| m.summary.id.clone()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn find(&self, doc_id: &str) -> Result<&ImportedMeeting> {
+        self.meetings
+            .iter()
+            .find(|m| m.summary.id == doc_id)
+            .ok_or_else(|| Error::Api {
+                endpoint: "import".to_string(),
+                status: 404,
+                message: format!("Document not found: {}", doc_id),
+            })
+    }
+}
+
+#[async_trait]
+impl DocumentSource for ImportSource {
+    async fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        Ok(self.meetings.iter().map(|m| m.summary.clone()).collect())
+    }
+
+    async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        Ok(self.find(doc_id)?.metadata.clone())
+    }
+
+    async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        Ok(self.find(doc_id)?.transcript.clone())
+    }
+
+    fn name(&self) -> &str {
+        "import"
+    }
+
+    fn source_of(&self, doc_id: &str) -> &str {
+        self.find(doc_id).map_or(self.name(), |m| m.source.as_str())
+    }
+}
+
+/// Writes, indexes and embeds caption files like synced meetings
+///
+/// Importing a file again replaces the earlier import of the same name.
+pub async fn import(
+    paths: &Paths,
+    files: &[PathBuf],
+    options: &ImportOptions,
+) -> Result<Vec<DocumentSummary>> {
+    sync_imported(paths, ImportSource::new(files, options)?).await
+}
+
+/// Writes, indexes and embeds the meetings of `source`, replacing earlier imports
+pub async fn sync_imported(paths: &Paths, source: ImportSource) -> Result<Vec<DocumentSummary>> {
+    sync_all(&source, paths, &source.sync_options()).await?;
+    Ok(source.meetings.into_iter().map(|m| m.summary).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VTT: &str = "WEBVTT\n\nNOTE exported by Zoom\n\n1\n00:00:01.000 --> 00:00:03.500\nAlice: Morning all.\n\n2\n00:00:04.000 --> 00:00:06.000 align:start\n<v Bob>Hi, <i>finally</i>.</v>\n\n00:01:00.250 --> 01:00.900\nJust text, no speaker.\nSecond line\n";
+
+    #[test]
+    fn test_parse_vtt() {
+        let captions = parse_vtt(VTT).unwrap();
+        assert_eq!(captions.len(), 3);
+        assert_eq!(
+            captions[0],
+            Caption {
+                start_ms: 1000,
+                end_ms: 3500,
+                speaker: Some("Alice".to_string()),
+                text: "Morning all.".to_string()
+            }
+        );
+        assert_eq!(captions[1].speaker.as_deref(), Some("Bob"));
+        assert_eq!(captions[1].text, "Hi, finally.");
+        assert_eq!(captions[2].start_ms, 60_250);
+        assert_eq!(captions[2].end_ms, 60_900);
+        assert_eq!(captions[2].speaker, None);
+        assert_eq!(captions[2].text, "Just text, no speaker. Second line");
+
+        assert!(parse_vtt("1\n00:00:01,000 --> 00:00:02,000\nHi\n").is_err());
+        assert!(parse_vtt("WEBVTT\n\n00:00:xx.000 --> 00:00:02.000\nHi\n").is_err());
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(title_from_name("2025-03-01_weekly-sync"), "weekly sync");
+        assert_eq!(title_from_name("2025-03-01"), "2025-03-01");
+        assert_eq!(title_from_name("retro_notes"), "retro notes");
+        assert_eq!(
+            date_from_name("2025-03-01_weekly-sync")
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-01T00:00:00+00:00"
+        );
+        assert!(date_from_name("retro").is_none());
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let srt = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\nAlice: Hello\r\nthere\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\n<i>[laughter]</i>\r\n";
+        let captions = parse_srt(srt).unwrap();
+        assert_eq!(captions.len(), 2);
+        assert_eq!(captions[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(captions[0].text, "Hello there");
+        assert_eq!(captions[0].end_ms, 2500);
+        assert_eq!(captions[1].speaker, None);
+        assert_eq!(captions[1].text, "[laughter]");
+    }
+
+    #[test]
+    fn test_imported_meeting() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("2025-03-01_zoom-standup.srt");
+        fs::write(
+            &path,
+            "1\n00:00:01,000 --> 00:00:02,000\nAlice: Hi\n\n2\n00:01:00,000 --> 00:01:30,000\nBob: Bye\n\n3\n00:01:31,000 --> 00:01:32,000\nAlice: Later\n",
+        )
+        .unwrap();
+
+        let meeting = ImportedMeeting::from_file(&path, &ImportOptions::default()).unwrap();
+        assert_eq!(meeting.summary.id, "2025-03-01_zoom-standup");
+        assert_eq!(meeting.metadata.title.as_deref(), Some("zoom standup"));
+        assert_eq!(meeting.metadata.participants, vec!["Alice", "Bob"]);
+        assert_eq!(meeting.metadata.duration_seconds, Some(92));
+        assert_eq!(meeting.transcript.entries.len(), 3);
+
+        let options = ImportOptions {
+            title: Some("Daily Standup".to_string()),
+            date: NaiveDate::from_ymd_opt(2025, 4, 2),
+        };
+        let meeting = ImportedMeeting::from_file(&path, &options).unwrap();
+        assert_eq!(meeting.summary.title.as_deref(), Some("Daily Standup"));
+        assert_eq!(
+            meeting.transcript.entries[0].start.as_deref(),
+            Some("2025-04-02T00:00:01.000Z")
+        );
+
+        let text = temp.path().join("notes.txt");
+        fs::write(&text, "hi").unwrap();
+        assert!(ImportedMeeting::from_file(&text, &options).is_err());
+    }
+
+    #[test]
+    fn test_to_raw_transcript() {
+        let captions = parse_vtt(VTT).unwrap();
+        let start = "2025-03-01T09:00:00Z".parse().unwrap();
+        let raw = to_raw_transcript(&captions, "standup", start);
+        assert_eq!(
+            raw.entries[0].start.as_deref(),
+            Some("2025-03-01T09:00:01.000Z")
+        );
+        assert_eq!(
+            raw.entries[2].end.as_deref(),
+            Some("2025-03-01T09:01:00.900Z")
+        );
+        assert_eq!(raw.entries[1].document_id.as_deref(), Some("standup"));
+    }
+}
@@ -0,0 +1,107 @@
+// ABOUTME: Secrets in the operating system's credential store via the keyring crate
+// ABOUTME: macOS Keychain, Secret Service on Linux and BSD, Credential Manager on Windows
+
+use crate::profile::Profile;
+use crate::{Error, Result};
+use keyring::Entry;
+
+/// Service name every muesli secret is stored under
+pub const SERVICE: &str = "muesli";
+
+/// Account holding the OpenAI API key
+pub const OPENAI_API_KEY: &str = "openai_api_key";
+
+/// Account holding the Granola bearer token of `profile`, or of no profile
+///
+/// Each profile gets its own entry so one account's token is never sent for another.
+pub fn granola_token_account(profile: Option<&Profile>) -> String {
+    match profile {
+        Some(profile) => format!("granola_token:{}", profile.name),
+        None => "granola_token".to_string(),
+    }
+}
+
+/// The credential store this platform uses, for messages
+pub fn backend_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macOS Keychain"
+    } else if cfg!(target_os = "windows") {
+        "Windows Credential Manager"
+    } else {
+        "Secret Service"
+    }
+}
+
+fn entry(account: &str) -> Result<Entry> {
+    // Tests never touch the real credential store
+    #[cfg(test)]
+    {
+        static MOCK: std::sync::Once = std::sync::Once::new();
+        MOCK.call_once(|| {
+            keyring::set_default_credential_builder(keyring::mock::default_credential_builder())
+        });
+    }
+    Entry::new(SERVICE, account)
+        .map_err(|e| Error::Auth(format!("Failed to access {}: {}", backend_name(), e)))
+}
+
+/// The secret stored for `account`, or None if there isn't one
+pub fn get(account: &str) -> Result<Option<String>> {
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(Error::Auth(format!(
+            "Failed to read {} from {}: {}",
+            account,
+            backend_name(),
+            e
+        ))),
+    }
+}
+
+/// Stores `secret` for `account`, replacing any earlier one
+pub fn set(account: &str, secret: &str) -> Result<()> {
+    entry(account)?.set_password(secret).map_err(|e| {
+        Error::Auth(format!(
+            "Failed to store {} in {}: {}",
+            account,
+            backend_name(),
+            e
+        ))
+    })
+}
+
+/// Removes the secret for `account`; false if there was none
+pub fn delete(account: &str) -> Result<bool> {
+    match entry(account)?.delete_password() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(Error::Auth(format!(
+            "Failed to remove {} from {}: {}",
+            account,
+            backend_name(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_granola_token_account() {
+        assert_eq!(granola_token_account(None), "granola_token");
+        let work = Profile {
+            name: "work".into(),
+            ..Default::default()
+        };
+        assert_eq!(granola_token_account(Some(&work)), "granola_token:work");
+    }
+
+    #[test]
+    fn test_missing_entry() {
+        assert_eq!(get("muesli_test_missing").unwrap(), None);
+        assert!(!delete("muesli_test_missing").unwrap());
+    }
+}
@@ -0,0 +1,93 @@
+// ABOUTME: Public library API for Muesli transcript sync
+// ABOUTME: Re-exports core modules for external use
+
+pub mod access;
+pub mod account;
+pub mod alerts;
+pub mod api;
+pub mod archive;
+pub mod auth;
+pub mod bundle;
+pub mod condense;
+pub mod config;
+pub mod convert;
+pub mod dedupe;
+pub mod document;
+pub mod error;
+pub mod export;
+pub mod find;
+pub mod gaps;
+pub mod goals;
+pub mod importers;
+pub mod ingest;
+pub mod manifest;
+pub mod model;
+pub mod notes;
+pub mod obsidian;
+pub mod offline;
+pub mod output;
+pub mod perf;
+pub mod person;
+pub mod pipeline;
+pub mod profile;
+pub mod related;
+pub mod search;
+pub mod source;
+pub mod speakers;
+pub mod stats;
+pub mod storage;
+pub mod subtitles;
+pub mod sync;
+pub mod template;
+pub mod term;
+pub mod testing;
+pub mod timings;
+pub mod url_handler;
+pub mod util;
+pub mod warnings;
+pub mod watch;
+
+#[cfg(feature = "index")]
+pub mod index;
+
+#[cfg(feature = "index")]
+pub mod snapshot;
+
+#[cfg(feature = "semantic")]
+pub mod embeddings;
+
+#[cfg(feature = "summaries")]
+pub mod retro;
+
+#[cfg(feature = "summaries")]
+pub mod summary;
+
+#[cfg(feature = "mcp")]
+pub mod mcp;
+
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+
+#[cfg(feature = "transcribe")]
+pub mod transcribe;
+
+#[cfg(feature = "keychain")]
+pub mod keychain;
+
+#[cfg(feature = "email")]
+pub mod email;
+
+#[cfg(feature = "email")]
+pub mod imap;
+
+pub use api::{ApiClient, AsyncApiClient, DocumentSource};
+pub use auth::resolve_token;
+pub use convert::{to_markdown, MarkdownOutput};
+pub use error::{Error, Result};
+pub use model::{
+    CalendarEvent, CalendarPerson, DocumentMetadata, DocumentPanel, DocumentSummary,
+    DocumentUpdates, Frontmatter, RawTranscript,
+};
+pub use search::{DocKind, SearchHit};
+pub use storage::{read_frontmatter, write_atomic, Paths};
+pub use sync::{sync_all, SyncOptions};
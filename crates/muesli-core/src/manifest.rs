@@ -0,0 +1,147 @@
+// ABOUTME: Writes muesli-index.json, a machine-readable listing of the archive
+// ABOUTME: Lets external tools (Obsidian plugins, Raycast) read metadata without the binary
+
+use crate::storage::{write_atomic, Catalog, CatalogEntry};
+use crate::{Paths, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// File name of the manifest in the data directory
+pub const MANIFEST_FILE: &str = "muesli-index.json";
+
+/// Bumped whenever a field is removed or changes meaning; new fields may be added freely
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Top level of `muesli-index.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    pub schema_version: u32,
+    pub generated_at: DateTime<Utc>,
+    /// Every synced document, oldest first
+    pub documents: Vec<ManifestDocument>,
+}
+
+/// One document; paths are relative to the data directory and use `/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestDocument {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Markdown transcript, e.g. `transcripts/2025-10-29_q4-planning.md`
+    pub path: String,
+    /// Raw API response, when it exists
+    pub raw_path: Option<String>,
+    /// Summary saved with `summarize --save`, when it exists
+    pub summary_path: Option<String>,
+    /// Entry and word timestamps, when the transcript has any
+    #[serde(default)]
+    pub timings_path: Option<String>,
+    pub participants: Vec<String>,
+    pub labels: Vec<String>,
+    pub organizations: Vec<String>,
+}
+
+/// `path` relative to `base` with forward slashes, or None if it lies outside
+fn relative(base: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(base).ok()?;
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Some(parts.join("/"))
+}
+
+fn document(paths: &Paths, entry: CatalogEntry) -> ManifestDocument {
+    let stem = entry
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let existing = |path: std::path::PathBuf| {
+        if path.exists() {
+            relative(&paths.data_dir, &path)
+        } else {
+            None
+        }
+    };
+
+    ManifestDocument {
+        path: relative(&paths.data_dir, &entry.path)
+            .unwrap_or_else(|| entry.path.display().to_string()),
+        raw_path: existing(paths.raw_dir.join(format!("{}.json", stem))),
+        summary_path: existing(paths.summaries_dir.join(format!("{}_summary.md", stem))),
+        timings_path: existing(crate::timings::path_for(paths, &stem)),
+        doc_id: entry.doc_id,
+        title: entry.title,
+        created_at: entry.created_at,
+        participants: entry.participants,
+        labels: entry.labels,
+        organizations: entry.organizations,
+    }
+}
+
+/// Builds the manifest from the catalog
+pub fn build(paths: &Paths) -> Result<Manifest> {
+    let documents = Catalog::open(paths)?
+        .entries()?
+        .into_iter()
+        .map(|entry| document(paths, entry))
+        .collect();
+
+    Ok(Manifest {
+        schema_version: SCHEMA_VERSION,
+        generated_at: Utc::now(),
+        documents,
+    })
+}
+
+/// Rewrites `muesli-index.json` atomically, returning the number of documents listed
+pub fn write(paths: &Paths) -> Result<usize> {
+    let manifest = build(paths)?;
+    let json = serde_json::to_string_pretty(&manifest)?;
+    write_atomic(
+        &paths.data_dir.join(MANIFEST_FILE),
+        json.as_bytes(),
+        &paths.tmp_dir,
+    )?;
+    Ok(manifest.documents.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_lists_documents_with_relative_paths() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        std::fs::write(
+            paths.transcripts_dir.join("2025-10-29_planning.md"),
+            "---\ndoc_id: d1\nsource: granola\ncreated_at: 2025-10-29T10:00:00Z\n\
+title: Planning\nlabels: [Q4]\ngenerator: muesli\n---\n\n# Planning\n",
+        )
+        .unwrap();
+        std::fs::write(paths.raw_dir.join("2025-10-29_planning.json"), "[]").unwrap();
+
+        assert_eq!(write(&paths).unwrap(), 1);
+
+        let json = std::fs::read_to_string(temp.path().join(MANIFEST_FILE)).unwrap();
+        let manifest: Manifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(manifest.schema_version, SCHEMA_VERSION);
+
+        let doc = &manifest.documents[0];
+        assert_eq!(doc.doc_id, "d1");
+        assert_eq!(doc.path, "transcripts/2025-10-29_planning.md");
+        assert_eq!(
+            doc.raw_path.as_deref(),
+            Some("raw/2025-10-29_planning.json")
+        );
+        assert_eq!(doc.summary_path, None);
+        assert_eq!(doc.timings_path, None);
+        assert_eq!(doc.labels, vec!["Q4"]);
+    }
+}
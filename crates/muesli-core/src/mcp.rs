@@ -0,0 +1,1056 @@
+// ABOUTME: Model Context Protocol server implementation
+// ABOUTME: Exposes muesli functionality as MCP tools for AI assistants
+
+mod cache;
+mod config;
+mod limits;
+
+use cache::ArchiveCache;
+use config::McpConfig;
+
+use crate::access::AccessPolicy;
+use crate::profile::Profile;
+use crate::storage::{parse_frontmatter, CatalogEntry, Paths};
+use chrono::NaiveDate;
+use rmcp::{
+    handler::server::{
+        router::{prompt::PromptRouter, tool::ToolRouter},
+        tool::ToolCallContext,
+        wrapper::Parameters,
+        ServerHandler,
+    },
+    model::{
+        CallToolRequestParam, CallToolResult, Content, ErrorData as McpError,
+        GetPromptRequestParam, GetPromptResult, ListPromptsResult, ListToolsResult,
+        PaginatedRequestParam, PromptMessage, PromptMessageRole,
+    },
+    prompt, prompt_handler, prompt_router,
+    schemars::JsonSchema,
+    service::{RequestContext, RoleServer},
+    tool, tool_router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct MuesliMcpService {
+    paths: Arc<Paths>,
+    access: AccessPolicy,
+    limits: limits::Limits,
+    cache: ArchiveCache,
+    /// Supplies the token for sync_documents
+    profile: Option<Profile>,
+    tool_router: ToolRouter<Self>,
+    prompt_router: PromptRouter<Self>,
+}
+
+impl MuesliMcpService {
+    pub fn new(
+        data_dir: Option<std::path::PathBuf>,
+        profile: Option<Profile>,
+    ) -> crate::Result<Self> {
+        let paths = Paths::new(data_dir, profile.as_ref())?;
+        let config = McpConfig::configured(&paths)?;
+        Ok(Self {
+            paths: Arc::new(paths),
+            access: config.access(),
+            limits: limits::Limits::new(
+                config.requests_per_minute,
+                config.max_concurrent_summaries,
+            ),
+            cache: ArchiveCache::new(config.access()),
+            profile,
+            tool_router: Self::tool_router(),
+            prompt_router: Self::prompt_router(),
+        })
+    }
+
+    /// Cataloged documents the access policy lets clients see, oldest first
+    fn shared_entries(&self) -> std::result::Result<Arc<Vec<CatalogEntry>>, McpError> {
+        self.cache
+            .shared_entries(&self.paths)
+            .map_err(|e| McpError::internal_error(format!("Failed to list documents: {}", e), None))
+    }
+
+    /// Whether a document exists and clients may see it, going by its cataloged access tag
+    #[cfg(feature = "index")]
+    fn is_shared_doc(&self, doc_id: &str) -> bool {
+        matches!(self.cache.lookup(&self.paths, doc_id), Ok(Some(entry)) if self.access.allows(entry.access))
+    }
+
+    /// Runs a search until it yields `limit` hits clients may see, or runs out
+    ///
+    /// `fetch` is asked for twice as many results each round, so hidden
+    /// documents near the top can't crowd out shared ones further down.
+    #[cfg(feature = "index")]
+    fn shared_hits(
+        &self,
+        limit: usize,
+        mut fetch: impl FnMut(usize) -> std::result::Result<Vec<crate::SearchHit>, McpError>,
+    ) -> std::result::Result<Vec<crate::SearchHit>, McpError> {
+        let mut candidates = limit.max(1);
+        loop {
+            let results = fetch(candidates)?;
+            let exhausted = results.len() < candidates;
+            let hits: Vec<_> = results
+                .into_iter()
+                .filter(|r| self.is_shared_doc(&r.doc_id))
+                .take(limit)
+                .collect();
+            if hits.len() >= limit || exhausted || candidates == usize::MAX {
+                return Ok(hits);
+            }
+            candidates = candidates.saturating_mul(2);
+        }
+    }
+
+    /// Looks up a document and reads its full markdown, or None if it is unknown
+    /// or its access tag keeps it from clients
+    ///
+    /// The tag is checked again in the text about to be returned, in case the
+    /// file changed since the catalog saw it; unreadable frontmatter stays hidden.
+    fn load_document(&self, doc_id: &str) -> Option<(CatalogEntry, String)> {
+        let entry = self.cache.lookup(&self.paths, doc_id).ok()??;
+        if !self.access.allows(entry.access) {
+            return None;
+        }
+        let content = std::fs::read_to_string(&entry.path).ok()?;
+        let fm = parse_frontmatter(&content).ok()??;
+        self.access.allows(fm.access).then_some((entry, content))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ListDocumentsRequest {
+    /// Maximum number of documents to return (default: 50)
+    #[serde(default = "default_page_size")]
+    limit: usize,
+    /// Documents to skip, newest first; pass the previous response's next_offset
+    #[serde(default)]
+    offset: usize,
+    /// Only meetings on or after this day, YYYY-MM-DD
+    #[serde(default)]
+    after: Option<String>,
+    /// Only meetings before this day, YYYY-MM-DD
+    #[serde(default)]
+    before: Option<String>,
+    /// Only meetings with a participant whose name or email contains this (case-insensitive)
+    #[serde(default)]
+    participant: Option<String>,
+}
+
+fn default_page_size() -> usize {
+    50
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct SearchDocumentsRequest {
+    /// Search query string
+    query: String,
+    /// Maximum number of results (default: 10)
+    #[serde(default = "default_limit")]
+    limit: usize,
+    /// Use semantic search with embeddings
+    #[serde(default)]
+    semantic: bool,
+    /// Only match documents with this label
+    #[serde(default)]
+    label: Option<String>,
+    /// Leave out documents with any of these labels
+    #[serde(default)]
+    exclude_labels: Vec<String>,
+}
+
+fn default_limit() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct GetDocumentRequest {
+    /// Document ID to retrieve
+    doc_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct SyncDocumentsRequest {
+    /// API token for authentication (optional, uses default auth if not provided)
+    #[serde(default)]
+    token: Option<String>,
+    /// Force reindex of all documents without re-downloading (requires index feature)
+    #[serde(default)]
+    reindex: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct SummarizeDocumentRequest {
+    /// Document ID to summarize
+    doc_id: String,
+    /// OpenAI API key (optional, uses keychain or env if not provided; unused with Ollama)
+    #[serde(default)]
+    api_key: Option<String>,
+    /// Prompt preset (standup, 1on1, interview, board, incident, "default", or a configured one);
+    /// matched from the meeting's labels when omitted
+    #[serde(default)]
+    preset: Option<String>,
+    /// Also save the summary to the summaries directory, as 'muesli summarize --save' does
+    #[serde(default)]
+    save: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct ListSummariesRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct GetStatsRequest {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct GetStatisticsRequest {
+    /// First day to count, YYYY-MM-DD (default: all history)
+    #[serde(default)]
+    since: Option<String>,
+    /// Last day to count, YYYY-MM-DD, inclusive (default: today)
+    #[serde(default)]
+    until: Option<String>,
+    /// Number of top participants to return (default: 10)
+    #[serde(default = "default_limit")]
+    top: usize,
+}
+
+fn parse_day(field: &str, value: Option<&str>) -> std::result::Result<Option<NaiveDate>, McpError> {
+    value
+        .map(|v| {
+            NaiveDate::parse_from_str(v.trim(), "%Y-%m-%d").map_err(|_| {
+                McpError::invalid_params(format!("{} must be YYYY-MM-DD, got '{}'", field, v), None)
+            })
+        })
+        .transpose()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct CompareDocumentsRequest {
+    /// Array of document IDs to compare
+    doc_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct FollowUpCheckRequest {
+    /// Previous meeting document ID
+    previous_doc_id: String,
+    /// Current meeting document ID
+    current_doc_id: String,
+}
+
+#[tool_router]
+impl MuesliMcpService {
+    #[tool(
+        description = "List meeting transcripts with metadata, newest first, a page at a time; filter by date range or participant"
+    )]
+    async fn list_documents(
+        &self,
+        params: Parameters<ListDocumentsRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let request = params.0;
+        let after = parse_day("after", request.after.as_deref())?;
+        let before = parse_day("before", request.before.as_deref())?;
+        let participant = request.participant.as_deref().map(str::to_lowercase);
+
+        let entries = self.shared_entries()?;
+
+        let matching: Vec<_> = entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                let day = entry.created_at.date_naive();
+                after.map_or(true, |after| day >= after)
+                    && before.map_or(true, |before| day < before)
+                    && participant.as_ref().map_or(true, |name| {
+                        entry
+                            .participants
+                            .iter()
+                            .any(|p| p.to_lowercase().contains(name))
+                    })
+            })
+            .collect();
+
+        let total = matching.len();
+        let end = request.offset.saturating_add(request.limit).min(total);
+        let docs: Vec<_> = matching
+            .into_iter()
+            .skip(request.offset)
+            .take(request.limit)
+            .map(|entry| {
+                serde_json::json!({
+                    "doc_id": entry.doc_id,
+                    "title": entry.title,
+                    "created_at": entry.created_at.to_rfc3339(),
+                    "path": entry.path.display().to_string(),
+                })
+            })
+            .collect();
+
+        let page = serde_json::json!({
+            "total": total,
+            "offset": request.offset,
+            "next_offset": (end < total).then_some(end),
+            "documents": docs,
+        });
+        let json_text = serde_json::to_string_pretty(&page)
+            .map_err(|e| McpError::internal_error(format!("Failed to serialize: {}", e), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json_text)]))
+    }
+
+    #[tool(description = "List meetings with a saved summary")]
+    async fn list_summaries(
+        &self,
+        _params: Parameters<ListSummariesRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let entries = self.shared_entries()?;
+
+        let summaries: Vec<_> = entries
+            .iter()
+            .filter_map(|entry| {
+                let path = crate::sync::summary_path(&self.paths, &entry.path);
+                path.exists().then(|| {
+                    serde_json::json!({
+                        "doc_id": entry.doc_id,
+                        "title": entry.title,
+                        "created_at": entry.created_at.to_rfc3339(),
+                        "summary_path": path.display().to_string(),
+                    })
+                })
+            })
+            .collect();
+
+        let json_text = serde_json::to_string_pretty(&summaries)
+            .map_err(|e| McpError::internal_error(format!("Failed to serialize: {}", e), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json_text)]))
+    }
+
+    #[tool(description = "Get the saved summary of a meeting")]
+    async fn get_summary(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let doc_id = &params.0.doc_id;
+        let (entry, _) = self.load_document(doc_id).ok_or_else(|| {
+            McpError::invalid_params(format!("Document not found: {}", doc_id), None)
+        })?;
+
+        let path = crate::sync::summary_path(&self.paths, &entry.path);
+        let summary = std::fs::read_to_string(&path).map_err(|_| {
+            McpError::invalid_params(
+                format!(
+                    "No saved summary for {}; call summarize_document with save set to create one",
+                    doc_id
+                ),
+                None,
+            )
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(summary)]))
+    }
+
+    #[tool(description = "Search meeting transcripts by text query")]
+    async fn search_documents(
+        &self,
+        #[cfg_attr(not(feature = "index"), allow(unused_variables))] params: Parameters<
+            SearchDocumentsRequest,
+        >,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        #[cfg(feature = "index")]
+        {
+            let query = &params.0.query;
+            let limit = params.0.limit;
+
+            // Check if index exists
+            if !self.paths.index_dir.exists() {
+                return Err(McpError::internal_error(
+                    "No index found. Run 'muesli sync' first to build the index.",
+                    None,
+                ));
+            }
+
+            let filters = crate::index::text::SearchFilters {
+                label: params.0.label.clone(),
+                exclude_labels: params.0.exclude_labels.clone(),
+                ..Default::default()
+            };
+
+            // Perform search
+            #[cfg(feature = "semantic")]
+            if params.0.semantic {
+                let hits = self.shared_hits(limit, |candidates| {
+                    crate::embeddings::semantic_search_filtered(
+                        &self.paths,
+                        query,
+                        candidates,
+                        &filters,
+                    )
+                    .map_err(|e| {
+                        McpError::internal_error(format!("Semantic search failed: {}", e), None)
+                    })
+                })?;
+
+                let json_text = serde_json::to_string_pretty(&hits).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize: {}", e), None)
+                })?;
+                return Ok(CallToolResult::success(vec![Content::text(json_text)]));
+            }
+
+            // Text search
+            let searcher = self.cache.searcher(&self.paths).map_err(|e| {
+                McpError::internal_error(format!("Failed to open index: {}", e), None)
+            })?;
+
+            let weights = crate::index::weights::SearchWeights::configured(&self.paths)
+                .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+            let hits = self.shared_hits(limit, |candidates| {
+                crate::index::text::search_in(&searcher, query, candidates, &filters, &weights)
+                    .map_err(|e| McpError::internal_error(format!("Search failed: {}", e), None))
+            })?;
+
+            let json_text = serde_json::to_string_pretty(&hits).map_err(|e| {
+                McpError::internal_error(format!("Failed to serialize: {}", e), None)
+            })?;
+            Ok(CallToolResult::success(vec![Content::text(json_text)]))
+        }
+        #[cfg(not(feature = "index"))]
+        {
+            Err(McpError::internal_error(
+                "Search feature not enabled. Rebuild with --features index",
+                None,
+            ))
+        }
+    }
+
+    #[tool(description = "Get full transcript content by document ID")]
+    async fn get_document(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let (_, content) = self.load_document(&params.0.doc_id).ok_or_else(|| {
+            McpError::invalid_params(format!("Document not found: {}", params.0.doc_id), None)
+        })?;
+
+        Ok(CallToolResult::success(vec![Content::text(content)]))
+    }
+
+    #[tool(
+        description = "Get the original transcript JSON (speaker turns with exact timestamps) by document ID"
+    )]
+    async fn get_raw_transcript(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let doc_id = &params.0.doc_id;
+        let (entry, _) = self.load_document(doc_id).ok_or_else(|| {
+            McpError::invalid_params(format!("Document not found: {}", doc_id), None)
+        })?;
+
+        let stem = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+        let raw_path = self.paths.raw_dir.join(format!("{}.json", stem));
+        let json = std::fs::read_to_string(&raw_path).map_err(|_| {
+            McpError::invalid_params(
+                format!(
+                    "No raw transcript saved for {} (run 'muesli fetch {}')",
+                    doc_id, doc_id
+                ),
+                None,
+            )
+        })?;
+        Ok(CallToolResult::success(vec![Content::text(json)]))
+    }
+
+    #[tool(
+        description = "Meeting statistics for a period: meeting count, total and dead hours, hours per week and top participants (same data as 'muesli stats --overview')"
+    )]
+    async fn get_statistics(
+        &self,
+        params: Parameters<GetStatisticsRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let since = parse_day("since", params.0.since.as_deref())?;
+        let until = parse_day("until", params.0.until.as_deref())?;
+
+        let meetings: Vec<_> = crate::stats::meetings_with_times(&self.paths)
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to compute statistics: {}", e), None)
+            })?
+            .into_iter()
+            .filter(|(fm, _)| self.access.allows(fm.access))
+            .collect();
+        let overview = crate::stats::overview_of(&meetings, since, until, params.0.top);
+
+        let json_text = serde_json::to_string_pretty(&overview)
+            .map_err(|e| McpError::internal_error(format!("Failed to serialize: {}", e), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json_text)]))
+    }
+
+    #[tool(
+        description = "What's in the archive: transcript, summary, index and vector counts, index size, last sync time and data directory. The counts cover the whole archive, including meetings hidden by their access tag"
+    )]
+    async fn get_stats(
+        &self,
+        _params: Parameters<GetStatsRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let stats = crate::archive::collect(&self.paths).map_err(|e| {
+            McpError::internal_error(format!("Failed to read the archive: {}", e), None)
+        })?;
+
+        let json_text = serde_json::to_string_pretty(&stats)
+            .map_err(|e| McpError::internal_error(format!("Failed to serialize: {}", e), None))?;
+        Ok(CallToolResult::success(vec![Content::text(json_text)]))
+    }
+
+    #[tool(description = "Sync new meeting transcripts from the API")]
+    async fn sync_documents(
+        &self,
+        params: Parameters<SyncDocumentsRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        crate::offline::ensure_online("syncing")
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+
+        // Create API client
+        let token = if let Some(ref t) = params.0.token {
+            t.clone()
+        } else {
+            crate::auth::resolve_token(None, self.profile.as_ref()).map_err(|e| {
+                McpError::internal_error(format!("Failed to resolve auth token: {}", e), None)
+            })?
+        };
+
+        let client = crate::api::ApiConfig::configured(&self.paths)
+            .and_then(
+                |config| Ok(crate::api::AsyncApiClient::new(token, None)?.with_config(config)),
+            )
+            .map_err(|e| {
+                McpError::internal_error(format!("Failed to create API client: {}", e), None)
+            })?;
+
+        // Perform sync
+        let options = crate::sync::SyncOptions {
+            #[cfg(feature = "index")]
+            reindex: params.0.reindex,
+            ..Default::default()
+        };
+
+        let synced = crate::sync::sync_all(&client, &self.paths, &options).await;
+        // A reindex replaces the index files under the cached reader
+        self.cache.invalidate();
+        synced.map_err(|e| McpError::internal_error(format!("Sync failed: {}", e), None))?;
+
+        Ok(CallToolResult::success(vec![Content::text(
+            "Sync completed successfully".to_string(),
+        )]))
+    }
+
+    #[tool(description = "Generate AI summary of a meeting transcript, optionally saving it")]
+    #[cfg(feature = "summaries")]
+    async fn summarize_document(
+        &self,
+        params: Parameters<SummarizeDocumentRequest>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        let (entry, content) = self.load_document(&params.0.doc_id).ok_or_else(|| {
+            McpError::invalid_params(format!("Document not found: {}", params.0.doc_id), None)
+        })?;
+        let _permit = self
+            .limits
+            .start_summary()
+            .map_err(|e| McpError::invalid_request(e.to_string(), None))?;
+
+        let body = crate::document::Document::parse(&entry.path, &content)
+            .map_err(|e| McpError::internal_error(e.to_string(), None))?
+            .body;
+
+        // Load config
+        let config_path = self.paths.data_dir.join("summary_config.json");
+        let config = crate::summary::SummaryConfig::load(&config_path)
+            .map_err(|e| McpError::internal_error(format!("Failed to load config: {}", e), None))?;
+        let config = match config
+            .choose_preset(params.0.preset.as_deref(), &entry.labels)
+            .map_err(|e| McpError::invalid_params(e.to_string(), None))?
+        {
+            Some((_, preset)) => config.with_preset(&preset),
+            None => config,
+        };
+
+        // Get API key (not needed for local providers)
+        let api_key = match params.0.api_key.clone() {
+            Some(key) => Some(key),
+            None => crate::summary::api_key_for(&config).map_err(|e| {
+                McpError::internal_error(format!("Failed to get OpenAI API key: {}", e), None)
+            })?,
+        };
+
+        // Generate summary
+        let summary = crate::summary::summarize_transcript(&body, api_key.as_deref(), &config)
+            .await
+            .map_err(|e| McpError::internal_error(format!("Summarization failed: {}", e), None))?;
+
+        let mut content = vec![Content::text(summary.clone())];
+        if params.0.save {
+            let path = crate::sync::save_summary(&self.paths, &entry, &summary).map_err(|e| {
+                McpError::internal_error(format!("Failed to save summary: {}", e), None)
+            })?;
+            content.push(Content::text(format!("Saved to {}", path.display())));
+        }
+        Ok(CallToolResult::success(content))
+    }
+}
+
+// Prompt implementations
+#[prompt_router]
+impl MuesliMcpService {
+    #[prompt(
+        name = "analyze_meeting",
+        description = "Generate a structured analysis prompt for a meeting transcript"
+    )]
+    async fn analyze_meeting_prompt(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_id = &params.0.doc_id;
+
+        // Find and read the document
+        if let Some((_, content)) = self.load_document(doc_id) {
+            let prompt_text = format!(
+                r#"Please analyze this meeting transcript and provide:
+
+1. **Key Decisions**: What decisions were made?
+2. **Action Items**: What tasks were assigned and to whom?
+3. **Discussion Topics**: What were the main topics discussed?
+4. **Open Questions**: What questions remain unanswered?
+5. **Next Steps**: What are the recommended next steps?
+
+# Meeting Transcript
+
+{}"#,
+                content
+            );
+
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompt_text,
+            )];
+        }
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            format!("Error: Document not found: {}", doc_id),
+        )]
+    }
+
+    #[prompt(
+        name = "compare_meetings",
+        description = "Generate a prompt to compare multiple meeting transcripts"
+    )]
+    async fn compare_meetings_prompt(
+        &self,
+        params: Parameters<CompareDocumentsRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_ids = &params.0.doc_ids;
+        let mut transcripts = Vec::new();
+
+        for doc_id in doc_ids {
+            if let Some((entry, content)) = self.load_document(doc_id) {
+                transcripts.push(format!(
+                    "## Meeting: {}\n\n{}",
+                    entry.title.unwrap_or_else(|| "Untitled".to_string()),
+                    content
+                ));
+            }
+        }
+
+        if transcripts.is_empty() {
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                "Error: No matching documents found".to_string(),
+            )];
+        }
+
+        let prompt_text = format!(
+            r#"Please compare these meeting transcripts and provide:
+
+1. **Common Themes**: What topics appear across multiple meetings?
+2. **Progress Tracking**: How have discussed items evolved over time?
+3. **Recurring Issues**: What problems keep coming up?
+4. **Stakeholder Involvement**: Who participates in which discussions?
+5. **Trend Analysis**: What patterns emerge across meetings?
+
+# Transcripts
+
+{}"#,
+            transcripts.join("\n\n---\n\n")
+        );
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            prompt_text,
+        )]
+    }
+
+    #[prompt(
+        name = "extract_action_items",
+        description = "Extract all action items with owners and deadlines from a meeting"
+    )]
+    async fn extract_action_items_prompt(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_id = &params.0.doc_id;
+
+        if let Some((_, content)) = self.load_document(doc_id) {
+            let prompt_text = format!(
+                r#"Please extract all action items from this meeting transcript.
+
+For each action item, identify:
+1. **Task Description**: What needs to be done?
+2. **Owner**: Who is responsible? (if mentioned)
+3. **Deadline**: When is it due? (if mentioned)
+4. **Status**: Was it marked as completed, in-progress, or new?
+5. **Dependencies**: Does it depend on anything else?
+
+Format as a structured list with clear sections.
+
+# Meeting Transcript
+
+{}"#,
+                content
+            );
+
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompt_text,
+            )];
+        }
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            format!("Error: Document not found: {}", doc_id),
+        )]
+    }
+
+    #[prompt(
+        name = "find_decisions",
+        description = "Extract key decisions made in one or more meetings"
+    )]
+    async fn find_decisions_prompt(
+        &self,
+        params: Parameters<CompareDocumentsRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_ids = &params.0.doc_ids;
+        let mut transcripts = Vec::new();
+
+        for doc_id in doc_ids {
+            if let Some((entry, content)) = self.load_document(doc_id) {
+                transcripts.push(format!(
+                    "## Meeting: {} ({})\n\n{}",
+                    entry.title.unwrap_or_else(|| "Untitled".to_string()),
+                    entry.created_at.format("%Y-%m-%d"),
+                    content
+                ));
+            }
+        }
+
+        if transcripts.is_empty() {
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                "Error: No matching documents found".to_string(),
+            )];
+        }
+
+        let prompt_text = format!(
+            r#"Please identify all key decisions made in these meeting transcripts.
+
+For each decision:
+1. **Decision**: What was decided?
+2. **Rationale**: Why was this decision made?
+3. **Alternatives Considered**: What other options were discussed?
+4. **Impact**: Who/what does this affect?
+5. **Date**: When was this decided?
+6. **Follow-up Required**: Any actions needed?
+
+Group decisions by theme or category if multiple meetings are provided.
+
+# Transcripts
+
+{}"#,
+            transcripts.join("\n\n---\n\n")
+        );
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            prompt_text,
+        )]
+    }
+
+    #[prompt(
+        name = "follow_up_check",
+        description = "Compare two meetings to check if action items were completed and decisions implemented"
+    )]
+    async fn follow_up_check_prompt(
+        &self,
+        params: Parameters<FollowUpCheckRequest>,
+    ) -> Vec<PromptMessage> {
+        let mut transcripts = Vec::new();
+
+        // Load both meetings
+        for doc_id in [&params.0.previous_doc_id, &params.0.current_doc_id] {
+            if let Some((entry, content)) = self.load_document(doc_id) {
+                let label = if doc_id == &params.0.previous_doc_id {
+                    "Previous"
+                } else {
+                    "Current"
+                };
+                transcripts.push(format!(
+                    "## {} Meeting: {} ({})\n\n{}",
+                    label,
+                    entry.title.unwrap_or_else(|| "Untitled".to_string()),
+                    entry.created_at.format("%Y-%m-%d"),
+                    content
+                ));
+            }
+        }
+
+        if transcripts.len() < 2 {
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                "Error: Could not find both meetings".to_string(),
+            )];
+        }
+
+        let prompt_text = format!(
+            r#"Please compare these two meetings to track progress and accountability.
+
+Analyze:
+
+1. **Action Items Follow-Through**:
+   - Which action items from the previous meeting were completed?
+   - Which are still pending or in progress?
+   - Which weren't mentioned (possibly forgotten)?
+
+2. **Decision Implementation**:
+   - Were decisions from the previous meeting implemented?
+   - Were any decisions reversed or modified?
+   - What was the outcome?
+
+3. **New vs. Recurring Issues**:
+   - What new topics emerged?
+   - What issues came up again (might indicate systemic problems)?
+
+4. **Progress Assessment**:
+   - Overall, is the team making progress on goals?
+   - Are there blockers preventing forward movement?
+
+5. **Accountability**:
+   - Who followed through on commitments?
+   - Where were there gaps in ownership?
+
+# Meetings
+
+{}"#,
+            transcripts.join("\n\n---\n\n")
+        );
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            prompt_text,
+        )]
+    }
+
+    #[prompt(
+        name = "write_followup_email",
+        description = "Generate a professional follow-up email after a meeting"
+    )]
+    async fn write_followup_email_prompt(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_id = &params.0.doc_id;
+
+        if let Some((entry, content)) = self.load_document(doc_id) {
+            let meeting_title = entry.title.unwrap_or_else(|| "Recent Meeting".to_string());
+            let meeting_date = entry.created_at.format("%B %d, %Y");
+
+            let prompt_text = format!(
+                r#"Please write a professional follow-up email for this meeting.
+
+The email should include:
+
+1. **Subject Line**: Clear and specific
+2. **Opening**: Brief thank you and context
+3. **Key Takeaways**: Main points discussed (2-3 bullet points)
+4. **Decisions Made**: Any decisions reached
+5. **Action Items**: Clear list with owners and deadlines
+   - Format: "- [Owner] will [task] by [deadline]"
+6. **Next Steps**: What happens next
+7. **Questions/Blockers**: Any open questions or blockers that need addressing
+8. **Closing**: Professional sign-off
+
+**Meeting Details:**
+- Title: {}
+- Date: {}
+
+Keep the tone professional but friendly. Be concise and actionable.
+
+# Meeting Transcript
+
+{}"#,
+                meeting_title, meeting_date, content
+            );
+
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompt_text,
+            )];
+        }
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            format!("Error: Document not found: {}", doc_id),
+        )]
+    }
+
+    #[prompt(
+        name = "schedule_followup_meeting",
+        description = "Generate agenda and details for a follow-up meeting"
+    )]
+    async fn schedule_followup_meeting_prompt(
+        &self,
+        params: Parameters<GetDocumentRequest>,
+    ) -> Vec<PromptMessage> {
+        let doc_id = &params.0.doc_id;
+
+        if let Some((entry, content)) = self.load_document(doc_id) {
+            let meeting_title = entry.title.unwrap_or_else(|| "Recent Meeting".to_string());
+            let meeting_date = entry.created_at.format("%B %d, %Y");
+
+            let prompt_text = format!(
+                r#"Based on this meeting, please create a plan for a follow-up meeting.
+
+Provide:
+
+1. **Suggested Meeting Title**: Clear and specific
+
+2. **Recommended Timing**:
+   - When should this meeting happen?
+   - How much time is needed? (30/60/90 minutes)
+
+3. **Required Attendees**:
+   - Who must attend and why?
+   - Any new stakeholders to include?
+
+4. **Pre-Meeting Preparation**:
+   - What should attendees review/prepare beforehand?
+   - Any materials to send in advance?
+
+5. **Proposed Agenda**:
+   - Opening (5 min): Recap of last meeting
+   - Main Topics (with time allocations)
+   - Action Item Review
+   - Decisions Needed
+   - Next Steps & Closing
+
+6. **Success Criteria**:
+   - What should be accomplished in this meeting?
+   - What decisions need to be made?
+
+7. **Follow-Up Items from Previous Meeting**:
+   - Which action items should be reviewed?
+   - Which decisions need verification?
+   - What open questions need resolution?
+
+**Previous Meeting:**
+- Title: {}
+- Date: {}
+
+# Previous Meeting Transcript
+
+{}"#,
+                meeting_title, meeting_date, content
+            );
+
+            return vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                prompt_text,
+            )];
+        }
+
+        vec![PromptMessage::new_text(
+            PromptMessageRole::User,
+            format!("Error: Document not found: {}", doc_id),
+        )]
+    }
+}
+
+#[prompt_handler(router = self.prompt_router)]
+impl ServerHandler for MuesliMcpService {
+    /// Every tool call counts against the rate limit before it is dispatched
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> std::result::Result<CallToolResult, McpError> {
+        self.limits
+            .admit()
+            .map_err(|e| McpError::invalid_request(e.to_string(), None))?;
+        let tcc = ToolCallContext::new(self, request, context);
+        self.tool_router.call(tcc).await
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> std::result::Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
+    fn get_info(&self) -> rmcp::model::ServerInfo {
+        use rmcp::model::{Implementation, PromptsCapability, ServerCapabilities, ToolsCapability};
+
+        rmcp::model::ServerInfo {
+            protocol_version: rmcp::model::ProtocolVersion::default(),
+            capabilities: ServerCapabilities {
+                tools: Some(ToolsCapability::default()),
+                prompts: Some(PromptsCapability::default()),
+                ..Default::default()
+            },
+            server_info: Implementation {
+                name: "muesli".to_string(),
+                title: Some("Muesli Meeting Transcript Manager".to_string()),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                icons: None,
+                website_url: None,
+            },
+            instructions: Some(
+                "Muesli MCP server for managing meeting transcripts. \
+                 Use tools to list, search, sync, and summarize transcripts. \
+                 Use prompts for structured analysis of meetings."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+pub async fn serve_mcp(
+    data_dir: Option<std::path::PathBuf>,
+    profile: Option<Profile>,
+) -> crate::Result<()> {
+    use rmcp::{transport::stdio, ServiceExt};
+
+    let service = MuesliMcpService::new(data_dir, profile)?;
+    let server = service.serve(stdio()).await.map_err(|e| {
+        crate::Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("MCP server failed: {}", e),
+        ))
+    })?;
+
+    server.waiting().await.map_err(|e| {
+        crate::Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("MCP server error: {}", e),
+        ))
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,253 @@
+// ABOUTME: Keeps the catalog's documents and an open search index reader between MCP tool calls
+// ABOUTME: The documents reload when catalog.sqlite or a transcript changes; a sync through the server drops both
+
+use crate::access::AccessPolicy;
+use crate::storage::catalog::file_modified;
+use crate::storage::{Catalog, CatalogEntry, Paths};
+use crate::Result;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Every cataloged document, oldest first, with an index by ID
+struct Documents {
+    /// Modification time of catalog.sqlite when the documents were read
+    stamp: Option<SystemTime>,
+    entries: Arc<Vec<CatalogEntry>>,
+    /// The entries the access policy lets clients see
+    shared: Arc<Vec<CatalogEntry>>,
+    by_id: HashMap<String, usize>,
+}
+
+/// Shared by every clone of the service
+#[derive(Clone, Default)]
+pub(super) struct ArchiveCache {
+    policy: AccessPolicy,
+    documents: Arc<Mutex<Option<Documents>>>,
+    /// Reloads by itself when a writer commits, in this process or another
+    #[cfg(feature = "index")]
+    reader: Arc<Mutex<Option<tantivy::IndexReader>>>,
+}
+
+fn catalog_stamp(paths: &Paths) -> Option<SystemTime> {
+    std::fs::metadata(paths.data_dir.join("catalog.sqlite"))
+        .and_then(|m| m.modified())
+        .ok()
+}
+
+impl ArchiveCache {
+    /// A cache whose shared entries follow `policy`
+    pub fn new(policy: AccessPolicy) -> Self {
+        Self {
+            policy,
+            ..Self::default()
+        }
+    }
+
+    /// Every cataloged document, oldest first
+    pub fn entries(&self, paths: &Paths) -> Result<Arc<Vec<CatalogEntry>>> {
+        self.load(paths, |d| &d.entries)
+    }
+
+    /// Cataloged documents clients may see, oldest first
+    pub fn shared_entries(&self, paths: &Paths) -> Result<Arc<Vec<CatalogEntry>>> {
+        self.load(paths, |d| &d.shared)
+    }
+
+    fn load(
+        &self,
+        paths: &Paths,
+        pick: fn(&Documents) -> &Arc<Vec<CatalogEntry>>,
+    ) -> Result<Arc<Vec<CatalogEntry>>> {
+        let mut documents = self.documents.lock().unwrap_or_else(|e| e.into_inner());
+        let stamp = catalog_stamp(paths);
+        // A transcript edited by hand may have a new access tag, so every call checks
+        let edited: Vec<CatalogEntry> = match documents.as_ref().filter(|d| d.stamp == stamp) {
+            Some(cached) => {
+                let edited: Vec<_> = cached
+                    .entries
+                    .iter()
+                    .filter(|e| file_modified(&e.path).is_some_and(|m| Some(m) != e.modified))
+                    .cloned()
+                    .collect();
+                if edited.is_empty() {
+                    return Ok(Arc::clone(pick(cached)));
+                }
+                edited
+            }
+            None => Vec::new(),
+        };
+
+        let catalog = Catalog::open(paths)?;
+        catalog.refresh_changed(&edited)?;
+        let entries = catalog.entries()?;
+        let shared = Arc::new(catalog.shared_entries(&self.policy)?);
+        let by_id = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.doc_id.clone(), i))
+            .collect();
+        let loaded = Documents {
+            // Opening may have rebuilt the catalog, so stamp it afterwards
+            stamp: catalog_stamp(paths),
+            entries: Arc::new(entries),
+            shared,
+            by_id,
+        };
+        let picked = Arc::clone(pick(&loaded));
+        *documents = Some(loaded);
+        Ok(picked)
+    }
+
+    /// Finds a document by ID
+    ///
+    /// Misses and moved files go to the catalog itself, which rescans the
+    /// transcripts directory for files added or renamed outside sync.
+    pub fn lookup(&self, paths: &Paths, doc_id: &str) -> Result<Option<CatalogEntry>> {
+        let entries = self.entries(paths)?;
+        let cached = self
+            .documents
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .and_then(|d| d.by_id.get(doc_id).copied())
+            .and_then(|i| entries.get(i))
+            .filter(|entry| entry.doc_id == doc_id && entry.path.exists())
+            .cloned();
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let entry = Catalog::open(paths)?.lookup(doc_id)?;
+        self.invalidate();
+        Ok(entry)
+    }
+
+    /// A searcher over the latest committed state of the text index
+    #[cfg(feature = "index")]
+    pub fn searcher(&self, paths: &Paths) -> Result<tantivy::Searcher> {
+        let mut reader = self.reader.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(reader) = reader.as_ref() {
+            return Ok(reader.searcher());
+        }
+
+        let index = crate::index::text::open_for_search(&paths.index_dir)?;
+        let opened = index
+            .reader()
+            .map_err(|e| crate::Error::Indexing(format!("Failed to create reader: {}", e)))?;
+        let searcher = opened.searcher();
+        *reader = Some(opened);
+        Ok(searcher)
+    }
+
+    /// Drops everything cached, for after a sync that may have rebuilt the index
+    pub fn invalidate(&self) {
+        *self.documents.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        #[cfg(feature = "index")]
+        {
+            *self.reader.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn entry(paths: &Paths, doc_id: &str) -> CatalogEntry {
+        let path = paths.transcripts_dir.join(format!("{}.md", doc_id));
+        std::fs::write(&path, "body").unwrap();
+        CatalogEntry {
+            doc_id: doc_id.into(),
+            modified: file_modified(&path),
+            path,
+            title: Some(doc_id.into()),
+            created_at: Utc::now(),
+            participants: vec![],
+            labels: vec![],
+            organizations: vec![],
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            access: None,
+        }
+    }
+
+    #[test]
+    fn test_entries_follow_catalog_changes() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let catalog = Catalog::open(&paths).unwrap();
+        catalog.upsert(&entry(&paths, "d1")).unwrap();
+
+        let cache = ArchiveCache::default();
+        let first = cache.entries(&paths).unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(Arc::ptr_eq(&first, &cache.entries(&paths).unwrap()));
+        assert_eq!(
+            cache
+                .lookup(&paths, "d1")
+                .unwrap()
+                .unwrap()
+                .title
+                .as_deref(),
+            Some("d1")
+        );
+
+        // A sync in another process writes the catalog
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        catalog.upsert(&entry(&paths, "d2")).unwrap();
+        assert_eq!(cache.entries(&paths).unwrap().len(), 2);
+        assert!(cache.lookup(&paths, "d2").unwrap().is_some());
+        assert!(cache.lookup(&paths, "missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_hand_edited_access_tag_applies_at_once() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let path = paths.transcripts_dir.join("d1.md");
+        let transcript = |access: &str| {
+            format!(
+                "---\ndoc_id: d1\nsource: granola\ncreated_at: 2025-01-01T10:00:00Z\n{}generator: muesli 1.0\n---\n\nbody\n",
+                access
+            )
+        };
+        std::fs::write(&path, transcript("")).unwrap();
+        Catalog::open(&paths).unwrap();
+
+        let cache = ArchiveCache::new(AccessPolicy::default());
+        assert_eq!(cache.shared_entries(&paths).unwrap().len(), 1);
+
+        std::fs::write(&path, transcript("access: private\n")).unwrap();
+        let edited = SystemTime::now() + std::time::Duration::from_secs(5);
+        filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(edited)).unwrap();
+        assert!(cache.shared_entries(&paths).unwrap().is_empty());
+        assert_eq!(
+            cache.lookup(&paths, "d1").unwrap().unwrap().access,
+            Some(crate::access::Access::Private)
+        );
+    }
+
+    #[test]
+    fn test_shared_entries_leave_out_private_documents() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let catalog = Catalog::open(&paths).unwrap();
+        catalog.upsert(&entry(&paths, "d1")).unwrap();
+        let mut private = entry(&paths, "d2");
+        private.access = Some(crate::access::Access::Private);
+        catalog.upsert(&private).unwrap();
+
+        let cache = ArchiveCache::new(AccessPolicy::default());
+        assert_eq!(cache.entries(&paths).unwrap().len(), 2);
+        let shared = cache.shared_entries(&paths).unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].doc_id, "d1");
+    }
+}
@@ -0,0 +1,82 @@
+// ABOUTME: Settings for the MCP server, read from [mcp] in the data directory's config.toml
+// ABOUTME: Covers which access levels clients may read and how hard they may call tools
+
+use crate::access::{Access, AccessPolicy};
+use crate::{config, Paths, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// `[mcp]` in config.toml; every key is optional
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct McpConfig {
+    /// Most restricted access level clients may see
+    pub max_access: Access,
+    /// Access level of documents without an `access` tag
+    pub default_access: Access,
+    /// Tool calls allowed in any 60 seconds; 0 means no limit
+    pub requests_per_minute: u32,
+    /// Summaries generated at once; 0 turns the summarize tool off
+    pub max_concurrent_summaries: usize,
+}
+
+impl Default for McpConfig {
+    fn default() -> Self {
+        let access = AccessPolicy::default();
+        Self {
+            max_access: access.max_access,
+            default_access: access.default_access,
+            requests_per_minute: 60,
+            max_concurrent_summaries: 1,
+        }
+    }
+}
+
+impl McpConfig {
+    /// The access policy and limits in a config file's `[mcp]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "mcp")
+    }
+
+    /// What the server started for this data directory allows its clients
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    pub fn access(&self) -> AccessPolicy {
+        AccessPolicy {
+            max_access: self.max_access,
+            default_access: self.default_access,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_config() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(McpConfig::load(&path).unwrap(), McpConfig::default());
+
+        std::fs::write(
+            &path,
+            "[mcp]\nmax_access = \"public\"\ndefault_access = \"private\"\nrequests_per_minute = 5\n",
+        )
+        .unwrap();
+        let config = McpConfig::load(&path).unwrap();
+        assert_eq!(config.requests_per_minute, 5);
+        assert_eq!(config.max_concurrent_summaries, 1);
+        let policy = config.access();
+        assert!(!policy.allows(None));
+        assert!(!policy.allows(Some(Access::Team)));
+        assert!(policy.allows(Some(Access::Public)));
+
+        std::fs::write(&path, "[mcp]\nmax_access = \"secret\"\n").unwrap();
+        let err = McpConfig::load(&path).unwrap_err();
+        assert!(err.to_string().contains("Invalid"));
+    }
+}
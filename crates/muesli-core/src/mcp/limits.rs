@@ -0,0 +1,154 @@
+// ABOUTME: Throttles MCP tool calls so a runaway agent loop can't hammer the server
+// ABOUTME: A sliding one-minute request window plus a cap on summaries in flight
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Why a call was turned away
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// Too many calls in the last minute; retry after the given wait
+    RateLimited { limit: u32, retry_after: Duration },
+    /// Every summary slot is taken
+    SummariesBusy { limit: usize },
+}
+
+impl std::fmt::Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::RateLimited { limit, retry_after } => write!(
+                f,
+                "Rate limit of {} tool calls per minute reached; retry in {}s",
+                limit,
+                retry_after.as_secs().max(1)
+            ),
+            Refusal::SummariesBusy { limit: 0 } => {
+                write!(
+                    f,
+                    "Summarizing is turned off (max_concurrent_summaries = 0)"
+                )
+            }
+            Refusal::SummariesBusy { limit } => write!(
+                f,
+                "{} summaries are already being generated; wait for one to finish",
+                limit
+            ),
+        }
+    }
+}
+
+/// Shared by every clone of the service, so the limits cover the whole client
+#[derive(Debug, Clone)]
+pub struct Limits {
+    requests_per_minute: u32,
+    max_summaries: usize,
+    recent: Arc<Mutex<VecDeque<Instant>>>,
+    summaries: Arc<AtomicUsize>,
+}
+
+impl Limits {
+    pub fn new(requests_per_minute: u32, max_summaries: usize) -> Self {
+        Self {
+            requests_per_minute,
+            max_summaries,
+            recent: Arc::new(Mutex::new(VecDeque::new())),
+            summaries: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Records a tool call, or refuses it when the last minute is already full
+    pub fn admit(&self) -> Result<(), Refusal> {
+        self.admit_at(Instant::now())
+    }
+
+    fn admit_at(&self, now: Instant) -> Result<(), Refusal> {
+        if self.requests_per_minute == 0 {
+            return Ok(());
+        }
+
+        let mut recent = self.recent.lock().unwrap_or_else(|e| e.into_inner());
+        while recent
+            .front()
+            .is_some_and(|&at| now.duration_since(at) >= WINDOW)
+        {
+            recent.pop_front();
+        }
+        if recent.len() >= self.requests_per_minute as usize {
+            let oldest = recent[0];
+            return Err(Refusal::RateLimited {
+                limit: self.requests_per_minute,
+                retry_after: WINDOW.saturating_sub(now.duration_since(oldest)),
+            });
+        }
+        recent.push_back(now);
+        Ok(())
+    }
+
+    /// Claims a summary slot until the returned permit is dropped
+    pub fn start_summary(&self) -> Result<SummaryPermit, Refusal> {
+        self.summaries
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.max_summaries).then_some(running + 1)
+            })
+            .map_err(|_| Refusal::SummariesBusy {
+                limit: self.max_summaries,
+            })?;
+        Ok(SummaryPermit(Arc::clone(&self.summaries)))
+    }
+}
+
+/// A claimed summary slot, released on drop
+#[derive(Debug)]
+pub struct SummaryPermit(Arc<AtomicUsize>);
+
+impl Drop for SummaryPermit {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_per_minute() {
+        let limits = Limits::new(2, 1);
+        let start = Instant::now();
+        assert!(limits.admit_at(start).is_ok());
+        assert!(limits.admit_at(start + Duration::from_secs(10)).is_ok());
+
+        let refusal = limits
+            .admit_at(start + Duration::from_secs(20))
+            .unwrap_err();
+        assert_eq!(
+            refusal,
+            Refusal::RateLimited {
+                limit: 2,
+                retry_after: Duration::from_secs(40)
+            }
+        );
+        assert!(refusal.to_string().contains("retry in 40s"));
+
+        // The first call has left the window
+        assert!(limits.admit_at(start + Duration::from_secs(60)).is_ok());
+        assert!(Limits::new(0, 1).admit_at(start).is_ok());
+    }
+
+    #[test]
+    fn test_concurrent_summaries() {
+        let limits = Limits::new(0, 1);
+        let permit = limits.start_summary().unwrap();
+        assert_eq!(
+            limits.clone().start_summary().unwrap_err(),
+            Refusal::SummariesBusy { limit: 1 }
+        );
+        drop(permit);
+        assert!(limits.start_summary().is_ok());
+        assert!(Limits::new(0, 0).start_summary().is_err());
+    }
+}
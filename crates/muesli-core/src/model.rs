@@ -0,0 +1,391 @@
+// ABOUTME: Serde data models for Granola API responses
+// ABOUTME: Tolerant parsing with optional fields and flexible timestamps
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentSummary {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_summary_deserialize_minimal() {
+        let json = r#"{"id": "doc123", "created_at": "2025-10-28T15:04:05Z"}"#;
+        let doc: DocumentSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(doc.id, "doc123");
+        assert!(doc.title.is_none());
+        assert!(doc.updated_at.is_none());
+    }
+
+    #[test]
+    fn test_document_summary_deserialize_full() {
+        let json = r#"{
+            "id": "doc123",
+            "title": "Planning Meeting",
+            "created_at": "2025-10-28T15:04:05Z",
+            "updated_at": "2025-10-29T01:23:45Z",
+            "extra_field": "ignored"
+        }"#;
+        let doc: DocumentSummary = serde_json::from_str(json).unwrap();
+        assert_eq!(doc.id, "doc123");
+        assert_eq!(doc.title.as_deref(), Some("Planning Meeting"));
+        assert!(doc.updated_at.is_some());
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentMetadata {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    #[serde(default)]
+    pub duration_seconds: Option<u64>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// The calendar event the meeting was recorded from, when Granola has one
+    #[serde(default, alias = "google_calendar_event")]
+    pub calendar_event: Option<CalendarEvent>,
+}
+
+/// A meeting's calendar event; reads Google Calendar's field names as well as its own
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organizer: Option<CalendarPerson>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attendees: Vec<CalendarPerson>,
+    /// Video call link
+    #[serde(
+        default,
+        alias = "hangoutLink",
+        alias = "conference_url",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub meeting_url: Option<String>,
+    /// Shared by every occurrence of a recurring meeting
+    #[serde(
+        default,
+        alias = "recurringEventId",
+        alias = "recurring_event_id",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub recurrence_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CalendarPerson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(
+        default,
+        alias = "displayName",
+        alias = "display_name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<String>,
+    /// "accepted", "declined", "tentative" or "needsAction", as the calendar has it
+    #[serde(
+        default,
+        alias = "responseStatus",
+        alias = "response_status",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub response: Option<String>,
+}
+
+impl CalendarEvent {
+    /// Whether `person` organized the meeting, matched on email
+    pub fn is_organizer(&self, person: &CalendarPerson) -> bool {
+        match (&self.organizer, &person.email) {
+            (Some(organizer), Some(email)) => organizer
+                .email
+                .as_deref()
+                .is_some_and(|o| o.eq_ignore_ascii_case(email)),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod metadata_tests {
+    use super::*;
+
+    #[test]
+    fn test_document_metadata_deserialize() {
+        let json = r#"{
+            "id": "doc123",
+            "title": "Q4 Planning",
+            "created_at": "2025-10-28T15:04:05Z",
+            "updated_at": "2025-10-29T01:23:45Z",
+            "participants": ["Alice", "Bob"],
+            "duration_seconds": 3600,
+            "labels": ["Planning", "Q4"]
+        }"#;
+        let meta: DocumentMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.participants.len(), 2);
+        assert_eq!(meta.duration_seconds, Some(3600));
+        assert_eq!(meta.labels.len(), 2);
+        assert!(meta.calendar_event.is_none());
+    }
+
+    #[test]
+    fn test_document_metadata_google_calendar_event() {
+        let json = r#"{
+            "created_at": "2025-10-28T15:04:05Z",
+            "google_calendar_event": {
+                "organizer": {"email": "alice@acme.com", "displayName": "Alice"},
+                "attendees": [
+                    {"email": "alice@acme.com", "displayName": "Alice", "responseStatus": "accepted"},
+                    {"email": "bob@acme.com", "responseStatus": "declined", "self": true}
+                ],
+                "hangoutLink": "https://meet.google.com/abc-defg-hij",
+                "recurringEventId": "weekly123",
+                "summary": "Planning"
+            }
+        }"#;
+        let meta: DocumentMetadata = serde_json::from_str(json).unwrap();
+        let event = meta.calendar_event.unwrap();
+        assert_eq!(event.attendees.len(), 2);
+        assert_eq!(event.attendees[1].response.as_deref(), Some("declined"));
+        assert!(event.is_organizer(&event.attendees[0]));
+        assert!(!event.is_organizer(&event.attendees[1]));
+        assert_eq!(
+            event.meeting_url.as_deref(),
+            Some("https://meet.google.com/abc-defg-hij")
+        );
+        assert_eq!(event.recurrence_id.as_deref(), Some("weekly123"));
+    }
+}
+
+/// One of Granola's own AI notes for a meeting ("panels" in the API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentPanel {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    /// A ProseMirror document, as Granola's editor stores it
+    #[serde(default)]
+    pub content: serde_json::Value,
+    /// The same notes as HTML, used when `content` is missing
+    #[serde(default)]
+    pub original_content: Option<String>,
+}
+
+/// One page of the document changes feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentUpdates {
+    /// Documents created or edited after the cursor that was sent
+    #[serde(default, alias = "docs", alias = "documents")]
+    pub changes: Vec<DocumentSummary>,
+    /// Where the next request continues from; kept when missing
+    #[serde(default, alias = "next_cursor")]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RawTranscript {
+    pub entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    #[serde(default)]
+    pub document_id: Option<String>,
+    #[serde(rename = "start_timestamp", default)]
+    pub start: Option<String>,
+    #[serde(rename = "end_timestamp", default)]
+    pub end: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub is_final: Option<bool>,
+    #[serde(default)]
+    pub speaker: Option<String>,
+    /// How sure the transcriber is of `speaker`, from 0 to 1, when it says
+    #[serde(
+        default,
+        alias = "speaker_confidence",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub confidence: Option<f64>,
+    /// Per-word timing, when the API provides it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTiming>,
+}
+
+/// One word of an entry with its own start and end
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    #[serde(rename = "start_timestamp", default)]
+    pub start: Option<String>,
+    #[serde(rename = "end_timestamp", default)]
+    pub end: Option<String>,
+}
+
+// Legacy types kept for backward compatibility with tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    #[serde(default)]
+    pub speaker: Option<String>,
+    #[serde(default)]
+    pub start: Option<TimestampValue>,
+    #[serde(default)]
+    pub end: Option<TimestampValue>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Monologue {
+    #[serde(default)]
+    pub speaker: Option<String>,
+    #[serde(default)]
+    pub start: Option<TimestampValue>,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Block {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TimestampValue {
+    Seconds(f64),
+    String(String),
+}
+
+#[cfg(test)]
+mod transcript_tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_transcript_deserialize() {
+        let json = r#"[
+            {
+                "document_id": "doc123",
+                "speaker": "Alice",
+                "start_timestamp": "2025-10-01T21:35:12.500Z",
+                "end_timestamp": "2025-10-01T21:35:18.000Z",
+                "text": "Hello",
+                "source": "microphone",
+                "id": "entry1",
+                "is_final": true
+            }
+        ]"#;
+        let transcript: RawTranscript = serde_json::from_str(json).unwrap();
+        assert_eq!(transcript.entries.len(), 1);
+        assert_eq!(transcript.entries[0].text, "Hello");
+        assert_eq!(transcript.entries[0].speaker.as_deref(), Some("Alice"));
+    }
+
+    #[test]
+    fn test_raw_transcript_minimal() {
+        let json = r#"[
+            {"text": "Just text"}
+        ]"#;
+        let transcript: RawTranscript = serde_json::from_str(json).unwrap();
+        assert_eq!(transcript.entries.len(), 1);
+        assert_eq!(transcript.entries[0].text, "Just text");
+        assert!(transcript.entries[0].speaker.is_none());
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frontmatter {
+    pub doc_id: String,
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub remote_updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    #[serde(default)]
+    pub duration_seconds: Option<u64>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Email domains of participants, excluding freemail providers
+    #[serde(default)]
+    pub organizations: Vec<String>,
+    /// Attendee names given to generic "Speaker N" labels
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inferred_speakers: Vec<crate::speakers::InferredSpeaker>,
+    /// Who the meeting may be shared with; set by hand and kept across syncs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<crate::access::Access>,
+    /// Hash of the normalized transcript text, shared by exact duplicates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// The document this one duplicates; hidden from search and kept across syncs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+    /// Duplicates merged into this document, which sync no longer downloads
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged_duplicates: Vec<String>,
+    /// Granola's own notes for the meeting, relative to the data directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Organizer, invitees, call link and recurrence from the calendar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub calendar_event: Option<CalendarEvent>,
+    pub generator: String,
+}
+
+#[cfg(test)]
+mod frontmatter_tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_roundtrip() {
+        let fm = Frontmatter {
+            doc_id: "doc123".into(),
+            source: "granola".into(),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            remote_updated_at: Some("2025-10-29T01:23:45Z".parse().unwrap()),
+            title: Some("Test Meeting".into()),
+            participants: vec!["Alice".into(), "Bob".into()],
+            duration_seconds: Some(3600),
+            labels: vec!["Planning".into()],
+            organizations: vec!["acme.com".into()],
+            inferred_speakers: vec![],
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        };
+
+        let yaml = serde_yaml::to_string(&fm).unwrap();
+        let parsed: Frontmatter = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.doc_id, "doc123");
+        assert_eq!(parsed.participants.len(), 2);
+    }
+}
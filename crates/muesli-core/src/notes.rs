@@ -0,0 +1,322 @@
+// ABOUTME: Granola's own AI notes ("panels") for a meeting, saved as notes/<name>.md during sync
+// ABOUTME: Converts the panels' ProseMirror documents to markdown, falling back to their HTML
+
+use crate::{storage::write_atomic, DocumentPanel, Paths, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// The `notes` frontmatter value: the notes file relative to the data directory
+pub fn link_for(stem: &str) -> String {
+    format!("notes/{}.md", stem)
+}
+
+/// `notes/<transcript name>.md`
+pub fn path_for(paths: &Paths, stem: &str) -> PathBuf {
+    paths.notes_dir.join(format!("{}.md", stem))
+}
+
+/// Points the notes for a renamed transcript back at its new name
+pub fn relink(paths: &Paths, from: &str, to: &str) -> Result<()> {
+    let path = path_for(paths, to);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    let relinked = content.replacen(&back_link(from), &back_link(to), 1);
+    if relinked != content {
+        write_atomic(&path, relinked.as_bytes(), &paths.tmp_dir)?;
+    }
+    Ok(())
+}
+
+fn back_link(stem: &str) -> String {
+    format!("_Transcript: [{}](../transcripts/{}.md)_", stem, stem)
+}
+
+/// The companion file: a link back to the transcript, then a section per panel
+pub fn to_markdown(title: Option<&str>, stem: &str, panels: &[DocumentPanel]) -> String {
+    let mut out = format!(
+        "# Notes: {}\n\n{}\n\n",
+        title.unwrap_or("Untitled Meeting"),
+        back_link(stem)
+    );
+    for panel in panels {
+        let heading = panel
+            .title
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .unwrap_or("Notes");
+        out.push_str(&format!("## {}\n\n", heading));
+        let body = panel_markdown(panel);
+        if body.trim().is_empty() {
+            out.push_str("_Empty_\n\n");
+        } else {
+            out.push_str(body.trim_end());
+            out.push_str("\n\n");
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn panel_markdown(panel: &DocumentPanel) -> String {
+    match &panel.content {
+        Value::Object(_) => {
+            let mut out = String::new();
+            block(&panel.content, 0, &mut out);
+            out
+        }
+        Value::String(text) => text.clone(),
+        _ => panel
+            .original_content
+            .as_deref()
+            .map(html_to_text)
+            .unwrap_or_default(),
+    }
+}
+
+fn node_type(node: &Value) -> &str {
+    node.get("type").and_then(Value::as_str).unwrap_or_default()
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn block(node: &Value, depth: usize, out: &mut String) {
+    match node_type(node) {
+        "doc" => {
+            for child in children(node) {
+                block(child, depth, out);
+            }
+        }
+        "heading" => {
+            // Panel sections are `##`, so the panel's own headings go below them
+            let level = node
+                .get("attrs")
+                .and_then(|a| a.get("level"))
+                .and_then(Value::as_u64)
+                .unwrap_or(1);
+            let hashes = "#".repeat((level as usize + 2).min(6));
+            out.push_str(&format!("{} {}\n\n", hashes, inline(children(node))));
+        }
+        "bulletList" | "orderedList" => {
+            list(node, depth, out);
+            out.push('\n');
+        }
+        "blockquote" => {
+            let mut inner = String::new();
+            for child in children(node) {
+                block(child, depth, &mut inner);
+            }
+            for line in inner.trim_end().lines() {
+                out.push_str(format!("> {}", line).trim_end());
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        "codeBlock" => {
+            out.push_str(&format!("```\n{}\n```\n\n", inline(children(node))));
+        }
+        "horizontalRule" => out.push_str("---\n\n"),
+        // Paragraphs, and anything newer than this converter, as text
+        _ => {
+            let text = inline(children(node));
+            if !text.trim().is_empty() {
+                out.push_str(&text);
+                out.push_str("\n\n");
+            }
+        }
+    }
+}
+
+fn list(node: &Value, depth: usize, out: &mut String) {
+    let ordered = node_type(node) == "orderedList";
+    let indent = "  ".repeat(depth);
+    for (i, item) in children(node).iter().enumerate() {
+        let marker = if ordered {
+            format!("{}.", i + 1)
+        } else {
+            "-".to_string()
+        };
+        let mut first = true;
+        for child in children(item) {
+            match node_type(child) {
+                "bulletList" | "orderedList" => {
+                    if first {
+                        out.push_str(&format!("{}{}\n", indent, marker));
+                        first = false;
+                    }
+                    list(child, depth + 1, out);
+                }
+                _ => {
+                    let text = inline(children(child));
+                    if first {
+                        out.push_str(&format!("{}{} {}\n", indent, marker, text));
+                        first = false;
+                    } else {
+                        out.push_str(&format!("{}  {}\n", indent, text));
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn inline(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node_type(node) {
+            "text" => {
+                let mut text = node
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let marks = node.get("marks").and_then(Value::as_array);
+                for mark in marks.map(Vec::as_slice).unwrap_or_default() {
+                    text = match node_type(mark) {
+                        "bold" | "strong" => format!("**{}**", text),
+                        "italic" | "em" => format!("_{}_", text),
+                        "code" => format!("`{}`", text),
+                        "link" => match mark
+                            .get("attrs")
+                            .and_then(|a| a.get("href"))
+                            .and_then(Value::as_str)
+                        {
+                            Some(href) => format!("[{}]({})", text, href),
+                            None => text,
+                        },
+                        _ => text,
+                    };
+                }
+                out.push_str(&text);
+            }
+            "hardBreak" => out.push('\n'),
+            // Mentions and other atoms carry their text as a label
+            _ => match node
+                .get("attrs")
+                .and_then(|a| a.get("label"))
+                .and_then(Value::as_str)
+            {
+                Some(label) => out.push_str(label),
+                None => out.push_str(&inline(children(node))),
+            },
+        }
+    }
+    out
+}
+
+/// Text of an HTML fragment: block ends become line breaks, list items bullets
+fn html_to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = rest[start + 1..start + end].trim().to_ascii_lowercase();
+        let name = tag.split_whitespace().next().unwrap_or_default();
+        match name {
+            "li" => out.push_str("\n- "),
+            "br" | "br/" => out.push('\n'),
+            "/p" | "/h1" | "/h2" | "/h3" | "/h4" | "/ul" | "/ol" | "/div" => out.push_str("\n\n"),
+            _ => {}
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+
+    let text = out
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let mut joined = lines.join("\n");
+    while joined.contains("\n\n\n") {
+        joined = joined.replace("\n\n\n", "\n\n");
+    }
+    joined.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn panel(title: &str, content: Value, html: Option<&str>) -> DocumentPanel {
+        DocumentPanel {
+            id: None,
+            title: Some(title.to_string()),
+            content,
+            original_content: html.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_prosemirror_becomes_markdown() {
+        let content = json!({"type": "doc", "content": [
+            {"type": "heading", "attrs": {"level": 1}, "content": [{"type": "text", "text": "Decisions"}]},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "Ship "},
+                {"type": "text", "text": "Friday", "marks": [{"type": "bold"}]},
+                {"type": "text", "text": ", see "},
+                {"type": "text", "text": "the plan", "marks": [{"type": "link", "attrs": {"href": "https://example.com"}}]}
+            ]},
+            {"type": "bulletList", "content": [
+                {"type": "listItem", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Alice: docs"}]},
+                    {"type": "bulletList", "content": [
+                        {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "API reference"}]}]}
+                    ]}
+                ]},
+                {"type": "listItem", "content": [{"type": "paragraph", "content": [{"type": "text", "text": "Bob: release"}]}]}
+            ]}
+        ]});
+
+        let md = to_markdown(
+            Some("Launch sync"),
+            "2025-03-01_launch-sync",
+            &[panel("Summary", content, None)],
+        );
+        assert_eq!(
+            md,
+            "# Notes: Launch sync\n\n\
+             _Transcript: [2025-03-01_launch-sync](../transcripts/2025-03-01_launch-sync.md)_\n\n\
+             ## Summary\n\n\
+             ### Decisions\n\n\
+             Ship **Friday**, see [the plan](https://example.com)\n\n\
+             - Alice: docs\n  - API reference\n- Bob: release\n"
+        );
+    }
+
+    #[test]
+    fn test_html_fallback_and_empty_panels() {
+        let md = to_markdown(
+            None,
+            "x",
+            &[
+                panel(
+                    "Action items",
+                    Value::Null,
+                    Some("<ul><li>Send &amp; file</li><li>Book room</li></ul>"),
+                ),
+                panel("", Value::Null, None),
+            ],
+        );
+        assert!(
+            md.contains("## Action items\n\n- Send & file\n- Book room\n\n## Notes\n\n_Empty_\n")
+        );
+    }
+}
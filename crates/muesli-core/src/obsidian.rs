@@ -0,0 +1,290 @@
+// ABOUTME: Exports transcripts into an Obsidian vault as notes with properties and wiki-links
+// ABOUTME: A mapping file in the vault keeps each document's note name, so re-exports update in place
+
+use crate::export::{strip_header, transcript_for};
+use crate::related::strip_related;
+use crate::storage::{write_atomic, Catalog};
+use crate::{Error, Frontmatter, Paths, Result};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Vault folder the meeting notes go into
+pub const NOTES_DIR: &str = "Meetings";
+
+/// doc_id -> note path relative to the vault, kept between exports
+const MAP_FILE: &str = ".muesli-obsidian.json";
+
+/// What [`export_vault`] did
+#[derive(Debug, Default)]
+pub struct VaultReport {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// Note properties, in the names Obsidian gives special meaning to where there is one
+#[derive(Serialize)]
+struct Properties<'a> {
+    title: &'a str,
+    date: String,
+    /// Obsidian's date & time property format
+    start: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<u64>,
+    participants: Vec<String>,
+    tags: Vec<String>,
+    doc_id: &'a str,
+    source: &'a str,
+}
+
+/// Writes every transcript as a note in `<vault>/Meetings`
+///
+/// A note keeps the name it got on its first export even if the meeting is
+/// renamed, so links to it keep working. Notes are rewritten on every export;
+/// hidden duplicates are left out. `condensed` drops small talk and filler.
+pub fn export_vault(paths: &Paths, vault: &Path, condensed: bool) -> Result<VaultReport> {
+    if !vault.is_dir() {
+        return Err(Error::InvalidInput(format!(
+            "{} is not a directory; create the vault first",
+            vault.display()
+        )));
+    }
+    std::fs::create_dir_all(vault.join(NOTES_DIR))?;
+
+    let map_path = vault.join(MAP_FILE);
+    let mut notes: BTreeMap<String, String> = match std::fs::read_to_string(&map_path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", map_path.display(), e)))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let mut taken: HashSet<String> = notes.values().map(|n| n.to_lowercase()).collect();
+
+    let mut report = VaultReport::default();
+    for entry in Catalog::open(paths)?.entries()? {
+        if entry.duplicate_of.is_some() {
+            continue;
+        }
+        let (fm, body) = transcript_for(paths, &entry, condensed)?;
+
+        let note = match notes.get(&entry.doc_id) {
+            Some(note) => note.clone(),
+            None => {
+                let note = unique_note_name(&fm, &taken);
+                taken.insert(note.to_lowercase());
+                notes.insert(entry.doc_id.clone(), note.clone());
+                note
+            }
+        };
+
+        let path = vault.join(&note);
+        let content = to_note(&fm, &body)?;
+        match std::fs::read_to_string(&path) {
+            Ok(existing) if existing == content => report.unchanged += 1,
+            Ok(_) => {
+                write_atomic(&path, content.as_bytes(), &paths.tmp_dir)?;
+                report.updated += 1;
+            }
+            Err(_) => {
+                write_atomic(&path, content.as_bytes(), &paths.tmp_dir)?;
+                report.created += 1;
+            }
+        }
+    }
+
+    write_atomic(
+        &map_path,
+        serde_json::to_string_pretty(&notes)?.as_bytes(),
+        &paths.tmp_dir,
+    )?;
+    Ok(report)
+}
+
+/// `Meetings/2025-03-01 Title.md`, with a counter when that name is taken
+fn unique_note_name(fm: &Frontmatter, taken: &HashSet<String>) -> String {
+    let stem = format!(
+        "{} {}",
+        fm.created_at.format("%Y-%m-%d"),
+        link_target(fm.title.as_deref().unwrap_or("Untitled Meeting"))
+    );
+    let mut name = format!("{}/{}.md", NOTES_DIR, stem.trim_end());
+    let mut n = 2;
+    while taken.contains(&name.to_lowercase()) {
+        name = format!("{}/{} ({}).md", NOTES_DIR, stem.trim_end(), n);
+        n += 1;
+    }
+    name
+}
+
+/// Text usable as a note name and inside `[[...]]`
+///
+/// Obsidian rejects `[]#^|` in links and file systems reject `\/:*?"<>`.
+fn link_target(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | '#' | '^' | '|' | '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let name = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    let name: String = name.chars().take(100).collect();
+    // A leading dot would hide the note
+    name.trim_start_matches('.').trim().to_string()
+}
+
+fn wiki_link(text: &str) -> String {
+    format!("[[{}]]", link_target(text))
+}
+
+/// Obsidian tags allow letters, digits, `_`, `-` and `/`, and no spaces
+fn tag(label: &str) -> String {
+    crate::util::slugify(label)
+}
+
+/// The note for one transcript: properties, a line of links, then the transcript
+pub fn to_note(fm: &Frontmatter, body: &str) -> Result<String> {
+    let title = fm.title.as_deref().unwrap_or("Untitled Meeting");
+    let date = fm.created_at.format("%Y-%m-%d").to_string();
+    let participants: Vec<String> = fm.participants.iter().map(|p| wiki_link(p)).collect();
+
+    let mut tags = vec!["meeting".to_string()];
+    for label in &fm.labels {
+        let tag = tag(label);
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    let properties = Properties {
+        title,
+        date: date.clone(),
+        start: fm.created_at.format("%Y-%m-%dT%H:%M").to_string(),
+        duration: fm.duration_seconds.map(|d| d / 60),
+        participants: participants.clone(),
+        tags,
+        doc_id: &fm.doc_id,
+        source: &fm.source,
+    };
+    let yaml = serde_yaml::to_string(&properties).map_err(|e| {
+        Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to serialize note properties: {}", e),
+        ))
+    })?;
+
+    let mut note = format!("---\n{}---\n\n# {}\n\n", yaml, title);
+    // Linking the daily note puts the meeting in its backlinks
+    note.push_str(&format!("Date: [[{}]]", date));
+    if !participants.is_empty() {
+        note.push_str(&format!(" · Participants: {}", participants.join(", ")));
+    }
+    note.push_str("\n\n");
+    note.push_str(strip_header(strip_related(body)).trim_end());
+    note.push('\n');
+    Ok(note)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn frontmatter() -> Frontmatter {
+        Frontmatter {
+            doc_id: "doc1".into(),
+            source: "granola".into(),
+            created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+            remote_updated_at: None,
+            title: Some("Q4 Planning: Roadmap".into()),
+            participants: vec!["Alice Smith".into(), "bob@acme.com".into()],
+            duration_seconds: Some(3170),
+            labels: vec!["Product Review".into()],
+            organizations: Vec::new(),
+            inferred_speakers: Vec::new(),
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        }
+    }
+
+    #[test]
+    fn test_note_has_properties_and_links() {
+        let body = "# Q4 Planning: Roadmap\n\n_Date: 2025-10-28_\n\n**Alice Smith:** Let's start\n";
+        let note = to_note(&frontmatter(), body).unwrap();
+
+        assert!(note.starts_with(
+            "---\ntitle: 'Q4 Planning: Roadmap'\ndate: 2025-10-28\nstart: 2025-10-28T15:04\n"
+        ));
+        assert!(note.contains("participants:\n- '[[Alice Smith]]'\n- '[[bob@acme.com]]'\n"));
+        assert!(note.contains("tags:\n- meeting\n- product-review\n"));
+        assert!(note.contains("\nduration: 52\n"));
+        assert!(note.contains(
+            "Date: [[2025-10-28]] · Participants: [[Alice Smith]], [[bob@acme.com]]\n\n**Alice Smith:** Let's start\n"
+        ));
+        assert_eq!(note.matches("# Q4 Planning").count(), 1);
+    }
+
+    #[test]
+    fn test_note_names() {
+        assert_eq!(link_target("Sync: [draft] #2 / Q&A?"), "Sync draft 2 Q&A");
+        assert_eq!(link_target(".hidden"), "hidden");
+
+        let mut taken = HashSet::new();
+        let name = unique_note_name(&frontmatter(), &taken);
+        assert_eq!(name, "Meetings/2025-10-28 Q4 Planning Roadmap.md");
+        taken.insert(name.to_lowercase());
+        assert_eq!(
+            unique_note_name(&frontmatter(), &taken),
+            "Meetings/2025-10-28 Q4 Planning Roadmap (2).md"
+        );
+    }
+
+    #[test]
+    fn test_reexport_updates_notes_in_place() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().join("data")), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let vault = temp.path().join("vault");
+        std::fs::create_dir(&vault).unwrap();
+
+        let md = paths.transcripts_dir.join("standup.md");
+        let write = |title: &str| {
+            std::fs::write(
+                &md,
+                format!(
+                    "---\ndoc_id: d1\nsource: granola\ncreated_at: 2025-03-01T10:00:00Z\ntitle: {}\ngenerator: muesli 1.0\n---\n\n# {}\n\n**Alice:** Hi\n",
+                    title, title
+                ),
+            )
+            .unwrap();
+            Catalog::open(&paths).unwrap().record_file(&md).unwrap();
+        };
+
+        write("Standup");
+        let report = export_vault(&paths, &vault, false).unwrap();
+        assert_eq!(report.created, 1);
+        let note = vault.join("Meetings/2025-03-01 Standup.md");
+        assert!(note.exists());
+
+        let report = export_vault(&paths, &vault, false).unwrap();
+        assert_eq!(report.unchanged, 1);
+
+        // A renamed meeting keeps its note, so links to it still work
+        write("Daily Standup");
+        let report = export_vault(&paths, &vault, false).unwrap();
+        assert_eq!(report.updated, 1);
+        assert!(std::fs::read_to_string(&note)
+            .unwrap()
+            .contains("# Daily Standup"));
+        assert_eq!(std::fs::read_dir(vault.join(NOTES_DIR)).unwrap().count(), 1);
+
+        assert!(export_vault(&paths, &temp.path().join("missing"), false).is_err());
+    }
+}
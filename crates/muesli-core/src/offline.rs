@@ -0,0 +1,59 @@
+// ABOUTME: The process-wide --offline switch, checked right before every network call
+// ABOUTME: Local commands keep working; anything that would connect fails with Error::Offline
+
+use crate::{Error, Result};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Refuses network calls for the rest of the process; call once at startup
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether network calls are refused
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Fails in offline mode; `what` names what needed the network, e.g. "the Granola API"
+pub fn ensure_online(what: &str) -> Result<()> {
+    if enabled() {
+        return Err(Error::Offline(format!("{} needs the network", what)));
+    }
+    Ok(())
+}
+
+/// Whether `url` points at this machine, like a local Ollama server
+///
+/// Loopback connections never leave the machine, so they are allowed offline.
+pub fn is_loopback(url: &str) -> bool {
+    let Ok(url) = reqwest::Url::parse(url) else {
+        return false;
+    };
+    match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_loopback_hosts() {
+        assert!(is_loopback("http://localhost:11434"));
+        assert!(is_loopback("http://127.0.0.1:11434/"));
+        assert!(is_loopback("http://[::1]:11434"));
+        assert!(!is_loopback("http://gpu-box.lan:11434"));
+        assert!(!is_loopback("https://api.openai.com"));
+        assert!(!is_loopback("not a url"));
+    }
+}
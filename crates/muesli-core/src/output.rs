@@ -0,0 +1,301 @@
+// ABOUTME: Shared result formatting for list/search style commands
+// ABOUTME: Renders rows as aligned tables, TSV, JSON arrays, or NDJSON with column selection
+
+use crate::{DocumentSummary, Error, Result, SearchHit};
+use serde_json::Value;
+
+/// Output format for tabular command results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// Human-readable aligned columns
+    #[default]
+    Table,
+    /// Tab-separated values
+    Tsv,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+}
+
+/// How results should be rendered
+#[derive(Debug, Clone, Default)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    /// Columns to include, in order (None = the row type's defaults)
+    pub columns: Option<Vec<String>>,
+    /// Print a header line for table/tsv output
+    pub header: bool,
+}
+
+/// A record that can be rendered by the output layer
+pub trait Row {
+    /// Every column this row type can produce, in default order
+    fn columns() -> &'static [&'static str];
+
+    /// Columns shown when `--columns` isn't given
+    fn default_columns() -> &'static [&'static str] {
+        Self::columns()
+    }
+
+    /// The value for a column (Null when absent)
+    fn value(&self, column: &str) -> Value;
+}
+
+impl Row for DocumentSummary {
+    fn columns() -> &'static [&'static str] {
+        &["id", "date", "title", "updated_at"]
+    }
+
+    fn value(&self, column: &str) -> Value {
+        match column {
+            "id" => Value::from(self.id.clone()),
+            "date" => Value::from(self.created_at.format("%Y-%m-%d").to_string()),
+            "title" => Value::from(self.title.clone()),
+            "updated_at" => Value::from(self.updated_at.map(|ts| ts.to_rfc3339())),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// A search hit with its 1-based rank, shared by every search mode's output
+#[derive(Debug, Clone)]
+pub struct SearchRow {
+    pub rank: usize,
+    pub hit: SearchHit,
+}
+
+impl SearchRow {
+    /// Numbers hits in the order given
+    pub fn ranked(hits: Vec<SearchHit>) -> Vec<SearchRow> {
+        hits.into_iter()
+            .enumerate()
+            .map(|(i, hit)| SearchRow { rank: i + 1, hit })
+            .collect()
+    }
+}
+
+impl Row for SearchRow {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "rank", "title", "date", "score", "path", "doc_id", "kind", "link", "snippet", "url",
+        ]
+    }
+
+    // `link` and `url` repeat the path and only snapshot hits carry a snippet,
+    // so these are only shown on request
+    fn default_columns() -> &'static [&'static str] {
+        &["rank", "title", "date", "score", "path", "doc_id", "kind"]
+    }
+
+    fn value(&self, column: &str) -> Value {
+        match column {
+            "rank" => Value::from(self.rank),
+            "doc_id" => Value::from(self.hit.doc_id.clone()),
+            "title" => Value::from(self.hit.title.clone()),
+            "date" => Value::from(self.hit.date.clone()),
+            // Round to keep output stable and readable
+            "score" => Value::from((self.hit.score as f64 * 1000.0).round() / 1000.0),
+            "path" => Value::from(self.hit.path.clone()),
+            "kind" => Value::from(self.hit.kind.as_str()),
+            // The path, or a link to the best matching moment in the transcript
+            "link" => Value::from(match &self.hit.anchor {
+                Some(anchor) => format!("{}#{}", self.hit.path, anchor),
+                None => self.hit.path.clone(),
+            }),
+            "snippet" => Value::from(self.hit.snippet.clone()),
+            // Opens the meeting through `muesli url-handler`
+            "url" => Value::from(crate::url_handler::doc_url(&self.hit.doc_id)),
+            _ => Value::Null,
+        }
+    }
+}
+
+fn selected_columns<R: Row>(opts: &OutputOptions) -> Result<Vec<String>> {
+    let available = R::columns();
+
+    let Some(requested) = &opts.columns else {
+        return Ok(R::default_columns().iter().map(|c| c.to_string()).collect());
+    };
+
+    for column in requested {
+        if !available.contains(&column.as_str()) {
+            return Err(Error::InvalidInput(format!(
+                "Unknown column '{}'. Available: {}",
+                column,
+                available.join(",")
+            )));
+        }
+    }
+
+    Ok(requested.clone())
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Renders rows in the requested format; the result has a trailing newline unless empty
+pub fn render<R: Row>(rows: &[R], opts: &OutputOptions) -> Result<String> {
+    let columns = selected_columns::<R>(opts)?;
+
+    let object = |row: &R| {
+        let map: serde_json::Map<String, Value> =
+            columns.iter().map(|c| (c.clone(), row.value(c))).collect();
+        Value::Object(map)
+    };
+
+    let mut out = String::new();
+
+    match opts.format {
+        OutputFormat::Json => {
+            let items: Vec<Value> = rows.iter().map(object).collect();
+            out.push_str(&serde_json::to_string_pretty(&items)?);
+            out.push('\n');
+        }
+        OutputFormat::Ndjson => {
+            for row in rows {
+                out.push_str(&serde_json::to_string(&object(row))?);
+                out.push('\n');
+            }
+        }
+        OutputFormat::Tsv => {
+            if opts.header {
+                out.push_str(&columns.join("\t"));
+                out.push('\n');
+            }
+            for row in rows {
+                let cells: Vec<String> = columns
+                    .iter()
+                    .map(|c| plain(&row.value(c)).replace(['\t', '\n'], " "))
+                    .collect();
+                out.push_str(&cells.join("\t"));
+                out.push('\n');
+            }
+        }
+        OutputFormat::Table => {
+            let cells: Vec<Vec<String>> = rows
+                .iter()
+                .map(|row| columns.iter().map(|c| plain(&row.value(c))).collect())
+                .collect();
+
+            let mut widths: Vec<usize> = columns
+                .iter()
+                .map(|c| if opts.header { c.chars().count() } else { 0 })
+                .collect();
+            for line in &cells {
+                for (i, cell) in line.iter().enumerate() {
+                    widths[i] = widths[i].max(cell.chars().count());
+                }
+            }
+
+            let mut push_line = |line: Vec<String>| {
+                let padded: Vec<String> = line
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cell)| {
+                        let pad = widths[i].saturating_sub(cell.chars().count());
+                        format!("{}{}", cell, " ".repeat(pad))
+                    })
+                    .collect();
+                out.push_str(padded.join("  ").trim_end());
+                out.push('\n');
+            };
+
+            if opts.header {
+                push_line(columns.iter().map(|c| c.to_uppercase()).collect());
+            }
+            for line in cells {
+                push_line(line);
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docs() -> Vec<DocumentSummary> {
+        vec![
+            DocumentSummary {
+                id: "doc1".into(),
+                title: Some("Planning".into()),
+                created_at: "2025-10-28T15:04:05Z".parse().unwrap(),
+                updated_at: None,
+            },
+            DocumentSummary {
+                id: "doc22".into(),
+                title: None,
+                created_at: "2025-10-29T15:04:05Z".parse().unwrap(),
+                updated_at: None,
+            },
+        ]
+    }
+
+    fn opts(format: OutputFormat) -> OutputOptions {
+        OutputOptions {
+            format,
+            columns: Some(vec!["id".into(), "title".into()]),
+            header: true,
+        }
+    }
+
+    #[test]
+    fn test_render_tsv_with_header() {
+        let out = render(&docs(), &opts(OutputFormat::Tsv)).unwrap();
+        assert_eq!(out, "id\ttitle\ndoc1\tPlanning\ndoc22\t\n");
+    }
+
+    #[test]
+    fn test_render_table_aligns_columns() {
+        let out = render(&docs(), &opts(OutputFormat::Table)).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], "ID     TITLE");
+        assert_eq!(lines[1], "doc1   Planning");
+        assert_eq!(lines[2], "doc22");
+    }
+
+    #[test]
+    fn test_render_json_and_ndjson() {
+        let json = render(&docs(), &opts(OutputFormat::Json)).unwrap();
+        let parsed: Vec<Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[0]["id"], "doc1");
+        assert!(parsed[1]["title"].is_null());
+
+        let ndjson = render(&docs(), &opts(OutputFormat::Ndjson)).unwrap();
+        assert_eq!(ndjson.lines().count(), 2);
+        assert_eq!(
+            ndjson.lines().next().unwrap(),
+            r#"{"id":"doc1","title":"Planning"}"#
+        );
+    }
+
+    #[test]
+    fn test_render_no_header() {
+        let mut options = opts(OutputFormat::Tsv);
+        options.header = false;
+        let out = render(&docs(), &options).unwrap();
+        assert!(out.starts_with("doc1\t"));
+    }
+
+    #[test]
+    fn test_unknown_column_rejected() {
+        let options = OutputOptions {
+            format: OutputFormat::Table,
+            columns: Some(vec!["nope".into()]),
+            header: true,
+        };
+        assert!(matches!(
+            render(&docs(), &options),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+// ABOUTME: Opt-in phase timings for sync, search and summarize, behind --profile-perf
+// ABOUTME: Spans add their wall-clock time to a process-wide table that is reported at exit
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static STARTED: OnceLock<Instant> = OnceLock::new();
+static PHASES: Mutex<Vec<(&'static str, u64, Duration)>> = Mutex::new(Vec::new());
+
+/// Starts recording spans for the rest of the process; call once at startup
+pub fn enable() {
+    let _ = STARTED.set(Instant::now());
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether spans are being recorded
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Times the enclosing scope as one call of `phase`, e.g. `"sync.api"`
+///
+/// Costs one atomic load when profiling is off. Keep spans of the same
+/// command from overlapping, or their times are counted twice.
+pub fn span(phase: &'static str) -> Span {
+    Span {
+        phase,
+        start: enabled().then(Instant::now),
+    }
+}
+
+/// Records its phase when dropped; see [`span`]
+#[must_use = "a span measures until it is dropped"]
+pub struct Span {
+    phase: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.phase, start.elapsed());
+        }
+    }
+}
+
+fn record(phase: &'static str, elapsed: Duration) {
+    let mut phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    match phases.iter_mut().find(|(name, _, _)| *name == phase) {
+        Some((_, calls, total)) => {
+            *calls += 1;
+            *total += elapsed;
+        }
+        None => phases.push((phase, 1, elapsed)),
+    }
+}
+
+/// Everything recorded so far, as written by `--profile-perf-json`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PerfReport {
+    /// Wall-clock time since [`enable`]
+    pub total_ms: f64,
+    /// In the order each phase first ran
+    pub phases: Vec<PhaseTiming>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    pub calls: u64,
+    pub total_ms: f64,
+}
+
+/// The phases recorded so far
+pub fn report() -> PerfReport {
+    let total = STARTED.get().map(Instant::elapsed).unwrap_or_default();
+    let phases = PHASES.lock().unwrap_or_else(|e| e.into_inner());
+    PerfReport {
+        total_ms: millis(total),
+        phases: phases
+            .iter()
+            .map(|(phase, calls, total)| PhaseTiming {
+                phase: phase.to_string(),
+                calls: *calls,
+                total_ms: millis(*total),
+            })
+            .collect(),
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1e6).round() / 1e3
+}
+
+impl PerfReport {
+    /// Aligned table with each phase's share of the total; the rest is "(other)"
+    pub fn to_table(&self) -> String {
+        let width = self
+            .phases
+            .iter()
+            .map(|p| p.phase.len())
+            .chain(["(other)".len()])
+            .max()
+            .unwrap_or_default();
+        let share = |ms: f64| {
+            if self.total_ms > 0.0 {
+                ms / self.total_ms * 100.0
+            } else {
+                0.0
+            }
+        };
+
+        let mut out = format!("Performance ({:.0} ms total)\n", self.total_ms);
+        for p in &self.phases {
+            out.push_str(&format!(
+                "  {:<width$}  {:>10.1} ms  {:>5.1}%  {} call{}\n",
+                p.phase,
+                p.total_ms,
+                share(p.total_ms),
+                p.calls,
+                if p.calls == 1 { "" } else { "s" },
+            ));
+        }
+        let other = self.total_ms - self.phases.iter().map(|p| p.total_ms).sum::<f64>();
+        if other > 0.0 {
+            out.push_str(&format!(
+                "  {:<width$}  {:>10.1} ms  {:>5.1}%\n",
+                "(other)",
+                other,
+                share(other),
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_add_up_per_phase() {
+        // Nothing is recorded until profiling is on
+        drop(span("test.off"));
+        assert!(!report().phases.iter().any(|p| p.phase == "test.off"));
+
+        enable();
+        for _ in 0..3 {
+            let _span = span("test.loop");
+        }
+        record("test.fixed", Duration::from_millis(250));
+
+        let report = report();
+        let phase = |name: &str| report.phases.iter().find(|p| p.phase == name).unwrap();
+        assert_eq!(phase("test.loop").calls, 3);
+        assert_eq!(phase("test.fixed").total_ms, 250.0);
+    }
+
+    #[test]
+    fn test_table_shows_shares_and_other() {
+        let report = PerfReport {
+            total_ms: 1000.0,
+            phases: vec![
+                PhaseTiming {
+                    phase: "sync.api".into(),
+                    calls: 4,
+                    total_ms: 600.0,
+                },
+                PhaseTiming {
+                    phase: "sync.index".into(),
+                    calls: 1,
+                    total_ms: 150.0,
+                },
+            ],
+        };
+        assert_eq!(
+            report.to_table(),
+            "Performance (1000 ms total)\n\
+             \x20 sync.api         600.0 ms   60.0%  4 calls\n\
+             \x20 sync.index       150.0 ms   15.0%  1 call\n\
+             \x20 (other)          250.0 ms   25.0%\n"
+        );
+    }
+}
@@ -0,0 +1,277 @@
+// ABOUTME: Attendance report for one person across synced meetings
+// ABOUTME: Counts meetings and hours together, frequent co-attendees and recurring labels
+
+use crate::storage::scan_transcripts;
+use crate::{Frontmatter, Paths, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// A name and how many of the person's meetings it appeared in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Tally {
+    pub name: String,
+    pub meetings: usize,
+}
+
+/// One meeting the person attended
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonMeeting {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub duration_seconds: Option<u64>,
+}
+
+/// Everything muesli knows about meetings with one person
+#[derive(Debug, Clone, Serialize)]
+pub struct PersonReport {
+    pub name: String,
+    pub since: Option<NaiveDate>,
+    /// Participant entries that matched the name
+    pub matched: Vec<String>,
+    /// Meetings in chronological order
+    pub meetings: Vec<PersonMeeting>,
+    /// Sum of recorded durations; meetings without one are not counted
+    pub total_hours: f64,
+    /// Other participants, most shared meetings first
+    pub co_attendees: Vec<Tally>,
+    /// Granola labels of the meetings, most frequent first
+    pub topics: Vec<Tally>,
+}
+
+fn words(s: &str) -> Vec<String> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether a participant entry contains the name's words in order
+///
+/// Works like the search `--participant` filter: "alice", "Alice Smith" and
+/// "alice@acme.com" all match "Alice Smith <alice@acme.com>".
+pub fn matches_person(participant: &str, name: &str) -> bool {
+    let wanted = words(name);
+    if wanted.is_empty() {
+        return false;
+    }
+    words(participant)
+        .windows(wanted.len())
+        .any(|window| window == wanted.as_slice())
+}
+
+/// Counts each name once per meeting, most frequent first (ties by name), keeping `limit`
+pub(crate) fn tally<'a>(names: impl Iterator<Item = &'a String>, limit: usize) -> Vec<Tally> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in names {
+        *counts.entry(name.as_str()).or_default() += 1;
+    }
+
+    let mut tallies: Vec<Tally> = counts
+        .into_iter()
+        .map(|(name, meetings)| Tally {
+            name: name.to_string(),
+            meetings,
+        })
+        .collect();
+    tallies.sort_by(|a, b| {
+        b.meetings
+            .cmp(&a.meetings)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    tallies.truncate(limit);
+    tallies
+}
+
+/// Builds the report from transcript frontmatter
+///
+/// `limit` caps the co-attendee and topic lists.
+pub fn build_report<'a, I>(
+    frontmatters: I,
+    name: &str,
+    since: Option<NaiveDate>,
+    limit: usize,
+) -> PersonReport
+where
+    I: IntoIterator<Item = &'a Frontmatter>,
+{
+    let mut matched = Vec::new();
+    let mut attended: Vec<&Frontmatter> = Vec::new();
+
+    for fm in frontmatters {
+        if since.is_some_and(|s| fm.created_at.date_naive() < s) {
+            continue;
+        }
+        let hits: Vec<&String> = fm
+            .participants
+            .iter()
+            .filter(|p| matches_person(p, name))
+            .collect();
+        if hits.is_empty() {
+            continue;
+        }
+        matched.extend(hits.into_iter().cloned());
+        attended.push(fm);
+    }
+
+    matched.sort();
+    matched.dedup();
+    attended.sort_by_key(|fm| fm.created_at);
+
+    let mut others = Vec::new();
+    let mut labels = Vec::new();
+    for fm in &attended {
+        let mut present: Vec<&String> = fm
+            .participants
+            .iter()
+            .filter(|p| !matched.contains(p))
+            .collect();
+        present.sort();
+        present.dedup();
+        others.extend(present);
+
+        let mut topics: Vec<&String> = fm.labels.iter().collect();
+        topics.sort();
+        topics.dedup();
+        labels.extend(topics);
+    }
+
+    let seconds: u64 = attended.iter().filter_map(|fm| fm.duration_seconds).sum();
+
+    PersonReport {
+        name: name.to_string(),
+        since,
+        co_attendees: tally(others.into_iter(), limit),
+        topics: tally(labels.into_iter(), limit),
+        total_hours: seconds as f64 / 3600.0,
+        meetings: attended
+            .into_iter()
+            .map(|fm| PersonMeeting {
+                doc_id: fm.doc_id.clone(),
+                title: fm.title.clone(),
+                created_at: fm.created_at,
+                duration_seconds: fm.duration_seconds,
+            })
+            .collect(),
+        matched,
+    }
+}
+
+/// Builds the report from the local transcripts
+pub fn person_report(
+    paths: &Paths,
+    name: &str,
+    since: Option<NaiveDate>,
+    limit: usize,
+) -> Result<PersonReport> {
+    let transcripts = scan_transcripts(&paths.transcripts_dir)?;
+    Ok(build_report(
+        transcripts.iter().map(|(_, fm)| fm),
+        name,
+        since,
+        limit,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fm(
+        created_at: &str,
+        participants: &[&str],
+        labels: &[&str],
+        duration: Option<u64>,
+    ) -> Frontmatter {
+        Frontmatter {
+            doc_id: created_at[..10].into(),
+            source: "granola".into(),
+            created_at: created_at.parse().unwrap(),
+            remote_updated_at: None,
+            title: None,
+            participants: participants.iter().map(|s| s.to_string()).collect(),
+            duration_seconds: duration,
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            organizations: vec![],
+            inferred_speakers: vec![],
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        }
+    }
+
+    #[test]
+    fn test_matches_person() {
+        let alice = "Alice Smith <alice@acme.com>";
+        assert!(matches_person(alice, "alice"));
+        assert!(matches_person(alice, "ALICE SMITH"));
+        assert!(matches_person(alice, "alice@acme.com"));
+        assert!(!matches_person(alice, "Alice Jones"));
+        assert!(!matches_person(alice, "ali"));
+        assert!(!matches_person(alice, " "));
+    }
+
+    #[test]
+    fn test_build_report() {
+        let alice = "Alice <alice@acme.com>";
+        let docs = vec![
+            fm(
+                "2025-03-01T10:00:00Z",
+                &[alice, "Bob", "Carol"],
+                &["Pricing"],
+                Some(1800),
+            ),
+            fm(
+                "2025-01-10T10:00:00Z",
+                &[alice, "Bob"],
+                &["Pricing", "Hiring"],
+                Some(3600),
+            ),
+            fm("2025-02-01T10:00:00Z", &[alice, "Carol"], &[], None),
+            fm(
+                "2025-02-15T10:00:00Z",
+                &["Bob", "Dan"],
+                &["Pricing"],
+                Some(900),
+            ),
+            // Before the cutoff
+            fm(
+                "2024-06-01T10:00:00Z",
+                &[alice, "Dan"],
+                &["Offsite"],
+                Some(7200),
+            ),
+        ];
+        let since = NaiveDate::from_ymd_opt(2025, 1, 1);
+
+        let report = build_report(&docs, "alice", since, 5);
+        assert_eq!(report.matched, vec![alice]);
+        let ids: Vec<&str> = report.meetings.iter().map(|m| m.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["2025-01-10", "2025-02-01", "2025-03-01"]);
+        assert!((report.total_hours - 1.5).abs() < 1e-9);
+        assert_eq!(
+            report.co_attendees,
+            vec![
+                Tally {
+                    name: "Bob".into(),
+                    meetings: 2
+                },
+                Tally {
+                    name: "Carol".into(),
+                    meetings: 2
+                },
+            ]
+        );
+        assert_eq!(report.topics[0].name, "Pricing");
+        assert_eq!(report.topics[0].meetings, 2);
+
+        let limited = build_report(&docs, "alice", None, 1);
+        assert_eq!(limited.meetings.len(), 4);
+        assert_eq!(limited.co_attendees.len(), 1);
+    }
+}
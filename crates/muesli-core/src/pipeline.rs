@@ -0,0 +1,593 @@
+// ABOUTME: Ordered processors that transform a transcript's entries, then its markdown, before writing
+// ABOUTME: Entry steps come from [markdown]'s layout keys; markdown steps are its [[markdown.processors]]
+
+use crate::convert::{anchor_id, strip_anchor, MarkdownOptions, MarkdownOutput};
+use crate::speakers::SpeakerAliases;
+use crate::template::EntryView;
+use crate::util::normalize_timestamp;
+use crate::{Error, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+
+/// What redacted text is replaced with
+pub const REDACTED: &str = "[redacted]";
+
+/// One step of the pipeline; gets the output of the step before it
+pub trait Processor {
+    /// Name used in error messages
+    fn name(&self) -> &str;
+    fn process(&self, output: MarkdownOutput) -> Result<MarkdownOutput>;
+}
+
+/// A step over the transcript's entries, run before they are laid out
+///
+/// These see speakers, times and confidences, which the written markdown no
+/// longer has.
+pub trait EntryProcessor {
+    fn name(&self) -> &str;
+    fn process(&self, entries: Vec<EntryView>) -> Vec<EntryView>;
+}
+
+/// A `[[markdown.processors]]` entry in config.toml
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "name", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum ProcessorConfig {
+    /// Drops an entry that repeats the previous one by the same speaker word for word
+    DedupeTurns,
+    /// Replaces `terms` (any case) and, with `emails`, email addresses in the body
+    Redact {
+        #[serde(default)]
+        terms: Vec<String>,
+        #[serde(default)]
+        emails: bool,
+    },
+    /// Pipes the body through an external program: `run = ["program", "arg", ...]`
+    Command { run: Vec<String> },
+}
+
+impl ProcessorConfig {
+    pub fn build(&self) -> Result<Box<dyn Processor>> {
+        Ok(match self {
+            ProcessorConfig::DedupeTurns => Box::new(DedupeTurns),
+            ProcessorConfig::Redact { terms, emails } => Box::new(Redact {
+                terms: terms.iter().filter(|t| !t.is_empty()).cloned().collect(),
+                emails: *emails,
+            }),
+            ProcessorConfig::Command { run } => {
+                if run.is_empty() {
+                    return Err(Error::InvalidInput(
+                        "Processor 'command' needs a program in run = [...]".to_string(),
+                    ));
+                }
+                Box::new(ExternalCommand { run: run.clone() })
+            }
+        })
+    }
+}
+
+/// The processors to run, in order: entry steps, then markdown steps
+#[derive(Default)]
+pub struct Pipeline {
+    entry_processors: Vec<Box<dyn EntryProcessor>>,
+    processors: Vec<Box<dyn Processor>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_config(configs: &[ProcessorConfig]) -> Result<Self> {
+        Ok(Pipeline {
+            entry_processors: Vec::new(),
+            processors: configs
+                .iter()
+                .map(ProcessorConfig::build)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// The whole chain `[markdown]` describes
+    ///
+    /// Speaker aliases, "(?)" marks, merged turns and anchors come first, in that
+    /// order, then the `[[processors]]`.
+    pub fn configured(options: &MarkdownOptions) -> Result<Self> {
+        let mut pipeline = Self::from_config(&options.processors)?
+            .with_entries(Box::new(AliasSpeakers(options.aliases.clone())));
+        if let Some(min_confidence) = options.min_confidence {
+            pipeline = pipeline.with_entries(Box::new(MarkDoubtful { min_confidence }));
+        }
+        if options.merge_turns {
+            pipeline = pipeline.with_entries(Box::new(MergeTurns));
+        }
+        // Templates get the ids whatever `anchors` is; the layout only writes them when it's set
+        Ok(pipeline.with_entries(Box::new(Anchors)))
+    }
+
+    /// Appends a processor, for transforms that aren't built in
+    pub fn with(mut self, processor: Box<dyn Processor>) -> Self {
+        self.processors.push(processor);
+        self
+    }
+
+    /// Appends an entry processor, run after the ones already added
+    pub fn with_entries(mut self, processor: Box<dyn EntryProcessor>) -> Self {
+        self.entry_processors.push(processor);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entry_processors.is_empty() && self.processors.is_empty()
+    }
+
+    pub fn run_entries(&self, mut entries: Vec<EntryView>) -> Vec<EntryView> {
+        for processor in &self.entry_processors {
+            entries = processor.process(entries);
+        }
+        entries
+    }
+
+    pub fn run(&self, mut output: MarkdownOutput) -> Result<MarkdownOutput> {
+        for processor in &self.processors {
+            output = processor.process(output)?;
+        }
+        Ok(output)
+    }
+}
+
+/// Shows each speaker under their canonical name from `[speakers.aliases]`
+pub struct AliasSpeakers(pub SpeakerAliases);
+
+impl EntryProcessor for AliasSpeakers {
+    fn name(&self) -> &str {
+        "aliases"
+    }
+
+    fn process(&self, mut entries: Vec<EntryView>) -> Vec<EntryView> {
+        for entry in &mut entries {
+            if let Some(canonical) = self.0.canonical(&entry.speaker) {
+                entry.speaker = canonical.to_string();
+            }
+        }
+        entries
+    }
+}
+
+/// Marks speakers attributed with less confidence than `min_confidence` as `Alice(?)`
+///
+/// Entries without a confidence are trusted as before.
+pub struct MarkDoubtful {
+    pub min_confidence: f64,
+}
+
+impl EntryProcessor for MarkDoubtful {
+    fn name(&self) -> &str {
+        "min-confidence"
+    }
+
+    fn process(&self, mut entries: Vec<EntryView>) -> Vec<EntryView> {
+        for entry in &mut entries {
+            if entry.confidence.is_some_and(|c| c < self.min_confidence) {
+                entry.speaker.push_str("(?)");
+            }
+        }
+        entries
+    }
+}
+
+/// Joins each run of entries by the same speaker into one
+///
+/// The joined entry keeps the first timestamp and the last end time. A gap
+/// in the recording starts a new entry even for the same speaker.
+pub struct MergeTurns;
+
+impl EntryProcessor for MergeTurns {
+    fn name(&self) -> &str {
+        "merge-turns"
+    }
+
+    fn process(&self, entries: Vec<EntryView>) -> Vec<EntryView> {
+        let mut merged: Vec<EntryView> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match merged.last_mut() {
+                Some(last) if last.speaker == entry.speaker && entry.gap_before.is_none() => {
+                    let text = entry.text.trim();
+                    if !text.is_empty() {
+                        if !last.text.is_empty() {
+                            last.text.push(' ');
+                        }
+                        last.text.push_str(text);
+                    }
+                    if entry.end.is_some() {
+                        last.end = entry.end;
+                    }
+                    last.confidence = match (last.confidence, entry.confidence) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    };
+                }
+                _ => merged.push(entry),
+            }
+        }
+        merged
+    }
+}
+
+/// Gives each timestamped entry a `t-HH-MM-SS` id to link to
+///
+/// Entries starting in the same second share a time; the first one gets the anchor.
+pub struct Anchors;
+
+impl EntryProcessor for Anchors {
+    fn name(&self) -> &str {
+        "anchors"
+    }
+
+    fn process(&self, mut entries: Vec<EntryView>) -> Vec<EntryView> {
+        let mut seen = HashSet::new();
+        for entry in &mut entries {
+            entry.anchor = entry
+                .start
+                .as_deref()
+                .and_then(normalize_timestamp)
+                .map(|ts| anchor_id(&ts))
+                .filter(|id| seen.insert(id.clone()));
+        }
+        entries
+    }
+}
+
+/// The `dedupe-turns` processor
+///
+/// Granola sometimes delivers the same utterance twice in a row. Speaker and text
+/// are compared with timestamps and anchors left out, in both speaker styles.
+pub struct DedupeTurns;
+
+impl Processor for DedupeTurns {
+    fn name(&self) -> &str {
+        "dedupe-turns"
+    }
+
+    fn process(&self, mut output: MarkdownOutput) -> Result<MarkdownOutput> {
+        let mut kept: Vec<&str> = Vec::new();
+        let mut heading_speaker = String::new();
+        let mut last: Option<(String, String)> = None;
+        let mut dropped = false;
+
+        for line in output.body.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                // The blank line after a dropped entry would leave a double gap
+                if !std::mem::take(&mut dropped) {
+                    kept.push(line);
+                }
+                continue;
+            }
+            dropped = false;
+
+            let (text, _) = strip_anchor(trimmed);
+            if let Some(heading) = text.strip_prefix("### ") {
+                heading_speaker = speaker_name(heading).to_string();
+                kept.push(line);
+                continue;
+            }
+            let key = match bold_entry(text) {
+                Some((speaker, text)) => (speaker.to_string(), text.to_string()),
+                None => (heading_speaker.clone(), text.to_string()),
+            };
+            if last.as_ref() == Some(&key) {
+                dropped = true;
+                continue;
+            }
+            last = Some(key);
+            kept.push(line);
+        }
+
+        let trailing_newline = output.body.ends_with('\n');
+        output.body = kept.join("\n");
+        if trailing_newline {
+            output.body.push('\n');
+        }
+        Ok(output)
+    }
+}
+
+/// `Alice` from `Alice (00:01:05)`
+fn speaker_name(label: &str) -> &str {
+    label.split(" (").next().unwrap_or(label).trim()
+}
+
+/// Speaker and text of a `**Alice (00:01:05):** text` line
+fn bold_entry(line: &str) -> Option<(&str, &str)> {
+    let (label, text) = line.strip_prefix("**")?.split_once(":** ")?;
+    Some((speaker_name(label), text.trim()))
+}
+
+/// The `redact` processor
+///
+/// Only the body is redacted; the frontmatter keeps titles and participants so the
+/// catalog and search still work.
+pub struct Redact {
+    pub terms: Vec<String>,
+    pub emails: bool,
+}
+
+impl Processor for Redact {
+    fn name(&self) -> &str {
+        "redact"
+    }
+
+    fn process(&self, mut output: MarkdownOutput) -> Result<MarkdownOutput> {
+        for term in &self.terms {
+            output.body = replace_ignore_case(&output.body, term, REDACTED);
+        }
+        if self.emails {
+            output.body = redact_emails(&output.body);
+        }
+        Ok(output)
+    }
+}
+
+/// `text` with every case-insensitive match of `term` replaced
+///
+/// Matches are found character by character, so letters whose lowercase form
+/// has a different length (e.g. "İ") still line up with the original text.
+fn replace_ignore_case(text: &str, term: &str, replacement: &str) -> String {
+    let needle: Vec<char> = term.chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return text.to_string();
+    }
+
+    let mut out = String::with_capacity(text.len());
+    let mut rest = 0;
+    for (start, _) in text.char_indices() {
+        if start < rest {
+            continue;
+        }
+        if let Some(len) = match_len(&text[start..], &needle) {
+            out.push_str(&text[rest..start]);
+            out.push_str(replacement);
+            rest = start + len;
+        }
+    }
+    out.push_str(&text[rest..]);
+    out
+}
+
+/// Byte length of the start of `text` that lowercases to `needle`, if it does
+fn match_len(text: &str, needle: &[char]) -> Option<usize> {
+    let mut wanted = needle;
+    for (i, c) in text.char_indices() {
+        for lower in c.to_lowercase() {
+            match wanted.split_first() {
+                Some((first, tail)) if *first == lower => wanted = tail,
+                _ => return None,
+            }
+        }
+        if wanted.is_empty() {
+            return Some(i + c.len_utf8());
+        }
+    }
+    None
+}
+
+/// `text` with anything shaped like `name@domain.tld` replaced
+fn redact_emails(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        // Keep punctuation around the address, e.g. "(bob@example.com)."
+        let edge = |c: char| !(c.is_alphanumeric() || c == '@');
+        let core = word.trim_matches(edge);
+        if is_email(core) {
+            let start = word.find(core).unwrap_or(0);
+            out.push_str(&word[..start]);
+            out.push_str(REDACTED);
+            out.push_str(&word[start + core.len()..]);
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+    };
+    for c in text.chars() {
+        if c.is_whitespace() || c == '<' || c == '>' {
+            flush(&mut word, &mut out);
+            out.push(c);
+        } else {
+            word.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+fn is_email(word: &str) -> bool {
+    match word.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain
+                    .rsplit_once('.')
+                    .is_some_and(|(host, tld)| !host.is_empty() && !tld.is_empty())
+        }
+        None => false,
+    }
+}
+
+/// The `command` processor: the body on stdin, the new body from stdout
+///
+/// A non-zero exit fails the conversion rather than writing a half-processed file.
+pub struct ExternalCommand {
+    pub run: Vec<String>,
+}
+
+impl Processor for ExternalCommand {
+    fn name(&self) -> &str {
+        &self.run[0]
+    }
+
+    fn process(&self, mut output: MarkdownOutput) -> Result<MarkdownOutput> {
+        let failed = |reason: String| {
+            Error::InvalidInput(format!("Processor command '{}' {}", self.name(), reason))
+        };
+        let mut child = Command::new(&self.run[0])
+            .args(&self.run[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| failed(format!("could not start: {}", e)))?;
+
+        // Write from another thread so a program that streams its output can't deadlock
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let body = std::mem::take(&mut output.body);
+        let writer = std::thread::spawn(move || stdin.write_all(body.as_bytes()));
+
+        let mut processed = String::new();
+        child
+            .stdout
+            .take()
+            .expect("stdout is piped")
+            .read_to_string(&mut processed)
+            .map_err(|e| failed(format!("wrote unreadable output: {}", e)))?;
+        let status = child.wait()?;
+        // A program that doesn't read all of stdin closes the pipe; its exit status decides
+        let _ = writer.join();
+        if !status.success() {
+            return Err(failed(format!("exited with {}", status)));
+        }
+
+        output.body = processed;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(body: &str) -> MarkdownOutput {
+        MarkdownOutput {
+            frontmatter_yaml: "doc_id: doc1\n".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn entry(speaker: &str, start: &str, text: &str, confidence: Option<f64>) -> EntryView {
+        EntryView {
+            speaker: speaker.to_string(),
+            timestamp: None,
+            anchor: None,
+            text: text.to_string(),
+            start: Some(start.to_string()),
+            end: None,
+            confidence,
+            gap_before: None,
+        }
+    }
+
+    #[test]
+    fn test_entry_steps_run_in_order() {
+        let options = MarkdownOptions {
+            min_confidence: Some(0.5),
+            merge_turns: true,
+            ..Default::default()
+        };
+        let entries = Pipeline::configured(&options).unwrap().run_entries(vec![
+            entry("Alice", "10:00:00", "Hi", Some(0.9)),
+            entry("Alice", "10:00:00", "there", None),
+            entry("Alice", "10:00:05", "Maybe me", Some(0.2)),
+        ]);
+
+        // A doubtful attribution isn't merged into a confident one
+        let shown: Vec<(&str, &str, Option<&str>)> = entries
+            .iter()
+            .map(|e| (e.speaker.as_str(), e.text.as_str(), e.anchor.as_deref()))
+            .collect();
+        assert_eq!(
+            shown,
+            vec![
+                ("Alice", "Hi there", Some("t-10-00-00")),
+                ("Alice(?)", "Maybe me", Some("t-10-00-05")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedupe_turns() {
+        let body = "# Sync\n\n**Alice (00:00:01):** Hello\n**Alice (00:00:02):** Hello\n**Bob (00:00:03):** Hello\n**Alice (00:00:04):** Hello\n";
+        let out = DedupeTurns.process(output(body)).unwrap();
+        assert_eq!(
+            out.body,
+            "# Sync\n\n**Alice (00:00:01):** Hello\n**Bob (00:00:03):** Hello\n**Alice (00:00:04):** Hello\n"
+        );
+
+        let body = "### Alice (00:00:01)\n\nHello\n\nHello\n\nBye\n\n### Bob (00:00:05)\n\nBye\n";
+        let out = DedupeTurns.process(output(body)).unwrap();
+        assert_eq!(
+            out.body,
+            "### Alice (00:00:01)\n\nHello\n\nBye\n\n### Bob (00:00:05)\n\nBye\n"
+        );
+    }
+
+    #[test]
+    fn test_redact() {
+        let redact = Redact {
+            terms: vec!["project falcon".to_string()],
+            emails: true,
+        };
+        let out = redact
+            .process(output(
+                "**Alice:** Project Falcon ships; mail bob@example.com (or <carol@corp.io>).\n",
+            ))
+            .unwrap();
+        assert_eq!(
+            out.body,
+            "**Alice:** [redacted] ships; mail [redacted] (or <[redacted]>).\n"
+        );
+        assert_eq!(out.frontmatter_yaml, "doc_id: doc1\n");
+        assert_eq!(redact_emails("@handle a@b"), "@handle a@b");
+
+        // Case folding that changes byte lengths doesn't stop a match
+        assert_eq!(
+            replace_ignore_case("İstanbul office: ÉTIENNE and étienne", "étienne", "[x]"),
+            "İstanbul office: [x] and [x]"
+        );
+        assert_eq!(replace_ignore_case("İSTANBUL", "i̇stanbul", "[x]"), "[x]");
+    }
+
+    #[test]
+    fn test_pipeline_from_config() {
+        #[derive(Deserialize)]
+        struct File {
+            processors: Vec<ProcessorConfig>,
+        }
+        let file: File = toml::from_str(
+            "[[processors]]\nname = \"dedupe-turns\"\n\n[[processors]]\nname = \"redact\"\nterms = [\"Hello\"]\n",
+        )
+        .unwrap();
+        let pipeline = Pipeline::from_config(&file.processors).unwrap();
+        let out = pipeline
+            .run(output("**A:** Hello there\n**A:** Hello there\n"))
+            .unwrap();
+        assert_eq!(out.body, "**A:** [redacted] there\n");
+
+        assert!(toml::from_str::<File>("[[processors]]\nname = \"shout\"\n").is_err());
+        assert!(Pipeline::from_config(&[ProcessorConfig::Command { run: vec![] }]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_external_command() {
+        let upper = ExternalCommand {
+            run: vec!["tr".to_string(), "a-z".to_string(), "A-Z".to_string()],
+        };
+        assert_eq!(upper.process(output("hi\n")).unwrap().body, "HI\n");
+
+        let failing = ExternalCommand {
+            run: vec!["false".to_string()],
+        };
+        assert!(failing.process(output("hi\n")).is_err());
+    }
+}
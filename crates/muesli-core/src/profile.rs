@@ -0,0 +1,117 @@
+// ABOUTME: Named profiles that keep several Granola accounts apart (--profile work)
+// ABOUTME: Each [name] section of profiles.toml picks a data directory and where the token comes from
+
+use crate::storage::default_data_dir;
+use crate::{Error, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// One `[name]` section of `profiles.toml`; every key is optional
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    /// Data directory; `profiles/<name>` in the default data directory when unset
+    pub data_dir: Option<PathBuf>,
+    /// Environment variable holding the bearer token, instead of BEARER_TOKEN
+    pub token_env: Option<String>,
+    /// Granola session file to read the token from, instead of the usual locations
+    pub session_file: Option<PathBuf>,
+}
+
+impl Profile {
+    /// Where this profile keeps its archive
+    pub fn data_dir(&self) -> Result<PathBuf> {
+        match &self.data_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => Ok(default_data_dir()?.join("profiles").join(&self.name)),
+        }
+    }
+}
+
+/// `profiles.toml` in the default data directory, shared by every profile
+pub fn config_path() -> Result<PathBuf> {
+    Ok(default_data_dir()?.join("profiles.toml"))
+}
+
+/// Loads the named profile from `profiles.toml`
+pub fn load(name: &str) -> Result<Profile> {
+    load_from(&config_path()?, name)
+}
+
+/// Loads the named profile from a TOML file of `[name]` sections
+///
+/// An unknown name is an error rather than a fresh profile, so a typo can't
+/// sync an account into the wrong archive.
+pub fn load_from(path: &Path, name: &str) -> Result<Profile> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(Error::InvalidInput(format!(
+            "Invalid profile name '{}': use letters, digits, '-' and '_'",
+            name
+        )));
+    }
+
+    let unknown = || {
+        Error::InvalidInput(format!(
+            "Unknown profile '{}'; add a [{}] section to {}",
+            name,
+            name,
+            path.display()
+        ))
+    };
+    if !path.exists() {
+        return Err(unknown());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let mut profiles: BTreeMap<String, Profile> = toml::from_str(&content)
+        .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))?;
+    let mut profile = profiles.remove(name).ok_or_else(unknown)?;
+    profile.name = name.to_string();
+    Ok(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_profile() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("profiles.toml");
+        std::fs::write(
+            &path,
+            "[work]\ndata_dir = \"/srv/work\"\ntoken_env = \"WORK_TOKEN\"\n\n[personal]\n",
+        )
+        .unwrap();
+
+        let work = load_from(&path, "work").unwrap();
+        assert_eq!(work.name, "work");
+        assert_eq!(work.data_dir().unwrap(), PathBuf::from("/srv/work"));
+        assert_eq!(work.token_env.as_deref(), Some("WORK_TOKEN"));
+
+        let personal = load_from(&path, "personal").unwrap();
+        assert!(personal.data_dir().unwrap().ends_with("profiles/personal"));
+        assert_eq!(personal.session_file, None);
+    }
+
+    #[test]
+    fn test_unknown_and_invalid_profiles() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("profiles.toml");
+        let err = load_from(&path, "work").unwrap_err().to_string();
+        assert!(err.contains("add a [work] section"), "{}", err);
+
+        std::fs::write(&path, "[work]\ntoken = \"x\"\n").unwrap();
+        assert!(load_from(&path, "work").is_err());
+        assert!(load_from(&path, "../work").is_err());
+        assert!(load_from(&path, "").is_err());
+    }
+}
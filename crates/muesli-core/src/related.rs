@@ -0,0 +1,275 @@
+// ABOUTME: "Related meetings" footer linking each transcript to similar earlier meetings
+// ABOUTME: Footer rendering/stripping is always available; `muesli link` computes it from embeddings
+
+use crate::convert::MarkdownOutput;
+use crate::pipeline::Processor;
+use crate::storage::CatalogEntry;
+
+/// Marks the start of the generated footer so it can be replaced idempotently
+pub const RELATED_START: &str = "<!-- muesli:related -->";
+/// Marks the end of the generated footer
+pub const RELATED_END: &str = "<!-- /muesli:related -->";
+
+/// Related meetings listed per transcript unless configured otherwise
+pub const DEFAULT_COUNT: usize = 3;
+
+/// A transcript body with any generated related-meetings footer removed
+///
+/// Indexing and embedding use this so links to other meetings don't count as
+/// content of this one.
+pub fn strip_related(body: &str) -> &str {
+    match body.find(RELATED_START) {
+        Some(start) => body[..start].trim_end_matches('\n'),
+        None => body,
+    }
+}
+
+/// Markdown footer listing related meetings as links relative to the transcript
+pub fn render_footer(related: &[&CatalogEntry]) -> String {
+    let mut footer = format!("{}\n## Related meetings\n\n", RELATED_START);
+    for entry in related {
+        let file = entry
+            .path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        footer.push_str(&format!(
+            "- [{}]({}) · {}\n",
+            entry.title.as_deref().unwrap_or("Untitled"),
+            file.replace(' ', "%20"),
+            entry.created_at.format("%Y-%m-%d")
+        ));
+    }
+    footer.push_str(RELATED_END);
+    footer.push('\n');
+    footer
+}
+
+/// File contents with the footer replaced (or removed when `footer` is None)
+///
+/// Anything after the end marker is kept, so notes added below the footer survive.
+pub fn with_footer(content: &str, footer: Option<&str>) -> String {
+    let (head, tail) = match content.find(RELATED_START) {
+        Some(start) => {
+            let tail = content[start..]
+                .find(RELATED_END)
+                .map(|end| &content[start + end + RELATED_END.len()..])
+                .unwrap_or("");
+            (&content[..start], tail.trim_start_matches('\n'))
+        }
+        None => (content, ""),
+    };
+
+    let mut out = head.trim_end_matches('\n').to_string();
+    out.push('\n');
+    if let Some(footer) = footer {
+        out.push('\n');
+        out.push_str(footer);
+    }
+    if !tail.is_empty() {
+        out.push('\n');
+        out.push_str(tail);
+    }
+    out
+}
+
+/// The `related` processor: puts `footer` in place of the current one, or
+/// removes it when there is none
+pub struct RelatedLinks {
+    pub footer: Option<String>,
+}
+
+impl Processor for RelatedLinks {
+    fn name(&self) -> &str {
+        "related"
+    }
+
+    fn process(&self, mut output: MarkdownOutput) -> crate::Result<MarkdownOutput> {
+        output.body = with_footer(&output.body, self.footer.as_deref());
+        Ok(output)
+    }
+}
+
+/// Outcome of a `muesli link` run
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinkReport {
+    /// Files whose footer changed
+    pub updated: usize,
+    /// Files already up to date
+    pub unchanged: usize,
+    /// Transcripts without an embedding
+    pub skipped: usize,
+}
+
+/// For each embedded transcript, the `count` most similar meetings created before it
+#[cfg(feature = "semantic")]
+pub fn related_meetings<'a>(
+    store: &crate::embeddings::VectorStore,
+    entries: &'a [CatalogEntry],
+    count: usize,
+) -> Vec<(&'a CatalogEntry, Vec<&'a CatalogEntry>)> {
+    use crate::embeddings::vector::cosine_similarity;
+    use rayon::prelude::*;
+    use std::collections::HashMap;
+
+    // Oldest first, so "earlier" is simply a prefix of the list. Vectors are
+    // looked up once here rather than per pair.
+    let vectors: HashMap<&str, &[f32]> = store.iter().collect();
+    let mut sorted: Vec<(&CatalogEntry, &[f32])> = entries
+        .iter()
+        .filter_map(|entry| Some((entry, *vectors.get(entry.doc_id.as_str())?)))
+        .collect();
+    sorted.sort_by(|a, b| {
+        a.0.created_at
+            .cmp(&b.0.created_at)
+            .then_with(|| a.0.doc_id.cmp(&b.0.doc_id))
+    });
+
+    sorted
+        .par_iter()
+        .enumerate()
+        .map(|(i, &(entry, vector))| {
+            let mut scored: Vec<(f32, &CatalogEntry)> = sorted[..i]
+                .iter()
+                .map(|&(other, other_vector)| (cosine_similarity(vector, other_vector), other))
+                .collect();
+            scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+            scored.truncate(count);
+            (entry, scored.into_iter().map(|(_, e)| e).collect())
+        })
+        .collect()
+}
+
+/// Rewrites the related-meetings footer of every transcript
+///
+/// Only files whose footer changes are written, and their modification time is
+/// kept at the meeting date.
+#[cfg(feature = "semantic")]
+pub fn link_all(paths: &crate::Paths, count: usize) -> crate::Result<LinkReport> {
+    use crate::embeddings::VectorStore;
+    use crate::pipeline::Pipeline;
+    use crate::storage::{set_file_time, strip_frontmatter, write_atomic, Catalog};
+
+    let store = VectorStore::load(&paths.index_dir.join("vectors"))?;
+    let entries = Catalog::open(paths)?.entries()?;
+    let related = related_meetings(&store, &entries, count);
+
+    let mut report = LinkReport {
+        skipped: entries.len() - related.len(),
+        ..Default::default()
+    };
+
+    for (entry, similar) in related {
+        let content = std::fs::read_to_string(&entry.path)?;
+        let body = strip_frontmatter(&content);
+        let frontmatter = &content[..content.len() - body.len()];
+        let footer = (!similar.is_empty()).then(|| render_footer(&similar));
+        let output = Pipeline::new()
+            .with(Box::new(RelatedLinks { footer }))
+            .run(MarkdownOutput {
+                frontmatter_yaml: String::new(),
+                body: body.to_string(),
+            })?;
+        let updated = format!("{}{}", frontmatter, output.body);
+
+        if updated == content {
+            report.unchanged += 1;
+            continue;
+        }
+
+        write_atomic(&entry.path, updated.as_bytes(), &paths.tmp_dir)?;
+        set_file_time(&entry.path, &entry.created_at)?;
+        report.updated += 1;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+
+    fn entry(doc_id: &str, title: &str, day: u32) -> CatalogEntry {
+        CatalogEntry {
+            doc_id: doc_id.to_string(),
+            path: PathBuf::from(format!("/archive/2025-01-{:02}_{}.md", day, doc_id)),
+            title: Some(title.to_string()),
+            created_at: Utc.with_ymd_and_hms(2025, 1, day, 10, 0, 0).unwrap(),
+            participants: Vec::new(),
+            labels: Vec::new(),
+            organizations: Vec::new(),
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: Vec::new(),
+            access: None,
+            modified: None,
+        }
+    }
+
+    #[test]
+    fn test_footer_is_idempotent() {
+        let older = entry("a", "Kickoff", 1);
+        let footer = render_footer(&[&older]);
+        assert!(footer.contains("- [Kickoff](2025-01-01_a.md) · 2025-01-01"));
+
+        let content = "---\ndoc_id: b\n---\n\n# Review\n\n**Alice:** Hi\n";
+        let once = with_footer(content, Some(&footer));
+        assert_eq!(with_footer(&once, Some(&footer)), once);
+        assert!(once.ends_with(&format!("{}\n", RELATED_END)));
+
+        // Removing the footer restores the original
+        assert_eq!(with_footer(&once, None), content);
+    }
+
+    #[test]
+    fn test_footer_keeps_trailing_notes() {
+        let footer = render_footer(&[&entry("a", "Kickoff", 1)]);
+        let content = format!("# Review\n\n{}\nMy notes\n", footer);
+
+        let replaced = with_footer(&content, Some(&render_footer(&[&entry("c", "Retro", 3)])));
+        assert!(replaced.contains("Retro"));
+        assert!(!replaced.contains("Kickoff"));
+        assert!(replaced.ends_with("My notes\n"));
+    }
+
+    #[test]
+    fn test_strip_related() {
+        let body = format!(
+            "# Review\n\n**Alice:** Hi\n\n{}",
+            render_footer(&[&entry("a", "Kickoff", 1)])
+        );
+        assert_eq!(strip_related(&body), "# Review\n\n**Alice:** Hi");
+        assert_eq!(strip_related("# Plain\n"), "# Plain\n");
+    }
+
+    #[cfg(feature = "semantic")]
+    #[test]
+    fn test_related_meetings_only_link_earlier_meetings() {
+        use crate::embeddings::VectorStore;
+
+        let entries = vec![
+            entry("a", "Pricing v1", 1),
+            entry("b", "Hiring", 2),
+            entry("c", "Pricing v2", 3),
+        ];
+        let mut store = VectorStore::new(2);
+        store.add_document("a".into(), vec![1.0, 0.0]).unwrap();
+        store.add_document("b".into(), vec![0.0, 1.0]).unwrap();
+        store.add_document("c".into(), vec![0.9, 0.1]).unwrap();
+
+        let related = related_meetings(&store, &entries, 1);
+        let by_id = |id: &str| {
+            related
+                .iter()
+                .find(|(e, _)| e.doc_id == id)
+                .map(|(_, r)| r.iter().map(|e| e.doc_id.as_str()).collect::<Vec<_>>())
+                .unwrap()
+        };
+
+        assert!(by_id("a").is_empty());
+        assert_eq!(by_id("b"), vec!["a"]);
+        assert_eq!(by_id("c"), vec!["a"]);
+    }
+}
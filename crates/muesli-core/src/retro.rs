@@ -0,0 +1,348 @@
+// ABOUTME: Quarterly retrospectives built from the saved summaries of a quarter's meetings
+// ABOUTME: Selects meetings by quarter and label/participant/org filters, then writes retros/<quarter>.md
+
+use crate::storage::{write_atomic, Catalog, CatalogEntry};
+use crate::util::{organizations_from_participants, slugify};
+use crate::{Paths, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A calendar quarter, written "2025-Q2"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quarter {
+    pub year: i32,
+    /// 1 to 4
+    pub quarter: u32,
+}
+
+impl Quarter {
+    /// First day of the quarter
+    pub fn start(self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, (self.quarter - 1) * 3 + 1, 1).expect("valid quarter")
+    }
+
+    /// First day after the quarter
+    pub fn end(self) -> NaiveDate {
+        match self.quarter {
+            4 => NaiveDate::from_ymd_opt(self.year + 1, 1, 1),
+            q => NaiveDate::from_ymd_opt(self.year, q * 3 + 1, 1),
+        }
+        .expect("valid quarter")
+    }
+
+    pub fn contains(self, at: DateTime<Utc>) -> bool {
+        let date = at.date_naive();
+        date >= self.start() && date < self.end()
+    }
+}
+
+impl FromStr for Quarter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Expected a quarter like 2025-Q2, got '{}'", s);
+        let (year, quarter) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let quarter = quarter
+            .strip_prefix(['Q', 'q'])
+            .and_then(|q| q.parse().ok())
+            .filter(|q| (1..=4).contains(q))
+            .ok_or_else(invalid)?;
+        let year = year
+            .parse()
+            .ok()
+            .filter(|y| (1..=9999).contains(y))
+            .ok_or_else(invalid)?;
+        Ok(Quarter { year, quarter })
+    }
+}
+
+impl fmt::Display for Quarter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-Q{}", self.year, self.quarter)
+    }
+}
+
+/// Restricts a retro to some meetings, written "label:teamA", "participant:alice" or "org:acme.com"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetroFilter {
+    Label(String),
+    Participant(String),
+    Org(String),
+}
+
+impl RetroFilter {
+    /// Case-insensitive; participants match on any part of the name or email
+    pub fn matches(&self, entry: &CatalogEntry) -> bool {
+        match self {
+            RetroFilter::Label(label) => entry.labels.iter().any(|l| l.eq_ignore_ascii_case(label)),
+            RetroFilter::Participant(name) => {
+                let name = name.to_lowercase();
+                entry
+                    .participants
+                    .iter()
+                    .any(|p| p.to_lowercase().contains(&name))
+            }
+            RetroFilter::Org(org) => {
+                // Older transcripts only carry participants
+                let organizations = if entry.organizations.is_empty() {
+                    organizations_from_participants(&entry.participants)
+                } else {
+                    entry.organizations.clone()
+                };
+                organizations.iter().any(|o| o.eq_ignore_ascii_case(org))
+            }
+        }
+    }
+}
+
+impl FromStr for RetroFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once(':')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .filter(|(_, v)| !v.is_empty())
+            .ok_or_else(|| format!("Expected key:value like label:teamA, got '{}'", s))?;
+        match key.to_lowercase().as_str() {
+            "label" => Ok(RetroFilter::Label(value.to_string())),
+            "participant" => Ok(RetroFilter::Participant(value.to_string())),
+            "org" => Ok(RetroFilter::Org(value.to_string())),
+            _ => Err(format!(
+                "Unknown filter '{}'; use label, participant or org",
+                key
+            )),
+        }
+    }
+}
+
+impl fmt::Display for RetroFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetroFilter::Label(v) => write!(f, "label:{}", v),
+            RetroFilter::Participant(v) => write!(f, "participant:{}", v),
+            RetroFilter::Org(v) => write!(f, "org:{}", v),
+        }
+    }
+}
+
+/// A meeting in the retro's window with a saved summary
+#[derive(Debug, Clone, Serialize)]
+pub struct RetroMeeting {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub summary_path: PathBuf,
+}
+
+/// The meetings a retrospective covers
+#[derive(Debug, Clone)]
+pub struct Retro {
+    pub quarter: Quarter,
+    pub filters: Vec<RetroFilter>,
+    /// Meetings with saved summaries, oldest first
+    pub meetings: Vec<RetroMeeting>,
+    /// Matching meetings left out because they have no saved summary
+    pub unsummarized: usize,
+}
+
+/// Finds the quarter's meetings that match every filter
+pub fn gather(paths: &Paths, quarter: Quarter, filters: &[RetroFilter]) -> Result<Retro> {
+    let mut entries: Vec<CatalogEntry> = Catalog::open(paths)?
+        .entries()?
+        .into_iter()
+        .filter(|e| quarter.contains(e.created_at) && filters.iter().all(|f| f.matches(e)))
+        .collect();
+    entries.sort_by_key(|e| e.created_at);
+
+    let mut meetings = Vec::new();
+    let mut unsummarized = 0;
+    for entry in entries {
+        let stem = entry.path.file_stem().unwrap_or_default().to_string_lossy();
+        let summary_path = paths.summaries_dir.join(format!("{}_summary.md", stem));
+        if !summary_path.exists() {
+            unsummarized += 1;
+            continue;
+        }
+        meetings.push(RetroMeeting {
+            doc_id: entry.doc_id,
+            title: entry.title,
+            created_at: entry.created_at,
+            summary_path,
+        });
+    }
+
+    Ok(Retro {
+        quarter,
+        filters: filters.to_vec(),
+        meetings,
+        unsummarized,
+    })
+}
+
+impl Retro {
+    /// "2025-Q2" followed by the filters, for headings and messages
+    pub fn scope(&self) -> String {
+        let mut scope = self.quarter.to_string();
+        for filter in &self.filters {
+            scope.push_str(&format!(" {}", filter));
+        }
+        scope
+    }
+
+    /// The saved summaries as one document to summarize, oldest first
+    pub fn combined_summaries(&self) -> Result<String> {
+        let mut combined = String::new();
+        for meeting in &self.meetings {
+            combined.push_str(&format!(
+                "## {} ({})\n\n{}\n\n",
+                meeting.title.as_deref().unwrap_or("Untitled"),
+                meeting.created_at.format("%Y-%m-%d"),
+                std::fs::read_to_string(&meeting.summary_path)?
+            ));
+        }
+        Ok(combined)
+    }
+
+    /// Where the retrospective is written: retros/2025-Q2.md, or retros/2025-Q2_label-teama.md
+    pub fn path(&self, paths: &Paths) -> PathBuf {
+        let mut name = self.quarter.to_string();
+        for filter in &self.filters {
+            name.push('_');
+            name.push_str(&slugify(&filter.to_string()));
+        }
+        paths.data_dir.join("retros").join(format!("{}.md", name))
+    }
+
+    /// The retrospective text with a heading and the meetings it drew on
+    pub fn document(&self, retrospective: &str) -> String {
+        let mut doc = format!(
+            "# Retrospective {}\n\nFrom {} meeting summaries.\n\n{}\n\n## Sources\n\n",
+            self.scope(),
+            self.meetings.len(),
+            retrospective.trim()
+        );
+        for meeting in &self.meetings {
+            doc.push_str(&format!(
+                "- {} {} ({})\n",
+                meeting.created_at.format("%Y-%m-%d"),
+                meeting.title.as_deref().unwrap_or("Untitled"),
+                meeting.doc_id
+            ));
+        }
+        doc
+    }
+
+    /// Writes the retrospective document, returning its path
+    pub fn save(&self, paths: &Paths, retrospective: &str) -> Result<PathBuf> {
+        let path = self.path(paths);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        write_atomic(
+            &path,
+            self.document(retrospective).as_bytes(),
+            &paths.tmp_dir,
+        )?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_quarter() {
+        let q: Quarter = "2025-q2".parse().unwrap();
+        assert_eq!(
+            q,
+            Quarter {
+                year: 2025,
+                quarter: 2
+            }
+        );
+        assert_eq!(q.start(), NaiveDate::from_ymd_opt(2025, 4, 1).unwrap());
+        assert_eq!(q.end(), NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+        assert_eq!(
+            "2025-Q4".parse::<Quarter>().unwrap().end().to_string(),
+            "2026-01-01"
+        );
+        assert_eq!(q.to_string(), "2025-Q2");
+        assert!("2025-Q5".parse::<Quarter>().is_err());
+        assert!("2025Q1".parse::<Quarter>().is_err());
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            "label:teamA".parse::<RetroFilter>().unwrap(),
+            RetroFilter::Label("teamA".into())
+        );
+        assert_eq!(
+            "Org: acme.com".parse::<RetroFilter>().unwrap(),
+            RetroFilter::Org("acme.com".into())
+        );
+        assert!("team:a".parse::<RetroFilter>().is_err());
+        assert!("label:".parse::<RetroFilter>().is_err());
+    }
+
+    #[test]
+    fn test_gather_and_save() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let catalog = Catalog::open(&paths).unwrap();
+
+        let meetings = [
+            ("a", "2025-05-02_standup", 5, vec!["TeamA"], true),
+            ("b", "2025-04-10_planning", 4, vec!["teama"], true),
+            ("c", "2025-06-01_sync", 6, vec!["teamA"], false),
+            ("d", "2025-05-20_other", 5, vec!["teamB"], true),
+            ("e", "2025-07-01_next", 7, vec!["teamA"], true),
+        ];
+        for (id, stem, month, labels, summarized) in meetings {
+            catalog
+                .upsert(&CatalogEntry {
+                    doc_id: id.into(),
+                    path: paths.transcripts_dir.join(format!("{}.md", stem)),
+                    title: Some(stem.into()),
+                    created_at: Utc.with_ymd_and_hms(2025, month, 5, 10, 0, 0).unwrap(),
+                    participants: vec![],
+                    labels: labels.into_iter().map(String::from).collect(),
+                    organizations: vec![],
+                    content_hash: None,
+                    duplicate_of: None,
+                    merged_duplicates: vec![],
+                    access: None,
+                    modified: None,
+                })
+                .unwrap();
+            if summarized {
+                let summary = paths.summaries_dir.join(format!("{}_summary.md", stem));
+                std::fs::write(summary, format!("Summary of {}", id)).unwrap();
+            }
+        }
+
+        let filters = vec![RetroFilter::Label("teamA".into())];
+        let retro = gather(&paths, "2025-Q2".parse().unwrap(), &filters).unwrap();
+        let ids: Vec<&str> = retro.meetings.iter().map(|m| m.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(retro.unsummarized, 1);
+        assert!(retro
+            .combined_summaries()
+            .unwrap()
+            .starts_with("## 2025-04-10_planning (2025-04-05)\n\nSummary of b"));
+
+        let path = retro.save(&paths, "Themes: shipping\n").unwrap();
+        assert_eq!(path, temp.path().join("retros/2025-Q2_label-teama.md"));
+        let doc = std::fs::read_to_string(path).unwrap();
+        assert!(doc.starts_with("# Retrospective 2025-Q2 label:teamA\n\nFrom 2 meeting summaries."));
+        assert!(doc.ends_with("- 2025-05-05 2025-05-02_standup (a)\n"));
+    }
+}
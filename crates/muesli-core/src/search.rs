@@ -0,0 +1,89 @@
+// ABOUTME: The public search result type shared by text, semantic and hybrid search
+// ABOUTME: Serialized as-is by the JSON output layer and the MCP server
+
+use serde::{Deserialize, Serialize};
+
+/// What an indexed document holds: a meeting transcript or a saved summary of one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocKind {
+    #[default]
+    Transcript,
+    Summary,
+}
+
+impl DocKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DocKind::Transcript => "transcript",
+            DocKind::Summary => "summary",
+        }
+    }
+}
+
+/// One ranked hit from any search mode
+///
+/// Stability: the field names, their JSON spelling and their meaning are part
+/// of the public API and only change in a major release. New fields may be
+/// added, always with a serde default so JSON written by older versions still
+/// deserializes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub doc_id: String,
+    pub title: Option<String>,
+    /// Meeting date as YYYY-MM-DD, or "unknown" for a vector whose transcript is gone
+    pub date: String,
+    /// Transcript path, or "unknown" alongside an unknown date
+    pub path: String,
+    /// Relevance; BM25 for text search, cosine similarity for semantic search
+    pub score: f32,
+    #[serde(default)]
+    pub kind: DocKind,
+    /// Anchor of the transcript entry that best matches the query, when the
+    /// transcript was written with anchors; link to it as `path#anchor`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+    /// Short excerpt shipped with an imported snapshot, for meetings shared as public
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_hit_json_is_stable() {
+        let hit = SearchHit {
+            doc_id: "doc1".to_string(),
+            title: Some("Standup".to_string()),
+            date: "2025-01-02".to_string(),
+            path: "/data/raw/doc1.json".to_string(),
+            score: 1.5,
+            kind: DocKind::Summary,
+            anchor: None,
+            snippet: None,
+        };
+
+        let json = serde_json::to_value(&hit).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "doc_id": "doc1",
+                "title": "Standup",
+                "date": "2025-01-02",
+                "path": "/data/raw/doc1.json",
+                "score": 1.5,
+                "kind": "summary",
+            })
+        );
+        assert_eq!(serde_json::from_value::<SearchHit>(json).unwrap(), hit);
+
+        // JSON without a kind predates summaries in the index
+        let old: SearchHit = serde_json::from_str(
+            r#"{"doc_id":"d","title":null,"date":"unknown","path":"unknown","score":0.5}"#,
+        )
+        .unwrap();
+        assert_eq!(old.kind, DocKind::Transcript);
+    }
+}
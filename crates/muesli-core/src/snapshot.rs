@@ -0,0 +1,592 @@
+// ABOUTME: Read-only search snapshots (catalog, text index, vectors) to hand to a teammate
+// ABOUTME: Transcripts and raw JSON stay behind; access tags decide which meetings and bodies go in
+
+use crate::access::{Access, AccessPolicy};
+use crate::config;
+use crate::find::parse_entries;
+use crate::index::text;
+use crate::index::writer::WriterConfig;
+use crate::search::SearchHit;
+use crate::speakers::SpeakerAliases;
+use crate::storage::{scan_transcripts, strip_frontmatter};
+use crate::util::organizations_from_participants;
+use crate::{Error, Paths, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{Cursor, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Bumped when a snapshot's layout changes in a way older versions can't read
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Name of the manifest at the top of a snapshot
+const MANIFEST: &str = "snapshot.json";
+
+/// Longest snippet kept for a public meeting, in characters
+const SNIPPET_CHARS: usize = 300;
+
+/// `snapshot.json`: what a snapshot holds, standing in for the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub schema_version: u32,
+    /// muesli version that wrote the snapshot
+    pub generator: String,
+    pub exported_at: DateTime<Utc>,
+    /// Most restricted access level included
+    pub max_access: Access,
+    pub documents: Vec<SnapshotDocument>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotDocument {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub participants: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    #[serde(default)]
+    pub organizations: Vec<String>,
+    /// The meeting's `access` tag; None when it was untagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access: Option<Access>,
+    /// Transcript file name on the exporter's machine
+    pub file: String,
+    /// Opening of the summary or transcript, only for meetings tagged public
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<String>,
+}
+
+/// `snapshots/<name>/` in the data directory
+pub fn snapshot_dir(paths: &Paths, name: &str) -> PathBuf {
+    paths.data_dir.join("snapshots").join(name)
+}
+
+/// Writes a snapshot of every meeting `policy` allows to `out`
+///
+/// The text index is rebuilt from the included meetings rather than copied, so
+/// nothing excluded by the policy can be found in it. The index keeps word
+/// positions, from which text can be pieced back together, so only meetings
+/// tagged public have their transcript and summary indexed; the rest are found
+/// by title, participants, labels and organizations.
+pub fn export(paths: &Paths, out: &Path, policy: AccessPolicy) -> Result<SnapshotManifest> {
+    let work = paths
+        .tmp_dir
+        .join(format!("snapshot-export-{}", std::process::id()));
+    if work.exists() {
+        fs::remove_dir_all(&work)?;
+    }
+    let result = build(paths, &work, out, policy);
+    let _ = fs::remove_dir_all(&work);
+    result
+}
+
+fn build(paths: &Paths, work: &Path, out: &Path, policy: AccessPolicy) -> Result<SnapshotManifest> {
+    let index_dir = work.join("index").join("tantivy");
+    let index = text::create_or_open_index(&index_dir)?;
+    let mut writer = WriterConfig::default().writer(&index)?;
+    let aliases = SpeakerAliases::configured(paths)?;
+
+    let mut documents = Vec::new();
+    for (path, fm) in scan_transcripts(&paths.transcripts_dir)? {
+        if fm.duplicate_of.is_some() || !policy.allows(fm.access) {
+            continue;
+        }
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+        let public = fm.access == Some(Access::Public);
+        let content = fs::read_to_string(&path)?;
+        let body = crate::related::strip_related(strip_frontmatter(&content));
+        let organizations = if fm.organizations.is_empty() {
+            organizations_from_participants(&fm.participants)
+        } else {
+            fm.organizations.clone()
+        };
+
+        // Paths are relative to the snapshot; the teammate has no transcripts to open
+        let date = fm.created_at.format("%Y-%m-%d").to_string();
+        let participants = aliases.with_canonical(&fm.participants);
+        let transcript_path = PathBuf::from("transcripts").join(&name);
+        let document = text::IndexedDocument {
+            doc_id: &fm.doc_id,
+            title: fm.title.as_deref(),
+            date: &date,
+            body: if public { body } else { "" },
+            path: &transcript_path,
+            organizations: &organizations,
+            participants: &participants,
+            labels: &fm.labels,
+        };
+        text::index_document_batch(&mut writer, &index, &document)?;
+
+        let snippet = if public {
+            let summary_name = format!("{}_summary.md", stem);
+            let summary = match fs::read_to_string(paths.summaries_dir.join(&summary_name)) {
+                Ok(summary) => Some(summary),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            if let Some(summary) = &summary {
+                let summary_path = PathBuf::from("summaries").join(&summary_name);
+                let document = text::IndexedDocument {
+                    body: summary,
+                    path: &summary_path,
+                    ..document
+                };
+                text::index_summary_batch(&mut writer, &index, &document)?;
+            }
+            snippet(summary.as_deref(), body)
+        } else {
+            None
+        };
+        documents.push(SnapshotDocument {
+            doc_id: fm.doc_id.clone(),
+            title: fm.title.clone(),
+            created_at: fm.created_at,
+            participants: fm.participants.clone(),
+            labels: fm.labels.clone(),
+            organizations,
+            access: fm.access,
+            file: name,
+            snippet,
+        });
+    }
+    writer
+        .commit()
+        .map_err(|e| Error::Indexing(format!("Failed to commit index: {}", e)))?;
+    drop(writer);
+    documents.sort_by_key(|d| d.created_at);
+
+    #[cfg(feature = "semantic")]
+    copy_vectors(paths, &work.join("index"), &documents)?;
+
+    let manifest = SnapshotManifest {
+        schema_version: SCHEMA_VERSION,
+        generator: format!("muesli {}", env!("CARGO_PKG_VERSION")),
+        exported_at: Utc::now(),
+        max_access: policy.max_access,
+        documents,
+    };
+
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST, options).map_err(zip_err)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    // The teammate needs the same model to embed their queries; the rest of
+    // config.toml (mailbox, callbacks, access levels) stays private
+    if let Some(embeddings) = config::raw_section(&paths.config_file, "embeddings")? {
+        let shared = toml::Table::from_iter([("embeddings".to_string(), embeddings)]);
+        zip.start_file(config::FILE_NAME, options)
+            .map_err(zip_err)?;
+        zip.write_all(shared.to_string().as_bytes())?;
+    }
+    for file in files_under(&work.join("index"))? {
+        let relative = file.strip_prefix(work).unwrap_or(&file);
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(name, options).map_err(zip_err)?;
+        zip.write_all(&fs::read(&file)?)?;
+    }
+    let bytes = zip.finish().map_err(zip_err)?.into_inner();
+
+    if let Some(parent) = out.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out, bytes)?;
+    Ok(manifest)
+}
+
+/// Keeps only the vectors of public meetings, since a vector is an embedding of the full body
+#[cfg(feature = "semantic")]
+fn copy_vectors(paths: &Paths, index_dir: &Path, documents: &[SnapshotDocument]) -> Result<()> {
+    use crate::embeddings::vector::VectorStore;
+
+    let source = paths.index_dir.join("vectors");
+    if !source.with_extension("meta.json").exists() {
+        return Ok(());
+    }
+    let vectors = VectorStore::load(&source)?;
+    let mut kept = VectorStore::new(vectors.dim());
+    if let Some(model) = vectors.model() {
+        kept.pin_model(model)?;
+    }
+    for doc in documents
+        .iter()
+        .filter(|d| d.access == Some(Access::Public))
+    {
+        if let Some(vector) = vectors.vector(&doc.doc_id) {
+            kept.add_document(doc.doc_id.clone(), vector.to_vec())?;
+        }
+    }
+    kept.save(&index_dir.join("tantivy").join("vectors"), index_dir)
+}
+
+/// Files below `dir`, skipping tantivy's lock files
+fn files_under(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_under(&path)?);
+        } else if !path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with(".tantivy-"))
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// The summary's prose, or the opening of the transcript when there is no summary
+fn snippet(summary: Option<&str>, body: &str) -> Option<String> {
+    let text = match summary {
+        Some(summary) => summary
+            .lines()
+            .map(|line| line.trim().trim_start_matches("- ").trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => parse_entries(body)
+            .iter()
+            .map(|entry| format!("{}: {}", entry.speaker, entry.text))
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= SNIPPET_CHARS {
+        return Some(text);
+    }
+    let cut: String = text.chars().take(SNIPPET_CHARS).collect();
+    Some(format!("{}…", cut.trim_end()))
+}
+
+/// Unpacks a snapshot into `snapshots/<name>/`, replacing an earlier import under that name
+pub fn import(paths: &Paths, file: &Path, name: &str) -> Result<SnapshotManifest> {
+    if name.is_empty() || crate::util::slugify(name) != name {
+        return Err(Error::InvalidInput(format!(
+            "Snapshot names may only use lowercase letters, digits and dashes: {}",
+            name
+        )));
+    }
+
+    let mut archive = zip::ZipArchive::new(fs::File::open(file)?).map_err(zip_err)?;
+    let manifest: SnapshotManifest = {
+        let entry = archive.by_name(MANIFEST).map_err(|_| {
+            Error::InvalidInput(format!("{} is not a muesli snapshot", file.display()))
+        })?;
+        serde_json::from_reader(entry)?
+    };
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(Error::InvalidInput(format!(
+            "{} was written by a newer muesli ({}); upgrade to import it",
+            file.display(),
+            manifest.generator
+        )));
+    }
+
+    // Unpack beside the destination so a bad archive never leaves half a snapshot
+    let staging = paths
+        .tmp_dir
+        .join(format!("snapshot-import-{}", std::process::id()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    let result = unpack(&mut archive, &staging);
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    // The snapshot has no transcripts; an empty directory keeps the catalog quiet
+    fs::create_dir_all(staging.join("transcripts"))?;
+
+    let target = snapshot_dir(paths, name);
+    if target.exists() {
+        fs::remove_dir_all(&target)?;
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&staging, &target)?;
+    Ok(manifest)
+}
+
+fn unpack(archive: &mut zip::ZipArchive<fs::File>, dir: &Path) -> Result<()> {
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(zip_err)?;
+        let relative = entry
+            .enclosed_name()
+            .filter(|p| {
+                p == Path::new(MANIFEST)
+                    || p == Path::new(config::FILE_NAME)
+                    || p.starts_with("index")
+            })
+            .ok_or_else(|| {
+                Error::InvalidInput(format!("Unexpected file in snapshot: {}", entry.name()))
+            })?;
+        let dest = dir.join(relative);
+        if entry.is_dir() {
+            fs::create_dir_all(&dest)?;
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut entry, &mut fs::File::create(&dest)?)?;
+    }
+    Ok(())
+}
+
+/// Imported snapshots by name, oldest export first within equal names
+pub fn list(paths: &Paths) -> Result<Vec<(String, SnapshotManifest)>> {
+    let dir = paths.data_dir.join("snapshots");
+    let mut snapshots = Vec::new();
+    if !dir.exists() {
+        return Ok(snapshots);
+    }
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        let manifest_path = path.join(MANIFEST);
+        if !manifest_path.exists() {
+            continue;
+        }
+        let manifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+        snapshots.push((
+            path.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned(),
+            manifest,
+        ));
+    }
+    snapshots.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(snapshots)
+}
+
+/// Paths for searching an imported snapshot, and its manifest
+///
+/// Models are shared with the main data directory so a snapshot doesn't need
+/// its own download.
+pub fn open(paths: &Paths, name: &str) -> Result<(Paths, SnapshotManifest)> {
+    let dir = snapshot_dir(paths, name);
+    let manifest_path = dir.join(MANIFEST);
+    if !manifest_path.exists() {
+        return Err(Error::InvalidInput(format!(
+            "No snapshot named '{}'; import one with 'muesli index import'",
+            name
+        )));
+    }
+    let manifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+    let mut snapshot_paths = Paths::new(Some(dir), None)?;
+    snapshot_paths.models_dir = paths.models_dir.clone();
+    Ok((snapshot_paths, manifest))
+}
+
+/// Fills in what a snapshot hit can't get from transcripts on disk
+pub fn describe_hits(hits: &mut [SearchHit], manifest: &SnapshotManifest) {
+    for hit in hits {
+        let Some(doc) = manifest.documents.iter().find(|d| d.doc_id == hit.doc_id) else {
+            continue;
+        };
+        if hit.path == "unknown" {
+            hit.title = doc.title.clone();
+            hit.date = doc.created_at.format("%Y-%m-%d").to_string();
+            hit.path = format!("transcripts/{}", doc.file);
+        }
+        hit.snippet = doc.snippet.clone();
+    }
+}
+
+fn zip_err(e: zip::result::ZipError) -> Error {
+    Error::Filesystem(e.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_transcript(paths: &Paths, name: &str, doc_id: &str, access: Option<&str>, body: &str) {
+        let access = access
+            .map(|a| format!("access: {}\n", a))
+            .unwrap_or_default();
+        fs::write(
+            paths.transcripts_dir.join(format!("{}.md", name)),
+            format!(
+                "---\ndoc_id: {}\nsource: granola\ncreated_at: 2025-03-01T10:00:00Z\ntitle: {}\n{}generator: muesli 1.0\n---\n\n# {}\n\n{}\n",
+                doc_id, name, access, name, body
+            ),
+        )
+        .unwrap();
+    }
+
+    fn exported(temp: &TempDir) -> PathBuf {
+        let paths = Paths::new(Some(temp.path().join("mine")), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        exported_from(temp, &paths)
+    }
+
+    fn exported_from(temp: &TempDir, paths: &Paths) -> PathBuf {
+        write_transcript(
+            paths,
+            "launch",
+            "d1",
+            Some("public"),
+            "**Alice:** The rocket launch moved to Friday",
+        );
+        write_transcript(
+            paths,
+            "roadmap",
+            "d2",
+            None,
+            "**Bob:** The rocket budget needs review",
+        );
+        write_transcript(
+            paths,
+            "review",
+            "d3",
+            Some("private"),
+            "**Carol:** My rocket salary review",
+        );
+
+        let out = temp.path().join("team.zip");
+        let manifest = export(paths, &out, AccessPolicy::default()).unwrap();
+        let ids: Vec<&str> = manifest
+            .documents
+            .iter()
+            .map(|d| d.doc_id.as_str())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&"d3"));
+        out
+    }
+
+    #[test]
+    fn test_snapshot_leaves_out_transcripts_and_private_meetings() {
+        let temp = TempDir::new().unwrap();
+        let out = exported(&temp);
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&out).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        assert!(names.contains(&MANIFEST.to_string()));
+        assert!(names
+            .iter()
+            .all(|n| n == MANIFEST || n.starts_with("index/tantivy/")));
+
+        // Public bodies are indexed, never stored; the rest aren't indexed at all
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let mut bytes = Vec::new();
+            std::io::Read::read_to_end(&mut entry, &mut bytes).unwrap();
+            let content = String::from_utf8_lossy(&bytes);
+            assert!(
+                !content.contains("salary"),
+                "{} leaks a private meeting",
+                entry.name()
+            );
+            assert!(
+                !content.contains("budget"),
+                "{} holds a non-public transcript",
+                entry.name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_imported_snapshot_is_searchable_with_public_snippets() {
+        let temp = TempDir::new().unwrap();
+        let out = exported(&temp);
+
+        let theirs = Paths::new(Some(temp.path().join("theirs")), None).unwrap();
+        theirs.ensure_dirs().unwrap();
+        import(&theirs, &out, "alice").unwrap();
+        assert_eq!(list(&theirs).unwrap()[0].0, "alice");
+
+        let (snapshot, manifest) = open(&theirs, "alice").unwrap();
+        let index = text::open_for_search(&snapshot.index_dir).unwrap();
+        let mut hits = text::search(&index, "rocket", 10).unwrap();
+        describe_hits(&mut hits, &manifest);
+
+        let mut found: Vec<(&str, Option<&str>)> = hits
+            .iter()
+            .map(|h| (h.doc_id.as_str(), h.snippet.as_deref()))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![("d1", Some("Alice: The rocket launch moved to Friday"))]
+        );
+        assert!(hits.iter().all(|h| h.path.starts_with("transcripts/")));
+
+        // A team meeting is found by its metadata, not its words
+        let hits = text::search(&index, "roadmap", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].doc_id, "d2");
+        assert!(text::search(&index, "budget", 10).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "semantic")]
+    #[test]
+    fn test_snapshot_keeps_vectors_of_public_meetings_only() {
+        use crate::embeddings::vector::VectorStore;
+
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().join("mine")), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let mut vectors = VectorStore::new(2);
+        for doc_id in ["d1", "d2", "d3"] {
+            vectors
+                .add_document(doc_id.to_string(), vec![1.0, 0.0])
+                .unwrap();
+        }
+        vectors
+            .save(&paths.index_dir.join("vectors"), &paths.tmp_dir)
+            .unwrap();
+        let out = exported_from(&temp, &paths);
+
+        let theirs = Paths::new(Some(temp.path().join("theirs")), None).unwrap();
+        theirs.ensure_dirs().unwrap();
+        import(&theirs, &out, "alice").unwrap();
+        let (snapshot, _) = open(&theirs, "alice").unwrap();
+        let shared = VectorStore::load(&snapshot.index_dir.join("vectors")).unwrap();
+        assert!(shared.vector("d1").is_some());
+        assert!(shared.vector("d2").is_none(), "team meeting vector shipped");
+        assert!(
+            shared.vector("d3").is_none(),
+            "private meeting vector shipped"
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_bad_names() {
+        let temp = TempDir::new().unwrap();
+        let out = exported(&temp);
+        let theirs = Paths::new(Some(temp.path().join("theirs")), None).unwrap();
+        assert!(import(&theirs, &out, "../escape").is_err());
+        assert!(open(&theirs, "missing").is_err());
+    }
+
+    #[test]
+    fn test_snippet_prefers_summary_prose() {
+        let summary = "## Summary\n\n- Launch moved to Friday\n";
+        assert_eq!(
+            snippet(Some(summary), "").as_deref(),
+            Some("Launch moved to Friday")
+        );
+        let long = "word ".repeat(100);
+        assert!(snippet(Some(&long), "").unwrap().ends_with('…'));
+    }
+}
@@ -0,0 +1,205 @@
+// ABOUTME: Document sources other than Granola, for `muesli sync --from-dir`
+// ABOUTME: DirectorySource serves a folder of raw transcript JSON and WebVTT/SRT caption files
+
+use crate::api::DocumentSource;
+use crate::ingest::{date_from_name, parse_captions, title_from_name, to_raw_transcript};
+use crate::{DocumentMetadata, DocumentSummary, Error, RawTranscript, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Optional `<name>.meta.json` beside a transcript, overriding what the file name gives
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Sidecar {
+    title: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    participants: Vec<String>,
+    labels: Vec<String>,
+    duration_seconds: Option<u64>,
+}
+
+/// A [`DocumentSource`] reading meetings from files in one folder
+///
+/// Each `<name>.json` (a raw transcript, as saved in `raw/`), `<name>.vtt` or `<name>.srt` is a
+/// meeting with document ID `<name>`. The title is the name with `-`/`_` as
+/// spaces and the date a leading `YYYY-MM-DD`, or the file's modification time;
+/// a `<name>.meta.json` can set `title`, `created_at`, `participants`, `labels`
+/// and `duration_seconds` instead. Editing a file makes the next sync update it.
+pub struct DirectorySource {
+    dir: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        if !dir.is_dir() {
+            return Err(Error::InvalidInput(format!(
+                "{} is not a directory",
+                dir.display()
+            )));
+        }
+        Ok(DirectorySource { dir })
+    }
+
+    /// Transcript files by document ID, in name order
+    fn transcripts(&self) -> Result<Vec<(String, PathBuf)>> {
+        let mut found = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if name.starts_with('.') || name.ends_with(".meta.json") || !path.is_file() {
+                continue;
+            }
+            if let Some(id) = name
+                .strip_suffix(".json")
+                .or_else(|| name.strip_suffix(".vtt"))
+                .or_else(|| name.strip_suffix(".srt"))
+            {
+                found.push((id.to_string(), path.clone()));
+            }
+        }
+        found.sort();
+        Ok(found)
+    }
+
+    fn find(&self, doc_id: &str) -> Result<PathBuf> {
+        self.transcripts()?
+            .into_iter()
+            .find(|(id, _)| id == doc_id)
+            .map(|(_, path)| path)
+            .ok_or_else(|| Error::Api {
+                endpoint: self.dir.display().to_string(),
+                status: 404,
+                message: format!("Document not found: {}", doc_id),
+            })
+    }
+
+    fn sidecar_path(&self, doc_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.meta.json", doc_id))
+    }
+
+    fn sidecar(&self, doc_id: &str) -> Result<Sidecar> {
+        let path = self.sidecar_path(doc_id);
+        if !path.exists() {
+            return Ok(Sidecar::default());
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    fn summary(&self, doc_id: &str, path: &Path) -> Result<DocumentSummary> {
+        let sidecar = self.sidecar(doc_id)?;
+        let modified = modified(path)?;
+        let sidecar_modified = modified_if_exists(&self.sidecar_path(doc_id))?;
+        Ok(DocumentSummary {
+            id: doc_id.to_string(),
+            title: Some(sidecar.title.unwrap_or_else(|| title_from_name(doc_id))),
+            created_at: sidecar
+                .created_at
+                .or_else(|| date_from_name(doc_id))
+                .unwrap_or(modified),
+            updated_at: Some(sidecar_modified.map_or(modified, |m| m.max(modified))),
+        })
+    }
+}
+
+#[async_trait]
+impl DocumentSource for DirectorySource {
+    async fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        self.transcripts()?
+            .iter()
+            .map(|(id, path)| self.summary(id, path))
+            .collect()
+    }
+
+    async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        let summary = self.summary(doc_id, &self.find(doc_id)?)?;
+        let sidecar = self.sidecar(doc_id)?;
+        Ok(DocumentMetadata {
+            id: Some(summary.id),
+            title: summary.title,
+            created_at: summary.created_at,
+            updated_at: summary.updated_at,
+            participants: sidecar.participants,
+            duration_seconds: sidecar.duration_seconds,
+            labels: sidecar.labels,
+            calendar_event: None,
+        })
+    }
+
+    async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        let path = self.find(doc_id)?;
+        if path.extension().is_some_and(|ext| ext != "json") {
+            let captions = parse_captions(&path)?;
+            let started_at = self.summary(doc_id, &path)?.created_at;
+            return Ok(to_raw_transcript(&captions, doc_id, started_at));
+        }
+        serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))
+    }
+
+    fn name(&self) -> &str {
+        "directory"
+    }
+}
+
+fn modified(path: &Path) -> Result<DateTime<Utc>> {
+    Ok(fs::metadata(path)?.modified()?.into())
+}
+
+fn modified_if_exists(path: &Path) -> Result<Option<DateTime<Utc>>> {
+    match fs::metadata(path) {
+        Ok(meta) => Ok(Some(meta.modified()?.into())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_directory_source() {
+        let source_dir = TempDir::new().unwrap();
+        fs::write(
+            source_dir.path().join("2025-03-01_standup.vtt"),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.000\n<v Alice>Morning.\n",
+        )
+        .unwrap();
+        fs::write(
+            source_dir.path().join("retro.json"),
+            r#"[{"speaker": "Bob", "text": "Ship it.", "start_timestamp": "2025-03-02T15:00:00Z"}]"#,
+        )
+        .unwrap();
+        fs::write(
+            source_dir.path().join("retro.meta.json"),
+            r#"{"title": "Q1 Retro", "created_at": "2025-03-02T15:00:00Z", "participants": ["Bob"]}"#,
+        )
+        .unwrap();
+        fs::write(source_dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let source = DirectorySource::new(source_dir.path()).unwrap();
+        let docs = source.list_documents().await.unwrap();
+        let ids: Vec<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["2025-03-01_standup", "retro"]);
+        assert_eq!(docs[1].title.as_deref(), Some("Q1 Retro"));
+
+        let meta = source.get_metadata("retro").await.unwrap();
+        assert_eq!(meta.participants, vec!["Bob"]);
+        let raw = source.get_transcript("2025-03-01_standup").await.unwrap();
+        assert_eq!(raw.entries[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(
+            raw.entries[0].start.as_deref(),
+            Some("2025-03-01T00:00:01.000Z")
+        );
+        assert!(source.get_transcript("missing").await.is_err());
+        assert!(DirectorySource::new(source_dir.path().join("notes.txt")).is_err());
+    }
+}
@@ -0,0 +1,519 @@
+// ABOUTME: Names generic "Speaker 1"/"Speaker 2" labels after a two-person meeting's attendees,
+// ABOUTME: and maps emails and name variants to one canonical name from config.toml
+
+use crate::{config, Paths, RawTranscript, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Guesses below this confidence are never applied without asking
+pub const AUTO_MIN_CONFIDENCE: f32 = 0.75;
+
+/// Most pieces of evidence shown when asking
+const MAX_EVIDENCE: usize = 3;
+
+/// Whether and how sync names generic speakers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpeakerNaming {
+    /// Keep the labels Granola reported
+    #[default]
+    Off,
+    /// Show each guess and ask before applying it
+    Ask,
+    /// Apply guesses of at least `AUTO_MIN_CONFIDENCE` without asking
+    Auto,
+}
+
+/// A generic label and the attendee it was mapped to, as recorded in frontmatter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InferredSpeaker {
+    pub label: String,
+    pub name: String,
+    /// How strongly the transcript favored this mapping over the swapped one (0.5 = no evidence)
+    pub confidence: f32,
+    /// Someone accepted the mapping at the sync prompt
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+/// A guessed naming of both speakers with the lines that support it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Proposal {
+    pub speakers: Vec<InferredSpeaker>,
+    pub evidence: Vec<String>,
+}
+
+impl Proposal {
+    pub fn confidence(&self) -> f32 {
+        self.speakers.first().map_or(0.0, |s| s.confidence)
+    }
+
+    /// The same proposal with the two names exchanged
+    pub fn swapped(&self) -> Proposal {
+        let mut speakers = self.speakers.clone();
+        let first = speakers[0].name.clone();
+        speakers[0].name = speakers[1].name.clone();
+        speakers[1].name = first;
+        for s in &mut speakers {
+            s.confidence = 1.0 - s.confidence;
+        }
+        Proposal {
+            speakers,
+            evidence: self.evidence.clone(),
+        }
+    }
+}
+
+/// An answer to the naming prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Accept,
+    Swap,
+    Skip,
+}
+
+/// Whether a label is one Granola uses when it can't tell who spoke ("Speaker 2")
+pub fn is_generic(label: &str) -> bool {
+    let lower = label.trim().to_lowercase();
+    match lower.strip_prefix("speaker") {
+        Some(rest) => {
+            let rest = rest.trim_start_matches([' ', '_', '-']);
+            rest.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+/// The transcript's speaker labels in order of appearance, if all of them are generic
+fn generic_labels(raw: &RawTranscript) -> Option<Vec<String>> {
+    let mut labels: Vec<String> = Vec::new();
+    for entry in &raw.entries {
+        let label = entry.speaker.as_deref()?;
+        if !is_generic(label) {
+            return None;
+        }
+        if !labels.iter().any(|l| l == label) {
+            labels.push(label.to_string());
+        }
+    }
+    Some(labels)
+}
+
+/// "Alice Smith" for "Alice Smith <alice@acme.com>", "alice" for a bare address
+pub fn display_name(participant: &str) -> String {
+    let name = participant.split('<').next().unwrap_or("").trim();
+    if !name.is_empty() && !name.contains('@') {
+        return name.to_string();
+    }
+    let address = participant
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+    address.split('@').next().unwrap_or(address).to_string()
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the words introduce the speaker as `first` ("I'm Alice", "this is Alice", "Alice here")
+fn introduces(words: &[String], first: &str) -> bool {
+    const BEFORE: &[&[&str]] = &[&["i'm"], &["i", "am"], &["this", "is"], &["name", "is"]];
+
+    words.iter().enumerate().any(|(i, w)| {
+        if w != first {
+            return false;
+        }
+        let preceded = BEFORE.iter().any(|pattern| {
+            i >= pattern.len()
+                && words[i - pattern.len()..i]
+                    .iter()
+                    .zip(pattern.iter())
+                    .all(|(a, b)| a == b)
+        });
+        preceded || words.get(i + 1).is_some_and(|next| next == "here")
+    })
+}
+
+/// Guesses which of two attendees each of two generic speakers is
+///
+/// Only applies when every entry has a generic label, there are exactly two
+/// labels and exactly two attendees. A speaker introducing themselves by
+/// name counts twice as much as a speaker saying the other attendee's name.
+/// Without any evidence the guess is a coin flip (confidence 0.5).
+pub fn propose(raw: &RawTranscript, participants: &[String]) -> Option<Proposal> {
+    let labels = generic_labels(raw)?;
+    if labels.len() != 2 || participants.len() != 2 {
+        return None;
+    }
+    let names: Vec<String> = participants.iter().map(|p| display_name(p)).collect();
+    let firsts: Vec<String> = names
+        .iter()
+        .map(|n| words(n).into_iter().next().unwrap_or_default())
+        .collect();
+
+    // votes[speaker][attendee]: evidence that this speaker is that attendee
+    let mut votes = [[0u32; 2]; 2];
+    let mut evidence = Vec::new();
+    for entry in &raw.entries {
+        let Some(s) = labels
+            .iter()
+            .position(|l| Some(l.as_str()) == entry.speaker.as_deref())
+        else {
+            continue;
+        };
+        let words = words(&entry.text);
+        for (a, first) in firsts.iter().enumerate() {
+            if first.chars().count() < 2 || !words.contains(first) {
+                continue;
+            }
+            if introduces(&words, first) {
+                votes[s][a] += 2;
+                evidence.push(format!(
+                    "{} introduced themselves as {}",
+                    labels[s], names[a]
+                ));
+            } else {
+                votes[s][1 - a] += 1;
+                evidence.push(format!("{} addressed {}", labels[s], names[a]));
+            }
+        }
+    }
+
+    let straight = votes[0][0] + votes[1][1];
+    let crossed = votes[0][1] + votes[1][0];
+    let (order, win, lose) = if crossed > straight {
+        ([1, 0], crossed, straight)
+    } else {
+        ([0, 1], straight, crossed)
+    };
+    let confidence = (win + 1) as f32 / (win + lose + 2) as f32;
+
+    evidence.dedup();
+    evidence.truncate(MAX_EVIDENCE);
+    Some(Proposal {
+        speakers: labels
+            .into_iter()
+            .zip(order)
+            .map(|(label, a)| InferredSpeaker {
+                label,
+                name: names[a].clone(),
+                confidence,
+                confirmed: false,
+            })
+            .collect(),
+        evidence,
+    })
+}
+
+/// Decides the names to render for a transcript
+///
+/// Names recorded for the same labels on an earlier sync are kept, so an
+/// updated meeting isn't asked about again. Otherwise `ask` is consulted in
+/// `Ask` mode, and confident guesses are applied in `Auto` mode.
+pub fn resolve<F>(
+    mode: SpeakerNaming,
+    raw: &RawTranscript,
+    participants: &[String],
+    previous: &[InferredSpeaker],
+    ask: F,
+) -> std::io::Result<Vec<InferredSpeaker>>
+where
+    F: FnOnce(&Proposal) -> std::io::Result<Choice>,
+{
+    let Some(labels) = generic_labels(raw) else {
+        return Ok(Vec::new());
+    };
+    let mut known: Vec<&str> = previous.iter().map(|s| s.label.as_str()).collect();
+    known.sort_unstable();
+    let mut current: Vec<&str> = labels.iter().map(String::as_str).collect();
+    current.sort_unstable();
+    if !previous.is_empty() && known == current {
+        return Ok(previous.to_vec());
+    }
+
+    if mode == SpeakerNaming::Off {
+        return Ok(Vec::new());
+    }
+    let Some(proposal) = propose(raw, participants) else {
+        return Ok(Vec::new());
+    };
+
+    if mode == SpeakerNaming::Auto {
+        return Ok(if proposal.confidence() >= AUTO_MIN_CONFIDENCE {
+            proposal.speakers
+        } else {
+            Vec::new()
+        });
+    }
+
+    let chosen = match ask(&proposal)? {
+        Choice::Accept => proposal,
+        Choice::Swap => proposal.swapped(),
+        Choice::Skip => return Ok(Vec::new()),
+    };
+    Ok(chosen
+        .speakers
+        .into_iter()
+        .map(|s| InferredSpeaker {
+            confirmed: true,
+            ..s
+        })
+        .collect())
+}
+
+/// Asks on the terminal whether to apply a proposal
+///
+/// Anything other than yes or swap skips the meeting.
+pub fn ask(title: Option<&str>, proposal: &Proposal) -> std::io::Result<Choice> {
+    use std::io::{BufRead, Write};
+
+    println!(
+        "\n\"{}\" has generic speakers and two attendees:",
+        title.unwrap_or("Untitled Meeting")
+    );
+    for s in &proposal.speakers {
+        println!("  {} → {}", s.label, s.name);
+    }
+    if proposal.evidence.is_empty() {
+        println!("  (a guess: no names were mentioned)");
+    } else {
+        println!(
+            "  ({:.0}% confident: {})",
+            proposal.confidence() * 100.0,
+            proposal.evidence.join("; ")
+        );
+    }
+    print!("Use these names? [y]es / [s]wap / [N]o: ");
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    Ok(match answer.trim().to_lowercase().as_str() {
+        "y" | "yes" => Choice::Accept,
+        "s" | "swap" => Choice::Swap,
+        _ => Choice::Skip,
+    })
+}
+
+/// The name to show for a speaker label
+pub fn rename<'a>(label: &'a str, speakers: &'a [InferredSpeaker]) -> &'a str {
+    speakers
+        .iter()
+        .find(|s| s.label == label)
+        .map_or(label, |s| s.name.as_str())
+}
+
+/// `[speakers.aliases]` in config.toml
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AliasFile {
+    /// Canonical name -> other names and addresses for the same person
+    aliases: BTreeMap<String, Vec<String>>,
+}
+
+/// Canonical names for speakers and participants known by several names
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeakerAliases {
+    /// Lowercased name or alias -> canonical name
+    lookup: HashMap<String, String>,
+}
+
+impl SpeakerAliases {
+    /// The map in a config file's `[speakers]` table; a name may belong to one person only
+    pub fn load(path: &Path) -> Result<Self> {
+        let file: AliasFile = config::load_section(path, "speakers")?;
+
+        let mut lookup = HashMap::new();
+        for (canonical, aliases) in &file.aliases {
+            for name in std::iter::once(canonical).chain(aliases) {
+                let key = name.trim().to_lowercase();
+                match lookup.insert(key, canonical.clone()) {
+                    Some(other) if &other != canonical => {
+                        return Err(config::invalid(
+                            path,
+                            "speakers",
+                            format!(
+                                "'{}' is listed for both '{}' and '{}'",
+                                name.trim(),
+                                other,
+                                canonical
+                            ),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(Self { lookup })
+    }
+
+    /// The names this data directory's transcripts and participant lists use
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    /// The canonical name for a speaker label or participant, if the map knows it
+    ///
+    /// Case doesn't matter. "Name <address>" matches on the whole text, the
+    /// address or the name.
+    pub fn canonical(&self, name: &str) -> Option<&str> {
+        if self.lookup.is_empty() {
+            return None;
+        }
+        let name = name.trim();
+        let mut candidates = vec![name];
+        if let Some((before, rest)) = name.split_once('<') {
+            candidates.push(rest.trim_end_matches('>'));
+            candidates.push(before);
+        }
+        candidates
+            .into_iter()
+            .map(|c| c.trim().to_lowercase())
+            .find_map(|c| self.lookup.get(&c))
+            .map(String::as_str)
+    }
+
+    /// The canonical name, or `name` itself when it has none
+    pub fn apply<'a>(&'a self, name: &'a str) -> &'a str {
+        self.canonical(name).unwrap_or(name)
+    }
+
+    /// Participants followed by the canonical names of any that have one
+    ///
+    /// For indexing, so a search finds a person by either name.
+    pub fn with_canonical(&self, participants: &[String]) -> Vec<String> {
+        let mut all = participants.to_vec();
+        for participant in participants {
+            if let Some(canonical) = self.canonical(participant) {
+                if !all.iter().any(|p| p == canonical) {
+                    all.push(canonical.to_string());
+                }
+            }
+        }
+        all
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(lines: &[(&str, &str)]) -> RawTranscript {
+        let entries: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|(speaker, text)| serde_json::json!({"speaker": speaker, "text": text}))
+            .collect();
+        serde_json::from_value(serde_json::Value::Array(entries)).unwrap()
+    }
+
+    fn attendees() -> Vec<String> {
+        vec!["Alice Smith <alice@acme.com>".into(), "bob@beta.io".into()]
+    }
+
+    #[test]
+    fn test_is_generic_and_display_name() {
+        assert!(is_generic("Speaker 2"));
+        assert!(is_generic("speaker"));
+        assert!(!is_generic("Speakerphone"));
+        assert!(!is_generic("Alice"));
+        assert_eq!(display_name("Alice Smith <alice@acme.com>"), "Alice Smith");
+        assert_eq!(display_name("bob@beta.io"), "bob");
+    }
+
+    #[test]
+    fn test_propose_from_names() {
+        let transcript = raw(&[
+            ("Speaker 1", "Hey Bob, thanks for joining"),
+            ("Speaker 2", "Of course. Bob here, I run sales"),
+            ("Speaker 1", "Great"),
+        ]);
+        let proposal = propose(&transcript, &attendees()).unwrap();
+        assert_eq!(proposal.speakers[0].label, "Speaker 1");
+        assert_eq!(proposal.speakers[0].name, "Alice Smith");
+        assert_eq!(proposal.speakers[1].name, "bob");
+        assert!((proposal.confidence() - 0.8).abs() < 1e-6);
+        assert_eq!(proposal.evidence.len(), 2);
+
+        // Reversed evidence flips the mapping
+        let transcript = raw(&[("Speaker 1", "I'm Bob"), ("Speaker 2", "Hi Bob")]);
+        let proposal = propose(&transcript, &attendees()).unwrap();
+        assert_eq!(proposal.speakers[0].name, "bob");
+
+        // Named speakers, three labels or three attendees: nothing to infer
+        assert!(propose(&raw(&[("Alice", "Hi"), ("Speaker 2", "Hi")]), &attendees()).is_none());
+        let mut three = attendees();
+        three.push("Carol".into());
+        assert!(propose(&transcript, &three).is_none());
+    }
+
+    #[test]
+    fn test_resolve() {
+        let transcript = raw(&[("Speaker 1", "Hey Bob"), ("Speaker 2", "Hi")]);
+        let never = |_: &Proposal| -> std::io::Result<Choice> { panic!("asked") };
+
+        // One mention is not enough to apply unasked
+        let auto = resolve(SpeakerNaming::Auto, &transcript, &attendees(), &[], never).unwrap();
+        assert!(auto.is_empty());
+
+        let swapped = resolve(SpeakerNaming::Ask, &transcript, &attendees(), &[], |_| {
+            Ok(Choice::Swap)
+        })
+        .unwrap();
+        assert_eq!(swapped[0].name, "bob");
+        assert!(swapped[0].confirmed);
+        assert!((swapped[0].confidence - 1.0 / 3.0).abs() < 1e-6);
+
+        // Earlier names are kept without asking, even with naming off
+        let kept = resolve(
+            SpeakerNaming::Off,
+            &transcript,
+            &attendees(),
+            &swapped,
+            never,
+        )
+        .unwrap();
+        assert_eq!(kept, swapped);
+        assert_eq!(rename("Speaker 1", &kept), "bob");
+        assert_eq!(rename("Speaker 3", &kept), "Speaker 3");
+    }
+
+    #[test]
+    fn test_speaker_aliases() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            SpeakerAliases::load(&path).unwrap().apply("John D"),
+            "John D"
+        );
+
+        std::fs::write(
+            &path,
+            "[speakers.aliases]\n\"John Doe\" = [\"jdoe@corp.com\", \"John D\"]\n",
+        )
+        .unwrap();
+        let aliases = SpeakerAliases::load(&path).unwrap();
+        assert_eq!(aliases.apply("john d"), "John Doe");
+        assert_eq!(aliases.apply("JOHN DOE"), "John Doe");
+        assert_eq!(aliases.apply("J. Doe <JDoe@corp.com>"), "John Doe");
+        assert_eq!(aliases.apply("Alice"), "Alice");
+        assert_eq!(
+            aliases.with_canonical(&["jdoe@corp.com".into(), "Alice".into()]),
+            vec!["jdoe@corp.com", "Alice", "John Doe"]
+        );
+
+        std::fs::write(
+            &path,
+            "[speakers.aliases]\n\"John Doe\" = [\"JD\"]\n\"Jane Doe\" = [\"jd\"]\n",
+        )
+        .unwrap();
+        let err = SpeakerAliases::load(&path).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("'JD' is listed for both 'Jane Doe' and 'John Doe'"));
+    }
+}
@@ -0,0 +1,424 @@
+// ABOUTME: Aggregate statistics computed from synced transcripts
+// ABOUTME: Per-organization meeting counts, per-meeting dead time and meeting load over a period
+
+use crate::gaps::{find_gaps, transcript_span, GAP_THRESHOLD_SECS};
+use crate::output::Row;
+use crate::person::{tally, Tally};
+use crate::storage::scan_transcripts;
+use crate::util::organizations_from_participants;
+use crate::{Frontmatter, Paths, RawTranscript, Result};
+use chrono::{Datelike, Days, NaiveDate};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Meeting counts for one organization (participant email domain)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrgStats {
+    pub organization: String,
+    pub meetings: usize,
+    pub first_meeting: NaiveDate,
+    pub last_meeting: NaiveDate,
+}
+
+impl Row for OrgStats {
+    fn columns() -> &'static [&'static str] {
+        &["organization", "meetings", "first_meeting", "last_meeting"]
+    }
+
+    fn value(&self, column: &str) -> Value {
+        match column {
+            "organization" => Value::from(self.organization.clone()),
+            "meetings" => Value::from(self.meetings),
+            "first_meeting" => Value::from(self.first_meeting.to_string()),
+            "last_meeting" => Value::from(self.last_meeting.to_string()),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Groups meetings by organization, most meetings first (ties by name)
+pub fn organization_stats<'a, I>(frontmatters: I) -> Vec<OrgStats>
+where
+    I: IntoIterator<Item = &'a Frontmatter>,
+{
+    let mut by_org: BTreeMap<String, OrgStats> = BTreeMap::new();
+
+    for fm in frontmatters {
+        let date = fm.created_at.date_naive();
+        // Older files have no organizations field; derive it from participants
+        let orgs = if fm.organizations.is_empty() {
+            organizations_from_participants(&fm.participants)
+        } else {
+            fm.organizations.clone()
+        };
+
+        for org in orgs {
+            let entry = by_org.entry(org.clone()).or_insert(OrgStats {
+                organization: org,
+                meetings: 0,
+                first_meeting: date,
+                last_meeting: date,
+            });
+            entry.meetings += 1;
+            entry.first_meeting = entry.first_meeting.min(date);
+            entry.last_meeting = entry.last_meeting.max(date);
+        }
+    }
+
+    let mut stats: Vec<OrgStats> = by_org.into_values().collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.meetings));
+    stats
+}
+
+/// Duration and dead time of one meeting
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeetingTime {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub date: NaiveDate,
+    /// Recorded duration, or the transcript's span when none was recorded
+    pub duration_seconds: Option<u64>,
+    /// Total of silences of at least two minutes between entries
+    pub dead_seconds: u64,
+    pub gaps: usize,
+}
+
+impl MeetingTime {
+    /// Dead time as a percentage of the duration
+    pub fn dead_share(&self) -> Option<f64> {
+        self.duration_seconds
+            .filter(|&d| d > 0)
+            .map(|d| (self.dead_seconds as f64 / d as f64 * 100.0).min(100.0))
+    }
+}
+
+impl Row for MeetingTime {
+    fn columns() -> &'static [&'static str] {
+        &[
+            "date",
+            "title",
+            "duration_min",
+            "dead_min",
+            "dead_pct",
+            "gaps",
+            "doc_id",
+        ]
+    }
+
+    fn value(&self, column: &str) -> Value {
+        match column {
+            "date" => Value::from(self.date.to_string()),
+            "title" => Value::from(self.title.clone()),
+            "duration_min" => Value::from(self.duration_seconds.map(|d| d / 60)),
+            "dead_min" => Value::from(self.dead_seconds / 60),
+            "dead_pct" => Value::from(self.dead_share().map(|p| (p * 10.0).round() / 10.0)),
+            "gaps" => Value::from(self.gaps),
+            "doc_id" => Value::from(self.doc_id.clone()),
+            _ => Value::Null,
+        }
+    }
+}
+
+/// Dead time of one meeting from its transcript entries
+pub fn meeting_time(fm: &Frontmatter, raw: &RawTranscript) -> MeetingTime {
+    let gaps = find_gaps(&raw.entries, GAP_THRESHOLD_SECS);
+    MeetingTime {
+        doc_id: fm.doc_id.clone(),
+        title: fm.title.clone(),
+        date: fm.created_at.date_naive(),
+        duration_seconds: fm
+            .duration_seconds
+            .or_else(|| transcript_span(&raw.entries)),
+        dead_seconds: gaps.iter().map(|g| g.seconds).sum(),
+        gaps: gaps.len(),
+    }
+}
+
+/// Dead time of a transcript from the raw JSON saved next to it, if there is one
+fn load_meeting_time(paths: &Paths, path: &Path, fm: &Frontmatter) -> Option<MeetingTime> {
+    let stem = path.file_stem()?;
+    let raw_path = paths
+        .raw_dir
+        .join(format!("{}.json", stem.to_string_lossy()));
+    let json = std::fs::read_to_string(&raw_path).ok()?;
+    match serde_json::from_str::<RawTranscript>(&json) {
+        Ok(raw) => Some(meeting_time(fm, &raw)),
+        Err(e) => {
+            eprintln!("Warning: Skipping {}: {}", raw_path.display(), e);
+            None
+        }
+    }
+}
+
+/// Dead time for every meeting with a raw transcript, largest share first
+///
+/// Timestamps come from the raw JSON saved next to each transcript; meetings
+/// without one are skipped.
+pub fn meeting_times(paths: &Paths) -> Result<Vec<MeetingTime>> {
+    let mut times = Vec::new();
+
+    for (path, fm) in scan_transcripts(&paths.transcripts_dir)? {
+        if let Some(time) = load_meeting_time(paths, &path, &fm) {
+            times.push(time);
+        }
+    }
+
+    times.sort_by(|a, b| {
+        b.dead_share()
+            .unwrap_or(0.0)
+            .total_cmp(&a.dead_share().unwrap_or(0.0))
+            .then_with(|| b.dead_seconds.cmp(&a.dead_seconds))
+            .then_with(|| b.date.cmp(&a.date))
+    });
+    Ok(times)
+}
+
+/// Meetings and hours in one week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WeekLoad {
+    /// The Monday the week starts on
+    pub week_start: NaiveDate,
+    pub meetings: usize,
+    pub hours: f64,
+}
+
+/// How much time went to meetings over a period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Overview {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub meetings: usize,
+    /// Sum of durations: recorded, or the transcript's span when none was recorded
+    pub hours: f64,
+    /// Meetings with neither, counted in `meetings` but not in `hours`
+    pub untimed: usize,
+    /// Long silences within those hours
+    pub dead_hours: f64,
+    /// Weeks with at least one meeting, oldest first
+    pub weeks: Vec<WeekLoad>,
+    /// Participants in the most meetings
+    pub top_participants: Vec<Tally>,
+}
+
+fn hours(seconds: u64) -> f64 {
+    (seconds as f64 / 36.0).round() / 100.0
+}
+
+/// Totals meetings between `since` and `until` (both inclusive)
+///
+/// `top` caps the participant list.
+pub fn overview_of(
+    meetings: &[(Frontmatter, MeetingTime)],
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    top: usize,
+) -> Overview {
+    let in_period: Vec<&(Frontmatter, MeetingTime)> = meetings
+        .iter()
+        .filter(|(_, time)| {
+            since.map_or(true, |s| time.date >= s) && until.map_or(true, |u| time.date <= u)
+        })
+        .collect();
+
+    let mut weeks: BTreeMap<NaiveDate, (usize, u64)> = BTreeMap::new();
+    for (_, time) in &in_period {
+        let monday = time.date - Days::new(time.date.weekday().num_days_from_monday() as u64);
+        let week = weeks.entry(monday).or_default();
+        week.0 += 1;
+        week.1 += time.duration_seconds.unwrap_or(0);
+    }
+
+    // Count each participant once per meeting
+    let participants = in_period.iter().flat_map(|(fm, _)| {
+        let mut present: Vec<&String> = fm.participants.iter().collect();
+        present.sort();
+        present.dedup();
+        present
+    });
+
+    Overview {
+        since,
+        until,
+        meetings: in_period.len(),
+        hours: hours(
+            in_period
+                .iter()
+                .filter_map(|(_, t)| t.duration_seconds)
+                .sum(),
+        ),
+        untimed: in_period
+            .iter()
+            .filter(|(_, t)| t.duration_seconds.is_none())
+            .count(),
+        dead_hours: hours(in_period.iter().map(|(_, t)| t.dead_seconds).sum()),
+        weeks: weeks
+            .into_iter()
+            .map(|(week_start, (meetings, seconds))| WeekLoad {
+                week_start,
+                meetings,
+                hours: hours(seconds),
+            })
+            .collect(),
+        top_participants: tally(participants, top),
+    }
+}
+
+/// Every synced transcript with its times
+///
+/// Unlike `meeting_times`, meetings without raw JSON are included, timed by
+/// their recorded duration alone.
+pub fn meetings_with_times(paths: &Paths) -> Result<Vec<(Frontmatter, MeetingTime)>> {
+    Ok(scan_transcripts(&paths.transcripts_dir)?
+        .into_iter()
+        .map(|(path, fm)| {
+            let time = load_meeting_time(paths, &path, &fm).unwrap_or_else(|| MeetingTime {
+                doc_id: fm.doc_id.clone(),
+                title: fm.title.clone(),
+                date: fm.created_at.date_naive(),
+                duration_seconds: fm.duration_seconds,
+                dead_seconds: 0,
+                gaps: 0,
+            });
+            (fm, time)
+        })
+        .collect())
+}
+
+/// Meeting load across every synced transcript between `since` and `until`
+pub fn overview(
+    paths: &Paths,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    top: usize,
+) -> Result<Overview> {
+    Ok(overview_of(&meetings_with_times(paths)?, since, until, top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fm(created_at: &str, participants: &[&str], organizations: &[&str]) -> Frontmatter {
+        Frontmatter {
+            doc_id: "doc".into(),
+            source: "granola".into(),
+            created_at: created_at.parse().unwrap(),
+            remote_updated_at: None,
+            title: None,
+            participants: participants.iter().map(|s| s.to_string()).collect(),
+            duration_seconds: None,
+            labels: vec![],
+            organizations: organizations.iter().map(|s| s.to_string()).collect(),
+            inferred_speakers: vec![],
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        }
+    }
+
+    #[test]
+    fn test_organization_stats() {
+        let docs = vec![
+            fm("2025-01-10T10:00:00Z", &[], &["acme.com"]),
+            fm("2025-03-01T10:00:00Z", &[], &["acme.com", "vendor.io"]),
+            // Legacy file: organizations derived from participants
+            fm("2024-12-01T10:00:00Z", &["Ann <ann@acme.com>"], &[]),
+        ];
+
+        let stats = organization_stats(&docs);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].organization, "acme.com");
+        assert_eq!(stats[0].meetings, 3);
+        assert_eq!(stats[0].first_meeting.to_string(), "2024-12-01");
+        assert_eq!(stats[0].last_meeting.to_string(), "2025-03-01");
+        assert_eq!(stats[1].organization, "vendor.io");
+        assert_eq!(stats[1].meetings, 1);
+    }
+
+    #[test]
+    fn test_meeting_time() {
+        let raw: RawTranscript = serde_json::from_str(
+            r#"[
+                {"start_timestamp": "2025-10-01T10:00:00Z", "end_timestamp": "2025-10-01T10:01:00Z", "text": "a"},
+                {"start_timestamp": "2025-10-01T10:31:00Z", "end_timestamp": "2025-10-01T10:32:00Z", "text": "b"},
+                {"start_timestamp": "2025-10-01T10:33:00Z", "end_timestamp": "2025-10-01T10:40:00Z", "text": "c"}
+            ]"#,
+        )
+        .unwrap();
+
+        let mut doc = fm("2025-10-01T10:00:00Z", &[], &[]);
+        let time = meeting_time(&doc, &raw);
+        assert_eq!(time.duration_seconds, Some(2400));
+        assert_eq!(time.dead_seconds, 1800);
+        assert_eq!(time.gaps, 1);
+        assert_eq!(time.value("dead_pct"), Value::from(75.0));
+
+        doc.duration_seconds = Some(3600);
+        let time = meeting_time(&doc, &raw);
+        assert_eq!(time.value("duration_min"), Value::from(60));
+        assert_eq!(time.value("dead_min"), Value::from(30));
+        assert_eq!(time.value("dead_pct"), Value::from(50.0));
+    }
+
+    #[test]
+    fn test_overview() {
+        let meeting = |created_at: &str, participants: &[&str], minutes: Option<u64>| {
+            let mut doc = fm(created_at, participants, &[]);
+            doc.duration_seconds = minutes.map(|m| m * 60);
+            let time = MeetingTime {
+                doc_id: doc.doc_id.clone(),
+                title: None,
+                date: doc.created_at.date_naive(),
+                duration_seconds: doc.duration_seconds,
+                dead_seconds: 600,
+                gaps: 1,
+            };
+            (doc, time)
+        };
+        let meetings = vec![
+            // Wednesday and Sunday of the same week
+            meeting("2025-10-01T10:00:00Z", &["Alice", "Bob", "Alice"], Some(30)),
+            meeting("2025-10-05T10:00:00Z", &["Bob"], Some(60)),
+            meeting("2025-10-06T10:00:00Z", &["Carol"], None),
+            meeting("2025-09-01T10:00:00Z", &["Bob"], Some(45)),
+        ];
+
+        let since = NaiveDate::from_ymd_opt(2025, 10, 1);
+        let overview = overview_of(&meetings, since, None, 1);
+        assert_eq!(overview.meetings, 3);
+        assert_eq!(overview.hours, 1.5);
+        assert_eq!(overview.untimed, 1);
+        assert_eq!(overview.dead_hours, 0.5);
+        assert_eq!(
+            overview.weeks,
+            vec![
+                WeekLoad {
+                    week_start: NaiveDate::from_ymd_opt(2025, 9, 29).unwrap(),
+                    meetings: 2,
+                    hours: 1.5,
+                },
+                WeekLoad {
+                    week_start: NaiveDate::from_ymd_opt(2025, 10, 6).unwrap(),
+                    meetings: 1,
+                    hours: 0.0,
+                },
+            ]
+        );
+        assert_eq!(
+            overview.top_participants,
+            vec![Tally {
+                name: "Bob".into(),
+                meetings: 2
+            }]
+        );
+
+        let until = NaiveDate::from_ymd_opt(2025, 9, 30);
+        assert_eq!(overview_of(&meetings, None, until, 5).meetings, 1);
+    }
+}
@@ -0,0 +1,711 @@
+// ABOUTME: XDG-compliant storage layer with atomic writes
+// ABOUTME: Handles paths, permissions, and frontmatter parsing
+
+use crate::profile::Profile;
+use crate::{Error, Frontmatter, RawTranscript, Result};
+use chrono::{DateTime, Utc};
+use filetime::FileTime;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub mod catalog;
+
+pub use catalog::{Catalog, CatalogEntry};
+
+pub struct Paths {
+    pub data_dir: PathBuf,
+    /// `config.toml`, with a table of settings per subsystem
+    pub config_file: PathBuf,
+    pub raw_dir: PathBuf,
+    pub transcripts_dir: PathBuf,
+    pub summaries_dir: PathBuf,
+    /// Granola's own notes for each meeting, beside the transcripts
+    pub notes_dir: PathBuf,
+    pub timings_dir: PathBuf,
+    pub index_dir: PathBuf,
+    pub models_dir: PathBuf,
+    pub tmp_dir: PathBuf,
+}
+
+impl Paths {
+    /// Paths under `data_dir_override`, else the profile's data directory, else the default
+    pub fn new(data_dir_override: Option<PathBuf>, profile: Option<&Profile>) -> Result<Self> {
+        let data_dir = match (data_dir_override, profile) {
+            (Some(dir), _) => dir,
+            (None, Some(profile)) => profile.data_dir()?,
+            (None, None) => default_data_dir()?,
+        };
+
+        Ok(Paths {
+            config_file: data_dir.join(crate::config::FILE_NAME),
+            raw_dir: data_dir.join("raw"),
+            transcripts_dir: data_dir.join("transcripts"),
+            summaries_dir: data_dir.join("summaries"),
+            notes_dir: data_dir.join("notes"),
+            timings_dir: data_dir.join("timings"),
+            index_dir: data_dir.join("index").join("tantivy"),
+            models_dir: data_dir.join("models"),
+            tmp_dir: data_dir.join("tmp"),
+            data_dir,
+        })
+    }
+
+    pub fn ensure_dirs(&self) -> Result<()> {
+        for dir in &[
+            &self.raw_dir,
+            &self.transcripts_dir,
+            &self.summaries_dir,
+            &self.notes_dir,
+            &self.timings_dir,
+            &self.index_dir,
+            &self.models_dir,
+            &self.tmp_dir,
+        ] {
+            fs::create_dir_all(dir)?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let perms = fs::Permissions::from_mode(0o700);
+                fs::set_permissions(dir, perms)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `muesli` in the platform's data directory, used when no other directory is given
+pub fn default_data_dir() -> Result<PathBuf> {
+    data_base_dir(env::consts::OS, |key| env::var(key).ok())
+        .map(|base| base.join("muesli"))
+        .ok_or_else(|| {
+            Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Could not determine home directory (HOME not set)",
+            ))
+        })
+}
+
+/// Resolves the base data directory for the given OS.
+///
+/// XDG Base Directory spec: use $XDG_DATA_HOME or fall back to ~/.local/share.
+/// On Windows, %LOCALAPPDATA% (then %APPDATA%) is used when XDG_DATA_HOME is unset.
+pub fn data_base_dir<F>(os: &str, get_env: F) -> Option<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(xdg_data) = get_env("XDG_DATA_HOME") {
+        return Some(PathBuf::from(xdg_data));
+    }
+
+    if os == "windows" {
+        if let Some(dir) = get_env("LOCALAPPDATA").or_else(|| get_env("APPDATA")) {
+            return Some(PathBuf::from(dir));
+        }
+    }
+
+    get_env("HOME")
+        .or_else(|| get_env("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".local").join("share"))
+}
+
+pub fn write_atomic(path: &Path, content: &[u8], tmp_dir: &Path) -> Result<()> {
+    use rand::Rng;
+
+    // Create temp file
+    let random: u32 = rand::thread_rng().gen();
+    let tmp_path = tmp_dir.join(format!("{:x}.part", random));
+    let marker_path = tmp_path.with_extension("dest");
+
+    // Write to temp
+    write_synced(&tmp_path, content)?;
+
+    // Set permissions
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let perms = fs::Permissions::from_mode(0o600);
+        fs::set_permissions(&tmp_path, perms)?;
+    }
+
+    // Atomic rename
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // The marker is the commit point: a .part without one was never fully written.
+    // It also records what it replaces, so recovery never undoes a later write.
+    // It isn't synced: losing it to a crash only drops this write, like crashing before it.
+    let marker = format!("{}\n{}", path.to_string_lossy(), fingerprint(path)?);
+    fs::write(&marker_path, marker)?;
+    replace_file(&tmp_path, path)?;
+    fs::remove_file(&marker_path)?;
+
+    Ok(())
+}
+
+/// Writes `content` and flushes it to disk before returning
+fn write_synced(path: &Path, content: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = fs::File::create(path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// A file's size and modification time, or "-" when there is no file
+///
+/// Enough to tell whether it was written since, without reading it.
+fn fingerprint(path: &Path) -> Result<String> {
+    match fs::metadata(path) {
+        Ok(meta) => {
+            let modified = meta
+                .modified()?
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            Ok(format!("{} {}", meta.len(), modified))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok("-".to_string()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Moves `from` over `to` in one step, replacing any file already there
+///
+/// On Windows `fs::rename` is `MoveFileExW` with `MOVEFILE_REPLACE_EXISTING`, so
+/// there is never a moment without a file at `to`.
+fn replace_file(from: &Path, to: &Path) -> Result<()> {
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+/// Temp files younger than this may belong to a write still in progress
+pub const PARTIAL_WRITE_GRACE: Duration = Duration::from_secs(5 * 60);
+
+/// Outcome of cleaning up after interrupted `write_atomic` calls
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Recovery {
+    /// Fully written temp files moved to their destination
+    pub completed: usize,
+    /// Incomplete temp files deleted
+    pub removed: usize,
+    /// Complete temp files deleted because their destination was written again since
+    pub superseded: usize,
+}
+
+/// Completes or discards `.part` files left in the temp directory by a crashed run
+///
+/// A `.part` with a `.dest` marker was fully written and is renamed into place,
+/// as long as the marker names a file inside the data directory and that file
+/// is missing or still what the marker says it replaced. A later run that
+/// rewrote the file wins, and the `.part` is deleted. One without a valid marker may be truncated
+/// and is deleted. Files newer than `min_age` are left alone in case another
+/// muesli process is writing them.
+pub fn recover_partial_writes(paths: &Paths, min_age: Duration) -> Result<Recovery> {
+    let mut recovery = Recovery::default();
+    let tmp_dir = &paths.tmp_dir;
+    if !tmp_dir.exists() {
+        return Ok(recovery);
+    }
+    let data_dir = fs::canonicalize(&paths.data_dir)?;
+
+    let is_stale = |path: &Path| {
+        fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= min_age)
+    };
+
+    for entry in fs::read_dir(tmp_dir)? {
+        let path = entry?.path();
+        if !path.exists() || !is_stale(&path) {
+            continue;
+        }
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("part") => {
+                let marker = path.with_extension("dest");
+                let content = fs::read_to_string(&marker).unwrap_or_default();
+                let destination = content.split_once('\n').and_then(|(dest, replaced)| {
+                    Some((inside(&data_dir, Path::new(dest))?, replaced.to_string()))
+                });
+                match destination {
+                    // A missing destination has nothing newer to lose
+                    Some((destination, replaced))
+                        if !destination.exists() || fingerprint(&destination)? == replaced =>
+                    {
+                        replace_file(&path, &destination)?;
+                        recovery.completed += 1;
+                    }
+                    Some(_) => {
+                        fs::remove_file(&path)?;
+                        recovery.superseded += 1;
+                    }
+                    None => {
+                        fs::remove_file(&path)?;
+                        recovery.removed += 1;
+                    }
+                }
+                if marker.exists() {
+                    fs::remove_file(&marker)?;
+                }
+            }
+            // The rename happened but the marker was never cleaned up
+            Some("dest") if !path.with_extension("part").exists() => {
+                fs::remove_file(&path)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(recovery)
+}
+
+/// `path` with its directory resolved, if that lies inside `dir` (itself canonical)
+fn inside(dir: &Path, path: &Path) -> Option<PathBuf> {
+    let parent = fs::canonicalize(path.parent()?).ok()?;
+    let resolved = parent.join(path.file_name()?);
+    resolved.starts_with(dir).then_some(resolved)
+}
+
+/// Set file modification time to match a given datetime
+pub fn set_file_time(path: &Path, datetime: &DateTime<Utc>) -> Result<()> {
+    let timestamp = datetime.timestamp();
+    let filetime = FileTime::from_unix_time(timestamp, 0);
+    filetime::set_file_mtime(path, filetime).map_err(|e| {
+        Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Failed to set file time: {}", e),
+        ))
+    })
+}
+
+/// A synced document's catalog entry and the raw JSON saved next to its transcript
+pub fn load_raw_transcript(paths: &Paths, doc_id: &str) -> Result<(CatalogEntry, RawTranscript)> {
+    let entry = Catalog::open(paths)?.entry_for(doc_id)?;
+    let stem = entry
+        .path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let raw_path = paths.raw_dir.join(format!("{}.json", stem));
+    let json = fs::read_to_string(&raw_path).map_err(|e| {
+        Error::Filesystem(std::io::Error::new(
+            e.kind(),
+            format!(
+                "Cannot read {} (run 'muesli fetch {}'): {}",
+                raw_path.display(),
+                doc_id,
+                e
+            ),
+        ))
+    })?;
+    Ok((entry, serde_json::from_str(&json)?))
+}
+
+pub fn read_frontmatter(md_path: &Path) -> Result<Option<Frontmatter>> {
+    if !md_path.exists() {
+        return Ok(None);
+    }
+
+    parse_frontmatter(&fs::read_to_string(md_path)?)
+}
+
+/// The frontmatter of a transcript already read into memory
+pub fn parse_frontmatter(content: &str) -> Result<Option<Frontmatter>> {
+    // Files edited on Windows may carry CRLF line endings
+    let content = content.replace("\r\n", "\n");
+
+    // Look for YAML frontmatter (--- ... ---)
+    if !content.starts_with("---\n") {
+        return Ok(None);
+    }
+
+    if content.len() < 4 {
+        return Ok(None);
+    }
+    let rest = &content[4..];
+    if let Some(end_pos) = rest.find("\n---\n") {
+        let yaml = &rest[..end_pos];
+        let fm: Frontmatter = serde_yaml::from_str(yaml).map_err(|e| {
+            Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to parse frontmatter: {}", e),
+            ))
+        })?;
+        Ok(Some(fm))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Returns the markdown body with any leading YAML frontmatter removed
+pub fn strip_frontmatter(content: &str) -> &str {
+    if let Some(rest) = content.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---\n") {
+            return rest[end + 5..].trim_start_matches('\n');
+        }
+    }
+    content
+}
+
+/// Replaces a transcript's frontmatter, keeping its body and modification time
+pub fn write_frontmatter(md_path: &Path, fm: &Frontmatter, tmp_dir: &Path) -> Result<()> {
+    let content = fs::read_to_string(md_path)?.replace("\r\n", "\n");
+    let yaml = serde_yaml::to_string(fm).map_err(|e| {
+        Error::Filesystem(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Failed to serialize frontmatter: {}", e),
+        ))
+    })?;
+
+    let updated = format!("---\n{}---\n\n{}", yaml, strip_frontmatter(&content));
+    write_atomic(md_path, updated.as_bytes(), tmp_dir)?;
+    set_file_time(md_path, &fm.created_at)
+}
+
+/// Reads the frontmatter of every markdown file in a directory
+///
+/// Files without parseable frontmatter are skipped. Results are sorted by path.
+pub fn scan_transcripts(dir: &Path) -> Result<Vec<(PathBuf, Frontmatter)>> {
+    let mut found = Vec::new();
+
+    if !dir.exists() {
+        return Ok(found);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+        if let Ok(Some(fm)) = read_frontmatter(&path) {
+            found.push((path, fm));
+        }
+    }
+
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(found)
+}
+
+/// The synced documents as `list` shows them, from transcript frontmatter
+///
+/// Newest first, leaving out hidden duplicates; needs no token or network.
+pub fn list_local(paths: &Paths) -> Result<Vec<crate::DocumentSummary>> {
+    let mut docs: Vec<_> = scan_transcripts(&paths.transcripts_dir)?
+        .into_iter()
+        .filter(|(_, fm)| fm.duplicate_of.is_none())
+        .map(|(_, fm)| crate::DocumentSummary {
+            id: fm.doc_id,
+            title: fm.title,
+            created_at: fm.created_at,
+            updated_at: fm.remote_updated_at,
+        })
+        .collect();
+    docs.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+    Ok(docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_local_reads_frontmatter_newest_first() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        for (name, doc_id, date, extra) in [
+            ("a", "d1", "2025-01-01", ""),
+            (
+                "b",
+                "d2",
+                "2025-02-01",
+                "remote_updated_at: 2025-02-02T00:00:00Z\n",
+            ),
+            ("c", "d3", "2025-03-01", "duplicate_of: d2\n"),
+        ] {
+            fs::write(
+                paths.transcripts_dir.join(format!("{}.md", name)),
+                format!(
+                    "---\ndoc_id: {}\nsource: granola\ncreated_at: {}T10:00:00Z\n{}title: {}\ngenerator: muesli 1.0\n---\n\nbody\n",
+                    doc_id, date, extra, name
+                ),
+            )
+            .unwrap();
+        }
+
+        let docs = list_local(&paths).unwrap();
+        let ids: Vec<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["d2", "d1"]);
+        assert_eq!(docs[0].title.as_deref(), Some("b"));
+        assert!(docs[0].updated_at.is_some());
+    }
+
+    #[test]
+    fn test_paths_new_with_override() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        assert_eq!(paths.data_dir, temp.path());
+        assert_eq!(paths.raw_dir, temp.path().join("raw"));
+    }
+
+    #[test]
+    fn test_data_base_dir_xdg_wins() {
+        let base = data_base_dir("windows", |k| match k {
+            "XDG_DATA_HOME" => Some("/xdg".into()),
+            "LOCALAPPDATA" => Some("C:\\Local".into()),
+            _ => None,
+        });
+        assert_eq!(base, Some(PathBuf::from("/xdg")));
+    }
+
+    #[test]
+    fn test_data_base_dir_windows() {
+        let base = data_base_dir("windows", |k| match k {
+            "APPDATA" => Some("C:\\Roaming".into()),
+            _ => None,
+        });
+        assert_eq!(base, Some(PathBuf::from("C:\\Roaming")));
+    }
+
+    #[test]
+    fn test_data_base_dir_unix_home() {
+        let base = data_base_dir("linux", |k| (k == "HOME").then(|| "/home/a".to_string()));
+        assert_eq!(base, Some(PathBuf::from("/home/a/.local/share")));
+        assert_eq!(data_base_dir("linux", |_| None), None);
+    }
+
+    #[test]
+    fn test_ensure_dirs_creates_structure() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        assert!(paths.raw_dir.exists());
+        assert!(paths.transcripts_dir.exists());
+        assert!(paths.tmp_dir.exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_ensure_dirs_sets_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let perms = fs::metadata(&paths.raw_dir).unwrap().permissions();
+        assert_eq!(
+            perms.mode() & 0o777,
+            0o700,
+            "raw_dir should have 0o700 permissions"
+        );
+
+        let perms = fs::metadata(&paths.transcripts_dir).unwrap().permissions();
+        assert_eq!(
+            perms.mode() & 0o777,
+            0o700,
+            "transcripts_dir should have 0o700 permissions"
+        );
+    }
+}
+
+#[cfg(test)]
+mod write_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_creates_file() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let target = temp.path().join("test.txt");
+        write_atomic(&target, b"hello", &paths.tmp_dir).unwrap();
+
+        assert!(target.exists());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "hello");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_write_atomic_sets_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let target = temp.path().join("test.txt");
+        write_atomic(&target, b"hello", &paths.tmp_dir).unwrap();
+
+        let perms = fs::metadata(&target).unwrap().permissions();
+        assert_eq!(perms.mode() & 0o777, 0o600);
+    }
+}
+
+#[cfg(test)]
+mod frontmatter_tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_leaves_no_temp_files() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        write_atomic(&paths.transcripts_dir.join("a.md"), b"hi", &paths.tmp_dir).unwrap();
+        assert_eq!(fs::read_dir(&paths.tmp_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_recover_partial_writes() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        let target = paths.transcripts_dir.join("done.md");
+
+        let marker = |dest: &Path| format!("{}\n{}", dest.display(), fingerprint(dest).unwrap());
+
+        // Crashed after the commit marker: finish the rename
+        fs::write(paths.tmp_dir.join("a.part"), "complete").unwrap();
+        fs::write(paths.tmp_dir.join("a.dest"), marker(&target)).unwrap();
+        // Crashed after the commit marker, then a later run rewrote the file: keep the newer one
+        let rewritten = paths.transcripts_dir.join("rewritten.md");
+        fs::write(&rewritten, "before").unwrap();
+        fs::write(paths.tmp_dir.join("e.part"), "stale").unwrap();
+        fs::write(paths.tmp_dir.join("e.dest"), marker(&rewritten)).unwrap();
+        fs::write(&rewritten, "newer").unwrap();
+        // Crashed after the commit marker, with the old file already gone: finish the write
+        let gone = paths.transcripts_dir.join("gone.md");
+        fs::write(&gone, "old").unwrap();
+        fs::write(paths.tmp_dir.join("f.part"), "replacement").unwrap();
+        fs::write(paths.tmp_dir.join("f.dest"), marker(&gone)).unwrap();
+        fs::remove_file(&gone).unwrap();
+        // Crashed mid-write: discard
+        fs::write(paths.tmp_dir.join("b.part"), "trunc").unwrap();
+        // Crashed after the rename: stale marker only
+        fs::write(paths.tmp_dir.join("c.dest"), "whatever").unwrap();
+        // A marker pointing out of the data directory is never followed
+        let outside = temp
+            .path()
+            .join("transcripts")
+            .join("..")
+            .join("..")
+            .join("escaped.md");
+        fs::write(paths.tmp_dir.join("d.part"), "planted").unwrap();
+        fs::write(paths.tmp_dir.join("d.dest"), marker(&outside)).unwrap();
+
+        // Fresh files might belong to a running sync
+        let recovery = recover_partial_writes(&paths, Duration::from_secs(3600)).unwrap();
+        assert_eq!(recovery, Recovery::default());
+
+        let recovery = recover_partial_writes(&paths, Duration::ZERO).unwrap();
+        assert_eq!(
+            recovery,
+            Recovery {
+                completed: 2,
+                removed: 2,
+                superseded: 1
+            }
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "complete");
+        assert_eq!(fs::read_to_string(&gone).unwrap(), "replacement");
+        assert_eq!(fs::read_to_string(&rewritten).unwrap(), "newer");
+        assert!(!outside.exists());
+        assert_eq!(fs::read_dir(&paths.tmp_dir).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_strip_frontmatter() {
+        assert_eq!(
+            strip_frontmatter("---\ndoc_id: x\n---\n\n# Title\nbody"),
+            "# Title\nbody"
+        );
+        assert_eq!(strip_frontmatter("# No frontmatter"), "# No frontmatter");
+    }
+
+    #[test]
+    fn test_read_frontmatter_valid() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("test.md");
+
+        let content = r#"---
+doc_id: "doc123"
+source: "granola"
+created_at: "2025-10-28T15:04:05Z"
+title: "Test"
+participants: []
+generator: "muesli 1.0"
+---
+
+# Test Meeting
+"#;
+        fs::write(&md_path, content).unwrap();
+
+        let fm = read_frontmatter(&md_path).unwrap();
+        assert!(fm.is_some());
+        assert_eq!(fm.unwrap().doc_id, "doc123");
+    }
+
+    #[test]
+    fn test_write_frontmatter_keeps_body() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("test.md");
+        fs::write(
+            &md_path,
+            "---\ndoc_id: doc123\nsource: granola\ncreated_at: 2025-10-28T15:04:05Z\ngenerator: muesli 1.0\n---\n\n# Test Meeting\n",
+        )
+        .unwrap();
+
+        let mut fm = read_frontmatter(&md_path).unwrap().unwrap();
+        fm.duplicate_of = Some("doc000".into());
+        write_frontmatter(&md_path, &fm, temp.path()).unwrap();
+
+        let content = fs::read_to_string(&md_path).unwrap();
+        assert!(content.contains("\nduplicate_of: doc000\n"));
+        assert!(content.ends_with("---\n\n# Test Meeting\n"));
+        let reread = read_frontmatter(&md_path).unwrap().unwrap();
+        assert_eq!(reread.duplicate_of.as_deref(), Some("doc000"));
+    }
+
+    #[test]
+    fn test_read_frontmatter_crlf() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("crlf.md");
+
+        let content = "---\r\ndoc_id: doc_crlf\r\nsource: granola\r\ncreated_at: \"2025-10-28T15:04:05Z\"\r\ngenerator: muesli 1.0\r\n---\r\n\r\n# Body\r\n";
+        fs::write(&md_path, content).unwrap();
+
+        let fm = read_frontmatter(&md_path).unwrap().unwrap();
+        assert_eq!(fm.doc_id, "doc_crlf");
+    }
+
+    #[test]
+    fn test_read_frontmatter_missing_file() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("missing.md");
+
+        let fm = read_frontmatter(&md_path).unwrap();
+        assert!(fm.is_none());
+    }
+
+    #[test]
+    fn test_read_frontmatter_no_yaml() {
+        let temp = TempDir::new().unwrap();
+        let md_path = temp.path().join("test.md");
+        fs::write(&md_path, "# Just content").unwrap();
+
+        let fm = read_frontmatter(&md_path).unwrap();
+        assert!(fm.is_none());
+    }
+}
@@ -0,0 +1,586 @@
+// ABOUTME: SQLite catalog of synced transcripts keyed by document ID
+// ABOUTME: Replaces directory scans + frontmatter parsing for doc_id -> path lookups
+
+use super::{read_frontmatter, scan_transcripts, Paths};
+use crate::access::{Access, AccessPolicy};
+use crate::{Error, Frontmatter, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Bumped whenever the table layout changes
+const SCHEMA_VERSION: i64 = 4;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS documents (
+    doc_id        TEXT PRIMARY KEY,
+    path          TEXT NOT NULL,
+    title         TEXT,
+    created_at    TEXT NOT NULL,
+    participants  TEXT NOT NULL DEFAULT '[]',
+    labels        TEXT NOT NULL DEFAULT '[]',
+    organizations TEXT NOT NULL DEFAULT '[]',
+    content_hash  TEXT,
+    duplicate_of  TEXT,
+    merged        TEXT NOT NULL DEFAULT '[]',
+    access        INTEGER, -- 0 public, 1 team, 2 private; NULL when untagged
+    modified      INTEGER  -- file modification time in ms when recorded
+);
+CREATE INDEX IF NOT EXISTS documents_created_at ON documents (created_at);
+CREATE INDEX IF NOT EXISTS documents_content_hash ON documents (content_hash);
+";
+
+const COLUMNS: &str = "doc_id, path, title, created_at, participants, labels, organizations, \
+                       content_hash, duplicate_of, merged, access, modified";
+
+/// Metadata for one transcript, as stored in the catalog
+#[derive(Debug, Clone, PartialEq)]
+pub struct CatalogEntry {
+    pub doc_id: String,
+    pub path: PathBuf,
+    pub title: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub participants: Vec<String>,
+    pub labels: Vec<String>,
+    pub organizations: Vec<String>,
+    pub content_hash: Option<String>,
+    /// Set on a duplicate hidden by `muesli dedupe --hide`
+    pub duplicate_of: Option<String>,
+    /// Documents merged into this one by `muesli dedupe --merge`
+    pub merged_duplicates: Vec<String>,
+    /// The transcript's `access` tag, None when untagged
+    pub access: Option<Access>,
+    /// The file's modification time when it was recorded (see `file_modified`)
+    pub modified: Option<i64>,
+}
+
+impl CatalogEntry {
+    pub fn from_frontmatter(path: &Path, fm: &Frontmatter) -> Self {
+        Self {
+            doc_id: fm.doc_id.clone(),
+            path: path.to_path_buf(),
+            title: fm.title.clone(),
+            created_at: fm.created_at,
+            participants: fm.participants.clone(),
+            labels: fm.labels.clone(),
+            organizations: fm.organizations.clone(),
+            content_hash: fm.content_hash.clone(),
+            duplicate_of: fm.duplicate_of.clone(),
+            merged_duplicates: fm.merged_duplicates.clone(),
+            access: fm.access,
+            modified: file_modified(path),
+        }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        let created_at: String = row.get(3)?;
+        let list = |idx: usize| -> rusqlite::Result<Vec<String>> {
+            let json: String = row.get(idx)?;
+            Ok(serde_json::from_str(&json).unwrap_or_default())
+        };
+
+        Ok(Self {
+            doc_id: row.get(0)?,
+            path: PathBuf::from(row.get::<_, String>(1)?),
+            title: row.get(2)?,
+            created_at: created_at
+                .parse()
+                .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+            participants: list(4)?,
+            labels: list(5)?,
+            organizations: list(6)?,
+            content_hash: row.get(7)?,
+            duplicate_of: row.get(8)?,
+            merged_duplicates: list(9)?,
+            access: row.get::<_, Option<i64>>(10)?.and_then(access_from_rank),
+            modified: row.get(11)?,
+        })
+    }
+}
+
+/// A file's modification time in milliseconds, or None if it can't be read
+///
+/// A recorded time that no longer matches means the file was edited since,
+/// e.g. an `access` tag added by hand.
+pub fn file_modified(path: &Path) -> Option<i64> {
+    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let millis = match modified.duration_since(std::time::UNIX_EPOCH) {
+        Ok(after) => after.as_millis() as i64,
+        Err(before) => -(before.duration().as_millis() as i64),
+    };
+    Some(millis)
+}
+
+/// Stored as a number so SQL can compare levels; `Access` is ordered least restricted first
+fn access_rank(access: Access) -> i64 {
+    access as i64
+}
+
+fn access_from_rank(rank: i64) -> Option<Access> {
+    [Access::Public, Access::Team, Access::Private]
+        .into_iter()
+        .find(|&access| access_rank(access) == rank)
+}
+
+fn catalog_err(e: rusqlite::Error) -> Error {
+    Error::Catalog(e.to_string())
+}
+
+/// Persistent doc_id -> transcript metadata map, kept up to date by sync
+///
+/// The catalog is a cache of what is on disk: when it is empty or points at a
+/// file that no longer exists, it is rebuilt from the transcripts directory.
+pub struct Catalog {
+    conn: Connection,
+    transcripts_dir: PathBuf,
+}
+
+impl Catalog {
+    /// Opens (or creates) `catalog.sqlite` in the data directory
+    pub fn open(paths: &Paths) -> Result<Self> {
+        std::fs::create_dir_all(&paths.data_dir)?;
+        Self::open_at(
+            &paths.data_dir.join("catalog.sqlite"),
+            &paths.transcripts_dir,
+        )
+    }
+
+    /// Opens a catalog database at an explicit path
+    pub fn open_at(db_path: &Path, transcripts_dir: &Path) -> Result<Self> {
+        let conn = Connection::open(db_path).map_err(catalog_err)?;
+        // Sync and the MCP server may touch the catalog at the same time
+        conn.busy_timeout(Duration::from_secs(5))
+            .map_err(catalog_err)?;
+        // The catalog is only a cache, so an older layout is dropped and rebuilt from disk
+        let version: i64 = conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(catalog_err)?;
+        if version < SCHEMA_VERSION {
+            conn.execute_batch("DROP TABLE IF EXISTS documents")
+                .map_err(catalog_err)?;
+        }
+        conn.execute_batch(SCHEMA).map_err(catalog_err)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .map_err(catalog_err)?;
+
+        let catalog = Self {
+            conn,
+            transcripts_dir: transcripts_dir.to_path_buf(),
+        };
+
+        if catalog.is_empty()? {
+            catalog.rebuild()?;
+        }
+
+        Ok(catalog)
+    }
+
+    fn is_empty(&self) -> Result<bool> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM documents", [], |row| row.get(0))
+            .map_err(catalog_err)?;
+        Ok(count == 0)
+    }
+
+    /// Inserts or replaces the entry for a document
+    pub fn upsert(&self, entry: &CatalogEntry) -> Result<()> {
+        self.conn
+            .execute(
+                &format!(
+                    "INSERT OR REPLACE INTO documents ({}) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                    COLUMNS
+                ),
+                params![
+                    entry.doc_id,
+                    entry.path.to_string_lossy(),
+                    entry.title,
+                    entry.created_at.to_rfc3339(),
+                    serde_json::to_string(&entry.participants)?,
+                    serde_json::to_string(&entry.labels)?,
+                    serde_json::to_string(&entry.organizations)?,
+                    entry.content_hash,
+                    entry.duplicate_of,
+                    serde_json::to_string(&entry.merged_duplicates)?,
+                    entry.access.map(access_rank),
+                    entry.modified,
+                ],
+            )
+            .map_err(catalog_err)?;
+        Ok(())
+    }
+
+    /// Reads a transcript's frontmatter and records it; files without frontmatter are ignored
+    pub fn record_file(&self, md_path: &Path) -> Result<()> {
+        if let Some(fm) = read_frontmatter(md_path)? {
+            self.upsert(&CatalogEntry::from_frontmatter(md_path, &fm))?;
+        }
+        Ok(())
+    }
+
+    /// Records again the entries whose files changed since, returning how many did
+    ///
+    /// Files that are gone are left alone; `lookup` finds where they went.
+    pub fn refresh_changed(&self, entries: &[CatalogEntry]) -> Result<usize> {
+        let mut refreshed = 0;
+        for entry in entries {
+            let modified = file_modified(&entry.path);
+            if modified.is_some() && modified != entry.modified {
+                self.record_file(&entry.path)?;
+                refreshed += 1;
+            }
+        }
+        Ok(refreshed)
+    }
+
+    /// Removes a document, returning whether it was present
+    pub fn remove(&self, doc_id: &str) -> Result<bool> {
+        let removed = self
+            .conn
+            .execute("DELETE FROM documents WHERE doc_id = ?1", params![doc_id])
+            .map_err(catalog_err)?;
+        Ok(removed > 0)
+    }
+
+    fn get(&self, doc_id: &str) -> Result<Option<CatalogEntry>> {
+        self.conn
+            .query_row(
+                &format!("SELECT {} FROM documents WHERE doc_id = ?1", COLUMNS),
+                params![doc_id],
+                CatalogEntry::from_row,
+            )
+            .optional()
+            .map_err(catalog_err)
+    }
+
+    /// Finds a document by ID
+    ///
+    /// Misses and stale paths (files renamed or added outside sync) trigger one
+    /// rebuild from disk before giving up.
+    pub fn lookup(&self, doc_id: &str) -> Result<Option<CatalogEntry>> {
+        if let Some(entry) = self.get(doc_id)? {
+            if entry.path.exists() {
+                return Ok(Some(entry));
+            }
+        }
+
+        self.rebuild()?;
+        self.get(doc_id)
+    }
+
+    /// A document's catalog entry, or a not-found error
+    pub fn entry_for(&self, doc_id: &str) -> Result<CatalogEntry> {
+        self.lookup(doc_id)?.ok_or_else(|| {
+            Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("No transcript found for document ID: {}", doc_id),
+            ))
+        })
+    }
+
+    /// A document by ID, or by its transcript's file name with or without `.md`
+    pub fn find(&self, reference: &str) -> Result<CatalogEntry> {
+        if let Some(entry) = self.lookup(reference)? {
+            return Ok(entry);
+        }
+
+        let name = reference.strip_suffix(".md").unwrap_or(reference);
+        self.entries()?
+            .into_iter()
+            .find(|e| e.path.file_stem().is_some_and(|stem| stem == name))
+            .ok_or_else(|| {
+                Error::Filesystem(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "No transcript found for document ID or file name: {}",
+                        reference
+                    ),
+                ))
+            })
+    }
+
+    /// Path of a document's markdown file, or a not-found error
+    pub fn path_for(&self, doc_id: &str) -> Result<PathBuf> {
+        self.entry_for(doc_id).map(|e| e.path)
+    }
+
+    /// Every cataloged document, oldest first
+    pub fn entries(&self) -> Result<Vec<CatalogEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM documents ORDER BY created_at, doc_id",
+                COLUMNS
+            ))
+            .map_err(catalog_err)?;
+        let rows = stmt
+            .query_map([], CatalogEntry::from_row)
+            .map_err(catalog_err)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(catalog_err)
+    }
+
+    /// Documents the access policy lets through, oldest first
+    ///
+    /// Goes by the tags recorded at the last sync or reindex, not the files.
+    pub fn shared_entries(&self, policy: &AccessPolicy) -> Result<Vec<CatalogEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM documents WHERE COALESCE(access, ?1) <= ?2
+                 ORDER BY created_at, doc_id",
+                COLUMNS
+            ))
+            .map_err(catalog_err)?;
+        let rows = stmt
+            .query_map(
+                params![
+                    access_rank(policy.default_access),
+                    access_rank(policy.max_access)
+                ],
+                CatalogEntry::from_row,
+            )
+            .map_err(catalog_err)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(catalog_err)
+    }
+
+    /// Other documents with the same content hash, leaving out hidden duplicates
+    pub fn same_content(&self, doc_id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT doc_id FROM documents
+                 WHERE content_hash = (SELECT content_hash FROM documents WHERE doc_id = ?1)
+                   AND doc_id != ?1 AND duplicate_of IS NULL
+                 ORDER BY created_at, doc_id",
+            )
+            .map_err(catalog_err)?;
+        let rows = stmt
+            .query_map(params![doc_id], |row| row.get(0))
+            .map_err(catalog_err)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .map_err(catalog_err)
+    }
+
+    /// Replaces the catalog contents with a fresh scan of the transcripts directory
+    pub fn rebuild(&self) -> Result<usize> {
+        let transcripts = scan_transcripts(&self.transcripts_dir)?;
+
+        let tx = self.conn.unchecked_transaction().map_err(catalog_err)?;
+        tx.execute("DELETE FROM documents", [])
+            .map_err(catalog_err)?;
+        for (path, fm) in &transcripts {
+            self.upsert(&CatalogEntry::from_frontmatter(path, fm))?;
+        }
+        tx.commit().map_err(catalog_err)?;
+
+        Ok(transcripts.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_md(dir: &Path, name: &str, doc_id: &str, date: &str) -> PathBuf {
+        let path = dir.join(format!("{}.md", name));
+        let content = format!(
+            "---\ndoc_id: {}\nsource: granola\ncreated_at: {}T10:00:00Z\ntitle: {}\nparticipants: [\"ann@acme.com\"]\nlabels: [sales]\ngenerator: muesli 1.0\n---\n\nbody\n",
+            doc_id, date, name
+        );
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn setup() -> (TempDir, Paths) {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+        (temp, paths)
+    }
+
+    #[test]
+    fn test_open_builds_from_existing_transcripts() {
+        let (_temp, paths) = setup();
+        write_md(&paths.transcripts_dir, "later", "d2", "2025-03-01");
+        write_md(&paths.transcripts_dir, "earlier", "d1", "2025-01-01");
+
+        let catalog = Catalog::open(&paths).unwrap();
+        let entries = catalog.entries().unwrap();
+        let ids: Vec<&str> = entries.iter().map(|e| e.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["d1", "d2"]);
+        assert_eq!(entries[0].participants, vec!["ann@acme.com"]);
+        assert_eq!(entries[0].labels, vec!["sales"]);
+    }
+
+    #[test]
+    fn test_record_lookup_and_remove() {
+        let (_temp, paths) = setup();
+        let catalog = Catalog::open(&paths).unwrap();
+        assert!(catalog.lookup("d1").unwrap().is_none());
+
+        let path = write_md(&paths.transcripts_dir, "standup", "d1", "2025-01-01");
+        catalog.record_file(&path).unwrap();
+        let entry = catalog.lookup("d1").unwrap().unwrap();
+        assert_eq!(entry.path, path);
+        assert_eq!(entry.title.as_deref(), Some("standup"));
+
+        assert!(catalog.remove("d1").unwrap());
+        assert!(!catalog.remove("d1").unwrap());
+    }
+
+    #[test]
+    fn test_lookup_repairs_stale_path() {
+        let (_temp, paths) = setup();
+        let old = write_md(&paths.transcripts_dir, "old-title", "d1", "2025-01-01");
+        let catalog = Catalog::open(&paths).unwrap();
+
+        // Renamed outside of sync
+        std::fs::remove_file(&old).unwrap();
+        let new = write_md(&paths.transcripts_dir, "new-title", "d1", "2025-01-01");
+
+        assert_eq!(catalog.path_for("d1").unwrap(), new);
+        assert!(catalog.path_for("missing").is_err());
+    }
+
+    #[test]
+    fn test_find_by_id_or_file_name() {
+        let (_temp, paths) = setup();
+        let path = write_md(
+            &paths.transcripts_dir,
+            "2025-01-01_standup",
+            "d1",
+            "2025-01-01",
+        );
+        let catalog = Catalog::open(&paths).unwrap();
+
+        assert_eq!(catalog.find("d1").unwrap().path, path);
+        assert_eq!(catalog.find("2025-01-01_standup").unwrap().doc_id, "d1");
+        assert_eq!(catalog.find("2025-01-01_standup.md").unwrap().doc_id, "d1");
+        let err = catalog.find("standup").unwrap_err();
+        assert!(err.to_string().contains("document ID or file name"));
+    }
+
+    #[test]
+    fn test_same_content_skips_hidden_duplicates() {
+        let (_temp, paths) = setup();
+        let catalog = Catalog::open(&paths).unwrap();
+        for (doc_id, hash, duplicate_of) in [
+            ("d1", Some("abc"), None),
+            ("d2", Some("abc"), None),
+            ("d3", Some("abc"), Some("d1")),
+            ("d4", Some("def"), None),
+            ("d5", None, None),
+        ] {
+            let path = write_md(&paths.transcripts_dir, doc_id, doc_id, "2025-01-01");
+            let fm = read_frontmatter(&path).unwrap().unwrap();
+            let mut entry = CatalogEntry::from_frontmatter(&path, &fm);
+            entry.content_hash = hash.map(String::from);
+            entry.duplicate_of = duplicate_of.map(String::from);
+            catalog.upsert(&entry).unwrap();
+        }
+
+        assert_eq!(catalog.same_content("d1").unwrap(), vec!["d2"]);
+        assert_eq!(catalog.same_content("d3").unwrap(), vec!["d1", "d2"]);
+        assert!(catalog.same_content("d4").unwrap().is_empty());
+        assert!(catalog.same_content("d5").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_shared_entries_follow_access_tags() {
+        let (_temp, paths) = setup();
+        for (doc_id, access) in [
+            ("d1", None),
+            ("d2", Some("public")),
+            ("d3", Some("private")),
+        ] {
+            let path = write_md(&paths.transcripts_dir, doc_id, doc_id, "2025-01-01");
+            if let Some(access) = access {
+                let content = std::fs::read_to_string(&path).unwrap();
+                let tagged =
+                    content.replacen("generator:", &format!("access: {}\ngenerator:", access), 1);
+                std::fs::write(&path, tagged).unwrap();
+            }
+        }
+        let catalog = Catalog::open(&paths).unwrap();
+        assert_eq!(
+            catalog.lookup("d3").unwrap().unwrap().access,
+            Some(Access::Private)
+        );
+
+        let shared = |max_access, default_access| -> Vec<String> {
+            let policy = AccessPolicy {
+                max_access,
+                default_access,
+            };
+            catalog
+                .shared_entries(&policy)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.doc_id)
+                .collect()
+        };
+        assert_eq!(shared(Access::Team, Access::Team), vec!["d1", "d2"]);
+        assert_eq!(shared(Access::Public, Access::Team), vec!["d2"]);
+        assert_eq!(shared(Access::Team, Access::Private), vec!["d2"]);
+        assert_eq!(
+            shared(Access::Private, Access::Private),
+            vec!["d1", "d2", "d3"]
+        );
+    }
+
+    #[test]
+    fn test_refresh_changed_picks_up_hand_edits() {
+        let (_temp, paths) = setup();
+        let path = write_md(&paths.transcripts_dir, "standup", "d1", "2025-01-01");
+        let catalog = Catalog::open(&paths).unwrap();
+        let entries = catalog.entries().unwrap();
+        assert_eq!(catalog.refresh_changed(&entries).unwrap(), 0);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(
+            &path,
+            content.replacen("generator:", "access: private\ngenerator:", 1),
+        )
+        .unwrap();
+        let edited = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+        filetime::set_file_mtime(&path, filetime::FileTime::from_system_time(edited)).unwrap();
+
+        assert_eq!(catalog.refresh_changed(&entries).unwrap(), 1);
+        let entry = catalog.lookup("d1").unwrap().unwrap();
+        assert_eq!(entry.access, Some(Access::Private));
+        assert!(catalog
+            .shared_entries(&AccessPolicy::default())
+            .unwrap()
+            .is_empty());
+        assert_eq!(catalog.refresh_changed(&[entry]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_open_rebuilds_older_schema() {
+        let (_temp, paths) = setup();
+        write_md(&paths.transcripts_dir, "standup", "d1", "2025-01-01");
+        let db_path = paths.data_dir.join("catalog.sqlite");
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE documents (doc_id TEXT PRIMARY KEY, path TEXT NOT NULL,
+                 title TEXT, created_at TEXT NOT NULL);
+                 INSERT INTO documents VALUES ('stale', '/gone.md', NULL, '2024-01-01T00:00:00Z');",
+            )
+            .unwrap();
+
+        let catalog = Catalog::open(&paths).unwrap();
+        let ids: Vec<String> = catalog
+            .entries()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.doc_id)
+            .collect();
+        assert_eq!(ids, vec!["d1"]);
+    }
+}
@@ -0,0 +1,212 @@
+// ABOUTME: Renders a transcript's timed entries as SRT or WebVTT subtitles
+// ABOUTME: Times are relative to the first entry so cues line up with the recording's start
+
+use crate::storage::load_raw_transcript;
+use crate::timings;
+use crate::{Error, Paths, RawTranscript, Result};
+use std::fmt;
+
+/// How long a cue stays up when the entry has no end and nothing follows it
+const DEFAULT_CUE_MS: u64 = 3000;
+
+/// Subtitle file formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum SubtitleFormat {
+    /// SubRip (.srt)
+    Srt,
+    /// WebVTT (.vtt), with speakers as voice tags
+    Vtt,
+}
+
+impl SubtitleFormat {
+    /// File extension without the dot
+    pub fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+}
+
+impl fmt::Display for SubtitleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// One subtitle shown from `start_ms` to `end_ms`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Cues for every timed, non-empty entry, in order of appearance
+///
+/// An entry without an end time lasts until the next one starts, or
+/// `DEFAULT_CUE_MS` if that is sooner.
+pub fn cues(raw: &RawTranscript) -> Vec<Cue> {
+    let Some(timings) = timings::build(raw, "") else {
+        return Vec::new();
+    };
+
+    let mut timed: Vec<_> = timings
+        .entries
+        .iter()
+        .filter(|t| !raw.entries[t.index].text.trim().is_empty())
+        .collect();
+    timed.sort_by_key(|t| (t.start_ms, t.index));
+
+    timed
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let entry = &raw.entries[t.index];
+            let next_start = timed.get(i + 1).map(|next| next.start_ms);
+            let end_ms = t.end_ms.unwrap_or_else(|| {
+                let default = t.start_ms + DEFAULT_CUE_MS;
+                next_start.map_or(default, |next| default.min(next))
+            });
+            Cue {
+                start_ms: t.start_ms,
+                // Players skip zero-length cues
+                end_ms: end_ms.max(t.start_ms + 1),
+                speaker: entry.speaker.clone(),
+                text: entry.text.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// "HH:MM:SS" plus the millisecond separator and milliseconds
+fn timestamp(ms: u64, separator: char) -> String {
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// SubRip: numbered cues with "Speaker: text"
+pub fn to_srt(cues: &[Cue]) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n",
+            i + 1,
+            timestamp(cue.start_ms, ','),
+            timestamp(cue.end_ms, ',')
+        ));
+        match &cue.speaker {
+            Some(speaker) => out.push_str(&format!("{}: {}\n\n", speaker, cue.text)),
+            None => out.push_str(&format!("{}\n\n", cue.text)),
+        }
+    }
+    out
+}
+
+/// Escapes the characters WebVTT treats as markup
+fn vtt_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// WebVTT: speakers become `<v Name>` voice tags players can style or show
+pub fn to_vtt(cues: &[Cue]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues {
+        out.push_str(&format!(
+            "{} --> {}\n",
+            timestamp(cue.start_ms, '.'),
+            timestamp(cue.end_ms, '.')
+        ));
+        let text = vtt_escape(&cue.text);
+        match &cue.speaker {
+            Some(speaker) => out.push_str(&format!("<v {}>{}\n\n", vtt_escape(speaker), text)),
+            None => out.push_str(&format!("{}\n\n", text)),
+        }
+    }
+    out
+}
+
+/// Renders a transcript in the given format
+pub fn render(raw: &RawTranscript, format: SubtitleFormat) -> String {
+    let cues = cues(raw);
+    match format {
+        SubtitleFormat::Srt => to_srt(&cues),
+        SubtitleFormat::Vtt => to_vtt(&cues),
+    }
+}
+
+/// Subtitles for a synced document, from the raw JSON saved next to its transcript
+///
+/// `condensed` leaves out small talk and filler first (see [`crate::condense`]).
+pub fn export(
+    paths: &Paths,
+    doc_id: &str,
+    format: SubtitleFormat,
+    condensed: bool,
+) -> Result<String> {
+    let (_, mut raw) = load_raw_transcript(paths, doc_id)?;
+    if condensed {
+        raw = crate::condense::condense(&raw).raw;
+    }
+
+    if cues(&raw).is_empty() {
+        return Err(Error::InvalidInput(format!(
+            "Transcript {} has no timestamps to build subtitles from",
+            doc_id
+        )));
+    }
+    Ok(render(&raw, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw() -> RawTranscript {
+        serde_json::from_str(
+            r#"[
+                {"speaker": "Alice", "start_timestamp": "2025-10-01T10:00:01Z",
+                 "end_timestamp": "2025-10-01T10:00:04.250Z", "text": "Morning all"},
+                {"speaker": "Bob", "start_timestamp": "2025-10-01T10:00:05Z",
+                 "text": "Q&A <after>"},
+                {"start_timestamp": "2025-10-01T10:00:06.500Z", "text": "  "},
+                {"start_timestamp": "2025-10-01T10:01:10Z", "text": "Anyone?"},
+                {"speaker": "Alice", "text": "untimed"}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cues() {
+        let cues = cues(&raw());
+        let spans: Vec<(u64, u64)> = cues.iter().map(|c| (c.start_ms, c.end_ms)).collect();
+        assert_eq!(spans, vec![(0, 3250), (4000, 7000), (69000, 72000)]);
+        assert_eq!(cues[2].speaker, None);
+    }
+
+    #[test]
+    fn test_to_srt() {
+        let srt = render(&raw(), SubtitleFormat::Srt);
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:03,250\nAlice: Morning all\n\n"));
+        assert!(srt.contains("3\n00:01:09,000 --> 00:01:12,000\nAnyone?\n\n"));
+    }
+
+    #[test]
+    fn test_to_vtt() {
+        let vtt = render(&raw(), SubtitleFormat::Vtt);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:03.250\n<v Alice>Morning all\n"));
+        assert!(vtt.contains("<v Bob>Q&amp;A &lt;after&gt;\n"));
+        assert_eq!(timestamp(3_723_004, '.'), "01:02:03.004");
+    }
+}
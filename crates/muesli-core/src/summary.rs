@@ -0,0 +1,1054 @@
+// ABOUTME: AI summarization using OpenAI, a local Ollama server, or an in-process GGUF model
+// ABOUTME: Chunks transcripts and generates meeting summaries
+
+use crate::{Error, Result};
+use async_openai::{
+    config::OpenAIConfig,
+    types::{
+        ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
+        ChatCompletionResponseFormat, ChatCompletionResponseFormatType,
+        CreateChatCompletionRequestArgs,
+    },
+    Client,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub mod diff;
+pub mod incident;
+pub mod interview;
+#[cfg(feature = "summaries-local")]
+mod local;
+pub mod structured;
+
+pub use incident::IncidentTimeline;
+pub use interview::InterviewPacket;
+#[cfg(feature = "summaries-local")]
+pub use local::LocalProvider;
+pub use structured::StructuredSummary;
+
+const DEFAULT_SUMMARY_PROMPT: &str = r#"You are an expert at turning messy transcripts into high-resolution, action-oriented summaries.
+
+Given the transcript below, produce a structured summary with these sections:
+
+1. Meeting Snapshot
+2. Executive Summary (3–7 bullets)
+3. Key Decisions (or "None")
+4. Action Items (owner, task, due, priority, source)
+5. Discussion Highlights by Topic
+6. Risks, Concerns, and Open Questions
+7. Nuanced Observations & Dynamics
+8. Ambiguities, Gaps, and Things You Refused to Guess
+
+Rules:
+- Use headings and bullet points.
+- Preserve important names, dates, and numbers accurately.
+- Only use information from the transcript; label any inferences as "(inferred)".
+- Be explicit when something is unclear, missing, or not specified.
+- Ignore small talk; focus on substance."#;
+
+const STANDUP_PROMPT: &str = r#"You are summarizing a team standup.
+
+Given the transcript below, produce:
+
+1. Per Person: what they finished, what they're doing next, and what blocks them
+2. Blockers Needing Help (owner and who can unblock, or "None")
+3. Follow-ups Agreed After Standup
+
+Rules:
+- Keep each person to three short bullets at most.
+- Use the names as spoken; write "(unknown)" rather than guessing who said something.
+- Skip status chatter that doesn't change anyone's plan."#;
+
+const ONE_ON_ONE_PROMPT: &str = r#"You are summarizing a 1:1 meeting between two people.
+
+Given the transcript below, produce:
+
+1. Topics Discussed (one line each)
+2. Feedback Given, in either direction, with the specific examples mentioned
+3. Career, Growth, and Wellbeing Notes (only what was actually said)
+4. Commitments: who will do what, and by when
+5. Topics to Revisit Next Time
+
+Rules:
+- Keep a neutral, private tone; this summary is for the two participants.
+- Attribute statements to the person who made them.
+- Do not characterize anyone's mood or performance beyond their own words."#;
+
+const INTERVIEW_PROMPT: &str = r#"You are summarizing a job interview for the hiring team.
+
+Given the transcript below, produce:
+
+1. Role and Interview Focus (as stated in the conversation)
+2. Candidate Background (experience and projects they described)
+3. Questions Asked and the Substance of Each Answer
+4. Evidence For and Against Each Competency Discussed, quoting briefly where useful
+5. Candidate's Questions and Concerns
+6. Open Points for Other Interviewers
+
+Rules:
+- Report what was said; do not give a hire/no-hire recommendation.
+- Leave out anything about age, family, health, religion, or other protected characteristics.
+- Label any inference as "(inferred)"."#;
+
+const BOARD_PROMPT: &str = r#"You are summarizing a board meeting for the minutes.
+
+Given the transcript below, produce:
+
+1. Attendees and Agenda Items Covered
+2. Key Metrics and Financials Presented (exact figures as stated)
+3. Resolutions and Votes (motion, mover, outcome), or "None"
+4. Decisions and Guidance from the Board
+5. Risks and Concerns Raised
+6. Action Items (owner, task, due date)
+
+Rules:
+- Be formal and precise; preserve numbers, dates, and names exactly.
+- Distinguish decisions from discussion.
+- Note explicitly when a figure or outcome was unclear in the recording."#;
+
+const INCIDENT_PROMPT: &str = r#"You are summarizing an incident review or postmortem meeting.
+
+Given the transcript below, produce:
+
+1. Incident Overview (what broke, for whom, and for how long, as stated)
+2. Timeline of Events (time as spoken, what happened, who noticed or acted)
+3. Detection and Response (how it was found, escalated and mitigated)
+4. Contributing Factors
+5. Follow-up Actions (owner, task, due date)
+6. Open Questions
+
+Rules:
+- Keep the review blameless: describe systems and decisions, not people's faults.
+- Preserve times, durations, error rates and other figures exactly as spoken.
+- Label any inference as "(inferred)"."#;
+
+const RETRO_PROMPT: &str = r#"You are writing a quarterly retrospective for a team.
+
+Below are summaries of the team's meetings over the quarter, oldest first.
+Produce:
+
+1. Themes (the 3–6 topics that took the most attention, with the meetings they came up in)
+2. Wins (shipped work, decisions that stuck, problems resolved)
+3. Recurring Blockers (anything raised in more than one meeting, with how often and whether it was resolved)
+4. Decisions That Changed Over the Quarter
+5. Open Threads to Carry into Next Quarter
+
+Rules:
+- Only use information from the summaries; label inferences as "(inferred)".
+- Prefer patterns across meetings over details of any one meeting.
+- Cite meetings by title and date."#;
+
+/// Built-in presets: name, labels that select it, prompt
+const BUILTIN_PRESETS: &[(&str, &[&str], &str)] = &[
+    ("standup", &["standup", "stand-up", "daily"], STANDUP_PROMPT),
+    (
+        "1on1",
+        &["1:1", "1on1", "1-on-1", "one-on-one"],
+        ONE_ON_ONE_PROMPT,
+    ),
+    ("interview", &["interview", "hiring"], INTERVIEW_PROMPT),
+    ("board", &["board", "board meeting"], BOARD_PROMPT),
+    (
+        incident::INCIDENT_PRESET,
+        &["incident", "postmortem", "post-mortem"],
+        INCIDENT_PROMPT,
+    ),
+    // Used by 'muesli retro' over saved summaries; no label selects it
+    (RETRO_PRESET, &[], RETRO_PROMPT),
+    // Used by 'muesli incident-timeline'; the reply must parse as an IncidentTimeline
+    (
+        incident::INCIDENT_TIMELINE_PRESET,
+        &[],
+        incident::INCIDENT_TIMELINE_PROMPT,
+    ),
+    // Used by 'muesli interview-packet'; the reply must parse as an InterviewPacket
+    (
+        interview::INTERVIEW_PACKET_PRESET,
+        &[],
+        interview::INTERVIEW_PACKET_PROMPT,
+    ),
+];
+
+/// Preset `muesli retro` summarizes a quarter's summaries with
+pub const RETRO_PRESET: &str = "retro";
+
+/// Name that selects the general prompt even when a label matches a preset
+pub const DEFAULT_PRESET: &str = "default";
+
+/// A summary prompt for one kind of meeting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub prompt: String,
+    /// Granola labels that select this preset automatically (case-insensitive)
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl Preset {
+    /// Whether one of a meeting's labels selects this preset
+    pub fn matches(&self, labels: &[String]) -> bool {
+        self.labels
+            .iter()
+            .any(|wanted| labels.iter().any(|l| l.eq_ignore_ascii_case(wanted)))
+    }
+}
+
+/// Host used for Ollama when `ollama_host` is not configured
+pub const DEFAULT_OLLAMA_HOST: &str = "http://localhost:11434";
+
+/// Which service generates summaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    /// OpenAI's chat completions API (needs an API key)
+    #[default]
+    #[serde(rename = "openai")]
+    #[cfg_attr(feature = "clap", value(name = "openai"))]
+    OpenAi,
+    /// A local Ollama server; transcripts never leave the machine
+    Ollama,
+    /// A GGUF model run inside muesli (requires the 'summaries-local' feature)
+    Local,
+}
+
+impl std::fmt::Display for Provider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Provider::OpenAi => write!(f, "openai"),
+            Provider::Ollama => write!(f, "ollama"),
+            Provider::Local => write!(f, "local"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SummaryConfig {
+    #[serde(default)]
+    pub provider: Provider,
+    pub model: String,
+    pub context_window_chars: usize,
+    pub custom_prompt: Option<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Language to write summaries in, regardless of the transcript's language
+    #[serde(default)]
+    pub summary_language: Option<String>,
+    /// Base URL of the Ollama server (default http://localhost:11434)
+    #[serde(default)]
+    pub ollama_host: Option<String>,
+    /// GGUF model file for the local provider
+    #[serde(default)]
+    pub local_model: Option<PathBuf>,
+    /// tokenizer.json for the local model (default: next to the model file)
+    #[serde(default)]
+    pub local_tokenizer: Option<PathBuf>,
+    /// Presets added to, or replacing, the built-in ones by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub presets: BTreeMap<String, Preset>,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            provider: Provider::OpenAi,
+            model: "gpt-5".to_string(),
+            context_window_chars: 300_000, // ~400K tokens for GPT-5 API
+            custom_prompt: None,
+            temperature: None, // GPT-5 only supports default temperature (1.0)
+            summary_language: None,
+            ollama_host: None,
+            local_model: None,
+            local_tokenizer: None,
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl SummaryConfig {
+    pub fn load(config_path: &Path) -> Result<Self> {
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(config_path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            Error::Filesystem(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to parse summary config: {}", e),
+            ))
+        })
+    }
+
+    pub fn save(&self, config_path: &Path, tmp_dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        crate::storage::write_atomic(config_path, json.as_bytes(), tmp_dir)
+    }
+
+    /// The Ollama server to use, without a trailing slash
+    pub fn ollama_host(&self) -> &str {
+        self.ollama_host
+            .as_deref()
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .unwrap_or(DEFAULT_OLLAMA_HOST)
+            .trim_end_matches('/')
+    }
+
+    pub fn prompt(&self) -> &str {
+        self.custom_prompt
+            .as_deref()
+            .unwrap_or(DEFAULT_SUMMARY_PROMPT)
+    }
+
+    /// Built-in presets overlaid with the configured ones, by name
+    pub fn presets(&self) -> BTreeMap<String, Preset> {
+        let mut presets: BTreeMap<String, Preset> = BUILTIN_PRESETS
+            .iter()
+            .map(|(name, labels, prompt)| {
+                let preset = Preset {
+                    prompt: prompt.to_string(),
+                    labels: labels.iter().map(|l| l.to_string()).collect(),
+                };
+                (name.to_string(), preset)
+            })
+            .collect();
+        presets.extend(self.presets.clone());
+        presets
+    }
+
+    /// The preset to summarize a meeting with, if any
+    ///
+    /// A requested name wins (`default` meaning none); otherwise the first
+    /// preset, by name, listing one of the meeting's labels.
+    pub fn choose_preset(
+        &self,
+        requested: Option<&str>,
+        labels: &[String],
+    ) -> Result<Option<(String, Preset)>> {
+        let mut presets = self.presets();
+
+        if let Some(name) = requested {
+            if name == DEFAULT_PRESET {
+                return Ok(None);
+            }
+            return match presets.remove(name) {
+                Some(preset) => Ok(Some((name.to_string(), preset))),
+                None => Err(Error::InvalidInput(format!(
+                    "Unknown preset '{}'; available: {}, {}",
+                    name,
+                    DEFAULT_PRESET,
+                    presets.keys().cloned().collect::<Vec<_>>().join(", ")
+                ))),
+            };
+        }
+
+        Ok(presets
+            .into_iter()
+            .find(|(_, preset)| preset.matches(labels)))
+    }
+
+    /// This config with a preset's prompt in place of the default or custom one
+    pub fn with_preset(&self, preset: &Preset) -> SummaryConfig {
+        SummaryConfig {
+            custom_prompt: Some(preset.prompt.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Assembles the full prompt sent to the model for one chunk of transcript
+    pub fn build_prompt(&self, text: &str) -> String {
+        let mut prompt = self.prompt().to_string();
+
+        if let Some(language) = self
+            .summary_language
+            .as_deref()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+        {
+            prompt.push_str(&format!(
+                "\n\nWrite the entire summary in {}, even if the transcript is in a different language. Keep names and direct quotes as spoken.",
+                language
+            ));
+        }
+
+        format!(
+            "{}\n\nTranscript:\n<<<TRANSCRIPT_START>>>\n{}\n<<<TRANSCRIPT_END>>>",
+            prompt, text
+        )
+    }
+}
+
+/// The OpenAI API key for this config: OPENAI_API_KEY, then the keychain
+///
+/// Returns None for providers that don't need one.
+pub fn api_key_for(config: &SummaryConfig) -> Result<Option<String>> {
+    match config.provider {
+        Provider::OpenAi => std::env::var("OPENAI_API_KEY")
+            .or_else(|_| get_api_key_from_keychain())
+            .map(Some),
+        Provider::Ollama | Provider::Local => Ok(None),
+    }
+}
+
+/// Calls OpenAI's chat completions API
+pub struct OpenAiProvider {
+    client: Client<OpenAIConfig>,
+    model: String,
+    temperature: Option<f32>,
+}
+
+impl OpenAiProvider {
+    pub fn new(api_key: &str, model: &str, temperature: Option<f32>) -> Self {
+        Self {
+            client: Client::with_config(OpenAIConfig::new().with_api_key(api_key)),
+            model: model.to_string(),
+            temperature,
+        }
+    }
+
+    pub async fn complete(&self, prompt: String) -> Result<String> {
+        self.request(prompt, false).await
+    }
+
+    /// Like `complete`, but in JSON mode so the reply is a single JSON object
+    pub async fn complete_json(&self, prompt: String) -> Result<String> {
+        self.request(prompt, true).await
+    }
+
+    async fn request(&self, prompt: String, json: bool) -> Result<String> {
+        let messages = vec![ChatCompletionRequestMessage::User(
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(prompt)
+                .build()
+                .map_err(|e| {
+                    Error::Summarization(format!("Failed to build user message: {}", e))
+                })?,
+        )];
+
+        let mut request_builder = CreateChatCompletionRequestArgs::default();
+        request_builder.model(&self.model).messages(messages);
+
+        // Only set temperature if configured (GPT-5 doesn't support custom temperature)
+        if let Some(temp) = self.temperature {
+            request_builder.temperature(temp);
+        }
+        if json {
+            request_builder.response_format(ChatCompletionResponseFormat {
+                r#type: ChatCompletionResponseFormatType::JsonObject,
+            });
+        }
+
+        let request = request_builder
+            .build()
+            .map_err(|e| Error::Summarization(format!("Failed to build request: {}", e)))?;
+
+        let response = self
+            .client
+            .chat()
+            .create(request)
+            .await
+            .map_err(|e| Error::Summarization(format!("OpenAI API error: {}", e)))?;
+
+        response
+            .choices
+            .first()
+            .and_then(|choice| choice.message.content.clone())
+            .ok_or_else(|| Error::Summarization("No response from OpenAI".into()))
+    }
+}
+
+/// Calls a local Ollama server's generate API
+pub struct OllamaProvider {
+    client: reqwest::Client,
+    host: String,
+    model: String,
+    temperature: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct OllamaResponse {
+    #[serde(default)]
+    response: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+impl OllamaProvider {
+    pub fn new(host: &str, model: &str, temperature: Option<f32>) -> Self {
+        Self {
+            // No timeout: local models can take minutes on long transcripts
+            client: reqwest::Client::new(),
+            host: host.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            temperature,
+        }
+    }
+
+    /// JSON body for a non-streaming generate request; `json` constrains the reply to JSON
+    fn request_body(&self, prompt: &str, json: bool) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "stream": false,
+        });
+        if let Some(temp) = self.temperature {
+            body["options"] = serde_json::json!({ "temperature": temp });
+        }
+        if json {
+            body["format"] = "json".into();
+        }
+        body
+    }
+
+    pub async fn complete(&self, prompt: String) -> Result<String> {
+        self.request(prompt, false).await
+    }
+
+    /// Like `complete`, but with Ollama's JSON output mode
+    pub async fn complete_json(&self, prompt: String) -> Result<String> {
+        self.request(prompt, true).await
+    }
+
+    async fn request(&self, prompt: String, json: bool) -> Result<String> {
+        let url = format!("{}/api/generate", self.host);
+        let response = self
+            .client
+            .post(&url)
+            .json(&self.request_body(&prompt, json))
+            .send()
+            .await
+            .map_err(|e| {
+                Error::Summarization(format!(
+                    "Could not reach Ollama at {} (is 'ollama serve' running?): {}",
+                    self.host, e
+                ))
+            })?;
+
+        let status = response.status();
+        let body: OllamaResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::Summarization(format!("Invalid response from Ollama: {}", e)))?;
+
+        if let Some(error) = body.error {
+            let hint = if status == reqwest::StatusCode::NOT_FOUND {
+                format!(
+                    " (pull it with 'ollama pull {}' or choose another with 'muesli set-config --model')",
+                    self.model
+                )
+            } else {
+                String::new()
+            };
+            return Err(Error::Summarization(format!(
+                "Ollama error: {}{}",
+                error, hint
+            )));
+        }
+
+        body.response
+            .filter(|r| !r.trim().is_empty())
+            .ok_or_else(|| Error::Summarization("No response from Ollama".into()))
+    }
+}
+
+/// Answers prompts; the configured provider in production
+///
+/// The summarize functions only see this trait, so tests can run them against
+/// [`crate::testing::FakeSummarizer`] without a network, keychain or model.
+#[async_trait]
+pub trait Summarizer: Send {
+    async fn complete(&mut self, prompt: String) -> Result<String>;
+
+    /// A reply the provider has been asked to keep to JSON; a plain completion by default
+    async fn complete_json(&mut self, prompt: String) -> Result<String> {
+        self.complete(prompt).await
+    }
+}
+
+/// The configured provider, ready to answer prompts
+pub(crate) enum Backend {
+    OpenAi(OpenAiProvider),
+    Ollama(OllamaProvider),
+    #[cfg(feature = "summaries-local")]
+    Local(Box<LocalProvider>),
+}
+
+impl Backend {
+    pub(crate) fn new(api_key: Option<&str>, config: &SummaryConfig) -> Result<Self> {
+        match config.provider {
+            Provider::OpenAi => {
+                crate::offline::ensure_online("summarizing with OpenAI")?;
+                let api_key = api_key.ok_or_else(|| {
+                    Error::Auth(
+                        "OpenAI API key not set. Set OPENAI_API_KEY or run: muesli set-api-key <key>"
+                            .into(),
+                    )
+                })?;
+                Ok(Backend::OpenAi(OpenAiProvider::new(
+                    api_key,
+                    &config.model,
+                    config.temperature,
+                )))
+            }
+            Provider::Ollama => {
+                // A local Ollama server is fine offline; a remote one isn't
+                if !crate::offline::is_loopback(config.ollama_host()) {
+                    crate::offline::ensure_online(&format!("Ollama at {}", config.ollama_host()))?;
+                }
+                Ok(Backend::Ollama(OllamaProvider::new(
+                    config.ollama_host(),
+                    &config.model,
+                    config.temperature,
+                )))
+            }
+            #[cfg(feature = "summaries-local")]
+            Provider::Local => Ok(Backend::Local(Box::new(LocalProvider::load(config)?))),
+            #[cfg(not(feature = "summaries-local"))]
+            Provider::Local => Err(Error::Summarization(
+                "The local provider needs muesli built with the 'summaries-local' feature".into(),
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Summarizer for Backend {
+    async fn complete(&mut self, prompt: String) -> Result<String> {
+        let _span = crate::perf::span("summarize.model");
+        match self {
+            Backend::OpenAi(provider) => provider.complete(prompt).await,
+            Backend::Ollama(provider) => provider.complete(prompt).await,
+            #[cfg(feature = "summaries-local")]
+            Backend::Local(provider) => provider.complete(prompt),
+        }
+    }
+
+    // The local model has no JSON mode and relies on the prompt alone
+    async fn complete_json(&mut self, prompt: String) -> Result<String> {
+        let _span = crate::perf::span("summarize.model");
+        match self {
+            Backend::OpenAi(provider) => provider.complete_json(prompt).await,
+            Backend::Ollama(provider) => provider.complete_json(prompt).await,
+            #[cfg(feature = "summaries-local")]
+            Backend::Local(provider) => provider.complete(prompt),
+        }
+    }
+}
+
+/// Summarizes a transcript with the configured provider
+///
+/// `api_key` is only required for OpenAI; see [`api_key_for`].
+pub async fn summarize_transcript(
+    transcript: &str,
+    api_key: Option<&str>,
+    config: &SummaryConfig,
+) -> Result<String> {
+    let mut backend = Backend::new(api_key, config)?;
+    summarize_transcript_with(&mut backend, transcript, config).await
+}
+
+/// Summarizes a transcript with any [`Summarizer`]
+pub async fn summarize_transcript_with(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+) -> Result<String> {
+    let text = condense(summarizer, transcript, config).await?;
+    summarizer.complete(config.build_prompt(&text)).await
+}
+
+/// Summarizes a transcript as decisions, action items and risks
+///
+/// Long transcripts are first condensed into prose summaries with the
+/// configured prompt. Replies that aren't valid JSON for the schema are
+/// retried up to `structured::MAX_ATTEMPTS` times.
+pub async fn summarize_structured(
+    transcript: &str,
+    api_key: Option<&str>,
+    config: &SummaryConfig,
+) -> Result<StructuredSummary> {
+    let mut backend = Backend::new(api_key, config)?;
+    summarize_structured_with(&mut backend, transcript, config).await
+}
+
+/// Summarizes a transcript as decisions, action items and risks with any [`Summarizer`]
+pub async fn summarize_structured_with(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+) -> Result<StructuredSummary> {
+    let text = condense(summarizer, transcript, config).await?;
+
+    let extract = SummaryConfig {
+        custom_prompt: Some(structured::STRUCTURED_PROMPT.to_string()),
+        ..config.clone()
+    };
+    complete_validated(
+        summarizer,
+        extract.build_prompt(&text),
+        "summary",
+        structured::parse,
+    )
+    .await
+}
+
+/// Extracts an interview packet: questions and answers, strengths, concerns and quotes
+///
+/// Long transcripts are first condensed with the `interview` preset so the
+/// chunk summaries keep questions and answers; the packet itself comes from
+/// the `interview-packet` preset, retried like structured summaries.
+pub async fn interview_packet(
+    transcript: &str,
+    api_key: Option<&str>,
+    config: &SummaryConfig,
+) -> Result<InterviewPacket> {
+    let mut backend = Backend::new(api_key, config)?;
+    interview_packet_with(&mut backend, transcript, config).await
+}
+
+/// Extracts an interview packet with any [`Summarizer`]
+pub async fn interview_packet_with(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+) -> Result<InterviewPacket> {
+    extract_with_presets(
+        summarizer,
+        transcript,
+        config,
+        (
+            interview::INTERVIEW_PRESET,
+            interview::INTERVIEW_PACKET_PRESET,
+        ),
+        "interview packet",
+        interview::parse,
+    )
+    .await
+}
+
+/// Extracts a postmortem skeleton: timestamped events, contributing factors and follow-ups
+///
+/// Long transcripts are first condensed with the `incident` preset so the
+/// chunk summaries keep times; the timeline comes from the
+/// `incident-timeline` preset, retried like structured summaries.
+pub async fn incident_timeline(
+    transcript: &str,
+    api_key: Option<&str>,
+    config: &SummaryConfig,
+) -> Result<IncidentTimeline> {
+    let mut backend = Backend::new(api_key, config)?;
+    incident_timeline_with(&mut backend, transcript, config).await
+}
+
+/// Extracts a postmortem skeleton with any [`Summarizer`]
+pub async fn incident_timeline_with(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+) -> Result<IncidentTimeline> {
+    extract_with_presets(
+        summarizer,
+        transcript,
+        config,
+        (
+            incident::INCIDENT_PRESET,
+            incident::INCIDENT_TIMELINE_PRESET,
+        ),
+        "incident timeline",
+        incident::parse,
+    )
+    .await
+}
+
+/// Condenses with the first preset, then asks for JSON with the second
+async fn extract_with_presets<T>(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+    (condense_with, extract_with): (&str, &str),
+    what: &str,
+    parse: fn(&str) -> Result<T>,
+) -> Result<T> {
+    let mut presets = config.presets();
+    let condensing = match presets.get(condense_with) {
+        Some(preset) => config.with_preset(preset),
+        None => config.clone(),
+    };
+    let text = condense(summarizer, transcript, &condensing).await?;
+
+    let preset = presets
+        .remove(extract_with)
+        .ok_or_else(|| Error::Summarization(format!("No '{}' preset", extract_with)))?;
+    let extract = config.with_preset(&preset);
+    complete_validated(summarizer, extract.build_prompt(&text), what, parse).await
+}
+
+/// Asks for JSON until `parse` accepts the reply, up to `structured::MAX_ATTEMPTS` times
+async fn complete_validated<T>(
+    summarizer: &mut dyn Summarizer,
+    base_prompt: String,
+    what: &str,
+    parse: fn(&str) -> Result<T>,
+) -> Result<T> {
+    let mut prompt = base_prompt.clone();
+    let mut attempt = 1;
+    loop {
+        let reply = summarizer.complete_json(prompt).await?;
+        match parse(&reply) {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < structured::MAX_ATTEMPTS => {
+                eprintln!(
+                    "Warning: Invalid JSON {} (attempt {}/{}): {}",
+                    what,
+                    attempt,
+                    structured::MAX_ATTEMPTS,
+                    e
+                );
+                prompt = structured::retry_prompt(&base_prompt, &e);
+                attempt += 1;
+            }
+            Err(e) => {
+                return Err(Error::Summarization(format!(
+                    "No valid JSON {} after {} attempts: {}",
+                    what, attempt, e
+                )))
+            }
+        }
+    }
+}
+
+/// Shrinks a transcript until it fits one prompt, summarizing chunks and then
+/// the summaries of those chunks as often as needed
+async fn condense(
+    summarizer: &mut dyn Summarizer,
+    transcript: &str,
+    config: &SummaryConfig,
+) -> Result<String> {
+    // Chunk if too long (based on configured context window)
+    let mut chunks = chunk_transcript(transcript, config.context_window_chars);
+
+    // Multiple chunks - summarize each, then summarize the summaries until they fit
+    while chunks.len() > 1 {
+        let mut chunk_summaries = Vec::new();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            // stderr, so piped JSON and the MCP protocol stay clean
+            eprintln!("Summarizing chunk {}/{}...", i + 1, chunks.len());
+            let summary = summarizer.complete(config.build_prompt(chunk)).await?;
+            chunk_summaries.push(summary);
+        }
+
+        // Combine summaries
+        let combined = chunk_summaries.join("\n\n---\n\n");
+        let next = chunk_transcript(&combined, config.context_window_chars);
+        if next.len() >= chunks.len() {
+            // Summaries aren't getting shorter; another round would never finish
+            return Ok(combined);
+        }
+        chunks = next;
+    }
+
+    Ok(chunks.swap_remove(0))
+}
+
+fn chunk_transcript(text: &str, max_chars: usize) -> Vec<String> {
+    if text.len() <= max_chars {
+        return vec![text.to_string()];
+    }
+
+    let mut chunks = Vec::new();
+    let mut current_chunk = String::new();
+
+    for line in text.lines() {
+        if current_chunk.len() + line.len() + 1 > max_chars && !current_chunk.is_empty() {
+            chunks.push(current_chunk.clone());
+            current_chunk.clear();
+        }
+        current_chunk.push_str(line);
+        current_chunk.push('\n');
+    }
+
+    if !current_chunk.is_empty() {
+        chunks.push(current_chunk);
+    }
+
+    chunks
+}
+
+pub fn get_api_key_from_keychain() -> Result<String> {
+    crate::keychain::get(crate::keychain::OPENAI_API_KEY)?.ok_or_else(|| {
+        Error::Auth(format!(
+            "OpenAI API key not found in {}. Set it with: muesli set-api-key <key>",
+            crate::keychain::backend_name()
+        ))
+    })
+}
+
+pub fn set_api_key_in_keychain(api_key: &str) -> Result<()> {
+    crate::keychain::set(crate::keychain::OPENAI_API_KEY, api_key)?;
+    println!(
+        "{}OpenAI API key stored in {}",
+        crate::term::emoji("✅ "),
+        crate::keychain::backend_name()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_transcript_short() {
+        let text = "Short transcript";
+        let chunks = chunk_transcript(text, 1000);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].contains("Short transcript"));
+    }
+
+    #[test]
+    fn test_chunk_transcript_long() {
+        let text = "Line 1\n".repeat(200); // 1400 chars
+        let chunks = chunk_transcript(&text, 500);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= 500 || chunk.lines().count() == 1);
+        }
+    }
+
+    #[test]
+    fn test_summary_prompt_format() {
+        assert!(DEFAULT_SUMMARY_PROMPT.contains("Meeting Snapshot"));
+        assert!(DEFAULT_SUMMARY_PROMPT.contains("Action Items"));
+        assert!(DEFAULT_SUMMARY_PROMPT.contains("Key Decisions"));
+        assert!(DEFAULT_SUMMARY_PROMPT.contains("Ambiguities, Gaps"));
+    }
+
+    #[test]
+    fn test_build_prompt_without_language() {
+        let prompt = SummaryConfig::default().build_prompt("hello");
+        assert!(prompt.starts_with(DEFAULT_SUMMARY_PROMPT));
+        assert!(!prompt.contains("Write the entire summary in"));
+        assert!(prompt.ends_with("<<<TRANSCRIPT_START>>>\nhello\n<<<TRANSCRIPT_END>>>"));
+    }
+
+    #[test]
+    fn test_build_prompt_with_language() {
+        let config = SummaryConfig {
+            summary_language: Some("German".into()),
+            custom_prompt: Some("Summarize.".into()),
+            ..Default::default()
+        };
+        let prompt = config.build_prompt("bonjour");
+
+        let instruction = prompt
+            .find("Write the entire summary in German")
+            .expect("language instruction missing");
+        // Instruction follows the base prompt and precedes the transcript
+        assert!(prompt.starts_with("Summarize."));
+        assert!(instruction < prompt.find("<<<TRANSCRIPT_START>>>").unwrap());
+    }
+
+    #[test]
+    fn test_build_prompt_ignores_blank_language() {
+        let config = SummaryConfig {
+            summary_language: Some("  ".into()),
+            ..Default::default()
+        };
+        assert!(!config
+            .build_prompt("x")
+            .contains("Write the entire summary in"));
+    }
+
+    #[test]
+    fn test_config_without_language_still_loads() {
+        let json = r#"{"model":"gpt-4o","context_window_chars":8000,"custom_prompt":null}"#;
+        let config: SummaryConfig = serde_json::from_str(json).unwrap();
+        assert!(config.summary_language.is_none());
+        assert_eq!(config.provider, Provider::OpenAi);
+        assert_eq!(config.ollama_host(), DEFAULT_OLLAMA_HOST);
+    }
+
+    #[test]
+    fn test_config_selects_ollama() {
+        let json = r#"{"provider":"ollama","model":"llama3.1","context_window_chars":24000,
+            "custom_prompt":null,"ollama_host":"http://gpu-box:11434/"}"#;
+        let config: SummaryConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.provider, Provider::Ollama);
+        assert_eq!(config.ollama_host(), "http://gpu-box:11434");
+        assert!(api_key_for(&config).unwrap().is_none());
+
+        let saved = serde_json::to_string(&config).unwrap();
+        assert!(saved.contains(r#""provider":"ollama""#));
+    }
+
+    #[test]
+    fn test_choose_preset() {
+        let json = r#"{"model":"gpt-5","context_window_chars":8000,"custom_prompt":null,
+            "presets":{"retro":{"prompt":"Summarize the retro","labels":["Retro"]},
+                       "standup":{"prompt":"Just blockers"}}}"#;
+        let config: SummaryConfig = serde_json::from_str(json).unwrap();
+        let labels = |ls: &[&str]| ls.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+
+        let (name, preset) = config.choose_preset(Some("1on1"), &[]).unwrap().unwrap();
+        assert_eq!(name, "1on1");
+        assert!(config
+            .with_preset(&preset)
+            .build_prompt("x")
+            .starts_with("You are summarizing a 1:1"));
+
+        // Labels match case-insensitively; configured presets replace built-ins
+        let (name, _) = config
+            .choose_preset(None, &labels(&["Team", "retro"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "retro");
+        let (_, standup) = config.choose_preset(Some("standup"), &[]).unwrap().unwrap();
+        assert_eq!(standup.prompt, "Just blockers");
+        assert!(config
+            .choose_preset(None, &labels(&["Interview"]))
+            .unwrap()
+            .is_some());
+
+        assert!(config
+            .choose_preset(Some(DEFAULT_PRESET), &labels(&["retro"]))
+            .unwrap()
+            .is_none());
+        assert!(config
+            .choose_preset(None, &labels(&["Sales"]))
+            .unwrap()
+            .is_none());
+        let err = config.choose_preset(Some("weekly"), &[]).unwrap_err();
+        assert!(err.to_string().contains(
+            "board, incident, incident-timeline, interview, interview-packet, retro, standup"
+        ));
+    }
+
+    #[test]
+    fn test_ollama_request_body() {
+        let provider = OllamaProvider::new("http://localhost:11434/", "llama3.1", Some(0.2));
+        assert_eq!(provider.host, "http://localhost:11434");
+
+        let body = provider.request_body("Summarize this", false);
+        assert_eq!(body["model"], "llama3.1");
+        assert_eq!(body["prompt"], "Summarize this");
+        assert_eq!(body["stream"], false);
+        assert!((body["options"]["temperature"].as_f64().unwrap() - 0.2).abs() < 1e-6);
+        assert!(body.get("format").is_none());
+
+        let body =
+            OllamaProvider::new(DEFAULT_OLLAMA_HOST, "llama3.1", None).request_body("x", true);
+        assert!(body.get("options").is_none());
+        assert_eq!(body["format"], "json");
+    }
+
+    #[test]
+    fn test_openai_requires_api_key() {
+        let err = Backend::new(None, &SummaryConfig::default()).err().unwrap();
+        assert!(err.to_string().contains("OPENAI_API_KEY"));
+    }
+}
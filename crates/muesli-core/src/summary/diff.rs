@@ -0,0 +1,178 @@
+// ABOUTME: Compares a regenerated summary with the version it replaced
+// ABOUTME: As a unified diff or two columns, so a new prompt or model can be judged before batch runs
+
+use crate::storage::CatalogEntry;
+use crate::sync::{previous_summary_path, summary_path};
+use crate::{Error, Paths, Result};
+use console::style;
+use similar::{ChangeTag, TextDiff};
+use std::path::PathBuf;
+
+/// The saved summary of a meeting and the one it replaced
+#[derive(Debug, Clone)]
+pub struct SummaryVersions {
+    pub previous_path: PathBuf,
+    pub previous: String,
+    pub current_path: PathBuf,
+    pub current: String,
+}
+
+impl SummaryVersions {
+    pub fn load(paths: &Paths, entry: &CatalogEntry) -> Result<Self> {
+        let current_path = summary_path(paths, &entry.path);
+        let previous_path = previous_summary_path(paths, &entry.path);
+        if !current_path.exists() {
+            return Err(Error::InvalidInput(format!(
+                "{} has no saved summary; run 'muesli summarize {} --save' first",
+                entry.doc_id, entry.doc_id
+            )));
+        }
+        if !previous_path.exists() {
+            return Err(Error::InvalidInput(format!(
+                "{} has only one summary version; the previous one is kept when 'summarize --save' replaces it",
+                entry.doc_id
+            )));
+        }
+        Ok(SummaryVersions {
+            previous: std::fs::read_to_string(&previous_path)?,
+            previous_path,
+            current: std::fs::read_to_string(&current_path)?,
+            current_path,
+        })
+    }
+
+    /// `diff -u` style, with `context` unchanged lines around each change
+    pub fn unified(&self, context: usize) -> String {
+        let diff = TextDiff::from_lines(&self.previous, &self.current);
+        let mut out = String::new();
+        out.push_str(&format!(
+            "{}\n{}\n",
+            style(format!("--- {}", self.previous_path.display())).bold(),
+            style(format!("+++ {}", self.current_path.display())).bold()
+        ));
+        for hunk in diff.unified_diff().context_radius(context).iter_hunks() {
+            out.push_str(&format!("{}\n", style(hunk.header()).cyan()));
+            for change in hunk.iter_changes() {
+                let line = format!("{}{}", change.tag(), change.value().trim_end_matches('\n'));
+                let line = match change.tag() {
+                    ChangeTag::Delete => style(line).red().to_string(),
+                    ChangeTag::Insert => style(line).green().to_string(),
+                    ChangeTag::Equal => line,
+                };
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Previous version on the left, current on the right, each `width` columns
+    ///
+    /// Changed lines are marked `|`, removed `<` and added `>` between the columns.
+    pub fn side_by_side(&self, width: usize) -> String {
+        let diff = TextDiff::from_lines(&self.previous, &self.current);
+        let mut rows: Vec<(String, char, String)> = Vec::new();
+        let (mut deleted, mut inserted) = (Vec::new(), Vec::new());
+
+        // Pair each run of removed lines with the added lines that replace it
+        let flush = |rows: &mut Vec<(String, char, String)>,
+                     deleted: &mut Vec<String>,
+                     inserted: &mut Vec<String>| {
+            for i in 0..deleted.len().max(inserted.len()) {
+                let (left, right) = (deleted.get(i), inserted.get(i));
+                let marker = match (left, right) {
+                    (Some(_), Some(_)) => '|',
+                    (Some(_), None) => '<',
+                    _ => '>',
+                };
+                rows.push((
+                    left.cloned().unwrap_or_default(),
+                    marker,
+                    right.cloned().unwrap_or_default(),
+                ));
+            }
+            deleted.clear();
+            inserted.clear();
+        };
+
+        for change in diff.iter_all_changes() {
+            let line = change.value().trim_end_matches('\n').to_string();
+            match change.tag() {
+                ChangeTag::Delete => deleted.push(line),
+                ChangeTag::Insert => inserted.push(line),
+                ChangeTag::Equal => {
+                    flush(&mut rows, &mut deleted, &mut inserted);
+                    rows.push((line.clone(), ' ', line));
+                }
+            }
+        }
+        flush(&mut rows, &mut deleted, &mut inserted);
+
+        let mut out = String::new();
+        for (left, marker, right) in rows {
+            let left = fit(&left, width);
+            let row = format!("{} {} {}", left, marker, fit(&right, width));
+            let row = row.trim_end().to_string();
+            let row = match marker {
+                ' ' => row,
+                '<' => style(row).red().to_string(),
+                '>' => style(row).green().to_string(),
+                _ => style(row).yellow().to_string(),
+            };
+            out.push_str(&row);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.previous == self.current
+    }
+}
+
+/// Pads or cuts `text` to exactly `width` characters
+fn fit(text: &str, width: usize) -> String {
+    let count = text.chars().count();
+    if count <= width {
+        format!("{}{}", text, " ".repeat(width - count))
+    } else {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(previous: &str, current: &str) -> SummaryVersions {
+        SummaryVersions {
+            previous_path: PathBuf::from("a_summary.previous.md"),
+            previous: previous.to_string(),
+            current_path: PathBuf::from("a_summary.md"),
+            current: current.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        console::set_colors_enabled(false);
+        let v = versions(
+            "# Summary\n- Ship Friday\n- Hire\n",
+            "# Summary\n- Ship Monday\n- Hire\n",
+        );
+        assert_eq!(
+            v.unified(3),
+            "--- a_summary.previous.md\n+++ a_summary.md\n@@ -1,3 +1,3 @@\n # Summary\n-- Ship Friday\n+- Ship Monday\n - Hire\n"
+        );
+        assert!(!v.is_unchanged());
+    }
+
+    #[test]
+    fn test_side_by_side() {
+        console::set_colors_enabled(false);
+        let v = versions("same\nold\ngone\n", "same\nnew\n");
+        assert_eq!(v.side_by_side(6), "same     same\nold    | new\ngone   <\n");
+        assert_eq!(fit("abcdefgh", 4), "abc…");
+    }
+}
@@ -0,0 +1,215 @@
+// ABOUTME: Incident timelines: timestamped events, contributing factors and follow-ups from an incident review
+// ABOUTME: Holds the timeline preset's prompt, its JSON shape and the postmortem markdown skeleton
+
+use super::structured::ActionItem;
+use super::SummaryConfig;
+use crate::{Frontmatter, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Preset `muesli incident-timeline` extracts with; no label selects it
+pub const INCIDENT_TIMELINE_PRESET: &str = "incident-timeline";
+
+/// Preset whose labels mark a meeting as an incident review
+pub const INCIDENT_PRESET: &str = "incident";
+
+pub(super) const INCIDENT_TIMELINE_PROMPT: &str = r#"You are preparing a postmortem from the transcript of an incident review meeting.
+
+Respond with only a JSON object, without prose or code fences, in exactly this shape:
+{"summary": "one or two sentences or null", "impact": "who or what was affected, and for how long, or null", "events": [{"time": "when it happened, as spoken, or null", "event": "...", "discussed_at": "transcript timestamp where it came up, or null"}], "contributing_factors": ["..."], "action_items": [{"task": "...", "owner": "name or null", "due": "date as spoken or null"}]}
+
+Rules:
+- events: everything that happened during the incident (trigger, detection, escalation, mitigation, resolution), in the order it happened.
+- time: keep times and dates exactly as spoken, with their time zone if one was given; null when no time was mentioned.
+- discussed_at: copy the timestamp from the transcript line where the event was described, if lines have one.
+- contributing_factors: technical and process conditions that caused the incident or made it worse, one sentence each.
+- action_items: follow-ups someone committed to; owner and due are null when not stated.
+- Keep it blameless: describe systems and decisions, not people's faults.
+- Use an empty array when there is nothing to list; label any inference as "(inferred)"."#;
+
+/// An incident review as data, for the postmortem document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IncidentTimeline {
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub impact: Option<String>,
+    pub events: Vec<TimelineEvent>,
+    pub contributing_factors: Vec<String>,
+    pub action_items: Vec<ActionItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineEvent {
+    /// When it happened, as spoken
+    #[serde(default)]
+    pub time: Option<String>,
+    pub event: String,
+    /// Where in the meeting it came up
+    #[serde(default)]
+    pub discussed_at: Option<String>,
+}
+
+/// Whether the meeting carries one of the incident preset's labels
+pub fn is_incident(config: &SummaryConfig, labels: &[String]) -> bool {
+    config
+        .presets()
+        .get(INCIDENT_PRESET)
+        .is_some_and(|preset| preset.matches(labels))
+}
+
+/// Parses a model reply, tolerating code fences or a sentence around the object
+pub fn parse(reply: &str) -> Result<IncidentTimeline> {
+    super::structured::parse_json(reply)
+}
+
+/// `summaries/<transcript name>_incident.md`, or `.json` for the structured timeline
+pub fn timeline_path(paths: &crate::Paths, transcript: &Path, json: bool) -> PathBuf {
+    let stem = transcript.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = if json { "json" } else { "md" };
+    paths
+        .summaries_dir
+        .join(format!("{}_incident.{}", stem, extension))
+}
+
+/// Placeholder for the parts a postmortem author still has to write
+const TODO: &str = "_TODO_";
+
+/// A postmortem skeleton: what was extracted, and TODOs for what the review didn't settle
+pub fn to_markdown(fm: &Frontmatter, timeline: &IncidentTimeline) -> String {
+    let title = fm.title.as_deref().unwrap_or("Untitled Meeting");
+    let mut out = format!("# Postmortem: {}\n\n", title);
+    out.push_str(&format!(
+        "_Review meeting: {}",
+        fm.created_at.format("%Y-%m-%d")
+    ));
+    if !fm.participants.is_empty() {
+        out.push_str(&format!(" · Participants: {}", fm.participants.join(", ")));
+    }
+    out.push_str("_\n\n");
+
+    for (heading, text) in [("Summary", &timeline.summary), ("Impact", &timeline.impact)] {
+        let text = text.as_deref().map(str::trim).filter(|t| !t.is_empty());
+        out.push_str(&format!("## {}\n\n{}\n\n", heading, text.unwrap_or(TODO)));
+    }
+
+    out.push_str("## Timeline\n\n");
+    if timeline.events.is_empty() {
+        out.push_str(&format!("{}\n\n", TODO));
+    }
+    for event in &timeline.events {
+        out.push_str("- ");
+        if let Some(time) = &event.time {
+            out.push_str(&format!("**{}** — ", time));
+        }
+        out.push_str(&event.event);
+        if let Some(at) = &event.discussed_at {
+            out.push_str(&format!(" _(discussed at {})_", at));
+        }
+        out.push('\n');
+    }
+    if !timeline.events.is_empty() {
+        out.push('\n');
+    }
+
+    out.push_str("## Contributing Factors\n\n");
+    if timeline.contributing_factors.is_empty() {
+        out.push_str(&format!("{}\n\n", TODO));
+    } else {
+        for factor in &timeline.contributing_factors {
+            out.push_str(&format!("- {}\n", factor));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Action Items\n\n");
+    if timeline.action_items.is_empty() {
+        out.push_str(&format!("{}\n\n", TODO));
+    } else {
+        for item in &timeline.action_items {
+            let details: Vec<&str> = [&item.owner, &item.due]
+                .into_iter()
+                .filter_map(|d| d.as_deref())
+                .collect();
+            if details.is_empty() {
+                out.push_str(&format!("- [ ] {}\n", item.task));
+            } else {
+                out.push_str(&format!("- [ ] {} ({})\n", item.task, details.join(", ")));
+            }
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&format!(
+        "## What Went Well\n\n{}\n\n## Lessons Learned\n\n{}\n",
+        TODO, TODO
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::incident_timeline_with;
+    use crate::testing::FakeSummarizer;
+
+    const REPLY: &str = r#"{"summary": "A bad config push took checkout down.", "impact": null,
+        "events": [{"time": "14:02 UTC", "event": "Config push to the payment service", "discussed_at": "00:01:10"},
+                   {"time": null, "event": "On-call paged by the error-rate alert", "discussed_at": null}],
+        "contributing_factors": ["No canary stage for config changes"],
+        "action_items": [{"task": "Add a config canary", "owner": "Sam", "due": null}]}"#;
+
+    #[test]
+    fn test_is_incident_uses_preset_labels() {
+        let config = SummaryConfig::default();
+        assert!(is_incident(&config, &["Postmortem".to_string()]));
+        assert!(!is_incident(&config, &["Interview".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_timeline_becomes_postmortem_skeleton() {
+        let mut summarizer = FakeSummarizer::new(REPLY);
+        let timeline = incident_timeline_with(
+            &mut summarizer,
+            "**Sam (00:01:10):** The push went out at 14:02 UTC\n",
+            &SummaryConfig::default(),
+        )
+        .await
+        .unwrap();
+        assert!(summarizer.prompts()[0].starts_with("You are preparing a postmortem"));
+        assert!(summarizer.prompts()[0].contains("**Sam (00:01:10):** The push went out"));
+
+        let fm = Frontmatter {
+            doc_id: "d1".into(),
+            source: "granola".into(),
+            created_at: "2025-03-02T09:00:00Z".parse().unwrap(),
+            remote_updated_at: None,
+            title: Some("Checkout outage review".into()),
+            participants: vec!["Sam".into()],
+            duration_seconds: None,
+            labels: vec!["Incident".into()],
+            organizations: Vec::new(),
+            inferred_speakers: Vec::new(),
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        };
+        let doc = to_markdown(&fm, &timeline);
+        assert!(doc.starts_with(
+            "# Postmortem: Checkout outage review\n\n_Review meeting: 2025-03-02 · Participants: Sam_\n\n\
+             ## Summary\n\nA bad config push took checkout down.\n\n## Impact\n\n_TODO_\n\n"
+        ));
+        assert!(doc.contains(
+            "## Timeline\n\n- **14:02 UTC** — Config push to the payment service _(discussed at 00:01:10)_\n\
+             - On-call paged by the error-rate alert\n\n"
+        ));
+        assert!(doc.contains("- [ ] Add a config canary (Sam)\n"));
+        assert!(doc.ends_with("## Lessons Learned\n\n_TODO_\n"));
+    }
+}
@@ -0,0 +1,206 @@
+// ABOUTME: Interview packets: questions, answers, strengths, concerns and quotes from an interview
+// ABOUTME: Holds the packet preset's prompt, its JSON shape and the markdown evaluation document
+
+use super::SummaryConfig;
+use crate::{Frontmatter, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Preset `muesli interview-packet` extracts with; no label selects it
+pub const INTERVIEW_PACKET_PRESET: &str = "interview-packet";
+
+/// Preset whose labels mark a meeting as an interview
+pub const INTERVIEW_PRESET: &str = "interview";
+
+pub(super) const INTERVIEW_PACKET_PROMPT: &str = r#"You are preparing an evaluation packet from a job interview transcript for the hiring team.
+
+Respond with only a JSON object, without prose or code fences, in exactly this shape:
+{"role": "role as stated or null", "questions": [{"question": "...", "answer": "..."}], "strengths": ["..."], "concerns": ["..."], "quotes": [{"speaker": "name or null", "text": "..."}], "candidate_questions": ["..."]}
+
+Rules:
+- questions: every substantive question the interviewers asked, in order, with the substance of the candidate's answer.
+- strengths and concerns: evidence from the answers, one sentence each, citing the question it came from.
+- quotes: the candidate's own words, verbatim, that best support a strength or concern.
+- candidate_questions: what the candidate asked the interviewers.
+- Report what was said; do not give a hire/no-hire recommendation.
+- Leave out anything about age, family, health, religion, or other protected characteristics.
+- Use an empty array when there is nothing to list; label any inference as "(inferred)"."#;
+
+/// An interview as data, for the hiring team's evaluation document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InterviewPacket {
+    #[serde(default)]
+    pub role: Option<String>,
+    pub questions: Vec<QuestionAnswer>,
+    pub strengths: Vec<String>,
+    pub concerns: Vec<String>,
+    pub quotes: Vec<Quote>,
+    #[serde(default)]
+    pub candidate_questions: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuestionAnswer {
+    pub question: String,
+    pub answer: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Quote {
+    #[serde(default)]
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// Whether the meeting carries one of the interview preset's labels
+pub fn is_interview(config: &SummaryConfig, labels: &[String]) -> bool {
+    config
+        .presets()
+        .get(INTERVIEW_PRESET)
+        .is_some_and(|preset| preset.matches(labels))
+}
+
+/// Parses a model reply, tolerating code fences or a sentence around the object
+pub fn parse(reply: &str) -> Result<InterviewPacket> {
+    super::structured::parse_json(reply)
+}
+
+/// `summaries/<transcript name>_interview.md`, or `.json` for the structured packet
+pub fn packet_path(paths: &crate::Paths, transcript: &Path, json: bool) -> PathBuf {
+    let stem = transcript.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = if json { "json" } else { "md" };
+    paths
+        .summaries_dir
+        .join(format!("{}_interview.{}", stem, extension))
+}
+
+/// The evaluation document: meeting details, then one section per part of the packet
+pub fn to_markdown(fm: &Frontmatter, packet: &InterviewPacket) -> String {
+    let title = fm.title.as_deref().unwrap_or("Untitled Meeting");
+    let mut out = format!("# Interview Packet: {}\n\n", title);
+    out.push_str(&format!("_Date: {}", fm.created_at.format("%Y-%m-%d")));
+    if !fm.participants.is_empty() {
+        out.push_str(&format!(" · Participants: {}", fm.participants.join(", ")));
+    }
+    out.push_str("_\n\n");
+    if let Some(role) = packet.role.as_deref().filter(|r| !r.trim().is_empty()) {
+        out.push_str(&format!("**Role:** {}\n\n", role.trim()));
+    }
+
+    out.push_str("## Questions and Answers\n\n");
+    if packet.questions.is_empty() {
+        out.push_str("None\n\n");
+    }
+    for (i, qa) in packet.questions.iter().enumerate() {
+        out.push_str(&format!(
+            "### {}. {}\n\n{}\n\n",
+            i + 1,
+            qa.question,
+            qa.answer
+        ));
+    }
+
+    for (heading, items) in [
+        ("Strengths", &packet.strengths),
+        ("Concerns", &packet.concerns),
+    ] {
+        out.push_str(&format!("## {}\n\n", heading));
+        out.push_str(&bullets(items));
+    }
+
+    out.push_str("## Quotes\n\n");
+    if packet.quotes.is_empty() {
+        out.push_str("None\n\n");
+    }
+    for quote in &packet.quotes {
+        out.push_str(&format!("> {}\n", quote.text));
+        if let Some(speaker) = &quote.speaker {
+            out.push_str(&format!(">\n> — {}\n", speaker));
+        }
+        out.push('\n');
+    }
+
+    out.push_str("## Candidate's Questions\n\n");
+    out.push_str(&bullets(&packet.candidate_questions));
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn bullets(items: &[String]) -> String {
+    if items.is_empty() {
+        return "None\n\n".to_string();
+    }
+    let mut out: String = items.iter().map(|item| format!("- {}\n", item)).collect();
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::interview_packet_with;
+    use crate::testing::FakeSummarizer;
+
+    const REPLY: &str = r#"{"role": "Backend Engineer",
+        "questions": [{"question": "Tell me about a hard outage", "answer": "Led the recovery of a failed database migration."}],
+        "strengths": ["Clear incident ownership (Q1)"],
+        "concerns": [],
+        "quotes": [{"speaker": "Dana", "text": "I wrote the rollback plan first."}],
+        "candidate_questions": ["How is on-call shared?"]}"#;
+
+    #[test]
+    fn test_is_interview_uses_preset_labels() {
+        let config = SummaryConfig::default();
+        assert!(is_interview(&config, &["Hiring".to_string()]));
+        assert!(!is_interview(&config, &["Standup".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_packet_is_extracted_and_rendered() {
+        let mut summarizer = FakeSummarizer::with_replies(&["not json", REPLY]);
+        let packet = interview_packet_with(
+            &mut summarizer,
+            "**Alex:** Tell me about a hard outage\n**Dana:** I wrote the rollback plan first.\n",
+            &SummaryConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(packet.role.as_deref(), Some("Backend Engineer"));
+        assert_eq!(summarizer.prompts().len(), 2);
+        assert!(summarizer.prompts()[0].starts_with("You are preparing an evaluation packet"));
+        assert!(summarizer.prompts()[1].contains("was rejected"));
+
+        let fm = Frontmatter {
+            doc_id: "d1".into(),
+            source: "granola".into(),
+            created_at: "2025-03-01T10:00:00Z".parse().unwrap(),
+            remote_updated_at: None,
+            title: Some("Interview: Dana".into()),
+            participants: vec!["Alex".into(), "Dana".into()],
+            duration_seconds: None,
+            labels: vec!["Interview".into()],
+            organizations: Vec::new(),
+            inferred_speakers: Vec::new(),
+            access: None,
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            notes: None,
+            calendar_event: None,
+            generator: "muesli 1.0".into(),
+        };
+        let doc = to_markdown(&fm, &packet);
+        assert!(doc.starts_with(
+            "# Interview Packet: Interview: Dana\n\n_Date: 2025-03-01 · Participants: Alex, Dana_\n\n**Role:** Backend Engineer\n"
+        ));
+        assert!(doc.contains("### 1. Tell me about a hard outage\n\nLed the recovery"));
+        assert!(doc.contains("## Concerns\n\nNone\n"));
+        assert!(doc.contains("> I wrote the rollback plan first.\n>\n> — Dana\n"));
+        assert!(doc.ends_with("## Candidate's Questions\n\n- How is on-call shared?\n"));
+    }
+}
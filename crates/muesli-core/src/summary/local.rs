@@ -0,0 +1,243 @@
+// ABOUTME: Offline summarization with a quantized GGUF model run on the CPU by candle
+// ABOUTME: Supports llama-architecture models (Llama 3.x, Mistral, TinyLlama) with their chat templates
+
+use super::SummaryConfig;
+use crate::{Error, Result};
+use candle_core::quantized::gguf_file;
+use candle_core::{Device, Tensor};
+use candle_transformers::generation::LogitsProcessor;
+use candle_transformers::models::quantized_llama::{ModelWeights, MAX_SEQ_LEN};
+use std::path::{Path, PathBuf};
+use tokenizers::Tokenizer;
+
+/// Longest summary generated for one prompt, in tokens
+const MAX_NEW_TOKENS: usize = 1024;
+
+/// Fixed so the same transcript and temperature give the same summary
+const SEED: u64 = 42;
+
+/// Tokens that end a reply in the chat formats we support
+const STOP_TOKENS: &[&str] = &[
+    "<|eot_id|>",
+    "<|end_of_text|>",
+    "<|im_end|>",
+    "<|endoftext|>",
+    "</s>",
+];
+
+fn candle_error(e: candle_core::Error) -> Error {
+    Error::Summarization(format!("Local model error: {}", e))
+}
+
+/// How a prompt is wrapped so an instruction-tuned model answers it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatTemplate {
+    Llama3,
+    ChatMl,
+    Mistral,
+    /// Base models without a chat template
+    Plain,
+}
+
+impl ChatTemplate {
+    /// Picks the format from the GGUF's `tokenizer.chat_template`
+    fn detect(template: Option<&str>) -> Self {
+        match template {
+            Some(t) if t.contains("<|start_header_id|>") => ChatTemplate::Llama3,
+            Some(t) if t.contains("<|im_start|>") => ChatTemplate::ChatMl,
+            Some(t) if t.contains("[INST]") => ChatTemplate::Mistral,
+            _ => ChatTemplate::Plain,
+        }
+    }
+
+    fn wrap(self, prompt: &str) -> String {
+        match self {
+            ChatTemplate::Llama3 => format!(
+                "<|begin_of_text|><|start_header_id|>user<|end_header_id|>\n\n{}<|eot_id|>\
+                 <|start_header_id|>assistant<|end_header_id|>\n\n",
+                prompt
+            ),
+            ChatTemplate::ChatMl => format!(
+                "<|im_start|>user\n{}<|im_end|>\n<|im_start|>assistant\n",
+                prompt
+            ),
+            ChatTemplate::Mistral => format!("[INST] {} [/INST]", prompt),
+            ChatTemplate::Plain => format!("{}\n\nSummary:\n", prompt),
+        }
+    }
+
+    /// Whether the tokenizer should add its own start-of-text token
+    fn adds_bos(self) -> bool {
+        matches!(self, ChatTemplate::Mistral | ChatTemplate::Plain)
+    }
+}
+
+/// The tokenizer to use with a model: configured, or tokenizer.json beside the GGUF
+fn tokenizer_path(model: &Path, configured: Option<&Path>) -> PathBuf {
+    match configured {
+        Some(path) => path.to_path_buf(),
+        None => model.with_file_name("tokenizer.json"),
+    }
+}
+
+/// A GGUF model loaded into memory
+pub struct LocalProvider {
+    model: ModelWeights,
+    tokenizer: Tokenizer,
+    template: ChatTemplate,
+    stop_tokens: Vec<u32>,
+    temperature: Option<f32>,
+    device: Device,
+}
+
+impl LocalProvider {
+    /// Loads the model and tokenizer named in the config
+    pub fn load(config: &SummaryConfig) -> Result<Self> {
+        let model_path = config.local_model.as_deref().ok_or_else(|| {
+            Error::Summarization(
+                "No local model configured. Set one with: muesli set-config --local-model <file.gguf>"
+                    .into(),
+            )
+        })?;
+        let tokenizer_path = tokenizer_path(model_path, config.local_tokenizer.as_deref());
+
+        let mut file = std::fs::File::open(model_path).map_err(|e| {
+            Error::Summarization(format!("Cannot open {}: {}", model_path.display(), e))
+        })?;
+        let content = gguf_file::Content::read(&mut file).map_err(|e| {
+            Error::Summarization(format!(
+                "{} is not a GGUF model: {}",
+                model_path.display(),
+                e
+            ))
+        })?;
+        let template = ChatTemplate::detect(
+            content
+                .metadata
+                .get("tokenizer.chat_template")
+                .and_then(|v| v.to_string().ok())
+                .map(String::as_str),
+        );
+
+        let device = Device::Cpu;
+        let model = ModelWeights::from_gguf(content, &mut file, &device).map_err(candle_error)?;
+        let tokenizer = Tokenizer::from_file(&tokenizer_path).map_err(|e| {
+            Error::Summarization(format!(
+                "Cannot load tokenizer {} (set it with 'muesli set-config --local-tokenizer'): {}",
+                tokenizer_path.display(),
+                e
+            ))
+        })?;
+        let stop_tokens = STOP_TOKENS
+            .iter()
+            .filter_map(|t| tokenizer.token_to_id(t))
+            .collect();
+
+        Ok(Self {
+            model,
+            tokenizer,
+            template,
+            stop_tokens,
+            temperature: config.temperature,
+            device,
+        })
+    }
+
+    /// Generates a reply to the prompt
+    ///
+    /// Runs on the calling thread; a summary takes minutes on a laptop CPU.
+    pub fn complete(&mut self, prompt: String) -> Result<String> {
+        let prompt = self.template.wrap(&prompt);
+        let encoding = self
+            .tokenizer
+            .encode(prompt, self.template.adds_bos())
+            .map_err(|e| Error::Summarization(format!("Tokenization failed: {}", e)))?;
+        let tokens = encoding.get_ids();
+
+        if tokens.len() + MAX_NEW_TOKENS > MAX_SEQ_LEN {
+            return Err(Error::Summarization(format!(
+                "Prompt is {} tokens but the local model fits {} including the summary; \
+                 lower the context window (e.g. muesli set-config --context-window 8000)",
+                tokens.len(),
+                MAX_SEQ_LEN
+            )));
+        }
+
+        let mut sampler = LogitsProcessor::new(SEED, self.temperature.map(f64::from), None);
+        let mut generated = Vec::new();
+        let mut input = Tensor::new(tokens, &self.device).map_err(candle_error)?;
+        let mut position = 0;
+
+        for _ in 0..MAX_NEW_TOKENS {
+            let step = input.dims1().map_err(candle_error)?;
+            let logits = self
+                .model
+                .forward(&input.unsqueeze(0).map_err(candle_error)?, position)
+                .and_then(|logits| logits.squeeze(0))
+                .map_err(candle_error)?;
+            position += step;
+
+            let next = sampler.sample(&logits).map_err(candle_error)?;
+            if self.stop_tokens.contains(&next) {
+                break;
+            }
+            generated.push(next);
+            input = Tensor::new(&[next], &self.device).map_err(candle_error)?;
+        }
+
+        let text = self
+            .tokenizer
+            .decode(&generated, true)
+            .map_err(|e| Error::Summarization(format!("Decoding failed: {}", e)))?;
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(Error::Summarization("No response from local model".into()));
+        }
+        Ok(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chat_template_detection() {
+        let llama3 = "{{ '<|start_header_id|>' + message['role'] + '<|end_header_id|>' }}";
+        assert_eq!(ChatTemplate::detect(Some(llama3)), ChatTemplate::Llama3);
+        assert_eq!(
+            ChatTemplate::detect(Some("{{'<|im_start|>' + message['role']}}")),
+            ChatTemplate::ChatMl
+        );
+        assert_eq!(
+            ChatTemplate::detect(Some("{{ '[INST] ' + message['content'] }}")),
+            ChatTemplate::Mistral
+        );
+        assert_eq!(ChatTemplate::detect(None), ChatTemplate::Plain);
+
+        let wrapped = ChatTemplate::ChatMl.wrap("Summarize");
+        assert!(wrapped.ends_with("<|im_start|>assistant\n"));
+        assert!(!ChatTemplate::Llama3.adds_bos());
+    }
+
+    #[test]
+    fn test_tokenizer_path_defaults_to_model_directory() {
+        let model = Path::new("/models/llama-3.2-3b-instruct-q4_k_m.gguf");
+        assert_eq!(
+            tokenizer_path(model, None),
+            PathBuf::from("/models/tokenizer.json")
+        );
+        assert_eq!(
+            tokenizer_path(model, Some(Path::new("/tok/llama3.json"))),
+            PathBuf::from("/tok/llama3.json")
+        );
+    }
+
+    #[test]
+    fn test_load_requires_configured_model() {
+        let err = LocalProvider::load(&SummaryConfig::default())
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("--local-model"));
+    }
+}
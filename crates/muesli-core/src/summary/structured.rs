@@ -0,0 +1,97 @@
+// ABOUTME: Structured (JSON) summaries: decisions, action items and risks as typed lists
+// ABOUTME: Holds the schema the model must answer in and the lenient-but-validated parser
+
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+/// How many times the model may answer before invalid JSON is an error
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// Prompt for `summarize --format json`; the reply must parse as [`StructuredSummary`]
+pub const STRUCTURED_PROMPT: &str = r#"You are extracting the outcomes of a meeting from its transcript.
+
+Respond with only a JSON object, without prose or code fences, in exactly this shape:
+{"decisions": ["..."], "action_items": [{"task": "...", "owner": "name or null", "due": "date as spoken or null"}], "risks": ["..."]}
+
+Rules:
+- decisions: choices the participants actually agreed on, one sentence each.
+- action_items: concrete tasks someone committed to; owner and due are null when not stated.
+- risks: concerns, blockers and open questions raised.
+- Use an empty array when there is nothing to list.
+- Only use information from the transcript; preserve names, dates, and numbers exactly."#;
+
+/// A meeting summary as data, for exporters that shouldn't parse markdown
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StructuredSummary {
+    pub decisions: Vec<String>,
+    pub action_items: Vec<ActionItem>,
+    pub risks: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActionItem {
+    pub task: String,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
+/// Parses a model reply, tolerating code fences or a sentence around the object
+pub fn parse(reply: &str) -> Result<StructuredSummary> {
+    parse_json(reply)
+}
+
+/// Parses the first `{` through the last `}` of a reply as `T`
+pub(crate) fn parse_json<T: serde::de::DeserializeOwned>(reply: &str) -> Result<T> {
+    let json = match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => return Err(Error::Summarization("Reply contains no JSON object".into())),
+    };
+    serde_json::from_str(json)
+        .map_err(|e| Error::Summarization(format!("Reply doesn't match the schema: {}", e)))
+}
+
+/// The prompt again, told what was wrong with the last reply
+pub fn retry_prompt(prompt: &str, error: &Error) -> String {
+    format!(
+        "{}\n\nYour previous reply was rejected ({}). Reply again with only the JSON object.",
+        prompt, error
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fenced_reply() {
+        let reply = "Here you go:\n```json\n{\"decisions\": [\"Ship Friday\"], \
+                     \"action_items\": [{\"task\": \"Write notes\", \"owner\": \"Alice\", \"due\": null}], \
+                     \"risks\": []}\n```";
+        let summary = parse(reply).unwrap();
+        assert_eq!(summary.decisions, vec!["Ship Friday"]);
+        assert_eq!(
+            summary.action_items,
+            vec![ActionItem {
+                task: "Write notes".into(),
+                owner: Some("Alice".into()),
+                due: None,
+            }]
+        );
+        assert!(summary.risks.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_other_shapes() {
+        assert!(parse("No decisions were made.").is_err());
+        assert!(parse(r#"{"decisions": [], "risks": []}"#).is_err());
+        assert!(
+            parse(r#"{"decisions": [], "action_items": [], "risks": [], "mood": "ok"}"#).is_err()
+        );
+        let err = parse(r#"{"decisions": "none", "action_items": [], "risks": []}"#).unwrap_err();
+        assert!(retry_prompt("P", &err).contains("doesn't match the schema"));
+    }
+}
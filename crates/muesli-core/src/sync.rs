@@ -0,0 +1,2262 @@
+// ABOUTME: Core sync logic for fetching and storing documents
+// ABOUTME: Handles update detection and progress reporting
+
+use crate::{
+    api::{DocumentSource, GRANOLA_SOURCE},
+    convert::{to_markdown_with_local, LocalFields, MarkdownOptions},
+    notes, perf,
+    speakers::{self, SpeakerNaming},
+    storage::{read_frontmatter, set_file_time, write_atomic, write_frontmatter, Catalog, Paths},
+    term, timings,
+    util::SlugOptions,
+    warnings::Warnings,
+    DocumentSummary, Error, Result,
+};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
+
+#[cfg(feature = "index")]
+use crate::index::{text, writer::WriterConfig};
+#[cfg(feature = "index")]
+use crate::{speakers::SpeakerAliases, util::organizations_from_participants};
+
+#[cfg(feature = "semantic")]
+use crate::embeddings::{
+    downloader,
+    engine::{Embedder, EmbeddingEngine},
+    models,
+    vector::VectorStore,
+};
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    filename: String,
+    updated_at: DateTime<Utc>,
+    /// The source that listed the document; caches from before imports only held Granola's
+    #[serde(default = "granola_source")]
+    source: String,
+}
+
+fn granola_source() -> String {
+    GRANOLA_SOURCE.to_string()
+}
+
+/// What the last syncs left behind: a file per document, and where the feed stopped
+#[derive(Default, Serialize, Deserialize)]
+struct SyncCache {
+    /// Updates feed position after the last complete sync; unset means list everything
+    #[serde(rename = "$cursor", default, skip_serializing_if = "Option::is_none")]
+    cursor: Option<String>,
+    /// When the feed last failed; it isn't tried again until `FEED_RETRY` later
+    #[serde(
+        rename = "$feed_failed_at",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    feed_failed_at: Option<DateTime<Utc>>,
+    /// doc_id -> file and remote timestamp
+    #[serde(flatten)]
+    documents: HashMap<String, CacheEntry>,
+}
+
+/// Load the sync cache
+fn load_cache(cache_path: &std::path::Path) -> SyncCache {
+    if !cache_path.exists() {
+        return SyncCache::default();
+    }
+
+    std::fs::read_to_string(cache_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Save the sync cache atomically
+fn save_cache(
+    cache_path: &std::path::Path,
+    cache: &SyncCache,
+    tmp_dir: &std::path::Path,
+) -> Result<()> {
+    let json = serde_json::to_string_pretty(cache)?;
+    write_atomic(cache_path, json.as_bytes(), tmp_dir)?;
+    Ok(())
+}
+
+/// Forgets cached documents whose markdown or raw JSON is missing on disk
+///
+/// The next sync then treats them as new and downloads them again. Returns the
+/// affected document IDs, sorted.
+pub fn drop_missing_cache_entries(paths: &Paths) -> Result<Vec<String>> {
+    let cache_path = paths.data_dir.join(".sync_cache.json");
+    let mut cache = load_cache(&cache_path);
+
+    let mut missing: Vec<String> = cache
+        .documents
+        .iter()
+        .filter(|(_, entry)| {
+            let md = paths.transcripts_dir.join(format!("{}.md", entry.filename));
+            let json = paths.raw_dir.join(format!("{}.json", entry.filename));
+            !md.exists() || !json.exists()
+        })
+        .map(|(doc_id, _)| doc_id.clone())
+        .collect();
+
+    if !missing.is_empty() {
+        for doc_id in &missing {
+            cache.documents.remove(doc_id);
+        }
+        // The feed won't list them again; the next sync lists everything
+        cache.cursor = None;
+        save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+    }
+
+    missing.sort();
+    Ok(missing)
+}
+
+/// Options controlling a sync run
+#[derive(Debug, Clone, Default)]
+pub struct SyncOptions {
+    /// Rebuild the text index from local files without downloading
+    pub reindex: bool,
+    /// With `reindex`, also regenerate every embedding from local files
+    pub embeddings: bool,
+    /// Allow `embeddings` to replace vectors built with a different model
+    pub migrate_model: bool,
+    /// Remove local copies of documents that were deleted remotely
+    pub prune: bool,
+    /// Only report what a sync would change, without writing anything
+    pub dry_run: bool,
+    /// Only sync documents created on or after this date (UTC)
+    pub since: Option<NaiveDate>,
+    /// Only sync documents created on or before this date (UTC)
+    pub until: Option<NaiveDate>,
+    /// Process the oldest meetings first instead of the newest
+    pub oldest_first: bool,
+    /// Stop after downloading or updating this many documents
+    pub max_docs: Option<usize>,
+    /// Document IDs to re-download, re-index and re-embed regardless of the cache
+    pub force: Vec<String>,
+    /// Refresh every transcript's related-meetings footer after syncing
+    pub link: bool,
+    /// Name generic "Speaker 1/2" labels after the meeting's two attendees
+    pub name_speakers: SpeakerNaming,
+    /// Overrides `min_confidence` from `[markdown]`
+    pub min_confidence: Option<f64>,
+    /// With `embeddings`, documents embedded between checkpoints
+    /// (`DEFAULT_EMBED_BATCH` when unset)
+    pub embed_batch_size: Option<usize>,
+}
+
+/// Orders documents by creation date so an interrupted sync has the most
+/// important end of the range (newest by default) on disk already
+fn sort_by_priority(docs: &mut [DocumentSummary], oldest_first: bool) {
+    if oldest_first {
+        docs.sort_by_key(|d| d.created_at);
+    } else {
+        docs.sort_by_key(|d| Reverse(d.created_at));
+    }
+}
+
+/// Applies the date range and priority order; forced documents bypass the
+/// range and go first so `--max-docs` cannot skip them
+fn select_docs(docs: Vec<DocumentSummary>, options: &SyncOptions) -> Vec<DocumentSummary> {
+    let (mut selected, rest): (Vec<_>, Vec<_>) = docs
+        .into_iter()
+        .partition(|d| options.force.contains(&d.id));
+
+    let mut rest = in_date_range(rest, options.since, options.until);
+    sort_by_priority(&mut rest, options.oldest_first);
+    selected.extend(rest);
+    selected
+}
+
+/// Keeps documents whose creation date falls within the inclusive range
+fn in_date_range(
+    docs: Vec<DocumentSummary>,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Vec<DocumentSummary> {
+    docs.into_iter()
+        .filter(|doc| {
+            let date = doc.created_at.date_naive();
+            since.map_or(true, |s| date >= s) && until.map_or(true, |u| date <= u)
+        })
+        .collect()
+}
+
+/// A change a sync would make to one document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlannedChange {
+    /// Not synced before
+    Download,
+    /// Remote is newer than the cached copy
+    Update,
+    /// Remote is newer and its filename changes (title or date changed)
+    Rename { from: String, to: String },
+    /// Up to date on disk but missing an embedding
+    Embed,
+    /// Deleted remotely; local copies would be removed
+    Prune { filename: String },
+}
+
+/// One line of a sync plan
+#[derive(Debug, Clone)]
+pub struct PlanEntry {
+    pub doc_id: String,
+    pub title: Option<String>,
+    pub change: PlannedChange,
+}
+
+impl std::fmt::Display for PlanEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let title = self.title.as_deref().unwrap_or("Untitled");
+        match &self.change {
+            PlannedChange::Download => write!(f, "download  {}  {}", self.doc_id, title),
+            PlannedChange::Update => write!(f, "update    {}  {}", self.doc_id, title),
+            PlannedChange::Rename { from, to } => {
+                write!(f, "rename    {}  {} -> {}", self.doc_id, from, to)
+            }
+            PlannedChange::Embed => write!(f, "embed     {}  {}", self.doc_id, title),
+            PlannedChange::Prune { filename } => {
+                write!(f, "prune     {}  {}", self.doc_id, filename)
+            }
+        }
+    }
+}
+
+/// Base filename (without extension) used for a document's markdown and raw JSON
+fn base_filename(slugs: &SlugOptions, created_at: &DateTime<Utc>, title: Option<&str>) -> String {
+    format!(
+        "{}_{}",
+        created_at.format("%Y-%m-%d"),
+        slugs.slugify(title.unwrap_or("untitled"))
+    )
+}
+
+/// Computes what a sync would do using the same cache comparison as `sync_all`
+///
+/// Renames are predicted from the listing's title; the metadata title used during
+/// the actual sync normally matches it. Like `sync_all`, forced documents count as
+/// updates and planning stops once `max_docs` documents would be written.
+fn plan_sync(
+    docs: &[DocumentSummary],
+    cache: &HashMap<String, CacheEntry>,
+    orphans: &[String],
+    has_embedding: impl Fn(&str) -> bool,
+    options: &SyncOptions,
+    slugs: &SlugOptions,
+) -> Vec<PlanEntry> {
+    let mut plan = Vec::new();
+    let mut written = 0;
+
+    for doc in docs {
+        if options.max_docs.is_some_and(|max| written >= max) {
+            break;
+        }
+
+        let change = match cache.get(&doc.id) {
+            None => Some(PlannedChange::Download),
+            Some(entry)
+                if options.force.contains(&doc.id)
+                    || doc.updated_at.unwrap_or(doc.created_at) > entry.updated_at =>
+            {
+                let filename = base_filename(slugs, &doc.created_at, doc.title.as_deref());
+                if filename != entry.filename {
+                    Some(PlannedChange::Rename {
+                        from: entry.filename.clone(),
+                        to: filename,
+                    })
+                } else {
+                    Some(PlannedChange::Update)
+                }
+            }
+            Some(_) if !has_embedding(&doc.id) => Some(PlannedChange::Embed),
+            Some(_) => None,
+        };
+
+        if let Some(change) = change {
+            if change != PlannedChange::Embed {
+                written += 1;
+            }
+            plan.push(PlanEntry {
+                doc_id: doc.id.clone(),
+                title: doc.title.clone(),
+                change,
+            });
+        }
+    }
+
+    for doc_id in orphans {
+        if let Some(entry) = cache.get(doc_id) {
+            plan.push(PlanEntry {
+                doc_id: doc_id.clone(),
+                title: None,
+                change: PlannedChange::Prune {
+                    filename: entry.filename.clone(),
+                },
+            });
+        }
+    }
+
+    plan
+}
+
+/// Returns cached doc_ids of `source` that no longer appear in its listing, sorted
+///
+/// Documents from other sources, e.g. imported captions, are never orphans of this listing.
+fn plan_prune(
+    cache: &HashMap<String, CacheEntry>,
+    remote_ids: &HashSet<&str>,
+    source: &str,
+) -> Vec<String> {
+    let mut orphans: Vec<String> = cache
+        .iter()
+        .filter(|(id, entry)| entry.source == source && !remote_ids.contains(id.as_str()))
+        .map(|(id, _)| id.clone())
+        .collect();
+    orphans.sort();
+    orphans
+}
+
+/// Deletes the markdown, raw JSON, saved summary, timings, notes and cached API responses of a document
+fn remove_local_files(paths: &Paths, doc_id: &str, filename: &str) -> Result<()> {
+    let candidates = [
+        paths.transcripts_dir.join(format!("{}.md", filename)),
+        paths.raw_dir.join(format!("{}.json", filename)),
+        paths.summaries_dir.join(format!("{}_summary.md", filename)),
+        timings::path_for(paths, filename),
+        notes::path_for(paths, filename),
+    ];
+
+    for path in candidates {
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+    }
+
+    crate::api::forget_cached(paths, doc_id)
+}
+
+/// Deletes a document's local files and drops it from the sync cache and catalog
+///
+/// `filename` is the transcript's file name without extension. Unless the
+/// document is listed elsewhere (see `merged_duplicates`), the next sync
+/// downloads it again.
+pub fn remove_document(paths: &Paths, doc_id: &str, filename: &str) -> Result<()> {
+    let cache_path = paths.data_dir.join(".sync_cache.json");
+    let mut cache = load_cache(&cache_path);
+    if cache.documents.remove(doc_id).is_some() {
+        cache.cursor = None;
+        save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+    }
+
+    remove_local_files(paths, doc_id, filename)?;
+    Catalog::open(paths)?.remove(doc_id)?;
+    Ok(())
+}
+
+/// Prints what a sync with these options would change, without writing anything
+async fn print_plan(
+    source: &dyn DocumentSource,
+    paths: &Paths,
+    options: &SyncOptions,
+) -> Result<()> {
+    println!("Fetching document list...");
+    let docs = source.list_documents().await?;
+    let cache = load_cache(&paths.data_dir.join(".sync_cache.json"));
+
+    let orphans = if options.prune && !docs.is_empty() {
+        let remote_ids: HashSet<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        plan_prune(&cache.documents, &remote_ids, source.name())
+    } else {
+        Vec::new()
+    };
+
+    let docs = select_docs(docs, options);
+
+    // Loading the vector store does not require the embedding model
+    #[cfg(feature = "semantic")]
+    let vector_store = {
+        let vector_path = paths.index_dir.join("vectors");
+        if paths.index_dir.join("vectors.meta.json").exists() {
+            Some(VectorStore::load(&vector_path)?)
+        } else {
+            None
+        }
+    };
+    #[cfg(feature = "semantic")]
+    let has_embedding = |id: &str| {
+        vector_store
+            .as_ref()
+            .is_some_and(|store| store.has_document(id))
+    };
+    #[cfg(not(feature = "semantic"))]
+    let has_embedding = |_: &str| true;
+
+    let slugs = SlugOptions::configured(paths)?;
+    let plan = plan_sync(
+        &docs,
+        &cache.documents,
+        &orphans,
+        has_embedding,
+        options,
+        &slugs,
+    );
+    if plan.is_empty() {
+        println!("Nothing to do; everything is up to date");
+        return Ok(());
+    }
+
+    for entry in &plan {
+        println!("{}", entry);
+    }
+
+    let count = |f: fn(&PlannedChange) -> bool| plan.iter().filter(|e| f(&e.change)).count();
+    println!(
+        "Would download {}, update {}, rename {}, embed {}, prune {} (dry run, nothing written)",
+        count(|c| matches!(c, PlannedChange::Download)),
+        count(|c| matches!(c, PlannedChange::Update)),
+        count(|c| matches!(c, PlannedChange::Rename { .. })),
+        count(|c| matches!(c, PlannedChange::Embed)),
+        count(|c| matches!(c, PlannedChange::Prune { .. })),
+    );
+
+    Ok(())
+}
+
+/// Downloads new and changed documents from `source`, then indexes and embeds them
+pub async fn sync_all(
+    source: &dyn DocumentSource,
+    paths: &Paths,
+    options: &SyncOptions,
+) -> Result<()> {
+    check_date_range(options)?;
+
+    // Preview the plan before creating directories, opening the index or loading models
+    if options.dry_run {
+        return print_plan(source, paths, options).await;
+    }
+
+    paths.ensure_dirs()?;
+
+    // Handle reindex mode (feature-gated)
+    #[cfg(feature = "index")]
+    if options.reindex {
+        reindex_all(paths)?;
+        #[cfg(feature = "semantic")]
+        if options.embeddings {
+            reembed_all(
+                paths,
+                options.migrate_model,
+                options.embed_batch_size.unwrap_or(DEFAULT_EMBED_BATCH),
+            )?;
+        }
+        refresh_manifest(paths);
+        return Ok(());
+    }
+
+    #[cfg(feature = "semantic")]
+    {
+        println!("Initializing embedding engine...");
+
+        // Ensure model is downloaded
+        let model_paths = downloader::ensure_model(&paths.models_dir, models::configured(paths)?)?;
+
+        // Create embedding engine
+        let mut engine = EmbeddingEngine::new(&model_paths)?;
+        println!(
+            "{}Embedding engine ready ({}, dimension: {})",
+            term::emoji("✅ "),
+            model_paths.model.name,
+            engine.dim()
+        );
+
+        return sync_documents(source, &mut engine, paths, options).await;
+    }
+
+    #[cfg(not(feature = "semantic"))]
+    return sync_documents(source, paths, options).await;
+}
+
+/// Days a failed updates feed is left alone before sync tries it again
+const FEED_RETRY_DAYS: i64 = 7;
+
+/// The documents to look at: changes from the updates feed, or every document
+///
+/// The feed continues from the cursor the last complete sync saved; without one
+/// it lists everything once. Pruning and `--force` need the full listing, as does
+/// a source without a feed. A feed that fails is noted in the cache and skipped,
+/// without another warning, for `FEED_RETRY_DAYS`. Returns the feed position to save
+/// after syncing.
+async fn list_changes(
+    source: &dyn DocumentSource,
+    cache: &mut SyncCache,
+    options: &SyncOptions,
+) -> Result<(Vec<DocumentSummary>, Option<String>)> {
+    let retry = chrono::Duration::days(FEED_RETRY_DAYS);
+    let feed_failed = cache
+        .feed_failed_at
+        .is_some_and(|failed| Utc::now() - failed < retry);
+    if !options.prune && options.force.is_empty() && !feed_failed {
+        if cache.cursor.is_some() {
+            println!("Fetching changes since the last sync...");
+        } else {
+            println!("Fetching document list...");
+        }
+        match read_feed(source, cache.cursor.as_deref()).await {
+            Ok(Some((docs, cursor))) => {
+                cache.feed_failed_at = None;
+                return Ok((docs, cursor));
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to read the updates feed, listing every document until {}: {}",
+                    (Utc::now() + retry).format("%Y-%m-%d"),
+                    e
+                );
+                cache.feed_failed_at = Some(Utc::now());
+            }
+        }
+    } else {
+        println!("Fetching document list...");
+    }
+    Ok((source.list_documents().await?, None))
+}
+
+/// Every page of the feed after `since`; a document changed twice is listed once
+async fn read_feed(
+    source: &dyn DocumentSource,
+    since: Option<&str>,
+) -> Result<Option<(Vec<DocumentSummary>, Option<String>)>> {
+    let mut cursor = since.map(str::to_string);
+    let mut docs: Vec<DocumentSummary> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    loop {
+        let Some(page) = source.get_updates(cursor.as_deref()).await? else {
+            return Ok(None);
+        };
+        for doc in page.changes {
+            match positions.get(&doc.id) {
+                Some(&i) => docs[i] = doc,
+                None => {
+                    positions.insert(doc.id.clone(), docs.len());
+                    docs.push(doc);
+                }
+            }
+        }
+        // A page that doesn't move the cursor would be served again forever
+        let advanced = page.cursor.is_some() && page.cursor != cursor;
+        if page.cursor.is_some() {
+            cursor = page.cursor;
+        }
+        if !page.has_more || !advanced {
+            break;
+        }
+    }
+    Ok(Some((docs, cursor)))
+}
+
+fn check_date_range(options: &SyncOptions) -> Result<()> {
+    match (options.since, options.until) {
+        (Some(since), Some(until)) if since > until => Err(Error::InvalidInput(format!(
+            "--since {} is after --until {}",
+            since, until
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Syncs like [`sync_all`], embedding with `embedder` instead of the configured model
+///
+/// Dry runs and `--reindex` go through [`sync_all`]; this only runs the sync itself.
+#[cfg(feature = "semantic")]
+pub async fn sync_with_embedder(
+    source: &dyn DocumentSource,
+    embedder: &mut dyn Embedder,
+    paths: &Paths,
+    options: &SyncOptions,
+) -> Result<()> {
+    check_date_range(options)?;
+    paths.ensure_dirs()?;
+    sync_documents(source, embedder, paths, options).await
+}
+
+async fn sync_documents(
+    source: &dyn DocumentSource,
+    #[cfg(feature = "semantic")] embedder: &mut dyn Embedder,
+    paths: &Paths,
+    options: &SyncOptions,
+) -> Result<()> {
+    // Indexes from older versions lack newer fields; rebuild them from local files
+    #[cfg(feature = "index")]
+    if text::schema_is_outdated(&paths.index_dir) {
+        println!("Search index format changed; rebuilding from local files...");
+        reindex_all(paths)?;
+    }
+
+    // Create or open the index and writer (feature-gated)
+    #[cfg(feature = "index")]
+    let writer_config = WriterConfig::configured(paths)?;
+    #[cfg(feature = "index")]
+    let (index, mut writer) = {
+        let idx = text::create_or_open_index(&paths.index_dir)?;
+        let wtr = writer_config.writer(&idx)?;
+        (idx, wtr)
+    };
+    #[cfg(feature = "index")]
+    let mut uncommitted = 0;
+
+    // Load or create the vector store (feature-gated)
+    #[cfg(feature = "semantic")]
+    let mut vector_store = {
+        let vector_path = paths.index_dir.join("vectors");
+        let metadata_path = paths.index_dir.join("vectors.meta.json");
+        let mut store = if metadata_path.exists() {
+            println!("Loading existing vector store...");
+            VectorStore::load(&vector_path)?
+        } else {
+            println!("Creating new vector store");
+            VectorStore::new(embedder.dim())
+        };
+        if store.is_empty() && store.dim() != embedder.dim() {
+            store = VectorStore::new(embedder.dim());
+        }
+        // Never mix vectors from two models in one store
+        store.pin_model(embedder.model())?;
+        store
+    };
+
+    let mut markdown_options = MarkdownOptions::configured(paths)?;
+    let slugs = SlugOptions::configured(paths)?;
+    let mut warnings = Warnings::new(paths, "sync");
+    if options.min_confidence.is_some() {
+        markdown_options.min_confidence = options.min_confidence;
+    }
+
+    // Prompts need someone at the keyboard; otherwise only confident guesses apply
+    let naming = match options.name_speakers {
+        SpeakerNaming::Ask if !std::io::stdin().is_terminal() => SpeakerNaming::Auto,
+        naming => naming,
+    };
+
+    // Load the sync cache (instant)
+    let cache_path = paths.data_dir.join(".sync_cache.json");
+    let mut cache = load_cache(&cache_path);
+
+    let feed_failed_at = cache.feed_failed_at;
+    let (docs, feed_cursor) = {
+        let _span = perf::span("sync.api");
+        list_changes(source, &mut cache, options).await?
+    };
+    if cache.feed_failed_at != feed_failed_at {
+        save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+    }
+
+    let catalog = Catalog::open(paths)?;
+
+    // Duplicates merged away stay gone; hidden ones are kept out of search
+    let mut merged = HashSet::new();
+    let mut hidden = HashSet::new();
+    for entry in catalog.entries()? {
+        merged.extend(entry.merged_duplicates);
+        if entry.duplicate_of.is_some() {
+            hidden.insert(entry.doc_id);
+        }
+    }
+
+    let mut pruned = 0;
+
+    if options.prune {
+        let remote_ids: HashSet<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+
+        let listed_before = cache.documents.values().any(|e| e.source == source.name());
+        if remote_ids.is_empty() && listed_before {
+            // An empty listing is far more likely an API hiccup than a wiped account
+            eprintln!("Warning: Remote returned no documents; skipping prune");
+        } else {
+            for doc_id in plan_prune(&cache.documents, &remote_ids, source.name()) {
+                if let Some(entry) = cache.documents.remove(&doc_id) {
+                    remove_local_files(paths, &doc_id, &entry.filename)?;
+                }
+                catalog.remove(&doc_id)?;
+
+                #[cfg(feature = "index")]
+                if let Err(e) = text::delete_document(&mut writer, &index, &doc_id) {
+                    warnings.warn("Failed to unindex document", format!("{}: {}", doc_id, e));
+                }
+
+                #[cfg(feature = "semantic")]
+                vector_store.remove_document(&doc_id);
+
+                println!("Pruned {}", doc_id);
+                pruned += 1;
+            }
+
+            if pruned > 0 {
+                save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+            }
+        }
+    }
+
+    for doc_id in &options.force {
+        if !docs.iter().any(|d| &d.id == doc_id) {
+            eprintln!(
+                "Warning: --force {}: not in the remote document list",
+                doc_id
+            );
+        }
+    }
+
+    // Date filters apply after pruning, which must see the full remote listing
+    let total = docs.len();
+    let docs = select_docs(docs, options);
+    if docs.len() < total {
+        println!("{} of {} documents match the date range", docs.len(), total);
+    }
+
+    let pb = term::progress_bar(docs.len() as u64, "[{bar:40}] {pos}/{len} docs", "##-");
+
+    let mut synced = 0;
+    let mut skipped = 0;
+    let mut reached_limit = false;
+
+    #[cfg(feature = "semantic")]
+    let mut embedded = 0;
+    #[cfg(feature = "semantic")]
+    let mut embed_failed = 0;
+
+    for doc_summary in &docs {
+        let forced = options.force.contains(&doc_summary.id);
+
+        if merged.contains(&doc_summary.id) && !forced {
+            skipped += 1;
+            pb.inc(1);
+            continue;
+        }
+
+        // Check cache for quick timestamp comparison
+        let should_update = if forced {
+            true
+        } else if let Some(cache_entry) = cache.documents.get(&doc_summary.id) {
+            let remote_ts = doc_summary.updated_at.unwrap_or(doc_summary.created_at);
+            remote_ts > cache_entry.updated_at
+        } else {
+            // Not in cache - new file
+            true
+        };
+
+        // Changed documents are re-embedded; unchanged ones only if they lack a vector
+        #[cfg(feature = "semantic")]
+        let needs_embedding = should_update
+            || (!hidden.contains(&doc_summary.id) && !vector_store.has_document(&doc_summary.id));
+
+        #[cfg(not(feature = "semantic"))]
+        let needs_embedding = false;
+
+        // If nothing to do, skip
+        if !should_update && !needs_embedding {
+            skipped += 1;
+            pb.inc(1);
+            continue;
+        }
+
+        // Fetch metadata and transcript from API, and Granola's notes when rewriting
+        let (meta, raw, panels) = {
+            let _span = perf::span("sync.api");
+            let meta = source.get_metadata(&doc_summary.id).await?;
+            let raw = source.get_transcript(&doc_summary.id).await?;
+            // Notes are extra: without them the transcript is still written
+            let panels = if should_update {
+                match source.get_panels(&doc_summary.id).await {
+                    Ok(panels) => Some(panels),
+                    Err(e) => {
+                        pb.suspend(|| {
+                            warnings.warn(
+                                "Failed to fetch notes for",
+                                format!("{}: {}", doc_summary.id, e),
+                            )
+                        });
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            (meta, raw, panels)
+        };
+
+        // Keep names given on an earlier sync and the access tag set by hand;
+        // only ask about documents being rewritten
+        let previous = cache
+            .documents
+            .get(&doc_summary.id)
+            .map(|entry| paths.transcripts_dir.join(format!("{}.md", entry.filename)))
+            .and_then(|path| read_frontmatter(&path).ok().flatten())
+            .map(|fm| LocalFields::from_frontmatter(&fm))
+            .unwrap_or_default();
+        let inferred_speakers = speakers::resolve(
+            if should_update {
+                naming
+            } else {
+                SpeakerNaming::Off
+            },
+            &raw,
+            &meta.participants,
+            &previous.inferred_speakers,
+            |proposal| pb.suspend(|| speakers::ask(meta.title.as_deref(), proposal)),
+        )?;
+        // Compute filename (may have changed if title changed)
+        let base_filename = base_filename(&slugs, &meta.created_at, meta.title.as_deref());
+        // When the notes couldn't be fetched, the ones from the last sync stay
+        let notes_link = match &panels {
+            Some(panels) if panels.is_empty() => None,
+            Some(_) => Some(notes::link_for(&base_filename)),
+            None => previous
+                .notes
+                .as_ref()
+                .map(|_| notes::link_for(&base_filename)),
+        };
+        let local = LocalFields {
+            inferred_speakers,
+            notes: notes_link,
+            source: source.source_of(&doc_summary.id).to_string(),
+            ..previous
+        };
+        let is_duplicate = local.duplicate_of.is_some();
+
+        // Convert to markdown and write the files
+        let convert_span = perf::span("sync.convert");
+        let md = to_markdown_with_local(&raw, &meta, &doc_summary.id, &markdown_options, &local)?;
+
+        if should_update {
+            let full_md = format!("---\n{}---\n\n{}", md.frontmatter_yaml, md.body);
+            let new_md_path = paths.transcripts_dir.join(format!("{}.md", base_filename));
+
+            // If filename changed in cache, remove old file
+            if let Some(old_entry) = cache.documents.get(&doc_summary.id) {
+                if old_entry.filename != base_filename {
+                    let old_path = paths
+                        .transcripts_dir
+                        .join(format!("{}.md", old_entry.filename));
+                    if old_path.exists() {
+                        std::fs::remove_file(&old_path)?;
+                    }
+                    let old_json = paths.raw_dir.join(format!("{}.json", old_entry.filename));
+                    if old_json.exists() {
+                        std::fs::remove_file(&old_json)?;
+                    }
+                    let old_timings = timings::path_for(paths, &old_entry.filename);
+                    if old_timings.exists() {
+                        std::fs::remove_file(&old_timings)?;
+                    }
+                    // Summaries and condensed copies aren't rewritten, so they follow the title
+                    for (old, new) in derived_files(paths, &old_entry.filename)
+                        .into_iter()
+                        .zip(derived_files(paths, &base_filename))
+                    {
+                        if old.exists() {
+                            std::fs::rename(&old, &new)?;
+                        }
+                    }
+                    let old_notes = notes::path_for(paths, &old_entry.filename);
+                    if old_notes.exists() {
+                        if panels.is_none() {
+                            std::fs::rename(&old_notes, notes::path_for(paths, &base_filename))?;
+                            notes::relink(paths, &old_entry.filename, &base_filename)?;
+                        } else {
+                            std::fs::remove_file(&old_notes)?;
+                        }
+                    }
+                }
+            }
+
+            // Write files
+            let json_path = paths.raw_dir.join(format!("{}.json", base_filename));
+            let raw_json = serde_json::to_string_pretty(&raw)?;
+
+            write_atomic(&json_path, raw_json.as_bytes(), &paths.tmp_dir)?;
+            write_atomic(&new_md_path, full_md.as_bytes(), &paths.tmp_dir)?;
+            timings::write(paths, &base_filename, &raw, &doc_summary.id)?;
+            if let Some(panels) = &panels {
+                let notes_path = notes::path_for(paths, &base_filename);
+                if panels.is_empty() {
+                    if notes_path.exists() {
+                        std::fs::remove_file(&notes_path)?;
+                    }
+                } else {
+                    let content = notes::to_markdown(meta.title.as_deref(), &base_filename, panels);
+                    write_atomic(&notes_path, content.as_bytes(), &paths.tmp_dir)?;
+                    set_file_time(&notes_path, &meta.created_at)?;
+                }
+            }
+
+            // Set file modification time to meeting creation date
+            set_file_time(&json_path, &meta.created_at)?;
+            set_file_time(&new_md_path, &meta.created_at)?;
+            catalog.record_file(&new_md_path)?;
+
+            if !is_duplicate {
+                let originals = catalog.same_content(&doc_summary.id)?;
+                if !originals.is_empty() {
+                    pb.suspend(|| {
+                        eprintln!(
+                            "Note: {} has the same transcript as {}; run 'muesli dedupe' to hide or merge it",
+                            doc_summary.id,
+                            originals.join(", ")
+                        )
+                    });
+                }
+            }
+
+            // Update cache - CRITICAL: store the same timestamp we compare against
+            // (doc_summary.updated_at, NOT meta.updated_at - they can differ!)
+            let stored_ts = doc_summary.updated_at.unwrap_or(doc_summary.created_at);
+            cache.documents.insert(
+                doc_summary.id.clone(),
+                CacheEntry {
+                    filename: base_filename.clone(),
+                    updated_at: stored_ts,
+                    source: local.source.clone(),
+                },
+            );
+
+            // Save cache immediately for incremental sync (atomically)
+            // If interrupted, next run will skip already-synced docs
+            save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+            drop(convert_span);
+
+            // Index the document (feature-gated, non-fatal); hidden duplicates stay out
+            #[cfg(feature = "index")]
+            if !is_duplicate {
+                let _span = perf::span("sync.index");
+                let date = meta.created_at.format("%Y-%m-%d").to_string();
+                let organizations = organizations_from_participants(&meta.participants);
+                if let Err(e) = text::index_document_batch(
+                    &mut writer,
+                    &index,
+                    &text::IndexedDocument {
+                        doc_id: &doc_summary.id,
+                        title: meta.title.as_deref(),
+                        date: &date,
+                        body: &md.body,
+                        path: &new_md_path,
+                        organizations: &organizations,
+                        participants: &markdown_options.aliases.with_canonical(&meta.participants),
+                        labels: &meta.labels,
+                    },
+                ) {
+                    pb.suspend(|| {
+                        warnings.warn(INDEX_FAILED, format!("{}: {}", doc_summary.id, e))
+                    });
+                }
+
+                uncommitted += 1;
+                if writer_config.commit_due(uncommitted) {
+                    match writer.commit() {
+                        Ok(_) => uncommitted = 0,
+                        Err(e) => pb.suspend(|| warnings.warn(INDEX_NOT_SAVED, e)),
+                    }
+                }
+            }
+
+            synced += 1;
+        } else {
+            drop(convert_span);
+        }
+
+        // Generate embeddings (feature-gated, non-fatal)
+        #[cfg(feature = "semantic")]
+        {
+            if needs_embedding && !is_duplicate {
+                let _span = perf::span("sync.embed");
+                let text_for_embedding = embedding_text(meta.title.as_deref(), &md.body);
+
+                // Replace rather than duplicate the vector of an updated document
+                match embedder
+                    .embed_passage(&text_for_embedding)
+                    .and_then(|vec| vector_store.upsert_document(doc_summary.id.clone(), vec))
+                {
+                    Ok(_) => embedded += 1,
+                    Err(e) => {
+                        pb.suspend(|| {
+                            warnings.warn(EMBED_FAILED, format!("{}: {}", doc_summary.id, e))
+                        });
+                        embed_failed += 1;
+                    }
+                }
+            }
+        }
+
+        pb.inc(1);
+
+        if options.max_docs.is_some_and(|max| synced >= max) {
+            reached_limit = true;
+            break;
+        }
+    }
+
+    pb.finish_with_message(format!(
+        "synced {} docs ({} new/updated, {} skipped)",
+        docs.len(),
+        synced,
+        skipped
+    ));
+    if reached_limit {
+        println!(
+            "Stopped after {} documents (--max-docs); run sync again to continue",
+            synced
+        );
+    }
+
+    // Commit all indexed documents in one batch (feature-gated)
+    #[cfg(feature = "index")]
+    {
+        if synced > 0 || pruned > 0 {
+            let _span = perf::span("sync.index");
+            if let Err(e) = writer.commit() {
+                warnings.warn(INDEX_NOT_SAVED, e);
+            } else if synced > 0 {
+                println!("Indexed {} documents", synced);
+            }
+        }
+    }
+
+    // Save vector store (feature-gated)
+    #[cfg(feature = "semantic")]
+    {
+        let vector_path = paths.index_dir.join("vectors");
+        let saved = {
+            let _span = perf::span("sync.embed");
+            vector_store.save(&vector_path, &paths.tmp_dir)
+        };
+        if let Err(e) = saved {
+            warnings.warn(VECTORS_NOT_SAVED, e);
+        } else if embedded > 0 {
+            println!(
+                "{}Generated embeddings for {} new documents",
+                term::emoji("✅ "),
+                embedded
+            );
+        } else if embed_failed == 0 {
+            println!(
+                "{}All documents already have embeddings",
+                term::emoji("✅ ")
+            );
+        }
+        if embed_failed > 0 {
+            println!(
+                "{}{} documents failed to embed",
+                term::emoji("⚠️  "),
+                embed_failed
+            );
+        }
+
+        // Re-downloaded files lost their footer, and new meetings are new link targets
+        if options.link {
+            let report = crate::related::link_all(paths, crate::related::DEFAULT_COUNT)?;
+            println!(
+                "Updated related-meeting links in {} transcripts",
+                report.updated
+            );
+        }
+    }
+
+    // Only a sync that saw every change, and stored all of them, may move the
+    // feed on; otherwise the next one starts from the old position again
+    let complete = !reached_limit
+        && options.since.is_none()
+        && options.until.is_none()
+        && !warnings
+            .kinds()
+            .iter()
+            .any(|kind| LEFT_UNDONE.contains(&kind.message.as_str()));
+    if let Some(cursor) = feed_cursor.filter(|_| complete) {
+        cache.cursor = Some(cursor);
+        save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+    }
+
+    refresh_manifest(paths);
+    if let Err(e) = write_atomic(
+        &paths.data_dir.join(LAST_SYNC_FILE),
+        Utc::now().to_rfc3339().as_bytes(),
+        &paths.tmp_dir,
+    ) {
+        eprintln!("Warning: Failed to record the sync time: {}", e);
+    }
+    warnings.finish();
+
+    Ok(())
+}
+
+/// Warnings of documents that were written but not indexed or embedded; the
+/// feed won't list those again, so a sync with any of them keeps its cursor
+const LEFT_UNDONE: &[&str] = &[
+    INDEX_FAILED,
+    INDEX_NOT_SAVED,
+    EMBED_FAILED,
+    VECTORS_NOT_SAVED,
+];
+const INDEX_FAILED: &str = "Failed to index document";
+const INDEX_NOT_SAVED: &str = "Failed to commit index changes:";
+const EMBED_FAILED: &str = "Failed to embed document";
+const VECTORS_NOT_SAVED: &str = "Failed to save vector store:";
+
+/// Records when the last sync with the API finished
+const LAST_SYNC_FILE: &str = ".last_sync";
+
+/// When the last sync finished, or None if the archive was never synced
+///
+/// Archives synced before the time was recorded fall back to when the sync
+/// cache last changed.
+pub fn last_sync(paths: &Paths) -> Option<DateTime<Utc>> {
+    let recorded = std::fs::read_to_string(paths.data_dir.join(LAST_SYNC_FILE))
+        .ok()
+        .and_then(|s| DateTime::parse_from_rfc3339(s.trim()).ok())
+        .map(|t| t.with_timezone(&Utc));
+    recorded.or_else(|| {
+        let modified = std::fs::metadata(paths.data_dir.join(".sync_cache.json"))
+            .and_then(|m| m.modified())
+            .ok()?;
+        Some(modified.into())
+    })
+}
+
+/// Where `summarize --save` keeps the summary of a transcript
+pub fn summary_path(paths: &Paths, transcript: &std::path::Path) -> std::path::PathBuf {
+    let stem = transcript.file_stem().unwrap_or_default().to_string_lossy();
+    paths.summaries_dir.join(format!("{}_summary.md", stem))
+}
+
+/// Where the summary replaced by the latest `summarize --save` is kept
+pub fn previous_summary_path(paths: &Paths, transcript: &std::path::Path) -> std::path::PathBuf {
+    let stem = transcript.file_stem().unwrap_or_default().to_string_lossy();
+    paths
+        .summaries_dir
+        .join(format!("{}_summary.previous.md", stem))
+}
+
+/// Saves a summary of a synced document, returning its path
+///
+/// A different summary already saved is kept as the previous version, for
+/// `summary diff`. The manifest and search index are refreshed too; failures
+/// there are only warnings since the summary itself is safely written.
+pub fn save_summary(
+    paths: &Paths,
+    entry: &crate::storage::CatalogEntry,
+    summary: &str,
+) -> Result<std::path::PathBuf> {
+    let path = summary_path(paths, &entry.path);
+    std::fs::create_dir_all(&paths.summaries_dir)?;
+    match std::fs::read_to_string(&path) {
+        Ok(saved) if saved != summary => {
+            std::fs::rename(&path, previous_summary_path(paths, &entry.path))?
+        }
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+        _ => {}
+    }
+    crate::storage::write_atomic(&path, summary.as_bytes(), &paths.tmp_dir)?;
+    refresh_manifest(paths);
+    #[cfg(feature = "index")]
+    if let Err(e) = index_saved_summary(paths, entry, &path, summary) {
+        eprintln!("Warning: Failed to index summary: {}", e);
+    }
+    Ok(path)
+}
+
+/// Adds a summary saved by `summarize --save` to the search index
+///
+/// Does nothing before the first sync or when the index needs rebuilding;
+/// the rebuild indexes every saved summary.
+#[cfg(feature = "index")]
+pub fn index_saved_summary(
+    paths: &Paths,
+    entry: &crate::storage::CatalogEntry,
+    summary_path: &std::path::Path,
+    summary: &str,
+) -> Result<()> {
+    if !paths.index_dir.exists() || text::schema_is_outdated(&paths.index_dir) {
+        return Ok(());
+    }
+
+    let index = text::create_or_open_index(&paths.index_dir)?;
+    let mut writer = WriterConfig::configured(paths)?.writer(&index)?;
+    let organizations = if entry.organizations.is_empty() {
+        organizations_from_participants(&entry.participants)
+    } else {
+        entry.organizations.clone()
+    };
+    let date = entry.created_at.format("%Y-%m-%d").to_string();
+    let participants = SpeakerAliases::configured(paths)?.with_canonical(&entry.participants);
+    text::index_summary_batch(
+        &mut writer,
+        &index,
+        &text::IndexedDocument {
+            doc_id: &entry.doc_id,
+            title: entry.title.as_deref(),
+            date: &date,
+            body: summary,
+            path: summary_path,
+            organizations: &organizations,
+            participants: &participants,
+            labels: &entry.labels,
+        },
+    )?;
+    writer
+        .commit()
+        .map_err(|e| Error::Indexing(format!("Failed to commit index: {}", e)))?;
+    Ok(())
+}
+
+/// Rewrites muesli-index.json; external tools only lose freshness if this fails
+pub fn refresh_manifest(paths: &Paths) {
+    if let Err(e) = crate::manifest::write(paths) {
+        eprintln!(
+            "Warning: Failed to write {}: {}",
+            crate::manifest::MANIFEST_FILE,
+            e
+        );
+    }
+}
+
+/// Title and body as embedded, truncated to stay within the model's token limit
+#[cfg(feature = "semantic")]
+fn embedding_text(title: Option<&str>, body: &str) -> String {
+    let mut text = match title {
+        Some(title) => format!("{}\n\n{}", title, body),
+        None => body.to_string(),
+    };
+
+    // Rough estimate: 1 token ≈ 4 chars, so ~500 tokens, well under the 512 limit
+    let max_chars = 2000;
+    if text.len() > max_chars {
+        let mut boundary = max_chars;
+        while !text.is_char_boundary(boundary) {
+            boundary -= 1;
+        }
+        text.truncate(boundary);
+    }
+
+    text
+}
+
+/// Documents embedded between checkpoints unless `--batch-size` says otherwise
+#[cfg(feature = "semantic")]
+pub const DEFAULT_EMBED_BATCH: usize = 32;
+
+/// Replaces the vector store with fresh embeddings of every local transcript
+///
+/// Works from the markdown on disk only (no API calls). A store built with a
+/// different model, or one that can no longer be read, is only replaced when
+/// `force` is set. The new store is swapped in only once every document is done.
+///
+/// Progress is checkpointed to `tmp/reembed.*` every `batch_size` documents, and
+/// a later run with the same model picks up where an interrupted one stopped.
+/// Files are read and tokenized on a second thread while the model runs.
+#[cfg(feature = "semantic")]
+pub fn reembed_all(paths: &Paths, force: bool, batch_size: usize) -> Result<()> {
+    use crate::storage::{scan_transcripts, strip_frontmatter};
+    use std::sync::mpsc;
+
+    let model_paths = downloader::ensure_model(&paths.models_dir, models::configured(paths)?)?;
+    let vector_path = paths.index_dir.join("vectors");
+    let checkpoint_path = paths.tmp_dir.join("reembed");
+    let batch_size = batch_size.max(1);
+
+    if !force && paths.index_dir.join("vectors.meta.json").exists() {
+        let mut existing = VectorStore::load(&vector_path).map_err(|e| {
+            Error::Embedding(format!(
+                "Cannot read the vector store ({}); run 'muesli reembed --force' to rebuild it",
+                e
+            ))
+        })?;
+        existing.pin_model(&model_paths.id)?;
+    }
+
+    let mut engine = EmbeddingEngine::new(&model_paths)?;
+    let mut store = match resume_checkpoint(&checkpoint_path, &model_paths.id) {
+        Some(store) => store,
+        None => {
+            let mut store = VectorStore::new(engine.dim());
+            store.pin_model(&model_paths.id)?;
+            store
+        }
+    };
+
+    // Duplicates hidden by `muesli dedupe` stay out of the store
+    let transcripts = scan_transcripts(&paths.transcripts_dir)?
+        .into_iter()
+        .filter(|(_, frontmatter)| frontmatter.duplicate_of.is_none());
+    let (done, pending): (Vec<_>, Vec<_>) =
+        transcripts.partition(|(_, frontmatter)| store.has_document(&frontmatter.doc_id));
+    if done.is_empty() {
+        println!(
+            "Re-embedding {} documents with {}...",
+            pending.len(),
+            model_paths.id
+        );
+    } else {
+        println!(
+            "Resuming: {} of {} documents already embedded with {}...",
+            done.len(),
+            done.len() + pending.len(),
+            model_paths.id
+        );
+    }
+    let pb = term::progress_bar(
+        (done.len() + pending.len()) as u64,
+        "[{bar:40}] {pos}/{len} docs ({eta} left)",
+        "##-",
+    );
+    pb.set_position(done.len() as u64);
+    // Only the documents embedded in this run say anything about the pace
+    pb.reset_eta();
+
+    let tokenizer = engine.passage_tokenizer();
+    let mut failed = 0;
+    let mut warnings = Warnings::new(paths, "reembed");
+    std::thread::scope(|scope| -> Result<()> {
+        // One batch of tokenized documents waits while the model works on another
+        let (sender, receiver) = mpsc::sync_channel(batch_size);
+        let pending = &pending;
+        scope.spawn(move || {
+            for (path, frontmatter) in pending {
+                let tokens =
+                    std::fs::read_to_string(path)
+                        .map_err(Error::from)
+                        .and_then(|content| {
+                            let body = crate::related::strip_related(strip_frontmatter(&content));
+                            tokenizer.tokenize(&embedding_text(frontmatter.title.as_deref(), body))
+                        });
+                if sender.send((path, &frontmatter.doc_id, tokens)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        for (done, (path, doc_id, tokens)) in receiver.into_iter().enumerate() {
+            if let Err(e) = tokens
+                .and_then(|tokens| engine.embed_tokens(&tokens))
+                .and_then(|vec| store.add_document(doc_id.clone(), vec))
+            {
+                pb.suspend(|| {
+                    warnings.warn("Failed to embed", format!("{}: {}", path.display(), e))
+                });
+                failed += 1;
+            }
+            pb.inc(1);
+
+            if (done + 1) % batch_size == 0 {
+                store.save(&checkpoint_path, &paths.tmp_dir)?;
+            }
+        }
+        Ok(())
+    })?;
+    pb.finish_and_clear();
+
+    store.save(&vector_path, &paths.tmp_dir)?;
+    VectorStore::delete(&checkpoint_path)?;
+    println!(
+        "{}Generated embeddings for {} documents",
+        term::emoji("✅ "),
+        store.len()
+    );
+    if failed > 0 {
+        println!(
+            "{}{} documents failed to embed",
+            term::emoji("⚠️  "),
+            failed
+        );
+    }
+    warnings.finish();
+
+    Ok(())
+}
+
+/// The store an interrupted `reembed_all` left behind, if it used the same model
+#[cfg(feature = "semantic")]
+fn resume_checkpoint(path: &std::path::Path, model: &downloader::ModelId) -> Option<VectorStore> {
+    if !path.with_extension("meta.json").exists() {
+        return None;
+    }
+    let resumed = VectorStore::load(path).and_then(|mut store| {
+        store.pin_model(model)?;
+        Ok(store)
+    });
+    match resumed {
+        Ok(store) => Some(store),
+        Err(e) => {
+            eprintln!(
+                "Warning: Starting over, the re-embed checkpoint is unusable: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Reindex all existing markdown files without re-downloading
+#[cfg(feature = "index")]
+fn reindex_all(paths: &Paths) -> Result<()> {
+    use std::fs;
+
+    println!("Reindexing all documents from disk...");
+
+    // Create or open the index
+    let config = WriterConfig::configured(paths)?;
+    let index = text::create_or_open_index(&paths.index_dir)?;
+    let mut writer = config.writer(&index)?;
+    let aliases = SpeakerAliases::configured(paths)?;
+
+    // Scan transcripts directory
+    let entries = fs::read_dir(&paths.transcripts_dir).map_err(crate::Error::Filesystem)?;
+
+    let mut indexed = 0;
+    let mut summaries = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
+    let mut uncommitted = 0;
+    let mut warnings = Warnings::new(paths, "reindex");
+
+    for entry in entries {
+        let entry = entry.map_err(crate::Error::Filesystem)?;
+        let path = entry.path();
+
+        // Only process .md files
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+
+        // One unreadable file shouldn't cost the rest of the archive its index
+        let transcript = match crate::document::Document::load(&path) {
+            Ok(transcript) => transcript,
+            Err(e) => {
+                warnings.warn("Skipping", e);
+                skipped += 1;
+                continue;
+            }
+        };
+        let frontmatter = match transcript.frontmatter {
+            Some(fm) => fm,
+            None => {
+                warnings.warn("Skipping", format!("{} (no frontmatter)", path.display()));
+                skipped += 1;
+                continue;
+            }
+        };
+        if frontmatter.duplicate_of.is_some() {
+            continue;
+        }
+
+        // Links to other meetings are not part of this meeting's content
+        let body = crate::related::strip_related(&transcript.body);
+
+        // Files written before organizations existed get them derived on the fly
+        let organizations = if frontmatter.organizations.is_empty() {
+            organizations_from_participants(&frontmatter.participants)
+        } else {
+            frontmatter.organizations.clone()
+        };
+
+        // Index the document
+        let date = frontmatter.created_at.format("%Y-%m-%d").to_string();
+        let participants = aliases.with_canonical(&frontmatter.participants);
+        let document = text::IndexedDocument {
+            doc_id: &frontmatter.doc_id,
+            title: frontmatter.title.as_deref(),
+            date: &date,
+            body,
+            path: &path,
+            organizations: &organizations,
+            participants: &participants,
+            labels: &frontmatter.labels,
+        };
+        match text::index_document_batch(&mut writer, &index, &document) {
+            Ok(_) => indexed += 1,
+            Err(e) => {
+                warnings.warn("Failed to index", format!("{}: {}", path.display(), e));
+                failed += 1;
+            }
+        }
+
+        // A saved summary is searchable under the same meeting
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let summary_path = paths.summaries_dir.join(format!("{}_summary.md", stem));
+        if summary_path.exists() {
+            let indexed_summary = fs::read_to_string(&summary_path)
+                .map_err(crate::Error::Filesystem)
+                .and_then(|summary| {
+                    let document = text::IndexedDocument {
+                        body: &summary,
+                        path: &summary_path,
+                        ..document
+                    };
+                    text::index_summary_batch(&mut writer, &index, &document)
+                });
+            match indexed_summary {
+                Ok(_) => summaries += 1,
+                Err(e) => {
+                    warnings.warn(
+                        "Failed to index",
+                        format!("{}: {}", summary_path.display(), e),
+                    );
+                    failed += 1;
+                }
+            }
+        }
+
+        uncommitted += 1;
+        if config.commit_due(uncommitted) {
+            writer
+                .commit()
+                .map_err(|e| crate::Error::Indexing(format!("Failed to commit index: {}", e)))?;
+            uncommitted = 0;
+        }
+    }
+
+    // Commit the index
+    writer
+        .commit()
+        .map_err(|e| crate::Error::Indexing(format!("Failed to commit index: {}", e)))?;
+
+    // Files may have been added or renamed by hand; resync the catalog too
+    Catalog::open(paths)?.rebuild()?;
+
+    println!(
+        "{}Reindexed {} documents and {} summaries",
+        term::emoji("✅ "),
+        indexed,
+        summaries
+    );
+    if failed > 0 {
+        println!(
+            "{}{} documents failed to index",
+            term::emoji("⚠️  "),
+            failed
+        );
+    }
+    if skipped > 0 {
+        println!(
+            "{}Skipped {} transcripts that could not be read",
+            term::emoji("⚠️  "),
+            skipped
+        );
+    }
+    warnings.finish();
+
+    Ok(())
+}
+
+/// Fix file modification dates for all existing files to match meeting creation dates
+pub fn fix_dates(paths: &Paths) -> Result<()> {
+    use std::fs;
+
+    println!("Fixing file modification dates...");
+
+    let entries = fs::read_dir(&paths.transcripts_dir).map_err(crate::Error::Filesystem)?;
+
+    let mut fixed = 0;
+    let mut failed = 0;
+
+    for entry in entries {
+        let entry = entry.map_err(crate::Error::Filesystem)?;
+        let path = entry.path();
+
+        // Only process .md files
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+
+        // Read frontmatter to get the created_at date
+        #[cfg(feature = "index")]
+        let frontmatter = match read_frontmatter(&path)? {
+            Some(fm) => fm,
+            None => {
+                eprintln!("Warning: Skipping {} (no frontmatter)", path.display());
+                failed += 1;
+                continue;
+            }
+        };
+
+        #[cfg(not(feature = "index"))]
+        let frontmatter = {
+            // Without index feature, we need to parse frontmatter manually
+            let content = fs::read_to_string(&path).map_err(crate::Error::Filesystem)?;
+            if !content.starts_with("---\n") {
+                eprintln!("Warning: Skipping {} (no frontmatter)", path.display());
+                failed += 1;
+                continue;
+            }
+            let rest = &content[4..];
+            if let Some(end_pos) = rest.find("\n---\n") {
+                let yaml = &rest[..end_pos];
+                match serde_yaml::from_str::<crate::Frontmatter>(yaml) {
+                    Ok(fm) => fm,
+                    Err(e) => {
+                        eprintln!(
+                            "Warning: Skipping {} (failed to parse frontmatter: {})",
+                            path.display(),
+                            e
+                        );
+                        failed += 1;
+                        continue;
+                    }
+                }
+            } else {
+                eprintln!("Warning: Skipping {} (no frontmatter)", path.display());
+                failed += 1;
+                continue;
+            }
+        };
+
+        // Set the file time
+        match set_file_time(&path, &frontmatter.created_at) {
+            Ok(_) => {
+                // Also fix the corresponding JSON file if it exists
+                let json_path = path.file_stem().map(|stem| {
+                    paths
+                        .raw_dir
+                        .join(format!("{}.json", stem.to_string_lossy()))
+                });
+                if let Some(json_path) = json_path.filter(|p| p.exists()) {
+                    if let Err(e) = set_file_time(&json_path, &frontmatter.created_at) {
+                        eprintln!(
+                            "Warning: Failed to set time for {}: {}",
+                            json_path.display(),
+                            e
+                        );
+                    }
+                }
+                fixed += 1;
+            }
+            Err(e) => {
+                eprintln!("Warning: Failed to set time for {}: {}", path.display(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("{}Fixed dates for {} files", term::emoji("✅ "), fixed);
+    if failed > 0 {
+        println!("{}{} files failed", term::emoji("⚠️  "), failed);
+    }
+
+    Ok(())
+}
+
+/// A transcript given the name the current filename rules produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rename {
+    pub doc_id: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Renames transcripts, and every file named after them, to follow `[filenames]`
+///
+/// Moves the markdown, raw JSON, timings, notes, saved summaries and condensed
+/// copy, and updates the sync cache, catalog, search index and manifest. A name that
+/// is already taken is left alone with a warning. With `dry_run` nothing changes.
+pub fn rename_files(paths: &Paths, dry_run: bool) -> Result<Vec<Rename>> {
+    use crate::storage::scan_transcripts;
+
+    let slugs = SlugOptions::configured(paths)?;
+    let cache_path = paths.data_dir.join(".sync_cache.json");
+    let mut cache = load_cache(&cache_path);
+    let catalog = Catalog::open(paths)?;
+
+    let mut renames = Vec::new();
+    let mut taken = HashSet::new();
+    for (path, fm) in scan_transcripts(&paths.transcripts_dir)? {
+        let from = path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let to = base_filename(&slugs, &fm.created_at, fm.title.as_deref());
+        if to == from {
+            continue;
+        }
+
+        let target = paths.transcripts_dir.join(format!("{}.md", to));
+        if target.exists() || !taken.insert(to.clone()) {
+            eprintln!(
+                "Warning: Not renaming {} to {}: the name is taken",
+                from, to
+            );
+            continue;
+        }
+
+        if !dry_run {
+            for (old, new) in stem_files(paths, &from)
+                .into_iter()
+                .zip(stem_files(paths, &to))
+            {
+                if old.exists() {
+                    std::fs::rename(&old, &new)?;
+                }
+            }
+            if fm.notes.is_some() {
+                let mut fm = fm.clone();
+                fm.notes = Some(notes::link_for(&to));
+                write_frontmatter(&target, &fm, &paths.tmp_dir)?;
+                notes::relink(paths, &from, &to)?;
+            }
+            if let Some(entry) = cache.documents.get_mut(&fm.doc_id) {
+                entry.filename = to.clone();
+            }
+            catalog.record_file(&target)?;
+        }
+
+        renames.push(Rename {
+            doc_id: fm.doc_id,
+            from,
+            to,
+        });
+    }
+
+    if !dry_run && !renames.is_empty() {
+        save_cache(&cache_path, &cache, &paths.tmp_dir)?;
+        // Search hits carry the transcript path
+        #[cfg(feature = "index")]
+        reindex_all(paths)?;
+        refresh_manifest(paths);
+    }
+
+    Ok(renames)
+}
+
+/// Every file named after a transcript's base filename
+fn stem_files(paths: &Paths, stem: &str) -> Vec<std::path::PathBuf> {
+    let mut files = vec![
+        paths.transcripts_dir.join(format!("{}.md", stem)),
+        paths.raw_dir.join(format!("{}.json", stem)),
+        timings::path_for(paths, stem),
+        notes::path_for(paths, stem),
+    ];
+    files.extend(derived_files(paths, stem));
+    files
+}
+
+/// Files made from a transcript after sync wrote it (summaries, condensed copy)
+fn derived_files(paths: &Paths, stem: &str) -> Vec<std::path::PathBuf> {
+    vec![
+        paths.summaries_dir.join(format!("{}_summary.md", stem)),
+        paths
+            .summaries_dir
+            .join(format!("{}_summary.previous.md", stem)),
+        paths.summaries_dir.join(format!("{}_summary.json", stem)),
+        paths.summaries_dir.join(format!("{}_interview.md", stem)),
+        paths.summaries_dir.join(format!("{}_interview.json", stem)),
+        paths.summaries_dir.join(format!("{}_incident.md", stem)),
+        paths.summaries_dir.join(format!("{}_incident.json", stem)),
+        paths
+            .data_dir
+            .join("condensed")
+            .join(format!("{}.md", stem)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Paths;
+    use tempfile::TempDir;
+
+    fn cache_entry(filename: &str) -> CacheEntry {
+        CacheEntry {
+            filename: filename.into(),
+            updated_at: Utc::now(),
+            source: granola_source(),
+        }
+    }
+
+    #[test]
+    fn test_plan_prune_finds_orphans() {
+        let mut cache = HashMap::new();
+        cache.insert("b".to_string(), cache_entry("2025-01-02_b"));
+        cache.insert("a".to_string(), cache_entry("2025-01-01_a"));
+        cache.insert("kept".to_string(), cache_entry("2025-01-03_kept"));
+        let imported = CacheEntry {
+            source: "vtt".into(),
+            ..cache_entry("2025-01-04_standup")
+        };
+        cache.insert("standup".to_string(), imported);
+
+        let remote: HashSet<&str> = ["kept", "new"].into_iter().collect();
+        assert_eq!(plan_prune(&cache, &remote, GRANOLA_SOURCE), vec!["a", "b"]);
+        assert_eq!(plan_prune(&cache, &remote, "vtt"), vec!["standup"]);
+    }
+
+    fn summary(id: &str, title: &str, updated: &str) -> DocumentSummary {
+        DocumentSummary {
+            id: id.into(),
+            title: Some(title.into()),
+            created_at: "2025-01-01T09:00:00Z".parse().unwrap(),
+            updated_at: Some(updated.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_plan_sync_classifies_changes() {
+        let at = |ts: &str| ts.parse::<DateTime<Utc>>().unwrap();
+        let mut cache = HashMap::new();
+        for (id, filename) in [
+            ("same", "2025-01-01_standup"),
+            ("newer", "2025-01-01_retro"),
+            ("renamed", "2025-01-01_old-title"),
+            ("no-vector", "2025-01-01_demo"),
+            ("gone", "2024-12-01_deleted"),
+        ] {
+            cache.insert(
+                id.to_string(),
+                CacheEntry {
+                    filename: filename.into(),
+                    updated_at: at("2025-01-02T00:00:00Z"),
+                    source: granola_source(),
+                },
+            );
+        }
+
+        let docs = vec![
+            summary("same", "Standup", "2025-01-02T00:00:00Z"),
+            summary("newer", "Retro", "2025-01-03T00:00:00Z"),
+            summary("renamed", "New Title", "2025-01-03T00:00:00Z"),
+            summary("no-vector", "Demo", "2025-01-02T00:00:00Z"),
+            summary("fresh", "Kickoff", "2025-01-02T00:00:00Z"),
+        ];
+        let orphans = vec!["gone".to_string()];
+
+        let has_embedding = |id: &str| id != "no-vector";
+        let plan = plan_sync(
+            &docs,
+            &cache,
+            &orphans,
+            has_embedding,
+            &SyncOptions::default(),
+            &SlugOptions::default(),
+        );
+        let changes: Vec<(&str, &PlannedChange)> = plan
+            .iter()
+            .map(|e| (e.doc_id.as_str(), &e.change))
+            .collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                ("newer", &PlannedChange::Update),
+                (
+                    "renamed",
+                    &PlannedChange::Rename {
+                        from: "2025-01-01_old-title".into(),
+                        to: "2025-01-01_new-title".into()
+                    }
+                ),
+                ("no-vector", &PlannedChange::Embed),
+                ("fresh", &PlannedChange::Download),
+                (
+                    "gone",
+                    &PlannedChange::Prune {
+                        filename: "2024-12-01_deleted".into()
+                    }
+                ),
+            ]
+        );
+
+        // The limit counts written documents; embed-only entries don't use it up
+        let options = SyncOptions {
+            max_docs: Some(2),
+            ..Default::default()
+        };
+        let slugs = SlugOptions::default();
+        let limited = plan_sync(&docs, &cache, &orphans, has_embedding, &options, &slugs);
+        let ids: Vec<&str> = limited.iter().map(|e| e.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["newer", "renamed", "gone"]);
+
+        // Forcing an up-to-date document plans an update for it
+        let options = SyncOptions {
+            force: vec!["same".into()],
+            ..Default::default()
+        };
+        let forced = plan_sync(&docs, &cache, &[], has_embedding, &options, &slugs);
+        assert_eq!(forced[0].doc_id, "same");
+        assert_eq!(forced[0].change, PlannedChange::Update);
+    }
+
+    #[test]
+    fn test_sort_by_priority() {
+        let doc = |id: &str, ts: &str| DocumentSummary {
+            id: id.into(),
+            title: None,
+            created_at: ts.parse().unwrap(),
+            updated_at: None,
+        };
+        let mut docs = vec![
+            doc("mid", "2025-02-01T10:00:00Z"),
+            doc("new", "2025-03-01T10:00:00Z"),
+            doc("old", "2025-01-01T10:00:00Z"),
+        ];
+
+        sort_by_priority(&mut docs, false);
+        let ids: Vec<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["new", "mid", "old"]);
+
+        sort_by_priority(&mut docs, true);
+        let ids: Vec<&str> = docs.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["old", "mid", "new"]);
+    }
+
+    #[test]
+    fn test_select_docs_puts_forced_first_and_ignores_range() {
+        let doc = |id: &str, ts: &str| DocumentSummary {
+            id: id.into(),
+            title: None,
+            created_at: ts.parse().unwrap(),
+            updated_at: None,
+        };
+        let docs = vec![
+            doc("old", "2023-01-01T10:00:00Z"),
+            doc("a", "2025-01-01T10:00:00Z"),
+            doc("b", "2025-02-01T10:00:00Z"),
+        ];
+        let options = SyncOptions {
+            since: NaiveDate::from_ymd_opt(2024, 1, 1),
+            force: vec!["old".into()],
+            ..Default::default()
+        };
+
+        let ids: Vec<String> = select_docs(docs, &options)
+            .into_iter()
+            .map(|d| d.id)
+            .collect();
+        assert_eq!(ids, vec!["old", "b", "a"]);
+    }
+
+    #[test]
+    fn test_in_date_range_is_inclusive() {
+        let doc = |id: &str, ts: &str| DocumentSummary {
+            id: id.into(),
+            title: None,
+            created_at: ts.parse().unwrap(),
+            updated_at: None,
+        };
+        let docs = vec![
+            doc("before", "2023-12-31T23:59:59Z"),
+            doc("start", "2024-01-01T00:00:00Z"),
+            doc("end", "2024-06-30T23:00:00Z"),
+            doc("after", "2024-07-01T00:00:00Z"),
+        ];
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok();
+
+        let kept = in_date_range(docs.clone(), date("2024-01-01"), date("2024-06-30"));
+        let ids: Vec<&str> = kept.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, vec!["start", "end"]);
+
+        assert_eq!(in_date_range(docs.clone(), None, None).len(), 4);
+        assert_eq!(in_date_range(docs, date("2024-07-01"), None).len(), 1);
+    }
+
+    #[test]
+    fn test_rename_files_follows_slug_options() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let old = "2025-01-01_quarterly-planning-review-with-the-extended-team";
+        std::fs::write(
+            paths.transcripts_dir.join(format!("{}.md", old)),
+            "---\ndoc_id: d1\nsource: granola\ncreated_at: 2025-01-01T10:00:00Z\n\
+             title: Quarterly planning review with the extended team\ngenerator: muesli 1.0\n---\n\nbody\n",
+        )
+        .unwrap();
+        std::fs::write(paths.raw_dir.join(format!("{}.json", old)), "{}").unwrap();
+        std::fs::write(
+            paths.summaries_dir.join(format!("{}_summary.md", old)),
+            "summary",
+        )
+        .unwrap();
+        let cache_path = paths.data_dir.join(".sync_cache.json");
+        let cache = SyncCache {
+            documents: HashMap::from([("d1".to_string(), cache_entry(old))]),
+            ..SyncCache::default()
+        };
+        save_cache(&cache_path, &cache, &paths.tmp_dir).unwrap();
+
+        // The defaults already match
+        assert!(rename_files(&paths, false).unwrap().is_empty());
+
+        std::fs::write(&paths.config_file, "[filenames]\nmax_length = 20\n").unwrap();
+        let planned = rename_files(&paths, true).unwrap();
+        assert_eq!(planned[0].to, "2025-01-01_quarterly-planning");
+        assert!(paths.transcripts_dir.join(format!("{}.md", old)).exists());
+
+        let renamed = rename_files(&paths, false).unwrap();
+        assert_eq!(renamed, planned);
+        let new = "2025-01-01_quarterly-planning";
+        assert!(paths.transcripts_dir.join(format!("{}.md", new)).exists());
+        assert!(paths.raw_dir.join(format!("{}.json", new)).exists());
+        assert!(paths
+            .summaries_dir
+            .join(format!("{}_summary.md", new))
+            .exists());
+        assert!(!paths.raw_dir.join(format!("{}.json", old)).exists());
+        assert_eq!(load_cache(&cache_path).documents["d1"].filename, new);
+        assert_eq!(
+            Catalog::open(&paths).unwrap().path_for("d1").unwrap(),
+            paths.transcripts_dir.join(format!("{}.md", new))
+        );
+    }
+
+    #[test]
+    fn test_cache_keeps_cursor_beside_documents() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join(".sync_cache.json");
+
+        // Caches written before the feed are a plain map of documents
+        std::fs::write(
+            &cache_path,
+            r#"{"d1": {"filename": "2025-01-01_a", "updated_at": "2025-01-01T10:00:00Z"}}"#,
+        )
+        .unwrap();
+        let mut cache = load_cache(&cache_path);
+        assert!(cache.cursor.is_none());
+        assert_eq!(cache.documents["d1"].filename, "2025-01-01_a");
+
+        cache.cursor = Some("c42".to_string());
+        save_cache(&cache_path, &cache, temp.path()).unwrap();
+        let cache = load_cache(&cache_path);
+        assert_eq!(cache.cursor.as_deref(), Some("c42"));
+        assert_eq!(cache.documents.len(), 1);
+    }
+
+    #[test]
+    fn test_drop_missing_cache_entries() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let mut cache = SyncCache {
+            cursor: Some("c42".to_string()),
+            ..SyncCache::default()
+        };
+        cache
+            .documents
+            .insert("ok".to_string(), cache_entry("2025-01-01_ok"));
+        cache
+            .documents
+            .insert("no-md".to_string(), cache_entry("2025-01-02_no-md"));
+        cache
+            .documents
+            .insert("no-json".to_string(), cache_entry("2025-01-03_no-json"));
+        let cache_path = paths.data_dir.join(".sync_cache.json");
+        save_cache(&cache_path, &cache, &paths.tmp_dir).unwrap();
+
+        for name in ["2025-01-01_ok", "2025-01-03_no-json"] {
+            std::fs::write(paths.transcripts_dir.join(format!("{}.md", name)), "x").unwrap();
+        }
+        for name in ["2025-01-01_ok", "2025-01-02_no-md"] {
+            std::fs::write(paths.raw_dir.join(format!("{}.json", name)), "{}").unwrap();
+        }
+
+        assert_eq!(
+            drop_missing_cache_entries(&paths).unwrap(),
+            vec!["no-json", "no-md"]
+        );
+        let remaining = load_cache(&cache_path);
+        assert_eq!(remaining.documents.len(), 1);
+        assert!(remaining.documents.contains_key("ok"));
+        // The feed won't bring the dropped documents back, so the next sync lists everything
+        assert!(remaining.cursor.is_none());
+        assert!(drop_missing_cache_entries(&paths).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "index")]
+    #[test]
+    fn test_reindex_skips_unreadable_transcripts() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        std::fs::write(
+            paths.transcripts_dir.join("2025-01-01_good.md"),
+            "---\ndoc_id: good\nsource: granola\ncreated_at: 2025-01-01T10:00:00Z\ntitle: Good\ngenerator: muesli 1.0\n---\n\nbody\n",
+        )
+        .unwrap();
+        std::fs::write(
+            paths.transcripts_dir.join("2025-01-02_broken.md"),
+            "---\ndoc_id: [unclosed\n---\n\nbody\n",
+        )
+        .unwrap();
+
+        reindex_all(&paths).unwrap();
+
+        let catalog = Catalog::open(&paths).unwrap();
+        assert!(catalog.lookup("good").unwrap().is_some());
+        assert_eq!(catalog.entries().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_remove_local_files() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let md = paths.transcripts_dir.join("2025-01-01_a.md");
+        let json = paths.raw_dir.join("2025-01-01_a.json");
+        let other = paths.transcripts_dir.join("2025-01-02_b.md");
+        let cache_dir = paths.data_dir.join("http_cache");
+        std::fs::create_dir_all(&cache_dir).unwrap();
+        let cached_name = |doc_id: &str| {
+            crate::api::fixture_name(
+                "/v1/get-document-transcript",
+                &serde_json::json!({ "document_id": doc_id }),
+            )
+        };
+        let cached = cache_dir.join(cached_name("a"));
+        let legacy_cached = cache_dir.join("v1_get-document-panels__a.json");
+        let other_cached = cache_dir.join(cached_name("b"));
+        for p in [&md, &json, &other, &cached, &legacy_cached, &other_cached] {
+            std::fs::write(p, "x").unwrap();
+        }
+
+        remove_local_files(&paths, "a", "2025-01-01_a").unwrap();
+
+        assert!(!md.exists());
+        assert!(!json.exists());
+        assert!(!cached.exists());
+        assert!(!legacy_cached.exists());
+        assert!(other.exists());
+        assert!(other_cached.exists());
+    }
+
+    #[test]
+    fn test_save_summary() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        let entry = crate::storage::CatalogEntry {
+            doc_id: "a".into(),
+            path: paths.transcripts_dir.join("2025-01-01_a.md"),
+            title: Some("A".into()),
+            created_at: Utc::now(),
+            participants: vec![],
+            labels: vec![],
+            organizations: vec![],
+            content_hash: None,
+            duplicate_of: None,
+            merged_duplicates: vec![],
+            access: None,
+            modified: None,
+        };
+
+        std::fs::create_dir_all(&paths.tmp_dir).unwrap();
+        let path = save_summary(&paths, &entry, "Decided things").unwrap();
+        assert_eq!(path, temp.path().join("summaries/2025-01-01_a_summary.md"));
+        assert_eq!(summary_path(&paths, &entry.path), path);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "Decided things");
+
+        // Regenerating keeps the version it replaces; an identical save keeps it too
+        let previous = previous_summary_path(&paths, &entry.path);
+        save_summary(&paths, &entry, "Decided more things").unwrap();
+        save_summary(&paths, &entry, "Decided more things").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&previous).unwrap(),
+            "Decided things"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "Decided more things"
+        );
+    }
+
+    #[test]
+    fn test_sync_creates_index_directory() {
+        // Verify that sync operation creates the index directory structure
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+
+        // Call ensure_dirs to set up directory structure
+        paths.ensure_dirs().unwrap();
+
+        // Verify index directory exists at the correct path
+        assert!(
+            paths.index_dir.exists(),
+            "index_dir should exist at {}",
+            paths.index_dir.display()
+        );
+
+        // Verify it's the tantivy subdirectory
+        assert!(
+            paths.index_dir.ends_with("index/tantivy"),
+            "index_dir should end with 'index/tantivy', got {}",
+            paths.index_dir.display()
+        );
+    }
+
+    #[cfg(feature = "semantic")]
+    #[test]
+    fn test_embedding_text_truncates_on_char_boundary() {
+        assert_eq!(embedding_text(Some("Standup"), "notes"), "Standup\n\nnotes");
+        assert_eq!(embedding_text(None, "notes"), "notes");
+
+        let long = "é".repeat(1500);
+        let text = embedding_text(None, &long);
+        assert_eq!(text.len(), 2000);
+        assert!(text.chars().all(|c| c == 'é'));
+    }
+
+    #[cfg(feature = "semantic")]
+    #[test]
+    fn test_resume_checkpoint_only_for_same_model() {
+        use crate::embeddings::downloader::ModelId;
+
+        let temp = TempDir::new().unwrap();
+        let checkpoint = temp.path().join("reembed");
+        let model = |hash: &str| ModelId {
+            name: "e5-small-v2".into(),
+            hash: hash.into(),
+        };
+        assert!(resume_checkpoint(&checkpoint, &model("a")).is_none());
+
+        let mut store = VectorStore::new(2);
+        store.pin_model(&model("a")).unwrap();
+        store.add_document("d1".into(), vec![1.0, 0.0]).unwrap();
+        store.save(&checkpoint, temp.path()).unwrap();
+
+        let resumed = resume_checkpoint(&checkpoint, &model("a")).unwrap();
+        assert!(resumed.has_document("d1"));
+        assert!(resume_checkpoint(&checkpoint, &model("b")).is_none());
+
+        VectorStore::delete(&checkpoint).unwrap();
+        assert!(!checkpoint.with_extension("meta.json").exists());
+        assert!(resume_checkpoint(&checkpoint, &model("a")).is_none());
+    }
+}
+
+#[cfg(all(test, feature = "index"))]
+mod index_tests {
+    use crate::index::text::create_or_open_index;
+    use crate::storage::Paths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_integration_with_sync() {
+        // Test that the index directory path works with the indexing module
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        // Verify we can create an index at the configured path
+        let index = create_or_open_index(&paths.index_dir).unwrap();
+        let schema = index.schema();
+
+        // Verify schema has required fields
+        assert!(schema.get_field("doc_id").is_ok());
+        assert!(schema.get_field("title").is_ok());
+        assert!(schema.get_field("body").is_ok());
+    }
+}
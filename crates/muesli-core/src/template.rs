@@ -0,0 +1,121 @@
+// ABOUTME: User templates for the transcript markdown body, written in Handlebars
+// ABOUTME: Exposes the frontmatter, meeting metadata and entries; muesli still writes the frontmatter
+
+use crate::{Error, Frontmatter, Paths, Result};
+use handlebars::Handlebars;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Template file in the data directory
+pub const TEMPLATE_FILE: &str = "templates/transcript.md.hbs";
+
+const NAME: &str = "transcript";
+
+/// A parsed transcript template, rendered once per document
+#[derive(Clone)]
+pub struct TranscriptTemplate {
+    path: PathBuf,
+    source: String,
+    registry: Arc<Handlebars<'static>>,
+}
+
+impl std::fmt::Debug for TranscriptTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranscriptTemplate")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl PartialEq for TranscriptTemplate {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.source == other.source
+    }
+}
+
+/// What a template sees
+#[derive(Debug, Serialize)]
+pub struct TemplateContext<'a> {
+    /// Every frontmatter field, as written at the top of the file
+    pub frontmatter: &'a Frontmatter,
+    pub metadata: TemplateMetadata<'a>,
+    pub entries: Vec<EntryView>,
+    /// The entries in the layout `[markdown]` asks for
+    pub transcript: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TemplateMetadata<'a> {
+    /// "Untitled Meeting" when the meeting has none
+    pub title: &'a str,
+    /// YYYY-MM-DD
+    pub date: String,
+    /// HH:MM, UTC
+    pub time: String,
+    pub duration_minutes: Option<u64>,
+    pub participants: &'a [String],
+    pub labels: &'a [String],
+    /// "Date: ... · Duration: ...m · Participants: ..." as in the default layout
+    pub line: String,
+    /// The "## Attendees" section from the calendar event, or empty
+    pub attendees: String,
+}
+
+/// One transcript entry as shown
+#[derive(Debug, Clone, Serialize)]
+pub struct EntryView {
+    /// Speaker with inferred names applied and "(?)" for doubtful attributions
+    pub speaker: String,
+    /// Start time at the configured precision, when timestamps are on
+    pub timestamp: Option<String>,
+    /// `t-HH-MM-SS` from the start time, unique within the transcript
+    pub anchor: Option<String>,
+    pub text: String,
+    /// Raw start and end times from the API
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub confidence: Option<f64>,
+    /// "[5m gap]" when the recording paused before this entry
+    pub gap_before: Option<String>,
+}
+
+impl TranscriptTemplate {
+    /// Parses a template file; a missing file means the built-in layout
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut registry = Handlebars::new();
+        // The output is markdown, so nothing should be HTML-escaped
+        registry.register_escape_fn(handlebars::no_escape);
+        registry
+            .register_template_string(NAME, &source)
+            .map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", path.display(), e)))?;
+
+        Ok(Some(Self {
+            path: path.to_path_buf(),
+            source,
+            registry: Arc::new(registry),
+        }))
+    }
+
+    /// The template in the data directory, if there is one
+    pub fn configured(paths: &Paths) -> Result<Option<Self>> {
+        Self::load(&paths.data_dir.join(TEMPLATE_FILE))
+    }
+
+    /// The markdown body for one document, ending in a newline
+    pub fn render(&self, context: &TemplateContext) -> Result<String> {
+        let mut body = self.registry.render(NAME, context).map_err(|e| {
+            Error::InvalidInput(format!("Rendering {} failed: {}", self.path.display(), e))
+        })?;
+        if !body.ends_with('\n') {
+            body.push('\n');
+        }
+        Ok(body)
+    }
+}
@@ -0,0 +1,143 @@
+// ABOUTME: Terminal capability policy for colors, emoji and progress bars
+// ABOUTME: Honors --color, NO_COLOR and TTY detection so piped output stays plain
+
+#[cfg(feature = "progress")]
+pub use indicatif::ProgressBar;
+#[cfg(not(feature = "progress"))]
+pub use quiet::ProgressBar;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// When to emit ANSI colors and emoji
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ColorChoice {
+    /// Decorate only when stdout is a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    /// Always decorate output
+    Always,
+    /// Never decorate output
+    Never,
+}
+
+static FANCY: OnceLock<bool> = OnceLock::new();
+
+fn decide(choice: ColorChoice, no_color: Option<&str>, is_tty: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        // https://no-color.org: any non-empty value disables color
+        ColorChoice::Auto => is_tty && no_color.map_or(true, str::is_empty),
+    }
+}
+
+/// Applies the color policy for the rest of the process; call once at startup
+pub fn init(choice: ColorChoice) {
+    let no_color = std::env::var("NO_COLOR").ok();
+    let fancy = decide(choice, no_color.as_deref(), std::io::stdout().is_terminal());
+    let _ = FANCY.set(fancy);
+
+    // indicatif styles its templates through `console`
+    console::set_colors_enabled(fancy);
+    console::set_colors_enabled_stderr(fancy);
+}
+
+/// Whether colors and emoji should be emitted
+pub fn fancy() -> bool {
+    *FANCY.get_or_init(|| {
+        let no_color = std::env::var("NO_COLOR").ok();
+        decide(
+            ColorChoice::Auto,
+            no_color.as_deref(),
+            std::io::stdout().is_terminal(),
+        )
+    })
+}
+
+/// Returns the emoji prefix when decorations are enabled, otherwise an empty string
+pub fn emoji(symbol: &'static str) -> &'static str {
+    if fancy() {
+        symbol
+    } else {
+        ""
+    }
+}
+
+/// Builds a progress bar that is hidden when stderr is not a terminal
+#[cfg(feature = "progress")]
+pub fn progress_bar(len: u64, template: &str, progress_chars: &str) -> ProgressBar {
+    use indicatif::ProgressStyle;
+
+    if !std::io::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+
+    let pb = ProgressBar::new(len);
+    if let Ok(style) = ProgressStyle::default_bar().template(template) {
+        pb.set_style(style.progress_chars(progress_chars));
+    }
+    pb
+}
+
+/// Without the `progress` feature every bar is hidden
+#[cfg(not(feature = "progress"))]
+pub fn progress_bar(_len: u64, _template: &str, _progress_chars: &str) -> ProgressBar {
+    ProgressBar::hidden()
+}
+
+/// Stands in for indicatif's bar when the `progress` feature is off
+#[cfg(not(feature = "progress"))]
+mod quiet {
+    use std::borrow::Cow;
+
+    /// Accepts the calls muesli makes on a progress bar and draws nothing
+    #[derive(Debug, Clone, Default)]
+    pub struct ProgressBar;
+
+    impl ProgressBar {
+        pub fn hidden() -> Self {
+            ProgressBar
+        }
+
+        pub fn inc(&self, _delta: u64) {}
+
+        pub fn set_position(&self, _pos: u64) {}
+
+        pub fn reset_eta(&self) {}
+
+        pub fn set_message(&self, _msg: impl Into<Cow<'static, str>>) {}
+
+        pub fn suspend<F: FnOnce() -> R, R>(&self, f: F) -> R {
+            f()
+        }
+
+        pub fn finish_and_clear(&self) {}
+
+        pub fn finish_with_message(&self, _msg: impl Into<Cow<'static, str>>) {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_choice_wins() {
+        assert!(decide(ColorChoice::Always, Some("1"), false));
+        assert!(!decide(ColorChoice::Never, None, true));
+    }
+
+    #[test]
+    fn test_auto_requires_tty() {
+        assert!(decide(ColorChoice::Auto, None, true));
+        assert!(!decide(ColorChoice::Auto, None, false));
+    }
+
+    #[test]
+    fn test_auto_honors_no_color() {
+        assert!(!decide(ColorChoice::Auto, Some("1"), true));
+        // An empty NO_COLOR does not disable color
+        assert!(decide(ColorChoice::Auto, Some(""), true));
+    }
+}
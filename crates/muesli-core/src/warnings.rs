@@ -0,0 +1,172 @@
+// ABOUTME: Collects the per-document warnings of a long operation (sync, reindex, re-embed)
+// ABOUTME: Shows the first few of each kind, logs all of them, and sums them up at the end
+
+use crate::{term, Paths};
+use chrono::Utc;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Warnings of one kind printed before the rest only go to the log
+pub const SHOWN_PER_KIND: usize = 3;
+
+/// Every warning of one message, e.g. "Failed to embed"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WarningKind {
+    pub message: String,
+    pub count: usize,
+    /// Detail of the first occurrence
+    pub first: String,
+}
+
+/// The warnings of one run, logged to `logs/<operation>.log` in the data directory
+///
+/// The log is started over by the first warning of each run, so it always holds
+/// the latest run's complete list.
+pub struct Warnings {
+    log_path: PathBuf,
+    log: Option<File>,
+    kinds: Vec<WarningKind>,
+}
+
+impl Warnings {
+    pub fn new(paths: &Paths, operation: &str) -> Self {
+        Warnings {
+            log_path: paths
+                .data_dir
+                .join("logs")
+                .join(format!("{}.log", operation)),
+            log: None,
+            kinds: Vec::new(),
+        }
+    }
+
+    /// Reports `Warning: <message> <detail>`
+    ///
+    /// Printed for the first `SHOWN_PER_KIND` of each message, then counted
+    /// quietly; every one is written to the log.
+    pub fn warn(&mut self, message: &str, detail: impl Display) {
+        let line = format!("Warning: {} {}", message, detail);
+        self.write_log(&line);
+
+        let kind = match self.kinds.iter_mut().position(|k| k.message == message) {
+            Some(i) => &mut self.kinds[i],
+            None => {
+                self.kinds.push(WarningKind {
+                    message: message.to_string(),
+                    count: 0,
+                    first: detail.to_string(),
+                });
+                self.kinds.last_mut().expect("just pushed")
+            }
+        };
+        kind.count += 1;
+        if kind.count <= SHOWN_PER_KIND {
+            eprintln!("{}", line);
+        } else if kind.count == SHOWN_PER_KIND + 1 {
+            eprintln!(
+                "Warning: more \"{}\" warnings are hidden; they are counted at the end",
+                message
+            );
+        }
+    }
+
+    fn write_log(&mut self, line: &str) {
+        if self.log.is_none() {
+            // Logging is best effort; the warning itself is still counted and shown
+            let _ = fs::create_dir_all(self.log_path.parent().unwrap_or(&self.log_path));
+            self.log = File::create(&self.log_path).ok();
+        }
+        if let Some(log) = &mut self.log {
+            let _ = writeln!(log, "{} {}", Utc::now().to_rfc3339(), line);
+        }
+    }
+
+    pub fn kinds(&self) -> &[WarningKind] {
+        &self.kinds
+    }
+
+    pub fn total(&self) -> usize {
+        self.kinds.iter().map(|k| k.count).sum()
+    }
+
+    /// Counts per kind, most frequent first, when some warnings weren't printed
+    pub fn summary(&self) -> Option<String> {
+        if self.kinds.iter().all(|k| k.count <= SHOWN_PER_KIND) {
+            return None;
+        }
+        let mut kinds: Vec<&WarningKind> = self.kinds.iter().collect();
+        kinds.sort_by_key(|k| std::cmp::Reverse(k.count));
+        let width = kinds.iter().map(|k| k.message.len()).max().unwrap_or(0);
+
+        let mut out = format!(
+            "{}{} warnings; all of them are in {}\n",
+            term::emoji("⚠️  "),
+            self.total(),
+            self.log_path.display()
+        );
+        for kind in kinds {
+            out.push_str(&format!(
+                "  {:>6}  {:<width$}  (first: {})\n",
+                kind.count, kind.message, kind.first
+            ));
+        }
+        Some(out)
+    }
+
+    /// Prints the summary, if there is one
+    pub fn finish(self) {
+        if let Some(summary) = self.summary() {
+            eprint!("{}", summary);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_counts_and_logs_every_warning() {
+        let temp = TempDir::new().unwrap();
+        let paths = Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+        let mut warnings = Warnings::new(&paths, "sync");
+        assert_eq!(warnings.summary(), None);
+
+        for i in 0..5 {
+            warnings.warn("Failed to embed", format!("doc{}: model error", i));
+        }
+        warnings.warn("Failed to index", "doc9: locked");
+        assert_eq!(warnings.total(), 6);
+        assert_eq!(
+            warnings.kinds()[0],
+            WarningKind {
+                message: "Failed to embed".to_string(),
+                count: 5,
+                first: "doc0: model error".to_string(),
+            }
+        );
+
+        let summary = warnings.summary().unwrap();
+        assert!(summary.contains("6 warnings; all of them are in"));
+        assert!(summary.contains("logs/sync.log"));
+        assert!(summary.contains("       5  Failed to embed  (first: doc0: model error)\n"));
+
+        let log = fs::read_to_string(temp.path().join("logs/sync.log")).unwrap();
+        assert_eq!(log.lines().count(), 6);
+        assert!(log
+            .lines()
+            .last()
+            .unwrap()
+            .ends_with("Warning: Failed to index doc9: locked"));
+
+        // A new run starts a new log
+        let mut next = Warnings::new(&paths, "sync");
+        next.warn("Failed to index", "doc1: locked");
+        assert_eq!(next.summary(), None);
+        let log = fs::read_to_string(temp.path().join("logs/sync.log")).unwrap();
+        assert_eq!(log.lines().count(), 1);
+    }
+}
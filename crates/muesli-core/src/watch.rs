@@ -0,0 +1,178 @@
+// ABOUTME: Long-running watch mode that syncs on a fixed interval
+// ABOUTME: Logs each run, backs off while the API is failing and compacts the index daily
+
+use crate::alerts;
+use crate::storage::Catalog;
+#[cfg(feature = "index")]
+use crate::util::format_bytes;
+use crate::{api::DocumentSource, storage::Paths, sync::sync_all, sync::SyncOptions, Result};
+use chrono::Local;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Upper bound on the delay between failed attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// First retry delay after a failure
+const INITIAL_BACKOFF: Duration = Duration::from_secs(30);
+
+/// How often watch mode merges search index segments
+const OPTIMIZE_EVERY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Delay before the next sync given how many runs in a row have failed
+///
+/// Successful runs wait the regular interval; failures retry sooner at first and
+/// then back off exponentially, never exceeding the larger of the interval and
+/// `MAX_BACKOFF`.
+pub fn next_delay(interval: Duration, consecutive_failures: u32) -> Duration {
+    if consecutive_failures == 0 {
+        return interval;
+    }
+
+    let cap = interval.max(MAX_BACKOFF);
+    let exponent = consecutive_failures.saturating_sub(1).min(16);
+    INITIAL_BACKOFF.saturating_mul(1 << exponent).min(cap)
+}
+
+fn log(message: &str) {
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+/// Compacts the text index, logging rather than failing the watch loop
+#[cfg(feature = "index")]
+fn optimize_index(paths: &Paths) {
+    let config = crate::index::writer::WriterConfig::configured(paths);
+    match config.and_then(|config| crate::index::text::optimize(&paths.index_dir, &config)) {
+        Ok(report) => log(&format!(
+            "Optimized index: {} -> {} segments, {} -> {}",
+            report.before.segments,
+            report.after.segments,
+            format_bytes(report.before.bytes),
+            format_bytes(report.after.bytes)
+        )),
+        Err(e) => log(&format!("Index optimize failed: {}", e)),
+    }
+}
+
+#[cfg(not(feature = "index"))]
+fn optimize_index(_paths: &Paths) {}
+
+/// Document IDs already in the catalog, so meetings added by a sync can be told apart
+fn known_doc_ids(paths: &Paths) -> Result<HashSet<String>> {
+    let entries = Catalog::open(paths)?.entries()?;
+    Ok(entries.into_iter().map(|e| e.doc_id).collect())
+}
+
+/// Checks meetings that were not in the catalog before the sync against the alert rules
+async fn check_alerts(paths: &Paths, rules_path: &Path, known: &HashSet<String>) -> Result<()> {
+    // Reloaded every run so rule edits apply without restarting watch mode
+    let rules = alerts::load_rules(rules_path)?;
+    if rules.is_empty() {
+        return Ok(());
+    }
+
+    let new_entries: Vec<_> = Catalog::open(paths)?
+        .entries()?
+        .into_iter()
+        .filter(|e| !known.contains(&e.doc_id))
+        .collect();
+    if new_entries.is_empty() {
+        return Ok(());
+    }
+
+    for (i, alert) in alerts::evaluate(paths, &rules, &new_entries)? {
+        log(&format!(
+            "Alert {}: {}",
+            alerts::describe(&alert),
+            alert.snippet
+        ));
+        for failure in alerts::dispatch(&rules[i], &alert).await {
+            log(&format!("Alert {}: {}", alert.rule, failure));
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs `sync_all` forever, sleeping `interval` between successful runs
+///
+/// Newly synced meetings are checked against the rules in `alerts_path`.
+pub async fn watch(
+    source: &dyn DocumentSource,
+    paths: &Paths,
+    options: &SyncOptions,
+    interval: Duration,
+    alerts_path: &Path,
+) -> Result<()> {
+    let mut failures = 0u32;
+    let mut last_optimized = Instant::now();
+
+    log(&format!(
+        "Watching for changes every {}s (Ctrl-C to stop)",
+        interval.as_secs()
+    ));
+
+    loop {
+        log("Starting sync");
+        let known = match known_doc_ids(paths) {
+            Ok(known) => Some(known),
+            Err(e) => {
+                log(&format!("Alerts skipped this run: {}", e));
+                None
+            }
+        };
+
+        match sync_all(source, paths, options).await {
+            Ok(()) => {
+                failures = 0;
+                log("Sync finished");
+
+                if let Some(known) = &known {
+                    if let Err(e) = check_alerts(paths, alerts_path, known).await {
+                        log(&format!("Alert check failed: {}", e));
+                    }
+                }
+
+                if last_optimized.elapsed() >= OPTIMIZE_EVERY {
+                    optimize_index(paths);
+                    last_optimized = Instant::now();
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                log(&format!("Sync failed ({} in a row): {}", failures, e));
+            }
+        }
+
+        let delay = next_delay(interval, failures);
+        log(&format!("Next sync in {}s", delay.as_secs()));
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_delay_success_uses_interval() {
+        let interval = Duration::from_secs(900);
+        assert_eq!(next_delay(interval, 0), interval);
+    }
+
+    #[test]
+    fn test_next_delay_backs_off() {
+        let interval = Duration::from_secs(900);
+        assert_eq!(next_delay(interval, 1), Duration::from_secs(30));
+        assert_eq!(next_delay(interval, 2), Duration::from_secs(60));
+        assert_eq!(next_delay(interval, 3), Duration::from_secs(120));
+        assert_eq!(next_delay(interval, 20), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_next_delay_respects_long_interval() {
+        let interval = Duration::from_secs(6 * 60 * 60);
+        assert_eq!(next_delay(interval, 30), interval);
+    }
+}
@@ -1,8 +1,18 @@
 // ABOUTME: Command-line interface definitions using clap
 // ABOUTME: Defines all subcommands and global flags
 
-use clap::{Parser, Subcommand};
+use crate::export::ExportFormat;
+use crate::output::{OutputFormat, OutputOptions};
+use crate::profile::Profile;
+use crate::storage::Paths;
+use crate::subtitles::SubtitleFormat;
+use crate::term::ColorChoice;
+use crate::Error;
+use chrono::NaiveDate;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "muesli")]
@@ -24,6 +34,10 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub data_dir: Option<PathBuf>,
 
+    /// Use a named account from profiles.toml (its own data directory and token)
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// Disable throttling (not recommended)
     #[arg(long, global = true)]
     pub no_throttle: bool,
@@ -31,6 +45,94 @@ pub struct Cli {
     /// Throttle range in ms (min:max)
     #[arg(long, global = true, value_parser = parse_throttle_range)]
     pub throttle_ms: Option<(u64, u64)>,
+
+    /// Record API responses as JSON fixtures in this directory
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay API responses from fixtures in this directory (no network)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub replay: Option<PathBuf>,
+
+    /// Never touch the network: local commands work as usual, commands that need it fail
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Output format for list, search, stats and report commands, or the export format;
+    /// json makes summarize, interview-packet and incident-timeline return structured output
+    #[arg(long, global = true, value_enum, default_value_t = Format::Table)]
+    pub format: Format,
+
+    /// Comma-separated columns to show (e.g. id,title,date)
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub columns: Option<Vec<String>>,
+
+    /// Omit the header line in table and tsv output
+    #[arg(long, global = true)]
+    pub no_header: bool,
+
+    /// When to use colors and emoji (auto honors NO_COLOR and TTY detection)
+    #[arg(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Print time spent in each phase (API, convert, index, embed, ...) to stderr at exit
+    #[arg(long, global = true)]
+    pub profile_perf: bool,
+
+    /// Write the phase timings to this file as JSON
+    #[arg(long, global = true, value_name = "FILE")]
+    pub profile_perf_json: Option<PathBuf>,
+}
+
+/// Values of the global `--format` flag
+///
+/// Export formats share the flag with the tabular ones so `muesli export
+/// --format srt` reads naturally; `Cli::check_format` rejects mismatches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Human-readable aligned columns
+    Table,
+    /// Tab-separated values
+    Tsv,
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    /// SubRip subtitles (export only)
+    Srt,
+    /// WebVTT subtitles (export only)
+    Vtt,
+    /// A standalone HTML page (export only)
+    Html,
+}
+
+impl Format {
+    /// The tabular format, if this is one
+    pub fn output(self) -> Option<OutputFormat> {
+        match self {
+            Format::Table => Some(OutputFormat::Table),
+            Format::Tsv => Some(OutputFormat::Tsv),
+            Format::Json => Some(OutputFormat::Json),
+            Format::Ndjson => Some(OutputFormat::Ndjson),
+            Format::Srt | Format::Vtt | Format::Html => None,
+        }
+    }
+
+    /// The export format, if this is one
+    pub fn export(self) -> Option<ExportFormat> {
+        match self {
+            Format::Srt => Some(ExportFormat::Subtitles(SubtitleFormat::Srt)),
+            Format::Vtt => Some(ExportFormat::Subtitles(SubtitleFormat::Vtt)),
+            Format::Html => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> String {
+        self.to_possible_value()
+            .map(|v| v.get_name().to_string())
+            .unwrap_or_default()
+    }
 }
 
 fn parse_throttle_range(s: &str) -> Result<(u64, u64), String> {
@@ -49,6 +151,69 @@ fn parse_throttle_range(s: &str) -> Result<(u64, u64), String> {
     Ok((min, max))
 }
 
+/// Parses a speaker confidence threshold between 0 and 1
+fn parse_confidence(s: &str) -> Result<f64, String> {
+    s.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|c| (0.0..=1.0).contains(c))
+        .ok_or_else(|| "Expected a confidence between 0 and 1, like 0.6".into())
+}
+
+fn parse_date(s: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| "Expected format: YYYY-MM-DD".into())
+}
+
+/// Parses a start date given as YYYY-MM-DD or as a lookback like "90d" or "12w"
+fn parse_since(s: &str) -> Result<NaiveDate, String> {
+    since_date(s, chrono::Local::now().date_naive())
+}
+
+fn since_date(s: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let s = s.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let invalid = || "Expected YYYY-MM-DD or a lookback like 30d or 12w".to_string();
+    let (number, unit) = s.split_at(s.len().saturating_sub(1));
+    let value: u64 = number.parse().map_err(|_| invalid())?;
+    let days = match unit {
+        "d" => value,
+        "w" => value * 7,
+        _ => return Err(invalid()),
+    };
+
+    today
+        .checked_sub_days(chrono::Days::new(days))
+        .ok_or_else(invalid)
+}
+
+/// Parses durations like "90s", "15m", "2h" or plain seconds; at least one minute
+fn parse_interval(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (number, unit) = match s.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((pos, _)) => s.split_at(pos),
+        None => (s, "s"),
+    };
+
+    let value: u64 = number
+        .parse()
+        .map_err(|_| "Expected a duration like 90s, 15m or 1h".to_string())?;
+    let secs = match unit {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 60 * 60,
+        _ => return Err(format!("Unknown unit '{}'; use s, m or h", unit)),
+    };
+
+    if secs < 60 {
+        return Err("Interval must be at least 1m".into());
+    }
+
+    Ok(Duration::from_secs(secs))
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     /// Sync all documents (default)
@@ -57,15 +222,164 @@ pub enum Commands {
         #[arg(long)]
         #[cfg(feature = "index")]
         reindex: bool,
+
+        /// With --reindex, also regenerate every embedding from local files
+        #[arg(long, requires = "reindex")]
+        #[cfg(feature = "semantic")]
+        embeddings: bool,
+
+        /// With --embeddings, replace vectors built with a different embedding model
+        #[arg(long, requires = "embeddings")]
+        #[cfg(feature = "semantic")]
+        migrate_model: bool,
+
+        /// With --embeddings, documents embedded between progress checkpoints [default: 32]
+        #[arg(long, value_name = "N", requires = "embeddings")]
+        #[cfg(feature = "semantic")]
+        batch_size: Option<NonZeroUsize>,
+
+        /// Refresh the "Related meetings" links in every transcript after syncing
+        #[arg(long)]
+        #[cfg(feature = "semantic")]
+        link: bool,
+
+        /// Remove local files, index entries and vectors for documents deleted remotely
+        #[arg(long)]
+        prune: bool,
+
+        /// Print what would be downloaded, updated, renamed, embedded or pruned, then exit
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only sync meetings created on or after this date (YYYY-MM-DD)
+        #[arg(long, value_parser = parse_date)]
+        since: Option<NaiveDate>,
+
+        /// Only sync meetings created on or before this date (YYYY-MM-DD)
+        #[arg(long, value_parser = parse_date)]
+        until: Option<NaiveDate>,
+
+        /// Sync the most recent meetings first (default)
+        #[arg(long, conflicts_with = "oldest_first")]
+        newest_first: bool,
+
+        /// Sync the oldest meetings first
+        #[arg(long)]
+        oldest_first: bool,
+
+        /// Stop after downloading or updating this many documents
+        #[arg(long, value_name = "N")]
+        max_docs: Option<NonZeroUsize>,
+
+        /// Re-download, re-index and re-embed this document even if unchanged (repeatable)
+        #[arg(long, value_name = "DOC_ID")]
+        force: Vec<String>,
+
+        /// Offer attendee names for "Speaker 1/2" in two-person meetings
+        #[arg(long)]
+        name_speakers: bool,
+
+        /// With --name-speakers, apply confident guesses without asking
+        #[arg(long, requires = "name_speakers")]
+        auto: bool,
+
+        /// Mark speakers attributed with less confidence than this (0-1) as "Alice(?)";
+        /// overrides [markdown] in config.toml
+        #[arg(long, value_parser = parse_confidence)]
+        min_confidence: Option<f64>,
+
+        /// Sync meetings from a folder of raw transcript JSON and WebVTT files instead of Granola
+        #[arg(long, value_name = "DIR", conflicts_with = "prune")]
+        from_dir: Option<PathBuf>,
+    },
+
+    /// Run continuously, syncing on a fixed interval with backoff on errors
+    Watch {
+        /// Time between syncs (e.g. 90s, 15m, 1h)
+        #[arg(long, default_value = "15m", value_parser = parse_interval)]
+        interval: Duration,
+
+        /// Also remove documents deleted remotely on each run
+        #[arg(long)]
+        prune: bool,
+
+        /// Alert rules checked against new meetings (default: <data-dir>/alerts.toml)
+        #[arg(long)]
+        alerts: Option<PathBuf>,
     },
 
     /// List all documents
-    List,
+    List {
+        /// List the synced transcripts from disk instead of asking the API (implied by --offline)
+        #[arg(long)]
+        local: bool,
+    },
 
     /// Fetch a specific document by ID
     Fetch {
         /// Document ID to fetch
         id: String,
+
+        /// Mark speakers attributed with less confidence than this (0-1) as "Alice(?)";
+        /// overrides [markdown] in config.toml
+        #[arg(long, value_parser = parse_confidence)]
+        min_confidence: Option<f64>,
+    },
+
+    /// Import WebVTT/SRT caption files (Zoom, Meet, Teams exports), Fireflies.ai/Otter.ai JSON exports or .eml notes emails as meetings
+    Import {
+        /// Caption files, each a meeting whose ID is its file name, .json exports from Fireflies.ai or Otter.ai, or .eml notes emails
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Meeting title [default: the file name]
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Meeting date (YYYY-MM-DD) [default: a leading date in the file name, else its modification time]
+        #[arg(long, value_parser = parse_date)]
+        date: Option<NaiveDate>,
+    },
+
+    /// Import meeting notes emailed to an IMAP folder (e.g. forwarded Otter or Zoom summaries), set up under [email] in config.toml
+    #[cfg(feature = "email")]
+    ImportEmail {
+        /// Folder to read [default: folder under [email] in config.toml, else Muesli]
+        #[arg(long)]
+        folder: Option<String>,
+
+        /// Read every message in the folder, not only unread ones
+        #[arg(long)]
+        all: bool,
+
+        /// Store the IMAP password (read from stdin) in the system keychain, then exit
+        #[cfg(feature = "keychain")]
+        #[arg(long, conflicts_with_all = ["folder", "all"])]
+        save_password: bool,
+    },
+
+    /// Transcribe recordings locally with whisper.cpp and store them as meetings (requires 'transcribe' feature)
+    #[cfg(feature = "transcribe")]
+    Transcribe {
+        /// Audio files (m4a, mp3, wav, flac, ogg); each becomes a meeting whose ID is its file name
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// whisper.cpp GGML model file; overrides [transcribe] in config.toml
+        #[arg(long)]
+        model: Option<PathBuf>,
+
+        /// Spoken language code, e.g. "en"; overrides [transcribe] in config.toml [default: detect]
+        #[arg(long)]
+        language: Option<String>,
+
+        /// Meeting title [default: the file name]
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Meeting date (YYYY-MM-DD) [default: a leading date in the file name, else its modification time]
+        #[arg(long, value_parser = parse_date)]
+        date: Option<NaiveDate>,
     },
 
     /// Search indexed documents (requires 'index' feature)
@@ -80,27 +394,313 @@ pub enum Commands {
 
         /// Use semantic search with embeddings (requires 'embeddings' feature)
         #[arg(long)]
-        #[cfg(feature = "embeddings")]
+        #[cfg(feature = "semantic")]
         semantic: bool,
+
+        /// Blend text and semantic rankings (weights from [search] in <data-dir>/config.toml)
+        #[arg(long, conflicts_with = "semantic")]
+        #[cfg(feature = "semantic")]
+        hybrid: bool,
+
+        /// Only show meetings with a participant from this email domain (e.g. acme.com)
+        #[arg(long)]
+        org: Option<String>,
+
+        /// Only show meetings with this participant (name or email, e.g. alice)
+        #[arg(long)]
+        participant: Option<String>,
+
+        /// Only show meetings with this Granola label (e.g. planning)
+        #[arg(long)]
+        label: Option<String>,
+
+        /// Hide meetings with this participant (repeatable)
+        #[arg(long, value_name = "NAME")]
+        exclude_participant: Vec<String>,
+
+        /// Hide meetings with this label (repeatable, e.g. personal)
+        #[arg(long, value_name = "LABEL")]
+        exclude_label: Vec<String>,
+
+        /// Also search summaries saved with 'summarize --save' (text search only)
+        #[arg(long)]
+        include_summaries: bool,
+
+        /// Search a snapshot imported with 'muesli index import' instead of your own meetings
+        #[arg(long, value_name = "NAME")]
+        snapshot: Option<String>,
+    },
+
+    /// Search inside one transcript, showing matching entries with context
+    FindIn {
+        /// Document ID to search
+        doc_id: String,
+
+        /// Words to look for (case-insensitive)
+        query: String,
+
+        /// Entries to show before and after each match
+        #[arg(short = 'C', long, default_value_t = 1)]
+        context: usize,
+
+        /// Maximum number of matches to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Also copy the matches to the clipboard
+        #[cfg(feature = "clipboard")]
+        #[arg(long)]
+        copy: bool,
+    },
+
+    /// Write a transcript as subtitles (--format srt or vtt) or a standalone HTML page (--format html),
+    /// every transcript into an Obsidian vault (--obsidian), or one meeting's complete record as a zip (--bundle)
+    Export {
+        /// Document ID to export
+        #[arg(required_unless_present_any = ["all", "obsidian"])]
+        doc_id: Option<String>,
+
+        /// Export every transcript, one file each, into the --output directory
+        #[arg(long, conflicts_with = "doc_id")]
+        all: bool,
+
+        /// Write to this file (with --all, this directory; default: exports/ in the data directory)
+        /// instead of stdout
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// Write every transcript as a note in this vault's Meetings folder, with wiki-links
+        #[arg(long, value_name = "VAULT", conflicts_with_all = ["doc_id", "all", "output"])]
+        obsidian: Option<PathBuf>,
+
+        /// Zip the transcript, raw JSON, timings, summaries and action items with a manifest
+        /// (default output: exports/ in the data directory)
+        #[arg(long, requires = "doc_id", conflicts_with_all = ["all", "condensed"])]
+        bundle: bool,
+
+        /// Leave out greetings, small talk and filler words
+        #[arg(long)]
+        condensed: bool,
+    },
+
+    /// Write a condensed copy of a transcript, without small talk or filler, for sharing
+    Condense {
+        /// Document ID to condense
+        doc_id: String,
+
+        /// Also ask the summary model which remaining paragraphs are small talk
+        #[cfg(feature = "summaries")]
+        #[arg(long)]
+        llm: bool,
+    },
+
+    /// List transcripts with identical content (from reconnects), or hide or merge the copies
+    Dedupe {
+        /// Keep duplicates on disk but out of search and embeddings
+        #[arg(long, conflicts_with = "merge")]
+        hide: bool,
+
+        /// Delete duplicates and stop syncing them; the earliest copy is kept
+        #[arg(long)]
+        merge: bool,
+    },
+
+    /// Show meeting counts per organization (participant email domain)
+    Orgs,
+
+    /// Show each meeting's duration and dead time (silences of 2+ minutes)
+    Stats {
+        /// Show totals instead: meetings, hours per week and top participants
+        #[arg(long)]
+        overview: bool,
+
+        /// Only count meetings since this date (YYYY-MM-DD) or lookback (e.g. 30d, 4w)
+        #[arg(long, value_parser = parse_since, requires = "overview")]
+        since: Option<NaiveDate>,
+
+        /// Only count meetings up to this date (YYYY-MM-DD, inclusive)
+        #[arg(long, value_parser = parse_date, requires = "overview")]
+        until: Option<NaiveDate>,
+
+        /// Participants to list
+        #[arg(short = 'n', long, default_value_t = 10, requires = "overview")]
+        top: usize,
+    },
+
+    /// Show the words recent meetings are about, ranked by TF-IDF (requires 'index' feature)
+    #[cfg(feature = "index")]
+    Terms {
+        /// Only count meetings since this date (YYYY-MM-DD) or lookback (e.g. 30d, 4w)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<NaiveDate>,
+
+        /// Only count meetings up to this date (YYYY-MM-DD, inclusive)
+        #[arg(long, value_parser = parse_date)]
+        until: Option<NaiveDate>,
+
+        /// Terms to list
+        #[arg(short = 'n', long, default_value_t = 25)]
+        top: usize,
+
+        /// Write a word cloud of the terms to this SVG file instead of listing them
+        #[arg(long, value_name = "FILE")]
+        svg: Option<PathBuf>,
+    },
+
+    /// Show terms rising or falling against an earlier period, as markdown (requires 'index' feature)
+    #[cfg(feature = "index")]
+    Trends {
+        /// Start of the period to look at (YYYY-MM-DD) or lookback (e.g. 30d, 4w)
+        #[arg(long, value_parser = parse_since)]
+        since: NaiveDate,
+
+        /// End of the period (YYYY-MM-DD, inclusive) [default: today]
+        #[arg(long, value_parser = parse_date)]
+        until: Option<NaiveDate>,
+
+        /// Start of the period to compare against [default: as long as the period, right before it]
+        #[arg(long, value_parser = parse_date, requires = "baseline_until")]
+        baseline_since: Option<NaiveDate>,
+
+        /// End of the period to compare against (inclusive)
+        #[arg(long, value_parser = parse_date, requires = "baseline_since")]
+        baseline_until: Option<NaiveDate>,
+
+        /// Terms to list in each direction
+        #[arg(short = 'n', long, default_value_t = 10)]
+        top: usize,
+
+        /// Example meetings per term
+        #[arg(long, default_value_t = 3)]
+        examples: usize,
+    },
+
+    /// Regenerate every embedding from local transcripts (no API calls)
+    #[cfg(feature = "semantic")]
+    Reembed {
+        /// Replace a store built with another model, or one that can no longer be read
+        #[arg(long)]
+        force: bool,
+
+        /// Documents embedded between progress checkpoints [default: 32]
+        #[arg(long, value_name = "N")]
+        batch_size: Option<NonZeroUsize>,
+    },
+
+    /// Append links to the most similar earlier meetings to every transcript
+    #[cfg(feature = "semantic")]
+    Link {
+        /// Related meetings to list per transcript (0 removes the section)
+        #[arg(short = 'n', long, default_value_t = crate::related::DEFAULT_COUNT)]
+        count: usize,
+    },
+
+    /// Show how often you meet someone, for how long, with whom and about what
+    Person {
+        /// Participant name or email (e.g. "Alice" or alice@acme.com)
+        name: String,
+
+        /// Only count meetings since this date (YYYY-MM-DD) or lookback (e.g. 90d, 12w)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<NaiveDate>,
+
+        /// Co-attendees and topics to list
+        #[arg(short = 'n', long, default_value_t = 5)]
+        limit: usize,
     },
 
-    /// Open the data directory in the system file browser
-    Open,
+    /// Show every meeting with an account, its open action items and last contact
+    Account {
+        /// Account name or domain (e.g. "acme" or "acme.com")
+        name: String,
+
+        /// Generate a combined summary from saved meeting summaries (requires 'summaries')
+        #[cfg(feature = "summaries")]
+        #[arg(long)]
+        summarize: bool,
+    },
+
+    /// Maintain the full-text search index
+    #[cfg(feature = "index")]
+    Index {
+        #[command(subcommand)]
+        action: IndexCommands,
+    },
+
+    /// Track goals (from goals.toml) across meetings
+    Goals {
+        #[command(subcommand)]
+        action: GoalsCommands,
+    },
+
+    /// Inspect authentication state, or keep a Granola token in the system keychain
+    Auth {
+        #[command(subcommand)]
+        action: AuthCommands,
+    },
+
+    /// Open a transcript (or its summary) in the default app, or the data directory
+    Open {
+        /// Document ID or transcript file name; without one, opens the data directory
+        doc_id: Option<String>,
+
+        /// Open the saved summary instead of the transcript
+        #[arg(long, requires = "doc_id")]
+        summary: bool,
+    },
+
+    /// Handle a muesli:// link (muesli://doc/<id>, muesli://search?q=...) from Shortcuts or another app
+    UrlHandler {
+        /// The link, e.g. muesli://doc/<id> or muesli://x-callback-url/search?q=pricing&x-success=...
+        #[arg(required_unless_present = "desktop_entry")]
+        url: Option<String>,
+
+        /// Print the document instead of opening it
+        #[arg(long)]
+        print: bool,
+
+        /// Print a freedesktop entry registering this command for muesli:// links
+        #[arg(long, conflicts_with = "url")]
+        desktop_entry: bool,
+    },
 
     /// Fix file modification dates to match meeting creation dates
     FixDates,
 
-    /// Store OpenAI API key in system keychain (macOS only)
+    /// Rename transcripts and the files named after them to follow [filenames] in config.toml
+    FixFilenames {
+        /// Show the renames without making them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Store OpenAI API key in the system keychain (macOS Keychain, Secret Service, Windows Credential Manager)
     #[cfg(feature = "summaries")]
     SetApiKey {
         /// OpenAI API key
         api_key: String,
     },
 
-    /// Configure summarization settings (model, context window, prompt)
+    /// Configure summarization settings (provider, model, context window, prompt)
     #[cfg(feature = "summaries")]
     SetConfig {
-        /// OpenAI model to use (e.g., gpt-5, gpt-4o, gpt-4o-mini)
+        /// Where summaries are generated: openai, ollama, or local (a GGUF model run in-process)
+        #[arg(long, value_enum)]
+        provider: Option<crate::summary::Provider>,
+
+        /// Ollama server URL (default http://localhost:11434); "" resets it
+        #[arg(long)]
+        ollama_host: Option<String>,
+
+        /// GGUF model file for the local provider
+        #[arg(long, value_name = "FILE")]
+        local_model: Option<PathBuf>,
+
+        /// tokenizer.json for the local model (default: next to the model file)
+        #[arg(long, value_name = "FILE")]
+        local_tokenizer: Option<PathBuf>,
+
+        /// Model to use (e.g., gpt-5 or gpt-4o-mini; llama3.1 with Ollama)
         #[arg(long)]
         model: Option<String>,
 
@@ -112,25 +712,196 @@ pub enum Commands {
         #[arg(long)]
         prompt_file: Option<std::path::PathBuf>,
 
+        /// Language to write summaries in (e.g., English, German); "" clears it
+        #[arg(long)]
+        language: Option<String>,
+
         /// Show current configuration
         #[arg(long)]
         show: bool,
     },
 
-    /// Summarize a transcript using OpenAI
+    /// Summarize a transcript using OpenAI or a local Ollama model
     #[cfg(feature = "summaries")]
     Summarize {
         /// Document ID to summarize
         doc_id: String,
 
+        /// Prompt preset (standup, 1on1, interview, board, incident, or one from the config);
+        /// default: matched from the meeting's labels
+        #[arg(long)]
+        preset: Option<String>,
+
         /// Save summary to file (default: print to stdout)
         #[arg(long)]
         save: bool,
+
+        /// Also copy the summary to the clipboard
+        #[cfg(feature = "clipboard")]
+        #[arg(long)]
+        copy: bool,
+    },
+
+    /// Work with saved summaries
+    #[cfg(feature = "summaries")]
+    Summary {
+        #[command(subcommand)]
+        action: SummaryCommands,
+    },
+
+    /// Build an evaluation packet from an interview: questions, answers, strengths, concerns, quotes
+    #[cfg(feature = "summaries")]
+    InterviewPacket {
+        /// Document ID of the interview
+        doc_id: String,
+
+        /// Build a packet even if the meeting isn't labeled as an interview
+        #[arg(long)]
+        force: bool,
+
+        /// Save the packet beside the summaries (default: print to stdout)
+        #[arg(long)]
+        save: bool,
+    },
+
+    /// Extract an incident review's timeline into a postmortem skeleton
+    #[cfg(feature = "summaries")]
+    IncidentTimeline {
+        /// Document ID of the incident review
+        doc_id: String,
+
+        /// Extract a timeline even if the meeting isn't labeled as an incident
+        #[arg(long)]
+        force: bool,
+
+        /// Save the postmortem beside the summaries (default: print to stdout)
+        #[arg(long)]
+        save: bool,
+    },
+
+    /// Write a quarterly retrospective from the quarter's saved summaries
+    #[cfg(feature = "summaries")]
+    Retro {
+        /// Quarter to cover (e.g. 2025-Q2)
+        #[arg(long)]
+        quarter: crate::retro::Quarter,
+
+        /// Only meetings matching label:NAME, participant:NAME or org:DOMAIN (repeatable)
+        #[arg(long = "filter", value_name = "KEY:VALUE")]
+        filters: Vec<crate::retro::RetroFilter>,
     },
 
     /// Start MCP (Model Context Protocol) server for AI assistant integration
     #[cfg(feature = "mcp")]
     Mcp,
+
+    /// Print a shell completion script (e.g. muesli completions zsh > ~/.zfunc/_muesli)
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+impl Commands {
+    /// Whether the command reads or writes the data directory, so a crashed
+    /// run's leftovers are cleaned up before it starts
+    pub fn uses_archive(&self) -> bool {
+        match self {
+            Commands::Auth { .. } | Commands::Completions { .. } => false,
+            #[cfg(feature = "summaries")]
+            Commands::SetApiKey { .. } | Commands::SetConfig { .. } => false,
+            _ => true,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum GoalsCommands {
+    /// Show mentions per goal over time
+    Report {
+        /// Goals file (default: <data-dir>/goals.toml)
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Also count semantically similar meetings (requires 'embeddings' feature)
+        #[cfg(feature = "semantic")]
+        #[arg(long)]
+        semantic: bool,
+
+        /// Extract each goal's latest status with the LLM (requires 'summaries' feature)
+        #[cfg(feature = "summaries")]
+        #[arg(long)]
+        status: bool,
+    },
+}
+
+#[cfg(feature = "summaries")]
+#[derive(Subcommand, Debug, Clone)]
+pub enum SummaryCommands {
+    /// Compare a meeting's saved summary with the version the last 'summarize --save' replaced
+    Diff {
+        /// Document ID
+        doc_id: String,
+
+        /// Show the versions in two columns instead of a unified diff
+        #[arg(long)]
+        side_by_side: bool,
+
+        /// Unchanged lines shown around each change (unified diff)
+        #[arg(short = 'C', long, default_value_t = 3)]
+        context: usize,
+
+        /// Width of each column with --side-by-side
+        #[arg(long, default_value_t = 60)]
+        width: usize,
+    },
+}
+
+#[cfg(feature = "index")]
+#[derive(Subcommand, Debug, Clone)]
+pub enum IndexCommands {
+    /// Merge segments and reclaim space left by deleted and updated documents
+    Optimize,
+    /// Show document counts, embedding details and drift between text and vector stores
+    Stats,
+    /// Write a read-only snapshot of the index for a teammate (no transcripts or raw JSON)
+    Export {
+        /// Zip file to write
+        out: PathBuf,
+
+        /// Most restricted access level to include; untagged meetings count as team
+        #[arg(long, value_enum, default_value = "team")]
+        max_access: crate::access::Access,
+    },
+    /// Unpack a teammate's snapshot so 'search --snapshot NAME' can query it
+    Import {
+        /// Snapshot zip written by 'muesli index export'
+        file: PathBuf,
+
+        /// Name to search it under (lowercase letters, digits and dashes)
+        #[arg(long)]
+        name: String,
+    },
+    /// List imported snapshots
+    Snapshots,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum AuthCommands {
+    /// Show where the token comes from, whether it works, and when it expires
+    Status,
+
+    /// Store a Granola bearer token in the system keychain (per --profile)
+    #[cfg(feature = "keychain")]
+    Login {
+        /// The token; read from stdin when omitted, keeping it out of shell history
+        token: Option<String>,
+    },
+
+    /// Remove the stored Granola bearer token
+    #[cfg(feature = "keychain")]
+    Logout,
 }
 
 impl Cli {
@@ -138,8 +909,98 @@ impl Cli {
         self.command.clone().unwrap_or(Commands::Sync {
             #[cfg(feature = "index")]
             reindex: false,
+            #[cfg(feature = "semantic")]
+            embeddings: false,
+            #[cfg(feature = "semantic")]
+            migrate_model: false,
+            #[cfg(feature = "semantic")]
+            batch_size: None,
+            #[cfg(feature = "semantic")]
+            link: false,
+            prune: false,
+            dry_run: false,
+            since: None,
+            until: None,
+            newest_first: false,
+            oldest_first: false,
+            max_docs: None,
+            force: Vec::new(),
+            name_speakers: false,
+            auto: false,
+            min_confidence: None,
+            from_dir: None,
         })
     }
+
+    /// The completion script for `shell`, covering every subcommand and flag
+    pub fn completions(shell: clap_complete::Shell) -> Vec<u8> {
+        // Generated in memory: clap_complete panics if writing to a closed pipe fails
+        let mut script = Vec::new();
+        clap_complete::generate(
+            shell,
+            &mut <Cli as CommandFactory>::command(),
+            "muesli",
+            &mut script,
+        );
+        script
+    }
+
+    /// Rejects a `--format` that the command can't produce
+    pub fn check_format(&self) -> crate::Result<()> {
+        let export = match &self.command {
+            Some(Commands::Export {
+                obsidian, bundle, ..
+            }) if obsidian.is_some() || *bundle => {
+                return match self.format {
+                    Format::Table => Ok(()),
+                    _ => Err(Error::InvalidInput(
+                        "--obsidian and --bundle choose their own formats; leave out --format"
+                            .into(),
+                    )),
+                }
+            }
+            Some(Commands::Export { .. }) => true,
+            _ => false,
+        };
+        match (export, self.format.output()) {
+            (true, Some(_)) => Err(Error::InvalidInput(format!(
+                "export can't write '{}'; use --format srt, vtt or html",
+                self.format.name()
+            ))),
+            (false, None) => Err(Error::InvalidInput(format!(
+                "--format {} only applies to 'muesli export'",
+                self.format.name()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// The tabular output format; table for export formats, which `check_format` rejects
+    pub fn output_format(&self) -> OutputFormat {
+        self.format.output().unwrap_or_default()
+    }
+
+    /// The `--profile` section of profiles.toml, if one was named
+    pub fn profile(&self) -> crate::Result<Option<Profile>> {
+        self.profile
+            .as_deref()
+            .map(crate::profile::load)
+            .transpose()
+    }
+
+    /// Data paths for `--data-dir`, else the profile's directory, else the default
+    pub fn paths(&self) -> crate::Result<Paths> {
+        Paths::new(self.data_dir.clone(), self.profile()?.as_ref())
+    }
+
+    /// Output options built from the global formatting flags
+    pub fn output_options(&self) -> OutputOptions {
+        OutputOptions {
+            format: self.output_format(),
+            columns: self.columns.clone(),
+            header: !self.no_header,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -158,4 +1019,273 @@ mod tests {
         assert!(parse_throttle_range("abc:def").is_err());
         assert!(parse_throttle_range("100").is_err());
     }
+
+    #[test]
+    fn test_uses_archive() {
+        let uses = |args: &[&str]| Cli::try_parse_from(args).unwrap().command().uses_archive();
+        assert!(uses(&["muesli"]));
+        assert!(uses(&["muesli", "list", "--local"]));
+        assert!(!uses(&["muesli", "completions", "bash"]));
+        assert!(!uses(&["muesli", "auth", "status"]));
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("15m").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_interval("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_interval("90").unwrap(), Duration::from_secs(90));
+        assert!(parse_interval("30s").is_err());
+        assert!(parse_interval("5d").is_err());
+        assert!(parse_interval("m").is_err());
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(
+            parse_date("2024-01-31").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 1, 31).unwrap()
+        );
+        assert!(parse_date("2024-13-01").is_err());
+        assert!(parse_date("01/31/2024").is_err());
+    }
+
+    #[test]
+    fn test_since_date() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        assert_eq!(
+            since_date("90d", today).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+        );
+        assert_eq!(
+            since_date("2w", today).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 17).unwrap()
+        );
+        assert_eq!(
+            since_date("2025-01-15", today).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+        assert!(since_date("3m", today).is_err());
+        assert!(since_date("d", today).is_err());
+        assert!(since_date("", today).is_err());
+    }
+
+    #[test]
+    fn test_output_flags() {
+        let cli = Cli::parse_from([
+            "muesli",
+            "list",
+            "--format",
+            "ndjson",
+            "--columns",
+            "id,title",
+            "--no-header",
+        ]);
+        let opts = cli.output_options();
+        assert_eq!(opts.format, OutputFormat::Ndjson);
+        assert_eq!(opts.columns, Some(vec!["id".into(), "title".into()]));
+        assert!(!opts.header);
+    }
+
+    #[test]
+    fn test_offline_is_global() {
+        let cli = Cli::parse_from(["muesli", "list", "--offline"]);
+        assert!(cli.offline);
+        assert!(matches!(cli.command, Some(Commands::List { local: false })));
+        assert!(!Cli::parse_from(["muesli", "list"]).offline);
+    }
+
+    #[test]
+    fn test_sync_from_dir() {
+        let cli = Cli::parse_from(["muesli", "sync", "--from-dir", "exports"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Sync { from_dir: Some(ref dir), .. }) if dir == std::path::Path::new("exports")
+        ));
+        // Pruning against a folder would delete every Granola meeting
+        assert!(
+            Cli::try_parse_from(["muesli", "sync", "--from-dir", "exports", "--prune"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_import_args() {
+        let cli = Cli::parse_from(["muesli", "import", "a.vtt", "b.srt", "--date", "2025-03-01"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Import { ref files, title: None, date: Some(_) }) if files.len() == 2
+        ));
+        assert!(Cli::try_parse_from(["muesli", "import"]).is_err());
+        assert!(Cli::try_parse_from(["muesli", "import", "a.vtt", "--date", "March"]).is_err());
+    }
+
+    #[cfg(feature = "index")]
+    #[test]
+    fn test_terms_args() {
+        let cli = Cli::parse_from([
+            "muesli",
+            "terms",
+            "--since",
+            "2025-03-01",
+            "--svg",
+            "cloud.svg",
+        ]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Terms {
+                since: Some(_),
+                until: None,
+                top: 25,
+                svg: Some(_)
+            })
+        ));
+        assert!(Cli::try_parse_from(["muesli", "terms", "--since", "soon"]).is_err());
+    }
+
+    #[cfg(feature = "index")]
+    #[test]
+    fn test_trends_args() {
+        let cli = Cli::parse_from(["muesli", "trends", "--since", "30d"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Trends {
+                until: None,
+                baseline_since: None,
+                top: 10,
+                examples: 3,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["muesli", "trends"]).is_err());
+        assert!(Cli::try_parse_from([
+            "muesli",
+            "trends",
+            "--since",
+            "2025-03-01",
+            "--baseline-since",
+            "2024-03-01"
+        ])
+        .is_err());
+    }
+
+    #[cfg(feature = "email")]
+    #[test]
+    fn test_import_email_args() {
+        let cli = Cli::parse_from(["muesli", "import-email", "--folder", "Notes", "--all"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::ImportEmail {
+                folder: Some(ref f),
+                all: true,
+                ..
+            }) if f == "Notes"
+        ));
+        #[cfg(feature = "keychain")]
+        assert!(
+            Cli::try_parse_from(["muesli", "import-email", "--save-password", "--all"]).is_err()
+        );
+    }
+
+    #[cfg(feature = "keychain")]
+    #[test]
+    fn test_auth_login_args() {
+        let cli = Cli::parse_from(["muesli", "--profile", "work", "auth", "login", "tok"]);
+        assert_eq!(cli.profile.as_deref(), Some("work"));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Auth {
+                action: AuthCommands::Login { token: Some(ref t) }
+            }) if t == "tok"
+        ));
+        let cli = Cli::parse_from(["muesli", "auth", "logout"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Auth {
+                action: AuthCommands::Logout
+            })
+        ));
+    }
+
+    #[cfg(feature = "transcribe")]
+    #[test]
+    fn test_transcribe_args() {
+        let cli = Cli::parse_from(["muesli", "transcribe", "standup.m4a", "--language", "en"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Transcribe { ref files, model: None, language: Some(ref language), .. })
+                if files.len() == 1 && language == "en"
+        ));
+        assert!(Cli::try_parse_from(["muesli", "transcribe"]).is_err());
+    }
+
+    #[test]
+    fn test_dedupe_flags() {
+        let cli = Cli::parse_from(["muesli", "dedupe", "--merge"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Dedupe {
+                hide: false,
+                merge: true
+            })
+        ));
+        assert!(Cli::try_parse_from(["muesli", "dedupe", "--hide", "--merge"]).is_err());
+    }
+
+    #[test]
+    fn test_completions() {
+        let cli = Cli::parse_from(["muesli", "completions", "zsh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions {
+                shell: clap_complete::Shell::Zsh
+            })
+        ));
+
+        let script = String::from_utf8(Cli::completions(clap_complete::Shell::Bash)).unwrap();
+        assert!(script.contains("completions"));
+        assert!(script.contains("--data-dir"));
+    }
+
+    #[test]
+    fn test_check_format() {
+        let export = Cli::parse_from(["muesli", "export", "doc1", "--format", "vtt"]);
+        assert!(export.check_format().is_ok());
+        assert_eq!(
+            export.format.export(),
+            Some(ExportFormat::Subtitles(SubtitleFormat::Vtt))
+        );
+
+        let all = Cli::parse_from(["muesli", "export", "--all", "--format", "html"]);
+        assert!(all.check_format().is_ok());
+        assert_eq!(all.format.export(), Some(ExportFormat::Html));
+        assert!(Cli::try_parse_from(["muesli", "export", "doc1", "--all"]).is_err());
+        assert!(Cli::try_parse_from(["muesli", "export", "--format", "html"]).is_err());
+
+        assert!(Cli::parse_from(["muesli", "export", "doc1"])
+            .check_format()
+            .is_err());
+        assert!(Cli::parse_from(["muesli", "export", "--obsidian", "vault"])
+            .check_format()
+            .is_ok());
+        assert!(Cli::parse_from([
+            "muesli",
+            "export",
+            "--obsidian",
+            "vault",
+            "--format",
+            "html"
+        ])
+        .check_format()
+        .is_err());
+        assert!(Cli::try_parse_from(["muesli", "export", "doc1", "--obsidian", "vault"]).is_err());
+
+        assert!(Cli::parse_from(["muesli", "export", "doc1", "--bundle"])
+            .check_format()
+            .is_ok());
+        assert!(Cli::try_parse_from(["muesli", "export", "--bundle"]).is_err());
+        assert!(Cli::try_parse_from(["muesli", "export", "--all", "--bundle"]).is_err());
+        let err = Cli::parse_from(["muesli", "list", "--format", "srt"])
+            .check_format()
+            .unwrap_err();
+        assert!(err.to_string().contains("--format srt only applies"));
+    }
 }
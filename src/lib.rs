@@ -1,32 +1,6 @@
-// ABOUTME: Public library API for Muesli transcript sync
-// ABOUTME: Re-exports core modules for external use
+// ABOUTME: Library half of the muesli binary: the command-line definitions
+// ABOUTME: Re-exports muesli-core so `muesli::sync`, `muesli::index` and friends keep resolving
 
-pub mod api;
-pub mod auth;
-pub mod cli;
-pub mod convert;
-pub mod error;
-pub mod model;
-pub mod storage;
-pub mod sync;
-pub mod util;
-
-#[cfg(feature = "index")]
-pub mod index;
-
-#[cfg(feature = "embeddings")]
-pub mod embeddings;
+pub use muesli_core::*;
 
-#[cfg(feature = "summaries")]
-pub mod summary;
-
-#[cfg(feature = "mcp")]
-pub mod mcp;
-
-pub use api::ApiClient;
-pub use auth::resolve_token;
-pub use convert::{to_markdown, MarkdownOutput};
-pub use error::{Error, Result};
-pub use model::{DocumentMetadata, DocumentSummary, Frontmatter, RawTranscript};
-pub use storage::{read_frontmatter, write_atomic, Paths};
-pub use sync::sync_all;
+pub mod cli;
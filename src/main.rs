@@ -3,12 +3,15 @@
 
 use clap::Parser;
 use muesli::{
-    api::ApiClient,
+    api::{ApiClient, ApiConfig, AsyncApiClient, DocumentSource},
     auth::resolve_token,
     cli::Cli,
-    storage::Paths,
-    sync::{fix_dates, sync_all},
-    Result,
+    output::render,
+    perf,
+    source::DirectorySource,
+    speakers::SpeakerNaming,
+    sync::{fix_dates, sync_all, SyncOptions},
+    term, Result,
 };
 
 fn main() {
@@ -20,36 +23,129 @@ fn main() {
 
 fn run() -> Result<()> {
     let cli = Cli::parse();
+    term::init(cli.color);
+    if cli.offline {
+        muesli::offline::enable();
+    }
+
+    let print_perf = cli.profile_perf;
+    let perf_json = cli.profile_perf_json.clone();
+    if print_perf || perf_json.is_some() {
+        perf::enable();
+    }
+    let result = execute(cli);
+
+    // Reported even when the command failed, since the timings show how far it got
+    if perf::enabled() {
+        let report = perf::report();
+        if print_perf {
+            eprint!("{}", report.to_table());
+        }
+        if let Some(path) = perf_json {
+            std::fs::write(&path, serde_json::to_string_pretty(&report)? + "\n")?;
+        }
+    }
+    result
+}
+
+fn execute(cli: Cli) -> Result<()> {
+    cli.check_format()?;
+    if cli.command().uses_archive() {
+        repair_archive(&cli.paths()?)?;
+    }
 
     match cli.command() {
         muesli::cli::Commands::Sync {
             #[cfg(feature = "index")]
             reindex,
+            #[cfg(feature = "semantic")]
+            embeddings,
+            #[cfg(feature = "semantic")]
+            migrate_model,
+            #[cfg(feature = "semantic")]
+            batch_size,
+            #[cfg(feature = "semantic")]
+            link,
+            prune,
+            dry_run,
+            since,
+            until,
+            newest_first: _,
+            oldest_first,
+            max_docs,
+            force,
+            name_speakers,
+            auto,
+            min_confidence,
+            from_dir,
         } => {
-            let client = create_client(&cli)?;
-            let paths = Paths::new(cli.data_dir)?;
-            #[cfg(feature = "index")]
-            {
-                sync_all(&client, &paths, reindex)?;
-            }
+            let source: Box<dyn DocumentSource> = match from_dir {
+                Some(dir) => Box::new(DirectorySource::new(dir)?),
+                None => Box::new(create_async_client(&cli)?),
+            };
+            let paths = cli.paths()?;
             #[cfg(not(feature = "index"))]
-            {
-                sync_all(&client, &paths, false)?;
-            }
+            let reindex = false;
+            #[cfg(not(feature = "semantic"))]
+            let (embeddings, migrate_model, link) = (false, false, false);
+            #[cfg(not(feature = "semantic"))]
+            let batch_size: Option<std::num::NonZeroUsize> = None;
+            let options = SyncOptions {
+                reindex,
+                embeddings,
+                migrate_model,
+                prune,
+                dry_run,
+                since,
+                until,
+                oldest_first,
+                max_docs: max_docs.map(|n| n.get()),
+                force,
+                link,
+                name_speakers: match (name_speakers, auto) {
+                    (false, _) => SpeakerNaming::Off,
+                    (true, false) => SpeakerNaming::Ask,
+                    (true, true) => SpeakerNaming::Auto,
+                },
+                min_confidence,
+                embed_batch_size: batch_size.map(|n| n.get()),
+            };
+            runtime()?.block_on(sync_all(source.as_ref(), &paths, &options))?;
         }
-        muesli::cli::Commands::List => {
-            let client = create_client(&cli)?;
-            let docs = client.list_documents()?;
+        muesli::cli::Commands::Watch {
+            interval,
+            prune,
+            alerts,
+        } => {
+            let client = create_async_client(&cli)?;
+            let paths = cli.paths()?;
+            let options = SyncOptions {
+                prune,
+                ..Default::default()
+            };
+            let alerts_path = alerts.unwrap_or_else(|| paths.data_dir.join("alerts.toml"));
+            // Fail fast on a broken rules file rather than on the first new meeting
+            muesli::alerts::load_rules(&alerts_path)?;
+            runtime()?.block_on(muesli::watch::watch(
+                &client,
+                &paths,
+                &options,
+                interval,
+                &alerts_path,
+            ))?;
+        }
+        muesli::cli::Commands::List { local } => {
+            let docs = if local || cli.offline {
+                muesli::storage::list_local(&cli.paths()?)?
+            } else {
+                create_client(&cli)?.list_documents()?
+            };
 
-            for doc in docs {
-                let date = doc.created_at.format("%Y-%m-%d");
-                let title = doc.title.as_deref().unwrap_or("Untitled");
-                println!("{}\t{}\t{}", doc.id, date, title);
-            }
+            print!("{}", render(&docs, &cli.output_options())?);
         }
-        muesli::cli::Commands::Fetch { id } => {
+        muesli::cli::Commands::Fetch { id, min_confidence } => {
             let client = create_client(&cli)?;
-            let paths = Paths::new(cli.data_dir)?;
+            let paths = cli.paths()?;
             paths.ensure_dirs()?;
 
             // Fetch metadata and transcript
@@ -61,38 +157,184 @@ fn run() -> Result<()> {
             let slug = muesli::util::slugify(meta.title.as_deref().unwrap_or("untitled"));
             let base_filename = format!("{}_{}", date, slug);
 
-            // Convert to markdown
-            let md = muesli::convert::to_markdown(&raw, &meta, &id)?;
+            let json_path = paths.raw_dir.join(format!("{}.json", base_filename));
+            let md_path = paths.transcripts_dir.join(format!("{}.md", base_filename));
+
+            // Convert to markdown, keeping speaker names and the access tag of an earlier fetch
+            let mut options = muesli::convert::MarkdownOptions::configured(&paths)?;
+            if min_confidence.is_some() {
+                options.min_confidence = min_confidence;
+            }
+            let local = muesli::read_frontmatter(&md_path)
+                .ok()
+                .flatten()
+                .map(|fm| muesli::convert::LocalFields::from_frontmatter(&fm))
+                .unwrap_or_default();
+            let md = muesli::convert::to_markdown_with_local(&raw, &meta, &id, &options, &local)?;
             let full_md = format!("---\n{}---\n\n{}", md.frontmatter_yaml, md.body);
 
             // Write files
-            let json_path = paths.raw_dir.join(format!("{}.json", base_filename));
-            let md_path = paths.transcripts_dir.join(format!("{}.md", base_filename));
 
             let raw_json = serde_json::to_string_pretty(&raw)?;
             muesli::storage::write_atomic(&json_path, raw_json.as_bytes(), &paths.tmp_dir)?;
             muesli::storage::write_atomic(&md_path, full_md.as_bytes(), &paths.tmp_dir)?;
+            let timings_path = muesli::timings::write(&paths, &base_filename, &raw, &id)?;
 
             // Set file modification time to meeting creation date
             muesli::storage::set_file_time(&json_path, &meta.created_at)?;
             muesli::storage::set_file_time(&md_path, &meta.created_at)?;
+            muesli::storage::Catalog::open(&paths)?.record_file(&md_path)?;
+            muesli::sync::refresh_manifest(&paths);
 
             println!("wrote {}", json_path.display());
             println!("wrote {}", md_path.display());
+            if let Some(path) = timings_path {
+                println!("wrote {}", path.display());
+            }
+        }
+        muesli::cli::Commands::Import { files, title, date } => {
+            let paths = cli.paths()?;
+            paths.ensure_dirs()?;
+            let options = muesli::ingest::ImportOptions { title, date };
+            let imported = runtime()?.block_on(muesli::ingest::import(&paths, &files, &options))?;
+            for doc in imported {
+                println!(
+                    "imported {} ({}, {})",
+                    doc.id,
+                    doc.title.unwrap_or_default(),
+                    doc.created_at.format("%Y-%m-%d")
+                );
+            }
+        }
+        #[cfg(feature = "email")]
+        muesli::cli::Commands::ImportEmail {
+            folder,
+            all,
+            #[cfg(feature = "keychain")]
+            save_password,
+        } => {
+            let paths = cli.paths()?;
+            let mut config = muesli::email::EmailConfig::configured(&paths)?;
+            #[cfg(feature = "keychain")]
+            if save_password {
+                let mut password = String::new();
+                std::io::stdin().read_line(&mut password)?;
+                let password = password.trim_end_matches(['\r', '\n']);
+                if password.is_empty() {
+                    return Err(muesli::Error::InvalidInput("No password given".into()));
+                }
+                muesli::keychain::set(&config.keychain_account()?, password)?;
+                println!(
+                    "{}IMAP password stored in {}",
+                    muesli::term::emoji("✅ "),
+                    muesli::keychain::backend_name()
+                );
+                return Ok(());
+            }
+            muesli::offline::ensure_online("reading email")?;
+            paths.ensure_dirs()?;
+            if let Some(folder) = folder {
+                config.folder = folder;
+            }
+            let imported =
+                runtime()?.block_on(muesli::email::import_folder(&paths, &config, all))?;
+            if imported.is_empty() {
+                println!("No new notes in {}", config.folder);
+            }
+            for doc in imported {
+                println!(
+                    "imported {} ({}, {})",
+                    doc.id,
+                    doc.title.unwrap_or_default(),
+                    doc.created_at.format("%Y-%m-%d")
+                );
+            }
+        }
+        #[cfg(feature = "transcribe")]
+        muesli::cli::Commands::Transcribe {
+            files,
+            model,
+            language,
+            title,
+            date,
+        } => {
+            let paths = cli.paths()?;
+            paths.ensure_dirs()?;
+            let mut config = muesli::transcribe::TranscribeConfig::configured(&paths)?;
+            config.model = model.or(config.model);
+            config.language = language.or(config.language);
+            let options = muesli::ingest::ImportOptions { title, date };
+            let transcribed = runtime()?.block_on(muesli::transcribe::transcribe(
+                &paths, &files, config, &options,
+            ))?;
+            for doc in transcribed {
+                println!(
+                    "transcribed {} ({}, {})",
+                    doc.id,
+                    doc.title.unwrap_or_default(),
+                    doc.created_at.format("%Y-%m-%d")
+                );
+            }
         }
         #[cfg(feature = "index")]
         muesli::cli::Commands::Search {
             query,
             limit,
-            #[cfg(feature = "embeddings")]
+            #[cfg(feature = "semantic")]
             semantic,
+            #[cfg(feature = "semantic")]
+            hybrid,
+            org,
+            participant,
+            label,
+            exclude_participant,
+            exclude_label,
+            include_summaries,
+            snapshot,
         } => {
-            let paths = Paths::new(cli.data_dir)?;
+            use muesli::output::{OutputFormat, SearchRow};
+
+            let mut paths = cli.paths()?;
+            let output = cli.output_options();
+            let weights = muesli::index::weights::SearchWeights::configured(&paths)?;
+
+            // An imported snapshot is searched like a data directory of its own
+            let manifest = match &snapshot {
+                Some(name) => {
+                    let (snapshot_paths, manifest) = muesli::snapshot::open(&paths, name)?;
+                    paths = snapshot_paths;
+                    Some(manifest)
+                }
+                None => None,
+            };
+
+            let filters = muesli::index::text::SearchFilters {
+                organization: org,
+                participant,
+                label,
+                exclude_participants: exclude_participant,
+                exclude_labels: exclude_label,
+                include_summaries,
+            };
 
             // Check for semantic search
-            #[cfg(feature = "embeddings")]
+            #[cfg(feature = "semantic")]
             {
-                if semantic {
+                if semantic || hybrid {
+                    // Vectors are per meeting; summaries are only in the text index
+                    if include_summaries {
+                        return Err(muesli::Error::InvalidInput(
+                            "--include-summaries works with text search only".to_string(),
+                        ));
+                    }
+                    // Filters match the catalog, which a snapshot doesn't have
+                    if manifest.is_some() && !filters.is_empty() {
+                        return Err(muesli::Error::InvalidInput(
+                            "Filters work with text search only when searching a snapshot"
+                                .to_string(),
+                        ));
+                    }
+
                     // Check if vector store exists
                     let metadata_path = paths.index_dir.join("vectors.meta.json");
                     if !metadata_path.exists() {
@@ -100,27 +342,27 @@ fn run() -> Result<()> {
                         std::process::exit(1);
                     }
 
-                    // Perform semantic search
-                    let results = muesli::embeddings::semantic_search(&paths, &query, limit)?;
+                    let mut results = if hybrid {
+                        muesli::embeddings::hybrid_search(
+                            &paths, &query, limit, &filters, &weights,
+                        )?
+                    } else {
+                        muesli::embeddings::semantic_search_filtered(
+                            &paths, &query, limit, &filters,
+                        )?
+                    };
 
-                    // Handle empty results
-                    if results.is_empty() {
+                    // Handle empty results (structured formats still emit an empty list)
+                    if results.is_empty() && output.format == OutputFormat::Table {
                         println!("No results found for: {}", query);
                         return Ok(());
                     }
 
-                    // Display results
-                    for (rank, result) in results.iter().enumerate() {
-                        let title = result.title.as_deref().unwrap_or("Untitled");
-                        println!(
-                            "{}. {} ({}) [score: {:.3}]  {}",
-                            rank + 1,
-                            title,
-                            result.date,
-                            result.score,
-                            result.path
-                        );
+                    match &manifest {
+                        Some(manifest) => muesli::snapshot::describe_hits(&mut results, manifest),
+                        None => muesli::find::link_hits(&mut results, &query),
                     }
+                    print!("{}", render(&SearchRow::ranked(results), &output)?);
                     return Ok(());
                 }
             }
@@ -133,61 +375,755 @@ fn run() -> Result<()> {
             }
 
             // Open the index
-            let index = muesli::index::text::create_or_open_index(&paths.index_dir)?;
+            let index = {
+                let _span = perf::span("search.open_index");
+                muesli::index::text::open_for_search(&paths.index_dir)?
+            };
 
             // Perform the search
-            let results = muesli::index::text::search(&index, &query, limit)?;
+            let mut results =
+                muesli::index::text::search_weighted(&index, &query, limit, &filters, &weights)?;
+            match &manifest {
+                Some(manifest) => muesli::snapshot::describe_hits(&mut results, manifest),
+                None => muesli::find::link_hits(&mut results, &query),
+            }
 
-            // Handle empty results
-            if results.is_empty() {
+            // Handle empty results (structured formats still emit an empty list)
+            if results.is_empty() && output.format == OutputFormat::Table {
                 println!("No results found for: {}", query);
                 return Ok(());
             }
 
-            // Display results
-            for (rank, result) in results.iter().enumerate() {
-                let title = result.title.as_deref().unwrap_or("Untitled");
-                println!("{}. {} ({})  {}", rank + 1, title, result.date, result.path);
+            print!("{}", render(&SearchRow::ranked(results), &output)?);
+        }
+        muesli::cli::Commands::FindIn {
+            doc_id,
+            query,
+            context,
+            limit,
+            #[cfg(feature = "clipboard")]
+            copy,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let path = muesli::storage::Catalog::open(&paths)?.path_for(&doc_id)?;
+            let content = std::fs::read_to_string(&path)?;
+            let hits = muesli::find::find_in(
+                muesli::storage::strip_frontmatter(&content),
+                &query,
+                context,
+                limit,
+            );
+
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&hits)?),
+                OutputFormat::Ndjson => {
+                    for hit in &hits {
+                        println!("{}", serde_json::to_string(hit)?);
+                    }
+                }
+                OutputFormat::Table | OutputFormat::Tsv => {
+                    print!("{}", format_find_hits(&hits, &query, &path))
+                }
+            }
+
+            #[cfg(feature = "clipboard")]
+            if copy && !hits.is_empty() {
+                muesli::clipboard::copy(&format_find_hits(&hits, &query, &path))?;
+                eprintln!(
+                    "{}Copied {} matches to clipboard",
+                    term::emoji("📋 "),
+                    hits.len()
+                );
+            }
+        }
+        muesli::cli::Commands::Export {
+            doc_id,
+            all,
+            output,
+            obsidian,
+            bundle,
+            condensed,
+        } => {
+            let paths = cli.paths()?;
+
+            if let Some(vault) = obsidian {
+                let report = muesli::obsidian::export_vault(&paths, &vault, condensed)?;
+                println!(
+                    "Exported to {}: {} new, {} updated, {} unchanged",
+                    vault.join(muesli::obsidian::NOTES_DIR).display(),
+                    report.created,
+                    report.updated,
+                    report.unchanged
+                );
+                return Ok(());
+            }
+            // check_format has already rejected the tabular formats
+            let format = cli
+                .format
+                .export()
+                .unwrap_or(muesli::export::ExportFormat::Subtitles(
+                    muesli::subtitles::SubtitleFormat::Srt,
+                ));
+
+            if all {
+                let dir = output.unwrap_or_else(|| paths.data_dir.join("exports"));
+                let report = muesli::export::export_all(&paths, &dir, format, condensed)?;
+                for (doc_id, reason) in &report.skipped {
+                    eprintln!("Skipped {}: {}", doc_id, reason);
+                }
+                println!(
+                    "Exported {} transcripts to {}",
+                    report.written,
+                    dir.display()
+                );
+                return Ok(());
+            }
+
+            // clap requires a document ID unless --all or --obsidian is given
+            let doc_id = doc_id.unwrap_or_default();
+
+            if bundle {
+                let out = match output {
+                    Some(path) => path,
+                    None => muesli::bundle::default_path(&paths, &doc_id)?,
+                };
+                let manifest = muesli::bundle::write_bundle(&paths, &doc_id, &out)?;
+                println!(
+                    "wrote {} ({} files; not included: {})",
+                    out.display(),
+                    manifest.files.len() + 1,
+                    manifest
+                        .missing
+                        .iter()
+                        .map(|m| m.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                return Ok(());
+            }
+            let exported = muesli::export::export(&paths, &doc_id, format, condensed)?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, exported)?;
+                    println!("wrote {}", path.display());
+                }
+                None => print!("{}", exported),
+            }
+        }
+        muesli::cli::Commands::Condense {
+            doc_id,
+            #[cfg(feature = "summaries")]
+            llm,
+        } => {
+            let paths = cli.paths()?;
+            let (entry, raw) = muesli::storage::load_raw_transcript(&paths, &doc_id)?;
+            #[cfg_attr(not(feature = "summaries"), allow(unused_mut))]
+            let mut condensed = muesli::condense::condense(&raw);
+
+            #[cfg(feature = "summaries")]
+            if llm {
+                let config = muesli::summary::SummaryConfig::load(
+                    &paths.data_dir.join("summary_config.json"),
+                )?;
+                let api_key = muesli::summary::api_key_for(&config)?;
+                println!(
+                    "Classifying {} paragraphs with {} via {}...",
+                    condensed.kept(),
+                    config.model,
+                    config.provider
+                );
+                let flags = runtime()?.block_on(muesli::condense::classify(
+                    &condensed.raw,
+                    api_key.as_deref(),
+                    &config,
+                ))?;
+                condensed.drop_flagged(&flags);
+            }
+
+            let path = muesli::condense::write(&paths, &entry, &condensed)?;
+            println!(
+                "Kept {} of {} entries",
+                condensed.kept(),
+                condensed.original
+            );
+            println!("wrote {}", path.display());
+        }
+        muesli::cli::Commands::Dedupe { hide, merge } => {
+            use muesli::dedupe::Resolution;
+
+            let paths = cli.paths()?;
+            let groups = muesli::dedupe::find_duplicates(&paths)?;
+            let resolution = match (hide, merge) {
+                (true, _) => Some(Resolution::Hide),
+                (_, true) => Some(Resolution::Merge),
+                _ => None,
+            };
+
+            match resolution {
+                None if groups.is_empty() => println!("No duplicate transcripts found"),
+                None => print!(
+                    "{}",
+                    render(&muesli::dedupe::rows(&groups), &cli.output_options())?
+                ),
+                Some(resolution) => {
+                    let report = muesli::dedupe::resolve(&paths, &groups, resolution)?;
+                    match resolution {
+                        Resolution::Hide => {
+                            println!("Hid {} duplicate transcripts from search", report.hidden)
+                        }
+                        Resolution::Merge => println!(
+                            "Merged {} duplicate transcripts into the earliest copy",
+                            report.merged
+                        ),
+                    }
+                }
+            }
+        }
+        muesli::cli::Commands::Orgs => {
+            let paths = cli.paths()?;
+            let transcripts = muesli::storage::scan_transcripts(&paths.transcripts_dir)?;
+            let stats = muesli::stats::organization_stats(transcripts.iter().map(|(_, fm)| fm));
+
+            print!("{}", render(&stats, &cli.output_options())?);
+        }
+        muesli::cli::Commands::Stats {
+            overview,
+            since,
+            until,
+            top,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            if overview {
+                let overview = muesli::stats::overview(&paths, since, until, top)?;
+                match cli.output_format() {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&overview)?),
+                    OutputFormat::Ndjson => println!("{}", serde_json::to_string(&overview)?),
+                    OutputFormat::Table | OutputFormat::Tsv => print_overview(&overview),
+                }
+            } else {
+                let times = muesli::stats::meeting_times(&paths)?;
+                print!("{}", render(&times, &cli.output_options())?);
+            }
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Terms {
+            since,
+            until,
+            top,
+            svg,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let report = muesli::index::terms::collect(&paths, since, until, top)?;
+            if let Some(svg) = svg {
+                std::fs::write(&svg, muesli::index::terms::word_cloud_svg(&report.terms))?;
+                println!(
+                    "Wrote a word cloud of {} terms from {} meetings to {}",
+                    report.terms.len(),
+                    report.meetings,
+                    svg.display()
+                );
+            } else {
+                match cli.output_format() {
+                    OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                    OutputFormat::Ndjson => println!("{}", serde_json::to_string(&report)?),
+                    OutputFormat::Table | OutputFormat::Tsv => print_terms(&report),
+                }
+            }
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Trends {
+            since,
+            until,
+            baseline_since,
+            baseline_until,
+            top,
+            examples,
+        } => {
+            use muesli::index::trends::{self, Period};
+            use muesli::output::OutputFormat;
+
+            let current = Period {
+                since,
+                until: until.unwrap_or_else(|| chrono::Local::now().date_naive()),
+            };
+            let baseline = match (baseline_since, baseline_until) {
+                (Some(since), Some(until)) => Period { since, until },
+                _ => current.previous(),
+            };
+            if current.until < current.since || baseline.until < baseline.since {
+                return Err(muesli::Error::InvalidInput(
+                    "A period's end is before its start".to_string(),
+                ));
+            }
+            let paths = cli.paths()?;
+            let report = trends::collect(&paths, baseline, current, top, examples)?;
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Ndjson => println!("{}", serde_json::to_string(&report)?),
+                OutputFormat::Table | OutputFormat::Tsv => {
+                    print!("{}", trends::to_markdown(&report))
+                }
+            }
+        }
+        #[cfg(feature = "semantic")]
+        muesli::cli::Commands::Reembed { force, batch_size } => {
+            let paths = cli.paths()?;
+            paths.ensure_dirs()?;
+            let batch_size = batch_size.map_or(muesli::sync::DEFAULT_EMBED_BATCH, |n| n.get());
+            muesli::sync::reembed_all(&paths, force, batch_size)?;
+        }
+        #[cfg(feature = "semantic")]
+        muesli::cli::Commands::Link { count } => {
+            let paths = cli.paths()?;
+            let report = muesli::related::link_all(&paths, count)?;
+
+            println!(
+                "Updated {} transcripts ({} already up to date, {} without embeddings)",
+                report.updated, report.unchanged, report.skipped
+            );
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Index {
+            action: muesli::cli::IndexCommands::Optimize,
+        } => {
+            use muesli::util::format_bytes;
+
+            let paths = cli.paths()?;
+            let config = muesli::index::writer::WriterConfig::configured(&paths)?;
+            let report = muesli::index::text::optimize(&paths.index_dir, &config)?;
+            let (before, after) = (report.before, report.after);
+
+            println!("Segments:     {} -> {}", before.segments, after.segments);
+            println!(
+                "Deleted docs: {} -> {}",
+                before.deleted_docs, after.deleted_docs
+            );
+            println!(
+                "Size:         {} -> {}",
+                format_bytes(before.bytes),
+                format_bytes(after.bytes)
+            );
+            if before == after {
+                println!("{}Index already optimized", term::emoji("✅ "));
+            }
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Index {
+            action: muesli::cli::IndexCommands::Stats,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let stats = muesli::index::stats::collect(&paths)?;
+
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+                OutputFormat::Ndjson => println!("{}", serde_json::to_string(&stats)?),
+                OutputFormat::Table | OutputFormat::Tsv => print_index_stats(&stats),
+            }
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Index {
+            action: muesli::cli::IndexCommands::Export { out, max_access },
+        } => {
+            let paths = cli.paths()?;
+            let policy = muesli::access::AccessPolicy {
+                max_access,
+                ..Default::default()
+            };
+            let manifest = muesli::snapshot::export(&paths, &out, policy)?;
+            println!(
+                "{}Snapshot of {} meetings saved to: {}",
+                muesli::term::emoji("✅ "),
+                manifest.documents.len(),
+                out.display()
+            );
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Index {
+            action: muesli::cli::IndexCommands::Import { file, name },
+        } => {
+            let paths = cli.paths()?;
+            let manifest = muesli::snapshot::import(&paths, &file, &name)?;
+            println!(
+                "Imported {} meetings as '{}'; search them with 'muesli search --snapshot {}'",
+                manifest.documents.len(),
+                name,
+                name
+            );
+        }
+        #[cfg(feature = "index")]
+        muesli::cli::Commands::Index {
+            action: muesli::cli::IndexCommands::Snapshots,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let snapshots = muesli::snapshot::list(&paths)?;
+
+            match cli.output_format() {
+                OutputFormat::Json => {
+                    let listed: Vec<_> = snapshots
+                        .iter()
+                        .map(|(name, m)| serde_json::json!({"name": name, "snapshot": m}))
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&listed)?);
+                }
+                OutputFormat::Ndjson => {
+                    for (name, m) in &snapshots {
+                        println!("{}", serde_json::json!({"name": name, "snapshot": m}));
+                    }
+                }
+                OutputFormat::Table | OutputFormat::Tsv => {
+                    if snapshots.is_empty() {
+                        println!(
+                            "No snapshots imported. Use 'muesli index import FILE --name NAME'."
+                        );
+                    }
+                    for (name, m) in &snapshots {
+                        println!(
+                            "{}\t{} meetings\texported {}\t{}",
+                            name,
+                            m.documents.len(),
+                            m.exported_at.format("%Y-%m-%d"),
+                            m.generator
+                        );
+                    }
+                }
+            }
+        }
+        muesli::cli::Commands::Person { name, since, limit } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let report = muesli::person::person_report(&paths, &name, since, limit)?;
+
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                OutputFormat::Ndjson => println!("{}", serde_json::to_string(&report)?),
+                OutputFormat::Table | OutputFormat::Tsv => print_person(&report),
+            }
+        }
+        muesli::cli::Commands::Account {
+            name,
+            #[cfg(feature = "summaries")]
+            summarize,
+        } => {
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let view = muesli::account::build_account(&paths, &name)?;
+
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&view)?),
+                OutputFormat::Ndjson => println!("{}", serde_json::to_string(&view)?),
+                OutputFormat::Table | OutputFormat::Tsv => print_account(&view),
+            }
+
+            #[cfg(feature = "summaries")]
+            if summarize {
+                let combined = muesli::account::combined_summaries(&view)?;
+                if combined.is_empty() {
+                    return Err(muesli::Error::Summarization(format!(
+                        "No saved summaries for '{}'. Run 'muesli summarize <doc_id> --save' first.",
+                        name
+                    )));
+                }
+
+                let mut config = muesli::summary::SummaryConfig::load(
+                    &paths.data_dir.join("summary_config.json"),
+                )?;
+                config.custom_prompt = Some(muesli::account::ACCOUNT_SUMMARY_PROMPT.to_string());
+
+                let api_key = muesli::summary::api_key_for(&config)?;
+                let summary = runtime()?.block_on(muesli::summary::summarize_transcript(
+                    &combined,
+                    api_key.as_deref(),
+                    &config,
+                ))?;
+                println!("\n{}\n", summary);
+            }
+        }
+        muesli::cli::Commands::Goals {
+            action:
+                muesli::cli::GoalsCommands::Report {
+                    file,
+                    #[cfg(feature = "semantic")]
+                    semantic,
+                    #[cfg(feature = "summaries")]
+                    status,
+                },
+        } => {
+            use muesli::goals;
+            use muesli::output::OutputFormat;
+
+            let paths = cli.paths()?;
+            let goals_path = file.unwrap_or_else(|| paths.data_dir.join("goals.toml"));
+            let transcripts = muesli::storage::scan_transcripts(&paths.transcripts_dir)?;
+
+            let mut reports = Vec::new();
+            for goal in goals::load_goals(&goals_path)? {
+                #[cfg_attr(not(feature = "semantic"), allow(unused_mut))]
+                let mut mentions = goals::find_mentions(&goal, &transcripts)?;
+
+                #[cfg(feature = "semantic")]
+                if semantic {
+                    goals::add_semantic_mentions(&paths, &goal, &mut mentions)?;
+                }
+
+                #[cfg_attr(not(feature = "summaries"), allow(unused_mut))]
+                let mut report = goals::GoalReport {
+                    monthly: goals::monthly_counts(&mentions),
+                    goal,
+                    mentions,
+                    status: None,
+                };
+
+                #[cfg(feature = "summaries")]
+                if status && !report.mentions.is_empty() {
+                    let mut config = muesli::summary::SummaryConfig::load(
+                        &paths.data_dir.join("summary_config.json"),
+                    )?;
+                    config.custom_prompt = Some(goals::GOAL_STATUS_PROMPT.to_string());
+                    let api_key = muesli::summary::api_key_for(&config)?;
+                    let excerpts = goals::status_excerpts(&report.goal, &report.mentions, 5);
+                    report.status =
+                        Some(runtime()?.block_on(muesli::summary::summarize_transcript(
+                            &excerpts,
+                            api_key.as_deref(),
+                            &config,
+                        ))?);
+                }
+
+                reports.push(report);
+            }
+
+            match cli.output_format() {
+                OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&reports)?),
+                OutputFormat::Ndjson => {
+                    for report in &reports {
+                        println!("{}", serde_json::to_string(report)?);
+                    }
+                }
+                OutputFormat::Table | OutputFormat::Tsv => {
+                    for report in &reports {
+                        print_goal_report(report);
+                    }
+                }
+            }
+        }
+        muesli::cli::Commands::Auth {
+            action: muesli::cli::AuthCommands::Status,
+        } => {
+            muesli::offline::ensure_online("checking the token")?;
+            let (token, source) = muesli::auth::resolve_token_with_source(
+                cli.token.clone(),
+                cli.profile()?.as_ref(),
+            )?;
+            if let Some(profile) = &cli.profile {
+                println!("Profile:      {}", profile);
+            }
+            println!("Token source: {}", source);
+
+            match muesli::auth::token_expiry(&token) {
+                Some(exp) if exp <= chrono::Utc::now() => {
+                    println!("Expires:      {} (expired)", exp.to_rfc3339())
+                }
+                Some(exp) => println!("Expires:      {}", exp.to_rfc3339()),
+                None => println!("Expires:      unknown (token is not a JWT)"),
+            }
+
+            let client = ApiClient::new(token, Some(cli.api_base.clone()))?
+                .with_config(ApiConfig::configured(&cli.paths()?)?)
+                .disable_throttle();
+            match client.check_token() {
+                Ok(()) => println!("Status:       valid"),
+                Err(e) => {
+                    println!("Status:       invalid");
+                    return Err(muesli::Error::Auth(format!(
+                        "Token from {} was rejected: {}",
+                        source, e
+                    )));
+                }
+            }
+        }
+        #[cfg(feature = "keychain")]
+        muesli::cli::Commands::Auth {
+            action: muesli::cli::AuthCommands::Login { token },
+        } => {
+            let token = match token {
+                Some(token) => token,
+                None => {
+                    let mut line = String::new();
+                    std::io::stdin().read_line(&mut line)?;
+                    line
+                }
+            };
+            let token = token.trim();
+            if token.is_empty() {
+                return Err(muesli::Error::InvalidInput("No token given".into()));
+            }
+            let account = muesli::keychain::granola_token_account(cli.profile()?.as_ref());
+            muesli::keychain::set(&account, token)?;
+            println!(
+                "{}Granola token stored in {}",
+                muesli::term::emoji("✅ "),
+                muesli::keychain::backend_name()
+            );
+        }
+        #[cfg(feature = "keychain")]
+        muesli::cli::Commands::Auth {
+            action: muesli::cli::AuthCommands::Logout,
+        } => {
+            let account = muesli::keychain::granola_token_account(cli.profile()?.as_ref());
+            if muesli::keychain::delete(&account)? {
+                println!(
+                    "Removed the Granola token from {}",
+                    muesli::keychain::backend_name()
+                );
+            } else {
+                println!("No Granola token stored");
             }
         }
-        muesli::cli::Commands::Open => {
-            let paths = Paths::new(cli.data_dir)?;
+        muesli::cli::Commands::Open { doc_id, summary } => {
+            let paths = cli.paths()?;
             paths.ensure_dirs()?;
 
-            // Open the data directory in the system file browser
-            if let Err(e) = open::that(&paths.data_dir) {
-                eprintln!("Failed to open data directory: {}", e);
+            let Some(doc_id) = doc_id else {
+                // Open the data directory in the system file browser
+                if let Err(e) = open::that(&paths.data_dir) {
+                    eprintln!("Failed to open data directory: {}", e);
+                    std::process::exit(1);
+                }
+                println!("Opened data directory: {}", paths.data_dir.display());
+                return Ok(());
+            };
+
+            let entry = muesli::storage::Catalog::open(&paths)?.find(&doc_id)?;
+            let path = if summary {
+                let path = muesli::sync::summary_path(&paths, &entry.path);
+                if !path.exists() {
+                    return Err(muesli::Error::InvalidInput(format!(
+                        "{} has no saved summary; run 'muesli summarize {} --save' first",
+                        doc_id, entry.doc_id
+                    )));
+                }
+                path
+            } else {
+                entry.path
+            };
+            if let Err(e) = open::that(&path) {
+                eprintln!("Failed to open {}: {}", path.display(), e);
                 std::process::exit(1);
             }
-            println!("Opened data directory: {}", paths.data_dir.display());
+            println!("Opened {}", path.display());
+        }
+        muesli::cli::Commands::UrlHandler {
+            url,
+            print,
+            desktop_entry,
+        } => {
+            if desktop_entry {
+                print!(
+                    "{}",
+                    muesli::url_handler::desktop_entry(&std::env::current_exe()?)
+                );
+                return Ok(());
+            }
+            use muesli::url_handler::{Callback, Show};
+
+            let paths = cli.paths()?;
+            let config = muesli::url_handler::UrlHandlerConfig::configured(&paths)?;
+            let request = muesli::url_handler::parse(&url.unwrap_or_default(), &config)?;
+
+            // The calling app gets the outcome through its callback, when it gave one
+            let handled =
+                match muesli::url_handler::handle(&paths, &request, print, &cli.output_options()) {
+                    Ok(handled) => handled,
+                    Err(e) => {
+                        if let Some(error) = request.error_callback(&e) {
+                            open::that(error.as_str())?;
+                        }
+                        return Err(e);
+                    }
+                };
+            match &handled.show {
+                Some(Show::Print(text)) => print!("{}", text),
+                Some(Show::Open(path)) => open::that(path)?,
+                None => {}
+            }
+            match request.callback(&handled.reply, confirm_callback) {
+                Some(Callback::Send(success)) => open::that(success.as_str())?,
+                Some(Callback::Cancelled(error)) => {
+                    eprintln!("Cancelled: nothing was sent to the calling app");
+                    if let Some(error) = error {
+                        open::that(error.as_str())?;
+                    }
+                }
+                None => {}
+            }
         }
         muesli::cli::Commands::FixDates => {
-            let paths = Paths::new(cli.data_dir)?;
+            let paths = cli.paths()?;
             fix_dates(&paths)?;
         }
+        muesli::cli::Commands::FixFilenames { dry_run } => {
+            let paths = cli.paths()?;
+            let renames = muesli::sync::rename_files(&paths, dry_run)?;
+            for rename in &renames {
+                println!("{} -> {}", rename.from, rename.to);
+            }
+            match (dry_run, renames.len()) {
+                (_, 0) => println!("All filenames already follow the rules"),
+                (true, n) => println!("Would rename {} transcripts", n),
+                (false, n) => {
+                    println!("Renamed {} transcripts", n);
+                    #[cfg(feature = "semantic")]
+                    println!("Run 'muesli link' to update links in related-meeting sections");
+                }
+            }
+        }
         #[cfg(feature = "summaries")]
         muesli::cli::Commands::SetApiKey { api_key } => {
             muesli::summary::set_api_key_in_keychain(&api_key)?;
         }
         #[cfg(feature = "summaries")]
         muesli::cli::Commands::SetConfig {
+            provider,
+            ollama_host,
+            local_model,
+            local_tokenizer,
             model,
             context_window,
             prompt_file,
+            language,
             show,
         } => {
-            let paths = Paths::new(cli.data_dir)?;
+            let paths = cli.paths()?;
             let config_path = paths.data_dir.join("summary_config.json");
 
             if show {
                 // Show current config
                 let config = muesli::summary::SummaryConfig::load(&config_path)?;
                 println!("Current summarization configuration:");
-                println!("  Model: {}", config.model);
+                print_provider(&config);
                 println!(
                     "  Context window: {} characters",
                     config.context_window_chars
                 );
+                println!(
+                    "  Summary language: {}",
+                    config
+                        .summary_language
+                        .as_deref()
+                        .unwrap_or("(same as transcript)")
+                );
                 println!(
                     "  Custom prompt: {}",
                     if config.custom_prompt.is_some() {
@@ -196,6 +1132,18 @@ fn run() -> Result<()> {
                         "No (using default)"
                     }
                 );
+                let presets: Vec<String> = config
+                    .presets()
+                    .into_iter()
+                    .map(|(name, preset)| {
+                        if preset.labels.is_empty() {
+                            name
+                        } else {
+                            format!("{} (labels: {})", name, preset.labels.join(", "))
+                        }
+                    })
+                    .collect();
+                println!("  Presets: {}", presets.join("; "));
                 if let Some(prompt) = &config.custom_prompt {
                     println!("\nCustom prompt:");
                     println!("{}", prompt);
@@ -207,6 +1155,19 @@ fn run() -> Result<()> {
             let mut config = muesli::summary::SummaryConfig::load(&config_path)?;
 
             // Update fields if provided
+            if let Some(p) = provider {
+                config.provider = p;
+            }
+            if let Some(host) = ollama_host {
+                let host = host.trim().to_string();
+                config.ollama_host = (!host.is_empty()).then_some(host);
+            }
+            if let Some(path) = local_model {
+                config.local_model = Some(std::fs::canonicalize(&path)?);
+            }
+            if let Some(path) = local_tokenizer {
+                config.local_tokenizer = Some(std::fs::canonicalize(&path)?);
+            }
             if let Some(m) = model {
                 config.model = m;
             }
@@ -217,125 +1178,760 @@ fn run() -> Result<()> {
                 let prompt = std::fs::read_to_string(&pf)?;
                 config.custom_prompt = Some(prompt);
             }
+            if let Some(lang) = language {
+                let lang = lang.trim().to_string();
+                config.summary_language = (!lang.is_empty()).then_some(lang);
+            }
 
             // Save config
             config.save(&config_path, &paths.tmp_dir)?;
-            println!("✅ Configuration saved");
-            println!("  Model: {}", config.model);
+            println!("{}Configuration saved", term::emoji("✅ "));
+            print_provider(&config);
             println!(
                 "  Context window: {} characters",
                 config.context_window_chars
             );
+            if let Some(lang) = &config.summary_language {
+                println!("  Summary language: {}", lang);
+            }
         }
         #[cfg(feature = "summaries")]
-        muesli::cli::Commands::Summarize { doc_id, save } => {
-            let paths = Paths::new(cli.data_dir)?;
+        muesli::cli::Commands::Summarize {
+            doc_id,
+            preset,
+            save,
+            #[cfg(feature = "clipboard")]
+            copy,
+        } => {
+            use muesli::output::OutputFormat;
+
+            // JSON asks the model for structured output; progress then goes to
+            // stderr so stdout is only the JSON
+            let format = cli.output_format();
+            let structured = matches!(format, OutputFormat::Json | OutputFormat::Ndjson);
+            let status = |message: String| {
+                if structured {
+                    eprintln!("{}", message);
+                } else {
+                    println!("{}", message);
+                }
+            };
+            let paths = cli.paths()?;
+            let load_span = perf::span("summarize.load");
 
             // Load config
             let config_path = paths.data_dir.join("summary_config.json");
             let config = muesli::summary::SummaryConfig::load(&config_path)?;
 
             // Find the markdown file for this doc_id
-            let md_path = find_transcript_by_id(&paths, &doc_id)?;
-
-            // Read the transcript
-            let content = std::fs::read_to_string(&md_path)?;
-
-            // Extract body (skip frontmatter)
-            let body = if content.starts_with("---\n") {
-                content
-                    .split("---\n")
-                    .nth(2)
-                    .unwrap_or(&content)
-                    .to_string()
-            } else {
-                content
+            let entry = muesli::storage::Catalog::open(&paths)?.entry_for(&doc_id)?;
+
+            // An explicit preset wins; otherwise one may match the meeting's labels
+            let config = match config.choose_preset(preset.as_deref(), &entry.labels)? {
+                Some((name, preset)) => {
+                    status(format!("Using the '{}' prompt preset", name));
+                    config.with_preset(&preset)
+                }
+                None => config,
             };
 
-            // Get API key
-            let api_key = std::env::var("OPENAI_API_KEY")
-                .or_else(|_| muesli::summary::get_api_key_from_keychain())?;
+            let body = muesli::document::Document::load(&entry.path)?.body;
+            drop(load_span);
 
-            // Run async summarization
-            println!(
-                "Summarizing with {} (context window: {} chars)...",
-                config.model, config.context_window_chars
-            );
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()?;
-            let summary = rt.block_on(muesli::summary::summarize_transcript(
-                &body, &api_key, &config,
-            ))?;
+            // Get API key (not needed for local providers)
+            let api_key = muesli::summary::api_key_for(&config)?;
 
-            if save {
-                // Save to summaries directory
-                let filename = md_path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .ok_or_else(|| {
-                        muesli::Error::Filesystem(std::io::Error::new(
-                            std::io::ErrorKind::InvalidInput,
-                            "Invalid filename",
-                        ))
-                    })?;
-                let summary_path = paths.summaries_dir.join(format!("{}_summary.md", filename));
+            // Run async summarization
+            status(format!(
+                "Summarizing with {} via {} (context window: {} chars)...",
+                config.model, config.provider, config.context_window_chars
+            ));
+            let summary = if structured {
+                let summary = runtime()?.block_on(muesli::summary::summarize_structured(
+                    &body,
+                    api_key.as_deref(),
+                    &config,
+                ))?;
+                match format {
+                    OutputFormat::Ndjson => serde_json::to_string(&summary)?,
+                    _ => serde_json::to_string_pretty(&summary)?,
+                }
+            } else {
+                runtime()?.block_on(muesli::summary::summarize_transcript(
+                    &body,
+                    api_key.as_deref(),
+                    &config,
+                ))?
+            };
 
+            let _span = perf::span("summarize.save");
+            if save && structured {
+                // Beside the markdown summary; not indexed, since it isn't prose
+                let summary_path =
+                    muesli::sync::summary_path(&paths, &entry.path).with_extension("json");
+                std::fs::create_dir_all(&paths.summaries_dir)?;
                 muesli::storage::write_atomic(&summary_path, summary.as_bytes(), &paths.tmp_dir)?;
-                println!("✅ Summary saved to: {}", summary_path.display());
+                eprintln!(
+                    "{}Summary saved to: {}",
+                    term::emoji("✅ "),
+                    summary_path.display()
+                );
+            } else if structured {
+                println!("{}", summary);
+            } else if save {
+                let summary_path = muesli::sync::save_summary(&paths, &entry, &summary)?;
+                println!(
+                    "{}Summary saved to: {}",
+                    term::emoji("✅ "),
+                    summary_path.display()
+                );
             } else {
                 // Print to stdout
                 println!("\n{}\n", summary);
             }
+
+            #[cfg(feature = "clipboard")]
+            if copy {
+                muesli::clipboard::copy(&summary)?;
+                status(format!("{}Summary copied to clipboard", term::emoji("📋 ")));
+            }
+        }
+        #[cfg(feature = "summaries")]
+        muesli::cli::Commands::Summary {
+            action:
+                muesli::cli::SummaryCommands::Diff {
+                    doc_id,
+                    side_by_side,
+                    context,
+                    width,
+                },
+        } => {
+            let paths = cli.paths()?;
+            let entry = muesli::storage::Catalog::open(&paths)?.entry_for(&doc_id)?;
+            let versions = muesli::summary::diff::SummaryVersions::load(&paths, &entry)?;
+
+            if versions.is_unchanged() {
+                println!("The summary is the same as the previous version");
+            } else if side_by_side {
+                print!("{}", versions.side_by_side(width));
+            } else {
+                print!("{}", versions.unified(context));
+            }
+        }
+        #[cfg(feature = "summaries")]
+        muesli::cli::Commands::InterviewPacket {
+            doc_id,
+            force,
+            save,
+        } => {
+            use muesli::summary::interview;
+
+            let paths = cli.paths()?;
+            let review = Review::load(
+                &paths,
+                &doc_id,
+                force,
+                "an interview",
+                interview::is_interview,
+            )?;
+            eprintln!(
+                "Building interview packet with {} via {}...",
+                review.config.model, review.config.provider
+            );
+            let packet = runtime()?.block_on(muesli::summary::interview_packet(
+                &review.body,
+                review.api_key()?.as_deref(),
+                &review.config,
+            ))?;
+
+            let json = json_output(&cli, &packet)?;
+            let path =
+                save.then(|| interview::packet_path(&paths, &review.entry.path, json.is_some()));
+            let document =
+                json.unwrap_or_else(|| interview::to_markdown(&review.frontmatter, &packet));
+            save_or_print(&paths, &document, path, "Interview packet")?;
+        }
+        #[cfg(feature = "summaries")]
+        muesli::cli::Commands::IncidentTimeline {
+            doc_id,
+            force,
+            save,
+        } => {
+            use muesli::summary::incident;
+
+            let paths = cli.paths()?;
+            let review = Review::load(
+                &paths,
+                &doc_id,
+                force,
+                "an incident review",
+                incident::is_incident,
+            )?;
+            eprintln!(
+                "Extracting incident timeline with {} via {}...",
+                review.config.model, review.config.provider
+            );
+            let timeline = runtime()?.block_on(muesli::summary::incident_timeline(
+                &review.body,
+                review.api_key()?.as_deref(),
+                &review.config,
+            ))?;
+
+            let json = json_output(&cli, &timeline)?;
+            let path =
+                save.then(|| incident::timeline_path(&paths, &review.entry.path, json.is_some()));
+            let document =
+                json.unwrap_or_else(|| incident::to_markdown(&review.frontmatter, &timeline));
+            save_or_print(&paths, &document, path, "Postmortem")?;
+        }
+        #[cfg(feature = "summaries")]
+        muesli::cli::Commands::Retro { quarter, filters } => {
+            let paths = cli.paths()?;
+            let retro = muesli::retro::gather(&paths, quarter, &filters)?;
+
+            if retro.unsummarized > 0 {
+                eprintln!(
+                    "Note: {} meetings in {} have no saved summary and are left out \
+                     (run 'muesli summarize <doc_id> --save' to include them)",
+                    retro.unsummarized,
+                    retro.scope()
+                );
+            }
+            if retro.meetings.is_empty() {
+                return Err(muesli::Error::Summarization(format!(
+                    "No saved summaries for {}. Run 'muesli summarize <doc_id> --save' first.",
+                    retro.scope()
+                )));
+            }
+
+            let config =
+                muesli::summary::SummaryConfig::load(&paths.data_dir.join("summary_config.json"))?;
+            let preset = config
+                .presets()
+                .remove(muesli::summary::RETRO_PRESET)
+                .expect("retro is a built-in preset");
+            let config = config.with_preset(&preset);
+
+            let api_key = muesli::summary::api_key_for(&config)?;
+            println!(
+                "Summarizing {} meeting summaries from {} with {} via {}...",
+                retro.meetings.len(),
+                retro.scope(),
+                config.model,
+                config.provider
+            );
+            let retrospective = runtime()?.block_on(muesli::summary::summarize_transcript(
+                &retro.combined_summaries()?,
+                api_key.as_deref(),
+                &config,
+            ))?;
+
+            let path = retro.save(&paths, &retrospective)?;
+            println!(
+                "{}Retrospective saved to: {}",
+                term::emoji("✅ "),
+                path.display()
+            );
         }
         #[cfg(feature = "mcp")]
         muesli::cli::Commands::Mcp => {
             // Run MCP server asynchronously
-            let rt = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()?;
-            rt.block_on(muesli::mcp::serve_mcp(cli.data_dir))?;
+            runtime()?.block_on(muesli::mcp::serve_mcp(cli.data_dir.clone(), cli.profile()?))?;
+        }
+        muesli::cli::Commands::Completions { shell } => {
+            use std::io::Write;
+            std::io::stdout().write_all(&Cli::completions(shell))?;
         }
     }
 
     Ok(())
 }
 
-/// Find a transcript file by document ID
+/// Prints one goal's mentions, monthly chart and status
+fn print_goal_report(report: &muesli::goals::GoalReport) {
+    println!("== {} ==", report.goal.name);
+
+    let Some(last) = report.mentions.last() else {
+        println!("No mentions found\n");
+        return;
+    };
+
+    let total: usize = report.mentions.iter().map(|m| m.count).sum();
+    println!(
+        "{} meetings, {} keyword mentions, last mentioned {} in \"{}\"",
+        report.mentions.len(),
+        total,
+        last.date,
+        last.title.as_deref().unwrap_or("Untitled")
+    );
+    println!();
+    print!("{}", muesli::goals::render_chart(&report.monthly, 40));
+
+    if let Some(status) = &report.status {
+        println!("\n{}", status);
+    }
+    println!();
+}
+
+/// Prints the human-readable account view
+#[cfg(feature = "index")]
+fn print_index_stats(stats: &muesli::index::stats::IndexStats) {
+    println!("Text index:   {} documents", stats.text_docs);
+
+    let Some(vectors) = &stats.vectors else {
+        println!("Vectors:      none");
+        return;
+    };
+    println!("Vectors:      {}", vectors.count);
+    println!("Dimension:    {}", vectors.dim);
+    println!(
+        "Model:        {}",
+        vectors.model.as_deref().unwrap_or("unknown")
+    );
+    println!("Average norm: {:.4}", vectors.average_norm);
+
+    if stats.in_sync() {
+        println!(
+            "{}Text index and vectors cover the same documents",
+            term::emoji("✅ ")
+        );
+        return;
+    }
+
+    if !stats.missing_vectors.is_empty() {
+        println!(
+            "\nWithout vectors ({}; 'muesli sync' embeds them):",
+            stats.missing_vectors.len()
+        );
+        for doc_id in &stats.missing_vectors {
+            println!("  {}", doc_id);
+        }
+    }
+    if !stats.missing_text.is_empty() {
+        println!(
+            "\nNot in text index ({}; 'muesli sync --reindex' rebuilds it):",
+            stats.missing_text.len()
+        );
+        for doc_id in &stats.missing_text {
+            println!("  {}", doc_id);
+        }
+    }
+}
+
+/// Provider lines of `set-config` output
 #[cfg(feature = "summaries")]
-fn find_transcript_by_id(paths: &Paths, doc_id: &str) -> muesli::Result<std::path::PathBuf> {
-    use std::fs;
+fn print_provider(config: &muesli::summary::SummaryConfig) {
+    use muesli::summary::Provider;
+
+    println!("  Provider: {}", config.provider);
+    match config.provider {
+        Provider::OpenAi => println!("  Model: {}", config.model),
+        Provider::Ollama => {
+            println!("  Ollama host: {}", config.ollama_host());
+            println!("  Model: {}", config.model);
+        }
+        Provider::Local => {
+            println!(
+                "  Model file: {}",
+                config
+                    .local_model
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "(not set)".to_string())
+            );
+            println!(
+                "  Tokenizer: {}",
+                config
+                    .local_tokenizer
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "(tokenizer.json next to the model)".to_string())
+            );
+        }
+    }
+}
 
-    let entries = fs::read_dir(&paths.transcripts_dir)?;
+/// Prints meeting totals, a weekly chart of hours and the most frequent participants
+#[cfg(feature = "index")]
+fn print_terms(report: &muesli::index::terms::TermsReport) {
+    let period = match (report.since, report.until) {
+        (Some(since), Some(until)) => format!(" from {} to {}", since, until),
+        (Some(since), None) => format!(" since {}", since),
+        (None, Some(until)) => format!(" until {}", until),
+        (None, None) => String::new(),
+    };
+    println!("Top terms in {} meetings{}:", report.meetings, period);
+    let width = report.terms.iter().map(|t| t.term.len()).max().unwrap_or(0);
+    for (rank, term) in report.terms.iter().enumerate() {
+        println!(
+            "  {:>3}. {:<width$}  {:>8.1}  {:>5}x in {} mtg",
+            rank + 1,
+            term.term,
+            term.score,
+            term.occurrences,
+            term.meetings
+        );
+    }
+}
+
+fn print_overview(overview: &muesli::stats::Overview) {
+    let period = match (overview.since, overview.until) {
+        (Some(since), Some(until)) => format!(" from {} to {}", since, until),
+        (Some(since), None) => format!(" since {}", since),
+        (None, Some(until)) => format!(" until {}", until),
+        (None, None) => String::new(),
+    };
+    let untimed = if overview.untimed > 0 {
+        format!(" ({} without a duration)", overview.untimed)
+    } else {
+        String::new()
+    };
+    println!(
+        "Meetings{}: {} · Total: {:.1}h{} · Dead time: {:.1}h",
+        period, overview.meetings, overview.hours, untimed, overview.dead_hours
+    );
+    if overview.meetings == 0 {
+        return;
+    }
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
+    println!("\nHours per week:");
+    let busiest = overview.weeks.iter().map(|w| w.hours).fold(0.0, f64::max);
+    for week in &overview.weeks {
+        let bar = if busiest > 0.0 {
+            "#".repeat((week.hours / busiest * 30.0).round() as usize)
+        } else {
+            String::new()
+        };
+        println!(
+            "  {}  {:>5.1}h  {:>3} mtg  {}",
+            week.week_start, week.hours, week.meetings, bar
+        );
+    }
 
-        if path.extension().and_then(|s| s.to_str()) != Some("md") {
-            continue;
+    if !overview.top_participants.is_empty() {
+        println!("\nTop participants:");
+        for tally in &overview.top_participants {
+            println!("  {:>4}  {}", tally.meetings, tally.name);
         }
+    }
+}
 
-        // Read frontmatter to check doc_id
-        if let Some(fm) = muesli::storage::read_frontmatter(&path)? {
-            if fm.doc_id == doc_id {
-                return Ok(path);
-            }
+fn print_person(report: &muesli::person::PersonReport) {
+    let period = report
+        .since
+        .map(|since| format!(" since {}", since))
+        .unwrap_or_default();
+    if report.meetings.is_empty() {
+        println!("No meetings found with: {}{}", report.name, period);
+        return;
+    }
+
+    println!("Person: {} ({})", report.name, report.matched.join(", "));
+    let untimed = report
+        .meetings
+        .iter()
+        .filter(|m| m.duration_seconds.is_none())
+        .count();
+    let untimed = if untimed > 0 {
+        format!(" ({} without a recorded duration)", untimed)
+    } else {
+        String::new()
+    };
+    println!(
+        "Meetings{}: {} · Total: {:.1}h{}",
+        period,
+        report.meetings.len(),
+        report.total_hours,
+        untimed
+    );
+    if let (Some(first), Some(last)) = (report.meetings.first(), report.meetings.last()) {
+        println!(
+            "First: {} · Last: {}",
+            first.created_at.format("%Y-%m-%d"),
+            last.created_at.format("%Y-%m-%d")
+        );
+    }
+
+    let print_tallies = |heading: &str, tallies: &[muesli::person::Tally], empty: &str| {
+        println!("\n{}:", heading);
+        if tallies.is_empty() {
+            println!("  ({})", empty);
+        }
+        for tally in tallies {
+            println!("  {:>3}  {}", tally.meetings, tally.name);
+        }
+    };
+    print_tallies("Common co-attendees", &report.co_attendees, "none");
+    print_tallies("Most-discussed topics", &report.topics, "no labels");
+}
+
+fn print_account(view: &muesli::account::AccountView) {
+    if view.meetings.is_empty() {
+        println!("No meetings found for account: {}", view.name);
+        return;
+    }
+
+    println!("Account: {} ({})", view.name, view.organizations.join(", "));
+    if let Some(last) = view.last_contact {
+        println!(
+            "Meetings: {} · Last contact: {}",
+            view.meetings.len(),
+            last.format("%Y-%m-%d")
+        );
+    }
+
+    println!("\nMeetings:");
+    for meeting in &view.meetings {
+        println!(
+            "  {}  {}  ({})",
+            meeting.created_at.format("%Y-%m-%d"),
+            meeting.title.as_deref().unwrap_or("Untitled"),
+            meeting.doc_id
+        );
+    }
+
+    println!("\nOpen action items:");
+    if view.action_items.is_empty() {
+        println!("  (none found in saved summaries)");
+    }
+    for item in &view.action_items {
+        println!("  - {}", item);
+    }
+}
+
+/// Plain-text find-in results, shared by stdout and `--copy`
+fn format_find_hits(hits: &[muesli::find::FindHit], query: &str, path: &std::path::Path) -> String {
+    if hits.is_empty() {
+        return format!("No matches for: {}\n", query);
+    }
+
+    let line = |marker: &str, entry: &muesli::find::Entry| {
+        let timestamp = entry.timestamp.as_deref().unwrap_or("--:--:--");
+        format!(
+            "{} [{}] {}: {}\n",
+            marker, timestamp, entry.speaker, entry.text
+        )
+    };
+
+    let mut out = String::new();
+    for (i, hit) in hits.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for entry in &hit.before {
+            out.push_str(&line(" ", entry));
+        }
+        out.push_str(&line(">", &hit.entry));
+        for entry in &hit.after {
+            out.push_str(&line(" ", entry));
+        }
+        if let Some(anchor) = &hit.entry.anchor {
+            out.push_str(&format!("  {}#{}\n", path.display(), anchor));
+        }
+    }
+    out
+}
+
+/// Asks the user before meeting data goes back through a link's x-success callback
+///
+/// Prompts on the terminal when there is one, else in a system dialog
+/// (osascript on macOS, zenity on Linux). Without either, the answer is no.
+fn confirm_callback(question: &str) -> bool {
+    use std::io::IsTerminal;
+
+    if std::io::stdin().is_terminal() {
+        eprint!("{} [y/N] ", question);
+        let mut answer = String::new();
+        return std::io::stdin().read_line(&mut answer).is_ok()
+            && matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+    }
+    let dialog = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display dialog \"{}\" buttons {{\"Don't Allow\", \"Allow\"}} default button \"Don't Allow\" with title \"muesli\"",
+            question.replace('\\', "").replace('"', "'")
+        );
+        std::process::Command::new("osascript")
+            .args(["-e", &script])
+            .output()
+            .map(|out| {
+                out.status.success()
+                    && String::from_utf8_lossy(&out.stdout).contains("button returned:Allow")
+            })
+    } else {
+        std::process::Command::new("zenity")
+            .args(["--question", "--title=muesli", "--text", question])
+            .status()
+            .map(|status| status.success())
+    };
+    dialog.unwrap_or(false)
+}
+
+/// Cleans up after a previously crashed run before a command reads or writes the archive
+///
+/// Reports go to stderr so they never mix with JSON output or the MCP stdio stream.
+fn repair_archive(paths: &muesli::Paths) -> Result<()> {
+    let recovery =
+        muesli::storage::recover_partial_writes(paths, muesli::storage::PARTIAL_WRITE_GRACE)?;
+    if recovery != muesli::storage::Recovery::default() {
+        eprintln!(
+            "Recovered interrupted writes: {} completed, {} incomplete removed, {} superseded by later writes removed",
+            recovery.completed, recovery.removed, recovery.superseded
+        );
+    }
+
+    let missing = muesli::sync::drop_missing_cache_entries(paths)?;
+    if !missing.is_empty() {
+        eprintln!(
+            "{} synced documents are missing local files and will be refetched on the next sync",
+            missing.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// A labeled meeting read for `interview-packet` or `incident-timeline`
+#[cfg(feature = "summaries")]
+struct Review {
+    config: muesli::summary::SummaryConfig,
+    entry: muesli::storage::CatalogEntry,
+    frontmatter: muesli::Frontmatter,
+    body: String,
+}
+
+#[cfg(feature = "summaries")]
+impl Review {
+    /// Reads the transcript, refusing meetings without the kind's labels unless `force`
+    fn load(
+        paths: &muesli::Paths,
+        doc_id: &str,
+        force: bool,
+        kind: &str,
+        labeled: fn(&muesli::summary::SummaryConfig, &[String]) -> bool,
+    ) -> Result<Self> {
+        let config =
+            muesli::summary::SummaryConfig::load(&paths.data_dir.join("summary_config.json"))?;
+        let entry = muesli::storage::Catalog::open(paths)?.entry_for(doc_id)?;
+        let frontmatter = muesli::storage::read_frontmatter(&entry.path)?.ok_or_else(|| {
+            muesli::Error::InvalidInput(format!("{} has no frontmatter", entry.path.display()))
+        })?;
+        if !force && !labeled(&config, &frontmatter.labels) {
+            let labels = if frontmatter.labels.is_empty() {
+                "none".to_string()
+            } else {
+                frontmatter.labels.join(", ")
+            };
+            return Err(muesli::Error::InvalidInput(format!(
+                "{} isn't labeled as {} (labels: {}); pass --force to use it anyway",
+                doc_id, kind, labels
+            )));
         }
+
+        let content = std::fs::read_to_string(&entry.path)?.replace("\r\n", "\n");
+        let body = muesli::storage::strip_frontmatter(&content).to_string();
+        Ok(Review {
+            config,
+            entry,
+            frontmatter,
+            body,
+        })
+    }
+
+    fn api_key(&self) -> Result<Option<String>> {
+        muesli::summary::api_key_for(&self.config)
     }
+}
+
+/// An extraction as JSON when `--format json` or `ndjson` asked for it
+#[cfg(feature = "summaries")]
+fn json_output<T: serde::Serialize>(cli: &Cli, value: &T) -> Result<Option<String>> {
+    use muesli::output::OutputFormat;
+    Ok(match cli.output_format() {
+        OutputFormat::Ndjson => Some(serde_json::to_string(value)?),
+        OutputFormat::Json => Some(serde_json::to_string_pretty(value)?),
+        _ => None,
+    })
+}
 
-    Err(muesli::Error::Filesystem(std::io::Error::new(
-        std::io::ErrorKind::NotFound,
-        format!("No transcript found for document ID: {}", doc_id),
-    )))
+/// Writes a generated document to `path`, or prints it when not saving
+#[cfg(feature = "summaries")]
+fn save_or_print(
+    paths: &muesli::Paths,
+    document: &str,
+    path: Option<std::path::PathBuf>,
+    what: &str,
+) -> Result<()> {
+    let Some(path) = path else {
+        println!("{}", document.trim_end());
+        return Ok(());
+    };
+    std::fs::create_dir_all(&paths.summaries_dir)?;
+    muesli::storage::write_atomic(&path, document.as_bytes(), &paths.tmp_dir)?;
+    eprintln!(
+        "{}{} saved to: {}",
+        term::emoji("✅ "),
+        what,
+        path.display()
+    );
+    Ok(())
+}
+
+/// Builds the single-threaded tokio runtime used by async commands.
+fn runtime() -> Result<tokio::runtime::Runtime> {
+    Ok(tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?)
+}
+
+/// Resolves the token for API clients; replay never talks to the API, so a missing token is fine.
+fn client_token(cli: &Cli) -> Result<String> {
+    // Fail on --offline before a missing token can be blamed
+    if cli.replay.is_none() {
+        muesli::offline::ensure_online("the Granola API")?;
+    }
+    match resolve_token(cli.token.clone(), cli.profile()?.as_ref()) {
+        Ok(token) => Ok(token),
+        Err(_) if cli.replay.is_some() => Ok(String::new()),
+        Err(e) => Err(e),
+    }
 }
 
 /// Creates an API client with auth and throttle configuration from CLI flags.
 fn create_client(cli: &Cli) -> Result<ApiClient> {
-    let token = resolve_token(cli.token.clone())?;
-    let mut client = ApiClient::new(token, Some(cli.api_base.clone()))?;
+    let paths = cli.paths()?;
+    let mut client = ApiClient::new(client_token(cli)?, Some(cli.api_base.clone()))?
+        .with_config(ApiConfig::configured(&paths)?)
+        .with_http_cache(&paths);
+
+    if let Some(dir) = &cli.replay {
+        client = client.with_replay(dir.clone()).disable_throttle();
+    } else if let Some(dir) = &cli.record {
+        client = client.with_record(dir.clone());
+    }
+
+    if cli.replay.is_some() || cli.no_throttle {
+        client = client.disable_throttle();
+    } else if let Some((min, max)) = cli.throttle_ms {
+        client = client.with_throttle(min, max);
+    }
+
+    Ok(client)
+}
+
+/// Creates an async API client with auth and throttle configuration from CLI flags.
+fn create_async_client(cli: &Cli) -> Result<AsyncApiClient> {
+    let paths = cli.paths()?;
+    let mut client = AsyncApiClient::new(client_token(cli)?, Some(cli.api_base.clone()))?
+        .with_config(ApiConfig::configured(&paths)?)
+        .with_http_cache(&paths);
+
+    if let Some(dir) = &cli.replay {
+        client = client.with_replay(dir.clone()).disable_throttle();
+    } else if let Some(dir) = &cli.record {
+        client = client.with_record(dir.clone());
+    }
 
-    if cli.no_throttle {
+    if cli.replay.is_some() || cli.no_throttle {
         client = client.disable_throttle();
     } else if let Some((min, max)) = cli.throttle_ms {
         client = client.with_throttle(min, max);
@@ -1,4 +1,4 @@
-use muesli::api::ApiClient;
+use muesli::api::{ApiClient, ApiConfig, AsyncApiClient};
 use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -71,3 +71,226 @@ async fn test_api_error_handling() {
         panic!("Expected API error");
     }
 }
+
+#[tokio::test]
+async fn test_record_then_replay() {
+    let mock_server = MockServer::start().await;
+    let fixtures = tempfile::TempDir::new().unwrap();
+
+    let response = serde_json::json!({
+        "docs": [
+            {
+                "id": "doc123",
+                "title": "Recorded Meeting",
+                "created_at": "2025-10-28T15:04:05Z"
+            }
+        ]
+    });
+
+    Mock::given(method("POST"))
+        .and(path("/v2/get-documents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let dir = fixtures.path().to_path_buf();
+
+    // Record against the mock server, then replay with an unreachable base URL
+    let (recorded, replayed) = tokio::task::spawn_blocking(move || {
+        let recorder = ApiClient::new("test_token".into(), Some(uri))
+            .unwrap()
+            .disable_throttle()
+            .with_record(dir.clone());
+        let recorded = recorder.list_documents().unwrap();
+
+        let replayer = ApiClient::new(String::new(), Some("http://127.0.0.1:9".into()))
+            .unwrap()
+            .with_replay(dir);
+        let replayed = replayer.list_documents().unwrap();
+        (recorded, replayed)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(replayed.len(), 1);
+    assert_eq!(replayed[0].title.as_deref(), Some("Recorded Meeting"));
+    assert!(fixtures.path().join("v2_get-documents.json").exists());
+}
+
+#[tokio::test]
+async fn test_recorded_error_replays_as_error() {
+    let mock_server = MockServer::start().await;
+    let fixtures = tempfile::TempDir::new().unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/v1/get-document-metadata"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("Not Found"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+    let dir = fixtures.path().to_path_buf();
+
+    let (recorded, replayed) = tokio::task::spawn_blocking(move || {
+        let recorder = ApiClient::new("test_token".into(), Some(uri))
+            .unwrap()
+            .disable_throttle()
+            .with_record(dir.clone());
+        let recorded = recorder.get_metadata("gone");
+
+        let replayer = ApiClient::new(String::new(), Some("http://127.0.0.1:9".into()))
+            .unwrap()
+            .with_replay(dir);
+        (recorded, replayer.get_metadata("gone"))
+    })
+    .await
+    .unwrap();
+
+    for result in [recorded, replayed] {
+        match result {
+            Err(muesli::Error::Api { status, .. }) => assert_eq!(status, 404),
+            other => panic!("expected a 404, got {:?}", other.map(|_| ())),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_html_login_page_is_auth_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v2/get-documents"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("Content-Type", "text/html; charset=utf-8")
+                .set_body_string("<!DOCTYPE html><html><body>Sign in</body></html>"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let uri = mock_server.uri();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let client = ApiClient::new("expired_token".into(), Some(uri))
+            .unwrap()
+            .disable_throttle();
+        client.list_documents()
+    })
+    .await
+    .unwrap();
+
+    match result {
+        Err(muesli::Error::Auth(message)) => assert!(message.contains("expired")),
+        other => panic!("Expected auth error, got {:?}", other.map(|d| d.len())),
+    }
+}
+
+#[tokio::test]
+async fn test_async_client_fetches_document() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/get-document-transcript"))
+        .and(header("Authorization", "Bearer test_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"speaker": "Alice", "text": "Hello"},
+            {"speaker": "Bob", "text": "Hi"}
+        ])))
+        .mount(&mock_server)
+        .await;
+
+    let client = AsyncApiClient::new("test_token".into(), Some(mock_server.uri()))
+        .unwrap()
+        .disable_throttle();
+
+    let transcript = client.get_transcript("doc123").await.unwrap();
+    assert_eq!(transcript.entries.len(), 2);
+    assert_eq!(transcript.entries[1].speaker.as_deref(), Some("Bob"));
+}
+
+#[tokio::test]
+async fn test_async_client_api_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v2/get-documents"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("Unauthorized"))
+        .mount(&mock_server)
+        .await;
+
+    let client = AsyncApiClient::new("bad_token".into(), Some(mock_server.uri()))
+        .unwrap()
+        .disable_throttle();
+
+    match client.list_documents().await {
+        Err(muesli::Error::Api { status, .. }) => assert_eq!(status, 401),
+        other => panic!("Expected API error, got {:?}", other.map(|d| d.len())),
+    }
+}
+
+#[tokio::test]
+async fn test_user_agent_has_version_and_configured_suffix() {
+    let mock_server = MockServer::start().await;
+
+    let user_agent = format!("muesli/{} (Rust) acme-proxy/42", env!("CARGO_PKG_VERSION"));
+    Mock::given(method("POST"))
+        .and(path("/v2/get-documents"))
+        .and(header("User-Agent", user_agent.as_str()))
+        .and(header("Authorization", "Bearer test_token"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"docs": []})))
+        .mount(&mock_server)
+        .await;
+
+    let config = ApiConfig {
+        user_agent_suffix: Some("acme-proxy/42".into()),
+    };
+    let client = AsyncApiClient::new("test_token".into(), Some(mock_server.uri()))
+        .unwrap()
+        .with_config(config)
+        .disable_throttle();
+
+    assert!(client.list_documents().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_unchanged_document_is_served_from_http_cache() {
+    let mock_server = MockServer::start().await;
+    let temp = tempfile::TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp.path().to_path_buf()), None).unwrap();
+    paths.ensure_dirs().unwrap();
+
+    // Mounted first so it wins once the client revalidates
+    Mock::given(method("POST"))
+        .and(path("/v1/get-document-transcript"))
+        .and(header("If-None-Match", "\"v1\""))
+        .respond_with(ResponseTemplate::new(304))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/v1/get-document-transcript"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("ETag", "\"v1\"")
+                .set_body_json(serde_json::json!([
+                    {"speaker": "Alice", "text": "Hello"}
+                ])),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = AsyncApiClient::new("test_token".into(), Some(mock_server.uri()))
+        .unwrap()
+        .with_http_cache(&paths)
+        .disable_throttle();
+
+    let first = client.get_transcript("doc123").await.unwrap();
+    let second = client.get_transcript("doc123").await.unwrap();
+    assert_eq!(second.entries.len(), 1);
+    assert_eq!(second.entries[0].text, first.entries[0].text);
+}
@@ -1,5 +1,5 @@
 // ABOUTME: Integration tests for end-to-end workflows
-// ABOUTME: Tests reindex, search, and semantic search without API mocking
+// ABOUTME: Tests sync, reindex, search, and semantic search without API mocking
 
 use muesli::Result;
 use std::fs;
@@ -74,7 +74,7 @@ fn test_reindex_workflow() -> Result<()> {
         "Analyzed user feedback from the latest product release.",
     )?;
 
-    // Run reindex (we call the indexing logic directly since sync_all requires ApiClient)
+    // Index the files directly, as a reindex would
     let index = text::create_or_open_index(&index_dir)?;
     let mut writer = index
         .writer(50_000_000)
@@ -182,7 +182,7 @@ fn test_markdown_index_search_roundtrip() -> Result<()> {
 }
 
 #[test]
-#[cfg(feature = "embeddings")]
+#[cfg(feature = "semantic")]
 fn test_semantic_search_workflow() -> Result<()> {
     use muesli::embeddings::vector::VectorStore;
 
@@ -210,7 +210,7 @@ fn test_semantic_search_workflow() -> Result<()> {
     store.add_document("doc3".to_string(), doc3_vec)?;
 
     // Save and reload
-    store.save(&vector_path)?;
+    store.save(&vector_path, temp_dir.path())?;
     let loaded_store = VectorStore::load(&vector_path)?;
 
     // Search with query vector similar to doc1
@@ -238,9 +238,469 @@ fn test_semantic_search_workflow() -> Result<()> {
     Ok(())
 }
 
+/// Syncs from `source`, embedding with a fake model when semantic search is built in
+async fn sync_from(
+    source: &dyn muesli::DocumentSource,
+    paths: &muesli::Paths,
+    options: &muesli::SyncOptions,
+) -> Result<()> {
+    #[cfg(feature = "semantic")]
+    {
+        let mut embedder = muesli::testing::FakeEmbedder::new(64);
+        muesli::sync::sync_with_embedder(source, &mut embedder, paths, options).await
+    }
+    #[cfg(not(feature = "semantic"))]
+    {
+        muesli::sync_all(source, paths, options).await
+    }
+}
+
+#[tokio::test]
+async fn test_sync_from_fake_source() -> Result<()> {
+    use muesli::testing::FakeSource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+
+    let mut source = FakeSource::new()
+        .with_meeting(
+            "doc1",
+            "Budget Review",
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &[("Alice", "We approved the marketing budget.")],
+        )
+        .with_meeting(
+            "doc2",
+            "Hiring Sync",
+            "2024-02-02T10:00:00Z".parse().unwrap(),
+            &[("Bob", "Two engineers start next month.")],
+        );
+
+    sync_from(&source, &paths, &options).await?;
+    let transcripts = fs::read_dir(&paths.transcripts_dir)?.count();
+    assert_eq!(transcripts, 2);
+
+    #[cfg(feature = "index")]
+    {
+        let index = muesli::index::text::open_for_search(&paths.index_dir)?;
+        let results = muesli::index::text::search(&index, "budget", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, "doc1");
+    }
+
+    // Newest first; unchanged documents are not downloaded again, edited ones are
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(source.fetched(), vec!["doc2", "doc1"]);
+
+    source.touch("doc1", "2024-03-01T10:00:00Z".parse().unwrap());
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(source.fetched(), vec!["doc2", "doc1", "doc1"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_from_directory() -> Result<()> {
+    use muesli::source::DirectorySource;
+
+    let source_dir = TempDir::new().unwrap();
+    fs::write(
+        source_dir.path().join("2024-02-05_zoom-standup.vtt"),
+        "WEBVTT\n\n00:00:01.000 --> 00:00:04.000\nAlice: The budget is approved.\n",
+    )?;
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let source = DirectorySource::new(source_dir.path())?;
+
+    sync_from(&source, &paths, &muesli::SyncOptions::default()).await?;
+    let transcript = fs::read_dir(&paths.transcripts_dir)?
+        .map(|e| e.unwrap().path())
+        .find(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .unwrap();
+    let content = fs::read_to_string(&transcript)?;
+    assert!(content.contains("doc_id: 2024-02-05_zoom-standup"));
+    assert!(content.contains("title: zoom standup"));
+    assert!(content.contains("**Alice (00:00:01):** The budget is approved."));
+
+    #[cfg(feature = "index")]
+    {
+        let index = muesli::index::text::open_for_search(&paths.index_dir)?;
+        let results = muesli::index::text::search(&index, "budget", 10)?;
+        assert_eq!(results[0].doc_id, "2024-02-05_zoom-standup");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_captions() -> Result<()> {
+    use muesli::ingest::{ImportOptions, ImportSource};
+
+    let exports = TempDir::new().unwrap();
+    let file = exports.path().join("zoom_0301.srt");
+    fs::write(
+        &file,
+        "1\n00:00:01,000 --> 00:00:04,000\nAlice: The roadmap slips a week.\n",
+    )?;
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = ImportOptions {
+        title: Some("Roadmap Review".to_string()),
+        date: chrono::NaiveDate::from_ymd_opt(2025, 3, 1),
+    };
+
+    // Importing again replaces the first import instead of adding a copy
+    for _ in 0..2 {
+        let source = ImportSource::new(std::slice::from_ref(&file), &options)?;
+        sync_from(&source, &paths, &source.sync_options()).await?;
+    }
+    let transcripts: Vec<_> = fs::read_dir(&paths.transcripts_dir)?
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    assert_eq!(transcripts.len(), 1);
+    assert!(transcripts[0].ends_with("2025-03-01_roadmap-review.md"));
+
+    #[cfg(feature = "index")]
+    {
+        let index = muesli::index::text::open_for_search(&paths.index_dir)?;
+        let results = muesli::index::text::search(&index, "roadmap", 10)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].doc_id, "zoom_0301");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prune_keeps_imported_meetings() -> Result<()> {
+    use muesli::ingest::{ImportOptions, ImportSource};
+    use muesli::testing::FakeSource;
+
+    let exports = TempDir::new().unwrap();
+    let file = exports.path().join("2025-03-01_zoom-standup.vtt");
+    fs::write(
+        &file,
+        "WEBVTT\n\n00:00:01.000 --> 00:00:03.000\nAlice: Shipping Friday.\n",
+    )?;
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+
+    let imports = ImportSource::new(&[file], &ImportOptions::default())?;
+    sync_from(&imports, &paths, &imports.sync_options()).await?;
+    let imported = paths.transcripts_dir.join("2025-03-01_zoom-standup.md");
+    assert!(fs::read_to_string(&imported)?.contains("source: vtt"));
+
+    // Granola has never heard of the import, so its listing must not prune it
+    let granola = FakeSource::new().with_meeting(
+        "doc1",
+        "Budget Review",
+        "2025-03-02T10:00:00Z".parse().unwrap(),
+        &[("Bob", "Budget approved.")],
+    );
+    let prune = muesli::SyncOptions {
+        prune: true,
+        ..Default::default()
+    };
+    sync_from(&granola, &paths, &prune).await?;
+
+    assert!(imported.exists());
+    assert!(paths.raw_dir.join("2025-03-01_zoom-standup.json").exists());
+    let synced = fs::read_to_string(paths.transcripts_dir.join("2025-03-02_budget-review.md"))?;
+    assert!(synced.contains("source: granola"));
+    let cache = fs::read_to_string(paths.data_dir.join(".sync_cache.json"))?;
+    assert!(cache.contains("\"source\": \"vtt\""));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_import_tool_exports() -> Result<()> {
+    use muesli::ingest::{ImportOptions, ImportSource};
+
+    let exports = TempDir::new().unwrap();
+    let fireflies = exports.path().join("fireflies.json");
+    fs::write(
+        &fireflies,
+        r#"{"id": "ff1", "title": "Vendor Review", "date": 1740823200000,
+            "sentences": [{"speaker_name": "Alice", "text": "Renewal terms first.", "start_time": 0, "end_time": 2}]}"#,
+    )?;
+    let otter = exports.path().join("otter.json");
+    fs::write(
+        &otter,
+        r#"{"otid": "ot1", "title": "Design Sync", "created_at": 1740909600,
+            "speakers": [{"id": 1, "speaker_name": "Carol"}],
+            "transcripts": [{"start_offset": 0, "end_offset": 3000, "speaker_id": 1, "transcript": "Mockups are up."}]}"#,
+    )?;
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+
+    let source = ImportSource::new(&[fireflies, otter], &ImportOptions::default())?;
+    sync_from(&source, &paths, &source.sync_options()).await?;
+
+    let vendor = fs::read_to_string(paths.transcripts_dir.join("2025-03-01_vendor-review.md"))?;
+    assert!(vendor.contains("doc_id: fireflies-ff1"));
+    assert!(vendor.contains("Renewal terms first."));
+    let design = fs::read_to_string(paths.transcripts_dir.join("2025-03-02_design-sync.md"))?;
+    assert!(design.contains("doc_id: otter-ot1"));
+    assert!(design.contains("Carol"));
+
+    Ok(())
+}
+
+#[cfg(feature = "email")]
+#[tokio::test]
+async fn test_import_notes_email() -> Result<()> {
+    use muesli::ingest::{ImportOptions, ImportSource};
+
+    let mail = TempDir::new().unwrap();
+    let eml = mail.path().join("notes.eml");
+    fs::write(
+        &eml,
+        "Message-ID: <n1@zoom.us>\r\nFrom: Zoom <no-reply@zoom.us>\r\n\
+         Subject: Meeting summary for Budget Review\r\nDate: Tue, 4 Mar 2025 15:00:00 +0000\r\n\r\n\
+         Quick recap\r\nThe team approved the Q2 budget.\r\n",
+    )?;
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+
+    let source = ImportSource::new(&[eml], &ImportOptions::default())?;
+    sync_from(&source, &paths, &source.sync_options()).await?;
+
+    let notes = fs::read_to_string(
+        paths
+            .transcripts_dir
+            .join("2025-03-04_meeting-summary-for-budget-review.md"),
+    )?;
+    assert!(notes.contains("doc_id: email-"));
+    assert!(notes.contains("**Zoom:** Quick recap"));
+    assert!(notes.contains("approved the Q2 budget"));
+
+    Ok(())
+}
+
 /// Helper to normalize a vector (for embedding simulation)
-#[cfg(feature = "embeddings")]
+#[cfg(feature = "semantic")]
 fn normalize_vector(vec: Vec<f32>) -> Vec<f32> {
     let magnitude: f32 = vec.iter().map(|x| x * x).sum::<f32>().sqrt();
     vec.iter().map(|x| x / magnitude).collect()
 }
+
+#[tokio::test]
+async fn test_dedupe_reconnected_meeting() -> Result<()> {
+    use muesli::dedupe::{find_duplicates, resolve, Resolution};
+    use muesli::testing::FakeSource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+
+    // A reconnect leaves a second document with the same transcript
+    let lines = [("Alice", "We approved the marketing budget.")];
+    let source = FakeSource::new()
+        .with_meeting(
+            "doc1",
+            "Budget Review",
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &lines,
+        )
+        .with_meeting(
+            "doc2",
+            "Budget Review (reconnected)",
+            "2024-02-01T10:07:00Z".parse().unwrap(),
+            &lines,
+        );
+    sync_from(&source, &paths, &options).await?;
+
+    let groups = find_duplicates(&paths)?;
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].canonical.doc_id, "doc1");
+
+    resolve(&paths, &groups, Resolution::Merge)?;
+    assert_eq!(fs::read_dir(&paths.transcripts_dir)?.count(), 1);
+
+    #[cfg(feature = "index")]
+    {
+        let index = muesli::index::text::open_for_search(&paths.index_dir)?;
+        let results = muesli::index::text::search(&index, "budget", 10)?;
+        let ids: Vec<&str> = results.iter().map(|r| r.doc_id.as_str()).collect();
+        assert_eq!(ids, vec!["doc1"]);
+    }
+
+    // The merged copy is not downloaded again
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(source.fetched(), vec!["doc2", "doc1"]);
+    assert_eq!(fs::read_dir(&paths.transcripts_dir)?.count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_writes_granola_notes() -> Result<()> {
+    use muesli::storage::read_frontmatter;
+    use muesli::testing::FakeSource;
+    use muesli::DocumentPanel;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+
+    let summary = DocumentPanel {
+        id: Some("panel1".into()),
+        title: Some("Summary".into()),
+        content: serde_json::json!({"type": "doc", "content": [
+            {"type": "paragraph", "content": [{"type": "text", "text": "Budget approved."}]}
+        ]}),
+        original_content: None,
+    };
+    let source = FakeSource::new()
+        .with_meeting(
+            "doc1",
+            "Budget Review",
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &[("Alice", "We approved the marketing budget.")],
+        )
+        .with_meeting(
+            "doc2",
+            "Hiring Sync",
+            "2024-02-02T10:00:00Z".parse().unwrap(),
+            &[("Bob", "Two engineers start next month.")],
+        )
+        .with_panels("doc1", vec![summary]);
+    sync_from(&source, &paths, &options).await?;
+
+    // Only the meeting with notes gets a companion file, linked from its frontmatter
+    let notes: Vec<_> = fs::read_dir(&paths.notes_dir)?
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(notes.len(), 1);
+    let stem = notes[0].file_stem().unwrap().to_string_lossy().to_string();
+    let content = fs::read_to_string(&notes[0])?;
+    assert!(content.starts_with("# Notes: Budget Review\n"));
+    assert!(content.contains(&format!("(../transcripts/{}.md)", stem)));
+    assert!(content.contains("## Summary\n\nBudget approved.\n"));
+
+    let with_notes =
+        read_frontmatter(&paths.transcripts_dir.join(format!("{}.md", stem)))?.unwrap();
+    assert_eq!(with_notes.notes, Some(format!("notes/{}.md", stem)));
+    for entry in fs::read_dir(&paths.transcripts_dir)? {
+        let fm = read_frontmatter(&entry?.path())?.unwrap();
+        if fm.doc_id == "doc2" {
+            assert!(fm.notes.is_none());
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_renames_files_when_title_changes() -> Result<()> {
+    use muesli::testing::FakeSource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+    let meeting = |title| {
+        FakeSource::new().with_meeting(
+            "doc1",
+            title,
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &[("Alice", "We approved the marketing budget.")],
+        )
+    };
+
+    sync_from(&meeting("Budget Review"), &paths, &options).await?;
+    let old = "2024-02-01_budget-review";
+    assert!(paths.transcripts_dir.join(format!("{}.md", old)).exists());
+    let summary = paths.summaries_dir.join(format!("{}_summary.md", old));
+    fs::write(&summary, "Budget approved.")?;
+
+    let mut retitled = meeting("Budget Review Q1");
+    retitled.touch("doc1", "2024-03-01T10:00:00Z".parse().unwrap());
+    sync_from(&retitled, &paths, &options).await?;
+
+    let new = "2024-02-01_budget-review-q1";
+    assert!(paths.transcripts_dir.join(format!("{}.md", new)).exists());
+    assert!(paths.raw_dir.join(format!("{}.json", new)).exists());
+    assert!(!paths.transcripts_dir.join(format!("{}.md", old)).exists());
+    assert!(!paths.raw_dir.join(format!("{}.json", old)).exists());
+    assert!(!summary.exists());
+    let moved = paths.summaries_dir.join(format!("{}_summary.md", new));
+    assert_eq!(fs::read_to_string(moved)?, "Budget approved.");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_follows_updates_feed() -> Result<()> {
+    use muesli::testing::FakeSource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+
+    let mut source = FakeSource::new()
+        .with_meeting(
+            "doc1",
+            "Budget Review",
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &[("Alice", "We approved the marketing budget.")],
+        )
+        .with_meeting(
+            "doc2",
+            "Hiring Sync",
+            "2024-02-02T10:00:00Z".parse().unwrap(),
+            &[("Bob", "Two engineers start next month.")],
+        )
+        .with_updates_feed();
+
+    // The first sync reads everything from the feed and saves its cursor
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(fs::read_dir(&paths.transcripts_dir)?.count(), 2);
+    let cache = fs::read_to_string(paths.data_dir.join(".sync_cache.json"))?;
+    assert!(cache.contains("\"$cursor\": \"2024-02-02T10:00:00+00:00\""));
+
+    // Later syncs only see what changed
+    source.touch("doc1", "2024-03-01T10:00:00Z".parse().unwrap());
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(source.fetched(), vec!["doc2", "doc1", "doc1"]);
+    assert_eq!(source.listings(), 0);
+
+    // Pruning needs the full listing
+    let prune = muesli::SyncOptions {
+        prune: true,
+        ..Default::default()
+    };
+    sync_from(&source, &paths, &prune).await?;
+    assert_eq!(source.listings(), 1);
+    assert_eq!(source.fetched().len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sync_stops_asking_a_failing_updates_feed() -> Result<()> {
+    use muesli::testing::FakeSource;
+
+    let temp_dir = TempDir::new().unwrap();
+    let paths = muesli::Paths::new(Some(temp_dir.path().to_path_buf()), None)?;
+    let options = muesli::SyncOptions::default();
+    let source = FakeSource::new()
+        .with_meeting(
+            "doc1",
+            "Budget Review",
+            "2024-02-01T10:00:00Z".parse().unwrap(),
+            &[("Alice", "We approved the marketing budget.")],
+        )
+        .with_failing_updates_feed(405);
+
+    // The failure is remembered, so the second sync goes straight to the listing
+    sync_from(&source, &paths, &options).await?;
+    sync_from(&source, &paths, &options).await?;
+    assert_eq!(source.feed_requests(), 1);
+    assert_eq!(source.listings(), 2);
+    assert_eq!(fs::read_dir(&paths.transcripts_dir)?.count(), 1);
+
+    Ok(())
+}